@@ -1,14 +1,31 @@
 use foundry_compilers::artifacts::ast::{
-  ContractDefinition, ContractDefinitionPart, SourceUnit, SourceUnitPart, Visibility,
+  ContractDefinition, ContractDefinitionPart, FunctionDefinition, SourceUnit, SourceUnitPart,
+  VariableDeclaration, Visibility,
 };
-use foundry_compilers::solc::SolcLanguage;
+use foundry_compilers::artifacts::{output_selection::OutputSelection, CompilerOutput, Settings};
+use foundry_compilers::solc::{Solc, SolcLanguage};
+use foundry_compilers::ProjectPathsConfig;
+use semver::Version;
 
-use super::{orchestrator::AstOrchestrator, stitcher, utils};
-use crate::internal::config::{AstConfig, AstConfigOptions, ResolveConflictStrategy};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use super::{
+  coverage, error::AstError, instrumenter, orchestrator::AstOrchestrator, parse_cache, parser,
+  stitcher, utils,
+};
+use crate::compiler::output::{
+  from_standard_json, CompileOutput, CompilerError, ModelCheckerDiagnostic, SeverityLevel,
+};
+use crate::internal::config::{AstConfig, AstConfigOptions, ExposeStrategy, ResolveConflictStrategy};
 use crate::internal::errors::{map_err_with_context, Error, Result};
 use crate::internal::logging::{ensure_rust_logger, update_level};
+use crate::internal::settings::{
+  CompilerSettingsOptions, ModelCheckerEngine, ModelCheckerSettingsOptions, ModelCheckerTarget,
+};
 use crate::internal::solc;
 use log::{error, info};
+use serde::Serialize;
 use serde_json::{json, Value};
 
 const VIRTUAL_SOURCE_PATH: &str = "__VIRTUAL__.sol";
@@ -17,7 +34,81 @@ const LOG_TARGET: &str = "tevm::ast";
 #[derive(Clone)]
 pub struct State {
   pub config: AstConfig,
-  pub ast: Option<SourceUnit>,
+  /// Every virtual source in the project, keyed by path. `from_source` populates the primary
+  /// entry (named by `primary_path`, below); `add_source` loads siblings a contract or a fragment
+  /// imports, so `validate` can hand solc the whole project and resolve those imports.
+  pub sources: BTreeMap<String, SourceUnit>,
+  /// The raw text behind every entry in `sources` that was loaded as text rather than a pre-built
+  /// AST, kept around so a later `from_source`/`add_source` call whose text `import`s one of these
+  /// paths can be flattened against it - see `load_source_text`.
+  raw_sources: BTreeMap<String, String>,
+  /// The path `from_source` first populated. `ast()`/`ast_mut()`/`validate`'s returned AST, and
+  /// contract lookups with no explicit override, all target this source by default.
+  primary_path: Option<String>,
+  /// Populated by `validate`; `None` until the first successful or failed validation call.
+  pub last_validation: Option<ValidationReport>,
+  /// Populated by `inject_fragment_contract`; lets `to_dot` color-code the stitched contract's
+  /// members by what the most recent stitch did with them, without threading a `StitchReport`
+  /// through every caller of `to_dot`.
+  last_stitch: Option<LastStitch>,
+  /// Backs `load_source_text`/`inject_fragment_string` so re-parsing the exact same source text
+  /// against the same solc version/settings - common across a batch of fragments targeting the
+  /// same base contract - skips solc entirely. See `parse_cache`.
+  parse_cache: parse_cache::ParseCache,
+}
+
+/// Where the most recent `stitch_fragment_nodes_into_contract` call landed, and what it reported,
+/// so `to_dot` can render that contract with conflict-outcome-colored edges on request.
+#[derive(Clone)]
+struct LastStitch {
+  path: String,
+  contract_idx: usize,
+  report: stitcher::StitchReport,
+}
+
+/// Where in `VIRTUAL_SOURCE_PATH` a [`ValidationDiagnostic`] applies, if solc attributed one.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationSourceLocation {
+  pub path: String,
+  pub start: i64,
+  pub length: i64,
+}
+
+/// One solc diagnostic from a `validate` call, kept in full rather than collapsed into a
+/// pass/fail string.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationDiagnostic {
+  pub severity: String,
+  pub error_code: Option<u32>,
+  #[serde(rename = "type")]
+  pub kind: String,
+  pub formatted_message: String,
+  pub source_location: Option<ValidationSourceLocation>,
+}
+
+/// Every diagnostic solc produced for a `validate` call, split by severity so hard errors remain
+/// distinguishable from warnings/info without callers re-parsing severity strings themselves.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationReport {
+  pub errors: Vec<ValidationDiagnostic>,
+  pub warnings: Vec<ValidationDiagnostic>,
+  pub infos: Vec<ValidationDiagnostic>,
+}
+
+impl ValidationReport {
+  /// All diagnostics (of any severity) carrying the given solc error code.
+  pub fn by_code(&self, code: u32) -> Vec<&ValidationDiagnostic> {
+    self
+      .errors
+      .iter()
+      .chain(self.warnings.iter())
+      .chain(self.infos.iter())
+      .filter(|diagnostic| diagnostic.error_code == Some(code))
+      .collect()
+  }
 }
 
 #[derive(Clone)]
@@ -54,7 +145,22 @@ pub fn init(options: Option<AstConfigOptions>) -> Result<State> {
     config.instrumented_contract()
   );
 
-  Ok(State { config, ast: None })
+  let parse_cache =
+    parse_cache::ParseCache::new(config.parse_cache_capacity, config.parse_cache_dir.clone());
+
+  Ok(State {
+    config,
+    sources: BTreeMap::new(),
+    raw_sources: BTreeMap::new(),
+    primary_path: None,
+    last_validation: None,
+    last_stitch: None,
+    parse_cache,
+  })
+}
+
+fn primary_path(state: &State) -> &str {
+  state.primary_path.as_deref().unwrap_or(VIRTUAL_SOURCE_PATH)
 }
 
 pub fn from_source(
@@ -62,6 +168,7 @@ pub fn from_source(
   target: SourceTarget,
   overrides: Option<&AstConfigOptions>,
 ) -> Result<()> {
+  let path = primary_path(state).to_string();
   match target {
     SourceTarget::Text(source) => {
       info!(
@@ -69,7 +176,7 @@ pub fn from_source(
         "loading AST from source text (len={})",
         source.len()
       );
-      load_source_text(state, &source, overrides)?;
+      load_source_text(state, &path, &source, overrides)?;
     }
     SourceTarget::Ast(unit) => {
       let node_count = unit.nodes.len();
@@ -78,13 +185,183 @@ pub fn from_source(
         "loading AST from pre-built unit (nodes={})",
         node_count
       );
-      load_source_ast(state, unit, overrides)?;
+      load_source_ast(state, &path, unit, overrides)?;
     }
   }
+  if state.primary_path.is_none() {
+    state.primary_path = Some(path);
+  }
   info!(target: LOG_TARGET, "AST source loaded");
   Ok(())
 }
 
+/// Loads an additional named source into the project alongside the primary one, so a contract in
+/// the primary source (or in a fragment) can import it. Unlike `from_source`, this never changes
+/// `primary_path` - `path` identifies this source for qualified `path:Contract` overrides and for
+/// solc's import resolution, nothing more.
+pub fn add_source(
+  state: &mut State,
+  path: &str,
+  target: SourceTarget,
+  overrides: Option<&AstConfigOptions>,
+) -> Result<()> {
+  match target {
+    SourceTarget::Text(source) => {
+      info!(
+        target: LOG_TARGET,
+        "loading additional AST source (path={}, len={})",
+        path,
+        source.len()
+      );
+      load_source_text(state, path, &source, overrides)?;
+    }
+    SourceTarget::Ast(unit) => {
+      let node_count = unit.nodes.len();
+      info!(
+        target: LOG_TARGET,
+        "loading additional pre-built AST source (path={}, nodes={})",
+        path,
+        node_count
+      );
+      load_source_ast(state, path, unit, overrides)?;
+    }
+  }
+  info!(target: LOG_TARGET, "additional AST source loaded");
+  Ok(())
+}
+
+/// Loads a whole multi-file project in one go: every entry in `sources` is parsed together in a
+/// single solc invocation (see [`AstOrchestrator::parse_source_units`]), so an import between them
+/// - direct, or reached through `overrides`'/`state.config`'s `remappings` - resolves instead of
+/// failing the way one-file-at-a-time `add_source` calls would. Equivalent to calling `from_source`
+/// with one entry and `add_source` with the rest, except every file is visible to solc's import
+/// resolver from the start. See [`resolve_primary_path`] for how the instrumentation target is
+/// picked when `primary_path` isn't given explicitly.
+pub fn from_sources(
+  state: &mut State,
+  sources: BTreeMap<String, String>,
+  primary_path: Option<String>,
+  overrides: Option<&AstConfigOptions>,
+) -> Result<()> {
+  if sources.is_empty() {
+    return Err(Error::new("from_sources requires at least one source"));
+  }
+
+  let config = resolve_config(state, overrides)?;
+  let solc = solc::ensure_installed(&config.solc.version)?;
+  info!(
+    target: LOG_TARGET,
+    "loading AST project (sources={})",
+    sources.len()
+  );
+
+  let mut units = map_err_with_context(
+    AstOrchestrator::parse_source_units(&sources, &solc, &config.solc.settings),
+    "Failed to parse project sources",
+  )?;
+
+  let resolved_primary =
+    resolve_primary_path(&units, primary_path, contract_override(state, overrides))?;
+
+  for (path, source) in &sources {
+    state.raw_sources.insert(path.clone(), source.clone());
+  }
+
+  for path in sources.keys() {
+    let mut unit = units
+      .remove(path)
+      .ok_or_else(|| Error::new(format!("Failed to parse \"{path}\"")))?;
+    renumber_for_project(state, &mut unit)?;
+    state.sources.insert(path.clone(), unit);
+  }
+
+  state.primary_path = Some(resolved_primary);
+  info!(target: LOG_TARGET, "AST project loaded");
+  Ok(())
+}
+
+/// The filesystem counterpart of [`from_sources`]: recursively collects every `.sol` file under
+/// `root_path`'s resolved source directory - the same Hardhat/Dapptools layout detection
+/// `config::find_source_dir` exposes to callers directly - and loads them the same way. Mirrors
+/// `compiler::collect_sol_files`.
+pub fn from_project(
+  state: &mut State,
+  root_path: &str,
+  primary_path: Option<String>,
+  overrides: Option<&AstConfigOptions>,
+) -> Result<()> {
+  let root = PathBuf::from(root_path);
+  let source_dir = ProjectPathsConfig::find_source_dir(&root);
+
+  let mut sources = BTreeMap::new();
+  collect_sol_sources(&source_dir, &mut sources)?;
+
+  from_sources(state, sources, primary_path, overrides)
+}
+
+/// Picks which parsed unit in `units` becomes `primary_path` for [`from_sources`]/[`from_project`].
+/// An explicit `primary_path` wins outright. Otherwise a qualified `path:Contract`
+/// `instrumented_contract` override names its path directly; an unqualified one is searched for
+/// across every unit via [`AstOrchestrator::find_target_contract`]. With neither, the
+/// lexicographically first path is chosen so the outcome stays deterministic rather than depending
+/// on solc's/the map's iteration order.
+fn resolve_primary_path(
+  units: &BTreeMap<String, SourceUnit>,
+  primary_path: Option<String>,
+  contract_name: Option<&str>,
+) -> Result<String> {
+  if let Some(path) = primary_path {
+    return if units.contains_key(&path) {
+      Ok(path)
+    } else {
+      Err(Error::new(format!("Unknown source path \"{path}\"")))
+    };
+  }
+
+  if let Some(name) = contract_name {
+    let (path_hint, bare_name) = split_qualified_name(name);
+    if let Some(path_hint) = path_hint {
+      return Ok(path_hint.to_string());
+    }
+    if let Ok((path, _)) = AstOrchestrator::find_target_contract(units, bare_name) {
+      return Ok(path.to_string());
+    }
+  }
+
+  units
+    .keys()
+    .next()
+    .cloned()
+    .ok_or_else(|| Error::new("from_sources requires at least one source"))
+}
+
+/// Recursively collects every `.sol` file under `dir`, keyed by its filesystem path with `\`
+/// normalized to `/` so project-relative names stay stable across platforms. Mirrors
+/// `compiler::collect_sol_files`; duplicated rather than shared since the two live in separate
+/// crate modules with no existing dependency between them.
+fn collect_sol_sources(dir: &Path, sources: &mut BTreeMap<String, String>) -> Result<()> {
+  let Ok(entries) = std::fs::read_dir(dir) else {
+    return Ok(());
+  };
+
+  for entry in entries.flatten() {
+    let path = entry.path();
+    if path.is_dir() {
+      collect_sol_sources(&path, sources)?;
+      continue;
+    }
+    if path.extension().and_then(|ext| ext.to_str()) != Some("sol") {
+      continue;
+    }
+
+    let contents = std::fs::read_to_string(&path)
+      .map_err(|err| Error::new(format!("Failed to read {}: {err}", path.display())))?;
+    sources.insert(path.to_string_lossy().replace('\\', "/"), contents);
+  }
+
+  Ok(())
+}
+
 pub fn inject_shadow(
   state: &mut State,
   fragment: FragmentTarget,
@@ -143,12 +420,37 @@ pub fn expose_internal_functions(
   Ok(())
 }
 
+pub fn instrument_coverage(
+  state: &mut State,
+  overrides: Option<&AstConfigOptions>,
+) -> Result<()> {
+  let contract = contract_override(state, overrides).unwrap_or("<all>");
+  info!(
+    target: LOG_TARGET,
+    "instrumenting coverage counters (contract={})",
+    contract
+  );
+  instrument_coverage_internal(state, overrides)?;
+  info!(target: LOG_TARGET, "coverage counters instrumented");
+  Ok(())
+}
+
 pub fn source_unit(state: &State) -> Option<&SourceUnit> {
-  state.ast.as_ref()
+  state.sources.get(primary_path(state))
 }
 
 pub fn source_unit_mut(state: &mut State) -> Option<&mut SourceUnit> {
-  state.ast.as_mut()
+  let path = primary_path(state).to_string();
+  state.sources.get_mut(&path)
+}
+
+/// Every source currently loaded in the project, including any siblings loaded via `add_source`.
+pub fn sources(state: &State) -> &BTreeMap<String, SourceUnit> {
+  &state.sources
+}
+
+pub fn validation_report(state: &State) -> Option<&ValidationReport> {
+  state.last_validation.as_ref()
 }
 
 fn contract_override<'a>(
@@ -181,32 +483,81 @@ fn resolve_config(state: &State, overrides: Option<&AstConfigOptions>) -> Result
   Ok(config)
 }
 
-fn target_ast_mut(state: &mut State) -> Result<&mut SourceUnit> {
+fn target_ast(state: &State) -> Result<&SourceUnit> {
   state
-    .ast
-    .as_mut()
+    .sources
+    .get(primary_path(state))
     .ok_or_else(|| Error::new("Ast has no target AST. Call from_source first."))
 }
 
-fn target_ast(state: &State) -> Result<&SourceUnit> {
-  state
-    .ast
-    .as_ref()
-    .ok_or_else(|| Error::new("Ast has no target AST. Call from_source first."))
+/// Splits a project-qualified contract name (`path/to/Base.sol:Base`) into its source path and
+/// bare contract name, the same `path:Contract` convention solc itself uses to disambiguate
+/// same-named contracts across files. Unqualified names are left as-is.
+fn split_qualified_name(name: &str) -> (Option<&str>, &str) {
+  match name.rsplit_once(':') {
+    Some((path, contract)) => (Some(path), contract),
+    None => (None, name),
+  }
 }
 
-fn find_contract_index(
+/// Resolves a contract to instrument across the whole project. A qualified `path:Contract` name
+/// goes straight to that source; an unqualified name is searched for starting with the primary
+/// source before falling back to siblings (the same order `contract_indices` reports "all
+/// contracts" in). No name at all falls back to `find_instrumented_contract_index`'s own
+/// last-contract-wins behaviour, scoped to the primary source only - siblings never silently
+/// become the instrumentation target just because one happens to be loaded.
+fn find_contract_in_project(
   state: &State,
-  ast: &SourceUnit,
-  contract_name: Option<&str>,
-) -> Result<usize> {
-  map_err_with_context(
-    stitcher::find_instrumented_contract_index(
-      ast,
-      contract_name.or_else(|| contract_override(state, None)),
-    ),
-    "Failed to locate target contract",
-  )
+  qualified_name: Option<&str>,
+) -> Result<(String, usize)> {
+  let (path_hint, bare_name) = match qualified_name {
+    Some(name) => {
+      let (path, contract) = split_qualified_name(name);
+      (path, Some(contract))
+    }
+    None => (None, None),
+  };
+
+  if let Some(path) = path_hint {
+    let unit = state.sources.get(path).ok_or_else(|| {
+      Error::new(format!(
+        "Unknown source path '{path}' in contract name '{}'",
+        qualified_name.unwrap_or_default()
+      ))
+    })?;
+    let idx = map_err_with_context(
+      stitcher::find_instrumented_contract_index(unit, bare_name),
+      "Failed to locate target contract",
+    )?;
+    return Ok((path.to_string(), idx));
+  }
+
+  let primary = primary_path(state).to_string();
+  let primary_unit = state
+    .sources
+    .get(&primary)
+    .ok_or_else(|| Error::new("Ast has no target AST. Call from_source first."))?;
+
+  let Some(name) = bare_name else {
+    let idx = map_err_with_context(
+      stitcher::find_instrumented_contract_index(primary_unit, None),
+      "Failed to locate target contract",
+    )?;
+    return Ok((primary, idx));
+  };
+
+  if let Ok(idx) = stitcher::find_instrumented_contract_index(primary_unit, Some(name)) {
+    return Ok((primary, idx));
+  }
+  for (path, unit) in &state.sources {
+    if *path == primary {
+      continue;
+    }
+    if let Ok(idx) = stitcher::find_instrumented_contract_index(unit, Some(name)) {
+      return Ok((path.clone(), idx));
+    }
+  }
+  Err(Error::new(format!("Contract '{name}' not found")))
 }
 
 fn inject_fragment_contract(
@@ -216,49 +567,135 @@ fn inject_fragment_contract(
   strategy: ResolveConflictStrategy,
 ) -> Result<()> {
   let contract_name = contract_override(state, overrides).map(|name| name.to_owned());
-  let contract_idx = {
-    let target_ast = target_ast(state)?;
-    find_contract_index(state, target_ast, contract_name.as_deref())?
-  };
+  let (path, contract_idx) = find_contract_in_project(state, contract_name.as_deref())?;
 
-  let target_ast = target_ast_mut(state)?;
-  map_err_with_context(
-    AstOrchestrator::stitch_fragment_into_contract(
-      target_ast,
+  let max_id = map_err_with_context(
+    utils::project_max_id(&state.sources),
+    "Failed to compute next AST id",
+  )?;
+  let unit = state
+    .sources
+    .get_mut(&path)
+    .ok_or_else(|| Error::new("Invalid contract index"))?;
+  let report = map_err_with_context(
+    stitcher::stitch_fragment_nodes_into_contract(
+      unit,
       contract_idx,
       &fragment_contract,
+      max_id,
       strategy,
     ),
     "Failed to stitch AST nodes",
+  )?;
+  state.last_stitch = Some(LastStitch {
+    path,
+    contract_idx,
+    report,
+  });
+  Ok(())
+}
+
+/// Renders the most recently stitched contract (or, if no stitch has happened yet, the default
+/// instrumented contract in the primary source) as a Graphviz DOT graph. See
+/// [`super::dot::render_contract`] for what the graph looks like.
+pub fn to_dot(state: &State) -> Result<String> {
+  let (unit, contract_idx, report) = match &state.last_stitch {
+    Some(last_stitch) => {
+      let unit = state.sources.get(&last_stitch.path).ok_or_else(|| {
+        Error::new(format!(
+          "Source '{}' from the last stitch is no longer loaded",
+          last_stitch.path
+        ))
+      })?;
+      (unit, last_stitch.contract_idx, Some(&last_stitch.report))
+    }
+    None => {
+      let path = primary_path(state).to_string();
+      let unit = state
+        .sources
+        .get(&path)
+        .ok_or_else(|| Error::new("Ast has no target AST. Call from_source first."))?;
+      let idx = map_err_with_context(
+        stitcher::find_instrumented_contract_index(unit, None),
+        "Failed to locate target contract",
+      )?;
+      (unit, idx, None)
+    }
+  };
+  map_err_with_context(
+    super::dot::render_contract(unit, contract_idx, report),
+    "Failed to render AST graph",
   )
 }
 
+/// Every contract in the project, or just the one `overrides`/`state.config` names (optionally
+/// qualified as `path:Contract`). The primary source's contracts are listed first so "no override"
+/// behaves exactly as it did before siblings existed.
+///
+/// Returns `Ok(vec![])`, rather than erring, when the whole project contains no
+/// `ContractDefinition` at all - modern Solidity allows file-level free functions and
+/// sources holding nothing but libraries/interfaces, and foundry-compilers already emits
+/// artifacts for them. Callers that can operate at source-unit scope instead (see
+/// `contractless_sources`) fall back there; callers that can't (e.g. coverage instrumentation,
+/// which has nowhere to hold its counters without a contract) simply have nothing to do.
 fn contract_indices(
   state: &State,
-  ast: &SourceUnit,
   overrides: Option<&AstConfigOptions>,
-) -> Result<Vec<usize>> {
+) -> Result<Vec<(String, usize)>> {
   if let Some(name) = contract_override(state, overrides) {
-    let idx = stitcher::find_instrumented_contract_index(ast, Some(name))?;
-    Ok(vec![idx])
+    Ok(vec![find_contract_in_project(state, Some(name))?])
   } else {
-    let indices = ast
+    let primary = primary_path(state).to_string();
+    let mut paths: Vec<&String> = state.sources.keys().collect();
+    paths.sort_by_key(|path| (**path != primary, (*path).clone()));
+
+    let mut indices = Vec::new();
+    for path in paths {
+      let unit = &state.sources[path];
+      for (idx, part) in unit.nodes.iter().enumerate() {
+        if matches!(part, SourceUnitPart::ContractDefinition(_)) {
+          indices.push((path.clone(), idx));
+        }
+      }
+    }
+
+    Ok(indices)
+  }
+}
+
+/// Whether any source currently loaded in the project declares at least one contract/library/
+/// interface - the gate `inject_shadow` uses to decide whether a fragment should stitch into a
+/// contract (the historical behaviour) or, for a contract-less project, directly into the primary
+/// source as a free function. See [`contractless_sources`] for the per-source equivalent.
+fn project_has_contracts(state: &State) -> bool {
+  state.sources.values().any(|unit| {
+    unit
       .nodes
       .iter()
-      .enumerate()
-      .filter_map(|(idx, part)| {
-        matches!(part, SourceUnitPart::ContractDefinition(_)).then_some(idx)
-      })
-      .collect::<Vec<_>>();
+      .any(|part| matches!(part, SourceUnitPart::ContractDefinition(_)))
+  })
+}
 
-    if indices.is_empty() {
-      Err(Error::new(
-        "Target AST does not contain any contract definitions",
-      ))
-    } else {
-      Ok(indices)
-    }
-  }
+/// Every source in the project that declares no contract at all (an interface-only file, a
+/// library of free functions, a file of plain constants, ...) - the sources
+/// `expose_functions_internal`'s free-function fallback operates over, since their functions have
+/// no `ContractDefinition` for `contract_indices` to report. The primary source is listed first,
+/// matching `contract_indices`' own ordering.
+fn contractless_sources(state: &State) -> Vec<String> {
+  let primary = primary_path(state).to_string();
+  let mut paths: Vec<&String> = state.sources.keys().collect();
+  paths.sort_by_key(|path| (**path != primary, (*path).clone()));
+
+  paths
+    .into_iter()
+    .filter(|path| {
+      !state.sources[*path]
+        .nodes
+        .iter()
+        .any(|part| matches!(part, SourceUnitPart::ContractDefinition(_)))
+    })
+    .cloned()
+    .collect()
 }
 
 fn mutate_contracts<F>(
@@ -269,12 +706,12 @@ fn mutate_contracts<F>(
 where
   F: FnMut(&mut ContractDefinition),
 {
-  let indices = {
-    let unit = target_ast(state)?;
-    contract_indices(state, unit, overrides)?
-  };
-  let unit = target_ast_mut(state)?;
-  for idx in indices {
+  let indices = contract_indices(state, overrides)?;
+  for (path, idx) in indices {
+    let unit = state
+      .sources
+      .get_mut(&path)
+      .ok_or_else(|| Error::new("Invalid contract index"))?;
     let SourceUnitPart::ContractDefinition(contract) = unit
       .nodes
       .get_mut(idx)
@@ -287,223 +724,1280 @@ where
   Ok(())
 }
 
+fn expose_strategy(overrides: Option<&AstConfigOptions>) -> ExposeStrategy {
+  overrides.map(|opts| opts.expose_strategy()).unwrap_or_default()
+}
+
 fn expose_variables_internal(
   state: &mut State,
   overrides: Option<&AstConfigOptions>,
 ) -> Result<()> {
-  mutate_contracts(state, overrides, |contract| {
-    for member in &mut contract.nodes {
-      if let ContractDefinitionPart::VariableDeclaration(variable) = member {
-        if matches!(
-          variable.visibility,
-          Visibility::Private | Visibility::Internal
-        ) {
-          variable.visibility = Visibility::Public;
+  match expose_strategy(overrides) {
+    ExposeStrategy::InPlace => mutate_contracts(state, overrides, |contract| {
+      for member in &mut contract.nodes {
+        if let ContractDefinitionPart::VariableDeclaration(variable) = member {
+          if matches!(
+            variable.visibility,
+            Visibility::Private | Visibility::Internal
+          ) {
+            variable.visibility = Visibility::Public;
+          }
         }
       }
+    }),
+    ExposeStrategy::Wrapper => {
+      wrap_exposed_members(state, overrides, exposed_variable_wrapper_snippet)
     }
-  })
+  }
 }
 
+/// Free functions (`SourceUnitPart::FunctionDefinition`) never carry a visibility specifier at
+/// all - they're implicitly callable wherever the file they live in is imported - so there is
+/// nothing for the `InPlace` strategy to flip; only `Wrapper` has anything to do for them, via
+/// [`wrap_exposed_free_functions`].
 fn expose_functions_internal(
   state: &mut State,
   overrides: Option<&AstConfigOptions>,
 ) -> Result<()> {
-  mutate_contracts(state, overrides, |contract| {
-    for member in &mut contract.nodes {
-      if let ContractDefinitionPart::FunctionDefinition(function) = member {
-        if matches!(
-          function.visibility,
-          Visibility::Private | Visibility::Internal
-        ) {
-          function.visibility = Visibility::Public;
+  match expose_strategy(overrides) {
+    ExposeStrategy::InPlace => mutate_contracts(state, overrides, |contract| {
+      for member in &mut contract.nodes {
+        if let ContractDefinitionPart::FunctionDefinition(function) = member {
+          if matches!(
+            function.visibility,
+            Visibility::Private | Visibility::Internal
+          ) {
+            function.visibility = Visibility::Public;
+          }
         }
       }
-    }
-  })
-}
-
-pub fn validate(state: &mut State, overrides: Option<&AstConfigOptions>) -> Result<()> {
-  info!(
-    target: LOG_TARGET,
-    "validating AST (current_contract={:?})",
-    state.config.instrumented_contract()
-  );
-  let config = resolve_config(state, overrides)?;
-  let mut compile_config = config.solc.clone();
-  compile_config.settings.stop_after = None;
-
-  let target = target_ast(state)?;
-  let mut ast_value = map_err_with_context(
-    serde_json::to_value(target),
-    "Failed to serialise AST for validation",
-  )?;
-  utils::sanitize_ast_value(&mut ast_value);
-
-  let settings_value = map_err_with_context(
-    serde_json::to_value(&compile_config.settings),
-    "Failed to serialise compiler settings",
-  )?;
-
-  let input = json!({
-    "language": "SolidityAST",
-    "sources": {
-      VIRTUAL_SOURCE_PATH: { "ast": ast_value }
-    },
-    "settings": settings_value
-  });
-
-  let solc = solc::ensure_installed(&compile_config.version)?;
-  let output: Value = map_err_with_context(solc.compile_as(&input), "Solc validation failed")?;
-
-  if let Some(errors) = output.get("errors").and_then(|value| value.as_array()) {
-    let mut messages = Vec::new();
-    for error in errors {
-      let severity = error
-        .get("severity")
-        .and_then(|value| value.as_str())
-        .unwrap_or_default();
-      if severity.eq_ignore_ascii_case("error") {
-        let message = error
-          .get("formattedMessage")
-          .and_then(|value| value.as_str())
-          .or_else(|| error.get("message").and_then(|value| value.as_str()))
-          .unwrap_or("Compilation error");
-        messages.push(message.to_string());
+    }),
+    ExposeStrategy::Wrapper => {
+      wrap_exposed_members(state, overrides, exposed_function_wrapper_snippet)?;
+      if contract_override(state, overrides).is_none() {
+        wrap_exposed_free_functions(state, overrides)?;
       }
-    }
-    if !messages.is_empty() {
-      error!(
-        target: LOG_TARGET,
-        "AST validation failed with {} error(s)",
-        messages.len()
-      );
-      return Err(Error::new(format!(
-        "AST validation failed:\n{}",
-        messages.join("\n")
-      )));
+      Ok(())
     }
   }
-
-  let next_ast_value = output
-    .get("sources")
-    .and_then(|sources| sources.get(VIRTUAL_SOURCE_PATH))
-    .and_then(|entry| entry.get("ast"))
-    .cloned()
-    .ok_or_else(|| Error::new("Validation succeeded but AST output was missing"))?;
-
-  let next_ast = map_err_with_context(
-    serde_json::from_value::<SourceUnit>(next_ast_value),
-    "Failed to deserialise validated AST",
-  )?;
-
-  state.ast = Some(next_ast);
-  info!(target: LOG_TARGET, "AST validation succeeded");
-  Ok(())
 }
 
-fn load_source_text(
+/// The source-unit counterpart of [`wrap_exposed_members`]: builds an `exposed_<name>` forwarder
+/// for every free function in each of [`contractless_sources`], then stitches the forwarders in
+/// through [`stitcher::stitch_fragment_parts_into_source_unit`] - the file-level equivalent of the
+/// contract-member stitch `wrap_exposed_members` uses.
+fn wrap_exposed_free_functions(
   state: &mut State,
-  source: &str,
   overrides: Option<&AstConfigOptions>,
 ) -> Result<()> {
   let config = resolve_config(state, overrides)?;
   let solc = solc::ensure_installed(&config.solc.version)?;
 
-  let ast = map_err_with_context(
-    AstOrchestrator::parse_source_unit(source, VIRTUAL_SOURCE_PATH, &solc, &config.solc.settings),
-    "Failed to parse target source",
-  )?;
+  for path in contractless_sources(state) {
+    let snippets: Vec<String> = {
+      let unit = &state.sources[&path];
+      unit
+        .nodes
+        .iter()
+        .filter_map(exposed_free_function_wrapper_snippet)
+        .collect()
+    };
+    if snippets.is_empty() {
+      continue;
+    }
+
+    let fragment_source = snippets.join("\n\n");
+    let fragment_unit = map_err_with_context(
+      AstOrchestrator::parse_source_unit(
+        &fragment_source,
+        "__AstFragment.sol",
+        &solc,
+        &config.solc.settings,
+      ),
+      "Failed to parse free-function exposure wrapper",
+    )?;
+
+    let max_id = map_err_with_context(
+      utils::project_max_id(&state.sources),
+      "Failed to compute next AST id",
+    )?;
+    let unit = state
+      .sources
+      .get_mut(&path)
+      .ok_or_else(|| Error::new("Invalid source path"))?;
+    map_err_with_context(
+      stitcher::stitch_fragment_parts_into_source_unit(
+        unit,
+        &fragment_unit,
+        max_id,
+        ResolveConflictStrategy::Safe,
+      ),
+      "Failed to stitch free-function exposure wrapper",
+    )?;
+  }
 
-  state.ast = Some(ast);
   Ok(())
 }
 
-fn load_source_ast(
+/// Builds a source-text `exposed_<name>` forwarder for every contract member `snippet_for`
+/// accepts, then stitches the forwarders in through the same fragment-parse + id-remapping path
+/// `inject_shadow` already uses, rather than mutating the member in place. Leaving the original
+/// node untouched keeps `super` dispatch and virtual/override resolution intact.
+fn wrap_exposed_members<F>(
   state: &mut State,
-  target_ast: SourceUnit,
   overrides: Option<&AstConfigOptions>,
-) -> Result<()> {
+  mut snippet_for: F,
+) -> Result<()>
+where
+  F: FnMut(&ContractDefinitionPart) -> Option<String>,
+{
   let config = resolve_config(state, overrides)?;
-  solc::ensure_installed(&config.solc.version)?;
+  let solc = solc::ensure_installed(&config.solc.version)?;
 
-  map_err_with_context(
-    stitcher::find_instrumented_contract_index(&target_ast, contract_override(state, overrides)),
-    "Failed to locate target contract",
-  )?;
+  let indices = contract_indices(state, overrides)?;
+
+  for (path, idx) in indices {
+    let snippets: Vec<String> = {
+      let unit = state
+        .sources
+        .get(&path)
+        .ok_or_else(|| Error::new("Invalid contract index"))?;
+      let SourceUnitPart::ContractDefinition(contract) = &unit.nodes[idx] else {
+        continue;
+      };
+      contract.nodes.iter().filter_map(&mut snippet_for).collect()
+    };
+    if snippets.is_empty() {
+      continue;
+    }
+
+    let fragment_source = snippets.join("\n\n");
+    let fragment_contract = map_err_with_context(
+      AstOrchestrator::parse_fragment_contract(&fragment_source, &solc, &config.solc.settings),
+      "Failed to parse exposure wrapper functions",
+    )?;
+
+    let max_id = map_err_with_context(
+      utils::project_max_id(&state.sources),
+      "Failed to compute next AST id",
+    )?;
+    let unit = state
+      .sources
+      .get_mut(&path)
+      .ok_or_else(|| Error::new("Invalid contract index"))?;
+    map_err_with_context(
+      stitcher::stitch_fragment_nodes_into_contract(
+        unit,
+        idx,
+        &fragment_contract,
+        max_id,
+        ResolveConflictStrategy::Safe,
+      ),
+      "Failed to stitch exposure wrapper functions",
+    )?;
+  }
 
-  state.ast = Some(target_ast);
   Ok(())
 }
 
-fn inject_fragment_string(
+/// Instruments every contract `instrument_coverage` targets, then stitches in the `__cov` ledger
+/// and its getter through the same fragment-parse + id-remapping path `wrap_exposed_members`
+/// uses. Contracts with no instrumentable function bodies are left untouched rather than growing
+/// a `__cov` mapping no counter ever writes to.
+fn instrument_coverage_internal(
   state: &mut State,
-  fragment_source: &str,
   overrides: Option<&AstConfigOptions>,
 ) -> Result<()> {
   let config = resolve_config(state, overrides)?;
   let solc = solc::ensure_installed(&config.solc.version)?;
 
-  let strategy = config.resolve_conflict_strategy;
-  let fragment_contract = map_err_with_context(
-    AstOrchestrator::parse_fragment_contract(fragment_source, &solc, &config.solc.settings),
-    "Failed to parse AST fragment",
+  let indices = contract_indices(state, overrides)?;
+  let mut next_id = map_err_with_context(
+    utils::project_max_id(&state.sources),
+    "Failed to compute next AST id",
   )?;
 
-  inject_fragment_contract(state, fragment_contract, overrides, strategy)
+  for (path, idx) in indices {
+    let block_count = {
+      let unit = state
+        .sources
+        .get_mut(&path)
+        .ok_or_else(|| Error::new("Invalid contract index"))?;
+      coverage::instrument_contract(unit, idx, &solc, &config.solc.settings, &mut next_id)?
+    };
+    if block_count == 0 {
+      continue;
+    }
+
+    let fragment_source = coverage::storage_fragment_source();
+    let fragment_contract = map_err_with_context(
+      AstOrchestrator::parse_fragment_contract(&fragment_source, &solc, &config.solc.settings),
+      "Failed to parse coverage storage fragment",
+    )?;
+
+    let unit = state
+      .sources
+      .get_mut(&path)
+      .ok_or_else(|| Error::new("Invalid contract index"))?;
+    map_err_with_context(
+      stitcher::stitch_fragment_nodes_into_contract(
+        unit,
+        idx,
+        &fragment_contract,
+        next_id,
+        ResolveConflictStrategy::Safe,
+      ),
+      "Failed to stitch coverage storage fragment",
+    )?;
+    next_id = map_err_with_context(
+      utils::project_max_id(&state.sources),
+      "Failed to compute next AST id",
+    )?;
+  }
+
+  Ok(())
 }
 
-fn inject_fragment_ast(
-  state: &mut State,
-  fragment_ast: SourceUnit,
-  overrides: Option<&AstConfigOptions>,
-) -> Result<()> {
-  let config = resolve_config(state, overrides)?;
-  solc::ensure_installed(&config.solc.version)?;
+/// The solc-reported Solidity type string for a declaration, used to re-declare the same type in
+/// generated wrapper source rather than walking its `TypeName` node by hand.
+fn declaration_type_string(declaration: &VariableDeclaration) -> Option<String> {
+  declaration.type_descriptions.type_string.clone()
+}
 
-  let strategy = config.resolve_conflict_strategy;
-  let fragment_contract = map_err_with_context(
+/// Reference types need an explicit data location in a function signature; value types (and
+/// solc-reported type strings that fail to parse) don't. `memory` is always valid on a `public`
+/// wrapper, whether the forwarder takes the value in or hands it back out.
+fn with_memory_location_if_needed(type_string: &str) -> String {
+  let needs_location = type_string.ends_with(']')
+    || type_string.starts_with("struct ")
+    || type_string.starts_with("mapping(")
+    || type_string == "string"
+    || type_string == "bytes";
+  if needs_location {
+    format!("{type_string} memory")
+  } else {
+    type_string.to_string()
+  }
+}
+
+fn exposed_variable_wrapper_snippet(part: &ContractDefinitionPart) -> Option<String> {
+  let ContractDefinitionPart::VariableDeclaration(variable) = part else {
+    return None;
+  };
+  if !matches!(
+    variable.visibility,
+    Visibility::Private | Visibility::Internal
+  ) {
+    return None;
+  }
+
+  let type_string = declaration_type_string(variable)?;
+  Some(format!(
+    "function exposed_{name}() public view returns ({ty}) {{ return {name}; }}",
+    name = variable.name,
+    ty = with_memory_location_if_needed(&type_string),
+  ))
+}
+
+/// solc's serialised `stateMutability`, read as untyped JSON rather than matched against the
+/// crate's `StateMutability` enum since its exact variant names aren't depended on elsewhere in
+/// this codebase.
+fn function_state_mutability_keyword(function: &FunctionDefinition) -> Option<&'static str> {
+  let value = serde_json::to_value(function).ok()?;
+  match value.get("stateMutability").and_then(Value::as_str)? {
+    "view" => Some("view"),
+    "pure" => Some("pure"),
+    "payable" => Some("payable"),
+    _ => None,
+  }
+}
+
+fn exposed_function_wrapper_snippet(part: &ContractDefinitionPart) -> Option<String> {
+  let ContractDefinitionPart::FunctionDefinition(function) = part else {
+    return None;
+  };
+  if !matches!(
+    function.visibility,
+    Visibility::Private | Visibility::Internal | Visibility::External
+  ) {
+    return None;
+  }
+  let call_external = matches!(function.visibility, Visibility::External);
+  function_forwarder_snippet(function, call_external)
+}
+
+/// The file-level counterpart of [`exposed_function_wrapper_snippet`]: every free function is a
+/// candidate, since (unlike a contract member) it never carries a visibility specifier to gate
+/// on, and is never `external` (so always called directly rather than through `this.`).
+fn exposed_free_function_wrapper_snippet(part: &SourceUnitPart) -> Option<String> {
+  let SourceUnitPart::FunctionDefinition(function) = part else {
+    return None;
+  };
+  function_forwarder_snippet(function, false)
+}
+
+/// Builds a public `exposed_<name>` forwarder for `function`, calling it as `this.<name>(...)`
+/// when `call_external` (required for an `external` contract member, whose parameters aren't
+/// otherwise reachable from inside the same contract) or plain `<name>(...)` otherwise. Shared by
+/// [`exposed_function_wrapper_snippet`] (contract members) and
+/// [`exposed_free_function_wrapper_snippet`] (file-level free functions).
+fn function_forwarder_snippet(function: &FunctionDefinition, call_external: bool) -> Option<String> {
+  // No implementation to forward to (e.g. an interface stub).
+  function.body.as_ref()?;
+
+  let params: Vec<(String, String)> = function
+    .parameters
+    .parameters
+    .iter()
+    .enumerate()
+    .map(|(idx, param)| {
+      let ty = with_memory_location_if_needed(&declaration_type_string(param)?);
+      let name = if param.name.is_empty() {
+        format!("arg{idx}")
+      } else {
+        param.name.clone()
+      };
+      Some((ty, name))
+    })
+    .collect::<Option<Vec<_>>>()?;
+
+  let returns: Vec<String> = function
+    .return_parameters
+    .parameters
+    .iter()
+    .map(|param| declaration_type_string(param).map(|ty| with_memory_location_if_needed(&ty)))
+    .collect::<Option<Vec<_>>>()?;
+
+  let param_list = params
+    .iter()
+    .map(|(ty, name)| format!("{ty} {name}"))
+    .collect::<Vec<_>>()
+    .join(", ");
+  let arg_list = params
+    .iter()
+    .map(|(_, name)| name.as_str())
+    .collect::<Vec<_>>()
+    .join(", ");
+  let returns_clause = if returns.is_empty() {
+    String::new()
+  } else {
+    format!(" returns ({})", returns.join(", "))
+  };
+  let mutability = function_state_mutability_keyword(function)
+    .map(|keyword| format!(" {keyword}"))
+    .unwrap_or_default();
+
+  let call_target = if call_external {
+    format!("this.{}({})", function.name, arg_list)
+  } else {
+    format!("{}({})", function.name, arg_list)
+  };
+  let body = if returns.is_empty() {
+    format!("{call_target};")
+  } else {
+    format!("return {call_target};")
+  };
+
+  Some(format!(
+    "function exposed_{name}({param_list}) public{mutability}{returns_clause} {{ {body} }}",
+    name = function.name,
+  ))
+}
+
+pub fn validate(
+  state: &mut State,
+  overrides: Option<&AstConfigOptions>,
+) -> Result<ValidationReport> {
+  info!(
+    target: LOG_TARGET,
+    "validating AST (current_contract={:?})",
+    state.config.instrumented_contract()
+  );
+  let config = resolve_config(state, overrides)?;
+  let mut compile_config = config.solc.clone();
+  compile_config.settings.stop_after = None;
+
+  // Unreachable once `target_ast` below succeeds, but guards the empty-project case with a clearer
+  // message than an empty `sources` object would get back from solc.
+  target_ast(state)?;
+
+  let mut sources_value = serde_json::Map::new();
+  for (path, unit) in &state.sources {
+    let mut ast_value = map_err_with_context(
+      serde_json::to_value(unit),
+      "Failed to serialise AST for validation",
+    )?;
+    utils::sanitize_ast_value(&mut ast_value);
+    sources_value.insert(path.clone(), json!({ "ast": ast_value }));
+  }
+
+  let settings_value = map_err_with_context(
+    serde_json::to_value(&compile_config.settings),
+    "Failed to serialise compiler settings",
+  )?;
+
+  let input = json!({
+    "language": "SolidityAST",
+    "sources": Value::Object(sources_value),
+    "settings": settings_value
+  });
+
+  let solc = solc::ensure_installed(&compile_config.version)?;
+  let output: Value = map_err_with_context(solc.compile_as(&input), "Solc validation failed")?;
+
+  let report = build_validation_report(&output);
+  state.last_validation = Some(report.clone());
+
+  if !report.errors.is_empty() {
+    let messages: Vec<&str> = report
+      .errors
+      .iter()
+      .map(|diagnostic| diagnostic.formatted_message.as_str())
+      .collect();
+    error!(
+      target: LOG_TARGET,
+      "AST validation failed with {} error(s)",
+      messages.len()
+    );
+    return Err(Error::new(format!(
+      "AST validation failed:\n{}",
+      messages.join("\n")
+    )));
+  }
+
+  let output_sources = output
+    .get("sources")
+    .ok_or_else(|| Error::new("Validation succeeded but AST output was missing"))?;
+
+  for path in state.sources.keys().cloned().collect::<Vec<_>>() {
+    let next_ast_value = output_sources
+      .get(&path)
+      .and_then(|entry| entry.get("ast"))
+      .cloned()
+      .ok_or_else(|| {
+        Error::new(format!(
+          "Validation succeeded but AST output for '{path}' was missing"
+        ))
+      })?;
+
+    let next_ast = map_err_with_context(
+      serde_json::from_value::<SourceUnit>(next_ast_value),
+      "Failed to deserialise validated AST",
+    )?;
+    state.sources.insert(path, next_ast);
+  }
+
+  info!(target: LOG_TARGET, "AST validation succeeded");
+  Ok(report)
+}
+
+/// Compiles the current project to bytecode/ABI artifacts: full (non-`stop_after`) settings, a
+/// complete `OutputSelection` covering bytecode/deployed bytecode/ABI/method identifiers/metadata,
+/// and - unlike `validate`, which treats any solc error as a hard `Err` - solc diagnostics are
+/// surfaced on the returned `CompileOutput` itself rather than aborting the call, since a caller
+/// instrumenting a contract that doesn't yet compile still wants the structured error list back.
+/// Reuses `compiler::output::from_standard_json`, the same standalone-source artifact path
+/// `compiler::compile_sources` goes through, so a source with no contract at all still gets an
+/// artifact entry instead of being silently dropped.
+pub fn compile(state: &State, overrides: Option<&AstConfigOptions>) -> Result<CompileOutput> {
+  info!(target: LOG_TARGET, "compiling AST to bytecode/ABI artifacts");
+  let config = resolve_config(state, overrides)?;
+  let mut compile_config = config.solc.clone();
+  compile_config.settings.stop_after = None;
+  compile_config.settings.output_selection = OutputSelection::default_output_selection();
+
+  let output = compile_with_settings(state, &compile_config.version, &compile_config.settings)?;
+  info!(target: LOG_TARGET, "AST compilation finished");
+  Ok(output)
+}
+
+/// Compiles the currently instrumented AST with solc's built-in model checker (SMTChecker)
+/// enabled, the engine for [`verify`] to drive against shadow invariants `inject_assertions`
+/// stitches in. Like `compile`, solc diagnostics are surfaced on the returned report rather than
+/// aborting the call - a violated property is exactly what a caller is checking for, not an error
+/// in the instrumentation. Defaults the model checker to the `chc` engine over `assert` targets
+/// when `overrides`/`state.config` don't configure one, so `verify()` with no extra setup still
+/// runs the checker instead of silently compiling with it off.
+pub fn verify(
+  state: &State,
+  overrides: Option<&AstConfigOptions>,
+) -> Result<VerificationReport> {
+  info!(target: LOG_TARGET, "verifying AST with solc's model checker");
+  let config = resolve_config(state, overrides)?;
+  let mut compile_config = config.solc.clone();
+  compile_config.settings.stop_after = None;
+  compile_config.settings.output_selection = OutputSelection::default_output_selection();
+
+  if compile_config.settings.model_checker.is_none() {
+    let defaults = CompilerSettingsOptions {
+      model_checker: Some(ModelCheckerSettingsOptions {
+        engine: Some(ModelCheckerEngine::Chc),
+        targets: Some(vec![ModelCheckerTarget::Assert]),
+        ..Default::default()
+      }),
+      ..Default::default()
+    };
+    compile_config.settings = map_err_with_context(
+      defaults.overlay(&compile_config.settings),
+      "Failed to apply default model checker settings",
+    )?;
+  }
+
+  let output = compile_with_settings(state, &compile_config.version, &compile_config.settings)?;
+  let report = build_verification_report(output);
+  info!(target: LOG_TARGET, "SMTChecker verification finished");
+  Ok(report)
+}
+
+/// Shared by [`compile`] and [`verify`]: serialises every source in `state.sources` as a
+/// `SolidityAST` compile input and runs it through solc with the given version/settings, the same
+/// standalone-source artifact path `compiler::compile_sources` goes through so a source with no
+/// contract at all still gets an artifact entry instead of being silently dropped.
+fn compile_with_settings(
+  state: &State,
+  version: &Version,
+  settings: &Settings,
+) -> Result<CompileOutput> {
+  // Unreachable once the loop below runs, but guards the empty-project case with a clearer
+  // message than an empty `sources` object would get back from solc.
+  target_ast(state)?;
+
+  let mut sources_value = serde_json::Map::new();
+  for (path, unit) in &state.sources {
+    let mut ast_value = map_err_with_context(
+      serde_json::to_value(unit),
+      "Failed to serialise AST for compilation",
+    )?;
+    utils::sanitize_ast_value(&mut ast_value);
+    sources_value.insert(path.clone(), json!({ "ast": ast_value }));
+  }
+
+  let settings_value = map_err_with_context(
+    serde_json::to_value(settings),
+    "Failed to serialise compiler settings",
+  )?;
+
+  let input = json!({
+    "language": "SolidityAST",
+    "sources": Value::Object(sources_value),
+    "settings": settings_value
+  });
+
+  let solc = solc::ensure_installed(version)?;
+  let output: CompilerOutput =
+    map_err_with_context(solc.compile_as(&input), "Solc compilation failed")?;
+
+  Ok(from_standard_json(output, &[], &BTreeMap::new(), false))
+}
+
+/// One SMTChecker/model-checker finding from a `verify` call, with its counterexample (if solc's
+/// message included one) split out so callers don't have to parse `diagnostic.message` themselves.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationFinding {
+  pub diagnostic: ModelCheckerDiagnostic,
+  pub counterexample: Option<String>,
+}
+
+impl From<ModelCheckerDiagnostic> for VerificationFinding {
+  fn from(diagnostic: ModelCheckerDiagnostic) -> Self {
+    let counterexample = diagnostic
+      .message
+      .split_once("Counterexample:")
+      .map(|(_, example)| example.trim().to_string());
+    Self {
+      diagnostic,
+      counterexample,
+    }
+  }
+}
+
+/// The result of a `verify` call: solc's model-checker findings split by outcome (so `holds()` -
+/// "every checked property held" - doesn't require a caller to inspect severities itself), plus
+/// any ordinary compiler diagnostics the same compile produced.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationReport {
+  pub violations: Vec<VerificationFinding>,
+  pub unproved: Vec<VerificationFinding>,
+  pub proved_safe: Vec<ModelCheckerDiagnostic>,
+  pub compile_errors: Vec<CompilerError>,
+}
+
+impl VerificationReport {
+  /// `true` when the model checker reported no violated property - i.e. every assertion/target it
+  /// could fully analyze held. An `unproved` finding (timeout, unsupported construct, ...) doesn't
+  /// count as a violation, but also isn't a guarantee the property holds.
+  pub fn holds(&self) -> bool {
+    self.violations.is_empty()
+  }
+}
+
+fn build_verification_report(output: CompileOutput) -> VerificationReport {
+  let mut report = VerificationReport::default();
+
+  for diagnostic in output.model_checker_diagnostics() {
+    match diagnostic.severity {
+      SeverityLevel::Error => report.violations.push(diagnostic.into()),
+      SeverityLevel::Warning => report.unproved.push(diagnostic.into()),
+      SeverityLevel::Info => report.proved_safe.push(diagnostic),
+    }
+  }
+
+  report.compile_errors = output
+    .errors
+    .into_iter()
+    .filter(|error| !error.message.starts_with("CHC:") && !error.message.starts_with("BMC:"))
+    .collect();
+
+  report
+}
+
+/// Stitches `assert(<expr>)` onto every `return` (and the end of the body) of a target function,
+/// the same way `instrumenter::inject_edges` already threads `before`/`after` snippets around a
+/// function's exit points - so a caller can drive [`verify`] against shadow invariants it supplies
+/// instead of ones already written into the contract. `function_selector` follows the same
+/// `name`/`name(paramTypes)`/`fallback`/`receive`/`constructor` convention `inject_edges` accepts.
+pub fn inject_assertions(
+  state: &mut State,
+  function_selector: &str,
+  assertions: &[String],
+  overrides: Option<&AstConfigOptions>,
+) -> Result<()> {
+  if assertions.is_empty() {
+    return Err(Error::new(
+      "injectAssertions requires at least one assertion.",
+    ));
+  }
+
+  info!(
+    target: LOG_TARGET,
+    "injecting {} assertion(s) into '{}'",
+    assertions.len(),
+    function_selector
+  );
+
+  let config = resolve_config(state, overrides)?;
+  let solc = solc::ensure_installed(&config.solc.version)?;
+  let contract_name = contract_override(state, overrides).map(|name| name.to_owned());
+  let (path, contract_idx) = find_contract_in_project(state, contract_name.as_deref())?;
+
+  let snippets: Vec<String> = assertions
+    .iter()
+    .map(|assertion| format!("assert({});", assertion.trim().trim_end_matches(';')))
+    .collect();
+
+  let source = state.raw_sources.get(&path).cloned();
+  let unit = state
+    .sources
+    .get_mut(&path)
+    .ok_or_else(|| Error::new("Invalid contract index"))?;
+
+  instrumenter::inject_edges(
+    unit,
+    contract_idx,
+    function_selector,
+    &[],
+    &snippets,
+    &solc,
+    &config.solc.settings,
+    source.as_deref(),
+  )?;
+
+  info!(target: LOG_TARGET, "assertions injected");
+  Ok(())
+}
+
+/// Splits solc's flat `errors` array (which, despite the key, carries every severity) into a
+/// [`ValidationReport`], preserving each diagnostic's code/type/location instead of collapsing
+/// hard errors into a single joined string.
+fn build_validation_report(output: &Value) -> ValidationReport {
+  let mut report = ValidationReport::default();
+  let Some(diagnostics) = output.get("errors").and_then(|value| value.as_array()) else {
+    return report;
+  };
+
+  for entry in diagnostics {
+    let diagnostic = parse_validation_diagnostic(entry);
+    match diagnostic.severity.to_ascii_lowercase().as_str() {
+      "error" => report.errors.push(diagnostic),
+      "info" => report.infos.push(diagnostic),
+      _ => report.warnings.push(diagnostic),
+    }
+  }
+
+  report
+}
+
+fn parse_validation_diagnostic(entry: &Value) -> ValidationDiagnostic {
+  let severity = entry
+    .get("severity")
+    .and_then(|value| value.as_str())
+    .unwrap_or_default()
+    .to_string();
+  let error_code = entry.get("errorCode").and_then(|value| {
+    value
+      .as_str()
+      .map(str::to_string)
+      .or_else(|| value.as_u64().map(|code| code.to_string()))
+  });
+  let error_code = error_code.and_then(|code| code.parse::<u32>().ok());
+  let kind = entry
+    .get("type")
+    .and_then(|value| value.as_str())
+    .unwrap_or_default()
+    .to_string();
+  let formatted_message = entry
+    .get("formattedMessage")
+    .and_then(|value| value.as_str())
+    .or_else(|| entry.get("message").and_then(|value| value.as_str()))
+    .unwrap_or("Compilation error")
+    .to_string();
+  let source_location = entry
+    .get("sourceLocation")
+    .and_then(|location| {
+      let path = location.get("file").and_then(|value| value.as_str())?.to_string();
+      let start = location.get("start").and_then(|value| value.as_i64())?;
+      let end = location.get("end").and_then(|value| value.as_i64())?;
+      Some(ValidationSourceLocation {
+        path,
+        start,
+        length: (end - start).max(0),
+      })
+    });
+
+  ValidationDiagnostic {
+    severity,
+    error_code,
+    kind,
+    formatted_message,
+    source_location,
+  }
+}
+
+/// Keeps node ids unique across the whole project: a freshly parsed unit starts its own ids near
+/// zero, so once more than one source is loaded, each later one is renumbered to continue from the
+/// highest id already in use - the same renumbering a stitched-in fragment already gets.
+fn renumber_for_project(state: &State, unit: &mut SourceUnit) -> Result<()> {
+  let highest = map_err_with_context(
+    utils::project_max_id(&state.sources),
+    "Failed to compute next AST id",
+  )?;
+  if highest == 0 {
+    return Ok(());
+  }
+  let mut next_id = highest;
+  *unit = map_err_with_context(
+    utils::clone_with_new_ids(unit, &mut next_id),
+    "Failed to renumber AST ids",
+  )?;
+  Ok(())
+}
+
+/// Parses `source` via solc, or returns the cached `SourceUnit` from an earlier call against the
+/// same source text, solc version, and settings - see `parse_cache`. A cache miss still goes
+/// through solc and is recorded for next time; a failure to persist the result to disk is logged
+/// but never fails the parse itself, since the in-memory entry is already good enough for the rest
+/// of this process.
+fn parse_source_cached(
+  state: &mut State,
+  source: &str,
+  file_name: &str,
+  solc: &Solc,
+  settings: &Settings,
+) -> std::result::Result<SourceUnit, AstError> {
+  let cache_key = parse_cache::key(source, &solc.version, settings);
+  if let Some(unit) = state.parse_cache.get(&cache_key) {
+    return Ok(unit);
+  }
+
+  let unit = AstOrchestrator::parse_source_unit(source, file_name, solc, settings)?;
+  if let Err(err) = state.parse_cache.insert(&cache_key, &unit) {
+    error!(target: LOG_TARGET, "failed to persist AST parse cache entry: {err}");
+  }
+  Ok(unit)
+}
+
+/// The fragment counterpart of [`parse_source_cached`]: wraps `fragment_source` in the same
+/// shadow `__AstFragment` contract template [`AstOrchestrator::parse_fragment_contract`] uses, but
+/// parses the wrapped text through the cache, so the same fragment applied against several targets
+/// in one batch only spawns solc once.
+fn parse_fragment_contract_cached(
+  state: &mut State,
+  fragment_source: &str,
+  solc: &Solc,
+  settings: &Settings,
+) -> std::result::Result<ContractDefinition, AstError> {
+  let wrapped = parser::wrap_fragment_source(fragment_source);
+  let unit = parse_source_cached(state, &wrapped, "__AstFragment.sol", solc, settings)?;
+  AstOrchestrator::extract_fragment_contract(&unit)
+}
+
+/// Parses `source` and loads it into `state.sources` under `path`. If `source` contains an
+/// `import`, it's flattened against every other raw text source already loaded (via `from_source`
+/// or an earlier `add_source` call) before parsing, since `parser::parse_source_ast` only ever
+/// hands solc the one file - see [`super::flatten`]. Imports referencing a sibling that hasn't
+/// been loaded yet still fail; callers must `add_source` dependencies before the file that imports
+/// them.
+fn load_source_text(
+  state: &mut State,
+  path: &str,
+  source: &str,
+  overrides: Option<&AstConfigOptions>,
+) -> Result<()> {
+  let config = resolve_config(state, overrides)?;
+  let solc = solc::ensure_installed(&config.solc.version)?;
+
+  state.raw_sources.insert(path.to_string(), source.to_string());
+
+  let parse_source = if source.contains("import") {
+    map_err_with_context(
+      super::flatten::flatten_source(path, &state.raw_sources, &[]),
+      "Failed to flatten imports for target source",
+    )?
+  } else {
+    source.to_string()
+  };
+
+  let mut ast = map_err_with_context(
+    parse_source_cached(state, &parse_source, path, &solc, &config.solc.settings),
+    "Failed to parse target source",
+  )?;
+  renumber_for_project(state, &mut ast)?;
+
+  state.sources.insert(path.to_string(), ast);
+  Ok(())
+}
+
+fn load_source_ast(
+  state: &mut State,
+  path: &str,
+  mut target_ast: SourceUnit,
+  overrides: Option<&AstConfigOptions>,
+) -> Result<()> {
+  let config = resolve_config(state, overrides)?;
+  solc::ensure_installed(&config.solc.version)?;
+
+  // Only the unit that is (or is about to become) the primary target needs to actually contain
+  // the configured contract; siblings loaded via `add_source` may hold nothing but a base/library
+  // the primary imports.
+  if state.primary_path.is_none() || state.primary_path.as_deref() == Some(path) {
+    map_err_with_context(
+      stitcher::find_instrumented_contract_index(&target_ast, contract_override(state, overrides)),
+      "Failed to locate target contract",
+    )?;
+  }
+
+  renumber_for_project(state, &mut target_ast)?;
+  state.sources.insert(path.to_string(), target_ast);
+  Ok(())
+}
+
+fn inject_fragment_string(
+  state: &mut State,
+  fragment_source: &str,
+  overrides: Option<&AstConfigOptions>,
+) -> Result<()> {
+  let config = resolve_config(state, overrides)?;
+  let solc = solc::ensure_installed(&config.solc.version)?;
+  let strategy = config.resolve_conflict_strategy;
+
+  if contract_override(state, overrides).is_none() && !project_has_contracts(state) {
+    let fragment_ast = map_err_with_context(
+      parse_source_cached(
+        state,
+        fragment_source,
+        "__AstFragment.sol",
+        &solc,
+        &config.solc.settings,
+      ),
+      "Failed to parse AST fragment",
+    )?;
+    return stitch_fragment_at_source_unit_scope(state, &fragment_ast, strategy);
+  }
+
+  let fragment_contract = map_err_with_context(
+    parse_fragment_contract_cached(state, fragment_source, &solc, &config.solc.settings),
+    "Failed to parse AST fragment",
+  )?;
+
+  inject_fragment_contract(state, fragment_contract, overrides, strategy)
+}
+
+fn inject_fragment_ast(
+  state: &mut State,
+  fragment_ast: SourceUnit,
+  overrides: Option<&AstConfigOptions>,
+) -> Result<()> {
+  let config = resolve_config(state, overrides)?;
+  solc::ensure_installed(&config.solc.version)?;
+  let strategy = config.resolve_conflict_strategy;
+
+  if contract_override(state, overrides).is_none() && !project_has_contracts(state) {
+    return stitch_fragment_at_source_unit_scope(state, &fragment_ast, strategy);
+  }
+
+  let fragment_contract = map_err_with_context(
     AstOrchestrator::extract_fragment_contract(&fragment_ast),
     "Failed to locate fragment contract",
   )?;
 
-  inject_fragment_contract(state, fragment_contract, overrides, strategy)
+  inject_fragment_contract(state, fragment_contract, overrides, strategy)?;
+  stitch_fragment_ast_siblings(state, &fragment_ast, overrides, strategy)
+}
+
+/// Once [`inject_fragment_ast`] has merged `fragment_ast`'s `ContractDefinition` into the target
+/// contract, splices whatever file-scope declarations the fragment also carried (free functions,
+/// constants, structs, enums, UDVTs, `using ... for` directives) into the same source file, right
+/// alongside the contract they arrived with - those have nowhere to live inside the contract merge
+/// itself. A no-op when the fragment held nothing but its `ContractDefinition`.
+fn stitch_fragment_ast_siblings(
+  state: &mut State,
+  fragment_ast: &SourceUnit,
+  overrides: Option<&AstConfigOptions>,
+  strategy: ResolveConflictStrategy,
+) -> Result<()> {
+  let contract_name = contract_override(state, overrides).map(|name| name.to_owned());
+  let (path, _) = find_contract_in_project(state, contract_name.as_deref())?;
+
+  let max_id = map_err_with_context(
+    utils::project_max_id(&state.sources),
+    "Failed to compute next AST id",
+  )?;
+  let unit = state
+    .sources
+    .get_mut(&path)
+    .ok_or_else(|| Error::new("Invalid contract index"))?;
+  map_err_with_context(
+    stitcher::stitch_fragment_file_scope_siblings(unit, fragment_ast, max_id, strategy),
+    "Failed to stitch AST fragment's file-scope declarations",
+  )?;
+  Ok(())
+}
+
+/// The free-function/contract-less counterpart of [`inject_fragment_contract`]: stitches
+/// `fragment_ast`'s file-level members (free functions, structs, imports, ...) directly into the
+/// primary source via [`stitcher::stitch_fragment_parts_into_source_unit`], for a project with no
+/// `ContractDefinition` anywhere to stitch contract members into - see [`project_has_contracts`].
+fn stitch_fragment_at_source_unit_scope(
+  state: &mut State,
+  fragment_ast: &SourceUnit,
+  strategy: ResolveConflictStrategy,
+) -> Result<()> {
+  let path = primary_path(state).to_string();
+  let max_id = map_err_with_context(
+    utils::project_max_id(&state.sources),
+    "Failed to compute next AST id",
+  )?;
+  let unit = state
+    .sources
+    .get_mut(&path)
+    .ok_or_else(|| Error::new("Ast has no target AST. Call from_source first."))?;
+  map_err_with_context(
+    stitcher::stitch_fragment_parts_into_source_unit(unit, fragment_ast, max_id, strategy),
+    "Failed to stitch AST nodes",
+  )?;
+  state.last_stitch = None;
+  Ok(())
+}
+
+/// Merges one or more standalone contracts into the source at `path` (the primary source by
+/// default) via [`utils::merge_contract_definitions`]: each `target` is parsed into its own
+/// `SourceUnit` and every top-level `ContractDefinition` it holds is appended to `path`'s nodes.
+/// Unlike `inject_fragment_*`/`inject_shadow`, nothing is stitched into an existing contract's
+/// members - this is for splicing a whole extra contract in alongside the ones already loaded,
+/// e.g. a generated helper contract next to user sources.
+pub fn merge_contracts(
+  state: &mut State,
+  targets: Vec<SourceTarget>,
+  path: Option<&str>,
+  overrides: Option<&AstConfigOptions>,
+) -> Result<()> {
+  let path = path
+    .map(str::to_string)
+    .unwrap_or_else(|| primary_path(state).to_string());
+
+  let mut fragment_contracts = Vec::new();
+  for target in targets {
+    let unit = match target {
+      SourceTarget::Text(source) => {
+        let config = resolve_config(state, overrides)?;
+        let solc = solc::ensure_installed(&config.solc.version)?;
+        map_err_with_context(
+          parse_source_cached(
+            state,
+            &source,
+            "__AstMergeFragment.sol",
+            &solc,
+            &config.solc.settings,
+          ),
+          "Failed to parse merge fragment",
+        )?
+      }
+      SourceTarget::Ast(unit) => unit,
+    };
+    for part in unit.nodes {
+      if let SourceUnitPart::ContractDefinition(contract) = part {
+        fragment_contracts.push(*contract);
+      }
+    }
+  }
+
+  let base = state
+    .sources
+    .get(&path)
+    .ok_or_else(|| Error::new(format!("Unknown source path '{path}'")))?;
+  let merged = map_err_with_context(
+    utils::merge_contract_definitions(base, fragment_contracts),
+    "Failed to merge contracts",
+  )?;
+  state.sources.insert(path, merged);
+  Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-  use super::*;
-  use crate::ast::utils;
-  use crate::internal::config::{AstConfigOptions, CompilerLanguage, SolcConfig};
-  use crate::internal::settings::{CompilerSettingsOptions, OptimizerSettingsOptions};
-  use crate::internal::solc;
-  use foundry_compilers::artifacts::CompilerOutput;
-  use foundry_compilers::solc::Solc;
-  use serde_json::{json, Value};
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::ast::utils;
+  use crate::internal::config::{AstConfigOptions, CompilerLanguage, SolcConfig};
+  use crate::internal::settings::{CompilerSettingsOptions, OptimizerSettingsOptions};
+  use crate::internal::solc;
+  use foundry_compilers::artifacts::CompilerOutput;
+  use foundry_compilers::solc::Solc;
+  use serde_json::{json, Value};
+
+  const INSTRUMENTED_CONTRACT: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Target {
+  uint256 private value;
+  function callMe() internal view returns (uint256) {
+    return value;
+  }
+}
+"#;
+
+  fn find_default_solc() -> Option<Solc> {
+    let version = solc::default_version().ok()?;
+    Solc::find_svm_installed_version(&version).ok().flatten()
+  }
+
+  #[test]
+  fn parses_and_injects_fragment() {
+    if find_default_solc().is_none() {
+      return;
+    }
+
+    let default_settings =
+      AstOrchestrator::sanitize_settings(None).expect("sanitize default settings");
+    let default_language = solc::default_language();
+    let mut config = SolcConfig::new(
+      CompilerLanguage::from(default_language),
+      &default_settings,
+      Option::<&AstConfigOptions>::None,
+    )
+    .expect("config");
+    config.settings = AstOrchestrator::sanitize_settings(Some(config.settings.clone()))
+      .expect("sanitize config settings");
+    solc::ensure_installed(&config.version).expect("ensure solc");
+
+    let mut state = init(None).expect("init ast");
+
+    from_source(
+      &mut state,
+      SourceTarget::Text(INSTRUMENTED_CONTRACT.into()),
+      None,
+    )
+    .expect("load source");
+
+    let overrides = AstConfigOptions {
+      solc: crate::SolcConfigOptions::default(),
+      instrumented_contract: Some("Target".into()),
+      logging_level: None,
+      resolve_conflict_strategy: None,
+    };
+
+    inject_shadow(
+      &mut state,
+      FragmentTarget::Text(
+        "function extra() public view returns (uint256) { return value; }".into(),
+      ),
+      Some(&overrides),
+    )
+    .expect("inject fragment");
+
+    let ast = source_unit(&state).expect("ast");
+    let contract = ast
+      .nodes
+      .iter()
+      .filter_map(|part| match part {
+        SourceUnitPart::ContractDefinition(contract) => Some(contract.as_ref()),
+        _ => None,
+      })
+      .last()
+      .expect("contract node");
+
+    assert!(contract.nodes.iter().any(|part| matches!(part,
+      ContractDefinitionPart::FunctionDefinition(function) if function.name == "extra"
+    )));
+
+    fn collect_ids(value: &Value, out: &mut Vec<i64>) {
+      match value {
+        Value::Object(map) => {
+          if let Some(Value::Number(id)) = map.get("id") {
+            if let Some(id) = id.as_i64() {
+              out.push(id);
+            }
+          }
+          map.values().for_each(|child| collect_ids(child, out));
+        }
+        Value::Array(items) => items.iter().for_each(|child| collect_ids(child, out)),
+        _ => {}
+      }
+    }
+
+    let mut ids = Vec::new();
+    collect_ids(&serde_json::to_value(ast).expect("serialize ast"), &mut ids);
+    let unique = ids
+      .iter()
+      .copied()
+      .collect::<std::collections::HashSet<_>>();
+    assert_eq!(ids.len(), unique.len());
+  }
+
+  #[test]
+  fn exposes_internal_members() {
+    if find_default_solc().is_none() {
+      return;
+    }
+    let default_settings =
+      AstOrchestrator::sanitize_settings(None).expect("sanitize default settings");
+    let default_language = solc::default_language();
+    let mut config = SolcConfig::new(
+      CompilerLanguage::from(default_language),
+      &default_settings,
+      Option::<&AstConfigOptions>::None,
+    )
+    .expect("config");
+    config.settings = AstOrchestrator::sanitize_settings(Some(config.settings.clone()))
+      .expect("sanitize config settings");
+    solc::ensure_installed(&config.version).expect("ensure solc");
+
+    let mut state = init(None).expect("init ast");
+
+    from_source(
+      &mut state,
+      SourceTarget::Text(INSTRUMENTED_CONTRACT.into()),
+      None,
+    )
+    .expect("load source");
+    let overrides = AstConfigOptions {
+      solc: crate::SolcConfigOptions::default(),
+      instrumented_contract: Some("Target".into()),
+      logging_level: None,
+      resolve_conflict_strategy: None,
+    };
+    expose_internal_variables(&mut state, Some(&overrides)).expect("expose vars");
+    expose_internal_functions(&mut state, Some(&overrides)).expect("expose funcs");
+
+    let ast = source_unit(&state).expect("ast");
+    let contract = ast
+      .nodes
+      .iter()
+      .filter_map(|part| match part {
+        SourceUnitPart::ContractDefinition(contract) => Some(contract.as_ref()),
+        _ => None,
+      })
+      .last()
+      .expect("contract node");
+
+    let variable_visibility = contract.nodes.iter().find_map(|part| match part {
+      ContractDefinitionPart::VariableDeclaration(variable) => Some(variable.visibility.clone()),
+      _ => None,
+    });
 
-  const INSTRUMENTED_CONTRACT: &str = r#"
-// SPDX-License-Identifier: MIT
-pragma solidity ^0.8.0;
+    assert_eq!(variable_visibility, Some(Visibility::Public));
 
-contract Target {
-  uint256 private value;
-  function callMe() internal view returns (uint256) {
-    return value;
-  }
-}
-"#;
+    let function_visibility = contract.nodes.iter().find_map(|part| match part {
+      ContractDefinitionPart::FunctionDefinition(function) => Some(function.visibility.clone()),
+      _ => None,
+    });
 
-  fn find_default_solc() -> Option<Solc> {
-    let version = solc::default_version().ok()?;
-    Solc::find_svm_installed_version(&version).ok().flatten()
+    assert_eq!(function_visibility, Some(Visibility::Public));
   }
 
   #[test]
-  fn parses_and_injects_fragment() {
+  fn overrides_do_not_persist_across_calls() {
     if find_default_solc().is_none() {
       return;
     }
 
+    let mut state = init(None).expect("init ast");
+    let initial_config = state.config.clone();
+
+    let mut overrides = AstConfigOptions::default();
+    overrides.instrumented_contract = Some("Target".to_string());
+    overrides.solc.settings = Some({
+      let mut settings = CompilerSettingsOptions::default();
+      settings.optimizer = Some(OptimizerSettingsOptions {
+        enabled: Some(true),
+        runs: Some(200),
+        ..Default::default()
+      });
+      settings
+    });
+
+    let initial_settings_json =
+      serde_json::to_value(&state.config.solc.settings).expect("serialize initial settings");
+
+    from_source(
+      &mut state,
+      SourceTarget::Text(INSTRUMENTED_CONTRACT.into()),
+      Some(&overrides),
+    )
+    .expect("load source with override");
+
+    assert_eq!(
+      state.config.instrumented_contract(),
+      initial_config.instrumented_contract()
+    );
+
+    assert_eq!(
+      serde_json::to_value(&state.config.solc.settings).expect("serialize settings"),
+      initial_settings_json,
+      "expected base compiler settings to remain unchanged after from_source override"
+    );
+
+    expose_internal_variables(&mut state, Some(&overrides))
+      .expect("apply override without persisting");
+
+    assert_eq!(
+      state.config.instrumented_contract(),
+      initial_config.instrumented_contract()
+    );
+
+    assert_eq!(
+      serde_json::to_value(&state.config.solc.settings).expect("serialize settings"),
+      initial_settings_json,
+      "expected base compiler settings to remain unchanged after expose override"
+    );
+
+    validate(&mut state, Some(&overrides)).expect("validate with override");
+
+    assert_eq!(
+      serde_json::to_value(&state.config.solc.settings).expect("serialize settings"),
+      initial_settings_json,
+      "expected base compiler settings to remain unchanged after validate override"
+    );
+  }
+
+  #[test]
+  fn ast_round_trip() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+
     let default_settings =
       AstOrchestrator::sanitize_settings(None).expect("sanitize default settings");
     let default_language = solc::default_language();
@@ -517,8 +2011,224 @@ contract Target {
       .expect("sanitize config settings");
     solc::ensure_installed(&config.version).expect("ensure solc");
 
-    let mut state = init(None).expect("init ast");
+    let mut state = init(None).expect("init ast");
+    from_source(
+      &mut state,
+      SourceTarget::Text(INSTRUMENTED_CONTRACT.into()),
+      None,
+    )
+    .expect("load source");
+    expose_internal_variables(&mut state, None).expect("expose vars");
+    expose_internal_functions(&mut state, None).expect("expose funcs");
+
+    let ast = source_unit(&state).expect("ast");
+    let mut ast_value = serde_json::to_value(ast).expect("serialize ast");
+    utils::sanitize_ast_value(&mut ast_value);
+
+    let settings_value =
+      serde_json::to_value(&state.config.solc.settings).expect("serialize settings");
+
+    let input = json!({
+      "language": "SolidityAST",
+      "sources": {
+        VIRTUAL_SOURCE_PATH: {
+          "ast": ast_value
+        }
+      },
+      "settings": settings_value
+    });
+
+    let output: CompilerOutput = solc
+      .compile_as(&input)
+      .expect("round-trip compilation attempt");
+
+    assert!(
+      output.errors.is_empty(),
+      "expected solc to compile ast without errors, but got errors: {:?}",
+      output.errors
+    );
+  }
+
+  const FREE_FUNCTION_SOURCE: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+function helper(uint256 value) pure returns (uint256) {
+  return value + 1;
+}
+"#;
+
+  #[test]
+  fn inject_shadow_appends_free_function_when_no_contracts_exist() {
+    if find_default_solc().is_none() {
+      return;
+    }
+
+    let mut state = init(None).expect("init ast");
+    from_source(&mut state, SourceTarget::Text(FREE_FUNCTION_SOURCE.into()), None)
+      .expect("load source");
+
+    inject_shadow(
+      &mut state,
+      FragmentTarget::Text(
+        "function another(uint256 value) pure returns (uint256) { return value + 2; }".into(),
+      ),
+      None,
+    )
+    .expect("inject fragment at source-unit scope");
+
+    let ast = source_unit(&state).expect("ast");
+    assert!(
+      !ast
+        .nodes
+        .iter()
+        .any(|part| matches!(part, SourceUnitPart::ContractDefinition(_))),
+      "expected the source to remain contract-less"
+    );
+    assert!(ast.nodes.iter().any(|part| matches!(part,
+      SourceUnitPart::FunctionDefinition(function) if function.name == "another"
+    )));
+  }
+
+  const BASE_SOURCE: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Base {
+  uint256 internal value;
+}
+"#;
+
+  const DERIVED_SOURCE: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+import "./Base.sol";
+
+contract Derived is Base {
+  function callMe() internal view returns (uint256) {
+    return value;
+  }
+}
+"#;
+
+  #[test]
+  fn from_sources_resolves_imports_across_files() {
+    if find_default_solc().is_none() {
+      return;
+    }
+
+    let mut state = init(None).expect("init ast");
+
+    let mut sources = BTreeMap::new();
+    sources.insert("Base.sol".to_string(), BASE_SOURCE.to_string());
+    sources.insert("Derived.sol".to_string(), DERIVED_SOURCE.to_string());
+
+    from_sources(&mut state, sources, Some("Derived.sol".to_string()), None).expect("load project");
+
+    assert_eq!(state.sources.len(), 2);
+    let ast = source_unit(&state).expect("ast");
+    assert!(ast.nodes.iter().any(|part| matches!(part,
+      SourceUnitPart::ContractDefinition(contract) if contract.name == "Derived"
+    )));
+  }
+
+  #[test]
+  fn from_sources_resolves_primary_from_unqualified_contract_override() {
+    if find_default_solc().is_none() {
+      return;
+    }
+
+    let mut state = init(None).expect("init ast");
+
+    let mut sources = BTreeMap::new();
+    sources.insert("Base.sol".to_string(), BASE_SOURCE.to_string());
+    sources.insert("Derived.sol".to_string(), DERIVED_SOURCE.to_string());
+
+    let mut overrides = AstConfigOptions::default();
+    overrides.instrumented_contract = Some("Derived".to_string());
+
+    from_sources(&mut state, sources, None, Some(&overrides)).expect("load project");
+
+    let ast = source_unit(&state).expect("ast");
+    assert!(ast.nodes.iter().any(|part| matches!(part,
+      SourceUnitPart::ContractDefinition(contract) if contract.name == "Derived"
+    )));
+  }
+
+  #[test]
+  fn exposes_free_functions_via_wrapper_strategy() {
+    if find_default_solc().is_none() {
+      return;
+    }
+
+    let mut state = init(None).expect("init ast");
+    from_source(&mut state, SourceTarget::Text(FREE_FUNCTION_SOURCE.into()), None)
+      .expect("load source");
+
+    let mut overrides = AstConfigOptions::default();
+    overrides.expose_strategy = Some(ExposeStrategy::Wrapper);
+
+    expose_internal_functions(&mut state, Some(&overrides)).expect("expose free function");
+
+    let ast = source_unit(&state).expect("ast");
+    assert!(ast.nodes.iter().any(|part| matches!(part,
+      SourceUnitPart::FunctionDefinition(function) if function.name == "exposed_helper"
+    )));
+  }
+
+  #[test]
+  fn compile_produces_bytecode_and_abi_for_every_source() {
+    if find_default_solc().is_none() {
+      return;
+    }
+
+    let mut state = init(None).expect("init ast");
+    from_source(
+      &mut state,
+      SourceTarget::Text(INSTRUMENTED_CONTRACT.into()),
+      None,
+    )
+    .expect("load source");
+
+    let output = compile(&state, None).expect("compile ast");
+
+    assert!(!output.has_compiler_errors(false, &std::collections::BTreeSet::new()));
+    let artifacts = output
+      .artifacts
+      .get(primary_path(&state))
+      .expect("artifact for primary source");
+    let contract = artifacts.contracts.get("Target").expect("contract artifact");
+    assert!(contract.state().creation_bytecode.is_some());
+  }
+
+  #[test]
+  fn compile_does_not_mutate_state_sources() {
+    if find_default_solc().is_none() {
+      return;
+    }
+
+    let mut state = init(None).expect("init ast");
+    from_source(
+      &mut state,
+      SourceTarget::Text(INSTRUMENTED_CONTRACT.into()),
+      None,
+    )
+    .expect("load source");
+
+    let before = serde_json::to_value(&state.sources).expect("serialize sources");
+    compile(&state, None).expect("compile ast");
+    let after = serde_json::to_value(&state.sources).expect("serialize sources");
+    assert_eq!(before, after);
+  }
+
+  #[test]
+  fn inject_assertions_stitches_assert_into_target_function() {
+    if find_default_solc().is_none() {
+      return;
+    }
 
+    let mut state = init(None).expect("init ast");
     from_source(
       &mut state,
       SourceTarget::Text(INSTRUMENTED_CONTRACT.into()),
@@ -533,14 +2243,13 @@ contract Target {
       resolve_conflict_strategy: None,
     };
 
-    inject_shadow(
+    inject_assertions(
       &mut state,
-      FragmentTarget::Text(
-        "function extra() public view returns (uint256) { return value; }".into(),
-      ),
+      "callMe",
+      &["value == value".to_string()],
       Some(&overrides),
     )
-    .expect("inject fragment");
+    .expect("inject assertions");
 
     let ast = source_unit(&state).expect("ast");
     let contract = ast
@@ -553,178 +2262,140 @@ contract Target {
       .last()
       .expect("contract node");
 
-    assert!(contract.nodes.iter().any(|part| matches!(part,
-      ContractDefinitionPart::FunctionDefinition(function) if function.name == "extra"
-    )));
-
-    fn collect_ids(value: &Value, out: &mut Vec<i64>) {
-      match value {
-        Value::Object(map) => {
-          if let Some(Value::Number(id)) = map.get("id") {
-            if let Some(id) = id.as_i64() {
-              out.push(id);
-            }
-          }
-          map.values().for_each(|child| collect_ids(child, out));
+    let function = contract
+      .nodes
+      .iter()
+      .find_map(|part| match part {
+        ContractDefinitionPart::FunctionDefinition(function) if function.name == "callMe" => {
+          Some(function)
         }
-        Value::Array(items) => items.iter().for_each(|child| collect_ids(child, out)),
-        _ => {}
-      }
-    }
+        _ => None,
+      })
+      .expect("callMe function");
 
-    let mut ids = Vec::new();
-    collect_ids(&serde_json::to_value(ast).expect("serialize ast"), &mut ids);
-    let unique = ids
-      .iter()
-      .copied()
-      .collect::<std::collections::HashSet<_>>();
-    assert_eq!(ids.len(), unique.len());
+    let body = function.body.as_ref().expect("function body");
+    let body_value = serde_json::to_value(body).expect("serialize function body");
+    assert!(body_value.to_string().contains("\"assert\""));
   }
 
   #[test]
-  fn exposes_internal_members() {
+  fn verify_runs_model_checker_and_returns_report() {
     if find_default_solc().is_none() {
       return;
     }
-    let default_settings =
-      AstOrchestrator::sanitize_settings(None).expect("sanitize default settings");
-    let default_language = solc::default_language();
-    let mut config = SolcConfig::new(
-      CompilerLanguage::from(default_language),
-      &default_settings,
-      Option::<&AstConfigOptions>::None,
-    )
-    .expect("config");
-    config.settings = AstOrchestrator::sanitize_settings(Some(config.settings.clone()))
-      .expect("sanitize config settings");
-    solc::ensure_installed(&config.version).expect("ensure solc");
 
     let mut state = init(None).expect("init ast");
-
     from_source(
       &mut state,
       SourceTarget::Text(INSTRUMENTED_CONTRACT.into()),
       None,
     )
     .expect("load source");
+
     let overrides = AstConfigOptions {
       solc: crate::SolcConfigOptions::default(),
       instrumented_contract: Some("Target".into()),
       logging_level: None,
       resolve_conflict_strategy: None,
     };
-    expose_internal_variables(&mut state, Some(&overrides)).expect("expose vars");
-    expose_internal_functions(&mut state, Some(&overrides)).expect("expose funcs");
-
-    let ast = source_unit(&state).expect("ast");
-    let contract = ast
-      .nodes
-      .iter()
-      .filter_map(|part| match part {
-        SourceUnitPart::ContractDefinition(contract) => Some(contract.as_ref()),
-        _ => None,
-      })
-      .last()
-      .expect("contract node");
-
-    let variable_visibility = contract.nodes.iter().find_map(|part| match part {
-      ContractDefinitionPart::VariableDeclaration(variable) => Some(variable.visibility.clone()),
-      _ => None,
-    });
-
-    assert_eq!(variable_visibility, Some(Visibility::Public));
 
-    let function_visibility = contract.nodes.iter().find_map(|part| match part {
-      ContractDefinitionPart::FunctionDefinition(function) => Some(function.visibility.clone()),
-      _ => None,
-    });
+    inject_assertions(
+      &mut state,
+      "callMe",
+      &["value == value".to_string()],
+      Some(&overrides),
+    )
+    .expect("inject assertions");
 
-    assert_eq!(function_visibility, Some(Visibility::Public));
+    let report = verify(&state, Some(&overrides)).expect("verify ast");
+    assert!(report.holds(), "tautological invariant should not be reported as violated");
   }
 
   #[test]
-  fn overrides_do_not_persist_across_calls() {
-    if find_default_solc().is_none() {
+  fn inject_shadow_ast_splices_file_scope_siblings_next_to_the_contract() {
+    let Some(solc) = find_default_solc() else {
       return;
-    }
-
-    let mut state = init(None).expect("init ast");
-    let initial_config = state.config.clone();
-
-    let mut overrides = AstConfigOptions::default();
-    overrides.instrumented_contract = Some("Target".to_string());
-    overrides.solc.settings = Some({
-      let mut settings = CompilerSettingsOptions::default();
-      settings.optimizer = Some(OptimizerSettingsOptions {
-        enabled: Some(true),
-        runs: Some(200),
-        ..Default::default()
-      });
-      settings
-    });
+    };
 
-    let initial_settings_json =
-      serde_json::to_value(&state.config.solc.settings).expect("serialize initial settings");
+    let default_settings =
+      AstOrchestrator::sanitize_settings(None).expect("sanitize default settings");
 
+    let mut state = init(None).expect("init ast");
     from_source(
       &mut state,
       SourceTarget::Text(INSTRUMENTED_CONTRACT.into()),
-      Some(&overrides),
+      None,
     )
-    .expect("load source with override");
+    .expect("load source");
 
-    assert_eq!(
-      state.config.instrumented_contract(),
-      initial_config.instrumented_contract()
-    );
+    let overrides = AstConfigOptions {
+      solc: crate::SolcConfigOptions::default(),
+      instrumented_contract: Some("Target".into()),
+      logging_level: None,
+      resolve_conflict_strategy: None,
+    };
 
-    assert_eq!(
-      serde_json::to_value(&state.config.solc.settings).expect("serialize settings"),
-      initial_settings_json,
-      "expected base compiler settings to remain unchanged after from_source override"
-    );
+    const FRAGMENT_WITH_SIBLINGS: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
 
-    expose_internal_variables(&mut state, Some(&overrides))
-      .expect("apply override without persisting");
+uint256 constant SHADOW_CONSTANT = 42;
 
-    assert_eq!(
-      state.config.instrumented_contract(),
-      initial_config.instrumented_contract()
-    );
+function shadowHelper(uint256 value) pure returns (uint256) {
+  return value + SHADOW_CONSTANT;
+}
 
-    assert_eq!(
-      serde_json::to_value(&state.config.solc.settings).expect("serialize settings"),
-      initial_settings_json,
-      "expected base compiler settings to remain unchanged after expose override"
-    );
+contract Target {
+  uint256 private value;
+  function callMe() internal view returns (uint256) {
+    return value;
+  }
+}
+"#;
+    let fragment_ast = AstOrchestrator::parse_source_unit(
+      FRAGMENT_WITH_SIBLINGS,
+      "Fragment.sol",
+      &solc,
+      &default_settings,
+    )
+    .expect("parse fragment ast");
 
-    validate(&mut state, Some(&overrides)).expect("validate with override");
+    inject_shadow(
+      &mut state,
+      FragmentTarget::Ast(fragment_ast),
+      Some(&overrides),
+    )
+    .expect("inject fragment ast");
 
+    let ast = source_unit(&state).expect("ast");
+    assert!(
+      ast.nodes.iter().any(
+        |part| matches!(part, SourceUnitPart::VariableDeclaration(v) if v.name == "SHADOW_CONSTANT")
+      ),
+      "expected the file-level constant to land at source-unit scope"
+    );
+    assert!(
+      ast.nodes.iter().any(
+        |part| matches!(part, SourceUnitPart::FunctionDefinition(f) if f.name == "shadowHelper")
+      ),
+      "expected the free function to land at source-unit scope"
+    );
     assert_eq!(
-      serde_json::to_value(&state.config.solc.settings).expect("serialize settings"),
-      initial_settings_json,
-      "expected base compiler settings to remain unchanged after validate override"
+      ast
+        .nodes
+        .iter()
+        .filter(|part| matches!(part, SourceUnitPart::ContractDefinition(_)))
+        .count(),
+      1,
+      "the fragment's ContractDefinition should have been merged, not re-appended"
     );
   }
 
   #[test]
-  fn ast_round_trip() {
-    let Some(solc) = find_default_solc() else {
+  fn merge_contracts_appends_a_standalone_contract_with_unique_ids() {
+    if find_default_solc().is_none() {
       return;
-    };
-
-    let default_settings =
-      AstOrchestrator::sanitize_settings(None).expect("sanitize default settings");
-    let default_language = solc::default_language();
-    let mut config = SolcConfig::new(
-      CompilerLanguage::from(default_language),
-      &default_settings,
-      Option::<&AstConfigOptions>::None,
-    )
-    .expect("config");
-    config.settings = AstOrchestrator::sanitize_settings(Some(config.settings.clone()))
-      .expect("sanitize config settings");
-    solc::ensure_installed(&config.version).expect("ensure solc");
+    }
 
     let mut state = init(None).expect("init ast");
     from_source(
@@ -733,34 +2404,86 @@ contract Target {
       None,
     )
     .expect("load source");
-    expose_internal_variables(&mut state, None).expect("expose vars");
-    expose_internal_functions(&mut state, None).expect("expose funcs");
 
-    let ast = source_unit(&state).expect("ast");
-    let mut ast_value = serde_json::to_value(ast).expect("serialize ast");
-    utils::sanitize_ast_value(&mut ast_value);
+    merge_contracts(
+      &mut state,
+      vec![SourceTarget::Text(
+        "contract Injected { uint256 public x; }".into(),
+      )],
+      None,
+      None,
+    )
+    .expect("merge contract");
 
-    let settings_value =
-      serde_json::to_value(&state.config.solc.settings).expect("serialize settings");
+    let ast = source_unit(&state).expect("ast");
+    let contracts: Vec<&ContractDefinition> = ast
+      .nodes
+      .iter()
+      .filter_map(|part| match part {
+        SourceUnitPart::ContractDefinition(contract) => Some(contract.as_ref()),
+        _ => None,
+      })
+      .collect();
+    assert!(contracts.iter().any(|contract| contract.name == "Target"));
+    let injected = contracts
+      .iter()
+      .find(|contract| contract.name == "Injected")
+      .expect("merged contract present");
+
+    let value = serde_json::to_value(ast).expect("serialize ast");
+    let exported = value
+      .get("exportedSymbols")
+      .and_then(Value::as_object)
+      .expect("exportedSymbols object");
+    assert_eq!(
+      exported.get("Injected").and_then(Value::as_array).and_then(|ids| ids.first()),
+      Some(&Value::Number((injected.id as i64).into()))
+    );
 
-    let input = json!({
-      "language": "SolidityAST",
-      "sources": {
-        VIRTUAL_SOURCE_PATH: {
-          "ast": ast_value
+    fn collect_ids(value: &Value, out: &mut Vec<i64>) {
+      match value {
+        Value::Object(map) => {
+          if let Some(Value::Number(id)) = map.get("id") {
+            if let Some(id) = id.as_i64() {
+              out.push(id);
+            }
+          }
+          map.values().for_each(|child| collect_ids(child, out));
         }
-      },
-      "settings": settings_value
-    });
+        Value::Array(items) => items.iter().for_each(|child| collect_ids(child, out)),
+        _ => {}
+      }
+    }
+    let mut ids = Vec::new();
+    collect_ids(&value, &mut ids);
+    let unique = ids
+      .iter()
+      .copied()
+      .collect::<std::collections::HashSet<_>>();
+    assert_eq!(ids.len(), unique.len(), "merged ast must have unique ids");
+  }
 
-    let output: CompilerOutput = solc
-      .compile_as(&input)
-      .expect("round-trip compilation attempt");
+  #[test]
+  fn merge_contracts_rejects_a_name_already_present() {
+    if find_default_solc().is_none() {
+      return;
+    }
 
-    assert!(
-      output.errors.is_empty(),
-      "expected solc to compile ast without errors, but got errors: {:?}",
-      output.errors
-    );
+    let mut state = init(None).expect("init ast");
+    from_source(
+      &mut state,
+      SourceTarget::Text(INSTRUMENTED_CONTRACT.into()),
+      None,
+    )
+    .expect("load source");
+
+    let err = merge_contracts(
+      &mut state,
+      vec![SourceTarget::Text("contract Target {}".into())],
+      None,
+      None,
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("Target"));
   }
 }
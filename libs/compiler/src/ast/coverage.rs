@@ -0,0 +1,272 @@
+use foundry_compilers::artifacts::ast::{
+  ContractDefinitionPart, FunctionDefinition, SourceUnit, SourceUnitPart,
+};
+use foundry_compilers::artifacts::Settings;
+use foundry_compilers::solc::Solc;
+use serde_json::Value;
+
+use super::printer::{indent, print_expression, print_statement};
+use super::{orchestrator::AstOrchestrator, utils};
+use crate::internal::errors::{map_err_with_context, Error, Result};
+
+/// Solidity source for the coverage ledger and its reader, stitched into the instrumented
+/// contract once per call to `instrument_coverage`. Block ids written by `instrument_contract`
+/// are keys into this same `__cov` mapping.
+pub fn storage_fragment_source() -> String {
+  "mapping(uint256 => uint256) __cov;\n\n\
+   function __covCount(uint256 blockId) public view returns (uint256) {\n  \
+   return __cov[blockId];\n}"
+    .to_string()
+}
+
+/// Allocates block ids in the order basic blocks are discovered, shared across every function in
+/// a contract so a single `__cov` mapping can address all of them without collisions.
+#[derive(Default)]
+struct BlockCounter(usize);
+
+impl BlockCounter {
+  fn next(&mut self) -> usize {
+    let id = self.0;
+    self.0 += 1;
+    id
+  }
+}
+
+/// Instruments every function body in the contract at `contract_idx` with coverage counters.
+/// Returns the number of basic blocks instrumented (0 if the contract has no instrumentable
+/// function bodies, in which case the caller should skip stitching in the `__cov` storage).
+/// `next_id` is threaded in (and advanced) by the caller rather than computed from `unit` alone, so
+/// ids stay unique across every source in a multi-unit project, not just within this one.
+pub fn instrument_contract(
+  unit: &mut SourceUnit,
+  contract_idx: usize,
+  solc: &Solc,
+  settings: &Settings,
+  next_id: &mut i64,
+) -> Result<usize> {
+  let mut counter = BlockCounter::default();
+
+  let SourceUnitPart::ContractDefinition(contract) = unit
+    .nodes
+    .get_mut(contract_idx)
+    .ok_or_else(|| Error::new("Invalid contract index"))?
+  else {
+    return Err(Error::new("Target index is not a contract definition"));
+  };
+
+  for member in &mut contract.nodes {
+    let ContractDefinitionPart::FunctionDefinition(function) = member else {
+      continue;
+    };
+    instrument_function(function, solc, settings, &mut counter, next_id)?;
+  }
+
+  Ok(counter.0)
+}
+
+/// Re-renders `function`'s body through the statement/expression printer, splicing a
+/// `__cov[blockId] += 1;` counter at the head of every basic block, then reparses the rendered
+/// text through solc and swaps in the result. Going through source text (rather than mutating the
+/// typed AST nodes in place) sidesteps constructing new `Block`/`Statement` nodes by hand, and
+/// means the usual fragment id-remapping path still applies to every node this introduces.
+fn instrument_function(
+  function: &mut FunctionDefinition,
+  solc: &Solc,
+  settings: &Settings,
+  counter: &mut BlockCounter,
+  next_id: &mut i64,
+) -> Result<()> {
+  let Some(body) = function.body.as_ref() else {
+    return Ok(());
+  };
+
+  let body_value = map_err_with_context(
+    serde_json::to_value(body),
+    "Failed to inspect function body for coverage instrumentation",
+  )?;
+  let rendered_body = render_instrumented_block(&body_value, counter)?;
+
+  let fragment_source = format!("function __CovWrapper() internal {rendered_body}");
+  let fragment_contract = map_err_with_context(
+    AstOrchestrator::parse_fragment_contract(&fragment_source, solc, settings),
+    "Failed to parse coverage-instrumented function body",
+  )?;
+
+  let new_body = fragment_contract
+    .nodes
+    .iter()
+    .find_map(|part| match part {
+      ContractDefinitionPart::FunctionDefinition(def) => def.body.clone(),
+      _ => None,
+    })
+    .ok_or_else(|| Error::new("Coverage instrumentation produced no function body"))?;
+
+  function.body = Some(utils::clone_with_new_ids(&new_body, next_id)?);
+  Ok(())
+}
+
+fn render_instrumented_block(value: &Value, counter: &mut BlockCounter) -> Result<String> {
+  render_statement_list(value, true, counter)
+}
+
+/// Renders `value`'s `statements` array, always inserting a counter at the head when
+/// `needs_leading_counter` (a function/if-branch/loop body entry), and again immediately before
+/// whatever follows a `return`, `revert`, or `require(...)` site - per the basic-block boundaries
+/// this instrumenter splits on. A trailing boundary with nothing left to instrument is dropped
+/// rather than emitting a counter that can never increment.
+fn render_statement_list(
+  value: &Value,
+  needs_leading_counter: bool,
+  counter: &mut BlockCounter,
+) -> Result<String> {
+  let statements = value
+    .get("statements")
+    .and_then(Value::as_array)
+    .cloned()
+    .unwrap_or_default();
+
+  let mut lines = Vec::with_capacity(statements.len() + 1);
+  if needs_leading_counter {
+    lines.push(counter_statement_text(counter));
+  }
+
+  let mut pending_boundary = false;
+  for statement in &statements {
+    if pending_boundary {
+      lines.push(counter_statement_text(counter));
+      pending_boundary = false;
+    }
+    lines.push(render_statement(statement, counter)?);
+    if is_block_boundary_trigger(statement) {
+      pending_boundary = true;
+    }
+  }
+
+  if lines.is_empty() {
+    return Ok("{}".to_string());
+  }
+  let body = lines
+    .iter()
+    .map(|line| indent(line))
+    .collect::<Vec<_>>()
+    .join("\n");
+  Ok(format!("{{\n{body}\n}}"))
+}
+
+fn render_statement(value: &Value, counter: &mut BlockCounter) -> Result<String> {
+  match value.get("nodeType").and_then(Value::as_str) {
+    Some("Block") => render_statement_list(value, true, counter),
+    Some("UncheckedBlock") => Ok(format!(
+      "unchecked {}",
+      render_statement_list(value, true, counter)?
+    )),
+    Some("IfStatement") => render_if_statement(value, counter),
+    Some("WhileStatement") => {
+      let condition = print_expression(field(value, "condition")?)?;
+      let body = render_block_or_statement(field(value, "body")?, counter)?;
+      Ok(format!("while ({condition}) {body}"))
+    }
+    Some("DoWhileStatement") => {
+      let condition = print_expression(field(value, "condition")?)?;
+      let body = render_statement_list(field(value, "body")?, true, counter)?;
+      Ok(format!("do {body} while ({condition});"))
+    }
+    Some("ForStatement") => render_for_statement(value, counter),
+    // Everything else (Return, ExpressionStatement, VariableDeclarationStatement, EmitStatement,
+    // RevertStatement, Break, Continue, PlaceholderStatement, ...) has no nested block of its own
+    // to instrument, so it's re-emitted verbatim. Node kinds the printer can't render (inline
+    // assembly, try/catch) surface as the same error it already raises for `to_source`.
+    _ => print_statement(value),
+  }
+}
+
+fn render_if_statement(value: &Value, counter: &mut BlockCounter) -> Result<String> {
+  let condition = print_expression(field(value, "condition")?)?;
+  let true_body = render_block_or_statement(field(value, "trueBody")?, counter)?;
+
+  match value.get("falseBody").filter(|v| !v.is_null()) {
+    Some(false_body) => {
+      let false_rendered = render_block_or_statement(false_body, counter)?;
+      Ok(format!(
+        "if ({condition}) {true_body} else {false_rendered}"
+      ))
+    }
+    None => Ok(format!("if ({condition}) {true_body}")),
+  }
+}
+
+fn render_for_statement(value: &Value, counter: &mut BlockCounter) -> Result<String> {
+  let init = match value.get("initializationExpression").filter(|v| !v.is_null()) {
+    Some(init) => print_statement(init)?,
+    None => ";".to_string(),
+  };
+  let condition = match value.get("condition").filter(|v| !v.is_null()) {
+    Some(condition) => print_expression(condition)?,
+    None => String::new(),
+  };
+  let loop_expression = match value.get("loopExpression").filter(|v| !v.is_null()) {
+    Some(loop_statement) => match loop_statement.get("expression").filter(|v| !v.is_null()) {
+      Some(expression) => print_expression(expression)?,
+      None => String::new(),
+    },
+    None => String::new(),
+  };
+  let body = render_block_or_statement(field(value, "body")?, counter)?;
+
+  Ok(format!(
+    "for ({init} {condition}; {loop_expression}) {body}"
+  ))
+}
+
+/// `IfStatement`/loop bodies are `BlockOrStatement`: either a braced `Block` or a single bare
+/// statement. Either way it's rendered as its own basic block, with a leading counter of its own.
+fn render_block_or_statement(value: &Value, counter: &mut BlockCounter) -> Result<String> {
+  match value.get("nodeType").and_then(Value::as_str) {
+    Some("Block") => render_statement_list(value, true, counter),
+    _ => {
+      let statement = render_statement(value, counter)?;
+      let head = counter_statement_text(counter);
+      Ok(format!("{{\n{}\n{}\n}}", indent(&head), indent(&statement)))
+    }
+  }
+}
+
+fn is_block_boundary_trigger(statement: &Value) -> bool {
+  match statement.get("nodeType").and_then(Value::as_str) {
+    Some("Return") | Some("RevertStatement") => true,
+    Some("ExpressionStatement") => is_require_call(statement.get("expression")),
+    _ => false,
+  }
+}
+
+fn is_require_call(expression: Option<&Value>) -> bool {
+  let Some(expression) = expression else {
+    return false;
+  };
+  if expression.get("nodeType").and_then(Value::as_str) != Some("FunctionCall") {
+    return false;
+  }
+  expression
+    .get("expression")
+    .map(|callee| {
+      callee.get("nodeType").and_then(Value::as_str) == Some("Identifier")
+        && callee.get("name").and_then(Value::as_str) == Some("require")
+    })
+    .unwrap_or(false)
+}
+
+fn counter_statement_text(counter: &mut BlockCounter) -> String {
+  format!("__cov[{}] += 1;", counter.next())
+}
+
+fn field<'a>(value: &'a Value, key: &str) -> Result<&'a Value> {
+  value.get(key).ok_or_else(|| {
+    let node_type = value
+      .get("nodeType")
+      .and_then(Value::as_str)
+      .unwrap_or("unknown");
+    Error::new(format!(
+      "Coverage instrumentation expected a \"{key}\" field on a {node_type} node"
+    ))
+  })
+}
@@ -0,0 +1,98 @@
+use foundry_compilers::artifacts::ast::{ContractDefinition, SourceUnit, SourceUnitPart};
+
+use super::error::AstError;
+use super::stitcher::{describe_part, StitchOutcome, StitchReport};
+
+/// Renders the contract at `contract_idx` in `unit` as a Graphviz DOT graph: one node for the
+/// contract, one child node per member (labeled with its kind and name), and an edge from the
+/// contract to each member colored by what the most recent
+/// [`super::stitcher::stitch_fragment_nodes_into_contract`] call (`report`) did with it - green for
+/// a freshly appended fragment member, orange for one that replaced an existing member, gray for a
+/// duplicate the fragment left alongside the original. Members a stitch never touched (or the
+/// whole graph, when `report` is `None`) get a plain black edge instead. Inspired by solang's
+/// `dotgraphviz` debug pass, scoped down to what this crate's stitcher needs it for: a fast way to
+/// see why a fragment produced unexpected duplicate or missing members.
+pub fn render_contract(
+  unit: &SourceUnit,
+  contract_idx: usize,
+  report: Option<&StitchReport>,
+) -> Result<String, AstError> {
+  let SourceUnitPart::ContractDefinition(contract) = unit.nodes.get(contract_idx).ok_or_else(
+    || AstError::InvalidContractStructure("Invalid contract index".to_string()),
+  )?
+  else {
+    return Err(AstError::InvalidContractStructure(
+      "Target index is not a contract".to_string(),
+    ));
+  };
+
+  Ok(render_contract_definition(contract, report))
+}
+
+fn render_contract_definition(
+  contract: &ContractDefinition,
+  report: Option<&StitchReport>,
+) -> String {
+  let contract_node = node_id("contract", &format!("{}_{}", contract.name, contract.id));
+
+  let mut lines = vec!["digraph AST {".to_string(), "  rankdir=LR;".to_string()];
+  lines.push(format!(
+    "  {contract_node} [shape=box, style=filled, fillcolor=lightblue, label=\"{}\"];",
+    escape(&contract.name)
+  ));
+
+  for (idx, part) in contract.nodes.iter().enumerate() {
+    let (name, kind) = describe_part(part);
+    let member_node = node_id(kind, &format!("{name}_{idx}"));
+    lines.push(format!(
+      "  {member_node} [shape=ellipse, label=\"{}\"];",
+      escape(&format!("{kind}: {name}"))
+    ));
+
+    let (color, edge_label) = edge_style(report, &name, kind);
+    lines.push(format!(
+      "  {contract_node} -> {member_node} [color={color}, label=\"{edge_label}\"];"
+    ));
+  }
+
+  lines.push("}".to_string());
+  lines.join("\n")
+}
+
+/// The edge color/label for one member, based on what `report` says happened to the first entry
+/// matching its name and kind tag - `None` (no stitch has run yet, or this member predates it)
+/// falls back to a plain untouched-looking edge.
+fn edge_style(
+  report: Option<&StitchReport>,
+  name: &str,
+  kind: &str,
+) -> (&'static str, &'static str) {
+  let Some(report) = report else {
+    return ("black", "");
+  };
+  let entry = report
+    .entries
+    .iter()
+    .find(|entry| entry.name == name && entry.kind == kind);
+  let Some(entry) = entry else {
+    return ("black", "");
+  };
+  match entry.outcome {
+    StitchOutcome::Appended => ("green", "appended"),
+    StitchOutcome::Replaced { .. } => ("orange", "replaced"),
+    StitchOutcome::Duplicated => ("gray", "duplicated"),
+    StitchOutcome::Skipped => ("black", "skipped"),
+  }
+}
+
+fn node_id(prefix: &str, name: &str) -> String {
+  let sanitized: String = name
+    .chars()
+    .map(|c| if c.is_alphanumeric() { c } else { '_' })
+    .collect();
+  format!("{prefix}_{sanitized}")
+}
+
+fn escape(value: &str) -> String {
+  value.replace('\\', "\\\\").replace('"', "\\\"")
+}
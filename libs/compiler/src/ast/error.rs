@@ -9,6 +9,11 @@ pub enum AstError {
   InvalidContractStructure(String),
   JsonError(String),
   CompilerError(String),
+  SelectorCollision {
+    selector: [u8; 4],
+    first: String,
+    second: String,
+  },
 }
 
 impl std::fmt::Display for AstError {
@@ -20,6 +25,15 @@ impl std::fmt::Display for AstError {
       Self::InvalidContractStructure(msg) => write!(f, "Invalid contract structure: {}", msg),
       Self::JsonError(msg) => write!(f, "JSON error: {}", msg),
       Self::CompilerError(msg) => write!(f, "Compiler error: {}", msg),
+      Self::SelectorCollision {
+        selector,
+        first,
+        second,
+      } => write!(
+        f,
+        "Selector collision: {first} and {second} both resolve to 0x{}",
+        selector.iter().map(|byte| format!("{byte:02x}")).collect::<String>()
+      ),
     }
   }
 }
@@ -0,0 +1,480 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use foundry_compilers::artifacts::remappings::Remapping;
+
+use super::error::AstError;
+
+/// An `import {A as B, C} from "target"` (or un-aliased `import "target"`) directive found while
+/// scanning a file. `path` is the resolved source key the import refers to; `aliases` holds every
+/// `(local, original)` rename the importing file declared, which only exists in that file's scope
+/// and must be folded back to `original` before its source is concatenated into the flattened
+/// unit.
+struct Import {
+  path: String,
+  aliases: Vec<(String, String)>,
+}
+
+/// Produces a single self-contained Solidity source for `entry` by inlining every file it
+/// transitively imports, in dependency-first order, so `parser::parse_source_ast` - which only
+/// ever hands solc one file - can resolve sources that `import` one another. Import resolution
+/// (relative paths, then `remappings`) mirrors `compiler::flatten`. Errors if `entry` is unknown,
+/// an import can't be resolved, or the import graph has a cycle - there's no well-defined "first"
+/// file to flatten into in that case.
+pub fn flatten_source(
+  entry: &str,
+  sources: &BTreeMap<String, String>,
+  remappings: &[Remapping],
+) -> Result<String, AstError> {
+  if !sources.contains_key(entry) {
+    return Err(AstError::ParseFailed(format!(
+      "Cannot flatten: unknown entry source \"{entry}\"."
+    )));
+  }
+
+  let mut order = Vec::new();
+  let mut emitted = BTreeSet::new();
+  let mut visiting = Vec::new();
+  visit(
+    entry,
+    sources,
+    remappings,
+    &mut emitted,
+    &mut visiting,
+    &mut order,
+  )?;
+
+  let mut spdx: Option<String> = None;
+  let mut pragmas = Vec::new();
+  let mut seen_pragmas = BTreeSet::new();
+  let mut body = String::new();
+
+  for path in &order {
+    let contents = sources
+      .get(path)
+      .expect("flatten order only ever contains keys from `sources`");
+    let imports = extract_imports(path, contents, sources, remappings)?;
+    let stripped = strip_directives(contents, &mut spdx, &mut pragmas, &mut seen_pragmas);
+    let rewritten = rewrite_aliases(&stripped, &imports);
+
+    if !body.is_empty() {
+      body.push('\n');
+    }
+    body.push_str(&format!("\n// File: {path}\n"));
+    body.push_str(rewritten.trim_end());
+    body.push('\n');
+  }
+
+  let mut output = format!(
+    "// SPDX-License-Identifier: {}\n",
+    spdx.unwrap_or_else(|| "UNLICENSED".to_string())
+  );
+  for pragma in &pragmas {
+    output.push_str(pragma);
+    output.push('\n');
+  }
+  output.push_str(&body);
+
+  Ok(output)
+}
+
+/// Post-order DFS over the import graph rooted at `path`: every import is visited (and appended
+/// to `order`) before `path` itself, so concatenating `order` in sequence always places a
+/// dependency ahead of its dependents.
+fn visit(
+  path: &str,
+  sources: &BTreeMap<String, String>,
+  remappings: &[Remapping],
+  emitted: &mut BTreeSet<String>,
+  visiting: &mut Vec<String>,
+  order: &mut Vec<String>,
+) -> Result<(), AstError> {
+  if emitted.contains(path) {
+    return Ok(());
+  }
+  if visiting.iter().any(|entry| entry == path) {
+    return Err(AstError::ParseFailed(format!(
+      "Cannot flatten: import cycle detected involving \"{path}\"."
+    )));
+  }
+
+  let contents = sources.get(path).ok_or_else(|| {
+    AstError::ParseFailed(format!("Cannot flatten: unresolved source \"{path}\"."))
+  })?;
+
+  visiting.push(path.to_string());
+  for import in extract_imports(path, contents, sources, remappings)? {
+    visit(&import.path, sources, remappings, emitted, visiting, order)?;
+  }
+  visiting.pop();
+
+  emitted.insert(path.to_string());
+  order.push(path.to_string());
+  Ok(())
+}
+
+/// Scans `contents` for every `import` directive, resolves each one's target against `sources`
+/// (relative paths first, then `remappings`), and collects the `as`-aliases declared by named
+/// imports (`import {Foo as Bar, Baz} from "./x.sol"`). Un-aliased imports (plain `import
+/// "./x.sol";` or `import {Baz} from "./x.sol"`) contribute no alias pairs.
+fn extract_imports(
+  importing_path: &str,
+  contents: &str,
+  sources: &BTreeMap<String, String>,
+  remappings: &[Remapping],
+) -> Result<Vec<Import>, AstError> {
+  let mut imports = Vec::new();
+  let mut lines = contents.lines().peekable();
+
+  while let Some(line) = lines.next() {
+    if !is_import_start(line.trim_start()) {
+      continue;
+    }
+
+    let mut joined = line.to_string();
+    while !joined.contains(';') {
+      match lines.next() {
+        Some(next) => {
+          joined.push('\n');
+          joined.push_str(next);
+        }
+        None => break,
+      }
+    }
+
+    let target = quoted_import_target(&joined).ok_or_else(|| {
+      AstError::ParseFailed(format!(
+        "Cannot flatten \"{importing_path}\": malformed import directive \"{}\".",
+        joined.trim()
+      ))
+    })?;
+
+    let path = resolve_import(importing_path, &target, sources, remappings).ok_or_else(|| {
+      AstError::ParseFailed(format!(
+        "Cannot flatten \"{importing_path}\": unresolved import \"{target}\"."
+      ))
+    })?;
+
+    imports.push(Import {
+      path,
+      aliases: parse_aliases(&joined),
+    });
+  }
+
+  Ok(imports)
+}
+
+fn quoted_import_target(directive: &str) -> Option<String> {
+  let after_keyword = directive.strip_prefix("import")?;
+  let quote_start = after_keyword.find(['"', '\''])?;
+  let quote_char = after_keyword.as_bytes()[quote_start] as char;
+  let rest = &after_keyword[quote_start + 1..];
+  let quote_end = rest.find(quote_char)?;
+  Some(rest[..quote_end].to_string())
+}
+
+/// Extracts every `Name as Alias` pair from a named-import directive's brace list. Entries with no
+/// `as` clause (including `import * as X from "..."`, a namespace alias rather than a symbol
+/// rename) are skipped - they don't need any identifier rewritten in the importing file's body.
+fn parse_aliases(directive: &str) -> Vec<(String, String)> {
+  let Some(brace_start) = directive.find('{') else {
+    return Vec::new();
+  };
+  let Some(brace_end) = directive[brace_start..].find('}') else {
+    return Vec::new();
+  };
+  let list = &directive[brace_start + 1..brace_start + brace_end];
+
+  list
+    .split(',')
+    .filter_map(|entry| {
+      let mut parts = entry.split_whitespace();
+      let original = parts.next()?;
+      match (parts.next(), parts.next()) {
+        (Some("as"), Some(alias)) => Some((alias.to_string(), original.to_string())),
+        _ => None,
+      }
+    })
+    .collect()
+}
+
+fn is_import_start(trimmed: &str) -> bool {
+  trimmed
+    .strip_prefix("import")
+    .map(|rest| {
+      rest.is_empty()
+        || rest.starts_with(|c: char| c.is_whitespace() || matches!(c, '{' | '"' | '\'' | '*'))
+    })
+    .unwrap_or(false)
+}
+
+/// Resolves an `import` target written in `importing_path` to a key in `sources`, trying a
+/// relative-path resolution first and falling back to the configured remappings. Returns `None`
+/// when no known source matches.
+fn resolve_import(
+  importing_path: &str,
+  import: &str,
+  sources: &BTreeMap<String, String>,
+  remappings: &[Remapping],
+) -> Option<String> {
+  if import.starts_with('.') {
+    let base = Path::new(importing_path).parent().unwrap_or(Path::new(""));
+    let joined = normalise_path(&base.join(import));
+    return sources
+      .keys()
+      .find(|candidate| normalise_path(Path::new(candidate)) == joined)
+      .cloned();
+  }
+
+  if sources.contains_key(import) {
+    return Some(import.to_string());
+  }
+
+  let mut best: Option<(&Remapping, &str)> = None;
+  for remapping in remappings {
+    if let Some(suffix) = import.strip_prefix(remapping.name.as_str()) {
+      if best
+        .map(|(current, _)| remapping.name.len() > current.name.len())
+        .unwrap_or(true)
+      {
+        best = Some((remapping, suffix));
+      }
+    }
+  }
+  let (remapping, suffix) = best?;
+  let candidate = normalise_path(&PathBuf::from(&remapping.path).join(suffix.trim_start_matches('/')));
+  sources
+    .keys()
+    .find(|key| normalise_path(Path::new(key)) == candidate)
+    .cloned()
+}
+
+/// Collapses `.`/`..` segments without touching the filesystem, so relative imports compare equal
+/// to the canonical keys already used throughout `sources`.
+fn normalise_path(path: &Path) -> String {
+  let mut stack: Vec<std::ffi::OsString> = Vec::new();
+  for component in path.components() {
+    match component {
+      std::path::Component::CurDir => {}
+      std::path::Component::ParentDir => {
+        stack.pop();
+      }
+      other => stack.push(other.as_os_str().to_os_string()),
+    }
+  }
+  PathBuf::from_iter(stack).to_string_lossy().replace('\\', "/")
+}
+
+/// Drops `// SPDX-License-Identifier`, `pragma`, and `import` lines from `contents`, folding the
+/// first SPDX identifier seen across the whole flatten into `spdx` and each distinct pragma (kept
+/// in first-seen order) into `pragmas`/`seen_pragmas`. Everything else passes through unchanged.
+fn strip_directives(
+  contents: &str,
+  spdx: &mut Option<String>,
+  pragmas: &mut Vec<String>,
+  seen_pragmas: &mut BTreeSet<String>,
+) -> String {
+  let mut output = String::with_capacity(contents.len());
+  let mut lines = contents.lines().peekable();
+
+  while let Some(line) = lines.next() {
+    let trimmed = line.trim_start();
+
+    if let Some(rest) = trimmed.strip_prefix("// SPDX-License-Identifier:") {
+      if spdx.is_none() {
+        *spdx = Some(rest.trim().to_string());
+      }
+      continue;
+    }
+
+    if trimmed.starts_with("pragma ") {
+      let pragma = trimmed.trim_end().to_string();
+      if seen_pragmas.insert(pragma.clone()) {
+        pragmas.push(pragma);
+      }
+      continue;
+    }
+
+    if is_import_start(trimmed) {
+      let mut joined = line.to_string();
+      while !joined.contains(';') {
+        match lines.next() {
+          Some(next) => {
+            joined.push('\n');
+            joined.push_str(next);
+          }
+          None => break,
+        }
+      }
+      continue;
+    }
+
+    output.push_str(line);
+    output.push('\n');
+  }
+
+  output
+}
+
+/// Rewrites every whole-word occurrence of each import's local alias back to the name it aliases,
+/// skipping matches inside string/char literals and `//`/`/* */` comments so an alias that happens
+/// to also appear in a doc comment or log string isn't touched.
+fn rewrite_aliases(body: &str, imports: &[Import]) -> String {
+  let mut result = body.to_string();
+  for import in imports {
+    for (alias, original) in &import.aliases {
+      result = replace_identifier(&result, alias, original);
+    }
+  }
+  result
+}
+
+/// Single-pass token scan that replaces whole-word occurrences of `from` with `to`, walking past
+/// string/char literals and comments verbatim rather than matching inside them.
+fn replace_identifier(source: &str, from: &str, to: &str) -> String {
+  let bytes = source.as_bytes();
+  let mut output = String::with_capacity(source.len());
+  let mut i = 0;
+
+  while i < bytes.len() {
+    let rest = &source[i..];
+
+    if rest.starts_with("//") {
+      let end = rest.find('\n').unwrap_or(rest.len());
+      output.push_str(&rest[..end]);
+      i += end;
+      continue;
+    }
+    if rest.starts_with("/*") {
+      let end = rest.find("*/").map(|idx| idx + 2).unwrap_or(rest.len());
+      output.push_str(&rest[..end]);
+      i += end;
+      continue;
+    }
+    if rest.starts_with('"') || rest.starts_with('\'') {
+      let quote = bytes[i] as char;
+      let mut end = 1;
+      let literal_rest = &rest[1..];
+      let mut chars = literal_rest.char_indices();
+      while let Some((idx, ch)) = chars.next() {
+        if ch == '\\' {
+          chars.next();
+          continue;
+        }
+        if ch == quote {
+          end = idx + 1 + 1;
+          break;
+        }
+        end = idx + 1 + ch.len_utf8();
+      }
+      output.push_str(&rest[..end]);
+      i += end;
+      continue;
+    }
+
+    let ch = rest.chars().next().unwrap();
+    if is_identifier_start(ch) {
+      let ident_len = rest
+        .char_indices()
+        .take_while(|(_, c)| is_identifier_continue(*c))
+        .last()
+        .map(|(idx, c)| idx + c.len_utf8())
+        .unwrap_or(0);
+      let ident = &rest[..ident_len];
+      if ident == from {
+        output.push_str(to);
+      } else {
+        output.push_str(ident);
+      }
+      i += ident_len;
+      continue;
+    }
+
+    output.push(ch);
+    i += ch.len_utf8();
+  }
+
+  output
+}
+
+fn is_identifier_start(ch: char) -> bool {
+  ch.is_ascii_alphabetic() || ch == '_' || ch == '$'
+}
+
+fn is_identifier_continue(ch: char) -> bool {
+  ch.is_ascii_alphanumeric() || ch == '_' || ch == '$'
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sources(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+    entries
+      .iter()
+      .map(|(path, contents)| (path.to_string(), contents.to_string()))
+      .collect()
+  }
+
+  #[test]
+  fn flattens_single_import_without_aliases() {
+    let sources = sources(&[
+      (
+        "A.sol",
+        "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.0;\nimport \"./B.sol\";\n\ncontract A is B {}\n",
+      ),
+      (
+        "B.sol",
+        "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.0;\n\ncontract B {}\n",
+      ),
+    ]);
+
+    let flattened = flatten_source("A.sol", &sources, &[]).expect("flatten");
+    assert!(flattened.contains("contract B {}"));
+    assert!(flattened.contains("contract A is B {}"));
+    assert_eq!(flattened.matches("SPDX-License-Identifier").count(), 1);
+    let b_pos = flattened.find("contract B").unwrap();
+    let a_pos = flattened.find("contract A").unwrap();
+    assert!(b_pos < a_pos, "dependency must be emitted before dependent");
+  }
+
+  #[test]
+  fn rewrites_aliased_imports_back_to_original_name() {
+    let sources = sources(&[
+      (
+        "A.sol",
+        "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.0;\nimport {Foo as Bar} from \"./B.sol\";\n\ncontract A is Bar {\n  // Bar here refers to Foo\n  string constant NOTE = \"still Bar in a string\";\n}\n",
+      ),
+      (
+        "B.sol",
+        "// SPDX-License-Identifier: MIT\npragma solidity ^0.8.0;\n\ncontract Foo {}\n",
+      ),
+    ]);
+
+    let flattened = flatten_source("A.sol", &sources, &[]).expect("flatten");
+    assert!(flattened.contains("contract A is Foo {"));
+    assert!(!flattened.contains("is Bar"));
+    assert!(
+      flattened.contains("still Bar in a string"),
+      "alias occurrences inside string literals must not be rewritten"
+    );
+  }
+
+  #[test]
+  fn detects_import_cycles() {
+    let sources = sources(&[
+      ("A.sol", "import \"./B.sol\";\ncontract A {}\n"),
+      ("B.sol", "import \"./A.sol\";\ncontract B {}\n"),
+    ]);
+
+    let err = flatten_source("A.sol", &sources, &[]).unwrap_err();
+    assert!(matches!(err, AstError::ParseFailed(_)));
+  }
+
+  #[test]
+  fn errors_on_unknown_entry() {
+    let sources = sources(&[("A.sol", "contract A {}\n")]);
+    let err = flatten_source("Missing.sol", &sources, &[]).unwrap_err();
+    assert!(matches!(err, AstError::ParseFailed(_)));
+  }
+}
@@ -9,7 +9,7 @@ use crate::internal::errors::{map_err_with_context, Error, Result};
 use serde_json::Value;
 use std::path::PathBuf;
 
-use super::{orchestrator::AstOrchestrator, parser, stitcher, utils};
+use super::{orchestrator::AstOrchestrator, parser, stitcher, utils, visit::AstVisitMut};
 
 #[derive(Debug)]
 enum FunctionSelectorKind {
@@ -31,6 +31,7 @@ pub fn inject_edges(
   after_snippets: &[String],
   solc: &Solc,
   settings: &Settings,
+  source: Option<&str>,
 ) -> Result<()> {
   if before_snippets.is_empty() && after_snippets.is_empty() {
     return Err(Error::new(
@@ -49,14 +50,14 @@ pub fn inject_edges(
   };
   let selector_kind = parse_selector(selector, solc, settings)?;
 
-  let function = resolve_function_mut(contract, &selector_kind)?;
+  let function = resolve_function_mut(contract, &selector_kind, source)?;
 
   let body = function
     .body
     .as_mut()
     .ok_or_else(|| Error::new("Cannot instrument a function without an implementation"))?;
 
-  ensure_no_inline_assembly(body)?;
+  ensure_no_inline_assembly(body, source)?;
 
   let before_statements = parse_statements(before_snippets, solc, settings)?;
   let after_statements = parse_statements(after_snippets, solc, settings)?;
@@ -79,6 +80,62 @@ pub fn inject_edges(
   Ok(())
 }
 
+/// The call-site counterpart to [`inject_edges`]: rather than wrapping a function's prefix and
+/// `return` points, splices `before_snippets` immediately before and `after_snippets` immediately
+/// after every expression statement that is a function call (internal, external or low-level,
+/// `require`/`assert` included) in the resolved function's body - reusing the same
+/// contract/function resolution, inline-assembly guard, and snippet parsing, just keying the
+/// splice on [`is_call_expression_statement`] instead of `Statement::Return`. Lets a caller build
+/// reentrancy/gas/trace shadow probes that fire around every call statement.
+pub fn inject_probes(
+  unit: &mut foundry_compilers::artifacts::ast::SourceUnit,
+  contract_idx: usize,
+  selector: &str,
+  before_snippets: &[String],
+  after_snippets: &[String],
+  solc: &Solc,
+  settings: &Settings,
+  source: Option<&str>,
+) -> Result<()> {
+  if before_snippets.is_empty() && after_snippets.is_empty() {
+    return Err(Error::new(
+      "injectShadowAroundCalls requires a `before` and/or `after` snippet.",
+    ));
+  }
+
+  let mut next_id = utils::max_id(unit)?;
+
+  let SourceUnitPart::ContractDefinition(contract) = unit
+    .nodes
+    .get_mut(contract_idx)
+    .ok_or_else(|| Error::new("Invalid contract index"))?
+  else {
+    return Err(Error::new("Target index is not a contract definition"));
+  };
+  let selector_kind = parse_selector(selector, solc, settings)?;
+
+  let function = resolve_function_mut(contract, &selector_kind, source)?;
+
+  let body = function
+    .body
+    .as_mut()
+    .ok_or_else(|| Error::new("Cannot instrument a function without an implementation"))?;
+
+  ensure_no_inline_assembly(body, source)?;
+
+  let before_statements = parse_statements(before_snippets, solc, settings)?;
+  let after_statements = parse_statements(after_snippets, solc, settings)?;
+
+  inject_around_calls(
+    &mut body.statements,
+    &before_statements,
+    &after_statements,
+    &mut next_id,
+  )?;
+
+  Ok(())
+}
+
 fn parse_selector(
   signature: &str,
   solc: &Solc,
@@ -130,6 +187,7 @@ fn parse_selector(
 fn resolve_function_mut<'a>(
   contract: &'a mut ContractDefinition,
   selector: &FunctionSelectorKind,
+  source: Option<&str>,
 ) -> Result<&'a mut FunctionDefinition> {
   let mut matches: Vec<usize> = Vec::new();
 
@@ -170,15 +228,29 @@ fn resolve_function_mut<'a>(
   }
 
   if matches.is_empty() {
-    return Err(Error::new(
-      "Target function not found for injectShadowAtEdges.",
-    ));
+    let hint = not_found_hint(contract, selector);
+    let message = match hint {
+      Some(hint) => format!("Target function not found for injectShadowAtEdges. {}", hint),
+      None => "Target function not found for injectShadowAtEdges.".to_string(),
+    };
+    return Err(Error::new(message));
   }
 
   if matches.len() > 1 {
-    return Err(Error::new(
-      "Function name is ambiguous. Please provide a full function signature.",
-    ));
+    let candidates = matches
+      .iter()
+      .filter_map(|idx| {
+        let ContractDefinitionPart::FunctionDefinition(function) = &contract.nodes[*idx] else {
+          return None;
+        };
+        Some(describe_function_candidate(function, source))
+      })
+      .collect::<Vec<_>>()
+      .join(", ");
+    return Err(Error::new(format!(
+      "Function name is ambiguous. Please provide a full function signature. Candidates: {}",
+      candidates
+    )));
   }
 
   let idx = matches[0];
@@ -192,47 +264,146 @@ fn resolve_function_mut<'a>(
   Ok(function)
 }
 
-fn ensure_no_inline_assembly(body: &Block) -> Result<()> {
+/// Builds the hint appended to a `Target function not found` error: when `selector` named a
+/// function that exists under a different signature, lists the signatures actually present so the
+/// caller can copy one verbatim; otherwise falls back to a `levenshtein_distance`-based "did you
+/// mean" suggestion against every function name in `contract`.
+fn not_found_hint(contract: &ContractDefinition, selector: &FunctionSelectorKind) -> Option<String> {
+  let requested_name = match selector {
+    FunctionSelectorKind::Canonical { name, .. } | FunctionSelectorKind::Name(name) => name,
+    FunctionSelectorKind::Fallback | FunctionSelectorKind::Receive | FunctionSelectorKind::Constructor => {
+      return None;
+    }
+  };
+
+  let functions: Vec<&FunctionDefinition> = contract
+    .nodes
+    .iter()
+    .filter_map(|part| {
+      let ContractDefinitionPart::FunctionDefinition(function) = part else {
+        return None;
+      };
+      Some(function)
+    })
+    .collect();
+
+  let same_name_signatures: Vec<String> = functions
+    .iter()
+    .filter(|function| function.name == *requested_name)
+    .map(|function| describe_function_candidate(function, None))
+    .collect();
+
+  if !same_name_signatures.is_empty() {
+    return Some(format!(
+      "A function named `{}` exists, but no overload matches the given signature. Available: {}.",
+      requested_name,
+      same_name_signatures.join(", ")
+    ));
+  }
+
+  let candidate_names: Vec<&str> = functions
+    .iter()
+    .map(|function| function.name.as_str())
+    .filter(|name| !name.is_empty())
+    .collect();
+  closest_name_suggestion(requested_name, &candidate_names)
+}
+
+/// Picks the one or two closest names to `requested` by [`utils::levenshtein_distance`], keeping
+/// only candidates within edit distance 2 (or a third of `requested`'s length, whichever is more
+/// permissive) and breaking ties by shortest candidate then lexicographic order.
+fn closest_name_suggestion(requested: &str, candidates: &[&str]) -> Option<String> {
+  let requested_len = requested.chars().count();
+  let mut scored: Vec<(usize, &str)> = candidates
+    .iter()
+    .map(|candidate| (utils::levenshtein_distance(requested, candidate), *candidate))
+    .filter(|(distance, _)| *distance <= 2 || distance * 3 <= requested_len)
+    .collect();
+  scored.sort_by(|(distance_a, name_a), (distance_b, name_b)| {
+    distance_a
+      .cmp(distance_b)
+      .then_with(|| name_a.len().cmp(&name_b.len()))
+      .then_with(|| name_a.cmp(name_b))
+  });
+  scored.dedup_by(|a, b| a.1 == b.1);
+
+  match &scored[..scored.len().min(2)] {
+    [] => None,
+    [(_, only)] => Some(format!("Did you mean `{}`?", only)),
+    [(_, first), (_, second)] => Some(format!("Did you mean `{}` or `{}`?", first, second)),
+    _ => None,
+  }
+}
+
+/// Describes an ambiguous `injectShadowAtEdges` candidate as `name(paramTypes) at line:col`, or
+/// just `name(paramTypes)` when `source` is unavailable (the target unit was loaded as a pre-built
+/// AST rather than text) or its `src` doesn't resolve against it.
+fn describe_function_candidate(function: &FunctionDefinition, source: Option<&str>) -> String {
+  let signature = stitcher::function_signature(function)
+    .map(|params| params.join(","))
+    .unwrap_or_default();
+  let head = format!("{}({})", function.name, signature);
+  match source.and_then(|source| describe_src(function, source)) {
+    Some(position) => format!("{} at {}", head, position),
+    None => head,
+  }
+}
+
+/// Resolves `node`'s `src` field against `source` into a `"line:col"` string for error messages.
+fn describe_src<T: serde::Serialize>(node: &T, source: &str) -> Option<String> {
+  let src = utils::node_src(node)?;
+  let (line, column) = utils::resolve_src_position(&src, source)?;
+  Some(format!("{}:{}", line, column))
+}
+
+fn ensure_no_inline_assembly(body: &Block, source: Option<&str>) -> Result<()> {
   for statement in &body.statements {
-    ensure_no_inline_assembly_in_statement(statement)?;
+    ensure_no_inline_assembly_in_statement(statement, source)?;
   }
   Ok(())
 }
 
-fn ensure_no_inline_assembly_in_statement(statement: &Statement) -> Result<()> {
+fn ensure_no_inline_assembly_in_statement(statement: &Statement, source: Option<&str>) -> Result<()> {
   match statement {
-    Statement::InlineAssembly(_) => Err(Error::new(
-      "injectShadowAtEdges does not support functions containing inline assembly.",
-    )),
+    Statement::InlineAssembly(_) => {
+      let message = match source.and_then(|source| describe_src(statement, source)) {
+        Some(position) => format!(
+          "injectShadowAtEdges does not support functions containing inline assembly (at {}).",
+          position
+        ),
+        None => "injectShadowAtEdges does not support functions containing inline assembly.".to_string(),
+      };
+      Err(Error::new(message))
+    }
     Statement::Block(block) => {
       for stmt in &block.statements {
-        ensure_no_inline_assembly_in_statement(stmt)?;
+        ensure_no_inline_assembly_in_statement(stmt, source)?;
       }
       Ok(())
     }
     Statement::IfStatement(if_stmt) => {
-      ensure_no_inline_assembly_in_block_or_statement(&if_stmt.true_body)?;
+      ensure_no_inline_assembly_in_block_or_statement(&if_stmt.true_body, source)?;
       if let Some(false_body) = &if_stmt.false_body {
-        ensure_no_inline_assembly_in_block_or_statement(false_body)?;
+        ensure_no_inline_assembly_in_block_or_statement(false_body, source)?;
       }
       Ok(())
     }
     Statement::WhileStatement(while_stmt) => {
-      ensure_no_inline_assembly_in_block_or_statement(&while_stmt.body)
+      ensure_no_inline_assembly_in_block_or_statement(&while_stmt.body, source)
     }
-    Statement::DoWhileStatement(do_stmt) => ensure_no_inline_assembly(&do_stmt.body),
+    Statement::DoWhileStatement(do_stmt) => ensure_no_inline_assembly(&do_stmt.body, source),
     Statement::ForStatement(for_stmt) => {
-      ensure_no_inline_assembly_in_block_or_statement(&for_stmt.body)
+      ensure_no_inline_assembly_in_block_or_statement(&for_stmt.body, source)
     }
     Statement::TryStatement(try_stmt) => {
       for clause in &try_stmt.clauses {
-        ensure_no_inline_assembly(&clause.block)?;
+        ensure_no_inline_assembly(&clause.block, source)?;
       }
       Ok(())
     }
     Statement::UncheckedBlock(unchecked) => {
       for stmt in &unchecked.statements {
-        ensure_no_inline_assembly_in_statement(stmt)?;
+        ensure_no_inline_assembly_in_statement(stmt, source)?;
       }
       Ok(())
     }
@@ -240,10 +411,15 @@ fn ensure_no_inline_assembly_in_statement(statement: &Statement) -> Result<()> {
   }
 }
 
-fn ensure_no_inline_assembly_in_block_or_statement(node: &BlockOrStatement) -> Result<()> {
+fn ensure_no_inline_assembly_in_block_or_statement(
+  node: &BlockOrStatement,
+  source: Option<&str>,
+) -> Result<()> {
   match node {
-    BlockOrStatement::Block(block) => ensure_no_inline_assembly(block),
-    BlockOrStatement::Statement(statement) => ensure_no_inline_assembly_in_statement(statement),
+    BlockOrStatement::Block(block) => ensure_no_inline_assembly(block, source),
+    BlockOrStatement::Statement(statement) => {
+      ensure_no_inline_assembly_in_statement(statement, source)
+    }
   }
 }
 
@@ -298,10 +474,15 @@ fn parse_statements(
   Ok(block.statements.clone())
 }
 
+/// Clones each of `statements` and renumbers the clone's ids via [`AstVisitMut`] rather than
+/// `utils::clone_with_new_ids`'s `serde_json` round trip - `before`/`after` snippets get spliced in
+/// at every `return` a function has, so this runs once per clone, not once per function.
 fn clone_statements(statements: &[Statement], next_id: &mut i64) -> Result<Vec<Statement>> {
   let mut clones = Vec::with_capacity(statements.len());
   for statement in statements {
-    clones.push(utils::clone_with_new_ids(statement, next_id)?);
+    let mut clone = statement.clone();
+    clone.renumber_ids(next_id).map_err(Error::from)?;
+    clones.push(clone);
   }
   Ok(clones)
 }
@@ -484,3 +665,202 @@ fn inject_after_in_statement(
     _ => Ok(()),
   }
 }
+
+/// Matches a `Statement::ExpressionStatement` wrapping a `FunctionCall` - including low-level
+/// `.call`/`.delegatecall`/`.staticcall`, which are still `FunctionCall` nodes whose callee
+/// expression happens to be a `MemberAccess`. Goes through `serde_json::Value` the same way
+/// `ast/coverage.rs::is_require_call` inspects an expression's `nodeType`, since
+/// `ExpressionStatement`'s typed shape isn't matched anywhere else in this crate.
+fn is_call_expression_statement(statement: &Statement) -> bool {
+  if !matches!(statement, Statement::ExpressionStatement(_)) {
+    return false;
+  }
+  let Ok(value) = serde_json::to_value(statement) else {
+    return false;
+  };
+  value
+    .get("expression")
+    .and_then(|expression| expression.get("nodeType"))
+    .and_then(Value::as_str)
+    == Some("FunctionCall")
+}
+
+/// The call-site counterpart to [`inject_after`]: walks `statements` the same way, but splices
+/// `before_template` immediately before and `after_template` immediately after every
+/// [`is_call_expression_statement`] match rather than after every `Return`. A bare non-block
+/// single statement can't receive a splice here either, for the same reason `inject_after` can't -
+/// there's no sibling slot to splice into without rewriting the statement into a block, which is
+/// out of scope.
+fn inject_around_calls(
+  statements: &mut Vec<Statement>,
+  before_template: &[Statement],
+  after_template: &[Statement],
+  next_id: &mut i64,
+) -> Result<()> {
+  let mut idx = 0;
+  while idx < statements.len() {
+    if is_call_expression_statement(&statements[idx]) {
+      if !before_template.is_empty() {
+        let clones = clone_statements(before_template, next_id)?;
+        let len = clones.len();
+        statements.splice(idx..idx, clones);
+        idx += len;
+      }
+      idx += 1;
+      if !after_template.is_empty() {
+        let clones = clone_statements(after_template, next_id)?;
+        let len = clones.len();
+        statements.splice(idx..idx, clones);
+        idx += len;
+      }
+      continue;
+    }
+
+    match &mut statements[idx] {
+      Statement::Block(block) => {
+        inject_around_calls(&mut block.statements, before_template, after_template, next_id)?;
+        idx += 1;
+      }
+      Statement::IfStatement(if_stmt) => {
+        inject_around_calls_in_block_or_statement(
+          &mut if_stmt.true_body,
+          before_template,
+          after_template,
+          next_id,
+        )?;
+        if let Some(false_body) = if_stmt.false_body.as_mut() {
+          inject_around_calls_in_block_or_statement(
+            false_body,
+            before_template,
+            after_template,
+            next_id,
+          )?;
+        }
+        idx += 1;
+      }
+      Statement::WhileStatement(while_stmt) => {
+        inject_around_calls_in_block_or_statement(
+          &mut while_stmt.body,
+          before_template,
+          after_template,
+          next_id,
+        )?;
+        idx += 1;
+      }
+      Statement::DoWhileStatement(do_stmt) => {
+        inject_around_calls(
+          &mut do_stmt.body.statements,
+          before_template,
+          after_template,
+          next_id,
+        )?;
+        idx += 1;
+      }
+      Statement::ForStatement(for_stmt) => {
+        inject_around_calls_in_block_or_statement(
+          &mut for_stmt.body,
+          before_template,
+          after_template,
+          next_id,
+        )?;
+        idx += 1;
+      }
+      Statement::TryStatement(try_stmt) => {
+        for TryCatchClause { block, .. } in &mut try_stmt.clauses {
+          inject_around_calls(&mut block.statements, before_template, after_template, next_id)?;
+        }
+        idx += 1;
+      }
+      Statement::UncheckedBlock(unchecked) => {
+        inject_around_calls(
+          &mut unchecked.statements,
+          before_template,
+          after_template,
+          next_id,
+        )?;
+        idx += 1;
+      }
+      _ => {
+        idx += 1;
+      }
+    }
+  }
+  Ok(())
+}
+
+fn inject_around_calls_in_block_or_statement(
+  target: &mut BlockOrStatement,
+  before_template: &[Statement],
+  after_template: &[Statement],
+  next_id: &mut i64,
+) -> Result<()> {
+  match target {
+    BlockOrStatement::Block(block) => {
+      inject_around_calls(&mut block.statements, before_template, after_template, next_id)
+    }
+    BlockOrStatement::Statement(statement) => {
+      inject_around_calls_in_statement(statement, before_template, after_template, next_id)
+    }
+  }
+}
+
+fn inject_around_calls_in_statement(
+  statement: &mut Statement,
+  before_template: &[Statement],
+  after_template: &[Statement],
+  next_id: &mut i64,
+) -> Result<()> {
+  match statement {
+    Statement::Block(block) => {
+      inject_around_calls(&mut block.statements, before_template, after_template, next_id)
+    }
+    Statement::IfStatement(if_stmt) => {
+      inject_around_calls_in_block_or_statement(
+        &mut if_stmt.true_body,
+        before_template,
+        after_template,
+        next_id,
+      )?;
+      if let Some(false_body) = if_stmt.false_body.as_mut() {
+        inject_around_calls_in_block_or_statement(
+          false_body,
+          before_template,
+          after_template,
+          next_id,
+        )?;
+      }
+      Ok(())
+    }
+    Statement::WhileStatement(while_stmt) => inject_around_calls_in_block_or_statement(
+      &mut while_stmt.body,
+      before_template,
+      after_template,
+      next_id,
+    ),
+    Statement::DoWhileStatement(do_stmt) => inject_around_calls(
+      &mut do_stmt.body.statements,
+      before_template,
+      after_template,
+      next_id,
+    ),
+    Statement::ForStatement(for_stmt) => inject_around_calls_in_block_or_statement(
+      &mut for_stmt.body,
+      before_template,
+      after_template,
+      next_id,
+    ),
+    Statement::TryStatement(try_stmt) => {
+      for TryCatchClause { block, .. } in &mut try_stmt.clauses {
+        inject_around_calls(&mut block.statements, before_template, after_template, next_id)?;
+      }
+      Ok(())
+    }
+    Statement::UncheckedBlock(unchecked) => inject_around_calls(
+      &mut unchecked.statements,
+      before_template,
+      after_template,
+      next_id,
+    ),
+    _ => Ok(()),
+  }
+}
@@ -1,24 +1,40 @@
+use std::collections::BTreeMap;
+
 use foundry_compilers::artifacts::ast::SourceUnit;
 use napi::bindgen_prelude::*;
 use napi::{Env, JsObject, JsUnknown};
 
+mod coverage;
 pub mod core;
+mod dot;
 mod error;
+pub(crate) mod flatten;
+pub(crate) mod instrumenter;
 pub(crate) mod orchestrator;
+pub(crate) mod parse_cache;
 pub(crate) mod parser;
+mod printer;
+mod selector;
 mod stitcher;
 pub(crate) mod utils;
+mod visit;
 
 #[cfg(test)]
 mod ast_tests;
 
 use core::{
-  expose_internal_functions, expose_internal_variables, from_source, init, inject_shadow,
-  source_unit, source_unit_mut, validate,
+  add_source, expose_internal_functions, expose_internal_variables, from_project, from_source,
+  from_sources, init, inject_assertions, inject_shadow, instrument_coverage, merge_contracts,
+  source_unit, source_unit_mut, validate, validation_report, verify,
+};
+use printer::to_source;
+pub use core::{
+  FragmentTarget, SourceTarget, State, ValidationDiagnostic, ValidationReport,
+  ValidationSourceLocation, VerificationFinding, VerificationReport,
 };
-pub use core::{FragmentTarget, SourceTarget, State};
 use utils::{from_js_value, sanitize_ast_value, to_js_value};
 
+use crate::compiler::output::{into_js_compile_output, CompileOutput, JsCompileOutput};
 use crate::internal::config::{parse_js_ast_options, AstConfig, AstConfigOptions};
 use crate::internal::errors::{map_napi_error, napi_error, to_napi_result, Result};
 
@@ -51,6 +67,48 @@ impl Ast {
     Ok(self)
   }
 
+  /// Load an additional named source into the project alongside the primary one, so a contract in
+  /// the primary source (or an injected fragment) can import it. `path` identifies the source for
+  /// solc's import resolution and for qualified `path:Contract` overrides.
+  pub fn add_source(
+    &mut self,
+    path: &str,
+    target: SourceTarget,
+    options: Option<AstConfigOptions>,
+  ) -> Result<&mut Self> {
+    add_source(&mut self.state, path, target, options.as_ref())?;
+    Ok(self)
+  }
+
+  /// Load a whole multi-file project at once: every entry in `sources` is parsed together so an
+  /// import between them resolves, instead of the one-file-at-a-time resolution `add_source` gets.
+  /// `primary_path` names which entry subsequent operations (and `ast()`) target; with none given,
+  /// it falls back to `instrumentedContract` (qualified `path:Contract` names their path directly,
+  /// unqualified ones are searched for across every source) and finally to the lexicographically
+  /// first path.
+  pub fn from_sources(
+    &mut self,
+    sources: BTreeMap<String, String>,
+    primary_path: Option<String>,
+    options: Option<AstConfigOptions>,
+  ) -> Result<&mut Self> {
+    from_sources(&mut self.state, sources, primary_path, options.as_ref())?;
+    Ok(self)
+  }
+
+  /// The filesystem counterpart of `from_sources`: recursively collects every `.sol` file under
+  /// `root_path`'s resolved source directory (the same Hardhat/Dapptools layout detection
+  /// `config::find_source_dir` exposes directly) and loads them the same way.
+  pub fn from_project(
+    &mut self,
+    root_path: &str,
+    primary_path: Option<String>,
+    options: Option<AstConfigOptions>,
+  ) -> Result<&mut Self> {
+    from_project(&mut self.state, root_path, primary_path, options.as_ref())?;
+    Ok(self)
+  }
+
   pub fn expose_internal_variables(
     &mut self,
     options: Option<AstConfigOptions>,
@@ -67,13 +125,95 @@ impl Ast {
     Ok(self)
   }
 
+  /// Inject coverage counters into every instrumentable function body (plus the backing `__cov`
+  /// storage and getter) so the instrumented contract can report which basic blocks executed.
+  pub fn instrument_coverage(&mut self, options: Option<AstConfigOptions>) -> Result<&mut Self> {
+    instrument_coverage(&mut self.state, options.as_ref())?;
+    Ok(self)
+  }
+
   /// Compile the current AST to ensure it represents a valid contract and refresh its references.
-  /// This is optional—`ast()` already returns the parsed tree you can work with directly.
+  /// This is optional—`ast()` already returns the parsed tree you can work with directly. The full
+  /// diagnostic report (errors, warnings, info) is retained on `State` and readable via
+  /// `validation_report`; a non-empty `errors` list still aborts with an `Err`.
   pub fn validate(&mut self, options: Option<AstConfigOptions>) -> Result<&mut Self> {
     validate(&mut self.state, options.as_ref())?;
     Ok(self)
   }
 
+  /// The report from the most recent `validate` call, if any.
+  pub fn validation_report(&self) -> Option<&ValidationReport> {
+    validation_report(&self.state)
+  }
+
+  /// Compile the current project to bytecode/ABI artifacts with full (non-`stop_after`) settings,
+  /// turning the AST editor into an end-to-end instrument-and-build tool. Unlike `validate`, a
+  /// source that fails to compile doesn't abort the call - solc's diagnostics come back on the
+  /// returned `CompileOutput` instead, and every source (even one with no contract) still gets an
+  /// artifact entry.
+  pub fn compile(&self, options: Option<AstConfigOptions>) -> Result<CompileOutput> {
+    core::compile(&self.state, options.as_ref())
+  }
+
+  /// Compile the currently instrumented AST with solc's SMTChecker enabled and summarize which
+  /// properties held, which were violated (with a counterexample where solc provided one), and
+  /// which couldn't be fully proved either way. Pair with `inject_assertions` to check shadow
+  /// invariants that aren't already asserted in the contract.
+  pub fn verify(&self, options: Option<AstConfigOptions>) -> Result<VerificationReport> {
+    core::verify(&self.state, options.as_ref())
+  }
+
+  /// Stitch `assert(<expr>)` for each entry in `assertions` onto every `return` (and the end) of
+  /// `function_selector`, so a subsequent `verify` call checks invariants the caller supplies
+  /// rather than only ones already written into the contract. `function_selector` accepts a bare
+  /// name, a full `name(paramTypes)` signature to disambiguate an overload, or `fallback`/
+  /// `receive`/`constructor`.
+  pub fn inject_assertions(
+    &mut self,
+    function_selector: &str,
+    assertions: &[String],
+    options: Option<AstConfigOptions>,
+  ) -> Result<&mut Self> {
+    core::inject_assertions(
+      &mut self.state,
+      function_selector,
+      assertions,
+      options.as_ref(),
+    )?;
+    Ok(self)
+  }
+
+  /// Splices one or more standalone contracts into the source at `path` (the primary source by
+  /// default): each of `targets` is parsed into its own `SourceUnit` and every top-level
+  /// `ContractDefinition` it holds is appended to `path`'s nodes, ids renumbered so they never
+  /// collide with what's already there. Unlike `inject_shadow`, nothing is stitched into an
+  /// existing contract's members - use this to add a whole extra contract next to the ones already
+  /// loaded, e.g. a generated helper contract alongside user sources. Fails if a merged contract's
+  /// name collides with one already present.
+  pub fn merge_contracts(
+    &mut self,
+    targets: Vec<SourceTarget>,
+    path: Option<&str>,
+    options: Option<AstConfigOptions>,
+  ) -> Result<&mut Self> {
+    merge_contracts(&mut self.state, targets, path, options.as_ref())?;
+    Ok(self)
+  }
+
+  /// Pretty-print the current target `SourceUnit` back to Solidity source, so callers can inspect
+  /// or snapshot the effect of `inject_shadow`/the expose helpers as readable text rather than an
+  /// AST. Fails if the AST contains a node kind the printer doesn't yet render.
+  pub fn to_source(&self) -> Result<String> {
+    to_source(&self.state)
+  }
+
+  /// Render the most recently stitched contract (or, with no stitch yet, the default instrumented
+  /// contract) as a Graphviz DOT graph, so callers can debug why a fragment produced unexpected
+  /// duplicate or missing members without manually diffing ASTs.
+  pub fn to_dot(&self) -> Result<String> {
+    core::to_dot(&self.state)
+  }
+
   pub fn ast(&self) -> Result<&SourceUnit> {
     source_unit(&self.state).ok_or_else(|| {
       crate::internal::errors::Error::new("Ast has no target unit. Call from_source first.")
@@ -171,6 +311,80 @@ impl JsAst {
     Ok(self.clone())
   }
 
+  /// Load an additional named source into the project alongside the primary one, so a contract in
+  /// the primary source (or an injected fragment) can import it. `path` identifies the source for
+  /// solc's import resolution and for qualified `path:Contract` overrides.
+  #[napi(
+    ts_args_type = "path: string, target: string | object, options?: AstConfigOptions | undefined",
+    ts_return_type = "this"
+  )]
+  pub fn add_source(
+    &mut self,
+    env: Env,
+    path: String,
+    target: Either<String, JsObject>,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<JsAst> {
+    let parsed = parse_js_ast_options(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| AstConfigOptions::try_from(opts))
+      .transpose()?;
+    let target = parse_source_target(&env, target)?;
+    to_napi_result(self.inner.add_source(&path, target, overrides))?;
+    Ok(self.clone())
+  }
+
+  /// Load a whole multi-file project at once: every entry in `sources` is parsed together so an
+  /// import between them resolves, instead of the one-file-at-a-time resolution `addSource` gets.
+  /// `primaryPath` names which entry subsequent operations (and `ast()`) target; with none given,
+  /// it falls back to `instrumentedContract` and finally to the lexicographically first path.
+  #[napi(
+    ts_args_type = "sources: Record<string, string>, primaryPath?: string | undefined, options?: AstConfigOptions | undefined",
+    ts_return_type = "this"
+  )]
+  pub fn from_sources(
+    &mut self,
+    env: Env,
+    sources: BTreeMap<String, String>,
+    primary_path: Option<String>,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<JsAst> {
+    let parsed = parse_js_ast_options(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| AstConfigOptions::try_from(opts))
+      .transpose()?;
+    to_napi_result(self.inner.from_sources(sources, primary_path, overrides))?;
+    Ok(self.clone())
+  }
+
+  /// The filesystem counterpart of `fromSources`: recursively collects every `.sol` file under
+  /// `rootPath`'s resolved source directory and loads them the same way.
+  #[napi(
+    ts_args_type = "rootPath: string, primaryPath?: string | undefined, options?: AstConfigOptions | undefined",
+    ts_return_type = "this"
+  )]
+  pub fn from_project(
+    &mut self,
+    env: Env,
+    root_path: String,
+    primary_path: Option<String>,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<JsAst> {
+    let parsed = parse_js_ast_options(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| AstConfigOptions::try_from(opts))
+      .transpose()?;
+    to_napi_result(
+      self
+        .inner
+        .from_project(&root_path, primary_path, overrides),
+    )?;
+    Ok(self.clone())
+  }
+
   /// Promote private/internal state variables to public visibility. Omitting `instrumentedContract`
   /// applies the change to all contracts.
   #[napi(
@@ -211,6 +425,26 @@ impl JsAst {
     Ok(self.clone())
   }
 
+  /// Inject coverage counters into every instrumentable function body (plus the backing `__cov`
+  /// storage and getter) so the instrumented contract can report which basic blocks executed.
+  #[napi(
+    ts_args_type = "options?: AstConfigOptions | undefined",
+    ts_return_type = "this"
+  )]
+  pub fn instrument_coverage(
+    &mut self,
+    env: Env,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<JsAst> {
+    let parsed = parse_js_ast_options(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| AstConfigOptions::try_from(opts))
+      .transpose()?;
+    to_napi_result(self.inner.instrument_coverage(overrides))?;
+    Ok(self.clone())
+  }
+
   /// Compile the current AST to ensure it represents a valid contract and refresh its references.
   /// This is optional—`ast()` already returns the parsed tree you can work with directly.
   #[napi(
@@ -238,6 +472,130 @@ impl JsAst {
     sanitize_ast_value(&mut ast_value);
     to_js_value(&env, &ast_value)
   }
+
+  /// The diagnostic report (errors, warnings, info) from the most recent `validate` call, or
+  /// `undefined` if `validate` hasn't been called yet.
+  #[napi(ts_return_type = "import('./ast-types').ValidationReport | undefined")]
+  pub fn validation_report(&self, env: Env) -> napi::Result<JsUnknown> {
+    match self.inner.validation_report() {
+      Some(report) => to_js_value(&env, report),
+      None => Ok(env.get_undefined()?.into_unknown()),
+    }
+  }
+
+  /// Compile the current project to bytecode/ABI artifacts with full (non-`stop_after`) settings,
+  /// turning the AST editor into an end-to-end instrument-and-build tool. Unlike `validate`, a
+  /// source that fails to compile doesn't reject the call - solc's diagnostics come back on the
+  /// returned `CompileOutput` instead, and every source (even one with no contract) still gets an
+  /// artifact entry.
+  #[napi(
+    ts_args_type = "options?: AstConfigOptions | undefined",
+    ts_return_type = "CompileOutput"
+  )]
+  pub fn compile(&self, env: Env, options: Option<JsUnknown>) -> napi::Result<JsCompileOutput> {
+    let parsed = parse_js_ast_options(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| AstConfigOptions::try_from(opts))
+      .transpose()?;
+    let output = to_napi_result(self.inner.compile(overrides))?;
+    Ok(into_js_compile_output(output, false))
+  }
+
+  /// Compile the currently instrumented AST with solc's SMTChecker enabled and summarize which
+  /// properties held, which were violated (with a counterexample where solc provided one), and
+  /// which couldn't be fully proved either way. Pair with `injectAssertions` to check shadow
+  /// invariants that aren't already asserted in the contract.
+  #[napi(
+    ts_args_type = "options?: AstConfigOptions | undefined",
+    ts_return_type = "import('./ast-types').VerificationReport"
+  )]
+  pub fn verify(&self, env: Env, options: Option<JsUnknown>) -> napi::Result<JsUnknown> {
+    let parsed = parse_js_ast_options(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| AstConfigOptions::try_from(opts))
+      .transpose()?;
+    let report = to_napi_result(self.inner.verify(overrides))?;
+    to_js_value(&env, &report)
+  }
+
+  /// Stitch `assert(<expr>)` for each entry in `assertions` onto every `return` (and the end) of
+  /// `functionSelector`, so a subsequent `verify` call checks invariants the caller supplies
+  /// rather than only ones already written into the contract. `functionSelector` accepts a bare
+  /// name, a full `name(paramTypes)` signature to disambiguate an overload, or `fallback`/
+  /// `receive`/`constructor`.
+  #[napi(
+    ts_args_type = "functionSelector: string, assertions: string[], options?: AstConfigOptions | undefined",
+    ts_return_type = "this"
+  )]
+  pub fn inject_assertions(
+    &mut self,
+    env: Env,
+    function_selector: String,
+    assertions: Vec<String>,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<JsAst> {
+    let parsed = parse_js_ast_options(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| AstConfigOptions::try_from(opts))
+      .transpose()?;
+    to_napi_result(
+      self
+        .inner
+        .inject_assertions(&function_selector, &assertions, overrides),
+    )?;
+    Ok(self.clone())
+  }
+
+  /// Splices one or more standalone contracts into the source at `path` (the primary source by
+  /// default): each entry in `targets` is parsed into its own AST and every top-level contract it
+  /// holds is appended to `path`'s nodes, ids renumbered so they never collide with what's already
+  /// there. Unlike `injectShadow`, nothing is stitched into an existing contract's members - use
+  /// this to add a whole extra contract next to the ones already loaded, e.g. a generated helper
+  /// contract alongside user sources. Fails if a merged contract's name collides with one already
+  /// present.
+  #[napi(
+    ts_args_type = "targets: Array<string | object>, path?: string | undefined, options?: AstConfigOptions | undefined",
+    ts_return_type = "this"
+  )]
+  pub fn merge_contracts(
+    &mut self,
+    env: Env,
+    targets: Vec<Either<String, JsObject>>,
+    path: Option<String>,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<JsAst> {
+    let parsed = parse_js_ast_options(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| AstConfigOptions::try_from(opts))
+      .transpose()?;
+    let targets = targets
+      .into_iter()
+      .map(|target| parse_source_target(&env, target))
+      .collect::<napi::Result<Vec<_>>>()?;
+    to_napi_result(
+      self
+        .inner
+        .merge_contracts(targets, path.as_deref(), overrides),
+    )?;
+    Ok(self.clone())
+  }
+
+  /// Pretty-print the current target AST back to Solidity source text.
+  #[napi]
+  pub fn to_source(&self) -> napi::Result<String> {
+    to_napi_result(self.inner.to_source())
+  }
+
+  /// Render the most recently stitched contract as a Graphviz DOT graph, so callers can debug why
+  /// instrumentation produced unexpected duplicate or missing members.
+  #[napi]
+  pub fn to_dot(&self) -> napi::Result<String> {
+    to_napi_result(self.inner.to_dot())
+  }
 }
 
 fn parse_source_target(env: &Env, target: Either<String, JsObject>) -> napi::Result<SourceTarget> {
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use foundry_compilers::artifacts::ast::{ContractDefinition, SourceUnit};
 use foundry_compilers::artifacts::{output_selection::OutputSelection, Settings};
 use foundry_compilers::solc::Solc;
@@ -28,6 +30,27 @@ impl AstOrchestrator {
     parser::parse_source_ast(source, file_name, solc, settings)
   }
 
+  /// The multi-file counterpart of [`Self::parse_source_unit`], for a target reached through an
+  /// import: parses every entry in `sources` together so imports between them (direct or via
+  /// `settings.remappings`) resolve, returning one `SourceUnit` per entry.
+  pub fn parse_source_units(
+    sources: &BTreeMap<String, String>,
+    solc: &Solc,
+    settings: &Settings,
+  ) -> Result<BTreeMap<String, SourceUnit>, AstError> {
+    parser::parse_source_units(sources, solc, settings)
+  }
+
+  /// Locates `contract_name` across every unit in `units`, the multi-file counterpart of
+  /// `stitcher::find_instrumented_contract_index` for a target that isn't necessarily declared in
+  /// the root file being stitched into. See [`stitcher::find_target_contract`].
+  pub fn find_target_contract<'a>(
+    units: &'a BTreeMap<String, SourceUnit>,
+    contract_name: &str,
+  ) -> Result<(&'a str, usize), AstError> {
+    stitcher::find_target_contract(units, contract_name)
+  }
+
   pub fn parse_fragment_contract(
     fragment_source: &str,
     solc: &Solc,
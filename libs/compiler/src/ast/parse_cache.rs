@@ -0,0 +1,211 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use foundry_compilers::artifacts::ast::SourceUnit;
+use foundry_compilers::artifacts::Settings;
+use semver::Version;
+
+use crate::internal::cache_key::keccak_hex_parts;
+use crate::internal::errors::{Error, Result};
+
+/// Applied when `AstConfigOptions::parse_cache_capacity` isn't set - generous enough for a batch
+/// instrumentation run stitching hundreds of fragments against a handful of base contracts,
+/// without holding an unbounded number of parsed units in memory.
+const DEFAULT_CAPACITY: usize = 64;
+
+/// Derives the cache key `core::load_source_text`/`core::inject_fragment_string` look a parsed
+/// `SourceUnit` up (and store one) under: a hash of the exact bytes handed to solc, the solc
+/// version, and the sanitized settings - any change to any of those invalidates the entry. Mirrors
+/// `compiler::cache::key`, but per single source text rather than per whole project, since a
+/// fragment or target is parsed one file at a time here.
+pub fn key(source: &str, solc_version: &Version, settings: &Settings) -> String {
+  let solc_version = solc_version.to_string();
+  let settings_json = serde_json::to_string(settings).unwrap_or_default();
+  keccak_hex_parts([source, solc_version.as_str(), settings_json.as_str()])
+}
+
+/// An in-memory, least-recently-used cache of parsed `SourceUnit`s, optionally backed by a
+/// directory of persisted AST JSON so repeated process runs over the same fixtures skip solc
+/// entirely. Lives alongside `parser` (which does the actual parsing this short-circuits) rather
+/// than inside `core::State`, even though today it's only reached through `State` - a future
+/// caller that parses outside any one `State` (e.g. a warm-up pass across many fixtures) can reuse
+/// it directly.
+#[derive(Clone)]
+pub struct ParseCache {
+  capacity: usize,
+  persist_dir: Option<PathBuf>,
+  entries: VecDeque<(String, SourceUnit)>,
+}
+
+impl ParseCache {
+  pub fn new(capacity: Option<usize>, persist_dir: Option<PathBuf>) -> Self {
+    Self {
+      capacity: capacity.unwrap_or(DEFAULT_CAPACITY).max(1),
+      persist_dir,
+      entries: VecDeque::new(),
+    }
+  }
+
+  /// Looks `key` up, promoting it to most-recently-used on a hit. Checks the in-memory entries
+  /// first, then `persist_dir` (populating the in-memory cache from disk so a repeat lookup this
+  /// process skips the read) before reporting a miss.
+  pub fn get(&mut self, key: &str) -> Option<SourceUnit> {
+    if let Some(pos) = self.entries.iter().position(|(entry_key, _)| entry_key == key) {
+      let (_, unit) = self.entries.remove(pos).expect("position was just located");
+      self.entries.push_back((key.to_string(), unit.clone()));
+      return Some(unit);
+    }
+
+    let unit = self.read_from_disk(key)?;
+    self.insert_in_memory(key.to_string(), unit.clone());
+    Some(unit)
+  }
+
+  /// Records `unit` under `key`, evicting the least-recently-used entry once the cache is full,
+  /// and persisting it to `persist_dir` if one was configured.
+  pub fn insert(&mut self, key: &str, unit: &SourceUnit) -> Result<()> {
+    self.insert_in_memory(key.to_string(), unit.clone());
+    self.write_to_disk(key, unit)
+  }
+
+  fn insert_in_memory(&mut self, key: String, unit: SourceUnit) {
+    if let Some(pos) = self.entries.iter().position(|(entry_key, _)| *entry_key == key) {
+      self.entries.remove(pos);
+    }
+    if self.entries.len() >= self.capacity {
+      self.entries.pop_front();
+    }
+    self.entries.push_back((key, unit));
+  }
+
+  fn disk_path(&self, key: &str) -> Option<PathBuf> {
+    self
+      .persist_dir
+      .as_ref()
+      .map(|dir| dir.join(format!("{key}.json")))
+  }
+
+  fn read_from_disk(&self, key: &str) -> Option<SourceUnit> {
+    let path = self.disk_path(key)?;
+    let contents = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents).ok()
+  }
+
+  fn write_to_disk(&self, key: &str, unit: &SourceUnit) -> Result<()> {
+    let Some(path) = self.disk_path(key) else {
+      return Ok(());
+    };
+    write_entry(&path, unit)
+  }
+}
+
+fn write_entry(path: &Path, unit: &SourceUnit) -> Result<()> {
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent).map_err(|err| {
+      Error::new(format!(
+        "Failed to create AST parse cache directory {}: {err}",
+        parent.display()
+      ))
+    })?;
+  }
+  let serialized = serde_json::to_string(unit)
+    .map_err(|err| Error::new(format!("Failed to serialize cached AST: {err}")))?;
+  fs::write(path, serialized).map_err(|err| {
+    Error::new(format!(
+      "Failed to write cached AST {}: {err}",
+      path.display()
+    ))
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{ast::orchestrator::AstOrchestrator, internal::solc};
+  use foundry_compilers::solc::Solc;
+  use std::collections::hash_map::DefaultHasher;
+  use std::hash::{Hash, Hasher};
+
+  const CONTRACT_A: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract A {}
+"#;
+  const CONTRACT_B: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract B {}
+"#;
+
+  fn find_default_solc() -> Option<Solc> {
+    let version = solc::default_version().ok()?;
+    Solc::find_svm_installed_version(&version).ok().flatten()
+  }
+
+  fn parse(source: &str, solc: &Solc, settings: &Settings) -> SourceUnit {
+    AstOrchestrator::parse_source_unit(source, "Sample.sol", solc, settings)
+      .expect("parse sample source")
+  }
+
+  #[test]
+  fn key_changes_when_source_or_solc_version_differ() {
+    let version = Version::new(0, 8, 20);
+    let settings = Settings::default();
+    let a = key(CONTRACT_A, &version, &settings);
+    let b = key(CONTRACT_B, &version, &settings);
+    assert_ne!(a, b);
+
+    let other_version = Version::new(0, 8, 21);
+    let c = key(CONTRACT_A, &other_version, &settings);
+    assert_ne!(a, c);
+  }
+
+  #[test]
+  fn get_promotes_entry_and_evicts_least_recently_used() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+    let settings = AstOrchestrator::sanitize_settings(None).expect("sanitize settings");
+
+    let unit_a = parse(CONTRACT_A, &solc, &settings);
+    let unit_b = parse(CONTRACT_B, &solc, &settings);
+
+    let mut cache = ParseCache::new(Some(2), None);
+    cache.insert("a", &unit_a).expect("insert a");
+    cache.insert("b", &unit_b).expect("insert b");
+
+    assert!(cache.get("a").is_some(), "a should still be cached");
+
+    cache.insert("c", &unit_b).expect("insert c");
+
+    assert!(cache.get("a").is_some(), "a was just promoted, not evicted");
+    assert!(cache.get("b").is_none(), "b was least-recently-used");
+    assert!(cache.get("c").is_some());
+  }
+
+  #[test]
+  fn persists_and_reloads_from_disk() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+    let settings = AstOrchestrator::sanitize_settings(None).expect("sanitize settings");
+    let unit = parse(CONTRACT_A, &solc, &settings);
+
+    let mut unique = DefaultHasher::new();
+    std::process::id().hash(&mut unique);
+    "persists_and_reloads_from_disk".hash(&mut unique);
+    let dir = std::env::temp_dir().join(format!("ast-parse-cache-test-{:016x}", unique.finish()));
+
+    let mut writer = ParseCache::new(Some(1), Some(dir.clone()));
+    writer.insert("disk-key", &unit).expect("insert");
+
+    let mut reader = ParseCache::new(Some(1), Some(dir.clone()));
+    let reloaded = reader.get("disk-key").expect("read back from disk");
+    assert_eq!(reloaded.nodes.len(), unit.nodes.len());
+
+    let _ = fs::remove_dir_all(&dir);
+  }
+}
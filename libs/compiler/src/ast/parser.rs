@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use foundry_compilers::artifacts::ast::{ContractDefinition, SourceUnit, SourceUnitPart};
@@ -13,24 +14,55 @@ pub fn parse_source_ast(
   solc: &Solc,
   settings: &Settings,
 ) -> Result<SourceUnit, AstError> {
-  let mut sources = Sources::new();
-  sources.insert(PathBuf::from(file_name), Source::new(source));
+  let mut sources = BTreeMap::new();
+  sources.insert(file_name.to_string(), source.to_string());
 
-  let mut input = SolcInput::new(SolcLanguage::Solidity, sources, settings.clone());
+  let mut units = parse_source_units(&sources, solc, settings)?;
+  units
+    .remove(file_name)
+    .ok_or_else(|| AstError::ParseFailed("Failed to extract AST".to_string()))
+}
+
+/// The multi-file counterpart of [`parse_source_ast`]: parses every entry in `sources` together
+/// in one solc invocation, so an import between them (direct or reached through
+/// `settings.remappings`) resolves instead of failing the way it would parsing `sources` one file
+/// at a time. Returns one `SourceUnit` per entry, keyed by the same file name.
+pub fn parse_source_units(
+  sources: &BTreeMap<String, String>,
+  solc: &Solc,
+  settings: &Settings,
+) -> Result<BTreeMap<String, SourceUnit>, AstError> {
+  let mut solc_sources = Sources::new();
+  for (file_name, source) in sources {
+    solc_sources.insert(PathBuf::from(file_name), Source::new(source));
+  }
+
+  let mut input = SolcInput::new(SolcLanguage::Solidity, solc_sources, settings.clone());
   input.sanitize(&solc.version);
 
   let compiler_output: serde_json::Value = solc
     .compile_as::<SolcInput, _>(&input)
     .map_err(|err| AstError::CompilerError(err.to_string()))?;
 
-  let ast_value = compiler_output
+  let sources_output = compiler_output
     .get("sources")
-    .and_then(|sources| sources.get(file_name))
-    .and_then(|entry| entry.get("ast"))
-    .ok_or_else(|| AstError::ParseFailed("Failed to extract AST".to_string()))?
-    .clone();
-
-  serde_json::from_value(ast_value).map_err(|err| AstError::JsonError(err.to_string()))
+    .ok_or_else(|| AstError::ParseFailed("Failed to extract AST".to_string()))?;
+
+  sources
+    .keys()
+    .map(|file_name| {
+      let ast_value = sources_output
+        .get(file_name)
+        .and_then(|entry| entry.get("ast"))
+        .ok_or_else(|| {
+          AstError::ParseFailed(format!("Failed to extract AST for \"{file_name}\""))
+        })?
+        .clone();
+      let unit: SourceUnit =
+        serde_json::from_value(ast_value).map_err(|err| AstError::JsonError(err.to_string()))?;
+      Ok((file_name.clone(), unit))
+    })
+    .collect()
 }
 
 pub fn wrap_fragment_source(source: &str) -> String {
@@ -0,0 +1,619 @@
+use foundry_compilers::artifacts::ast::SourceUnitPart;
+use serde_json::Value;
+
+use super::core::State;
+use crate::internal::errors::{Error, Result};
+
+/// Pretty-prints the current `SourceUnit` back to Solidity text, so `inject_shadow`/the expose
+/// helpers can be inspected, diffed, or snapshotted as source rather than as AST JSON.
+///
+/// Node kinds this doesn't yet know how to render (most struct/enum/event/modifier bodies, and
+/// several statement/expression forms) come back as an `Err` rather than silently dropped or
+/// mis-rendered output.
+pub fn to_source(state: &State) -> Result<String> {
+  let unit = super::core::source_unit(state)
+    .ok_or_else(|| Error::new("Ast has no target unit. Call from_source first."))?;
+
+  let mut parts = Vec::with_capacity(unit.nodes.len());
+  for part in &unit.nodes {
+    parts.push(print_source_unit_part(part)?);
+  }
+  Ok(parts.join("\n\n"))
+}
+
+fn print_source_unit_part(part: &SourceUnitPart) -> Result<String> {
+  match part {
+    SourceUnitPart::PragmaDirective(pragma) => Ok(print_pragma(&to_value(pragma)?)),
+    SourceUnitPart::ImportDirective(import) => Ok(print_import(&to_value(import)?)),
+    SourceUnitPart::ContractDefinition(contract) => print_contract(&to_value(contract)?),
+    other => Err(unsupported("top-level declaration", &to_value(other)?)),
+  }
+}
+
+fn print_pragma(value: &Value) -> String {
+  let literals = string_array(value, "literals");
+  format!("pragma {};", literals.join(" "))
+}
+
+fn print_import(value: &Value) -> String {
+  let path = value.get("file").and_then(Value::as_str).unwrap_or("");
+  let unit_alias = value
+    .get("unitAlias")
+    .and_then(Value::as_str)
+    .filter(|alias| !alias.is_empty());
+  let symbol_aliases = value
+    .get("symbolAliases")
+    .and_then(Value::as_array)
+    .filter(|aliases| !aliases.is_empty());
+
+  if let Some(alias) = unit_alias {
+    return format!("import \"{path}\" as {alias};");
+  }
+
+  if let Some(aliases) = symbol_aliases {
+    let names = aliases
+      .iter()
+      .filter_map(|entry| {
+        let foreign = entry.get("foreign")?.get("name")?.as_str()?;
+        match entry.get("local").and_then(Value::as_str) {
+          Some(local) => Some(format!("{foreign} as {local}")),
+          None => Some(foreign.to_string()),
+        }
+      })
+      .collect::<Vec<_>>()
+      .join(", ");
+    return format!("import {{{names}}} from \"{path}\";");
+  }
+
+  format!("import \"{path}\";")
+}
+
+fn print_contract(value: &Value) -> Result<String> {
+  let name = value.get("name").and_then(Value::as_str).unwrap_or_default();
+  let kind = value
+    .get("contractKind")
+    .and_then(Value::as_str)
+    .unwrap_or("contract");
+  let is_abstract = value
+    .get("abstract")
+    .and_then(Value::as_bool)
+    .unwrap_or(false);
+
+  let base_contracts = value
+    .get("baseContracts")
+    .and_then(Value::as_array)
+    .cloned()
+    .unwrap_or_default();
+  let bases = base_contracts
+    .iter()
+    .map(print_inheritance_specifier)
+    .collect::<Result<Vec<_>>>()?;
+  let inheritance = if bases.is_empty() {
+    String::new()
+  } else {
+    format!(" is {}", bases.join(", "))
+  };
+
+  let abstract_prefix = if is_abstract { "abstract " } else { "" };
+
+  let members = value
+    .get("nodes")
+    .and_then(Value::as_array)
+    .cloned()
+    .unwrap_or_default();
+  let mut body = Vec::with_capacity(members.len());
+  for member in &members {
+    body.push(indent(&print_contract_part(member)?));
+  }
+
+  let header = format!("{abstract_prefix}{kind} {name}{inheritance} {{");
+  if body.is_empty() {
+    Ok(format!("{header}\n}}"))
+  } else {
+    Ok(format!("{header}\n{}\n}}", body.join("\n\n")))
+  }
+}
+
+fn print_inheritance_specifier(value: &Value) -> Result<String> {
+  let name = value
+    .get("baseName")
+    .and_then(|base_name| base_name.get("name"))
+    .and_then(Value::as_str)
+    .unwrap_or_default();
+  let arguments = value.get("arguments").and_then(Value::as_array);
+  match arguments {
+    None | Some([]) => Ok(name.to_string()),
+    Some(args) => {
+      let rendered = args
+        .iter()
+        .map(print_expression)
+        .collect::<Result<Vec<_>>>()?;
+      Ok(format!("{name}({})", rendered.join(", ")))
+    }
+  }
+}
+
+fn print_contract_part(value: &Value) -> Result<String> {
+  match value.get("nodeType").and_then(Value::as_str) {
+    Some("VariableDeclaration") => print_state_variable(value),
+    Some("FunctionDefinition") => print_function(value),
+    _ => Err(unsupported("contract member", value)),
+  }
+}
+
+fn print_state_variable(value: &Value) -> Result<String> {
+  let name = value.get("name").and_then(Value::as_str).unwrap_or_default();
+  let type_string = type_string(value);
+  let visibility = visibility_keyword(value);
+  let constant = value
+    .get("constant")
+    .and_then(Value::as_bool)
+    .unwrap_or(false);
+  let mutability = value.get("mutability").and_then(Value::as_str);
+  let modifier = if constant {
+    " constant"
+  } else if mutability == Some("immutable") {
+    " immutable"
+  } else {
+    ""
+  };
+  let visibility_clause = visibility.map(|kw| format!(" {kw}")).unwrap_or_default();
+
+  let initial_value = value
+    .get("value")
+    .filter(|v| !v.is_null())
+    .map(print_expression)
+    .transpose()?;
+  let assignment = initial_value
+    .map(|rendered| format!(" = {rendered}"))
+    .unwrap_or_default();
+
+  Ok(format!(
+    "{type_string}{visibility_clause}{modifier} {name}{assignment};"
+  ))
+}
+
+fn print_function(value: &Value) -> Result<String> {
+  let name = value.get("name").and_then(Value::as_str).unwrap_or_default();
+  let kind = value.get("kind").and_then(Value::as_str).unwrap_or("function");
+
+  let header_name = match kind {
+    "constructor" => "constructor".to_string(),
+    "fallback" => "fallback".to_string(),
+    "receive" => "receive".to_string(),
+    _ => format!("function {name}"),
+  };
+
+  let params = value
+    .get("parameters")
+    .and_then(|list| list.get("parameters"))
+    .and_then(Value::as_array)
+    .cloned()
+    .unwrap_or_default();
+  let param_list = params
+    .iter()
+    .map(print_parameter)
+    .collect::<Result<Vec<_>>>()?
+    .join(", ");
+
+  let visibility = visibility_keyword(value)
+    .map(|kw| format!(" {kw}"))
+    .unwrap_or_default();
+  let mutability = value
+    .get("stateMutability")
+    .and_then(Value::as_str)
+    .filter(|mutability| *mutability != "nonpayable")
+    .map(|mutability| format!(" {mutability}"))
+    .unwrap_or_default();
+  let virtual_clause = value
+    .get("virtual")
+    .and_then(Value::as_bool)
+    .filter(|is_virtual| *is_virtual)
+    .map(|_| " virtual".to_string())
+    .unwrap_or_default();
+  let modifiers = print_modifier_invocations(value)?;
+
+  let returns = value
+    .get("returnParameters")
+    .and_then(|list| list.get("parameters"))
+    .and_then(Value::as_array)
+    .cloned()
+    .unwrap_or_default();
+  let returns_clause = if returns.is_empty() {
+    String::new()
+  } else {
+    let rendered = returns
+      .iter()
+      .map(print_parameter)
+      .collect::<Result<Vec<_>>>()?
+      .join(", ");
+    format!(" returns ({rendered})")
+  };
+
+  let signature = format!(
+    "{header_name}({param_list}){visibility}{mutability}{virtual_clause}{modifiers}{returns_clause}"
+  );
+
+  match value.get("body") {
+    Some(body) if !body.is_null() => {
+      let rendered_body = print_block(body)?;
+      Ok(format!("{signature} {rendered_body}"))
+    }
+    _ => Ok(format!("{signature};")),
+  }
+}
+
+fn print_modifier_invocations(function: &Value) -> Result<String> {
+  let modifiers = function
+    .get("modifiers")
+    .and_then(Value::as_array)
+    .cloned()
+    .unwrap_or_default();
+  if modifiers.is_empty() {
+    return Ok(String::new());
+  }
+
+  let rendered = modifiers
+    .iter()
+    .map(|modifier| {
+      let name = modifier
+        .get("modifierName")
+        .and_then(|modifier_name| modifier_name.get("name"))
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+      let arguments = modifier.get("arguments").and_then(Value::as_array);
+      match arguments {
+        None => Ok(name.to_string()),
+        Some(args) => {
+          let rendered_args = args
+            .iter()
+            .map(print_expression)
+            .collect::<Result<Vec<_>>>()?;
+          Ok(format!("{name}({})", rendered_args.join(", ")))
+        }
+      }
+    })
+    .collect::<Result<Vec<_>>>()?;
+
+  Ok(format!(" {}", rendered.join(" ")))
+}
+
+fn print_parameter(value: &Value) -> Result<String> {
+  let ty = type_string(value);
+  let name = value.get("name").and_then(Value::as_str).unwrap_or_default();
+  if name.is_empty() {
+    Ok(ty)
+  } else {
+    Ok(format!("{ty} {name}"))
+  }
+}
+
+fn type_string(value: &Value) -> String {
+  value
+    .get("typeDescriptions")
+    .and_then(|descriptions| descriptions.get("typeString"))
+    .and_then(Value::as_str)
+    .unwrap_or("<unknown type>")
+    .to_string()
+}
+
+fn visibility_keyword(value: &Value) -> Option<&'static str> {
+  match value.get("visibility").and_then(Value::as_str)? {
+    "public" => Some("public"),
+    "internal" => Some("internal"),
+    "private" => Some("private"),
+    "external" => Some("external"),
+    _ => None,
+  }
+}
+
+fn print_block(value: &Value) -> Result<String> {
+  let statements = value
+    .get("statements")
+    .and_then(Value::as_array)
+    .cloned()
+    .unwrap_or_default();
+  if statements.is_empty() {
+    return Ok("{}".to_string());
+  }
+
+  let rendered = statements
+    .iter()
+    .map(|statement| print_statement(statement).map(|line| indent(&line)))
+    .collect::<Result<Vec<_>>>()?;
+  Ok(format!("{{\n{}\n}}", rendered.join("\n")))
+}
+
+/// Renders a single statement node. Shared with the coverage instrumenter, which re-emits a
+/// function body verbatim through this printer except where it needs to splice in a counter.
+pub(crate) fn print_statement(value: &Value) -> Result<String> {
+  match value.get("nodeType").and_then(Value::as_str) {
+    Some("Block") => print_block(value),
+    Some("UncheckedBlock") => Ok(format!("unchecked {}", print_block(value)?)),
+    Some("ExpressionStatement") => {
+      let expression = value
+        .get("expression")
+        .ok_or_else(|| Error::new("ExpressionStatement is missing its expression"))?;
+      Ok(format!("{};", print_expression(expression)?))
+    }
+    Some("Return") => match value.get("expression").filter(|v| !v.is_null()) {
+      Some(expression) => Ok(format!("return {};", print_expression(expression)?)),
+      None => Ok("return;".to_string()),
+    },
+    Some("VariableDeclarationStatement") => print_variable_declaration_statement(value),
+    Some("IfStatement") => print_if_statement(value),
+    Some("EmitStatement") => {
+      let call = value
+        .get("eventCall")
+        .ok_or_else(|| Error::new("EmitStatement is missing its event call"))?;
+      Ok(format!("emit {};", print_expression(call)?))
+    }
+    Some("RevertStatement") => {
+      let call = value
+        .get("errorCall")
+        .ok_or_else(|| Error::new("RevertStatement is missing its error call"))?;
+      Ok(format!("revert {};", print_expression(call)?))
+    }
+    Some("Break") => Ok("break;".to_string()),
+    Some("Continue") => Ok("continue;".to_string()),
+    Some("PlaceholderStatement") => Ok("_;".to_string()),
+    _ => Err(unsupported("statement", value)),
+  }
+}
+
+fn print_variable_declaration_statement(value: &Value) -> Result<String> {
+  let declarations = value
+    .get("declarations")
+    .and_then(Value::as_array)
+    .cloned()
+    .unwrap_or_default();
+  let rendered_declarations = declarations
+    .iter()
+    .map(|declaration| {
+      if declaration.is_null() {
+        Ok("".to_string())
+      } else {
+        print_parameter(declaration)
+      }
+    })
+    .collect::<Result<Vec<_>>>()?;
+  let lhs = if rendered_declarations.len() == 1 {
+    rendered_declarations[0].clone()
+  } else {
+    format!("({})", rendered_declarations.join(", "))
+  };
+
+  let initial_value = value
+    .get("initialValue")
+    .filter(|v| !v.is_null())
+    .map(print_expression)
+    .transpose()?;
+
+  match initial_value {
+    Some(rendered) => Ok(format!("{lhs} = {rendered};")),
+    None => Ok(format!("{lhs};")),
+  }
+}
+
+fn print_if_statement(value: &Value) -> Result<String> {
+  let condition = value
+    .get("condition")
+    .ok_or_else(|| Error::new("IfStatement is missing its condition"))?;
+  let true_body = value
+    .get("trueBody")
+    .ok_or_else(|| Error::new("IfStatement is missing its true branch"))?;
+  let rendered_true = print_block_or_statement(true_body)?;
+
+  match value.get("falseBody").filter(|v| !v.is_null()) {
+    Some(false_body) => {
+      let rendered_false = print_block_or_statement(false_body)?;
+      Ok(format!(
+        "if ({}) {} else {}",
+        print_expression(condition)?,
+        rendered_true,
+        rendered_false
+      ))
+    }
+    None => Ok(format!(
+      "if ({}) {}",
+      print_expression(condition)?,
+      rendered_true
+    )),
+  }
+}
+
+fn print_block_or_statement(value: &Value) -> Result<String> {
+  match value.get("nodeType").and_then(Value::as_str) {
+    Some("Block") => print_block(value),
+    _ => print_statement(value),
+  }
+}
+
+/// Renders a single expression node. Shared with the coverage instrumenter.
+pub(crate) fn print_expression(value: &Value) -> Result<String> {
+  match value.get("nodeType").and_then(Value::as_str) {
+    Some("Identifier") => Ok(value
+      .get("name")
+      .and_then(Value::as_str)
+      .unwrap_or_default()
+      .to_string()),
+    Some("Literal") => print_literal(value),
+    Some("BinaryOperation") => {
+      let operator = value.get("operator").and_then(Value::as_str).unwrap_or("?");
+      let left = print_expression(value.get("leftExpression").ok_or_else(|| {
+        Error::new("BinaryOperation is missing its left operand")
+      })?)?;
+      let right = print_expression(value.get("rightExpression").ok_or_else(|| {
+        Error::new("BinaryOperation is missing its right operand")
+      })?)?;
+      Ok(format!("({left} {operator} {right})"))
+    }
+    Some("UnaryOperation") => {
+      let operator = value.get("operator").and_then(Value::as_str).unwrap_or("?");
+      let sub_expression = print_expression(value.get("subExpression").ok_or_else(|| {
+        Error::new("UnaryOperation is missing its operand")
+      })?)?;
+      let prefix = value
+        .get("prefix")
+        .and_then(Value::as_bool)
+        .unwrap_or(true);
+      if prefix {
+        Ok(format!("{operator}{sub_expression}"))
+      } else {
+        Ok(format!("{sub_expression}{operator}"))
+      }
+    }
+    Some("Assignment") => {
+      let operator = value.get("operator").and_then(Value::as_str).unwrap_or("=");
+      let lhs = print_expression(value.get("leftHandSide").ok_or_else(|| {
+        Error::new("Assignment is missing its left-hand side")
+      })?)?;
+      let rhs = print_expression(value.get("rightHandSide").ok_or_else(|| {
+        Error::new("Assignment is missing its right-hand side")
+      })?)?;
+      Ok(format!("{lhs} {operator} {rhs}"))
+    }
+    Some("FunctionCall") => {
+      let callee = print_expression(
+        value
+          .get("expression")
+          .ok_or_else(|| Error::new("FunctionCall is missing its callee"))?,
+      )?;
+      let arguments = value
+        .get("arguments")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+      let rendered_args = arguments
+        .iter()
+        .map(print_expression)
+        .collect::<Result<Vec<_>>>()?
+        .join(", ");
+      Ok(format!("{callee}({rendered_args})"))
+    }
+    Some("MemberAccess") => {
+      let base = print_expression(
+        value
+          .get("expression")
+          .ok_or_else(|| Error::new("MemberAccess is missing its base expression"))?,
+      )?;
+      let member = value
+        .get("memberName")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+      Ok(format!("{base}.{member}"))
+    }
+    Some("IndexAccess") => {
+      let base = print_expression(
+        value
+          .get("baseExpression")
+          .ok_or_else(|| Error::new("IndexAccess is missing its base expression"))?,
+      )?;
+      match value.get("indexExpression").filter(|v| !v.is_null()) {
+        Some(index) => Ok(format!("{base}[{}]", print_expression(index)?)),
+        None => Ok(format!("{base}[]")),
+      }
+    }
+    Some("TupleExpression") => {
+      let components = value
+        .get("components")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+      let rendered = components
+        .iter()
+        .map(|component| {
+          if component.is_null() {
+            Ok(String::new())
+          } else {
+            print_expression(component)
+          }
+        })
+        .collect::<Result<Vec<_>>>()?
+        .join(", ");
+      Ok(format!("({rendered})"))
+    }
+    Some("Conditional") => {
+      let condition = print_expression(
+        value
+          .get("condition")
+          .ok_or_else(|| Error::new("Conditional is missing its condition"))?,
+      )?;
+      let true_expression = print_expression(
+        value
+          .get("trueExpression")
+          .ok_or_else(|| Error::new("Conditional is missing its true branch"))?,
+      )?;
+      let false_expression = print_expression(
+        value
+          .get("falseExpression")
+          .ok_or_else(|| Error::new("Conditional is missing its false branch"))?,
+      )?;
+      Ok(format!(
+        "({condition} ? {true_expression} : {false_expression})"
+      ))
+    }
+    _ => Err(unsupported("expression", value)),
+  }
+}
+
+fn print_literal(value: &Value) -> Result<String> {
+  let kind = value.get("kind").and_then(Value::as_str).unwrap_or("number");
+  match kind {
+    "string" | "unicodeString" => {
+      let text = value.get("value").and_then(Value::as_str).unwrap_or_default();
+      Ok(format!("{:?}", text))
+    }
+    "hexString" => {
+      let hex = value
+        .get("hexValue")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+      Ok(format!("hex\"{hex}\""))
+    }
+    _ => Ok(
+      value
+        .get("value")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string(),
+    ),
+  }
+}
+
+pub(crate) fn indent(text: &str) -> String {
+  text
+    .lines()
+    .map(|line| format!("  {line}"))
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+fn string_array(value: &Value, key: &str) -> Vec<String> {
+  value
+    .get(key)
+    .and_then(Value::as_array)
+    .map(|items| {
+      items
+        .iter()
+        .filter_map(Value::as_str)
+        .map(str::to_string)
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
+fn to_value<T: serde::Serialize>(node: &T) -> Result<Value> {
+  serde_json::to_value(node)
+    .map_err(|err| Error::new(format!("Failed to inspect AST node: {err}")))
+}
+
+fn unsupported(context: &str, node: &Value) -> Error {
+  let node_type = node
+    .get("nodeType")
+    .and_then(Value::as_str)
+    .unwrap_or("unknown");
+  Error::new(format!(
+    "to_source does not yet support a {context} of kind \"{node_type}\""
+  ))
+}
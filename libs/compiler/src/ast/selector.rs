@@ -0,0 +1,164 @@
+use foundry_compilers::artifacts::ast::{
+  ContractDefinitionPart, FunctionDefinition, FunctionKind, VariableDeclaration, Visibility,
+};
+use sha3::{Digest, Keccak256};
+use std::collections::HashMap;
+
+use super::error::AstError;
+
+/// `ConflictKey::Function` only catches exact-name clashes, so two differently-named externally
+/// callable functions that happen to hash to the same 4-byte selector can be stitched together
+/// without anyone noticing - the resulting contract would be undeployable (or silently ambiguous,
+/// depending on the compiler/tooling) long after this stitch reported success. Called once after
+/// both the `Safe` and `Replace` strategies finish, over the contract's full merged member list.
+pub(crate) fn check_selector_collisions(nodes: &[ContractDefinitionPart]) -> Result<(), AstError> {
+  let mut seen: HashMap<[u8; 4], String> = HashMap::new();
+
+  for part in nodes {
+    let ContractDefinitionPart::FunctionDefinition(function) = part else {
+      continue;
+    };
+    if !is_externally_callable(function) {
+      continue;
+    }
+    let Some(signature) = canonical_signature(function) else {
+      continue;
+    };
+    let selector = selector_for(&signature);
+    match seen.get(&selector) {
+      Some(existing) if existing != &signature => {
+        return Err(AstError::SelectorCollision {
+          selector,
+          first: existing.clone(),
+          second: signature,
+        });
+      }
+      Some(_) => {}
+      None => {
+        seen.insert(selector, signature);
+      }
+    }
+  }
+
+  Ok(())
+}
+
+fn is_externally_callable(function: &FunctionDefinition) -> bool {
+  matches!(function.kind(), FunctionKind::Function)
+    && matches!(function.visibility, Visibility::Public | Visibility::External)
+}
+
+/// Builds the canonical `name(type,type,...)` ABI signature a dispatcher would hash, or `None` if
+/// any parameter's type can't be resolved at all (in which case this function is skipped rather
+/// than guessed at, the same way [`super::stitcher::parameter_type_key`] falls back to a
+/// placeholder instead of failing).
+fn canonical_signature(function: &FunctionDefinition) -> Option<String> {
+  let params = function
+    .parameters
+    .parameters
+    .iter()
+    .map(canonical_parameter_type)
+    .collect::<Option<Vec<_>>>()?;
+  Some(format!("{}({})", function.name, params.join(",")))
+}
+
+/// Best-effort canonical ABI type for one parameter. Elementary and array types are derived
+/// directly from solc's `typeIdentifier` (e.g. `t_uint256` -> `uint256`, `t_array$_t_bool_$dyn...`
+/// -> `bool[]`); enums and contract references canonicalize to their fixed ABI stand-ins (`uint8`,
+/// `address`). Structs/tuples would need their member types, which aren't reachable from a bare
+/// parameter declaration, so they fall back to solc's `typeString` - not the true tuple form, but
+/// still distinct enough to catch a same-named-struct collision rather than silently ignoring it.
+fn canonical_parameter_type(param: &VariableDeclaration) -> Option<String> {
+  let descriptions = &param.type_descriptions;
+  if let Some(identifier) = &descriptions.type_identifier {
+    if identifier.contains("t_struct$") || identifier.contains("t_tuple$") {
+      return descriptions.type_string.clone().or_else(|| Some(identifier.clone()));
+    }
+    return Some(canonicalize_identifier(identifier));
+  }
+  descriptions.type_string.clone()
+}
+
+fn canonicalize_identifier(identifier: &str) -> String {
+  let base = strip_location_suffix(identifier);
+
+  if let Some(array) = canonicalize_array(base) {
+    return array;
+  }
+  if base.starts_with("t_enum$_") {
+    return "uint8".to_string();
+  }
+  if base.starts_with("t_contract$_") {
+    return "address".to_string();
+  }
+  base.strip_prefix("t_").unwrap_or(base).to_string()
+}
+
+const LOCATION_SUFFIXES: &[&str] = &[
+  "_storage_ptr",
+  "_memory_ptr",
+  "_calldata_ptr",
+  "_storage",
+  "_memory",
+  "_calldata",
+];
+
+fn strip_location_suffix(identifier: &str) -> &str {
+  for suffix in LOCATION_SUFFIXES {
+    if let Some(base) = identifier.strip_suffix(suffix) {
+      return base;
+    }
+  }
+  identifier
+}
+
+/// Solc's array identifiers look like `t_array$_<element>_$<length>_<location>`, where `<length>`
+/// is either a number or the literal `dyn`. Recurses on `<element>` so nested arrays canonicalize
+/// correctly (e.g. `uint256[][3]` for a length-3 array of dynamic `uint256[]` arrays).
+fn canonicalize_array(identifier: &str) -> Option<String> {
+  let rest = identifier.strip_prefix("t_array$_")?;
+  let (element, length) = rest.rsplit_once("_$")?;
+  let length = strip_location_suffix(length);
+  let bracket = if length == "dyn" {
+    "[]".to_string()
+  } else {
+    format!("[{length}]")
+  };
+  Some(format!("{}{bracket}", canonicalize_identifier(element)))
+}
+
+fn selector_for(signature: &str) -> [u8; 4] {
+  let digest = Keccak256::digest(signature.as_bytes());
+  [digest[0], digest[1], digest[2], digest[3]]
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn selector_matches_well_known_erc20_transfer_selector() {
+    assert_eq!(selector_for("transfer(address,uint256)"), [0xa9, 0x05, 0x9c, 0xbb]);
+  }
+
+  #[test]
+  fn canonicalizes_elementary_types() {
+    assert_eq!(canonicalize_identifier("t_uint256"), "uint256");
+    assert_eq!(canonicalize_identifier("t_address"), "address");
+    assert_eq!(canonicalize_identifier("t_bool"), "bool");
+    assert_eq!(canonicalize_identifier("t_enum$_Kind_$12"), "uint8");
+    assert_eq!(canonicalize_identifier("t_contract$_Token_$34"), "address");
+  }
+
+  #[test]
+  fn canonicalizes_array_types() {
+    assert_eq!(
+      canonicalize_identifier("t_array$_t_uint256_$dyn_storage_ptr"),
+      "uint256[]"
+    );
+    assert_eq!(
+      canonicalize_identifier("t_array$_t_address_$5_memory_ptr"),
+      "address[5]"
+    );
+  }
+}
@@ -4,11 +4,11 @@ use foundry_compilers::artifacts::ast::{
 };
 use serde::Serialize;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use crate::internal::config::ResolveConflictStrategy;
 
-use super::{error::AstError, utils};
+use super::{error::AstError, selector, utils};
 
 pub fn find_instrumented_contract_index(
   unit: &SourceUnit,
@@ -44,13 +44,88 @@ pub fn find_instrumented_contract_index(
     })
 }
 
+/// The multi-file counterpart of [`find_instrumented_contract_index`]: a stitch target reached
+/// through a remapped import isn't necessarily declared in the root file being stitched into, so
+/// this searches every unit in `units` (as returned by [`super::parser::parse_source_units`]) in
+/// key order rather than just one `SourceUnit`'s top-level `nodes`.
+///
+/// `contract_name` may be a bare name (`"Target"`, matching the first declaration found across
+/// `units`) or fully-qualified as `source:Name`/`source/Name` (e.g. `"src/Target.sol:Target"`) to
+/// disambiguate same-named contracts declared in different files. Returns the source file the
+/// match lives in together with its index into that unit's `nodes`, so the caller can look up the
+/// right `SourceUnit` before renumbering/stitching against it.
+pub fn find_target_contract<'a>(
+  units: &'a BTreeMap<String, SourceUnit>,
+  contract_name: &str,
+) -> Result<(&'a str, usize), AstError> {
+  let (source_filter, name) = match contract_name
+    .split_once(':')
+    .or_else(|| contract_name.rsplit_once('/'))
+  {
+    Some((source, name)) => (Some(source), name),
+    None => (None, contract_name),
+  };
+
+  for (source, unit) in units {
+    if source_filter.is_some_and(|expected| expected != source) {
+      continue;
+    }
+    for (idx, part) in unit.nodes.iter().enumerate() {
+      if let SourceUnitPart::ContractDefinition(contract) = part {
+        if contract.name == name {
+          return Ok((source.as_str(), idx));
+        }
+      }
+    }
+  }
+
+  Err(AstError::InvalidContractStructure(format!(
+    "Contract '{contract_name}' not found"
+  )))
+}
+
+/// Per-member outcome of a single [`stitch_fragment_nodes_into_contract`] call, so callers can
+/// surface what actually happened (e.g. "replaced `hello()`, appended `replacementCounter`")
+/// instead of the stitch succeeding silently under either strategy.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StitchReport {
+  pub entries: Vec<StitchEntry>,
+}
+
+/// One fragment member's conflict key (`None` for any future member kind `contract_part_key`
+/// doesn't yet compute one for), human-readable name and kind, and what the stitch did with it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StitchEntry {
+  pub key: Option<ConflictKey>,
+  pub name: String,
+  pub kind: &'static str,
+  pub outcome: StitchOutcome,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum StitchOutcome {
+  /// No existing member shared this entry's `ConflictKey`; the fragment member was appended as-is.
+  Appended,
+  /// `Replace` strategy found an existing member with the same `ConflictKey` and overwrote it,
+  /// reusing `old_ids` where possible; anything left over was renumbered into `new_id_range`.
+  Replaced {
+    old_ids: Vec<i64>,
+    new_id_range: (i64, i64),
+  },
+  /// `Safe` strategy found an existing member with the same `ConflictKey`; the fragment member was
+  /// appended alongside it rather than replacing it, so the contract now has both.
+  Duplicated,
+  /// The fragment member has no `ConflictKey`, so it was appended without any conflict tracking.
+  Skipped,
+}
+
 pub fn stitch_fragment_nodes_into_contract(
   target: &mut SourceUnit,
   contract_idx: usize,
   fragment_contract: &ContractDefinition,
   max_target_id: i64,
   strategy: ResolveConflictStrategy,
-) -> Result<(), AstError> {
+) -> Result<StitchReport, AstError> {
   let SourceUnitPart::ContractDefinition(target_contract) = target
     .nodes
     .get_mut(contract_idx)
@@ -61,14 +136,33 @@ pub fn stitch_fragment_nodes_into_contract(
     ));
   };
 
-  match strategy {
+  let report = match strategy {
     ResolveConflictStrategy::Safe => {
       let mut fragment = fragment_contract.clone();
       utils::renumber_contract_definition(&mut fragment, max_target_id)?;
+
+      let existing_keys: std::collections::HashSet<ConflictKey> = target_contract
+        .nodes
+        .iter()
+        .filter_map(|part| contract_part_key(part).ok().flatten())
+        .collect();
+
+      let mut entries = Vec::with_capacity(fragment.nodes.len());
+      for part in &fragment.nodes {
+        let key = contract_part_key(part)?;
+        let (name, kind) = describe_part(part);
+        let outcome = match &key {
+          Some(existing_key) if existing_keys.contains(existing_key) => StitchOutcome::Duplicated,
+          Some(_) => StitchOutcome::Appended,
+          None => StitchOutcome::Skipped,
+        };
+        entries.push(StitchEntry { key, name, kind, outcome });
+      }
+
       target_contract
         .nodes
         .extend(fragment.nodes.into_iter().map(resolve_contract_part));
-      Ok(())
+      StitchReport { entries }
     }
     ResolveConflictStrategy::Replace => {
       let mut next_id = max_target_id;
@@ -98,8 +192,22 @@ pub fn stitch_fragment_nodes_into_contract(
         append_nodes.push(part);
       }
 
-      for (idx, ids, mut part) in replacements {
-        renumber_part_with_snapshot(&mut part, &ids, &mut next_id)?;
+      let mut entries = Vec::with_capacity(replacements.len() + append_nodes.len());
+
+      for (idx, old_ids, mut part) in replacements {
+        let key = contract_part_key(&part)?;
+        let (name, kind) = describe_part(&part);
+        let range_start = next_id;
+        renumber_part_with_snapshot(&mut part, &old_ids, &mut next_id)?;
+        entries.push(StitchEntry {
+          key,
+          name,
+          kind,
+          outcome: StitchOutcome::Replaced {
+            old_ids,
+            new_id_range: (range_start, next_id),
+          },
+        });
         let slot = target_contract.nodes.get_mut(idx).ok_or_else(|| {
           AstError::InvalidContractStructure("Replacement index out of bounds".to_string())
         })?;
@@ -107,21 +215,480 @@ pub fn stitch_fragment_nodes_into_contract(
       }
 
       for mut part in append_nodes {
+        let key = contract_part_key(&part)?;
+        let (name, kind) = describe_part(&part);
         renumber_part_with_snapshot(&mut part, &[], &mut next_id)?;
+        entries.push(StitchEntry {
+          key: key.clone(),
+          name,
+          kind,
+          outcome: if key.is_some() {
+            StitchOutcome::Appended
+          } else {
+            StitchOutcome::Skipped
+          },
+        });
         target_contract.nodes.push(part);
       }
 
-      Ok(())
+      StitchReport { entries }
+    }
+  };
+
+  selector::check_selector_collisions(&target_contract.nodes)?;
+  Ok(report)
+}
+
+/// Merges a fragment's file-level declarations (free functions, structs, enums, user-defined value
+/// types, file-level constants, imports) directly into `target.nodes`, the same way
+/// [`stitch_fragment_nodes_into_contract`] merges a fragment's members into a single contract.
+/// `ConflictKey`'s name+signature/kind matching only makes sense inside a `ContractDefinition`, so
+/// file-level members are tracked under the separate [`SourceUnitConflictKey`] instead; everything
+/// else (pragmas, `ContractDefinition`s, file-level `using` directives) is appended untracked.
+pub fn stitch_fragment_parts_into_source_unit(
+  target: &mut SourceUnit,
+  fragment: &SourceUnit,
+  max_target_id: i64,
+  strategy: ResolveConflictStrategy,
+) -> Result<SourceUnitStitchReport, AstError> {
+  match strategy {
+    ResolveConflictStrategy::Safe => {
+      let mut next_id = max_target_id;
+      let fragment = utils::clone_with_new_ids(fragment, &mut next_id)?;
+
+      let existing_keys: std::collections::HashSet<SourceUnitConflictKey> = target
+        .nodes
+        .iter()
+        .filter_map(|part| source_unit_part_key(part).ok().flatten())
+        .collect();
+
+      let mut entries = Vec::with_capacity(fragment.nodes.len());
+      for part in &fragment.nodes {
+        let key = source_unit_part_key(part)?;
+        let (name, kind) = describe_source_unit_part(part);
+        let outcome = match &key {
+          Some(existing_key) if existing_keys.contains(existing_key) => StitchOutcome::Duplicated,
+          Some(_) => StitchOutcome::Appended,
+          None => StitchOutcome::Skipped,
+        };
+        entries.push(SourceUnitStitchEntry { key, name, kind, outcome });
+      }
+
+      target.nodes.extend(fragment.nodes);
+      Ok(SourceUnitStitchReport { entries })
+    }
+    ResolveConflictStrategy::Replace => {
+      let mut next_id = max_target_id;
+      let mut target_index_by_key: HashMap<SourceUnitConflictKey, (usize, Vec<i64>)> =
+        HashMap::new();
+      for (idx, part) in target.nodes.iter().enumerate() {
+        if let Some(key) = source_unit_part_key(part)? {
+          let ids = collect_source_unit_part_ids(part)?;
+          target_index_by_key.insert(key, (idx, ids));
+        }
+      }
+
+      let mut replacements: Vec<(usize, Vec<i64>, SourceUnitPart)> = Vec::new();
+      let mut append_nodes: Vec<SourceUnitPart> = Vec::new();
+
+      for part in fragment.nodes.iter().cloned() {
+        if let Some(key) = source_unit_part_key(&part)? {
+          if let Some((idx, ids)) = target_index_by_key.remove(&key) {
+            replacements.push((idx, ids, part));
+            continue;
+          }
+        }
+        append_nodes.push(part);
+      }
+
+      let mut entries = Vec::with_capacity(replacements.len() + append_nodes.len());
+
+      for (idx, old_ids, mut part) in replacements {
+        let key = source_unit_part_key(&part)?;
+        let (name, kind) = describe_source_unit_part(&part);
+        let range_start = next_id;
+        renumber_source_unit_part_with_snapshot(&mut part, &old_ids, &mut next_id)?;
+        entries.push(SourceUnitStitchEntry {
+          key,
+          name,
+          kind,
+          outcome: StitchOutcome::Replaced {
+            old_ids,
+            new_id_range: (range_start, next_id),
+          },
+        });
+        let slot = target.nodes.get_mut(idx).ok_or_else(|| {
+          AstError::InvalidContractStructure("Replacement index out of bounds".to_string())
+        })?;
+        *slot = part;
+      }
+
+      for mut part in append_nodes {
+        let key = source_unit_part_key(&part)?;
+        let (name, kind) = describe_source_unit_part(&part);
+        renumber_source_unit_part_with_snapshot(&mut part, &[], &mut next_id)?;
+        entries.push(SourceUnitStitchEntry {
+          key: key.clone(),
+          name,
+          kind,
+          outcome: if key.is_some() {
+            StitchOutcome::Appended
+          } else {
+            StitchOutcome::Skipped
+          },
+        });
+        target.nodes.push(part);
+      }
+
+      Ok(SourceUnitStitchReport { entries })
+    }
+  }
+}
+
+/// The sibling-splicing counterpart to [`stitch_fragment_nodes_into_contract`], for a fragment
+/// whose `ContractDefinition` isn't its only top-level node: free functions, constants, structs,
+/// enums, UDVTs, and `using ... for` directives written alongside a contract have nowhere to live
+/// inside that contract merge, so once the contract's own members have been stitched in via
+/// [`stitch_fragment_nodes_into_contract`], this splices whatever's left of the fragment's file
+/// scope into `target` instead - same renumbering and conflict handling as
+/// [`stitch_fragment_parts_into_source_unit`], just skipping the `PragmaDirective`/
+/// `ContractDefinition` entries (the latter would otherwise re-append the very contract whose
+/// members just got merged); the fragment's `ImportDirective`s are merged separately through
+/// `merge_fragment_imports`.
+pub fn stitch_fragment_file_scope_siblings(
+  target: &mut SourceUnit,
+  fragment: &SourceUnit,
+  max_target_id: i64,
+  strategy: ResolveConflictStrategy,
+) -> Result<SourceUnitStitchReport, AstError> {
+  let mut siblings = fragment.clone();
+  siblings.nodes = fragment
+    .nodes
+    .iter()
+    .filter(|part| {
+      !matches!(
+        part,
+        SourceUnitPart::PragmaDirective(_) | SourceUnitPart::ContractDefinition(_)
+      )
+    })
+    .cloned()
+    .collect();
+
+  let mut next_id = max_target_id;
+  merge_fragment_imports(target, &mut siblings, &mut next_id)?;
+
+  stitch_fragment_parts_into_source_unit(target, &siblings, next_id, strategy)
+}
+
+/// Splits `fragment`'s `ImportDirective`s out and merges them into `target` directly, ahead of
+/// everything [`stitch_fragment_file_scope_siblings`] hands the rest of `fragment` to
+/// [`stitch_fragment_parts_into_source_unit`] for: an import already present in `target` (matched
+/// by resolved absolute path, same as [`source_unit_part_key`]) is dropped instead of duplicated,
+/// and one whose alias collides with an alias `target` already has in scope *from a different
+/// file* gets a fresh, unique alias - with every `Identifier`/`UserDefinedTypeName` reference to
+/// the old alias in what's left of `fragment` rewritten to match, so a shadow fragment that leans
+/// on an external library doesn't silently reference a name that no longer resolves once stitched
+/// in.
+fn merge_fragment_imports(
+  target: &mut SourceUnit,
+  fragment: &mut SourceUnit,
+  next_id: &mut i64,
+) -> Result<(), AstError> {
+  let mut target_paths: std::collections::HashSet<String> = std::collections::HashSet::new();
+  let mut target_aliases: HashMap<String, String> = HashMap::new();
+  for part in &target.nodes {
+    if let SourceUnitPart::ImportDirective(import) = part {
+      let value =
+        serde_json::to_value(import.as_ref()).map_err(|err| AstError::JsonError(err.to_string()))?;
+      let Some(path) = import_key(&value) else {
+        continue;
+      };
+      for alias in import_directive_aliases(&value) {
+        target_aliases.insert(alias, path.clone());
+      }
+      target_paths.insert(path);
+    }
+  }
+
+  let mut rest = Vec::with_capacity(fragment.nodes.len());
+  let mut imports = Vec::new();
+  for part in fragment.nodes.drain(..) {
+    if matches!(part, SourceUnitPart::ImportDirective(_)) {
+      imports.push(part);
+    } else {
+      rest.push(part);
+    }
+  }
+  fragment.nodes = rest;
+
+  for part in imports {
+    let SourceUnitPart::ImportDirective(import) = &part else {
+      unreachable!("filtered to ImportDirective above")
+    };
+    let mut value = serde_json::to_value(import.as_ref())
+      .map_err(|err| AstError::JsonError(err.to_string()))?;
+    let Some(path) = import_key(&value) else {
+      continue;
+    };
+    if target_paths.contains(&path) {
+      continue;
+    }
+
+    for alias in import_directive_aliases(&value) {
+      let collides_with_other_file = target_aliases
+        .get(&alias)
+        .is_some_and(|existing_path| existing_path != &path);
+
+      if collides_with_other_file {
+        let fresh = fresh_alias(&alias, &target_aliases);
+        rewrite_import_alias(&mut value, &alias, &fresh);
+        fragment.nodes = fragment
+          .nodes
+          .iter()
+          .map(|node| utils::rename_identifier_references(node, &alias, &fresh))
+          .collect::<Result<Vec<_>, AstError>>()?;
+        target_aliases.insert(fresh, path.clone());
+      } else {
+        target_aliases.insert(alias, path.clone());
+      }
+    }
+
+    let mut renumbered: SourceUnitPart =
+      serde_json::from_value(value).map_err(|err| AstError::JsonError(err.to_string()))?;
+    renumber_source_unit_part_with_snapshot(&mut renumbered, &[], next_id)?;
+    target.nodes.push(renumbered);
+    target_paths.insert(path);
+  }
+
+  Ok(())
+}
+
+/// Every symbol an `ImportDirective` brings into scope under a name a later reference could
+/// collide with: each `{Foo as Bar}` pair's local alias (`Bar`) - or, lacking one, the imported
+/// symbol's own name, since that's what's actually in scope - plus the import's own `unitAlias`
+/// (`import "..." as Lib;`) when set. Untyped JSON access for the same reason [`import_key`] and
+/// `using_for_library_key` are - `ImportDirective`'s `symbolAliases`/`unitAlias` fields aren't
+/// otherwise depended on in typed form anywhere in this codebase.
+fn import_directive_aliases(directive: &Value) -> Vec<String> {
+  let mut aliases = Vec::new();
+  if let Some(unit_alias) = directive.get("unitAlias").and_then(Value::as_str) {
+    if !unit_alias.is_empty() {
+      aliases.push(unit_alias.to_string());
+    }
+  }
+  if let Some(symbol_aliases) = directive.get("symbolAliases").and_then(Value::as_array) {
+    for entry in symbol_aliases {
+      let local = entry.get("local").and_then(Value::as_str);
+      let foreign = entry
+        .get("foreign")
+        .and_then(|foreign| foreign.get("name"))
+        .and_then(Value::as_str);
+      if let Some(name) = local.or(foreign) {
+        aliases.push(name.to_string());
+      }
+    }
+  }
+  aliases
+}
+
+/// Renames every occurrence of `old_alias` as an import's `unitAlias` or a `symbolAliases` entry's
+/// `local` name to `new_alias`, mirroring what [`import_directive_aliases`] reads.
+fn rewrite_import_alias(directive: &mut Value, old_alias: &str, new_alias: &str) {
+  if let Some(Value::String(unit_alias)) = directive.get_mut("unitAlias") {
+    if unit_alias == old_alias {
+      *unit_alias = new_alias.to_string();
+    }
+  }
+  if let Some(Value::Array(symbol_aliases)) = directive.get_mut("symbolAliases") {
+    for entry in symbol_aliases {
+      if let Some(Value::String(local)) = entry.get_mut("local") {
+        if local == old_alias {
+          *local = new_alias.to_string();
+        }
+      }
+    }
+  }
+}
+
+/// The first `{base}_{n}` (n starting at 2) not already in use as a key of `taken`, so a colliding
+/// import alias gets a name that reads as an obvious variant of the original rather than an
+/// arbitrary generated one.
+fn fresh_alias(base: &str, taken: &HashMap<String, String>) -> String {
+  let mut candidate = format!("{base}_2");
+  let mut suffix = 2;
+  while taken.contains_key(&candidate) {
+    suffix += 1;
+    candidate = format!("{base}_{suffix}");
+  }
+  candidate
+}
+
+/// Mirrors [`StitchReport`]/[`StitchEntry`], but for [`stitch_fragment_parts_into_source_unit`]'s
+/// file-level members, which are tracked under [`SourceUnitConflictKey`] instead of `ConflictKey`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SourceUnitStitchReport {
+  pub entries: Vec<SourceUnitStitchEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SourceUnitStitchEntry {
+  pub key: Option<SourceUnitConflictKey>,
+  pub name: String,
+  pub kind: &'static str,
+  pub outcome: StitchOutcome,
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum SourceUnitConflictKey {
+  Function { name: String, signature: Vec<String> },
+  Struct(String),
+  Enum(String),
+  UserDefinedValueType(String),
+  Constant(String),
+  Import(String),
+}
+
+/// The file-level counterpart of [`contract_part_key`]: free functions key on name + parameter
+/// types (the same [`function_signature`] a contract-level function uses, since a selector
+/// collision between two free functions is exactly as undeployable as between two contract
+/// members); structs/enums/UDVTs/constants key on name; imports key on their resolved path so the
+/// same library isn't imported twice. Everything else this doesn't track a key for (pragmas,
+/// `ContractDefinition`s, file-level `using` directives) is always appended, never replaced.
+fn source_unit_part_key(part: &SourceUnitPart) -> Result<Option<SourceUnitConflictKey>, AstError> {
+  match part {
+    SourceUnitPart::FunctionDefinition(function) => Ok(Some(SourceUnitConflictKey::Function {
+      name: function.name.clone(),
+      signature: function_signature(function)?,
+    })),
+    SourceUnitPart::StructDefinition(struct_definition) => Ok(Some(SourceUnitConflictKey::Struct(
+      struct_definition.name.clone(),
+    ))),
+    SourceUnitPart::EnumDefinition(enum_definition) => Ok(Some(SourceUnitConflictKey::Enum(
+      enum_definition.name.clone(),
+    ))),
+    SourceUnitPart::UserDefinedValueTypeDefinition(value_type) => Ok(Some(
+      SourceUnitConflictKey::UserDefinedValueType(value_type.name.clone()),
+    )),
+    SourceUnitPart::VariableDeclaration(variable) => {
+      Ok(Some(SourceUnitConflictKey::Constant(variable.name.clone())))
+    }
+    SourceUnitPart::ImportDirective(import) => {
+      let value =
+        serde_json::to_value(import).map_err(|err| AstError::JsonError(err.to_string()))?;
+      Ok(import_key(&value).map(SourceUnitConflictKey::Import))
     }
+    _ => Ok(None),
   }
 }
 
+/// An import's resolved absolute path, or (if the fragment hasn't gone through solc's import
+/// resolution yet) the raw import string as written, read as untyped JSON since `ImportDirective`'s
+/// exact field names aren't depended on in typed form anywhere else in this codebase - the same
+/// reasoning [`using_for_library_key`] applies to `UsingForDirective`.
+fn import_key(directive: &Value) -> Option<String> {
+  directive
+    .get("absolutePath")
+    .and_then(Value::as_str)
+    .or_else(|| directive.get("file").and_then(Value::as_str))
+    .map(str::to_string)
+}
+
+/// The human-readable name and kind tag for a file-level fragment member, the [`SourceUnit`]
+/// counterpart of [`describe_part`].
+fn describe_source_unit_part(part: &SourceUnitPart) -> (String, &'static str) {
+  match part {
+    SourceUnitPart::PragmaDirective(_) => ("pragma directive".to_string(), "pragma_directive"),
+    SourceUnitPart::ImportDirective(import) => {
+      let value = serde_json::to_value(import).unwrap_or(Value::Null);
+      let name = import_key(&value).unwrap_or_default();
+      (name, "import_directive")
+    }
+    SourceUnitPart::ContractDefinition(contract) => (contract.name.clone(), "contract"),
+    SourceUnitPart::FunctionDefinition(function) => (function.name.clone(), "function"),
+    SourceUnitPart::VariableDeclaration(variable) => (variable.name.clone(), "constant"),
+    SourceUnitPart::StructDefinition(struct_definition) => {
+      (struct_definition.name.clone(), "struct")
+    }
+    SourceUnitPart::EnumDefinition(enum_definition) => (enum_definition.name.clone(), "enum"),
+    SourceUnitPart::UserDefinedValueTypeDefinition(value_type) => {
+      (value_type.name.clone(), "user_defined_value_type")
+    }
+    other => {
+      let node_type = serde_json::to_value(other)
+        .ok()
+        .and_then(|value| {
+          value
+            .get("nodeType")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+      (node_type.clone(), node_type_kind_tag(&node_type))
+    }
+  }
+}
+
+/// A static kind tag for a file-level member `describe_source_unit_part` doesn't have a dedicated
+/// match arm for (and so doesn't track a `SourceUnitConflictKey` for either).
+fn node_type_kind_tag(node_type: &str) -> &'static str {
+  match node_type {
+    "UsingForDirective" => "using_for_directive",
+    "ErrorDefinition" => "error",
+    _ => "unknown",
+  }
+}
+
+fn collect_source_unit_part_ids(part: &SourceUnitPart) -> Result<Vec<i64>, AstError> {
+  let json = serde_json::to_value(part).map_err(|err| AstError::JsonError(err.to_string()))?;
+  let mut ids = Vec::new();
+  collect_ids_from_value(&json, &mut ids);
+  Ok(ids)
+}
+
+fn renumber_source_unit_part_with_snapshot(
+  part: &mut SourceUnitPart,
+  snapshot: &[i64],
+  next_id: &mut i64,
+) -> Result<(), AstError> {
+  let mut json =
+    serde_json::to_value(&*part).map_err(|err| AstError::JsonError(err.to_string()))?;
+  let mut snapshot_iter = snapshot.iter();
+  assign_ids_with_snapshot(&mut json, &mut snapshot_iter, next_id);
+  utils::sanitize_ast_value(&mut json);
+  *part = serde_json::from_value(json).map_err(|err| AstError::JsonError(err.to_string()))?;
+  Ok(())
+}
+
 fn resolve_contract_part(part: ContractDefinitionPart) -> ContractDefinitionPart {
   part
 }
 
+/// The human-readable name and kind tag for a fragment member, used to populate [`StitchEntry`]
+/// independently of its `ConflictKey`. Also reused by [`super::dot`] to label graph nodes.
+pub(crate) fn describe_part(part: &ContractDefinitionPart) -> (String, &'static str) {
+  match part {
+    ContractDefinitionPart::FunctionDefinition(function) => (function.name.clone(), "function"),
+    ContractDefinitionPart::VariableDeclaration(variable) => (variable.name.clone(), "variable"),
+    ContractDefinitionPart::EventDefinition(event) => (event.name.clone(), "event"),
+    ContractDefinitionPart::ErrorDefinition(error) => (error.name.clone(), "error"),
+    ContractDefinitionPart::ModifierDefinition(modifier) => (modifier.name.clone(), "modifier"),
+    ContractDefinitionPart::StructDefinition(struct_definition) => {
+      (struct_definition.name.clone(), "struct")
+    }
+    ContractDefinitionPart::EnumDefinition(enum_definition) => {
+      (enum_definition.name.clone(), "enum")
+    }
+    ContractDefinitionPart::UserDefinedValueTypeDefinition(value_type) => {
+      (value_type.name.clone(), "user_defined_value_type")
+    }
+    ContractDefinitionPart::UsingForDirective(_) => {
+      ("using for directive".to_string(), "using_for_directive")
+    }
+  }
+}
+
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
-enum ConflictKey {
+pub enum ConflictKey {
   Function {
     name: String,
     signature: Vec<String>,
@@ -134,6 +701,11 @@ enum ConflictKey {
   Struct(String),
   Enum(String),
   UserDefinedValueType(String),
+  UsingFor {
+    library: Option<String>,
+    target_type: Option<String>,
+    global: bool,
+  },
 }
 
 fn contract_part_key(part: &ContractDefinitionPart) -> Result<Option<ConflictKey>, AstError> {
@@ -164,8 +736,53 @@ fn contract_part_key(part: &ContractDefinitionPart) -> Result<Option<ConflictKey
     ContractDefinitionPart::UserDefinedValueTypeDefinition(value_type) => Ok(Some(
       ConflictKey::UserDefinedValueType(value_type.name.clone()),
     )),
-    ContractDefinitionPart::UsingForDirective(_) => Ok(None),
+    ContractDefinitionPart::UsingForDirective(using_for) => {
+      let value =
+        serde_json::to_value(using_for).map_err(|err| AstError::JsonError(err.to_string()))?;
+      Ok(Some(ConflictKey::UsingFor {
+        library: using_for_library_key(&value)?,
+        target_type: type_name_key(value.get("typeName"))?,
+        global: value.get("global").and_then(Value::as_bool).unwrap_or(false),
+      }))
+    }
+  }
+}
+
+/// The library half of a `using` directive's conflict key: either the single library's
+/// id-stripped type identifier (`using SafeMath for ...`), or an id-stripped dump of the whole
+/// per-function binding list (`using {add, sub} for ...`), read as untyped JSON like
+/// [`parameter_type_key`] since `UsingForDirective`'s exact field names aren't depended on
+/// elsewhere in this codebase.
+fn using_for_library_key(directive: &Value) -> Result<Option<String>, AstError> {
+  if let Some(library_name) = directive.get("libraryName") {
+    return type_name_key(Some(library_name));
+  }
+  if let Some(function_list) = directive.get("functionList") {
+    return Ok(Some(serialise_without_ids(function_list)?));
+  }
+  Ok(None)
+}
+
+/// The same id-stripped type-identifier logic [`parameter_type_key`] uses for a function
+/// parameter's type, applied to an arbitrary solc `TypeName` node (or `None` for `using X for *`).
+fn type_name_key(type_name: Option<&Value>) -> Result<Option<String>, AstError> {
+  let Some(type_name) = type_name else {
+    return Ok(None);
+  };
+  let descriptions = type_name.get("typeDescriptions");
+  if let Some(identifier) = descriptions
+    .and_then(|d| d.get("typeIdentifier"))
+    .and_then(Value::as_str)
+  {
+    return Ok(Some(identifier.to_string()));
   }
+  if let Some(type_string) = descriptions
+    .and_then(|d| d.get("typeString"))
+    .and_then(Value::as_str)
+  {
+    return Ok(Some(type_string.to_string()));
+  }
+  Ok(Some(serialise_without_ids(type_name)?))
 }
 
 pub(crate) fn function_signature(function: &FunctionDefinition) -> Result<Vec<String>, AstError> {
@@ -366,6 +983,71 @@ uint256 public replacementCounter;
     assert_eq!(contract.name, "Target");
   }
 
+  const IMPORTING_SOURCE: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+import "Imported.sol";
+
+contract Root {}
+"#;
+
+  const IMPORTED_SOURCE: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Target {}
+"#;
+
+  #[test]
+  fn locates_contract_reached_through_an_import() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+    let settings = AstOrchestrator::sanitize_settings(None).expect("sanitize default settings");
+    let sources = BTreeMap::from([
+      ("Root.sol".to_string(), IMPORTING_SOURCE.to_string()),
+      ("Imported.sol".to_string(), IMPORTED_SOURCE.to_string()),
+    ]);
+    let units = parser::parse_source_units(&sources, &solc, &settings).expect("parse sources");
+
+    let (source, idx) = find_target_contract(&units, "Target").expect("find target contract");
+    assert_eq!(source, "Imported.sol");
+    let SourceUnitPart::ContractDefinition(contract) = &units[source].nodes[idx] else {
+      panic!("Expected contract definition");
+    };
+    assert_eq!(contract.name, "Target");
+  }
+
+  #[test]
+  fn locates_contract_by_fully_qualified_name() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+    let settings = AstOrchestrator::sanitize_settings(None).expect("sanitize default settings");
+    let sources = BTreeMap::from([
+      ("Root.sol".to_string(), IMPORTING_SOURCE.to_string()),
+      ("Imported.sol".to_string(), IMPORTED_SOURCE.to_string()),
+    ]);
+    let units = parser::parse_source_units(&sources, &solc, &settings).expect("parse sources");
+
+    let (source, _) =
+      find_target_contract(&units, "Imported.sol:Target").expect("find target contract");
+    assert_eq!(source, "Imported.sol");
+  }
+
+  #[test]
+  fn errors_when_target_contract_is_missing() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+    let settings = AstOrchestrator::sanitize_settings(None).expect("sanitize default settings");
+    let sources = BTreeMap::from([("Root.sol".to_string(), IMPORTING_SOURCE.to_string())]);
+    let units = parser::parse_source_units(&sources, &solc, &settings).expect("parse sources");
+
+    assert!(find_target_contract(&units, "Missing").is_err());
+  }
+
   #[test]
   fn stitches_fragment_into_contract() {
     let Some(solc) = find_default_solc() else {
@@ -408,7 +1090,7 @@ uint256 public replacementCounter;
     let idx = find_instrumented_contract_index(&unit, Some("Target")).expect("target index");
     let max_id = utils::max_id(&unit).expect("max target id");
 
-    stitch_fragment_nodes_into_contract(
+    let report = stitch_fragment_nodes_into_contract(
       &mut unit,
       idx,
       &fragment,
@@ -417,6 +1099,20 @@ uint256 public replacementCounter;
     )
     .expect("stitch safe");
 
+    assert!(
+      report
+        .entries
+        .iter()
+        .any(|entry| entry.name == "hello" && entry.outcome == StitchOutcome::Duplicated),
+      "expected the conflicting hello() member to be reported as Duplicated"
+    );
+    assert!(
+      report.entries.iter().any(|entry| {
+        entry.name == "replacementCounter" && entry.outcome == StitchOutcome::Appended
+      }),
+      "expected the non-conflicting replacementCounter member to be reported as Appended"
+    );
+
     let SourceUnitPart::ContractDefinition(contract) = &unit.nodes[idx] else {
       panic!("Expected contract definition");
     };
@@ -443,7 +1139,7 @@ uint256 public replacementCounter;
     let idx = find_instrumented_contract_index(&unit, Some("Target")).expect("target index");
     let max_id = utils::max_id(&unit).expect("max target id");
 
-    let original_function_id = {
+    let (original_function_id, original_ids) = {
       let SourceUnitPart::ContractDefinition(contract) = &unit.nodes[idx] else {
         panic!("Expected contract definition");
       };
@@ -452,7 +1148,7 @@ uint256 public replacementCounter;
         .iter()
         .find_map(|part| match part {
           ContractDefinitionPart::FunctionDefinition(function) if function.name == "hello" => {
-            Some(function.id)
+            Some((function.id, collect_ids(part).expect("collect original ids")))
           }
           _ => None,
         })
@@ -462,7 +1158,7 @@ uint256 public replacementCounter;
     let fragment =
       parser::parse_fragment_contract(REPLACEMENT_FRAGMENT, &solc, &settings).expect("fragment");
 
-    stitch_fragment_nodes_into_contract(
+    let report = stitch_fragment_nodes_into_contract(
       &mut unit,
       idx,
       &fragment,
@@ -471,6 +1167,24 @@ uint256 public replacementCounter;
     )
     .expect("stitch replace");
 
+    let hello_entry = report
+      .entries
+      .iter()
+      .find(|entry| entry.name == "hello")
+      .expect("report entry for replaced hello()");
+    match &hello_entry.outcome {
+      StitchOutcome::Replaced { old_ids, .. } => {
+        assert_eq!(old_ids, &original_ids)
+      }
+      other => panic!("expected hello() to be reported as Replaced, got {other:?}"),
+    }
+    assert!(
+      report.entries.iter().any(|entry| {
+        entry.name == "replacementCounter" && entry.outcome == StitchOutcome::Appended
+      }),
+      "expected the new replacementCounter member to be reported as Appended"
+    );
+
     let SourceUnitPart::ContractDefinition(contract) = &unit.nodes[idx] else {
       panic!("Expected contract definition");
     };
@@ -515,4 +1229,355 @@ uint256 public replacementCounter;
       .expect("appended variable present");
     assert!((appended_variable.id as i64) > max_id);
   }
+
+  const TARGET_WITH_USING_FOR: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Target {
+  using SafeMath for uint256;
+}
+"#;
+
+  const USING_FOR_FRAGMENT: &str = "using SafeMath for uint256;";
+
+  #[test]
+  fn replace_strategy_dedupes_matching_using_for_directives() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+    let settings = AstOrchestrator::sanitize_settings(None).expect("sanitize default settings");
+    let mut unit = parser::parse_source_ast(TARGET_WITH_USING_FOR, "Target.sol", &solc, &settings)
+      .expect("parse target source");
+    let fragment =
+      parser::parse_fragment_contract(USING_FOR_FRAGMENT, &solc, &settings).expect("fragment");
+    let idx = find_instrumented_contract_index(&unit, Some("Target")).expect("target index");
+    let max_id = utils::max_id(&unit).expect("max target id");
+
+    let report = stitch_fragment_nodes_into_contract(
+      &mut unit,
+      idx,
+      &fragment,
+      max_id,
+      ResolveConflictStrategy::Replace,
+    )
+    .expect("stitch replace");
+
+    assert!(
+      report
+        .entries
+        .iter()
+        .any(|entry| matches!(entry.outcome, StitchOutcome::Replaced { .. })),
+      "expected the re-bound using-for directive to be reported as Replaced"
+    );
+
+    let SourceUnitPart::ContractDefinition(contract) = &unit.nodes[idx] else {
+      panic!("Expected contract definition");
+    };
+    let using_for_count = contract
+      .nodes
+      .iter()
+      .filter(|part| matches!(part, ContractDefinitionPart::UsingForDirective(_)))
+      .count();
+    assert_eq!(
+      using_for_count, 1,
+      "expected Replace to dedupe the matching using-for directive instead of duplicating it"
+    );
+  }
+
+  #[test]
+  fn safe_strategy_reports_duplicate_using_for_directives() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+    let settings = AstOrchestrator::sanitize_settings(None).expect("sanitize default settings");
+    let mut unit = parser::parse_source_ast(TARGET_WITH_USING_FOR, "Target.sol", &solc, &settings)
+      .expect("parse target source");
+    let fragment =
+      parser::parse_fragment_contract(USING_FOR_FRAGMENT, &solc, &settings).expect("fragment");
+    let idx = find_instrumented_contract_index(&unit, Some("Target")).expect("target index");
+    let max_id = utils::max_id(&unit).expect("max target id");
+
+    let report = stitch_fragment_nodes_into_contract(
+      &mut unit,
+      idx,
+      &fragment,
+      max_id,
+      ResolveConflictStrategy::Safe,
+    )
+    .expect("stitch safe");
+
+    assert!(
+      report
+        .entries
+        .iter()
+        .any(|entry| entry.outcome == StitchOutcome::Duplicated),
+      "expected the re-bound using-for directive to be reported as Duplicated under Safe"
+    );
+  }
+
+  const TARGET_WITH_FREE_FUNCTION: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+function helper() pure returns (uint256) {
+  return 1;
+}
+
+contract Target {}
+"#;
+
+  const FREE_FUNCTION_FRAGMENT: &str = "function other() pure returns (uint256) { return 2; }";
+
+  const REPLACEMENT_FREE_FUNCTION_FRAGMENT: &str = r#"
+function helper() pure returns (uint256) {
+  return 3;
+}
+"#;
+
+  #[test]
+  fn stitches_free_function_into_source_unit() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+    let settings = AstOrchestrator::sanitize_settings(None).expect("sanitize default settings");
+    let mut unit =
+      parser::parse_source_ast(TARGET_WITH_FREE_FUNCTION, "Target.sol", &solc, &settings)
+        .expect("parse target source");
+    let fragment =
+      parser::parse_source_ast(FREE_FUNCTION_FRAGMENT, "Fragment.sol", &solc, &settings)
+        .expect("parse fragment source unit");
+    let max_id = utils::max_id(&unit).expect("max target id");
+
+    let report = stitch_fragment_parts_into_source_unit(
+      &mut unit,
+      &fragment,
+      max_id,
+      ResolveConflictStrategy::Safe,
+    )
+    .expect("stitch safe");
+
+    assert!(
+      report
+        .entries
+        .iter()
+        .any(|entry| entry.name == "other" && entry.outcome == StitchOutcome::Appended),
+      "expected the non-conflicting free function to be reported as Appended"
+    );
+    assert!(unit.nodes.iter().any(|part| matches!(part,
+      SourceUnitPart::FunctionDefinition(function) if function.name == "other"
+    )));
+  }
+
+  #[test]
+  fn replace_strategy_overwrites_conflicting_free_function() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+    let settings = AstOrchestrator::sanitize_settings(None).expect("sanitize default settings");
+    let mut unit =
+      parser::parse_source_ast(TARGET_WITH_FREE_FUNCTION, "Target.sol", &solc, &settings)
+        .expect("parse target source");
+    let fragment = parser::parse_source_ast(
+      REPLACEMENT_FREE_FUNCTION_FRAGMENT,
+      "Fragment.sol",
+      &solc,
+      &settings,
+    )
+    .expect("parse fragment source unit");
+    let max_id = utils::max_id(&unit).expect("max target id");
+
+    let report = stitch_fragment_parts_into_source_unit(
+      &mut unit,
+      &fragment,
+      max_id,
+      ResolveConflictStrategy::Replace,
+    )
+    .expect("stitch replace");
+
+    assert!(
+      report.entries.iter().any(|entry| {
+        entry.name == "helper" && matches!(entry.outcome, StitchOutcome::Replaced { .. })
+      }),
+      "expected the conflicting free function to be reported as Replaced"
+    );
+
+    let helper_functions = unit
+      .nodes
+      .iter()
+      .filter(|part| {
+        matches!(part, SourceUnitPart::FunctionDefinition(function) if function.name == "helper")
+      })
+      .count();
+    assert_eq!(helper_functions, 1);
+  }
+
+  const LIB_A: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+library LibA {
+  function helper(uint256 x) internal pure returns (uint256) {
+    return x + 1;
+  }
+}
+"#;
+
+  const LIB_B: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+library LibB {
+  function helper(uint256 x) internal pure returns (uint256) {
+    return x + 2;
+  }
+}
+"#;
+
+  const TARGET_WITH_IMPORT: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+import {LibA as Lib} from "LibA.sol";
+
+contract Target {
+  function callMe() internal pure returns (uint256) {
+    return Lib.helper(1);
+  }
+}
+"#;
+
+  const FRAGMENT_REIMPORTING_LIB_A: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+import {LibA as Lib} from "LibA.sol";
+
+function useLib(uint256 x) pure returns (uint256) {
+  return Lib.helper(x);
+}
+"#;
+
+  const FRAGMENT_IMPORTING_LIB_B: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+import {LibB as Lib} from "LibB.sol";
+
+function useLib(uint256 x) pure returns (uint256) {
+  return Lib.helper(x);
+}
+"#;
+
+  #[test]
+  fn merge_fragment_imports_skips_an_import_already_present_by_path() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+    let settings = AstOrchestrator::sanitize_settings(None).expect("sanitize default settings");
+
+    let target_sources = BTreeMap::from([
+      ("Target.sol".to_string(), TARGET_WITH_IMPORT.to_string()),
+      ("LibA.sol".to_string(), LIB_A.to_string()),
+    ]);
+    let mut target_units =
+      parser::parse_source_units(&target_sources, &solc, &settings).expect("parse target");
+    let mut unit = target_units.remove("Target.sol").expect("target unit");
+
+    let fragment_sources = BTreeMap::from([
+      ("Fragment.sol".to_string(), FRAGMENT_REIMPORTING_LIB_A.to_string()),
+      ("LibA.sol".to_string(), LIB_A.to_string()),
+    ]);
+    let mut fragment_units =
+      parser::parse_source_units(&fragment_sources, &solc, &settings).expect("parse fragment");
+    let fragment = fragment_units.remove("Fragment.sol").expect("fragment unit");
+
+    let max_id = utils::max_id(&unit).expect("max target id");
+    stitch_fragment_file_scope_siblings(&mut unit, &fragment, max_id, ResolveConflictStrategy::Safe)
+      .expect("stitch siblings");
+
+    let import_count = unit
+      .nodes
+      .iter()
+      .filter(|part| matches!(part, SourceUnitPart::ImportDirective(_)))
+      .count();
+    assert_eq!(import_count, 1, "LibA.sol should only be imported once");
+
+    assert!(unit.nodes.iter().any(|part| matches!(part,
+      SourceUnitPart::FunctionDefinition(function) if function.name == "useLib"
+    )));
+  }
+
+  #[test]
+  fn merge_fragment_imports_rewrites_a_colliding_alias_to_a_fresh_name() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+    let settings = AstOrchestrator::sanitize_settings(None).expect("sanitize default settings");
+
+    let target_sources = BTreeMap::from([
+      ("Target.sol".to_string(), TARGET_WITH_IMPORT.to_string()),
+      ("LibA.sol".to_string(), LIB_A.to_string()),
+    ]);
+    let mut target_units =
+      parser::parse_source_units(&target_sources, &solc, &settings).expect("parse target");
+    let mut unit = target_units.remove("Target.sol").expect("target unit");
+
+    let fragment_sources = BTreeMap::from([
+      ("Fragment.sol".to_string(), FRAGMENT_IMPORTING_LIB_B.to_string()),
+      ("LibB.sol".to_string(), LIB_B.to_string()),
+    ]);
+    let mut fragment_units =
+      parser::parse_source_units(&fragment_sources, &solc, &settings).expect("parse fragment");
+    let fragment = fragment_units.remove("Fragment.sol").expect("fragment unit");
+
+    let max_id = utils::max_id(&unit).expect("max target id");
+    stitch_fragment_file_scope_siblings(&mut unit, &fragment, max_id, ResolveConflictStrategy::Safe)
+      .expect("stitch siblings");
+
+    let import_count = unit
+      .nodes
+      .iter()
+      .filter(|part| matches!(part, SourceUnitPart::ImportDirective(_)))
+      .count();
+    assert_eq!(
+      import_count, 2,
+      "LibA.sol and LibB.sol are different files, both should be kept"
+    );
+
+    let lib_b_import = unit
+      .nodes
+      .iter()
+      .find_map(|part| {
+        let SourceUnitPart::ImportDirective(import) = part else {
+          return None;
+        };
+        let value = serde_json::to_value(import.as_ref()).expect("serialize import");
+        (import_key(&value).as_deref() == Some("LibB.sol")).then_some(value)
+      })
+      .expect("LibB.sol import present");
+    assert!(
+      serde_json::to_string(&lib_b_import)
+        .expect("serialize import")
+        .contains("\"local\":\"Lib_2\""),
+      "LibB.sol's colliding alias should have been rewritten to Lib_2"
+    );
+
+    let use_lib = unit
+      .nodes
+      .iter()
+      .find(|part| matches!(part,
+        SourceUnitPart::FunctionDefinition(function) if function.name == "useLib"
+      ))
+      .expect("useLib function stitched in");
+    let serialized = serde_json::to_string(use_lib).expect("serialize useLib");
+    assert!(
+      serialized.contains("\"name\":\"Lib_2\""),
+      "useLib should reference the rewritten alias"
+    );
+    assert!(
+      !serialized.contains("\"name\":\"Lib\""),
+      "useLib should no longer reference the original, colliding alias"
+    );
+  }
 }
@@ -1,4 +1,6 @@
-use foundry_compilers::artifacts::ast::{ContractDefinition, SourceUnit};
+use std::collections::BTreeMap;
+
+use foundry_compilers::artifacts::ast::{ContractDefinition, SourceUnit, SourceUnitPart};
 use napi::{Env, JsUnknown};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
@@ -30,6 +32,50 @@ where
   env.from_js_value(value)
 }
 
+/// Renames every reference to `old_name` within `value` to `new_name`: an `Identifier` or
+/// `UserDefinedTypeName`/`IdentifierPath` node whose own `name` is `old_name`. Used to repoint a
+/// shadow fragment's references at an import alias that [`super::stitcher::merge_fragment_imports`]
+/// had to rewrite to dodge a collision with an alias already in scope in the target source unit.
+pub fn rename_identifier_references<T>(
+  value: &T,
+  old_name: &str,
+  new_name: &str,
+) -> std::result::Result<T, AstError>
+where
+  T: Serialize + DeserializeOwned,
+{
+  let mut json = serde_json::to_value(value).map_err(|err| AstError::JsonError(err.to_string()))?;
+  walk_rename(&mut json, old_name, new_name);
+  serde_json::from_value(json).map_err(|err| AstError::JsonError(err.to_string()))
+}
+
+const RENAMEABLE_NODE_TYPES: [&str; 3] = ["Identifier", "UserDefinedTypeName", "IdentifierPath"];
+
+fn walk_rename(node: &mut Value, old_name: &str, new_name: &str) {
+  match node {
+    Value::Object(map) => {
+      let is_renameable = matches!(
+        map.get("nodeType"),
+        Some(Value::String(node_type)) if RENAMEABLE_NODE_TYPES.contains(&node_type.as_str())
+      );
+      if is_renameable {
+        if let Some(Value::String(name)) = map.get_mut("name") {
+          if name == old_name {
+            *name = new_name.to_string();
+          }
+        }
+      }
+      for child in map.values_mut() {
+        walk_rename(child, old_name, new_name);
+      }
+    }
+    Value::Array(items) => items
+      .iter_mut()
+      .for_each(|child| walk_rename(child, old_name, new_name)),
+    _ => {}
+  }
+}
+
 fn walk_max_id(node: &Value, max_id: &mut i64) {
   match node {
     Value::Object(map) => {
@@ -52,7 +98,66 @@ pub fn max_id(unit: &SourceUnit) -> std::result::Result<i64, AstError> {
   Ok(max_id)
 }
 
-fn walk_renumber(node: &mut Value, next_id: &mut i64) {
+/// Extracts a serializable AST node's own `src` field (`"byteOffset:byteLength:fileIndex"`), for
+/// feeding into [`resolve_src_position`]. Goes through `serde_json::Value` rather than a typed
+/// field access since callers reach this for `Statement`, whose dozens of variants don't share a
+/// common struct to borrow `src` from directly.
+pub fn node_src<T: Serialize>(node: &T) -> Option<String> {
+  let value = serde_json::to_value(node).ok()?;
+  value.get("src")?.as_str().map(str::to_string)
+}
+
+/// Resolves a `src` string (`"byteOffset:byteLength:fileIndex"`) to a 1-based `(line, column)`
+/// within `source`, by counting newlines up to `byteOffset` - the same `"start:length:fileIndex"`
+/// convention `instrument::printer::slice_src` already parses solc's source maps with. Returns
+/// `None` when `src` is malformed or `byteOffset` falls outside `source`.
+pub fn resolve_src_position(src: &str, source: &str) -> Option<(usize, usize)> {
+  let byte_offset: usize = src.split(':').next()?.parse().ok()?;
+  let prefix = source.get(..byte_offset)?;
+  let line = prefix.matches('\n').count() + 1;
+  let column = prefix.rsplit('\n').next().unwrap_or(prefix).chars().count() + 1;
+  Some((line, column))
+}
+
+/// Classic two-row edit-distance DP between `a` and `b`: O(n·m) time, only the previous row kept
+/// rather than the full table. Powers `injectShadowAtEdges`' "did you mean" suggestions when a
+/// requested function name doesn't resolve.
+pub fn levenshtein_distance(a: &str, b: &str) -> usize {
+  let a: Vec<char> = a.chars().collect();
+  let b: Vec<char> = b.chars().collect();
+  let mut previous: Vec<usize> = (0..=b.len()).collect();
+  let mut current = vec![0usize; b.len() + 1];
+
+  for (i, &a_char) in a.iter().enumerate() {
+    current[0] = i + 1;
+    for (j, &b_char) in b.iter().enumerate() {
+      let cost = usize::from(a_char != b_char);
+      current[j + 1] = (previous[j + 1] + 1)
+        .min(current[j] + 1)
+        .min(previous[j] + cost);
+    }
+    std::mem::swap(&mut previous, &mut current);
+  }
+
+  previous[b.len()]
+}
+
+/// The highest node id in use across every source in a multi-unit project, so a fragment stitched
+/// into one unit (or a newly loaded sibling source) can be renumbered to stay unique project-wide
+/// rather than only unique within the unit it lands in.
+pub fn project_max_id(
+  sources: &std::collections::BTreeMap<String, SourceUnit>,
+) -> std::result::Result<i64, AstError> {
+  let mut highest = 0;
+  for unit in sources.values() {
+    highest = highest.max(max_id(unit)?);
+  }
+  Ok(highest)
+}
+
+/// Walks `node`'s `id` fields in pre-order, renumbering each from `next_id` - the `serde_json`
+/// fallback [`super::visit::AstVisitMut`] reaches for when a node shape isn't covered typed.
+pub(crate) fn walk_renumber(node: &mut Value, next_id: &mut i64) {
   match node {
     Value::Object(map) => {
       if let Some(id_value) = map.get_mut("id") {
@@ -83,6 +188,116 @@ pub fn renumber_contract_definition(
   Ok(())
 }
 
+fn walk_renumber_tracked(node: &mut Value, next_id: &mut i64, mapping: &mut BTreeMap<i64, i64>) {
+  match node {
+    Value::Object(map) => {
+      if let Some(Value::Number(old)) = map.get("id") {
+        if let Some(old_id) = old.as_i64() {
+          *next_id += 1;
+          mapping.insert(old_id, *next_id);
+          map.insert("id".to_string(), Value::Number((*next_id).into()));
+        }
+      }
+      for child in map.values_mut() {
+        walk_renumber_tracked(child, next_id, mapping);
+      }
+    }
+    Value::Array(items) => items
+      .iter_mut()
+      .for_each(|child| walk_renumber_tracked(child, next_id, mapping)),
+    _ => {}
+  }
+}
+
+const BACK_REFERENCE_KEYS: [&str; 2] = ["scope", "referencedDeclaration"];
+
+fn walk_rewrite_back_references(node: &mut Value, mapping: &BTreeMap<i64, i64>) {
+  match node {
+    Value::Object(map) => {
+      for key in BACK_REFERENCE_KEYS {
+        if let Some(Value::Number(num)) = map.get(key) {
+          if let Some(old_id) = num.as_i64() {
+            if let Some(new_id) = mapping.get(&old_id) {
+              map.insert(key.to_string(), Value::Number((*new_id).into()));
+            }
+          }
+        }
+      }
+      for child in map.values_mut() {
+        walk_rewrite_back_references(child, mapping);
+      }
+    }
+    Value::Array(items) => items
+      .iter_mut()
+      .for_each(|child| walk_rewrite_back_references(child, mapping)),
+    _ => {}
+  }
+}
+
+fn contract_names(unit: &SourceUnit) -> Vec<String> {
+  unit
+    .nodes
+    .iter()
+    .filter_map(|part| match part {
+      SourceUnitPart::ContractDefinition(contract) => Some(contract.name.clone()),
+      _ => None,
+    })
+    .collect()
+}
+
+/// Appends one or more standalone `ContractDefinition` subtrees onto `base`'s top-level `nodes`,
+/// building on [`max_id`] and the same renumbering [`renumber_contract_definition`] uses: each
+/// fragment is renumbered above whichever id is highest so far (so two fragments merged in the
+/// same call never collide with each other either), `exportedSymbols` gains an entry mapping the
+/// merged contract's name to its new id, and every `scope`/`referencedDeclaration` inside the
+/// fragment that pointed at one of its own (now-renumbered) ids is rewritten to match - renumbering
+/// alone would otherwise leave those back-references dangling. Errors if a fragment's contract
+/// shares a name with one already in `base` or with an earlier fragment in the same call, since
+/// solc itself rejects two same-named contracts in one source unit.
+pub fn merge_contract_definitions(
+  base: &SourceUnit,
+  fragments: Vec<ContractDefinition>,
+) -> std::result::Result<SourceUnit, AstError> {
+  let mut base_value = serde_json::to_value(base)?;
+  let mut names: std::collections::BTreeSet<String> = contract_names(base).into_iter().collect();
+  let mut next_id = max_id(base)?;
+
+  for contract in fragments {
+    if !names.insert(contract.name.clone()) {
+      return Err(AstError::InvalidContractStructure(format!(
+        "Contract '{}' already exists in the base source unit or an earlier fragment",
+        contract.name
+      )));
+    }
+
+    let mut value = serde_json::to_value(&contract)?;
+    let mut mapping = BTreeMap::new();
+    walk_renumber_tracked(&mut value, &mut next_id, &mut mapping);
+    walk_rewrite_back_references(&mut value, &mapping);
+    sanitize_ast_value(&mut value);
+
+    let new_id = value.get("id").and_then(Value::as_i64);
+
+    base_value
+      .get_mut("nodes")
+      .and_then(Value::as_array_mut)
+      .ok_or_else(|| AstError::InvalidContractStructure("Base AST missing nodes".to_string()))?
+      .push(value);
+
+    if let (Some(new_id), Some(exported)) = (
+      new_id,
+      base_value.get_mut("exportedSymbols").and_then(Value::as_object_mut),
+    ) {
+      exported.insert(
+        contract.name,
+        Value::Array(vec![Value::Number(new_id.into())]),
+      );
+    }
+  }
+
+  serde_json::from_value(base_value).map_err(|err| AstError::JsonError(err.to_string()))
+}
+
 pub fn sanitize_ast_value(value: &mut Value) {
   fn sanitize(node: &mut Value, parent_key: Option<&str>) -> bool {
     match node {
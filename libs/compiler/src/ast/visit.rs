@@ -0,0 +1,110 @@
+use foundry_compilers::artifacts::ast::{Block, BlockOrStatement, Statement, TryCatchClause};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::error::AstError;
+use super::utils::{sanitize_ast_value, walk_renumber};
+
+/// Rewrites every `id` in a typed `Statement`/`Block` subtree in place, advancing `next_id` for
+/// each one assigned - the typed counterpart to `utils::clone_with_new_ids`'s `serde_json`
+/// round-trip, used by `instrumenter::clone_statements` so cloning the handful of `before`/`after`
+/// snippet statements `injectShadowAtEdges` injects doesn't serialize/deserialize a statement on
+/// every clone. Block-shaped control flow (`Block`, `UncheckedBlock`, the bodies of
+/// `if`/`while`/`do`/`try`) is walked typed all the way down; any `Expression` a statement carries,
+/// along with every statement shape not matched below, still goes through `serde_json` scoped to
+/// just that one value, since `Expression`'s many variants (`FunctionCall`, `BinaryOperation`,
+/// `MemberAccess`, ...) don't share enough common shape to walk typed without risking an
+/// incomplete id rewrite.
+pub(crate) trait AstVisitMut {
+  fn renumber_ids(&mut self, next_id: &mut i64) -> std::result::Result<(), AstError>;
+}
+
+impl AstVisitMut for Block {
+  fn renumber_ids(&mut self, next_id: &mut i64) -> std::result::Result<(), AstError> {
+    *next_id += 1;
+    self.id = *next_id;
+    for statement in &mut self.statements {
+      statement.renumber_ids(next_id)?;
+    }
+    Ok(())
+  }
+}
+
+impl AstVisitMut for Statement {
+  fn renumber_ids(&mut self, next_id: &mut i64) -> std::result::Result<(), AstError> {
+    match self {
+      Statement::Block(block) => block.renumber_ids(next_id),
+      Statement::UncheckedBlock(unchecked) => {
+        *next_id += 1;
+        unchecked.id = *next_id;
+        for statement in &mut unchecked.statements {
+          statement.renumber_ids(next_id)?;
+        }
+        Ok(())
+      }
+      Statement::IfStatement(if_stmt) => {
+        *next_id += 1;
+        if_stmt.id = *next_id;
+        renumber_value_in_place(&mut if_stmt.condition, next_id)?;
+        renumber_block_or_statement(&mut if_stmt.true_body, next_id)?;
+        if let Some(false_body) = if_stmt.false_body.as_mut() {
+          renumber_block_or_statement(false_body, next_id)?;
+        }
+        Ok(())
+      }
+      Statement::WhileStatement(while_stmt) => {
+        *next_id += 1;
+        while_stmt.id = *next_id;
+        renumber_value_in_place(&mut while_stmt.condition, next_id)?;
+        renumber_block_or_statement(&mut while_stmt.body, next_id)
+      }
+      Statement::DoWhileStatement(do_stmt) => {
+        *next_id += 1;
+        do_stmt.id = *next_id;
+        renumber_value_in_place(&mut do_stmt.condition, next_id)?;
+        do_stmt.body.renumber_ids(next_id)
+      }
+      Statement::TryStatement(try_stmt) => {
+        *next_id += 1;
+        try_stmt.id = *next_id;
+        renumber_value_in_place(&mut try_stmt.external_call, next_id)?;
+        for TryCatchClause { block, .. } in &mut try_stmt.clauses {
+          block.renumber_ids(next_id)?;
+        }
+        Ok(())
+      }
+      // `ForStatement`'s init/condition/loop-expression slots and every leaf variant
+      // (`ExpressionStatement`, `Return`, `VariableDeclarationStatement`, `EmitStatement`,
+      // `RevertStatement`, `InlineAssembly`, ...) fall back to a single `serde_json` round trip
+      // scoped to just this one statement - the same cost `clone_with_new_ids` already pays
+      // today, just no longer paid by every statement in a snippet when most are blocks/if/
+      // while/try.
+      other => renumber_value_in_place(other, next_id),
+    }
+  }
+}
+
+fn renumber_block_or_statement(
+  node: &mut BlockOrStatement,
+  next_id: &mut i64,
+) -> std::result::Result<(), AstError> {
+  match node {
+    BlockOrStatement::Block(block) => block.renumber_ids(next_id),
+    BlockOrStatement::Statement(statement) => statement.renumber_ids(next_id),
+  }
+}
+
+/// The `serde_json` fallback for any node shape [`AstVisitMut`] doesn't cover typed: serializes
+/// just `node`, walks its `id` fields with the same numbering the typed impls above use, and
+/// deserializes the result back in place.
+fn renumber_value_in_place<T>(node: &mut T, next_id: &mut i64) -> std::result::Result<(), AstError>
+where
+  T: Serialize + DeserializeOwned,
+{
+  let mut value =
+    serde_json::to_value(&*node).map_err(|err| AstError::JsonError(err.to_string()))?;
+  walk_renumber(&mut value, next_id);
+  sanitize_ast_value(&mut value);
+  *node = serde_json::from_value(value).map_err(|err| AstError::JsonError(err.to_string()))?;
+  Ok(())
+}
@@ -1,15 +1,26 @@
 use foundry_compilers::{
-  solc::{SolcCompiler, SolcLanguage},
+  artifacts::Settings,
+  solc::{CliSettings, SolcCompiler, SolcLanguage, SolcSettings},
   ProjectBuilder, ProjectPathsConfig,
 };
 use napi::bindgen_prelude::*;
 use std::path::PathBuf;
 
-use super::project::SolidityProject;
+use super::project::{apply_explicit_remappings, auto_detect_remappings, SolidityProject};
+use crate::internal::errors::map_napi_error;
+use crate::internal::settings::{
+  CompilerSettingsOptions, JsModelCheckerSettingsOptions, ModelCheckerSettingsOptions,
+};
 
 #[napi]
 pub struct SolidityProjectBuilder {
   builder: ProjectBuilder<SolcCompiler>,
+  paths: Option<ProjectPathsConfig<SolcLanguage>>,
+  model_checker: Option<ModelCheckerSettingsOptions>,
+  /// Mirrors whatever `ephemeral`/`set_cached` last did to `builder`, since
+  /// `ProjectBuilder<SolcCompiler>` doesn't expose a getter for it - needed to carry the same
+  /// cache mode over to the `SolidityProject` this builds.
+  cached: bool,
 }
 
 #[napi]
@@ -19,6 +30,9 @@ impl SolidityProjectBuilder {
   pub fn new() -> Self {
     SolidityProjectBuilder {
       builder: ProjectBuilder::default(),
+      paths: None,
+      model_checker: None,
+      cached: true,
     }
   }
 
@@ -26,15 +40,16 @@ impl SolidityProjectBuilder {
   #[napi]
   pub fn hardhat_paths(&mut self, root_path: String) -> Result<()> {
     let root = PathBuf::from(root_path);
-    let paths: ProjectPathsConfig<SolcLanguage> =
+    let mut paths: ProjectPathsConfig<SolcLanguage> =
       ProjectPathsConfig::hardhat(&root).map_err(|e| {
         Error::new(
           Status::GenericFailure,
           format!("Failed to create hardhat paths: {}", e),
         )
       })?;
+    auto_detect_remappings(&mut paths);
 
-    self.builder = std::mem::replace(&mut self.builder, ProjectBuilder::default()).paths(paths);
+    self.paths = Some(paths);
     Ok(())
   }
 
@@ -42,22 +57,38 @@ impl SolidityProjectBuilder {
   #[napi]
   pub fn dapptools_paths(&mut self, root_path: String) -> Result<()> {
     let root = PathBuf::from(root_path);
-    let paths: ProjectPathsConfig<SolcLanguage> =
+    let mut paths: ProjectPathsConfig<SolcLanguage> =
       ProjectPathsConfig::dapptools(&root).map_err(|e| {
         Error::new(
           Status::GenericFailure,
           format!("Failed to create dapptools paths: {}", e),
         )
       })?;
+    auto_detect_remappings(&mut paths);
 
-    self.builder = std::mem::replace(&mut self.builder, ProjectBuilder::default()).paths(paths);
+    self.paths = Some(paths);
     Ok(())
   }
 
+  /// Add explicit `from=to` import remappings on top of whatever layout paths
+  /// (`hardhat_paths`/`dapptools_paths`) already auto-detected. Must be called after one of those.
+  #[napi]
+  pub fn remappings(&mut self, remappings: Vec<String>) -> Result<()> {
+    let paths = self.paths.as_mut().ok_or_else(|| {
+      Error::new(
+        Status::GenericFailure,
+        "Project paths must be configured (via hardhatPaths/dapptoolsPaths) before remappings",
+      )
+    })?;
+
+    apply_explicit_remappings(paths, &remappings)
+  }
+
   /// Enable ephemeral mode (disable caching)
   #[napi]
   pub fn ephemeral(&mut self) -> &Self {
     self.builder = std::mem::replace(&mut self.builder, ProjectBuilder::default()).ephemeral();
+    self.cached = false;
     self
   }
 
@@ -66,6 +97,7 @@ impl SolidityProjectBuilder {
   pub fn set_cached(&mut self, cached: bool) -> &Self {
     self.builder =
       std::mem::replace(&mut self.builder, ProjectBuilder::default()).set_cached(cached);
+    self.cached = cached;
     self
   }
 
@@ -115,10 +147,44 @@ impl SolidityProjectBuilder {
     self
   }
 
+  /// Configure solc's SMTChecker formal-verification pass (`Settings.modelChecker`): an engine
+  /// (`chc`, `bmc`, or `all`), verification targets, an optional timeout in milliseconds, and the
+  /// contracts/sources to analyze. Findings surface on `CompileOutput.modelCheckerDiagnostics`,
+  /// separate from ordinary compiler errors/warnings.
+  #[napi]
+  pub fn model_checker(&mut self, options: JsModelCheckerSettingsOptions) -> Result<()> {
+    let options: ModelCheckerSettingsOptions = map_napi_error(
+      serde_json::to_value(&options).and_then(serde_json::from_value),
+      "Failed to convert model checker settings",
+    )?;
+    self.model_checker = Some(options);
+    Ok(())
+  }
+
   /// Build the project
   #[napi]
   pub fn build(&mut self) -> Result<SolidityProject> {
-    let builder = std::mem::replace(&mut self.builder, ProjectBuilder::default());
+    let mut builder = std::mem::replace(&mut self.builder, ProjectBuilder::default());
+    let root = self.paths.as_ref().map(|paths| paths.root.clone());
+    if let Some(paths) = self.paths.take() {
+      builder = builder.paths(paths);
+    }
+    if let Some(model_checker) = self.model_checker.take() {
+      let overrides = CompilerSettingsOptions {
+        model_checker: Some(model_checker),
+        ..Default::default()
+      };
+      let settings = overrides.overlay(&Settings::default())?;
+      builder = builder.settings(SolcSettings {
+        settings,
+        cli_settings: CliSettings {
+          extra_args: Vec::new(),
+          allow_paths: Vec::new(),
+          base_path: root,
+          include_paths: Vec::new(),
+        },
+      });
+    }
     let project = builder.build(SolcCompiler::default()).map_err(|e| {
       Error::new(
         Status::GenericFailure,
@@ -126,6 +192,9 @@ impl SolidityProjectBuilder {
       )
     })?;
 
-    Ok(SolidityProject { project })
+    Ok(SolidityProject {
+      project: std::sync::Arc::new(project),
+      cached: self.cached,
+    })
   }
 }
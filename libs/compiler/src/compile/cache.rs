@@ -0,0 +1,193 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use foundry_compilers::artifacts::ConfigurableContractArtifact;
+use serde::{Deserialize, Serialize};
+
+use super::output::{project_contract, ArtifactSelection, CoreContractArtifact};
+use crate::internal::cache_key::keccak_hex;
+use crate::internal::errors::{map_err_with_context, Result};
+
+/// Per-source fingerprint this cache persists at `ProjectPaths::cache`: the file's content hash,
+/// its resolved direct imports (so a later run can transitively mark importers dirty without
+/// re-parsing every file), and the artifact names it produced last time it was actually compiled.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct SourceCacheEntry {
+  content_hash: String,
+  imports: Vec<String>,
+  artifact_names: Vec<String>,
+}
+
+/// The on-disk shape of the cache file: every recorded source, keyed by its canonicalized path
+/// string, plus the solc version/settings digest they were all recorded under. A digest mismatch
+/// means solc itself would produce different output for the same source text, so the whole index
+/// is discarded rather than trusted file-by-file.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct CacheIndex {
+  settings_digest: String,
+  files: BTreeMap<String, SourceCacheEntry>,
+}
+
+/// Content hash for one source file's text, as recorded in `SourceCacheEntry::content_hash`.
+pub fn content_hash(contents: &str) -> String {
+  keccak_hex(contents.as_bytes())
+}
+
+/// Digest identifying a solc version + settings pair, so a cache primed under one compiler
+/// configuration never looks "unchanged" against a different one.
+pub fn settings_digest(solc_version: &str, settings_json: &str) -> String {
+  keccak_hex(format!("{solc_version}:{settings_json}").as_bytes())
+}
+
+fn read_index(cache_path: &Path) -> CacheIndex {
+  fs::read_to_string(cache_path)
+    .ok()
+    .and_then(|contents| serde_json::from_str(&contents).ok())
+    .unwrap_or_default()
+}
+
+fn write_index(cache_path: &Path, index: &CacheIndex) -> Result<()> {
+  if let Some(parent) = cache_path.parent() {
+    fs::create_dir_all(parent).ok();
+  }
+  let serialised = map_err_with_context(
+    serde_json::to_string(index),
+    "Failed to serialise content-hash compile cache",
+  )?;
+  fs::write(cache_path, serialised).ok();
+  Ok(())
+}
+
+/// Determines which of `sources` need recompiling: anything whose content hash changed since the
+/// last recorded entry, anything with no recorded entry at all, and anything that (transitively,
+/// via `imports`) depends on one of those. The entire stored index is treated as empty - so every
+/// source comes back dirty - when its `settings_digest` no longer matches `settings_digest`,
+/// since a solc version/settings change invalidates every prior fingerprint at once.
+pub fn filter_dirty(
+  cache_path: &Path,
+  sources: &BTreeMap<PathBuf, String>,
+  imports: &BTreeMap<PathBuf, Vec<PathBuf>>,
+  settings_digest: &str,
+) -> BTreeSet<PathBuf> {
+  let stored = read_index(cache_path);
+  let files = if stored.settings_digest == settings_digest {
+    stored.files
+  } else {
+    BTreeMap::new()
+  };
+
+  let mut dirty: BTreeSet<PathBuf> = BTreeSet::new();
+  let mut stack: Vec<PathBuf> = Vec::new();
+  for (path, contents) in sources {
+    let key = path.to_string_lossy().to_string();
+    let unchanged = files
+      .get(&key)
+      .is_some_and(|entry| entry.content_hash == content_hash(contents));
+    if !unchanged && dirty.insert(path.clone()) {
+      stack.push(path.clone());
+    }
+  }
+
+  let mut dependents: BTreeMap<&PathBuf, Vec<&PathBuf>> = BTreeMap::new();
+  for (importer, imported_list) in imports {
+    for imported in imported_list {
+      dependents.entry(imported).or_default().push(importer);
+    }
+  }
+
+  while let Some(file) = stack.pop() {
+    if let Some(importers) = dependents.get(&file) {
+      for importer in importers {
+        if dirty.insert((*importer).clone()) {
+          stack.push((*importer).clone());
+        }
+      }
+    }
+  }
+
+  dirty
+}
+
+/// Persists `sources`' current content hashes/imports under `cache_path`, along with
+/// `artifact_names` recording which contract names each source produced - the set a later
+/// `load_clean_artifacts` call rehydrates for files `filter_dirty` leaves out of the dirty set.
+/// Replaces the whole index rather than merging, since `filter_dirty` already recomputed a
+/// complete dirty set over every reachable source this run. A source with no `artifact_names`
+/// entry keeps the names the previous index (under the same `settings_digest`) recorded for it.
+pub fn record(
+  cache_path: &Path,
+  settings_digest: &str,
+  sources: &BTreeMap<PathBuf, String>,
+  imports: &BTreeMap<PathBuf, Vec<PathBuf>>,
+  artifact_names: &BTreeMap<PathBuf, Vec<String>>,
+) -> Result<()> {
+  let previous = read_index(cache_path);
+  let mut files = BTreeMap::new();
+  for (path, contents) in sources {
+    let key = path.to_string_lossy().to_string();
+    let entry_imports = imports
+      .get(path)
+      .map(|list| list.iter().map(|p| p.to_string_lossy().to_string()).collect())
+      .unwrap_or_default();
+    let names = artifact_names.get(path).cloned().or_else(|| {
+      (previous.settings_digest == settings_digest)
+        .then(|| previous.files.get(&key).map(|entry| entry.artifact_names.clone()))
+        .flatten()
+    });
+    files.insert(
+      key,
+      SourceCacheEntry {
+        content_hash: content_hash(contents),
+        imports: entry_imports,
+        artifact_names: names.unwrap_or_default(),
+      },
+    );
+  }
+
+  write_index(
+    cache_path,
+    &CacheIndex {
+      settings_digest: settings_digest.to_string(),
+      files,
+    },
+  )
+}
+
+/// Rebuilds a `CoreContractArtifact` for every artifact name `record` previously stored against
+/// each of `clean_files`, by reading back the `{ContractName}.json` artifact Foundry's own project
+/// writer already left under `artifacts_dir`. Mirrors
+/// `crate::compiler::incremental::load_clean_artifacts`, but reuses `project_contract` to rebuild
+/// the `compile`-facade's own `CoreContractArtifact` shape instead of `SourceArtifacts`/`Contract`.
+/// A name whose artifact file is missing or fails to parse is silently left out rather than
+/// guessed at - the caller already knows it needs a full recompile once something is amiss.
+pub fn load_clean_artifacts(
+  cache_path: &Path,
+  artifacts_dir: &Path,
+  root: &Path,
+  clean_files: &[PathBuf],
+) -> Vec<CoreContractArtifact> {
+  let index = read_index(cache_path);
+  let mut result = Vec::new();
+
+  for file in clean_files {
+    let key = file.to_string_lossy().to_string();
+    let Some(entry) = index.files.get(&key) else {
+      continue;
+    };
+    let relative = file.strip_prefix(root).unwrap_or(file);
+
+    for name in &entry.artifact_names {
+      let path = artifacts_dir.join(relative).join(format!("{name}.json"));
+      let Ok(contents) = fs::read_to_string(&path) else {
+        continue;
+      };
+      let Ok(artifact) = serde_json::from_str::<ConfigurableContractArtifact>(&contents) else {
+        continue;
+      };
+      result.push(project_contract(name, &artifact, ArtifactSelection::default()));
+    }
+  }
+
+  result
+}
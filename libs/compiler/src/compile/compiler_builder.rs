@@ -0,0 +1,197 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use foundry_compilers::{
+  artifacts::{output_selection::OutputSelection, Settings},
+  solc::{CliSettings, SolcCompiler, SolcSettings},
+  Project, ProjectBuilder,
+};
+use napi::bindgen_prelude::*;
+
+use super::output;
+use crate::internal::errors::map_napi_error;
+use crate::internal::settings::{apply_extra_output, ExtraOutputKind};
+use crate::types::CompileOutput;
+
+/// Fluent, per-call compile configuration for a `SolidityProject`, reached via
+/// `SolidityProject::compiler`. Defaults to compiling everything with the project's own settings
+/// and cache mode; `quiet`/`extraOutput` each nudge that away from the shared, already-built
+/// `Project` and onto a freshly rebuilt one scoped to this call, while `fileFilter` only narrows
+/// which of the project's sources `compile()` sends.
+#[napi]
+pub struct SolidityCompileBuilder {
+  project: Arc<Project<SolcCompiler>>,
+  cached: bool,
+  quiet: bool,
+  extra_output: Vec<ExtraOutputKind>,
+  file_filter: Vec<String>,
+}
+
+impl SolidityCompileBuilder {
+  pub(crate) fn new(project: Arc<Project<SolcCompiler>>, cached: bool) -> Self {
+    SolidityCompileBuilder {
+      project,
+      cached,
+      quiet: false,
+      extra_output: Vec::new(),
+      file_filter: Vec::new(),
+    }
+  }
+
+  /// Builds a `Project` for this call. Returns the shared project unchanged when neither `quiet`
+  /// nor `extraOutput` were requested; otherwise rebuilds an equivalent project from the same
+  /// paths with `Settings` adjusted for parsing-only analysis and/or the extra output selection.
+  fn resolve_project(&self) -> Result<Arc<Project<SolcCompiler>>> {
+    if !self.quiet && self.extra_output.is_empty() {
+      return Ok(Arc::clone(&self.project));
+    }
+
+    let mut settings = Settings::default();
+    if self.quiet {
+      settings.stop_after = Some("parsing".to_string());
+      settings.output_selection = OutputSelection::ast_output_selection();
+    }
+    apply_extra_output(&mut settings, &self.extra_output);
+
+    let paths = self.project.paths.clone();
+    let base_path = paths.root.clone();
+    let mut builder = ProjectBuilder::default().paths(paths).settings(SolcSettings {
+      settings,
+      cli_settings: CliSettings {
+        extra_args: Vec::new(),
+        allow_paths: Vec::new(),
+        base_path: Some(base_path),
+        include_paths: Vec::new(),
+      },
+    });
+    if !self.cached {
+      builder = builder.ephemeral();
+    }
+
+    let project = map_napi_error(
+      builder.build(SolcCompiler::default()),
+      "Failed to build project",
+    )?;
+    Ok(Arc::new(project))
+  }
+
+  /// Resolves `file_filter`'s glob patterns against `project`'s own source set. Only `*` wildcards
+  /// are supported - enough to match an extension (`*.t.sol`) or a directory prefix
+  /// (`src/interfaces/*`) without pulling in a full glob implementation.
+  fn filtered_sources(&self, project: &Project<SolcCompiler>) -> Result<Vec<PathBuf>> {
+    let sources = map_napi_error(project.sources(), "Failed to get sources")?;
+    if self.file_filter.is_empty() {
+      return Ok(sources.into_keys().collect());
+    }
+
+    Ok(
+      sources
+        .into_keys()
+        .filter(|path| {
+          let text = path.to_string_lossy();
+          self
+            .file_filter
+            .iter()
+            .any(|pattern| glob_match(pattern, &text))
+        })
+        .collect(),
+    )
+  }
+
+  fn run(&self, paths: Option<Vec<PathBuf>>) -> Result<CompileOutput> {
+    let project = self.resolve_project()?;
+    let paths = match paths {
+      Some(paths) => paths,
+      None => self.filtered_sources(&project)?,
+    };
+
+    let compiled = map_napi_error(project.compile_files(paths), "Failed to compile project")?;
+    Ok(output::into_compile_output(compiled))
+  }
+}
+
+#[napi]
+impl SolidityCompileBuilder {
+  /// Stop after parsing and select only the AST, skipping code generation. Useful for
+  /// analysis-only calls (linting, import graphs) that don't need bytecode.
+  #[napi]
+  pub fn quiet(&mut self) -> &Self {
+    self.quiet = true;
+    self
+  }
+
+  /// Request additional solc output beyond the default ABI/bytecode, e.g. `storageLayout` or
+  /// `gasEstimates`. See `ExtraOutputKind` for the full set. The selection is sent to solc, but
+  /// `output::project_contract` does not copy these outputs onto the returned artifacts, so their
+  /// fields come back unset.
+  #[napi]
+  pub fn extra_output(&mut self, kinds: Vec<ExtraOutputKind>) -> &Self {
+    self.extra_output = kinds;
+    self
+  }
+
+  /// Restrict `compile()` to source paths matching at least one of these patterns (`*` wildcard
+  /// only). Has no effect on `compileFile`/`compileFiles`, which already take an explicit path
+  /// list.
+  #[napi]
+  pub fn file_filter(&mut self, patterns: Vec<String>) -> &Self {
+    self.file_filter = patterns;
+    self
+  }
+
+  /// Compile all contracts in the project matching `fileFilter` (or everything, if unset)
+  #[napi]
+  pub fn compile(&self) -> Result<CompileOutput> {
+    self.run(None)
+  }
+
+  /// Compile a single file
+  #[napi]
+  pub fn compile_file(&self, file_path: String) -> Result<CompileOutput> {
+    self.run(Some(vec![PathBuf::from(file_path)]))
+  }
+
+  /// Compile multiple files
+  #[napi]
+  pub fn compile_files(&self, file_paths: Vec<String>) -> Result<CompileOutput> {
+    self.run(Some(file_paths.into_iter().map(PathBuf::from).collect()))
+  }
+}
+
+/// Lightweight `*`-only glob matcher: splits `pattern` on `*` and checks the fragments occur in
+/// `text`, in order, pinning the first/last fragment to the start/end when `pattern` doesn't begin
+/// or end with `*`.
+fn glob_match(pattern: &str, text: &str) -> bool {
+  if !pattern.contains('*') {
+    return text == pattern;
+  }
+
+  let mut rest = text;
+  let parts: Vec<&str> = pattern.split('*').collect();
+  for (index, part) in parts.iter().enumerate() {
+    if part.is_empty() {
+      continue;
+    }
+    if index == 0 {
+      if !rest.starts_with(part) {
+        return false;
+      }
+      rest = &rest[part.len()..];
+      continue;
+    }
+    if index == parts.len() - 1 && !pattern.ends_with('*') {
+      return rest.ends_with(part);
+    }
+    match rest.find(part) {
+      Some(found) => {
+        rest = &rest[found + part.len()..];
+      }
+      None => return false,
+    }
+  }
+
+  if pattern.ends_with('*') {
+    return true;
+  }
+  rest.is_empty()
+}
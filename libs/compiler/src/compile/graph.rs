@@ -0,0 +1,347 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use foundry_compilers::artifacts::remappings::Remapping;
+use napi::bindgen_prelude::*;
+use semver::VersionReq;
+
+use super::project::SolidityProject;
+use crate::internal::errors::map_napi_error;
+
+/// One source file's position in the resolved import graph.
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct SourceGraphNode {
+  pub path: String,
+  /// Imports this file declares directly, resolved through the project's remappings.
+  pub imports: Vec<String>,
+  /// Every file reachable by following `imports` transitively (deduplicated, unordered).
+  pub transitive_imports: Vec<String>,
+  /// The `pragma solidity` constraint on this file, if present.
+  #[napi(ts_type = "string | undefined")]
+  pub version_requirement: Option<String>,
+}
+
+/// The resolved import graph for a project's sources, returned by `SolidityProject::resolve_graph`.
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct SourceGraph {
+  pub nodes: Vec<SourceGraphNode>,
+}
+
+/// A cycle in the import graph: the ordered chain of files that import one another back around to
+/// the first. Solidity itself permits circular imports, so this is informational, not an error.
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct ImportCycle {
+  pub files: Vec<String>,
+}
+
+#[napi]
+impl SolidityProject {
+  /// Builds the resolved import graph over the project's sources: each file's direct imports
+  /// (resolved through the project's remappings), its transitive closure, and the solc version
+  /// requirement parsed from its `pragma`.
+  #[napi]
+  pub fn resolve_graph(&self) -> Result<SourceGraph> {
+    let adjacency = self.source_adjacency()?;
+    let contents = self.source_contents()?;
+
+    let nodes = adjacency
+      .iter()
+      .map(|(path, imports)| SourceGraphNode {
+        path: path.clone(),
+        imports: imports.clone(),
+        transitive_imports: transitive_closure(path, &adjacency),
+        version_requirement: contents
+          .get(path)
+          .and_then(|source| parse_version_pragma(source))
+          .map(|req| req.to_string()),
+      })
+      .collect();
+
+    Ok(SourceGraph { nodes })
+  }
+
+  /// Dependency-first build order: a file never precedes anything it imports outside a cycle.
+  /// Files inside an import cycle (legal in Solidity) are appended afterwards, in path order, so a
+  /// file that imports one of them can precede it.
+  #[napi]
+  pub fn topological_order(&self) -> Result<Vec<String>> {
+    let adjacency = self.source_adjacency()?;
+    let cyclic: BTreeSet<String> = find_cycles(&adjacency)
+      .into_iter()
+      .flatten()
+      .collect();
+
+    Ok(topological_order_from(&adjacency, &cyclic))
+  }
+
+  /// The cycles `find_cycles` finds in the import graph, each as the ordered chain of files that
+  /// import one another back around to the first.
+  #[napi]
+  pub fn detect_import_cycles(&self) -> Result<Vec<ImportCycle>> {
+    let adjacency = self.source_adjacency()?;
+    Ok(
+      find_cycles(&adjacency)
+        .into_iter()
+        .map(|files| ImportCycle { files })
+        .collect(),
+    )
+  }
+}
+
+impl SolidityProject {
+  /// `source_adjacency`, with both the source path and each of its imports parsed back into a
+  /// `PathBuf` - the shape `compile::cache`'s content-hash cache keys its fingerprints by.
+  pub(crate) fn import_adjacency_paths(&self) -> Result<BTreeMap<PathBuf, Vec<PathBuf>>> {
+    let adjacency = self.source_adjacency()?;
+    Ok(
+      adjacency
+        .into_iter()
+        .map(|(path, imports)| {
+          (
+            PathBuf::from(path),
+            imports.into_iter().map(PathBuf::from).collect(),
+          )
+        })
+        .collect(),
+    )
+  }
+
+  fn source_contents(&self) -> Result<BTreeMap<String, String>> {
+    let sources = map_napi_error(self.project.sources(), "Failed to get sources")?;
+    Ok(
+      sources
+        .iter()
+        .map(|(path, source)| {
+          (
+            path.to_string_lossy().to_string(),
+            source.content.as_str().to_string(),
+          )
+        })
+        .collect(),
+    )
+  }
+
+  /// The `path -> direct imports` adjacency for every project source, with each import resolved
+  /// either as a relative path or through the project's remappings.
+  fn source_adjacency(&self) -> Result<BTreeMap<String, Vec<String>>> {
+    let contents = self.source_contents()?;
+    let remappings = &self.project.paths.remappings;
+
+    Ok(
+      contents
+        .iter()
+        .map(|(path, text)| {
+          let imports = extract_imports(text)
+            .into_iter()
+            .filter_map(|import| resolve_import(path, &import, &contents, remappings))
+            .collect();
+          (path.clone(), imports)
+        })
+        .collect(),
+    )
+  }
+}
+
+/// Scans `contents` for `import "..."` / `import {...} from "..."` / `import * as X from "..."`
+/// statements and returns the quoted import target of each one, in source order. This is a
+/// lightweight scan (no full Solidity parser), sufficient for graph resolution.
+fn extract_imports(contents: &str) -> Vec<String> {
+  let mut imports = Vec::new();
+  let mut rest = contents;
+  while let Some(start) = rest.find("import") {
+    let after_keyword = &rest[start + "import".len()..];
+    if let Some(quote_start) = after_keyword
+      .find(['"', '\''])
+      .filter(|&idx| after_keyword[..idx].find(';').is_none())
+    {
+      let quote_char = after_keyword.as_bytes()[quote_start] as char;
+      let quoted = &after_keyword[quote_start + 1..];
+      if let Some(quote_end) = quoted.find(quote_char) {
+        imports.push(quoted[..quote_end].to_string());
+        rest = &quoted[quote_end + 1..];
+        continue;
+      }
+    }
+    rest = after_keyword;
+  }
+  imports
+}
+
+/// Resolves an `import` target written in `importing_path` to a key in `sources`, trying a
+/// relative-path resolution first and falling back to the project's remappings. Returns `None`
+/// when no known source matches.
+fn resolve_import(
+  importing_path: &str,
+  import: &str,
+  sources: &BTreeMap<String, String>,
+  remappings: &[Remapping],
+) -> Option<String> {
+  if import.starts_with('.') {
+    let base = Path::new(importing_path).parent().unwrap_or(Path::new(""));
+    let joined = normalise_path(&base.join(import));
+    if sources.contains_key(&joined) {
+      return Some(joined);
+    }
+    return sources
+      .keys()
+      .find(|candidate| normalise_path(Path::new(candidate)) == joined)
+      .cloned();
+  }
+
+  if sources.contains_key(import) {
+    return Some(import.to_string());
+  }
+
+  let mut best: Option<(&Remapping, &str)> = None;
+  for remapping in remappings {
+    if let Some(suffix) = import.strip_prefix(remapping.name.as_str()) {
+      if best
+        .map(|(current, _)| remapping.name.len() > current.name.len())
+        .unwrap_or(true)
+      {
+        best = Some((remapping, suffix));
+      }
+    }
+  }
+  if let Some((remapping, suffix)) = best {
+    let candidate =
+      normalise_path(&PathBuf::from(&remapping.path).join(suffix.trim_start_matches('/')));
+    if sources.contains_key(&candidate) {
+      return Some(candidate);
+    }
+    return sources
+      .keys()
+      .find(|key| normalise_path(Path::new(key)) == candidate)
+      .cloned();
+  }
+
+  None
+}
+
+/// Collapses `.`/`..` segments without touching the filesystem, so relative imports compare equal
+/// to the canonical keys already used throughout `sources`.
+fn normalise_path(path: &Path) -> String {
+  let mut stack: Vec<std::ffi::OsString> = Vec::new();
+  for component in path.components() {
+    match component {
+      std::path::Component::CurDir => {}
+      std::path::Component::ParentDir => {
+        stack.pop();
+      }
+      other => stack.push(other.as_os_str().to_os_string()),
+    }
+  }
+  PathBuf::from_iter(stack).to_string_lossy().replace('\\', "/")
+}
+
+/// Extracts the `pragma solidity <constraint>;` expression from a source, if present, and parses
+/// it as a `VersionReq`. Solidity pragmas separate multiple comparators with whitespace (e.g.
+/// `>=0.8.0 <0.9.0`) rather than the comma `VersionReq::parse` expects, so they're rejoined here.
+fn parse_version_pragma(source: &str) -> Option<VersionReq> {
+  let marker = "pragma solidity";
+  let start = source.find(marker)? + marker.len();
+  let rest = &source[start..];
+  let end = rest.find(';')?;
+  let expr = rest[..end].trim();
+  if expr.is_empty() {
+    return None;
+  }
+
+  let normalised = expr.split_whitespace().collect::<Vec<_>>().join(", ");
+  VersionReq::parse(&normalised).ok()
+}
+
+/// Every file reachable from `node` by following `adjacency` transitively (not including `node`
+/// itself unless it's part of a cycle that loops back to it).
+fn transitive_closure(node: &str, adjacency: &BTreeMap<String, Vec<String>>) -> Vec<String> {
+  let mut seen: BTreeSet<String> = BTreeSet::new();
+  let mut stack: Vec<String> = adjacency.get(node).cloned().unwrap_or_default();
+
+  while let Some(next) = stack.pop() {
+    if seen.insert(next.clone()) {
+      if let Some(imports) = adjacency.get(&next) {
+        stack.extend(imports.iter().cloned());
+      }
+    }
+  }
+
+  seen.into_iter().collect()
+}
+
+/// Detects cycles in the `path -> imports` adjacency via DFS, returning each cycle as the chain of
+/// files from re-entering the stack back to itself. Each file is expanded once, so a cycle that
+/// runs through a file an earlier branch already finished exploring is not reported.
+fn find_cycles(adjacency: &BTreeMap<String, Vec<String>>) -> Vec<Vec<String>> {
+  let mut cycles = Vec::new();
+  let mut visited: BTreeSet<String> = BTreeSet::new();
+  let mut stack: Vec<String> = Vec::new();
+
+  fn visit(
+    node: &str,
+    adjacency: &BTreeMap<String, Vec<String>>,
+    visited: &mut BTreeSet<String>,
+    stack: &mut Vec<String>,
+    cycles: &mut Vec<Vec<String>>,
+  ) {
+    if let Some(pos) = stack.iter().position(|entry| entry == node) {
+      cycles.push(stack[pos..].to_vec());
+      return;
+    }
+    if !visited.insert(node.to_string()) {
+      return;
+    }
+    stack.push(node.to_string());
+    if let Some(imports) = adjacency.get(node) {
+      for imported in imports {
+        visit(imported, adjacency, visited, stack, cycles);
+      }
+    }
+    stack.pop();
+  }
+
+  for node in adjacency.keys() {
+    visit(node, adjacency, &mut visited, &mut stack, &mut cycles);
+  }
+  cycles
+}
+
+/// Post-order DFS over the import adjacency: a file is placed only after everything it imports
+/// that isn't in `cyclic`. Files inside a cycle are skipped during the walk and appended
+/// afterwards in path order - there's no well-defined position for them - so a file importing one
+/// of them is placed before it.
+fn topological_order_from(
+  adjacency: &BTreeMap<String, Vec<String>>,
+  cyclic: &BTreeSet<String>,
+) -> Vec<String> {
+  let mut order = Vec::new();
+  let mut emitted: BTreeSet<String> = BTreeSet::new();
+
+  fn visit(
+    node: &str,
+    adjacency: &BTreeMap<String, Vec<String>>,
+    cyclic: &BTreeSet<String>,
+    emitted: &mut BTreeSet<String>,
+    order: &mut Vec<String>,
+  ) {
+    if cyclic.contains(node) || !emitted.insert(node.to_string()) {
+      return;
+    }
+    if let Some(imports) = adjacency.get(node) {
+      for imported in imports {
+        visit(imported, adjacency, cyclic, emitted, order);
+      }
+    }
+    order.push(node.to_string());
+  }
+
+  for node in adjacency.keys() {
+    visit(node, adjacency, cyclic, &mut emitted, &mut order);
+  }
+  for node in cyclic {
+    order.push(node.clone());
+  }
+  order
+}
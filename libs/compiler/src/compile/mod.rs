@@ -1,7 +1,13 @@
 mod builder;
+mod cache;
+mod compiler_builder;
+mod graph;
+mod multi;
 pub(crate) mod output;
 mod project;
 
 pub use builder::SolidityProjectBuilder;
+pub use compiler_builder::SolidityCompileBuilder;
+pub use multi::{compile_many, CompileJob};
 pub(crate) use output::from_standard_json;
 pub use project::SolidityProject;
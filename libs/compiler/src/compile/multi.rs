@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use foundry_compilers::{
+  solc::{SolcCompiler, SolcLanguage},
+  Project, ProjectBuilder, ProjectPathsConfig,
+};
+use napi::bindgen_prelude::*;
+
+use super::output::{self, ArtifactSelection, CoreContractArtifact};
+use super::project::auto_detect_remappings;
+use crate::internal::errors::{map_napi_error, napi_error};
+use crate::internal::solc;
+use crate::types::CompileOutput;
+
+/// One job for `compile_many`: an independent project root, compiled with its own pinned solc
+/// version, and restricted to `sources` when given (every source under the root otherwise). Each
+/// job gets its own `Project<SolcCompiler>`, so a set of files whose `pragma solidity` ranges can't
+/// all be satisfied by one solc invocation can still be compiled together in a single call.
+#[napi(object)]
+pub struct CompileJob {
+  pub root_path: String,
+  pub solc_version: String,
+  #[napi(ts_type = "string[] | undefined")]
+  pub sources: Option<Vec<String>>,
+}
+
+/// Compiles several `CompileJob`s concurrently on a worker pool sized to available cores, merging
+/// every job's artifacts into one `CompileOutput`. Artifacts are deduplicated by `contract_name`,
+/// first job wins - so a file present under more than one job's `sources` subset doesn't produce a
+/// duplicate - while `errors` are concatenated in job order.
+#[napi]
+pub fn compile_many(jobs: Vec<CompileJob>) -> Result<CompileOutput> {
+  if jobs.is_empty() {
+    return Err(napi_error("compileMany requires at least one job."));
+  }
+
+  let worker_count = std::thread::available_parallelism()
+    .map(|count| count.get())
+    .unwrap_or(1)
+    .max(1);
+
+  let mut job_outputs: Vec<output::CoreCompileOutput> = Vec::with_capacity(jobs.len());
+  for chunk in jobs.chunks(worker_count) {
+    let chunk_outputs: Vec<Result<output::CoreCompileOutput>> = std::thread::scope(|scope| {
+      let handles: Vec<_> = chunk.iter().map(|job| scope.spawn(|| run_job(job))).collect();
+      handles
+        .into_iter()
+        .map(|handle| {
+          handle
+            .join()
+            .unwrap_or_else(|_| Err(napi_error("A compileMany worker thread panicked")))
+        })
+        .collect()
+    });
+    for job_output in chunk_outputs {
+      job_outputs.push(job_output?);
+    }
+  }
+
+  Ok(merge_job_outputs(job_outputs))
+}
+
+/// Builds and compiles a single `CompileJob`: a fresh, ephemeral `Project<SolcCompiler>` pinned to
+/// `job.solc_version`, restricted to `job.sources` when given. Stamps every resulting artifact's
+/// `compiler_version` with `job.solc_version`, which `into_core_compile_output`'s other callers
+/// leave empty since they have no single pinned version to attribute an artifact to.
+fn run_job(job: &CompileJob) -> Result<output::CoreCompileOutput> {
+  let version = map_napi_error(solc::parse_version(&job.solc_version), "Failed to parse solc version")?;
+  let solc_binary = map_napi_error(solc::ensure_installed(&version), "Failed to resolve solc version")?;
+
+  let root = PathBuf::from(&job.root_path);
+  let mut paths: ProjectPathsConfig<SolcLanguage> = map_napi_error(
+    ProjectPathsConfig::hardhat(&root),
+    "Failed to create hardhat paths",
+  )?;
+  auto_detect_remappings(&mut paths);
+
+  let project: Project<SolcCompiler> = map_napi_error(
+    ProjectBuilder::default()
+      .paths(paths)
+      .ephemeral()
+      .build(SolcCompiler::Specific(solc_binary)),
+    "Failed to build project",
+  )?;
+
+  let compiled = match &job.sources {
+    Some(sources) => {
+      let source_paths: Vec<PathBuf> = sources.iter().map(PathBuf::from).collect();
+      map_napi_error(project.compile_files(source_paths), "Failed to compile job")?
+    }
+    None => map_napi_error(project.compile(), "Failed to compile job")?,
+  };
+
+  let mut core = output::into_core_compile_output(&compiled, ArtifactSelection::default());
+  for artifact in &mut core.artifacts {
+    artifact.compiler_version = job.solc_version.clone();
+  }
+  Ok(core)
+}
+
+/// Combines every job's `CoreCompileOutput` into the single result `compile_many` returns: artifacts
+/// deduplicated by `contract_name` (first job wins, by job order rather than completion order, so a
+/// chunked worker pool's result stays deterministic), `errors` concatenated in job order, and
+/// `source_list` taken from the first job that has one (each job compiled its own sources under its
+/// own solc invocation, so there's no single combined indexing to reconstruct).
+fn merge_job_outputs(outputs: Vec<output::CoreCompileOutput>) -> CompileOutput {
+  let mut by_name: BTreeMap<String, CoreContractArtifact> = BTreeMap::new();
+  let mut errors = Vec::new();
+  let mut has_compiler_errors = false;
+  let mut source_list = Vec::new();
+
+  for job_output in outputs {
+    has_compiler_errors = has_compiler_errors || job_output.has_compiler_errors;
+    errors.extend(job_output.errors);
+    if source_list.is_empty() {
+      source_list = job_output.source_list;
+    }
+    for artifact in job_output.artifacts {
+      by_name.entry(artifact.contract_name.clone()).or_insert(artifact);
+    }
+  }
+
+  let (model_checker_diagnostics, errors): (Vec<_>, Vec<_>) =
+    errors.into_iter().partition(output::is_model_checker_diagnostic);
+
+  CompileOutput {
+    artifacts: by_name
+      .into_values()
+      .map(output::to_types_contract_artifact)
+      .collect(),
+    errors: errors.into_iter().map(output::to_types_compiler_error).collect(),
+    model_checker_diagnostics: model_checker_diagnostics
+      .into_iter()
+      .map(output::to_types_model_checker_diagnostic)
+      .collect(),
+    has_compiler_errors,
+    source_list,
+    // No single project's file cache spans several independently-compiled jobs, and rehydrating
+    // standalone sources across jobs isn't something any caller of `compile_many` has asked for yet.
+    standalone_sources: Vec::new(),
+    cached: false,
+    // Same reasoning as `standalone_sources` above - no caller of `compile_many` has asked for
+    // build-info emission across a multi-job compile yet.
+    build_info_path: None,
+  }
+}
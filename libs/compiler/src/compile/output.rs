@@ -1,8 +1,17 @@
-use foundry_compilers::artifacts::{CompilerOutput, Contract, Error};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use foundry_compilers::artifacts::{Bytecode, CompilerOutput, Contract, Error, Settings, SourceFile};
 use foundry_compilers::solc::SolcCompiler;
-use foundry_compilers::{Artifact, ProjectCompileOutput};
+use foundry_compilers::{Artifact, Project, ProjectCompileOutput};
 use serde_json::Value;
 
+use super::cache;
+use crate::ast::utils::sanitize_ast_value;
+use crate::internal::errors::map_err_with_context;
+use crate::internal::source_map::{decode_compact_entries, RawJump};
+use crate::types;
+
 #[derive(Debug, Clone)]
 pub struct CoreCompilerError {
   pub message: String,
@@ -17,26 +26,198 @@ pub struct CoreSourceLocation {
   pub end: i32,
 }
 
+/// One decoded instruction entry from solc's compact source-map string (`s:l:f:j:m`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceMapEntry {
+  pub start: u32,
+  pub length: u32,
+  pub file_index: i32,
+  pub jump: JumpType,
+  pub modifier_depth: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JumpType {
+  In,
+  Out,
+  Regular,
+}
+
+impl From<RawJump> for JumpType {
+  fn from(jump: RawJump) -> Self {
+    match jump {
+      RawJump::In => JumpType::In,
+      RawJump::Out => JumpType::Out,
+      RawJump::Regular => JumpType::Regular,
+    }
+  }
+}
+
+/// Decode solc's compact source-map string into per-instruction entries.
+///
+/// Each `;`-separated entry has the form `s:l:f:j:m`; any field, or an entire
+/// trailing entry, may be empty, meaning "inherit the previous instruction's value". The actual
+/// decode is shared with [`crate::compiler::sourcemap::decode_source_map_entries`] via
+/// [`decode_compact_entries`]; this just wraps each [`RawSourceMapEntry`] in this module's own
+/// `SourceMapEntry`.
+pub fn decode_source_map(raw: &str) -> Vec<SourceMapEntry> {
+  decode_compact_entries(raw)
+    .into_iter()
+    .map(|entry| SourceMapEntry {
+      start: entry.start,
+      length: entry.length,
+      file_index: entry.file_index,
+      jump: entry.jump.into(),
+      modifier_depth: entry.modifier_depth,
+    })
+    .collect()
+}
+
+/// A single bytecode object (creation or deployed) with its decoded source map.
+#[derive(Debug, Clone, Default)]
+pub struct CoreContractBytecode {
+  pub object: Vec<u8>,
+  pub source_map: Option<Vec<SourceMapEntry>>,
+}
+
+/// One unresolved library reference from solc's `evm.bytecode.linkReferences`/
+/// `evm.deployedBytecode.linkReferences`: a 20-byte placeholder slot at byte offset `start` (of
+/// length `length`, always 20) in a contract's bytecode, left for `library` declared in `file`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkRef {
+  pub file: String,
+  pub library: String,
+  pub start: usize,
+  pub length: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct CoreContractArtifact {
   pub contract_name: String,
   pub abi: Option<Value>,
-  pub bytecode: Option<Vec<u8>>,
-  pub deployed_bytecode: Option<Vec<u8>>,
+  pub bytecode: Option<CoreContractBytecode>,
+  pub deployed_bytecode: Option<CoreContractBytecode>,
+  /// Unresolved library placeholders across `bytecode` and `deployed_bytecode`, keyed by
+  /// `"<file>:<library>"`. Populated straight from solc's own `linkReferences`, not re-derived by
+  /// scanning the bytecode hex the way [`crate::contract::linker::unresolved_link_references`]
+  /// does for the already-assembled [`crate::contract::ContractBytecode`].
+  pub link_references: BTreeMap<String, Vec<LinkRef>>,
+  /// Raw, still solc-compact-encoded creation source map, gated by
+  /// `ArtifactSelection::source_maps`. `bytecode`'s own `source_map` is already decoded into
+  /// `SourceMapEntry`s; this keeps the original string around for callers (debuggers, coverage
+  /// tooling) that want to re-decode or diff it directly rather than through decoded entries.
+  pub source_map: Option<String>,
+  /// Same as `source_map`, but for `deployed_bytecode`.
+  pub deployed_source_map: Option<String>,
+  /// Populated when `ExtraOutputKind::StorageLayout` was requested.
+  pub storage_layout: Option<Value>,
+  /// Populated when `ExtraOutputKind::GasEstimates` was requested.
+  pub gas_estimates: Option<Value>,
+  /// Populated when `ExtraOutputKind::MethodIdentifiers` was requested.
+  pub method_identifiers: Option<Value>,
+  /// Populated when `ExtraOutputKind::Metadata` was requested.
+  pub metadata: Option<Value>,
+  /// Populated when `ExtraOutputKind::DevDoc` was requested.
+  pub devdoc: Option<Value>,
+  /// Populated when `ExtraOutputKind::UserDoc` was requested.
+  pub userdoc: Option<Value>,
+  /// Populated when `ExtraOutputKind::Ir` was requested.
+  pub ir: Option<String>,
+  /// Populated when `ExtraOutputKind::IrOptimized` was requested.
+  pub ir_optimized: Option<String>,
+  /// Populated when `ArtifactSelection::ast` is set: the compiled source file's full Solidity AST,
+  /// as solc returns it. Shared across every contract compiled from the same file.
+  pub ast: Option<Value>,
+  /// The solc version that produced this artifact. Only `compile::multi::compile_many` - which
+  /// pins an explicit version per job - stamps this; every other caller compiles through a single
+  /// `SolcCompiler::default()` project (auto-detecting per file) with no one version to attribute
+  /// an artifact to, so it's left empty there.
+  pub compiler_version: String,
 }
 
-#[derive(Debug, Clone)]
+/// Gates the optional, potentially-large fields `project_contract`/`standard_contract` populate
+/// beyond what solc's own output selection already gates - `storage_layout`/`gas_estimates`/etc.
+/// are naturally `None` unless requested via `ExtraOutputKind`, so they need no flag here. A source
+/// map or full AST, by contrast, is available as soon as bytecode/AST output is selected at all, so
+/// callers that only want bytecode opt into paying for them explicitly.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ArtifactSelection {
+  pub source_maps: bool,
+  pub ast: bool,
+}
+
+impl CoreContractArtifact {
+  /// Splices a 20-byte address into every placeholder span `link_references` records for each
+  /// `(file, library)` key present in `libs`, in both `bytecode` and `deployed_bytecode`. Errors,
+  /// without modifying either bytecode object, if any `link_references` entry has no matching
+  /// `libs` key - listing every such still-unlinked `file:library` name.
+  pub fn link_libraries(
+    &mut self,
+    libs: &BTreeMap<(String, String), [u8; 20]>,
+  ) -> crate::internal::errors::Result<()> {
+    let mut unresolved = Vec::new();
+    for (key, refs) in &self.link_references {
+      for link_ref in refs {
+        if !libs.contains_key(&(link_ref.file.clone(), link_ref.library.clone())) {
+          unresolved.push(key.clone());
+        }
+      }
+    }
+    if !unresolved.is_empty() {
+      unresolved.sort();
+      unresolved.dedup();
+      return Err(crate::internal::errors::Error::new(format!(
+        "Unresolved library references: {}",
+        unresolved.join(", ")
+      )));
+    }
+
+    for refs in self.link_references.values() {
+      for link_ref in refs {
+        let Some(address) = libs.get(&(link_ref.file.clone(), link_ref.library.clone())) else {
+          continue;
+        };
+        if let Some(bytecode) = self.bytecode.as_mut() {
+          splice_address(&mut bytecode.object, link_ref.start, link_ref.length, address);
+        }
+        if let Some(bytecode) = self.deployed_bytecode.as_mut() {
+          splice_address(&mut bytecode.object, link_ref.start, link_ref.length, address);
+        }
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// Overwrites `object[start..start + length]` with `address` when the span fits - a bytecode
+/// object only carries one of `bytecode`/`deployed_bytecode`'s placeholder spans at a time, so a
+/// `LinkRef` from the other object is silently a no-op here rather than an error.
+fn splice_address(object: &mut [u8], start: usize, length: usize, address: &[u8; 20]) {
+  let Some(slot) = object.get_mut(start..start + length) else {
+    return;
+  };
+  let copy_len = slot.len().min(address.len());
+  slot[..copy_len].copy_from_slice(&address[..copy_len]);
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct CoreCompileOutput {
   pub artifacts: Vec<CoreContractArtifact>,
   pub errors: Vec<CoreCompilerError>,
   pub has_compiler_errors: bool,
+  /// Maps a source map's `file_index` back to the path solc compiled it from.
+  pub source_list: Vec<String>,
 }
 
-pub fn into_core_compile_output(output: ProjectCompileOutput<SolcCompiler>) -> CoreCompileOutput {
+pub fn into_core_compile_output(
+  output: &ProjectCompileOutput<SolcCompiler>,
+  selection: ArtifactSelection,
+) -> CoreCompileOutput {
   let has_compiler_errors = output.has_compiler_errors();
   let artifacts = output
     .artifacts()
-    .map(|(name, artifact)| project_contract(&name, artifact))
+    .map(|(name, artifact)| project_contract(&name, artifact, selection))
     .collect();
   let errors = output
     .output()
@@ -44,23 +225,287 @@ pub fn into_core_compile_output(output: ProjectCompileOutput<SolcCompiler>) -> C
     .iter()
     .map(to_compiler_error)
     .collect();
+  let source_list = build_source_list(&output.output().sources);
 
   CoreCompileOutput {
     artifacts,
     has_compiler_errors,
     errors,
+    source_list,
   }
 }
 
-pub fn from_standard_json(output: CompilerOutput) -> CoreCompileOutput {
+/// Bridges a raw project compile into the napi-facing `crate::types::CompileOutput`, the shape
+/// `SolidityProject::compile`/`compile_file`/`compile_files` return.
+pub fn into_compile_output(output: ProjectCompileOutput<SolcCompiler>) -> types::CompileOutput {
+  // Reports whether the project's file cache determined nothing needed recompiling, not just for
+  // this call but across `compile`/`compile_file`/`compile_files`, since they all share the same
+  // underlying `Project` and therefore the same on-disk cache.
+  let cached = output.is_unchanged();
+  let core = into_core_compile_output(&output, ArtifactSelection::default());
+  let standalone_sources = standalone_source_artifacts(&output);
+  let (model_checker_diagnostics, errors): (Vec<_>, Vec<_>) =
+    core.errors.into_iter().partition(is_model_checker_diagnostic);
+
+  types::CompileOutput {
+    artifacts: core.artifacts.into_iter().map(to_types_contract_artifact).collect(),
+    errors: errors.into_iter().map(to_types_compiler_error).collect(),
+    model_checker_diagnostics: model_checker_diagnostics
+      .into_iter()
+      .map(to_types_model_checker_diagnostic)
+      .collect(),
+    has_compiler_errors: core.has_compiler_errors,
+    source_list: core.source_list,
+    standalone_sources,
+    cached,
+    // The project-bound facade writes its own `build_infos` cache entries through
+    // `foundry_compilers`' normal project pipeline; `Compiler::write_build_info` is specific to
+    // the flat-file `compileSources`/`compileFiles` facade's `emitBuildInfo` option.
+    build_info_path: None,
+  }
+}
+
+/// solc reports SMTChecker/model-checker findings through the same `errors` array as ordinary
+/// diagnostics, distinguished only by a `CHC:` (CHC engine) or `BMC:` (BMC engine) message prefix.
+pub(crate) fn is_model_checker_diagnostic(error: &CoreCompilerError) -> bool {
+  let message = error.message.trim_start();
+  message.starts_with("CHC:") || message.starts_with("BMC:")
+}
+
+/// Source paths that produced at least one contract, i.e. everything `artifacts_with_files`
+/// attributes a `ConfigurableContractArtifact` to. Used to tell which `output.output().sources`
+/// entries are standalone (no `ContractDefinition`).
+fn contract_source_paths(output: &ProjectCompileOutput<SolcCompiler>) -> BTreeSet<PathBuf> {
+  output
+    .artifacts_with_files()
+    .map(|(path, _, _)| path.clone())
+    .collect()
+}
+
+/// Ports `standalone_source_file_to_artifact`: every compiled source path appears exactly once in
+/// `CompileOutput`, either via its contracts or, for library-less files with no
+/// `ContractDefinition` (free functions, bare constants, `error`/`struct` declarations,
+/// pragma-only sources), via one of these entries carrying just its AST.
+fn standalone_source_artifacts(
+  output: &ProjectCompileOutput<SolcCompiler>,
+) -> Vec<types::StandaloneSourceArtifact> {
+  let with_contracts = contract_source_paths(output);
+
+  output
+    .output()
+    .sources
+    .iter()
+    .filter(|(path, source)| source.ast.is_some() && !with_contracts.contains(*path))
+    .filter_map(|(path, source)| {
+      let mut ast = serde_json::to_value(source.ast.as_ref()?).ok()?;
+      sanitize_ast_value(&mut ast);
+      Some(types::StandaloneSourceArtifact {
+        source_path: path.to_string_lossy().to_string(),
+        source_id: source.id,
+        ast,
+      })
+    })
+    .collect()
+}
+
+/// Runs `project.compile_files` over only the sources `cache::filter_dirty` reports as changed
+/// (by content hash, or transitively via `imports`), rehydrating everything else from
+/// `cache_path`/`artifacts_dir`'s last-recorded artifacts via `cache::load_clean_artifacts` instead
+/// of recompiling them. Always re-records a fresh cache index afterwards, so the next call only
+/// needs to send what's dirty **then**. Rehydrated sources have no AST handy without a full
+/// recompile, so `standalone_sources` only ever reflects files compiled this run.
+pub fn into_incremental_compile_output(
+  project: &Project<SolcCompiler>,
+  cache_path: &Path,
+  artifacts_dir: &Path,
+  root: &Path,
+  sources: &BTreeMap<PathBuf, String>,
+  imports: &BTreeMap<PathBuf, Vec<PathBuf>>,
+) -> crate::internal::errors::Result<types::CompileOutput> {
+  let settings_json = serde_json::to_string(&Settings::default()).unwrap_or_default();
+  let digest = cache::settings_digest("default", &settings_json);
+
+  let dirty = cache::filter_dirty(cache_path, sources, imports, &digest);
+  let clean_files: Vec<PathBuf> = sources
+    .keys()
+    .filter(|path| !dirty.contains(*path))
+    .cloned()
+    .collect();
+
+  let mut artifacts = Vec::new();
+  let mut errors = Vec::new();
+  let mut has_compiler_errors = false;
+  let mut source_list = Vec::new();
+  let mut standalone_sources = Vec::new();
+  let mut artifact_names: BTreeMap<PathBuf, Vec<String>> = BTreeMap::new();
+
+  if !dirty.is_empty() {
+    let compiled = map_err_with_context(
+      project.compile_files(dirty.iter().cloned().collect::<Vec<_>>()),
+      "Failed to compile project",
+    )?;
+    for path in &dirty {
+      artifact_names.entry(path.clone()).or_default();
+    }
+    for (path, name, _) in compiled.artifacts_with_files() {
+      artifact_names.entry(path.clone()).or_default().push(name.clone());
+    }
+    standalone_sources = standalone_source_artifacts(&compiled);
+    let core = into_core_compile_output(&compiled, ArtifactSelection::default());
+    has_compiler_errors = core.has_compiler_errors;
+    errors = core.errors;
+    source_list = core.source_list;
+    artifacts.extend(core.artifacts);
+  }
+
+  artifacts.extend(cache::load_clean_artifacts(
+    cache_path,
+    artifacts_dir,
+    root,
+    &clean_files,
+  ));
+
+  map_err_with_context(
+    cache::record(cache_path, &digest, sources, imports, &artifact_names),
+    "Failed to persist incremental compile cache",
+  )?;
+
+  let (model_checker_diagnostics, errors): (Vec<_>, Vec<_>) =
+    errors.into_iter().partition(is_model_checker_diagnostic);
+
+  Ok(types::CompileOutput {
+    artifacts: artifacts.into_iter().map(to_types_contract_artifact).collect(),
+    errors: errors.into_iter().map(to_types_compiler_error).collect(),
+    model_checker_diagnostics: model_checker_diagnostics
+      .into_iter()
+      .map(to_types_model_checker_diagnostic)
+      .collect(),
+    has_compiler_errors,
+    source_list,
+    standalone_sources,
+    cached: dirty.is_empty(),
+    build_info_path: None,
+  })
+}
+
+pub(crate) fn to_types_contract_artifact(artifact: CoreContractArtifact) -> types::ContractArtifact {
+  let CoreContractArtifact {
+    contract_name,
+    abi,
+    bytecode,
+    deployed_bytecode,
+    // `types::ContractArtifact` doesn't carry link references, raw source maps, or the AST yet -
+    // `CoreContractArtifact` is the only shape that needs them (`link_libraries`, debuggers reading
+    // the core API directly), before any JS-facing conversion happens.
+    link_references: _,
+    source_map: _,
+    deployed_source_map: _,
+    storage_layout,
+    gas_estimates,
+    method_identifiers,
+    metadata,
+    devdoc,
+    userdoc,
+    ir,
+    ir_optimized,
+    ast: _,
+    // `types::ContractArtifact` doesn't carry which solc version produced an artifact yet either -
+    // same reasoning as `link_references`/`source_map` above.
+    compiler_version: _,
+  } = artifact;
+
+  let abi_json = abi.as_ref().and_then(|abi| serde_json::to_string(abi).ok());
+
+  types::ContractArtifact {
+    contract_name,
+    abi,
+    abi_json,
+    bytecode: bytecode.map(to_types_bytecode),
+    deployed_bytecode: deployed_bytecode.map(to_types_bytecode),
+    storage_layout,
+    gas_estimates,
+    method_identifiers,
+    metadata,
+    devdoc,
+    userdoc,
+    ir,
+    ir_optimized,
+  }
+}
+
+fn to_types_bytecode(bytecode: CoreContractBytecode) -> types::ContractBytecode {
+  let CoreContractBytecode { object, source_map } = bytecode;
+  types::ContractBytecode {
+    hex: Some(format!("0x{}", hex::encode(&object))),
+    bytes: Some(object),
+    source_map: source_map.map(|entries| entries.iter().map(to_types_source_map_entry).collect()),
+  }
+}
+
+fn to_types_source_map_entry(entry: &SourceMapEntry) -> types::SourceMapEntry {
+  types::SourceMapEntry {
+    start: entry.start,
+    length: entry.length,
+    file_index: entry.file_index,
+    jump: to_types_jump_type(entry.jump),
+    modifier_depth: entry.modifier_depth,
+  }
+}
+
+fn to_types_jump_type(jump: JumpType) -> types::JumpType {
+  match jump {
+    JumpType::In => types::JumpType::In,
+    JumpType::Out => types::JumpType::Out,
+    JumpType::Regular => types::JumpType::Regular,
+  }
+}
+
+pub(crate) fn to_types_compiler_error(error: CoreCompilerError) -> types::CompilerError {
+  types::CompilerError {
+    message: error.message,
+    severity: error.severity,
+    source_location: error.source_location.map(to_types_source_location),
+  }
+}
+
+fn to_types_source_location(location: CoreSourceLocation) -> types::SourceLocation {
+  types::SourceLocation {
+    file: location.file,
+    start: location.start,
+    end: location.end,
+  }
+}
+
+pub(crate) fn to_types_model_checker_diagnostic(error: CoreCompilerError) -> types::ModelCheckerDiagnostic {
+  types::ModelCheckerDiagnostic {
+    message: error.message,
+    severity: error.severity,
+    source_location: error.source_location.map(to_types_source_location),
+  }
+}
+
+pub fn from_standard_json(output: CompilerOutput, selection: ArtifactSelection) -> CoreCompileOutput {
   let has_compiler_errors = output.has_error();
   let CompilerOutput {
-    errors, contracts, ..
+    errors,
+    contracts,
+    sources,
+    ..
   } = output;
+  let source_list = build_source_list(&sources);
   let artifacts = contracts
-    .into_values()
-    .flat_map(|set| set.into_iter())
-    .map(|(name, contract)| standard_contract(name, contract))
+    .into_iter()
+    .flat_map(|(path, set)| {
+      let ast = selection
+        .ast
+        .then(|| sources.get(&path))
+        .flatten()
+        .and_then(|source| source.ast.as_ref())
+        .and_then(|ast| serde_json::to_value(ast).ok());
+      set
+        .into_iter()
+        .map(move |(name, contract)| standard_contract(name, contract, ast.clone(), selection))
+    })
     .collect();
   let errors = errors.iter().map(to_compiler_error).collect();
 
@@ -68,58 +513,185 @@ pub fn from_standard_json(output: CompilerOutput) -> CoreCompileOutput {
     artifacts,
     has_compiler_errors,
     errors,
+    source_list,
+  }
+}
+
+fn build_source_list(sources: &BTreeMap<PathBuf, SourceFile>) -> Vec<String> {
+  let mut by_index: BTreeMap<u32, String> = BTreeMap::new();
+  for (path, source) in sources {
+    by_index.insert(source.id, path.to_string_lossy().to_string());
+  }
+
+  let len = by_index.keys().next_back().map(|last| *last + 1).unwrap_or(0);
+  (0..len)
+    .map(|index| by_index.get(&index).cloned().unwrap_or_default())
+    .collect()
+}
+
+fn bytecode_to_core(bytecode: &Bytecode) -> Option<CoreContractBytecode> {
+  let object = bytecode.object.as_bytes()?.to_vec();
+  let source_map = bytecode
+    .source_map
+    .as_ref()
+    .map(|raw| decode_source_map(raw));
+  Some(CoreContractBytecode { object, source_map })
+}
+
+/// Merges `bytecode`'s and `deployed_bytecode`'s `linkReferences` into the flattened
+/// `"<file>:<library>" -> Vec<LinkRef>` shape `CoreContractArtifact::link_references` stores.
+fn link_references_to_core(
+  bytecode: Option<&Bytecode>,
+  deployed_bytecode: Option<&Bytecode>,
+) -> BTreeMap<String, Vec<LinkRef>> {
+  let mut by_key: BTreeMap<String, Vec<LinkRef>> = BTreeMap::new();
+  for bytecode in [bytecode, deployed_bytecode].into_iter().flatten() {
+    for (file, libraries) in &bytecode.link_references {
+      for (library, offsets) in libraries {
+        let key = format!("{file}:{library}");
+        for offset in offsets {
+          by_key.entry(key.clone()).or_default().push(LinkRef {
+            file: file.clone(),
+            library: library.clone(),
+            start: offset.start as usize,
+            length: offset.length as usize,
+          });
+        }
+      }
+    }
   }
+  by_key
 }
 
-fn project_contract(name: &str, artifact: &impl Artifact) -> CoreContractArtifact {
+pub(crate) fn project_contract(
+  name: &str,
+  artifact: &impl Artifact,
+  selection: ArtifactSelection,
+) -> CoreContractArtifact {
   let bytecode_cow = artifact.get_contract_bytecode();
   let abi = bytecode_cow
     .abi
     .as_ref()
     .and_then(|abi| serde_json::to_value(&**abi).ok());
-  let bytecode = bytecode_cow
-    .bytecode
-    .as_ref()
-    .and_then(|bytecode| bytecode.object.as_bytes())
-    .map(|bytes| bytes.to_vec());
-  let deployed_bytecode = bytecode_cow
+  let bytecode_raw = bytecode_cow.bytecode.as_ref();
+  let deployed_raw = bytecode_cow
     .deployed_bytecode
     .as_ref()
-    .and_then(|bytecode| bytecode.bytecode.as_ref())
-    .and_then(|bytecode| bytecode.object.as_bytes())
-    .map(|bytes| bytes.to_vec());
+    .and_then(|bytecode| bytecode.bytecode.as_ref());
+  let link_references = link_references_to_core(bytecode_raw, deployed_raw);
+  let source_map = selection
+    .source_maps
+    .then(|| bytecode_raw.and_then(|bytecode| bytecode.source_map.clone()))
+    .flatten();
+  let deployed_source_map = selection
+    .source_maps
+    .then(|| deployed_raw.and_then(|bytecode| bytecode.source_map.clone()))
+    .flatten();
+  let bytecode = bytecode_raw.and_then(bytecode_to_core);
+  let deployed_bytecode = deployed_raw.and_then(bytecode_to_core);
 
   CoreContractArtifact {
     contract_name: name.to_string(),
     abi,
     bytecode,
     deployed_bytecode,
+    link_references,
+    source_map,
+    deployed_source_map,
+    // Not populated for project-based compiles: `Artifact` doesn't expose the raw solc
+    // output needed for these, only the pure `compile_standard_sources` path does.
+    storage_layout: None,
+    gas_estimates: None,
+    method_identifiers: None,
+    metadata: None,
+    devdoc: None,
+    userdoc: None,
+    ir: None,
+    ir_optimized: None,
+    // Same limitation as the fields above: `Artifact` has no path back to the compiled source's AST.
+    ast: None,
+    // Stamped afterwards by `compile::multi::run_job`, the only caller that knows a single pinned
+    // version for every artifact it produces.
+    compiler_version: String::new(),
   }
 }
 
-fn standard_contract(name: String, contract: Contract) -> CoreContractArtifact {
+fn standard_contract(
+  name: String,
+  contract: Contract,
+  ast: Option<Value>,
+  selection: ArtifactSelection,
+) -> CoreContractArtifact {
   let abi = contract
     .abi
     .as_ref()
     .and_then(|abi| serde_json::to_value(abi).ok());
-  let bytecode = contract
+  let bytecode_raw = contract.evm.as_ref().and_then(|evm| evm.bytecode.as_ref());
+  let deployed_raw = contract
+    .evm
+    .as_ref()
+    .and_then(|evm| evm.deployed_bytecode.as_ref())
+    .and_then(|bytecode| bytecode.bytecode.as_ref());
+  let link_references = link_references_to_core(bytecode_raw, deployed_raw);
+  let source_map = selection
+    .source_maps
+    .then(|| bytecode_raw.and_then(|bytecode| bytecode.source_map.clone()))
+    .flatten();
+  let deployed_source_map = selection
+    .source_maps
+    .then(|| deployed_raw.and_then(|bytecode| bytecode.source_map.clone()))
+    .flatten();
+  let bytecode = bytecode_raw.and_then(bytecode_to_core);
+  let deployed_bytecode = deployed_raw.and_then(bytecode_to_core);
+
+  let storage_layout = contract
+    .storage_layout
+    .as_ref()
+    .and_then(|layout| serde_json::to_value(layout).ok());
+  let gas_estimates = contract
     .evm
     .as_ref()
-    .and_then(|evm| evm.bytecode.as_ref())
-    .and_then(|bytecode| bytecode.object.as_bytes())
-    .map(|bytes| bytes.to_vec());
-  let deployed_bytecode = contract
+    .and_then(|evm| evm.gas_estimates.as_ref())
+    .and_then(|estimates| serde_json::to_value(estimates).ok());
+  let method_identifiers = contract
     .evm
     .as_ref()
-    .and_then(|evm| evm.deployed_bytecode.as_ref())
-    .and_then(|bytecode| bytecode.bytes())
-    .map(|bytes| bytes.to_vec());
+    .and_then(|evm| serde_json::to_value(&evm.method_identifiers).ok());
+  let metadata = contract
+    .metadata
+    .as_ref()
+    .and_then(|metadata| serde_json::to_value(metadata).ok());
+  let devdoc = contract
+    .devdoc
+    .as_ref()
+    .and_then(|devdoc| serde_json::to_value(devdoc).ok());
+  let userdoc = contract
+    .userdoc
+    .as_ref()
+    .and_then(|userdoc| serde_json::to_value(userdoc).ok());
 
   CoreContractArtifact {
     contract_name: name,
     abi,
     bytecode,
     deployed_bytecode,
+    link_references,
+    source_map,
+    deployed_source_map,
+    storage_layout,
+    gas_estimates,
+    method_identifiers,
+    metadata,
+    devdoc,
+    userdoc,
+    ir: contract.ir.clone(),
+    ir_optimized: contract.ir_optimized.clone(),
+    // Already gated by `selection.ast` in `from_standard_json`, which is the only caller with a
+    // source path to look the AST up by.
+    ast,
+    // `from_standard_json` has no per-job solc version either - `compile::multi` stamps it after
+    // the fact on its own copy of these artifacts.
+    compiler_version: String::new(),
   }
 }
 
@@ -1,77 +1,185 @@
 use foundry_compilers::{
+  artifacts::remappings::Remapping,
   solc::{SolcCompiler, SolcLanguage},
-  Project, ProjectBuilder, ProjectCompileOutput, ProjectPathsConfig,
+  Project, ProjectBuilder, ProjectPathsConfig,
 };
 use napi::bindgen_prelude::*;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
 
+use super::compiler_builder::SolidityCompileBuilder;
 use super::output;
 use crate::internal::errors::map_napi_error;
 use crate::types::CompileOutput;
 
+/// Adds whatever `Remapping::find_many` discovers under each of `paths.libraries` (e.g.
+/// `node_modules/` for a Hardhat layout, `lib/` for Dapptools) on top of anything already present,
+/// skipping duplicates. Lets imports like `@openzeppelin/contracts/...` resolve without the caller
+/// hand-writing a remapping for every installed library.
+pub(crate) fn auto_detect_remappings(paths: &mut ProjectPathsConfig<SolcLanguage>) {
+  let mut seen: BTreeSet<String> = paths
+    .remappings
+    .iter()
+    .map(|remapping| remapping.to_string())
+    .collect();
+
+  for lib in paths.libraries.clone() {
+    for remapping in Remapping::find_many(&lib) {
+      if seen.insert(remapping.to_string()) {
+        paths.remappings.push(remapping);
+      }
+    }
+  }
+}
+
+/// Parses each `from=to` string and merges it onto `paths.remappings`, skipping any that are
+/// already present (by their canonical `to_string()` form).
+pub(crate) fn apply_explicit_remappings(
+  paths: &mut ProjectPathsConfig<SolcLanguage>,
+  remappings: &[String],
+) -> Result<()> {
+  let mut seen: BTreeSet<String> = paths
+    .remappings
+    .iter()
+    .map(|remapping| remapping.to_string())
+    .collect();
+
+  for raw in remappings {
+    let remapping = Remapping::from_str(raw).map_err(|err| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Invalid remapping \"{raw}\": {err}"),
+      )
+    })?;
+    if seen.insert(remapping.to_string()) {
+      paths.remappings.push(remapping);
+    }
+  }
+
+  Ok(())
+}
+
 #[napi]
 pub struct SolidityProject {
-  pub(crate) project: Project<SolcCompiler>,
+  pub(crate) project: Arc<Project<SolcCompiler>>,
+  /// Whether `project` was built with foundry-compilers' file cache enabled; carried over to the
+  /// projects `SolidityCompileBuilder` rebuilds when `quiet`/`extraOutput` is set.
+  pub(crate) cached: bool,
 }
 
 #[napi]
 impl SolidityProject {
-  /// Create a new project from a root path using Hardhat layout
-  #[napi(factory)]
-  pub fn from_hardhat_root(root_path: String) -> Result<Self> {
+  /// Create a new project from a root path using Hardhat layout. `cached` opts into
+  /// foundry-compilers' file cache (content hash + imports + solc settings), so a later `compile`
+  /// on a project built from the same root only recompiles the dirty subset; defaults to `false`
+  /// (every compile is a full, ephemeral solc invocation).
+  #[napi(factory, ts_args_type = "rootPath: string, cached?: boolean | undefined")]
+  pub fn from_hardhat_root(root_path: String, cached: Option<bool>) -> Result<Self> {
     let root = PathBuf::from(&root_path);
-    let paths: ProjectPathsConfig<SolcLanguage> = map_napi_error(
+    let mut paths: ProjectPathsConfig<SolcLanguage> = map_napi_error(
       ProjectPathsConfig::hardhat(&root),
       "Failed to create hardhat paths",
     )?;
+    auto_detect_remappings(&mut paths);
 
+    let cached = cached.unwrap_or(false);
+    let mut builder = ProjectBuilder::default().paths(paths);
+    if !cached {
+      builder = builder.ephemeral();
+    }
     let project = map_napi_error(
-      ProjectBuilder::default()
-        .paths(paths)
-        .build(SolcCompiler::default()),
+      builder.build(SolcCompiler::default()),
       "Failed to build project",
     )?;
 
-    Ok(SolidityProject { project })
+    Ok(SolidityProject {
+      project: Arc::new(project),
+      cached,
+    })
   }
 
-  /// Create a new project from a root path using Dapptools layout
-  #[napi(factory)]
-  pub fn from_dapptools_root(root_path: String) -> Result<Self> {
+  /// Create a new project from a root path using Dapptools layout. See `from_hardhat_root` for
+  /// what `cached` does.
+  #[napi(factory, ts_args_type = "rootPath: string, cached?: boolean | undefined")]
+  pub fn from_dapptools_root(root_path: String, cached: Option<bool>) -> Result<Self> {
     let root = PathBuf::from(&root_path);
-    let paths: ProjectPathsConfig<SolcLanguage> = map_napi_error(
+    let mut paths: ProjectPathsConfig<SolcLanguage> = map_napi_error(
       ProjectPathsConfig::dapptools(&root),
       "Failed to create dapptools paths",
     )?;
+    auto_detect_remappings(&mut paths);
 
+    let cached = cached.unwrap_or(false);
+    let mut builder = ProjectBuilder::default().paths(paths);
+    if !cached {
+      builder = builder.ephemeral();
+    }
     let project = map_napi_error(
-      ProjectBuilder::default()
-        .paths(paths)
-        .build(SolcCompiler::default()),
+      builder.build(SolcCompiler::default()),
       "Failed to build project",
     )?;
 
-    Ok(SolidityProject { project })
+    Ok(SolidityProject {
+      project: Arc::new(project),
+      cached,
+    })
+  }
+
+  /// Start a fluent, per-call compile configuration - quiet/analysis-only mode, extra solc output
+  /// selection, and file filtering - built from this project's paths and cache mode.
+  /// `compile`/`compileFile`/`compileFiles` are thin wrappers over this builder's defaults.
+  #[napi]
+  pub fn compiler(&self) -> SolidityCompileBuilder {
+    SolidityCompileBuilder::new(Arc::clone(&self.project), self.cached)
   }
 
   /// Compile all contracts in the project
   #[napi]
   pub fn compile(&self) -> Result<CompileOutput> {
-    self.compile_with(Project::compile, "Compilation failed")
+    self.compiler().compile()
   }
 
   /// Compile a single file
   #[napi]
   pub fn compile_file(&self, file_path: String) -> Result<CompileOutput> {
-    let path = PathBuf::from(file_path);
-    self.compile_with(|project| project.compile_file(&path), "Compilation failed")
+    self.compiler().compile_file(file_path)
   }
 
   /// Compile multiple files
   #[napi]
   pub fn compile_files(&self, file_paths: Vec<String>) -> Result<CompileOutput> {
-    let paths: Vec<PathBuf> = file_paths.iter().map(PathBuf::from).collect();
-    self.compile_with(|project| project.compile_files(paths), "Compilation failed")
+    self.compiler().compile_files(file_paths)
+  }
+
+  /// Compile only the sources that changed (by content hash, including transitively through
+  /// imports) since the last `compileIncremental` call, rehydrating every other contract's
+  /// artifact from `ProjectPaths::cache`/`ProjectPaths::artifacts` instead of recompiling it. This
+  /// is a separate, content-hash-driven cache from the `cached` flag on `from_hardhat_root`/
+  /// `from_dapptools_root` (which only toggles foundry-compilers' own file cache) -
+  /// `compileIncremental` tracks its fingerprints itself, so it works the same way
+  /// whether or not this project was built with `cached: true`.
+  #[napi]
+  pub fn compile_incremental(&self) -> Result<CompileOutput> {
+    let sources = map_napi_error(self.project.sources(), "Failed to get sources")?;
+    let source_texts: BTreeMap<PathBuf, String> = sources
+      .iter()
+      .map(|(path, source)| (path.clone(), source.content.as_str().to_string()))
+      .collect();
+    let imports = self.import_adjacency_paths()?;
+
+    map_napi_error(
+      output::into_incremental_compile_output(
+        &self.project,
+        &self.project.paths.cache,
+        &self.project.paths.artifacts,
+        &self.project.paths.root,
+        &source_texts,
+        &imports,
+      ),
+      "Failed to run incremental compile",
+    )
   }
 
   /// Find the path of a contract by its name
@@ -98,16 +206,15 @@ impl SolidityProject {
     )
   }
 
-  fn compile_with<F>(&self, compile_fn: F, context: &str) -> Result<CompileOutput>
-  where
-    F: FnOnce(
-      &Project<SolcCompiler>,
-    ) -> std::result::Result<
-      ProjectCompileOutput<SolcCompiler>,
-      foundry_compilers::error::SolcError,
-    >,
-  {
-    let output = map_napi_error(compile_fn(&self.project), context)?;
-    Ok(output::into_compile_output(output))
+  /// Get the resolved import remappings (auto-detected and explicit) for the project
+  #[napi]
+  pub fn get_remappings(&self) -> Vec<String> {
+    self
+      .project
+      .paths
+      .remappings
+      .iter()
+      .map(|remapping| remapping.to_string())
+      .collect()
   }
 }
@@ -0,0 +1,298 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use super::output::SourceArtifacts;
+use crate::contract::Contract;
+use crate::internal::config::ArtifactFormat;
+use crate::internal::errors::{Error, Result};
+
+/// Writes a just-finished project/contract compile's artifacts to disk in a specific on-disk
+/// shape, dispatched on `CompilerConfig::artifact_format`. Foundry's own project writer already
+/// produces the default Foundry layout during `Project::compile()`/`compile_file()`, so
+/// `FoundryArtifactEmitter` has nothing left to do; `HardhatArtifactEmitter` mirrors Hardhat's own
+/// `<ContractName>.json` artifact shape alongside it, so Hardhat-based tooling can read tevm's
+/// output directly without a conversion step.
+pub(crate) trait ArtifactEmitter {
+  fn emit(
+    &self,
+    artifacts_dir: &Path,
+    artifacts: &BTreeMap<String, SourceArtifacts>,
+    emit_sourceless_artifacts: bool,
+  ) -> Result<()>;
+}
+
+pub(crate) struct FoundryArtifactEmitter;
+
+impl ArtifactEmitter for FoundryArtifactEmitter {
+  fn emit(
+    &self,
+    _artifacts_dir: &Path,
+    _artifacts: &BTreeMap<String, SourceArtifacts>,
+    _emit_sourceless_artifacts: bool,
+  ) -> Result<()> {
+    Ok(())
+  }
+}
+
+pub(crate) struct HardhatArtifactEmitter;
+
+impl ArtifactEmitter for HardhatArtifactEmitter {
+  fn emit(
+    &self,
+    artifacts_dir: &Path,
+    artifacts: &BTreeMap<String, SourceArtifacts>,
+    emit_sourceless_artifacts: bool,
+  ) -> Result<()> {
+    for (source_path, entry) in artifacts {
+      for (name, contract) in &entry.contracts {
+        let state = contract.state();
+        let record = json!({
+          "contractName": name,
+          "sourceName": source_path,
+          "abi": state.abi.clone().unwrap_or(serde_json::Value::Array(Vec::new())),
+          "bytecode": state.creation_bytecode.as_ref().map(|bytecode| bytecode.to_hex()).unwrap_or_default(),
+          "deployedBytecode": state.deployed_bytecode.as_ref().map(|bytecode| bytecode.to_hex()).unwrap_or_default(),
+          "linkReferences": unresolved_link_references(
+            state.creation_bytecode.as_ref().map(|bytecode| bytecode.to_hex()).as_deref(),
+          ),
+          "deployedLinkReferences": unresolved_link_references(
+            state.deployed_bytecode.as_ref().map(|bytecode| bytecode.to_hex()).as_deref(),
+          ),
+        });
+
+        let dir = artifacts_dir.join(source_path);
+        fs::create_dir_all(&dir).map_err(|err| {
+          Error::new(format!(
+            "Failed to create Hardhat artifact directory {}: {err}",
+            dir.display()
+          ))
+        })?;
+
+        let path = dir.join(format!("{name}.json"));
+        let serialised = serde_json::to_string_pretty(&record).map_err(|err| {
+          Error::new(format!("Failed to serialise Hardhat artifact for {name}: {err}"))
+        })?;
+        fs::write(&path, serialised).map_err(|err| {
+          Error::new(format!("Failed to write Hardhat artifact {}: {err}", path.display()))
+        })?;
+      }
+
+      if emit_sourceless_artifacts && entry.contracts.is_empty() {
+        self.write_sourceless_artifact(artifacts_dir, source_path, entry)?;
+      }
+    }
+    Ok(())
+  }
+}
+
+impl HardhatArtifactEmitter {
+  /// Writes a minimal placeholder record for a source file that declares no contracts (an
+  /// interface-only file, a library of free functions, a file of plain constants, ...), so tooling
+  /// walking the artifacts directory finds an entry for every compiled source instead of silently
+  /// missing the ones that never produced a named contract.
+  fn write_sourceless_artifact(
+    &self,
+    artifacts_dir: &Path,
+    source_path: &str,
+    entry: &SourceArtifacts,
+  ) -> Result<()> {
+    let record = json!({
+      "sourceName": source_path,
+      "sourceId": entry.source_id,
+      "contracts": [],
+    });
+
+    let dir = artifacts_dir.join(source_path);
+    fs::create_dir_all(&dir).map_err(|err| {
+      Error::new(format!(
+        "Failed to create Hardhat artifact directory {}: {err}",
+        dir.display()
+      ))
+    })?;
+
+    let path = dir.join("_source.json");
+    let serialised = serde_json::to_string_pretty(&record).map_err(|err| {
+      Error::new(format!(
+        "Failed to serialise Hardhat source record for {source_path}: {err}"
+      ))
+    })?;
+    fs::write(&path, serialised).map_err(|err| {
+      Error::new(format!(
+        "Failed to write Hardhat source record {}: {err}",
+        path.display()
+      ))
+    })
+  }
+}
+
+/// Thin JSON wrapper around [`crate::contract::linker::unresolved_link_references`] - the shared
+/// scan also backing `JsContractBytecode::link_references` - for the Hardhat artifact shape, which
+/// wants plain `{start, length}` objects rather than the napi `LinkReferenceOffset` type.
+fn unresolved_link_references(hex: Option<&str>) -> Value {
+  let by_placeholder = crate::contract::linker::unresolved_link_references(hex);
+  let by_placeholder: BTreeMap<String, Vec<Value>> = by_placeholder
+    .into_iter()
+    .map(|(placeholder, offsets)| {
+      let offsets = offsets
+        .into_iter()
+        .map(|(start, length)| json!({ "start": start, "length": length }))
+        .collect();
+      (placeholder, offsets)
+    })
+    .collect();
+
+  json!(by_placeholder)
+}
+
+pub(crate) fn emitter_for(format: ArtifactFormat) -> Box<dyn ArtifactEmitter> {
+  match format {
+    ArtifactFormat::Foundry => Box::new(FoundryArtifactEmitter),
+    ArtifactFormat::Hardhat => Box::new(HardhatArtifactEmitter),
+  }
+}
+
+// -----------------------------------------------------------------------------
+// Standalone JSON artifact writer
+// -----------------------------------------------------------------------------
+
+/// Full vs. compact shape for a single artifact file a [`JsonArtifactWriter`] writes - `Full`
+/// carries every `ContractState` field a downstream tool might want (abi, bytecode, deployed
+/// bytecode, metadata, userdoc/devdoc, storage layout, link references); `Compact` keeps only
+/// `{ abi, bytecode, deployedBytecode }`, for a deploy pipeline that doesn't want the rest riding
+/// along.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArtifactShape {
+  Full,
+  Compact,
+}
+
+/// Files an `ArtifactWriter` found already present under its output directory before a write -
+/// paths relative to that directory, slash-normalized. Handed in so [`JsonArtifactWriter::write`]
+/// can tell a stale artifact (left over from a previous write, now orphaned because its
+/// source/contract was renamed or removed) from one it's about to (re)write, and delete it.
+#[derive(Clone, Debug, Default)]
+pub struct WriteContext {
+  pub existing_files: BTreeSet<String>,
+}
+
+/// What a write call did: every file it wrote (`written`) and every stale file from the incoming
+/// `WriteContext` it deleted because nothing in this write touched it (`removed`).
+#[derive(Clone, Debug, Default)]
+pub struct WriteReport {
+  pub written: BTreeSet<String>,
+  pub removed: BTreeSet<String>,
+}
+
+/// Persists a compile's artifacts to an arbitrary directory as plain JSON. Unlike
+/// [`ArtifactEmitter`], which dispatches on `CompilerConfig::artifact_format` to match an existing
+/// Foundry/Hardhat project's on-disk layout, this is the crate's own default shape for embedders
+/// that want `CompileOutput` written to disk without adopting either convention - one file per
+/// `(source, contract)`, plus a combined `raw_artifacts` build-info file.
+pub(crate) trait ArtifactWriter {
+  fn write(
+    &self,
+    artifacts_dir: &Path,
+    artifacts: &BTreeMap<String, SourceArtifacts>,
+    raw_artifacts: &Value,
+    context: &WriteContext,
+  ) -> Result<WriteReport>;
+}
+
+pub(crate) struct JsonArtifactWriter {
+  pub shape: ArtifactShape,
+}
+
+impl JsonArtifactWriter {
+  fn contract_record(&self, name: &str, source_path: &str, contract: &Contract) -> Value {
+    let state = contract.state();
+    let creation_hex = state.creation_bytecode.as_ref().map(|bytecode| bytecode.to_hex());
+    let deployed_hex = state.deployed_bytecode.as_ref().map(|bytecode| bytecode.to_hex());
+    let abi = state.abi.clone().unwrap_or(Value::Array(Vec::new()));
+
+    match self.shape {
+      ArtifactShape::Compact => json!({
+        "abi": abi,
+        "bytecode": creation_hex.unwrap_or_default(),
+        "deployedBytecode": deployed_hex.unwrap_or_default(),
+      }),
+      ArtifactShape::Full => json!({
+        "contractName": name,
+        "sourceName": source_path,
+        "abi": abi,
+        "bytecode": creation_hex.clone().unwrap_or_default(),
+        "deployedBytecode": deployed_hex.clone().unwrap_or_default(),
+        "metadata": state.metadata.clone(),
+        "userdoc": state.userdoc.clone(),
+        "devdoc": state.devdoc.clone(),
+        "storageLayout": state.storage_layout.clone(),
+        "linkReferences": unresolved_link_references(creation_hex.as_deref()),
+        "deployedLinkReferences": unresolved_link_references(deployed_hex.as_deref()),
+      }),
+    }
+  }
+}
+
+impl ArtifactWriter for JsonArtifactWriter {
+  fn write(
+    &self,
+    artifacts_dir: &Path,
+    artifacts: &BTreeMap<String, SourceArtifacts>,
+    raw_artifacts: &Value,
+    context: &WriteContext,
+  ) -> Result<WriteReport> {
+    let mut written = BTreeSet::new();
+
+    for (source_path, entry) in artifacts {
+      for (name, contract) in &entry.contracts {
+        let relative = normalize_slashes(&format!("{source_path}/{name}.json"));
+        let record = self.contract_record(name, source_path, contract);
+        write_json_file(artifacts_dir, &relative, &record)?;
+        written.insert(relative);
+      }
+    }
+
+    let build_info_relative = "build-info.json".to_string();
+    write_json_file(artifacts_dir, &build_info_relative, raw_artifacts)?;
+    written.insert(build_info_relative);
+
+    let removed: BTreeSet<String> = context.existing_files.difference(&written).cloned().collect();
+    for relative in &removed {
+      let path = artifacts_dir.join(relative);
+      if path.exists() {
+        fs::remove_file(&path).map_err(|err| {
+          Error::new(format!("Failed to remove stale artifact {}: {err}", path.display()))
+        })?;
+      }
+    }
+
+    Ok(WriteReport { written, removed })
+  }
+}
+
+/// Collapses `\`-separated path components to `/` - solc's own paths (and the contract names
+/// derived from them) are always `/`-separated even when tevm itself runs on Windows, but joining
+/// them onto `artifacts_dir` with `Path::join` would otherwise round-trip through native `\`
+/// separators before a relative path is recorded in a `WriteReport`/`WriteContext`.
+fn normalize_slashes(path: &str) -> String {
+  path.replace('\\', "/")
+}
+
+fn write_json_file(artifacts_dir: &Path, relative: &str, value: &Value) -> Result<()> {
+  let path = artifacts_dir.join(relative);
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent).map_err(|err| {
+      Error::new(format!(
+        "Failed to create artifact directory {}: {err}",
+        parent.display()
+      ))
+    })?;
+  }
+
+  let serialised = serde_json::to_string_pretty(value)
+    .map_err(|err| Error::new(format!("Failed to serialise artifact {relative}: {err}")))?;
+  fs::write(&path, serialised)
+    .map_err(|err| Error::new(format!("Failed to write artifact {}: {err}", path.display())))
+}
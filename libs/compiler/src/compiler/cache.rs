@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use foundry_compilers::artifacts::Settings;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+
+use super::output::{CompileOutput, CompileOutputJson};
+use crate::internal::cache_key::keccak_hex_parts;
+use crate::internal::errors::{map_err_with_context, Error, Result};
+
+/// On-disk cache for the synthetic (non-project) compile paths in [`super::project_runner`] and,
+/// for a context with no project at all, the standard-sources/AST branches of [`super::core`]: a
+/// flat key-value index under the cache directory, keyed on a hash of the input (source content,
+/// solc version, and settings) rather than per-file fingerprints, since `InlineSource`/`SourceMap`
+/// compiles have no import graph of their own to track dirty dependents through - the whole input
+/// is the unit of change. `super::core`'s callers never write a virtual source file to disk, so
+/// they always record an empty `source_paths` - there's nothing that can go stale underneath a
+/// hash that already captures the whole input. Real project compiles
+/// (`compile_project`/`compile_contract`) don't use this cache at all; they delegate to
+/// `foundry_compilers::Project`'s own incremental cache (`solidity-files-cache.json`, toggled by
+/// the same `config.cache_enabled` flag via `set_cached` in `build_project`), which already does
+/// per-file dirty tracking against the import graph.
+const CACHE_INDEX_FILENAME: &str = "compile-cache.json";
+
+/// One entry in the on-disk compile cache: the compiled output plus the virtual source paths it
+/// was produced from, so a stale entry (cache directory wiped, but the index survived) is
+/// detected instead of served.
+#[derive(Serialize, Deserialize)]
+struct CachedCompileEntry {
+  source_paths: Vec<PathBuf>,
+  output: CompileOutputJson,
+}
+
+fn index_path(cache_dir: &Path) -> PathBuf {
+  cache_dir.join(CACHE_INDEX_FILENAME)
+}
+
+fn read_index(cache_dir: &Path) -> BTreeMap<String, CachedCompileEntry> {
+  fs::read_to_string(index_path(cache_dir))
+    .ok()
+    .and_then(|contents| serde_json::from_str(&contents).ok())
+    .unwrap_or_default()
+}
+
+fn write_index(cache_dir: &Path, index: &BTreeMap<String, CachedCompileEntry>) -> Result<()> {
+  let path = index_path(cache_dir);
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent).map_err(|err| {
+      Error::new(format!(
+        "Failed to create compile cache directory {}: {err}",
+        parent.display()
+      ))
+    })?;
+  }
+
+  let serialised = map_err_with_context(
+    serde_json::to_string(index),
+    "Failed to serialise compile cache index",
+  )?;
+  fs::write(&path, serialised).map_err(|err| {
+    Error::new(format!(
+      "Failed to write compile cache index {}: {err}",
+      path.display()
+    ))
+  })
+}
+
+/// Derives the composite `hash(content_hash + solc_version + settings)` cache key: any change to
+/// the source content, the solc version, or the sanitized settings (optimizer, EVM version, output
+/// selection, ...) produces a different key.
+pub fn key(
+  content_hashes: impl IntoIterator<Item = String>,
+  solc_version: &Version,
+  settings: &Settings,
+) -> String {
+  let mut parts: Vec<String> = content_hashes.into_iter().collect();
+  parts.push(solc_version.to_string());
+  parts.push(serde_json::to_string(settings).unwrap_or_default());
+  keccak_hex_parts(parts.iter().map(String::as_str))
+}
+
+/// Looks up `key` in the cache under `cache_dir`, returning the cached output only if every
+/// virtual source it was compiled from is still present on disk.
+pub fn read(cache_dir: &Path, key: &str) -> Option<CompileOutput> {
+  let index = read_index(cache_dir);
+  let entry = index.get(key)?;
+  if !entry.source_paths.iter().all(|path| path.exists()) {
+    return None;
+  }
+  let mut output = CompileOutput::from_json(&entry.output).ok()?;
+  output.cached = true;
+  Some(output)
+}
+
+/// Persists `output` under `key`, recording `source_paths` so a later `read` can detect a stale
+/// entry if the cache directory (but not the index) gets wiped.
+pub fn write(
+  cache_dir: &Path,
+  key: String,
+  source_paths: Vec<PathBuf>,
+  output: &CompileOutput,
+) -> Result<()> {
+  let mut index = read_index(cache_dir);
+  index.insert(
+    key,
+    CachedCompileEntry {
+      source_paths,
+      output: output.to_json(),
+    },
+  );
+  write_index(cache_dir, &index)
+}
+
+/// Deletes the compile cache index under `cache_dir`, if one exists. Subsequent compiles behave as
+/// if nothing had ever been cached; this doesn't touch `foundry_compilers::Project`'s own
+/// `solidity-files-cache.json` for real projects, since that cache lives under the project's own
+/// paths and is managed entirely by foundry-compilers.
+pub fn clear(cache_dir: &Path) -> Result<()> {
+  let path = index_path(cache_dir);
+  match fs::remove_file(&path) {
+    Ok(()) => Ok(()),
+    Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+    Err(err) => Err(Error::new(format!(
+      "Failed to clear compile cache {}: {err}",
+      path.display()
+    ))),
+  }
+}
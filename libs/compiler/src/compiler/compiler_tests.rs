@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+  use std::collections::BTreeMap;
+  use std::path::PathBuf;
+
+  use crate::compiler::Compiler;
+  use crate::internal::config::{CompilerConfigOptions, CompilerLanguage};
+
+  /// Builds a `Compiler` configured for Vyper, which `Compiler::new` can initialise without
+  /// reaching out to `solc::ensure_installed` - mirrors `compiler::core::tests`' own
+  /// `compile_vyper_source*` tests, which sidestep a real solc install the same way.
+  fn vyper_compiler() -> Compiler {
+    let options = CompilerConfigOptions {
+      compiler: Some(CompilerLanguage::Vyper),
+      ..Default::default()
+    };
+    Compiler::new(Some(options)).expect("construct vyper compiler")
+  }
+
+  #[test]
+  fn compile_files_rejects_an_empty_path_list() {
+    let compiler = vyper_compiler();
+    let err = compiler.compile_files(Vec::new(), None).unwrap_err();
+    assert!(err
+      .to_string()
+      .contains("compileFiles requires at least one path"));
+  }
+
+  #[test]
+  fn config_mut_changes_are_visible_through_config() {
+    let mut compiler = vyper_compiler();
+    assert!(!compiler.config().deny_warnings);
+
+    compiler.config_mut().deny_warnings = true;
+
+    assert!(compiler.config().deny_warnings);
+  }
+
+  #[test]
+  fn from_root_attaches_a_project_and_resolves_its_paths() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    std::fs::create_dir_all(temp.path().join("src")).expect("create src dir");
+
+    let options = CompilerConfigOptions {
+      compiler: Some(CompilerLanguage::Vyper),
+      ..Default::default()
+    };
+    let compiler = Compiler::from_root(temp.path(), Some(options)).expect("construct compiler");
+
+    assert!(compiler.project().is_some());
+
+    let paths = compiler.get_paths().expect("resolve paths");
+    let expected_root = temp.path().canonicalize().expect("canonicalize temp dir");
+    assert_eq!(PathBuf::from(&paths.root), expected_root);
+  }
+
+  #[test]
+  fn resolve_graph_returns_an_empty_graph_with_no_project_and_no_sources() {
+    let compiler = vyper_compiler();
+    let graph = compiler
+      .resolve_graph(Some(BTreeMap::new()), None)
+      .expect("resolve graph");
+    assert!(graph.nodes.is_empty());
+  }
+
+  #[test]
+  fn into_state_hands_back_the_same_config() {
+    let compiler = vyper_compiler();
+    let state = compiler.into_state();
+    assert_eq!(state.config.language, CompilerLanguage::Vyper);
+  }
+}
@@ -11,17 +11,24 @@ use foundry_compilers::compilers::vyper::VyperInput;
 use foundry_compilers::compilers::CompilerOutput as FoundryCompilerOutput;
 use serde_json::{json, Value};
 
+use super::cache;
 use super::input::CompilationInput;
 use super::output::{build_compile_output, from_standard_json, vyper_error_to_core, CompileOutput};
-use super::project_runner::ProjectRunner;
+use super::project_runner::{
+  candidate_versions, config_version_satisfies_all, partition_by_version, write_build_info,
+  ProjectRunner,
+};
 use crate::ast::utils;
+use crate::internal::config;
 use crate::internal::config::{
   CompilerConfig, CompilerConfigOptions, CompilerLanguage, SolcConfig,
 };
 use crate::internal::errors::{map_err_with_context, Error, Result};
 use crate::internal::project::{
-  create_synthetic_context, FoundryAdapter, HardhatAdapter, ProjectContext, ProjectLayout,
+  create_synthetic_context, default_cache_dir, load_detected, FoundryAdapter, HardhatAdapter,
+  ProjectContext, ProjectLayout,
 };
+use crate::internal::resolver::Graph as ImportGraph;
 use crate::internal::{solc, vyper};
 
 #[derive(Clone)]
@@ -75,6 +82,13 @@ pub fn init_from_root(config: CompilerConfig, root: &Path) -> Result<State> {
   init(config, Some(context))
 }
 
+/// Like `init_from_foundry_root`/`init_from_hardhat_root`, but for a directory whose ecosystem
+/// the caller doesn't already know - `load_detected` probes `root` for each ecosystem's marker
+/// files and picks the matching adapter itself.
+pub fn init_from_detected_root(config: CompilerConfig, root: &Path) -> Result<State> {
+  init_with_context(config, || load_detected(root))
+}
+
 pub fn resolve_config(
   state: &State,
   overrides: Option<&CompilerConfigOptions>,
@@ -195,17 +209,54 @@ fn compile_standard_sources(
   match language {
     CompilerLanguage::Solidity | CompilerLanguage::Yul => {
       let solc_language = to_solc_language(language)?;
+
+      if matches!(language, CompilerLanguage::Solidity) {
+        let contents = source_contents(&sources);
+        if config.auto_detect_solc_version || !config_version_satisfies_all(config, &contents) {
+          return compile_multi_version_sources(config, contents, solc_language);
+        }
+      }
+
       let solc_config = SolcConfig {
         version: config.solc_version.clone(),
         settings: config.solc_settings.clone(),
         language: solc_language,
       };
+
+      // Unlike `ProjectRunner::compile`, this path never writes a virtual source file to disk, so
+      // there's nothing for `cache::read`'s staleness check to go stale - an empty `source_paths`
+      // is always fresh, and the content hash baked into the key is the only thing that matters.
+      let cache_dir = default_cache_dir();
+      let cache_key = if config.cache_enabled {
+        let content_hashes = sources
+          .values()
+          .map(|source| Source::content_hash_of(source.content.as_str()));
+        let key = cache::key(content_hashes, &solc_config.version, &solc_config.settings);
+        if let Some(cached) = cache::read(&cache_dir, &key) {
+          return Ok(cached);
+        }
+        Some(key)
+      } else {
+        None
+      };
+
       let solc = solc::ensure_installed(&solc_config.version)?;
       let mut input = SolcInput::new(solc_language, sources, solc_config.settings.clone());
       input.sanitize(&solc.version);
       let output: CompilerOutput =
         map_err_with_context(solc.compile_as(&input), "Solc compilation failed")?;
-      Ok(from_standard_json(output))
+      let build_info_path = write_build_info(config, &solc.version, &input, &output)?;
+      let mut result = from_standard_json(
+        output,
+        &config.ignored_error_codes,
+        &config.severity_overrides,
+        config.promote_all_warnings_to_errors,
+      );
+      result.build_info_path = build_info_path.map(|path| path.to_string_lossy().into_owned());
+      if let Some(key) = cache_key {
+        cache::write(&cache_dir, key, Vec::new(), &result)?;
+      }
+      Ok(result)
     }
     CompilerLanguage::Vyper => {
       let vyper_compiler = vyper::ensure_installed(config.vyper_settings.path.clone())?;
@@ -236,11 +287,109 @@ fn compile_standard_sources(
         &compiler_output.sources,
         raw_artifacts,
         errors,
+        &config.ignored_error_codes,
+        &config.severity_overrides,
+        config.promote_all_warnings_to_errors,
       ))
     }
   }
 }
 
+fn source_contents(sources: &Sources) -> BTreeMap<String, String> {
+  sources
+    .iter()
+    .map(|(path, source)| (path.to_string_lossy().into_owned(), source.content.as_str().to_string()))
+    .collect()
+}
+
+/// The `compile_standard_sources` counterpart of `ProjectRunner::compile_multi_version`: used when
+/// sources aren't routed through a project at all (e.g. a synthetic, cache-disabled context, or a
+/// caller that asked for `auto_detect_solc_version`), this buckets `contents` the same way - first-fit
+/// groups whose pragmas share a satisfiable version - and runs one direct
+/// `solc.compile_as` per bucket instead of going through `foundry_compilers::Project`, merging
+/// every bucket's output into a single `CompileOutput`.
+///
+/// Every group's solc is resolved up front, before any bucket starts compiling, so two worker
+/// threads can never race to install the same missing version. Buckets are otherwise independent
+/// - distinct solc binaries, distinct inputs - so, mirroring `ProjectRunner::compile_multi_version`,
+/// they're compiled concurrently up to `config.solc_jobs` workers at a time (defaulting to the
+/// available CPU count). Outputs are folded into `CompileOutput::merge` in bucket order rather than
+/// completion order, keeping the merged result deterministic regardless of which worker finishes
+/// first.
+fn compile_multi_version_sources(
+  config: &CompilerConfig,
+  contents: BTreeMap<String, String>,
+  solc_language: FoundrySolcLanguage,
+) -> Result<CompileOutput> {
+  let pool = candidate_versions(config.offline_mode)?;
+  let groups = partition_by_version(&contents, &pool)?;
+
+  if !config.restrictions.is_empty() {
+    let resolved_versions: BTreeMap<String, semver::Version> = groups
+      .iter()
+      .flat_map(|group| group.sources.iter().map(|(path, _)| (path.clone(), group.version.clone())))
+      .collect();
+    config::check_restrictions(&config.restrictions, &resolved_versions, &config.solc_settings)?;
+  }
+
+  for group in &groups {
+    solc::ensure_available(&group.version, config.offline_mode)?;
+  }
+
+  let jobs = config
+    .solc_jobs
+    .filter(|&jobs| jobs > 0)
+    .unwrap_or_else(|| std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1));
+
+  let mut outputs = Vec::with_capacity(groups.len());
+  for chunk in groups.chunks(jobs.max(1)) {
+    let chunk_outputs: Vec<Result<CompileOutput>> = std::thread::scope(|scope| {
+      let handles: Vec<_> = chunk
+        .iter()
+        .map(|group| {
+          scope.spawn(|| {
+            let solc = solc::ensure_installed(&group.version)?;
+
+            let mut bucket_sources = Sources::new();
+            for (path, content) in &group.sources {
+              bucket_sources.insert(PathBuf::from(path), Source::new(content.clone()));
+            }
+
+            let mut input = SolcInput::new(solc_language, bucket_sources, config.solc_settings.clone());
+            input.sanitize(&solc.version);
+            let output: CompilerOutput =
+              map_err_with_context(solc.compile_as(&input), "Solc compilation failed")?;
+            let build_info_path = write_build_info(config, &solc.version, &input, &output)?;
+            let mut result = from_standard_json(
+              output,
+              &config.ignored_error_codes,
+              &config.severity_overrides,
+              config.promote_all_warnings_to_errors,
+            );
+            result.build_info_path = build_info_path.map(|path| path.to_string_lossy().into_owned());
+            Ok(result)
+          })
+        })
+        .collect();
+
+      handles
+        .into_iter()
+        .map(|handle| {
+          handle
+            .join()
+            .unwrap_or_else(|_| Err(Error::new("A compilation worker thread panicked")))
+        })
+        .collect()
+    });
+
+    for output in chunk_outputs {
+      outputs.push(output?);
+    }
+  }
+
+  Ok(CompileOutput::merge(outputs))
+}
+
 fn compile_ast_sources(
   config: &CompilerConfig,
   ast_sources: BTreeMap<String, SourceUnit>,
@@ -256,20 +405,34 @@ fn compile_ast_sources(
     settings: config.solc_settings.clone(),
     language: FoundrySolcLanguage::Solidity,
   };
-  let solc = solc::ensure_installed(&solc_config.version)?;
-  let settings_value = map_err_with_context(
-    serde_json::to_value(&solc_config.settings),
-    "Failed to serialize settings",
-  )?;
 
   let mut sources_value = serde_json::Map::new();
-  for (file_name, unit) in ast_sources {
+  let mut content_hashes = Vec::with_capacity(ast_sources.len());
+  for (file_name, unit) in &ast_sources {
     let mut ast_value =
-      map_err_with_context(serde_json::to_value(&unit), "Failed to serialise AST value")?;
+      map_err_with_context(serde_json::to_value(unit), "Failed to serialise AST value")?;
     utils::sanitize_ast_value(&mut ast_value);
-    sources_value.insert(file_name, json!({ "ast": ast_value }));
+    content_hashes.push(Source::content_hash_of(&ast_value.to_string()));
+    sources_value.insert(file_name.clone(), json!({ "ast": ast_value }));
   }
 
+  let cache_dir = default_cache_dir();
+  let cache_key = if config.cache_enabled {
+    let key = cache::key(content_hashes, &solc_config.version, &solc_config.settings);
+    if let Some(cached) = cache::read(&cache_dir, &key) {
+      return Ok(cached);
+    }
+    Some(key)
+  } else {
+    None
+  };
+
+  let solc = solc::ensure_installed(&solc_config.version)?;
+  let settings_value = map_err_with_context(
+    serde_json::to_value(&solc_config.settings),
+    "Failed to serialize settings",
+  )?;
+
   let input = json!({
     "language": "SolidityAST",
     "sources": sources_value,
@@ -278,7 +441,16 @@ fn compile_ast_sources(
 
   let output: CompilerOutput =
     map_err_with_context(solc.compile_as(&input), "Solc compilation failed")?;
-  Ok(from_standard_json(output))
+  let result = from_standard_json(
+    output,
+    &config.ignored_error_codes,
+    &config.severity_overrides,
+    config.promote_all_warnings_to_errors,
+  );
+  if let Some(key) = cache_key {
+    cache::write(&cache_dir, key, Vec::new(), &result)?;
+  }
+  Ok(result)
 }
 
 fn compile_file_paths(
@@ -335,12 +507,46 @@ fn compile_file_paths(
   let final_language = language_override
     .or(detected_language)
     .unwrap_or(config.language);
+
+  if matches!(final_language, CompilerLanguage::Solidity) {
+    pull_in_transitive_imports(config, &mut string_entries)?;
+  }
+
   let mut updated = config.clone();
   updated.language = final_language;
   let sources = sources_from_map(string_entries);
   compile_standard_sources(&updated, sources, final_language)
 }
 
+/// Expands `entries` in place with every file transitively `import`ed by one of its members that
+/// isn't already present, so a caller handing `compile_files` a single entrypoint doesn't have to
+/// enumerate its whole dependency tree itself. Resolution follows the same rules
+/// `ProjectRunner::compile`'s `FilePaths` arm uses for an on-disk project - `config.remappings`
+/// first, then `config.library_paths` - via the shared [`ImportGraph`] resolver; import cycles are
+/// tolerated (Solidity allows them), and an import that doesn't resolve to a file on disk is simply
+/// left out, the same as it would be for a real project compile.
+fn pull_in_transitive_imports(
+  config: &CompilerConfig,
+  entries: &mut BTreeMap<String, String>,
+) -> Result<()> {
+  let roots: Vec<PathBuf> = entries.keys().map(PathBuf::from).collect();
+  let closure = ImportGraph::build(&roots, &config.remappings, &BTreeSet::new(), &config.library_paths);
+
+  for path in closure.reachable_files() {
+    let key = path.to_string_lossy().into_owned();
+    if entries.contains_key(&key) {
+      continue;
+    }
+    let content = map_err_with_context(
+      fs::read_to_string(&path),
+      "Failed to read an imported source file",
+    )?;
+    entries.insert(key, content);
+  }
+
+  Ok(())
+}
+
 fn try_parse_ast_from_file(
   canonical_path: &str,
   content: &str,
@@ -530,6 +736,17 @@ mod tests {
       .contains("compileSources requires all entries to share the same language"));
   }
 
+  #[test]
+  fn source_contents_reads_every_entry_as_a_plain_string() {
+    let mut sources = Sources::new();
+    sources.insert(PathBuf::from("A.sol"), Source::new("contract A {}"));
+    sources.insert(PathBuf::from("B.sol"), Source::new("contract B {}"));
+
+    let contents = source_contents(&sources);
+    assert_eq!(contents.get("A.sol").map(String::as_str), Some("contract A {}"));
+    assert_eq!(contents.get("B.sol").map(String::as_str), Some("contract B {}"));
+  }
+
   #[test]
   fn compile_vyper_source() {
     let mut config = CompilerConfig::default();
@@ -571,4 +788,25 @@ mod tests {
       "unexpected error: {err}"
     );
   }
+
+  #[test]
+  fn pull_in_transitive_imports_adds_files_reachable_from_the_entry_set() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path();
+    fs::write(root.join("A.sol"), "import \"./B.sol\";\ncontract A {}\n").expect("write A");
+    fs::write(root.join("B.sol"), "contract B {}\n").expect("write B");
+
+    let config = CompilerConfig::default();
+    let entry = root.join("A.sol").canonicalize().expect("canonical entry");
+    let mut entries = BTreeMap::new();
+    entries.insert(
+      entry.to_string_lossy().into_owned(),
+      fs::read_to_string(&entry).expect("read A"),
+    );
+
+    pull_in_transitive_imports(&config, &mut entries).expect("pull imports");
+
+    assert_eq!(entries.len(), 2);
+    assert!(entries.values().any(|content| content.contains("contract B")));
+  }
 }
@@ -0,0 +1,246 @@
+use std::collections::BTreeMap;
+
+use super::output::{CompilerError, SecondarySourceLocation, SeverityLevel};
+
+const ANSI_BOLD: &str = "\x1b[1m";
+const ANSI_RED: &str = "\x1b[31m";
+const ANSI_YELLOW: &str = "\x1b[33m";
+const ANSI_BLUE: &str = "\x1b[34m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+fn severity_label(severity: SeverityLevel) -> &'static str {
+  match severity {
+    SeverityLevel::Error => "error",
+    SeverityLevel::Warning => "warning",
+    SeverityLevel::Info => "info",
+  }
+}
+
+fn severity_color(severity: SeverityLevel) -> &'static str {
+  match severity {
+    SeverityLevel::Error => ANSI_RED,
+    SeverityLevel::Warning => ANSI_YELLOW,
+    SeverityLevel::Info => ANSI_BLUE,
+  }
+}
+
+fn paint(text: &str, code: &str, color: bool) -> String {
+  if color {
+    format!("{code}{text}{ANSI_RESET}")
+  } else {
+    text.to_string()
+  }
+}
+
+/// 1-based `(line, column)` of byte offset `offset` within `source`, clamped to its length.
+fn line_col(source: &str, offset: i32) -> (usize, usize) {
+  let offset = (offset.max(0) as usize).min(source.len());
+  let mut line = 1;
+  let mut line_start = 0;
+  for (index, byte) in source.as_bytes()[..offset].iter().enumerate() {
+    if *byte == b'\n' {
+      line += 1;
+      line_start = index + 1;
+    }
+  }
+  (line, offset - line_start + 1)
+}
+
+/// Renders the `  --> file:line:col` header, offending source line, and a caret/underline span
+/// beneath it for the byte range `[start, end)` of `source` - the same frame shape for a
+/// diagnostic's primary `source_location` and for each of its `secondary_source_locations`, just
+/// with a different `label` underneath the span.
+fn render_frame(
+  file: &str,
+  source: &str,
+  start: i32,
+  end: i32,
+  label: Option<&str>,
+  color: bool,
+) -> String {
+  let (line, column) = line_col(source, start);
+  let (end_line, end_column) = line_col(source, end.max(start));
+  let line_text = source.lines().nth(line - 1).unwrap_or_default();
+  let span_len = if end_line == line {
+    end_column.saturating_sub(column).max(1)
+  } else {
+    line_text.len().saturating_sub(column - 1).max(1)
+  };
+
+  let gutter = line.to_string();
+  let pad = " ".repeat(gutter.len());
+  let caret = paint(&"^".repeat(span_len), ANSI_BOLD, color);
+  let underline = format!("{pad} | {}{caret}", " ".repeat(column - 1));
+  let underline = match label {
+    Some(label) => format!("{underline} {label}"),
+    None => underline,
+  };
+
+  format!("{pad}--> {file}:{line}:{column}\n{pad} |\n{gutter} | {line_text}\n{underline}")
+}
+
+/// Renders one `secondary_source_locations` entry as its own frame, with the entry's message as
+/// the label under its span - skipped entirely when it has no file, or `sources` has no text for
+/// that file.
+fn render_secondary_frame(
+  location: &SecondarySourceLocation,
+  sources: &BTreeMap<String, String>,
+  color: bool,
+) -> Option<String> {
+  let file = location.file.as_deref()?;
+  let source = sources.get(file)?;
+  let start = location.start.unwrap_or(0);
+  let end = location.end.unwrap_or(start);
+  Some(render_frame(
+    file,
+    source,
+    start,
+    end,
+    location.message.as_deref(),
+    color,
+  ))
+}
+
+/// Renders a full code-frame diagnostic: a `severity[code]: message` header, the primary frame
+/// from `error.source_location`, and one inlined frame per `error.secondary_source_locations`
+/// entry whose file appears in `sources`. Returns `None` when `error` has no `source_location`, or
+/// `sources` has no text for its file - in either case there's nothing to render a frame from.
+pub fn render_diagnostic(
+  error: &CompilerError,
+  sources: &BTreeMap<String, String>,
+  color: bool,
+) -> Option<String> {
+  let location = error.source_location.as_ref()?;
+  let source = sources.get(&location.file)?;
+
+  let code_suffix = error
+    .error_code
+    .map(|code| format!("[{code}]"))
+    .unwrap_or_default();
+  let header = paint(
+    &format!("{}{code_suffix}", severity_label(error.severity)),
+    severity_color(error.severity),
+    color,
+  );
+
+  let mut out = format!(
+    "{header}: {}\n{}",
+    error.message,
+    render_frame(
+      &location.file,
+      source,
+      location.start,
+      location.end,
+      None,
+      color
+    )
+  );
+
+  for secondary in error.secondary_source_locations.iter().flatten() {
+    if let Some(frame) = render_secondary_frame(secondary, sources, color) {
+      out.push('\n');
+      out.push_str(&frame);
+    }
+  }
+
+  Some(out)
+}
+
+/// `error` with `formatted_message` replaced by [`render_diagnostic`]'s output, when a frame could
+/// be rendered for it - left untouched (including whatever `formatted_message` solc/vyper already
+/// set) when `sources` has no text for its file.
+pub fn with_rendered_frame(
+  error: &CompilerError,
+  sources: &BTreeMap<String, String>,
+  color: bool,
+) -> CompilerError {
+  let mut error = error.clone();
+  if let Some(frame) = render_diagnostic(&error, sources, color) {
+    error.formatted_message = Some(frame);
+  }
+  error
+}
+
+#[cfg(test)]
+mod tests {
+  use super::super::output::SourceLocation;
+  use super::*;
+
+  fn error_at(message: &str, file: &str, start: i32, end: i32) -> CompilerError {
+    CompilerError {
+      message: message.to_string(),
+      formatted_message: None,
+      component: "general".to_string(),
+      severity: SeverityLevel::Error,
+      error_type: "TypeError".to_string(),
+      error_code: Some(9574),
+      source_location: Some(SourceLocation { file: file.to_string(), start, end }),
+      secondary_source_locations: None,
+      vyper_source_location: None,
+    }
+  }
+
+  #[test]
+  fn renders_a_plain_text_frame_with_header_and_caret() {
+    let mut sources = BTreeMap::new();
+    sources.insert(
+      "Foo.sol".to_string(),
+      "contract Foo {\n  uint x = wrong;\n}\n".to_string(),
+    );
+    let error = error_at("Undeclared identifier.", "Foo.sol", 25, 30);
+
+    let rendered = render_diagnostic(&error, &sources, false).unwrap();
+
+    assert!(rendered.contains("error[9574]: Undeclared identifier."));
+    assert!(rendered.contains("--> Foo.sol:2:11"));
+    assert!(rendered.contains("uint x = wrong;"));
+    assert!(rendered.contains("^^^^^"));
+  }
+
+  #[test]
+  fn colors_the_severity_header_when_requested() {
+    let mut sources = BTreeMap::new();
+    sources.insert("Foo.sol".to_string(), "x".to_string());
+    let error = error_at("bad", "Foo.sol", 0, 1);
+
+    let rendered = render_diagnostic(&error, &sources, true).unwrap();
+
+    assert!(rendered.contains(ANSI_RED));
+    assert!(rendered.contains(ANSI_RESET));
+  }
+
+  #[test]
+  fn inlines_secondary_frames_with_their_own_messages() {
+    let mut sources = BTreeMap::new();
+    sources.insert("Foo.sol".to_string(), "uint a;\nuint a;\n".to_string());
+    let mut error = error_at("Identifier already declared.", "Foo.sol", 8, 12);
+    error.secondary_source_locations = Some(vec![SecondarySourceLocation {
+      file: Some("Foo.sol".to_string()),
+      start: Some(0),
+      end: Some(4),
+      message: Some("The previous declaration is here.".to_string()),
+    }]);
+
+    let rendered = render_diagnostic(&error, &sources, false).unwrap();
+
+    assert!(rendered.contains("The previous declaration is here."));
+    assert_eq!(rendered.matches("-->").count(), 2);
+  }
+
+  #[test]
+  fn returns_none_without_source_text_for_the_file() {
+    let error = error_at("bad", "Missing.sol", 0, 1);
+
+    assert!(render_diagnostic(&error, &BTreeMap::new(), false).is_none());
+  }
+
+  #[test]
+  fn with_rendered_frame_leaves_the_message_untouched_when_source_is_unavailable() {
+    let mut error = error_at("bad", "Missing.sol", 0, 1);
+    error.formatted_message = Some("solc's own message".to_string());
+
+    let rendered = with_rendered_frame(&error, &BTreeMap::new(), false);
+
+    assert_eq!(rendered.formatted_message.as_deref(), Some("solc's own message"));
+  }
+}
@@ -0,0 +1,171 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use foundry_compilers::artifacts::remappings::Remapping;
+
+use super::graph::{extract_imports, resolve_import};
+use crate::internal::config::CompilerConfig;
+use crate::internal::errors::{Error, Result};
+
+/// Produces a single self-contained Solidity source for `entry` by inlining every file it
+/// transitively imports, in dependency-first order, and merging their `pragma`/SPDX directives
+/// into one header. Import resolution (relative paths, then `config.remappings`) mirrors
+/// `resolve_graph`. Errors if `entry` is unknown, an import can't be resolved, or the import graph
+/// has a cycle - there's no well-defined "first" file to flatten into in that case.
+pub fn flatten_source(
+  config: &CompilerConfig,
+  sources: &BTreeMap<String, String>,
+  entry: &str,
+) -> Result<String> {
+  if !sources.contains_key(entry) {
+    return Err(Error::new(format!(
+      "Cannot flatten: unknown entry source \"{entry}\"."
+    )));
+  }
+
+  let mut order = Vec::new();
+  let mut emitted = BTreeSet::new();
+  let mut visiting = Vec::new();
+  visit(
+    entry,
+    sources,
+    &config.remappings,
+    &mut emitted,
+    &mut visiting,
+    &mut order,
+  )?;
+
+  let mut spdx: Option<String> = None;
+  let mut pragmas = Vec::new();
+  let mut seen_pragmas = BTreeSet::new();
+  let mut body = String::new();
+
+  for path in &order {
+    let contents = sources
+      .get(path)
+      .expect("flatten order only ever contains keys from `sources`");
+    let stripped = strip_directives(contents, &mut spdx, &mut pragmas, &mut seen_pragmas);
+
+    if !body.is_empty() {
+      body.push('\n');
+    }
+    body.push_str(&format!("\n// File: {path}\n"));
+    body.push_str(stripped.trim_end());
+    body.push('\n');
+  }
+
+  let mut output = format!(
+    "// SPDX-License-Identifier: {}\n",
+    spdx.unwrap_or_else(|| "UNLICENSED".to_string())
+  );
+  for pragma in &pragmas {
+    output.push_str(pragma);
+    output.push('\n');
+  }
+  output.push_str(&body);
+
+  Ok(output)
+}
+
+/// Post-order DFS over the import graph rooted at `path`: every import is visited (and appended
+/// to `order`) before `path` itself, so concatenating `order` in sequence always places a
+/// dependency ahead of its dependents.
+fn visit(
+  path: &str,
+  sources: &BTreeMap<String, String>,
+  remappings: &[Remapping],
+  emitted: &mut BTreeSet<String>,
+  visiting: &mut Vec<String>,
+  order: &mut Vec<String>,
+) -> Result<()> {
+  if emitted.contains(path) {
+    return Ok(());
+  }
+  if visiting.iter().any(|entry| entry == path) {
+    return Err(Error::new(format!(
+      "Cannot flatten: import cycle detected involving \"{path}\"."
+    )));
+  }
+
+  let contents = sources
+    .get(path)
+    .ok_or_else(|| Error::new(format!("Cannot flatten: unresolved source \"{path}\".")))?;
+
+  visiting.push(path.to_string());
+  for import in extract_imports(contents) {
+    match resolve_import(path, &import, sources, remappings) {
+      Some(target) => visit(&target, sources, remappings, emitted, visiting, order)?,
+      None => {
+        return Err(Error::new(format!(
+          "Cannot flatten \"{path}\": unresolved import \"{import}\"."
+        )))
+      }
+    }
+  }
+  visiting.pop();
+
+  emitted.insert(path.to_string());
+  order.push(path.to_string());
+  Ok(())
+}
+
+/// Drops `// SPDX-License-Identifier`, `pragma`, and `import` lines from `contents`, folding the
+/// first SPDX identifier seen across the whole flatten into `spdx` and each distinct pragma (kept
+/// in first-seen order) into `pragmas`/`seen_pragmas`. Everything else passes through unchanged.
+fn strip_directives(
+  contents: &str,
+  spdx: &mut Option<String>,
+  pragmas: &mut Vec<String>,
+  seen_pragmas: &mut BTreeSet<String>,
+) -> String {
+  let mut output = String::with_capacity(contents.len());
+  let mut lines = contents.lines().peekable();
+
+  while let Some(line) = lines.next() {
+    let trimmed = line.trim_start();
+
+    if let Some(rest) = trimmed.strip_prefix("// SPDX-License-Identifier:") {
+      if spdx.is_none() {
+        *spdx = Some(rest.trim().to_string());
+      }
+      continue;
+    }
+
+    if trimmed.starts_with("pragma ") {
+      let pragma = trimmed.trim_end().to_string();
+      if seen_pragmas.insert(pragma.clone()) {
+        pragmas.push(pragma);
+      }
+      continue;
+    }
+
+    if is_import_start(trimmed) {
+      // `import` statements can span multiple lines (e.g. a multi-symbol named import); keep
+      // consuming lines until the terminating `;` shows up.
+      let mut joined = line.to_string();
+      while !joined.contains(';') {
+        match lines.next() {
+          Some(next) => {
+            joined.push('\n');
+            joined.push_str(next);
+          }
+          None => break,
+        }
+      }
+      continue;
+    }
+
+    output.push_str(line);
+    output.push('\n');
+  }
+
+  output
+}
+
+fn is_import_start(trimmed: &str) -> bool {
+  trimmed
+    .strip_prefix("import")
+    .map(|rest| {
+      rest.is_empty() || rest.starts_with(|c: char| c.is_whitespace() || matches!(c, '{' | '"' | '\''))
+    })
+    .unwrap_or(false)
+}
@@ -0,0 +1,320 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use foundry_compilers::artifacts::remappings::Remapping;
+use serde::{Deserialize, Serialize};
+
+use super::project_runner::{candidate_versions, parse_version_pragma};
+use crate::internal::config::CompilerConfig;
+use crate::internal::errors::Result;
+
+/// One file in the resolved import graph, with the highest solc version its own pragma allows
+/// that is no older than the version resolved for any file it imports, if one could be determined.
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphNode {
+  pub path: String,
+  #[napi(ts_type = "string | undefined")]
+  pub pragma: Option<String>,
+  #[napi(ts_type = "string | undefined")]
+  pub resolved_version: Option<String>,
+}
+
+/// A directed `path` imports `imports` edge in the graph.
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphEdge {
+  pub path: String,
+  pub imports: String,
+}
+
+/// An `import` statement in `path` that couldn't be resolved to a known source, either because it
+/// isn't a relative path and no configured remapping matches it, or because the
+/// resolved path doesn't correspond to any source in the graph.
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnresolvedImport {
+  pub path: String,
+  pub import: String,
+}
+
+/// A cycle in the import graph, as the ordered sequence of files that import one another back
+/// around to the first. Files inside a cycle have no `resolved_version` - there's no well-defined
+/// topological order to derive one from.
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportCycle {
+  pub files: Vec<String>,
+}
+
+/// Every non-cyclic file that resolved to the same `solcVersion`, the version `ProjectRunner`
+/// would actually invoke to compile them together.
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionBucket {
+  pub solc_version: String,
+  pub files: Vec<String>,
+}
+
+/// The resolved import DAG for a set of sources, returned by `Compiler::resolve_graph` before any
+/// compilation happens.
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceGraph {
+  pub nodes: Vec<GraphNode>,
+  pub edges: Vec<GraphEdge>,
+  pub unresolved_imports: Vec<UnresolvedImport>,
+  pub cycles: Vec<ImportCycle>,
+  pub version_buckets: Vec<VersionBucket>,
+}
+
+/// Scans `contents` for `import "..."` / `import {...} from "..."` / `import * as X from "..."`
+/// statements and returns the quoted import target of each one, in source order. This is a
+/// lightweight scan (no full Solidity parser), sufficient for graph resolution and diagnostics.
+pub(crate) fn extract_imports(contents: &str) -> Vec<String> {
+  let mut imports = Vec::new();
+  let mut rest = contents;
+  while let Some(start) = rest.find("import") {
+    let after_keyword = &rest[start + "import".len()..];
+    if let Some(quote_start) = after_keyword
+      .find(['"', '\''])
+      .filter(|&idx| after_keyword[..idx].find(';').is_none())
+    {
+      let quote_char = after_keyword.as_bytes()[quote_start] as char;
+      let quoted = &after_keyword[quote_start + 1..];
+      if let Some(quote_end) = quoted.find(quote_char) {
+        imports.push(quoted[..quote_end].to_string());
+        rest = &quoted[quote_end + 1..];
+        continue;
+      }
+    }
+    rest = after_keyword;
+  }
+  imports
+}
+
+/// Resolves an `import` target written in `importing_path` to a key in `sources`, trying a
+/// relative-path resolution first and falling back to the configured remappings. Returns `None`
+/// when no known source matches.
+pub(super) fn resolve_import(
+  importing_path: &str,
+  import: &str,
+  sources: &BTreeMap<String, String>,
+  remappings: &[Remapping],
+) -> Option<String> {
+  if import.starts_with('.') {
+    let base = Path::new(importing_path).parent().unwrap_or(Path::new(""));
+    let joined = normalise_path(&base.join(import));
+    if sources.contains_key(&joined) {
+      return Some(joined);
+    }
+    return sources
+      .keys()
+      .find(|candidate| normalise_path(Path::new(candidate)) == joined)
+      .cloned();
+  }
+
+  if sources.contains_key(import) {
+    return Some(import.to_string());
+  }
+
+  let mut best: Option<(&Remapping, &str)> = None;
+  for remapping in remappings {
+    if let Some(suffix) = import.strip_prefix(remapping.name.as_str()) {
+      if best.map(|(current, _)| remapping.name.len() > current.name.len()).unwrap_or(true) {
+        best = Some((remapping, suffix));
+      }
+    }
+  }
+  if let Some((remapping, suffix)) = best {
+    let candidate = normalise_path(&PathBuf::from(&remapping.path).join(suffix.trim_start_matches('/')));
+    if sources.contains_key(&candidate) {
+      return Some(candidate);
+    }
+    return sources
+      .keys()
+      .find(|key| normalise_path(Path::new(key)) == candidate)
+      .cloned();
+  }
+
+  None
+}
+
+/// Collapses `.`/`..` segments without touching the filesystem, so relative imports compare
+/// equal to the canonical keys already used throughout `sources`.
+fn normalise_path(path: &Path) -> String {
+  let mut stack: Vec<std::ffi::OsString> = Vec::new();
+  for component in path.components() {
+    match component {
+      std::path::Component::CurDir => {}
+      std::path::Component::ParentDir => {
+        stack.pop();
+      }
+      other => stack.push(other.as_os_str().to_os_string()),
+    }
+  }
+  PathBuf::from_iter(stack).to_string_lossy().replace('\\', "/")
+}
+
+/// Detects cycles in the `path -> imports` adjacency via DFS, returning each cycle as the chain of
+/// files from re-entering the stack back to itself.
+fn find_cycles(adjacency: &BTreeMap<String, Vec<String>>) -> Vec<Vec<String>> {
+  let mut cycles = Vec::new();
+  let mut visited: BTreeSet<String> = BTreeSet::new();
+  let mut stack: Vec<String> = Vec::new();
+
+  fn visit(
+    node: &str,
+    adjacency: &BTreeMap<String, Vec<String>>,
+    visited: &mut BTreeSet<String>,
+    stack: &mut Vec<String>,
+    cycles: &mut Vec<Vec<String>>,
+  ) {
+    if let Some(pos) = stack.iter().position(|entry| entry == node) {
+      cycles.push(stack[pos..].to_vec());
+      return;
+    }
+    if !visited.insert(node.to_string()) {
+      return;
+    }
+    stack.push(node.to_string());
+    if let Some(imports) = adjacency.get(node) {
+      for imported in imports {
+        visit(imported, adjacency, visited, stack, cycles);
+      }
+    }
+    stack.pop();
+  }
+
+  for node in adjacency.keys() {
+    visit(node, adjacency, &mut visited, &mut stack, &mut cycles);
+  }
+  cycles
+}
+
+/// Builds the resolved import graph for `sources`, grouping acyclic files into the solc version
+/// buckets `ProjectRunner::compile_multi_version` would actually compile them under: a file's
+/// bucket is always the same version or newer than every file it imports.
+pub fn resolve_graph(config: &CompilerConfig, sources: &BTreeMap<String, String>) -> Result<SourceGraph> {
+  let mut adjacency: BTreeMap<String, Vec<String>> = BTreeMap::new();
+  let mut edges = Vec::new();
+  let mut unresolved_imports = Vec::new();
+
+  for (path, contents) in sources {
+    let mut resolved_imports = Vec::new();
+    for import in extract_imports(contents) {
+      match resolve_import(path, &import, sources, &config.remappings) {
+        Some(target) => {
+          edges.push(GraphEdge {
+            path: path.clone(),
+            imports: target.clone(),
+          });
+          resolved_imports.push(target);
+        }
+        None => unresolved_imports.push(UnresolvedImport {
+          path: path.clone(),
+          import,
+        }),
+      }
+    }
+    adjacency.insert(path.clone(), resolved_imports);
+  }
+
+  let cycles = find_cycles(&adjacency);
+  let cyclic_files: BTreeSet<&str> = cycles
+    .iter()
+    .flat_map(|cycle| cycle.iter().map(String::as_str))
+    .collect();
+
+  let pool = candidate_versions(config.offline_mode)?;
+  let mut resolved_versions: BTreeMap<String, semver::Version> = BTreeMap::new();
+  let mut nodes = Vec::new();
+
+  for path in sources.keys() {
+    if cyclic_files.contains(path.as_str()) {
+      nodes.push(GraphNode {
+        path: path.clone(),
+        pragma: None,
+        resolved_version: None,
+      });
+    }
+  }
+
+  // Acyclic files only, in dependency-first (post-) order so a file's floor already reflects
+  // every import it depends on by the time it's resolved.
+  let mut order = Vec::new();
+  let mut emitted: BTreeSet<String> = BTreeSet::new();
+  fn post_order(
+    node: &str,
+    adjacency: &BTreeMap<String, Vec<String>>,
+    cyclic_files: &BTreeSet<&str>,
+    emitted: &mut BTreeSet<String>,
+    order: &mut Vec<String>,
+  ) {
+    if cyclic_files.contains(node) || !emitted.insert(node.to_string()) {
+      return;
+    }
+    if let Some(imports) = adjacency.get(node) {
+      for imported in imports {
+        post_order(imported, adjacency, cyclic_files, emitted, order);
+      }
+    }
+    order.push(node.to_string());
+  }
+  for path in sources.keys() {
+    post_order(path, &adjacency, &cyclic_files, &mut emitted, &mut order);
+  }
+
+  for path in order {
+    let contents = &sources[&path];
+    let pragma = parse_version_pragma(contents);
+    let floor = adjacency
+      .get(&path)
+      .into_iter()
+      .flatten()
+      .filter_map(|imported| resolved_versions.get(imported))
+      .max()
+      .cloned();
+
+    let candidates: Vec<semver::Version> = pool
+      .iter()
+      .filter(|version| pragma.as_ref().map(|req| req.matches(version)).unwrap_or(true))
+      .filter(|version| floor.as_ref().map(|floor| *version >= floor).unwrap_or(true))
+      .cloned()
+      .collect();
+
+    let resolved_version = candidates.into_iter().max();
+    if let Some(version) = resolved_version.clone() {
+      resolved_versions.insert(path.clone(), version);
+    }
+
+    nodes.push(GraphNode {
+      path: path.clone(),
+      pragma: pragma.map(|req| req.to_string()),
+      resolved_version: resolved_version.map(|version| version.to_string()),
+    });
+  }
+
+  let mut buckets: BTreeMap<String, Vec<String>> = BTreeMap::new();
+  for (path, version) in &resolved_versions {
+    buckets.entry(version.to_string()).or_default().push(path.clone());
+  }
+  let version_buckets = buckets
+    .into_iter()
+    .map(|(solc_version, files)| VersionBucket { solc_version, files })
+    .collect();
+
+  Ok(SourceGraph {
+    nodes,
+    edges,
+    unresolved_imports,
+    cycles: cycles.into_iter().map(|files| ImportCycle { files }).collect(),
+    version_buckets,
+  })
+}
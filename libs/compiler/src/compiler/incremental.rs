@@ -0,0 +1,495 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use foundry_compilers::artifacts::{
+  sources::Source as FoundrySource, ConfigurableContractArtifact, Settings,
+};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+
+use super::output::{CompilerError, SourceArtifacts};
+use crate::contract::Contract;
+use crate::internal::cache_key::keccak_hex;
+use crate::internal::errors::{map_err_with_context, Result};
+use crate::internal::resolver::Graph;
+
+/// Per-file incremental build cache for the `FilePaths` compile path, layered over
+/// `resolver::Graph`: a file is dirty if its content hash, mtime, solc version, or solc settings
+/// changed since the last recorded fingerprint, or if anything it (transitively) imports is dirty.
+/// This is deliberately a separate file from foundry-compilers' own `SOLIDITY_FILES_CACHE_FILENAME`
+/// - that file's format is foundry-compilers' to own, and `set_cached` in `build_project` already
+/// points the project at it - so this cache only ever narrows the file list handed to
+/// `compile_files`, the same file list `ProjectRunner::compile` would otherwise pass unfiltered.
+///
+/// Narrowing the file list means solc never re-sees a clean file, so `load_clean_artifacts`
+/// reloads its already-on-disk artifact and `output::merge_clean_artifacts` folds it back into the
+/// `CompileOutput` this run returns - callers still see every requested file's contracts, not just
+/// the ones that actually needed recompiling.
+const INCREMENTAL_CACHE_FILENAME: &str = "incremental-files-cache.json";
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+struct FileFingerprint {
+  content_hash: String,
+  mtime_secs: u64,
+  solc_version: String,
+  settings_hash: String,
+  /// Diagnostics attached to this file the last time it was actually recompiled - see
+  /// `record_errors`/`load_clean_errors`. Defaulted so an index written before this field existed
+  /// still deserializes, just with nothing to restore for a clean run until the next recompile.
+  #[serde(default)]
+  errors: Vec<CompilerError>,
+}
+
+fn index_path(cache_dir: &Path) -> PathBuf {
+  cache_dir.join(INCREMENTAL_CACHE_FILENAME)
+}
+
+fn read_index(cache_dir: &Path) -> BTreeMap<PathBuf, FileFingerprint> {
+  fs::read_to_string(index_path(cache_dir))
+    .ok()
+    .and_then(|contents| serde_json::from_str(&contents).ok())
+    .unwrap_or_default()
+}
+
+fn write_index(cache_dir: &Path, index: &BTreeMap<PathBuf, FileFingerprint>) -> Result<()> {
+  let path = index_path(cache_dir);
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent).ok();
+  }
+  let serialised = map_err_with_context(
+    serde_json::to_string(index),
+    "Failed to serialise incremental build cache",
+  )?;
+  fs::write(&path, serialised).ok();
+  Ok(())
+}
+
+fn hash_settings(settings: &Settings) -> String {
+  keccak_hex(serde_json::to_string(settings).unwrap_or_default().as_bytes())
+}
+
+fn fingerprint_of(file: &Path, solc_version: &str, settings_hash: &str) -> Option<FileFingerprint> {
+  let contents = fs::read_to_string(file).ok()?;
+  let mtime_secs = fs::metadata(file)
+    .and_then(|meta| meta.modified())
+    .ok()
+    .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+    .map(|duration| duration.as_secs())
+    .unwrap_or_default();
+
+  Some(FileFingerprint {
+    content_hash: FoundrySource::content_hash_of(&contents),
+    mtime_secs,
+    solc_version: solc_version.to_string(),
+    settings_hash: settings_hash.to_string(),
+    errors: Vec::new(),
+  })
+}
+
+impl FileFingerprint {
+  /// Whether `self` and `other` describe the same input - everything but `errors`, which is
+  /// populated after the fact by `record_errors` and would otherwise make every file look dirty
+  /// the moment it recorded a single diagnostic.
+  fn same_input(&self, other: &FileFingerprint) -> bool {
+    self.content_hash == other.content_hash
+      && self.mtime_secs == other.mtime_secs
+      && self.solc_version == other.solc_version
+      && self.settings_hash == other.settings_hash
+  }
+}
+
+/// Whether `artifacts_dir` already holds an artifact for `file` (relative to `root`), using the
+/// same `<source path>/<ContractName>.json` layout Foundry's own project writer produces.
+fn has_artifact(artifacts_dir: &Path, root: &Path, file: &Path) -> bool {
+  let relative = file.strip_prefix(root).unwrap_or(file);
+  let dir = artifacts_dir.join(relative);
+  fs::read_dir(&dir)
+    .map(|mut entries| entries.next().is_some())
+    .unwrap_or(false)
+}
+
+/// Narrows `files` down to the ones that need recompiling: anything whose fingerprint changed,
+/// anything that (transitively, via `closure`'s import edges) depends on a changed file, and
+/// anything missing its artifact under `artifacts_dir`. Rewrites the cache under `cache_dir` with
+/// every reachable file's current fingerprint before returning, so the next call compares against
+/// this run's state rather than the one before it.
+pub fn filter_dirty(
+  cache_dir: &Path,
+  root: &Path,
+  artifacts_dir: &Path,
+  files: &[PathBuf],
+  closure: &Graph,
+  solc_version: &Version,
+  settings: &Settings,
+) -> Result<Vec<PathBuf>> {
+  let stored = read_index(cache_dir);
+  let solc_version = solc_version.to_string();
+  let settings_hash = hash_settings(settings);
+
+  let reachable = closure.reachable_files();
+  let mut fingerprints: BTreeMap<PathBuf, FileFingerprint> = BTreeMap::new();
+  let mut changed: Vec<PathBuf> = Vec::new();
+
+  for file in &reachable {
+    match fingerprint_of(file, &solc_version, &settings_hash) {
+      Some(mut fingerprint) => {
+        let previous = stored.get(file);
+        match previous {
+          Some(previous) if previous.same_input(&fingerprint) => {
+            // Unchanged - carry its last-recorded diagnostics forward so a run that doesn't touch
+            // this file at all doesn't lose them; `record_errors` overwrites this for anything
+            // that actually gets recompiled below.
+            fingerprint.errors = previous.errors.clone();
+          }
+          _ => changed.push(file.clone()),
+        }
+        fingerprints.insert(file.clone(), fingerprint);
+      }
+      // Unreadable right now (removed, permissions, ...) - always treat as needing recompilation
+      // rather than silently reusing a stale fingerprint.
+      None => changed.push(file.clone()),
+    }
+  }
+
+  let mut dependents: BTreeMap<&PathBuf, Vec<&PathBuf>> = BTreeMap::new();
+  for (importer, imported) in closure.edges() {
+    dependents.entry(imported).or_default().push(importer);
+  }
+
+  let mut dirty: BTreeSet<PathBuf> = changed.iter().cloned().collect();
+  let mut stack = changed;
+  while let Some(file) = stack.pop() {
+    if let Some(importers) = dependents.get(&file) {
+      for importer in importers {
+        if dirty.insert((*importer).clone()) {
+          stack.push((*importer).clone());
+        }
+      }
+    }
+  }
+
+  write_index(cache_dir, &fingerprints)?;
+
+  let mut result: Vec<PathBuf> = files
+    .iter()
+    .filter(|file| {
+      dirty.contains(file.as_path()) || !has_artifact(artifacts_dir, root, file.as_path())
+    })
+    .cloned()
+    .collect();
+  result.sort();
+  result.dedup();
+  Ok(result)
+}
+
+/// Reconstructs `SourceArtifacts` for files `filter_dirty` left out of the dirty set, by reading
+/// back the `{ContractName}.json` artifacts Foundry's own project writer already left under
+/// `artifacts_dir` for them. Without this, a compile that only recompiled the dirty subset would
+/// silently drop every clean file from the returned `CompileOutput::artifacts`, even though its
+/// artifact is sitting untouched on disk - see `output::merge_clean_artifacts`, the caller this
+/// feeds. A file whose directory is missing, unreadable, or written in a non-Foundry artifact
+/// shape (e.g. `config.artifact_format` is `Hardhat`, which writes a different JSON shape under
+/// the same file name) is simply left out rather than guessed at.
+pub fn load_clean_artifacts(
+  artifacts_dir: &Path,
+  root: &Path,
+  files: &[PathBuf],
+) -> BTreeMap<String, SourceArtifacts> {
+  let mut result = BTreeMap::new();
+
+  for file in files {
+    let relative = file.strip_prefix(root).unwrap_or(file);
+    let dir = artifacts_dir.join(relative);
+    let Ok(entries) = fs::read_dir(&dir) else {
+      continue;
+    };
+
+    let key = relative.to_string_lossy().to_string();
+    let mut entry = SourceArtifacts {
+      source_path: Some(key.clone()),
+      ..Default::default()
+    };
+
+    for dir_entry in entries.flatten() {
+      let path = dir_entry.path();
+      if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+        continue;
+      }
+      let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+        continue;
+      };
+      let Ok(contents) = fs::read_to_string(&path) else {
+        continue;
+      };
+      let Ok(artifact) = serde_json::from_str::<ConfigurableContractArtifact>(&contents) else {
+        continue;
+      };
+
+      let mut contract = Contract::from_configurable_artifact(name, &artifact);
+      contract.state_mut().source_path = Some(key.clone());
+      if entry.source_id.is_none() {
+        entry.source_id = contract.state().source_id;
+      }
+      entry.contracts.insert(name.to_string(), contract);
+    }
+
+    if !entry.contracts.is_empty() {
+      result.insert(key, entry);
+    }
+  }
+
+  result
+}
+
+/// Persists the diagnostics a fresh compile attached to each of `files` - the ones it actually
+/// recompiled - into their fingerprint entries, matched by resolving each error's
+/// `source_location.file` relative to `root` the same way `filter_dirty`'s callers resolve
+/// `files` themselves. A file in `files` with no matching error still gets its entry cleared to
+/// an empty list, so a diagnostic that no longer reproduces doesn't linger forever in
+/// `load_clean_errors`. Errors with no `source_location` (can't be attributed to one file) are not
+/// persisted here at all; they're only ever reported for the run that actually produced them.
+pub fn record_errors(
+  cache_dir: &Path,
+  root: &Path,
+  files: &[PathBuf],
+  errors: &[CompilerError],
+) -> Result<()> {
+  if files.is_empty() {
+    return Ok(());
+  }
+
+  let mut grouped: BTreeMap<PathBuf, Vec<CompilerError>> =
+    files.iter().map(|file| (file.clone(), Vec::new())).collect();
+  let by_relative: BTreeMap<String, &PathBuf> = files
+    .iter()
+    .map(|file| {
+      let relative = file.strip_prefix(root).unwrap_or(file);
+      (relative.to_string_lossy().to_string(), file)
+    })
+    .collect();
+
+  for error in errors {
+    let Some(location) = &error.source_location else {
+      continue;
+    };
+    if let Some(file) = by_relative.get(location.file.as_str()) {
+      grouped.entry((*file).clone()).or_default().push(error.clone());
+    }
+  }
+
+  let mut index = read_index(cache_dir);
+  for (file, errors) in grouped {
+    if let Some(fingerprint) = index.get_mut(&file) {
+      fingerprint.errors = errors;
+    }
+  }
+  write_index(cache_dir, &index)
+}
+
+/// Diagnostics `record_errors` previously attached to the subset of `files` that are still clean
+/// this run, so skipping their recompilation doesn't silently drop a warning/error that still
+/// applies - `CompileOutput::merge`-style accumulation otherwise only sees diagnostics from
+/// whatever actually got recompiled.
+pub fn load_clean_errors(cache_dir: &Path, files: &[PathBuf]) -> Vec<CompilerError> {
+  let index = read_index(cache_dir);
+  files
+    .iter()
+    .filter_map(|file| index.get(file))
+    .flat_map(|fingerprint| fingerprint.errors.clone())
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::output::{SeverityLevel, SourceLocation};
+  use std::collections::BTreeSet as StdBTreeSet;
+  use tempfile::tempdir;
+
+  fn write(dir: &Path, relative: &str, contents: &str) -> PathBuf {
+    let path = dir.join(relative);
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent).expect("create parent");
+    }
+    fs::write(&path, contents).expect("write file");
+    path
+  }
+
+  fn version() -> Version {
+    Version::new(0, 8, 19)
+  }
+
+  #[test]
+  fn an_unchanged_file_with_an_artifact_is_skipped() {
+    let temp = tempdir().expect("tempdir");
+    let root = temp.path();
+    let cache_dir = root.join("cache");
+    let artifacts_dir = root.join("out");
+    let a = write(root, "A.sol", "contract A {}");
+
+    fs::create_dir_all(artifacts_dir.join("A.sol")).expect("mkdir");
+    fs::write(artifacts_dir.join("A.sol/A.json"), "{}").expect("write artifact");
+
+    let closure = Graph::build(&[a.clone()], &[], &StdBTreeSet::new(), &[]);
+    let settings = Settings::default();
+
+    // First pass has no stored fingerprint yet, so the file is dirty once.
+    let first = filter_dirty(
+      &cache_dir, root, &artifacts_dir, &[a.clone()], &closure, &version(), &settings,
+    )
+    .expect("filter");
+    assert_eq!(first, vec![a.clone()]);
+
+    // Second pass sees the fingerprint just written and the artifact on disk, so it's clean.
+    let second = filter_dirty(
+      &cache_dir, root, &artifacts_dir, &[a.clone()], &closure, &version(), &settings,
+    )
+    .expect("filter");
+    assert!(second.is_empty());
+  }
+
+  #[test]
+  fn a_changed_dependency_marks_its_importer_dirty() {
+    let temp = tempdir().expect("tempdir");
+    let root = temp.path();
+    let cache_dir = root.join("cache");
+    let artifacts_dir = root.join("out");
+    let a = write(root, "A.sol", "import \"./B.sol\";\ncontract A {}");
+    let b = write(root, "B.sol", "contract B {}");
+
+    for file in ["A.sol", "B.sol"] {
+      fs::create_dir_all(artifacts_dir.join(file)).expect("mkdir");
+      fs::write(artifacts_dir.join(file).join("X.json"), "{}").expect("write artifact");
+    }
+
+    let closure = Graph::build(&[a.clone()], &[], &StdBTreeSet::new(), &[]);
+    let settings = Settings::default();
+    let inputs = [a.clone(), b.clone()];
+    filter_dirty(&cache_dir, root, &artifacts_dir, &inputs, &closure, &version(), &settings)
+      .expect("prime cache");
+
+    fs::write(&b, "contract B { uint256 x; }").expect("modify B");
+    let dirty =
+      filter_dirty(&cache_dir, root, &artifacts_dir, &inputs, &closure, &version(), &settings)
+        .expect("filter");
+    assert!(dirty.contains(&a));
+    assert!(dirty.contains(&b));
+  }
+
+  #[test]
+  fn a_missing_artifact_forces_recompilation_even_when_unchanged() {
+    let temp = tempdir().expect("tempdir");
+    let root = temp.path();
+    let cache_dir = root.join("cache");
+    let artifacts_dir = root.join("out");
+    let a = write(root, "A.sol", "contract A {}");
+
+    let closure = Graph::build(&[a.clone()], &[], &StdBTreeSet::new(), &[]);
+    let settings = Settings::default();
+    filter_dirty(&cache_dir, root, &artifacts_dir, &[a.clone()], &closure, &version(), &settings)
+      .expect("prime cache");
+
+    // No artifact was ever written, so the file stays in the dirty set despite an unchanged
+    // fingerprint.
+    let dirty = filter_dirty(
+      &cache_dir, root, &artifacts_dir, &[a.clone()], &closure, &version(), &settings,
+    )
+    .expect("filter");
+    assert_eq!(dirty, vec![a]);
+  }
+
+  #[test]
+  fn a_file_with_no_artifact_directory_is_left_out_of_the_reload() {
+    let temp = tempdir().expect("tempdir");
+    let root = temp.path();
+    let artifacts_dir = root.join("out");
+    let a = write(root, "A.sol", "contract A {}");
+
+    let reloaded = load_clean_artifacts(&artifacts_dir, root, &[a]);
+
+    assert!(reloaded.is_empty());
+  }
+
+  #[test]
+  fn an_artifact_file_that_does_not_parse_is_skipped_rather_than_erroring() {
+    let temp = tempdir().expect("tempdir");
+    let root = temp.path();
+    let artifacts_dir = root.join("out");
+    let a = write(root, "A.sol", "contract A {}");
+
+    fs::create_dir_all(artifacts_dir.join("A.sol")).expect("mkdir");
+    fs::write(artifacts_dir.join("A.sol/A.json"), "{}").expect("write unparsable artifact");
+
+    let reloaded = load_clean_artifacts(&artifacts_dir, root, &[a]);
+
+    assert!(reloaded.is_empty());
+  }
+
+  fn warning_on(file: &str) -> CompilerError {
+    CompilerError {
+      message: "unused variable".into(),
+      formatted_message: None,
+      component: "general".into(),
+      severity: SeverityLevel::Warning,
+      error_type: "Warning".into(),
+      error_code: Some(2072),
+      source_location: Some(SourceLocation {
+        file: file.to_string(),
+        start: 0,
+        end: 1,
+      }),
+      secondary_source_locations: None,
+      vyper_source_location: None,
+    }
+  }
+
+  #[test]
+  fn record_errors_round_trips_through_load_clean_errors() {
+    let temp = tempdir().expect("tempdir");
+    let root = temp.path();
+    let cache_dir = root.join("cache");
+    let artifacts_dir = root.join("out");
+    let a = write(root, "A.sol", "contract A {}");
+
+    let closure = Graph::build(&[a.clone()], &[], &StdBTreeSet::new(), &[]);
+    let settings = Settings::default();
+    filter_dirty(&cache_dir, root, &artifacts_dir, &[a.clone()], &closure, &version(), &settings)
+      .expect("prime cache");
+
+    record_errors(&cache_dir, root, &[a.clone()], &[warning_on("A.sol")]).expect("record errors");
+
+    // A.sol is unchanged and skipped on the next pass, but its previously-recorded warning should
+    // still surface rather than silently disappear.
+    let dirty = filter_dirty(
+      &cache_dir, root, &artifacts_dir, &[a.clone()], &closure, &version(), &settings,
+    )
+    .expect("filter");
+    assert!(dirty.is_empty());
+
+    let restored = load_clean_errors(&cache_dir, &[a]);
+    assert_eq!(restored.len(), 1);
+    assert_eq!(restored[0].message, "unused variable");
+  }
+
+  #[test]
+  fn recompiling_a_file_clears_a_stale_recorded_error() {
+    let temp = tempdir().expect("tempdir");
+    let root = temp.path();
+    let cache_dir = root.join("cache");
+    let artifacts_dir = root.join("out");
+    let a = write(root, "A.sol", "contract A {}");
+
+    let closure = Graph::build(&[a.clone()], &[], &StdBTreeSet::new(), &[]);
+    let settings = Settings::default();
+    filter_dirty(&cache_dir, root, &artifacts_dir, &[a.clone()], &closure, &version(), &settings)
+      .expect("prime cache");
+    record_errors(&cache_dir, root, &[a.clone()], &[warning_on("A.sol")]).expect("record errors");
+
+    // Recompiling the file with no errors this time should clear the stale warning, not leave it
+    // stuck forever.
+    record_errors(&cache_dir, root, &[a.clone()], &[]).expect("clear errors");
+
+    assert!(load_clean_errors(&cache_dir, &[a]).is_empty());
+  }
+}
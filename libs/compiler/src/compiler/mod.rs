@@ -1,9 +1,10 @@
 use std::collections::BTreeMap;
+use std::fs;
 use std::path::{Path, PathBuf};
 
 use foundry_compilers::artifacts::ast::SourceUnit;
 use napi::bindgen_prelude::*;
-use napi::{Env, JsObject, JsUnknown};
+use napi::{Env, JsObject, JsUnknown, Task};
 use serde_json::Value;
 
 use crate::ast::utils::from_js_value;
@@ -16,16 +17,28 @@ use crate::internal::project::{default_cache_dir, synthetic_project_paths, Proje
 use crate::internal::solc;
 pub use core::{
   compile_contract, compile_files, compile_project, compile_source, compile_sources, init,
-  init_from_foundry_root, init_from_hardhat_root, init_from_root, resolve_config, SourceTarget,
-  SourceValue, State,
+  init_from_detected_root, init_from_foundry_root, init_from_hardhat_root, init_from_root,
+  resolve_config, SourceTarget, SourceValue, State,
 };
+use graph::SourceGraph;
 pub use input::CompilationInput;
 use output::{into_js_compile_output, CompileOutput, JsCompileOutput};
+pub use verify::{
+  verify_diagnostics, DiagnosticExpectation, DiagnosticVerification, UnexpectedDiagnostic,
+};
 
+mod artifact_output;
+mod cache;
 pub mod core;
+mod diagnostics;
+mod flatten;
+pub mod graph;
+mod incremental;
 mod input;
 pub mod output;
 mod project_runner;
+pub(crate) mod sourcemap;
+mod verify;
 
 #[cfg(test)]
 mod compiler_tests;
@@ -81,11 +94,25 @@ impl Compiler {
     Ok(Self { state })
   }
 
+  /// Instantiate a compiler scoped to an arbitrary project root whose ecosystem isn't known ahead
+  /// of time - `foundry.toml`, a hardhat config or `build-info` directory, and a dapp(1)-style
+  /// `src/`+`lib/` pair are each probed for in turn, falling back to the same synthetic workspace
+  /// `new`/`from_root` use when none of them match.
+  pub fn from_detected_root<P: AsRef<Path>>(
+    root: P,
+    options: Option<CompilerConfigOptions>,
+  ) -> Result<Self> {
+    let config = CompilerConfig::from_options(options).map_err(Error::from)?;
+    let state = init_from_detected_root(config, root.as_ref())?;
+    Ok(Self { state })
+  }
+
   /// Parse the supplied semantic version and ensure the matching `solc` binary is present on disk.
-  /// The download is skipped when the version already exists.
-  pub fn install_solc_version(version: &str) -> Result<()> {
+  /// The download is skipped when the version already exists. When `offline` is set this never
+  /// reaches out to the network: it fails fast with an error naming the missing version instead.
+  pub fn install_solc_version(version: &str, offline: bool) -> Result<()> {
     let parsed = solc::parse_version(version)?;
-    solc::install_version(&parsed)
+    solc::install_version(&parsed, offline)
   }
 
   /// Return whether the requested `solc` version is already available locally.
@@ -94,6 +121,22 @@ impl Compiler {
     solc::is_version_installed(&parsed)
   }
 
+  /// Parse and install whichever of `versions` aren't already present, in parallel up to a
+  /// bounded worker pool, reporting what happened to each one. Useful for preparing a workspace
+  /// where different contracts pin different compilers up front, instead of installing each
+  /// version in sequence. When `offline` is set this never reaches out to the network: any
+  /// missing version fails fast instead of being downloaded.
+  pub fn install_solc_versions(
+    versions: &[String],
+    offline: bool,
+  ) -> Result<Vec<solc::InstallOutcome>> {
+    let parsed = versions
+      .iter()
+      .map(|version| solc::parse_version(version))
+      .collect::<Result<Vec<_>>>()?;
+    solc::install_many(&parsed, offline)
+  }
+
   /// Compile a single inline source string or Solidity AST using the compiler's current
   /// configuration merged with any per-call overrides. Returns a `CompileOutput` that mirrors the
   /// TypeScript `CompileOutput<THasErrors, undefined>` shape. Passing an empty string results in a
@@ -154,6 +197,42 @@ impl Compiler {
     compile_contract(&self.state, &config, contract_name)
   }
 
+  /// Resolve the import dependency graph and per-file solc version buckets without compiling
+  /// anything. When `sources` is omitted, every `.sol` file under the attached project's source
+  /// directory is scanned; pass an explicit map to resolve an in-memory set instead.
+  pub fn resolve_graph(
+    &self,
+    sources: Option<BTreeMap<String, String>>,
+    options: Option<CompilerConfigOptions>,
+  ) -> Result<SourceGraph> {
+    let config = self.resolve_call_config(options.as_ref())?;
+    let sources = match sources {
+      Some(sources) => sources,
+      None => gather_project_sources(self.state.project.as_ref())?,
+    };
+    graph::resolve_graph(&config, &sources)
+  }
+
+  /// Produce a single self-contained Solidity source for `entry` by inlining every file it
+  /// transitively imports, resolved through the same remappings/include-path machinery as
+  /// `resolve_graph`, with duplicate `pragma`/SPDX directives merged into one header. When
+  /// `sources` is omitted, every `.sol` file under the attached project's source directory is
+  /// scanned, same as `resolve_graph`. Feed the result into `JsAst::from_source` to parse or
+  /// instrument the whole dependency tree as one unit.
+  pub fn flatten(
+    &self,
+    entry: &str,
+    sources: Option<BTreeMap<String, String>>,
+    options: Option<CompilerConfigOptions>,
+  ) -> Result<String> {
+    let config = self.resolve_call_config(options.as_ref())?;
+    let sources = match sources {
+      Some(sources) => sources,
+      None => gather_project_sources(self.state.project.as_ref())?,
+    };
+    flatten::flatten_source(&config, &sources, entry)
+  }
+
   /// Access the resolved compiler configuration backing this instance.
   pub fn config(&self) -> &CompilerConfig {
     &self.state.config
@@ -185,6 +264,14 @@ impl Compiler {
     self.state
   }
 
+  /// Discard the on-disk compile cache used by the synthetic (non-project) compile paths
+  /// (`compile_source`/`compile_sources`), so the next call recompiles instead of serving a
+  /// previously cached result. A project compiler's own `solidity-files-cache.json` (used by
+  /// `compile_project`/`compile_contract`) is managed by foundry-compilers and unaffected by this.
+  pub fn clear_cache() -> Result<()> {
+    cache::clear(&default_cache_dir())
+  }
+
   fn resolve_call_config(
     &self,
     overrides: Option<&CompilerConfigOptions>,
@@ -195,13 +282,49 @@ impl Compiler {
 
 fn resolve_project_paths(state: &State) -> Result<ProjectPaths> {
   if let Some(context) = &state.project {
-    return Ok(context.project_paths());
+    return Ok(ProjectPaths::from_config(&context.paths));
   }
 
   let base_dir = default_cache_dir();
   synthetic_project_paths(base_dir.as_path())
 }
 
+/// Recursively collects every `.sol` file under the attached project's source directory, keyed by
+/// its filesystem path. Returns an empty map when no project is attached rather than erroring, so
+/// `resolve_graph` still behaves sensibly for a bare synthetic compiler instance.
+fn gather_project_sources(project: Option<&ProjectContext>) -> Result<BTreeMap<String, String>> {
+  let Some(context) = project else {
+    return Ok(BTreeMap::new());
+  };
+
+  let mut sources = BTreeMap::new();
+  collect_sol_files(&context.paths.sources, &mut sources)?;
+  Ok(sources)
+}
+
+fn collect_sol_files(dir: &Path, sources: &mut BTreeMap<String, String>) -> Result<()> {
+  let Ok(entries) = fs::read_dir(dir) else {
+    return Ok(());
+  };
+
+  for entry in entries.flatten() {
+    let path = entry.path();
+    if path.is_dir() {
+      collect_sol_files(&path, sources)?;
+      continue;
+    }
+    if path.extension().and_then(|ext| ext.to_str()) != Some("sol") {
+      continue;
+    }
+
+    let contents = fs::read_to_string(&path)
+      .map_err(|err| Error::new(format!("Failed to read {}: {err}", path.display())))?;
+    sources.insert(path.to_string_lossy().replace('\\', "/"), contents);
+  }
+
+  Ok(())
+}
+
 #[napi(js_name = "Compiler")]
 #[derive(Clone)]
 pub struct JsCompiler {
@@ -219,10 +342,15 @@ impl JsCompiler {
 impl JsCompiler {
   /// Download and install a `solc` binary that matches the requested semantic
   /// version. The promise resolves once the binary has been persisted locally.
-  #[napi]
-  pub fn install_solc_version(version: String) -> napi::Result<AsyncTask<solc::InstallSolcTask>> {
+  /// Pass `offline: true` to fail fast instead of reaching out to the network
+  /// when the version isn't already installed.
+  #[napi(ts_args_type = "version: string, offline?: boolean | undefined")]
+  pub fn install_solc_version(
+    version: String,
+    offline: Option<bool>,
+  ) -> napi::Result<AsyncTask<solc::InstallSolcTask>> {
     let parsed = to_napi_result(solc::parse_version(&version))?;
-    Ok(solc::install_async(parsed))
+    Ok(solc::install_async(parsed, offline.unwrap_or(false)))
   }
 
   /// Check whether a `solc` binary for the provided version is already available.
@@ -232,6 +360,23 @@ impl JsCompiler {
     to_napi_result(solc::is_version_installed(&parsed))
   }
 
+  /// Download and install whichever of `versions` aren't already available, in parallel up to a
+  /// bounded worker pool. The promise resolves with the versions that were newly installed versus
+  /// those that were already present, so a caller preparing a multi-version workspace can report
+  /// progress without checking each version itself. Pass `offline: true` to fail fast instead of
+  /// reaching out to the network for any version that isn't already installed.
+  #[napi(ts_args_type = "versions: string[], offline?: boolean | undefined")]
+  pub fn install_solc_versions(
+    versions: Vec<String>,
+    offline: Option<bool>,
+  ) -> napi::Result<AsyncTask<solc::InstallSolcVersionsTask>> {
+    let parsed = versions
+      .iter()
+      .map(|version| to_napi_result(solc::parse_version(version)))
+      .collect::<napi::Result<Vec<_>>>()?;
+    Ok(solc::install_many_async(parsed, offline.unwrap_or(false)))
+  }
+
   /// Create a compiler that automatically discovers nearby project configuration.
   /// Pass `CompilerConfigOptions` to override defaults such as the solc version or
   /// remappings used for inline compilation.
@@ -331,7 +476,11 @@ impl JsCompiler {
     let config = self.resolve_call_config(overrides.as_ref())?;
     let target = parse_source_target(&env, target)?;
     let output = to_napi_result(compile_source(&self.inner.state, &config, target))?;
-    Ok(into_js_compile_output(output))
+    Ok(into_js_compile_output(
+      output,
+      config.deny_warnings,
+      &config.suppressed_warning_codes,
+    ))
   }
 
   /// Compile a keyed map of sources or AST entries. Entries must share a language
@@ -355,7 +504,11 @@ impl JsCompiler {
     let config = self.resolve_call_config(overrides.as_ref())?;
     let map = Self::parse_sources_object(&env, sources)?;
     let output = to_napi_result(compile_sources(&self.inner.state, &config, map))?;
-    Ok(into_js_compile_output(output))
+    Ok(into_js_compile_output(
+      output,
+      config.deny_warnings,
+      &config.suppressed_warning_codes,
+    ))
   }
 
   /// Compile concrete files on disk. Language is inferred from extensions unless the
@@ -383,7 +536,11 @@ impl JsCompiler {
     let language_override = language_override(overrides.as_ref());
     let path_bufs = paths.into_iter().map(PathBuf::from).collect();
     let output = to_napi_result(compile_files(&config, path_bufs, language_override))?;
-    Ok(into_js_compile_output(output))
+    Ok(into_js_compile_output(
+      output,
+      config.deny_warnings,
+      &config.suppressed_warning_codes,
+    ))
   }
 
   /// Compile the project associated with this compiler instance, returning a snapshot
@@ -404,7 +561,11 @@ impl JsCompiler {
       .transpose()?;
     let config = self.resolve_call_config(overrides.as_ref())?;
     let output = to_napi_result(compile_project(&self.inner.state, &config))?;
-    Ok(into_js_compile_output(output))
+    Ok(into_js_compile_output(
+      output,
+      config.deny_warnings,
+      &config.suppressed_warning_codes,
+    ))
   }
 
   /// Compile a single contract from the attached project by its canonical name.
@@ -425,7 +586,60 @@ impl JsCompiler {
       .transpose()?;
     let config = self.resolve_call_config(overrides.as_ref())?;
     let output = to_napi_result(compile_contract(&self.inner.state, &config, &contract_name))?;
-    Ok(into_js_compile_output(output))
+    Ok(into_js_compile_output(
+      output,
+      config.deny_warnings,
+      &config.suppressed_warning_codes,
+    ))
+  }
+
+  /// Resolve the import dependency graph and per-file solc version buckets without
+  /// compiling anything. Pass a keyed map of path to source text to resolve an
+  /// in-memory set; omit `sources` to scan the attached project's source directory.
+  #[napi(
+    ts_args_type = "sources?: Record<string, string> | undefined, options?: CompilerConfigOptions | undefined"
+  )]
+  pub fn resolve_graph(
+    &self,
+    env: Env,
+    sources: Option<JsObject>,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<SourceGraph> {
+    let parsed = parse_js_compiler_config(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| CompilerConfigOptions::try_from(opts))
+      .transpose()?;
+    let sources = sources
+      .map(|sources| from_js_value(&env, sources.into_unknown()))
+      .transpose()?;
+    to_napi_result(self.inner.resolve_graph(sources, overrides))
+  }
+
+  /// Inline `entry`'s transitive imports into a single self-contained Solidity source, merging
+  /// duplicate `pragma`/SPDX directives into one header. Pass a keyed map of path to source text
+  /// to flatten an in-memory set; omit `sources` to scan the attached project's source directory.
+  /// Useful for verification workflows and for feeding a whole dependency tree into
+  /// `JsAst.fromSource` as one unit.
+  #[napi(
+    ts_args_type = "entry: string, sources?: Record<string, string> | undefined, options?: CompilerConfigOptions | undefined"
+  )]
+  pub fn flatten(
+    &self,
+    env: Env,
+    entry: String,
+    sources: Option<JsObject>,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<String> {
+    let parsed = parse_js_compiler_config(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| CompilerConfigOptions::try_from(opts))
+      .transpose()?;
+    let sources = sources
+      .map(|sources| from_js_value(&env, sources.into_unknown()))
+      .transpose()?;
+    to_napi_result(self.inner.flatten(&entry, sources, overrides))
   }
 
   /// Return the canonicalised project paths used for artifacts, cache directories,
@@ -434,6 +648,169 @@ impl JsCompiler {
   pub fn get_paths(&self) -> napi::Result<ProjectPaths> {
     to_napi_result(self.inner.get_paths())
   }
+
+  /// Override key paths from the most recently applied `CompilerConfigOptions.solcSettings` that
+  /// weren't recognized against the settings schema - e.g. a misspelled `optmizer` - so callers
+  /// can warn about unsupported options instead of failing opaquely at solc invocation time.
+  #[napi]
+  pub fn ignored_settings_keys(&self) -> Vec<String> {
+    self.inner.config().ignored_settings_keys.clone()
+  }
+
+  /// Discard the on-disk compile cache shared by every synthetic (non-project) compiler instance,
+  /// so the next `compileSource`/`compileSources` call recompiles from scratch.
+  #[napi]
+  pub fn clear_cache() -> napi::Result<()> {
+    to_napi_result(Compiler::clear_cache())
+  }
+
+  /// Async variant of `compileSource` that runs the solc invocation on a worker thread instead of
+  /// blocking the event loop.
+  #[napi(
+    ts_args_type = "target: string | object, options?: CompilerConfigOptions | undefined",
+    ts_return_type = "Promise<CompileOutput<true, undefined> | CompileOutput<false, undefined>>"
+  )]
+  pub fn compile_source_async(
+    &self,
+    env: Env,
+    target: Either<String, JsObject>,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<AsyncTask<CompileTask>> {
+    let parsed = parse_js_compiler_config(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| CompilerConfigOptions::try_from(opts))
+      .transpose()?;
+    let config = self.resolve_call_config(overrides.as_ref())?;
+    let target = parse_source_target(&env, target)?;
+    Ok(AsyncTask::new(CompileTask {
+      request: Some(CompileRequest::Source {
+        state: self.inner.state.clone(),
+        target,
+      }),
+      deny_warnings: config.deny_warnings,
+      config,
+    }))
+  }
+
+  /// Async variant of `compileSources` that runs the solc invocation on a worker thread instead of
+  /// blocking the event loop.
+  #[napi(
+    ts_generic_types = "TSources extends Record<string, string | object> = Record<string, string | object>",
+    ts_args_type = "sources: TSources, options?: CompilerConfigOptions | undefined",
+    ts_return_type = "Promise<CompileOutput<true, Extract<keyof TSources, string>[]> | CompileOutput<false, Extract<keyof TSources, string>[]>>"
+  )]
+  pub fn compile_sources_async(
+    &self,
+    env: Env,
+    sources: JsObject,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<AsyncTask<CompileTask>> {
+    let parsed = parse_js_compiler_config(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| CompilerConfigOptions::try_from(opts))
+      .transpose()?;
+    let config = self.resolve_call_config(overrides.as_ref())?;
+    let sources = Self::parse_sources_object(&env, sources)?;
+    Ok(AsyncTask::new(CompileTask {
+      request: Some(CompileRequest::Sources {
+        state: self.inner.state.clone(),
+        sources,
+      }),
+      deny_warnings: config.deny_warnings,
+      config,
+    }))
+  }
+
+  /// Async variant of `compileFiles` that runs the solc invocation - including, across a
+  /// mixed-pragma set of files, its per-version-group parallel scheduling - on a worker thread
+  /// instead of blocking the event loop.
+  #[napi(
+    ts_generic_types = "TFilePaths extends readonly string[] = readonly string[]",
+    ts_args_type = "paths: TFilePaths, options?: CompilerConfigOptions | undefined",
+    ts_return_type = "Promise<CompileOutput<true, TFilePaths> | CompileOutput<false, TFilePaths>>"
+  )]
+  pub fn compile_files_async(
+    &self,
+    env: Env,
+    paths: Vec<String>,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<AsyncTask<CompileTask>> {
+    if paths.is_empty() {
+      return Err(napi_error("compileFilesAsync requires at least one path."));
+    }
+    let parsed = parse_js_compiler_config(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| CompilerConfigOptions::try_from(opts))
+      .transpose()?;
+    let config = self.resolve_call_config(overrides.as_ref())?;
+    let language_override = language_override(overrides.as_ref());
+    let path_bufs = paths.into_iter().map(PathBuf::from).collect();
+    Ok(AsyncTask::new(CompileTask {
+      request: Some(CompileRequest::Files {
+        paths: path_bufs,
+        language_override,
+      }),
+      deny_warnings: config.deny_warnings,
+      config,
+    }))
+  }
+
+  /// Async variant of `compileProject` that runs the solc invocation on a worker thread instead of
+  /// blocking the event loop.
+  #[napi(
+    ts_args_type = "options?: CompilerConfigOptions | undefined",
+    ts_return_type = "Promise<CompileOutput<true, string[]> | CompileOutput<false, string[]>>"
+  )]
+  pub fn compile_project_async(
+    &self,
+    env: Env,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<AsyncTask<CompileTask>> {
+    let parsed = parse_js_compiler_config(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| CompilerConfigOptions::try_from(opts))
+      .transpose()?;
+    let config = self.resolve_call_config(overrides.as_ref())?;
+    Ok(AsyncTask::new(CompileTask {
+      request: Some(CompileRequest::Project {
+        state: self.inner.state.clone(),
+      }),
+      deny_warnings: config.deny_warnings,
+      config,
+    }))
+  }
+
+  /// Async variant of `compileContract` that runs the solc invocation on a worker thread instead
+  /// of blocking the event loop.
+  #[napi(
+    ts_args_type = "contractName: string, options?: CompilerConfigOptions | undefined",
+    ts_return_type = "Promise<CompileOutput<true, undefined> | CompileOutput<false, undefined>>"
+  )]
+  pub fn compile_contract_async(
+    &self,
+    env: Env,
+    contract_name: String,
+    options: Option<JsUnknown>,
+  ) -> napi::Result<AsyncTask<CompileTask>> {
+    let parsed = parse_js_compiler_config(&env, options)?;
+    let overrides = parsed
+      .as_ref()
+      .map(|opts| CompilerConfigOptions::try_from(opts))
+      .transpose()?;
+    let config = self.resolve_call_config(overrides.as_ref())?;
+    Ok(AsyncTask::new(CompileTask {
+      request: Some(CompileRequest::Contract {
+        state: self.inner.state.clone(),
+        contract_name,
+      }),
+      deny_warnings: config.deny_warnings,
+      config,
+    }))
+  }
 }
 
 impl JsCompiler {
@@ -497,3 +874,59 @@ fn language_override(overrides: Option<&CompilerConfigOptions>) -> Option<Compil
       .or_else(|| opts.solc.language.map(CompilerLanguage::from))
   })
 }
+
+/// The arguments one of the `compile*Async` methods hands off to `CompileTask`, captured
+/// synchronously (while `Env`/`JsObject` parsing is still possible) so `compute` only ever touches
+/// plain Rust data and can run entirely on napi's worker pool.
+enum CompileRequest {
+  Source { state: State, target: SourceTarget },
+  Sources { state: State, sources: BTreeMap<String, SourceValue> },
+  Files { paths: Vec<PathBuf>, language_override: Option<CompilerLanguage> },
+  Project { state: State },
+  Contract { state: State, contract_name: String },
+}
+
+/// Runs one compile off the JS main thread. Built from an already-parsed `CompileRequest` plus the
+/// resolved `CompilerConfig`, so the actual solc invocation - including, for multi-version virtual
+/// sources, its per-group parallel scheduling (see `ProjectRunner::compile_multi_version`) -
+/// happens on a worker thread instead of stalling the event loop.
+pub struct CompileTask {
+  request: Option<CompileRequest>,
+  config: CompilerConfig,
+  deny_warnings: bool,
+}
+
+impl Task for CompileTask {
+  type Output = CompileOutput;
+  type JsValue = JsCompileOutput;
+
+  fn compute(&mut self) -> napi::Result<Self::Output> {
+    let request = self
+      .request
+      .take()
+      .expect("CompileTask::compute should only run once");
+    to_napi_result(match request {
+      CompileRequest::Source { state, target } => compile_source(&state, &self.config, target),
+      CompileRequest::Sources { state, sources } => {
+        compile_sources(&state, &self.config, sources)
+      }
+      CompileRequest::Files {
+        paths,
+        language_override,
+      } => compile_files(&self.config, paths, language_override),
+      CompileRequest::Project { state } => compile_project(&state, &self.config),
+      CompileRequest::Contract {
+        state,
+        contract_name,
+      } => compile_contract(&state, &self.config, &contract_name),
+    })
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+    Ok(into_js_compile_output(
+      output,
+      self.deny_warnings,
+      &self.config.suppressed_warning_codes,
+    ))
+  }
+}
@@ -1,6 +1,6 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::convert::TryFrom;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use foundry_compilers::artifacts::contract::Contract as FoundryContract;
 use foundry_compilers::artifacts::{
@@ -20,18 +20,21 @@ use semver::Version;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 
+use super::artifact_output::ArtifactWriter;
+use super::diagnostics;
+use super::sourcemap;
 use crate::ast::{utils::sanitize_ast_value, Ast, JsAst, SourceTarget};
 use crate::contract;
-use crate::contract::{Contract, JsContract, JsContractState};
-use crate::internal::config::AstConfigOptions;
-use crate::internal::errors::napi_error;
+use crate::contract::{Contract, JsCompactContractArtifact, JsContract, JsContractState};
+use crate::internal::config::{ArtifactFormat, AstConfigOptions};
+use crate::internal::errors::{napi_error, to_napi_result};
 
 // -----------------------------------------------------------------------------
 // Shared error and location types
 // -----------------------------------------------------------------------------
 
 #[napi(string_enum)]
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum SeverityLevel {
   Error,
   Warning,
@@ -39,13 +42,31 @@ pub enum SeverityLevel {
 }
 
 #[napi(object)]
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SourceLocation {
   pub file: String,
   pub start: i32,
   pub end: i32,
 }
 
+/// Decoded solc source maps for one contract, keyed on `CompileOutput::source_maps` by the same
+/// `<path>:<name>` identifier `collate_project_artifacts`/`build_compile_output` use for
+/// `artifacts`. `deployed`/`deployed_entries` are always empty for now: nothing in the current
+/// artifact ingestion captures a raw deployed-bytecode source map to decode, only
+/// `creationSourceMap`.
+#[napi(object)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractSourceMaps {
+  pub creation: Vec<SourceLocation>,
+  pub deployed: Vec<SourceLocation>,
+  /// Full-fidelity decode of the creation bytecode's source map: every field solc's compact
+  /// `s:l:f:j:m` format carries (including jump type and modifier depth), not just the
+  /// `start`/`end` byte range `creation` resolves `f` into a file name for.
+  pub creation_entries: Vec<sourcemap::SourceMapEntry>,
+  pub deployed_entries: Vec<sourcemap::SourceMapEntry>,
+}
+
 #[napi(object)]
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SecondarySourceLocation {
@@ -78,17 +99,130 @@ pub struct CompilerError {
   pub vyper_source_location: Option<VyperSourceLocation>,
 }
 
+/// Which SMTChecker engine (`ModelCheckerSettingsOptions::engine`) reported a
+/// `ModelCheckerDiagnostic`.
+#[napi(string_enum)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ModelCheckerEngineKind {
+  Chc,
+  Bmc,
+}
+
+/// An SMTChecker/model-checker finding - an unproven assertion, arithmetic overflow, etc. - split
+/// out of `errors` by its `CHC:`/`BMC:` message prefix so callers don't have to parse generic
+/// compiler diagnostics to tell a model-checker counterexample from an ordinary solc error.
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelCheckerDiagnostic {
+  pub engine: ModelCheckerEngineKind,
+  pub message: String,
+  pub severity: SeverityLevel,
+  pub source_location: Option<SourceLocation>,
+}
+
+/// Splits out the `errors` entries that are SMTChecker/model-checker findings - identified the
+/// same way solc's own CLI summary does, by the engine prefix on `message` - rather than ordinary
+/// compiler diagnostics.
+fn model_checker_diagnostics(errors: &[CompilerError]) -> Vec<ModelCheckerDiagnostic> {
+  errors
+    .iter()
+    .filter_map(|error| {
+      let engine = if error.message.starts_with("CHC:") {
+        ModelCheckerEngineKind::Chc
+      } else if error.message.starts_with("BMC:") {
+        ModelCheckerEngineKind::Bmc
+      } else {
+        return None;
+      };
+
+      Some(ModelCheckerDiagnostic {
+        engine,
+        message: error.message.clone(),
+        severity: error.severity.clone(),
+        source_location: error.source_location.clone(),
+      })
+    })
+    .collect()
+}
+
 // -----------------------------------------------------------------------------
 // Core domain types (Rust-facing)
 // -----------------------------------------------------------------------------
 
+/// Identifies one version of one contract, mirroring foundry/ethers' own `ArtifactId`. The same
+/// `(path, name)` pair can legitimately produce more than one `Contract` when a project compiles
+/// under multiple solc versions - conflicting `pragma solidity` ranges across imports force a
+/// source into more than one version bucket (see `partition_by_version`) - and `versions.last()`
+/// in `aggregated_to_value` used to silently discard every version but the last one iterated.
+/// `path` and `source` are both the source file's path here: an `ArtifactId` never names an
+/// on-disk artifact file. `compiler` covers the other way
+/// a `(path, name)` pair can collide: a project mixing Solidity and Vyper sources (or a caller
+/// that compiles the same project with both) produces a `Widget.sol`/`Widget` entry per
+/// compiler, not one - see `compiler_label_for_path`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ArtifactId {
+  pub path: String,
+  pub name: String,
+  pub source: String,
+  pub compiler: String,
+  pub version: Version,
+}
+
+impl ArtifactId {
+  fn to_js(&self) -> JsArtifactId {
+    JsArtifactId {
+      source_path: self.path.clone(),
+      contract_name: self.name.clone(),
+      compiler: self.compiler.clone(),
+      version: self.version.to_string(),
+    }
+  }
+}
+
+/// Which compiler produced a given source path, inferred from its extension the same way
+/// `infer_compiler_language` (`compiler/core.rs`) picks a `CompilerLanguage` to invoke - but
+/// collapsed to the two labels `ArtifactId` actually needs to disambiguate, since Yul shares
+/// solc's toolchain and therefore solc's artifact shape.
+fn compiler_label_for_path(path: &str) -> String {
+  let extension = Path::new(path)
+    .extension()
+    .and_then(|ext| ext.to_str())
+    .map(|ext| ext.to_ascii_lowercase());
+  match extension.as_deref() {
+    Some("vy") | Some("vyi") => "vyper".to_string(),
+    _ => "solc".to_string(),
+  }
+}
+
+/// `{ sourcePath, contractName, compiler, version }` view of `ArtifactId` for JS callers - see
+/// `ArtifactId`'s doc comment for what each field disambiguates.
+#[napi(object, js_name = "ArtifactId")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsArtifactId {
+  pub source_path: String,
+  pub contract_name: String,
+  pub compiler: String,
+  pub version: String,
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct SourceArtifacts {
   pub source_path: Option<String>,
   pub source_id: Option<u32>,
   pub solc_version: Option<Version>,
   pub ast: Option<SourceUnit>,
+  /// The contract callers see by default: one entry per name. Where `contracts_by_version` is
+  /// populated, this is resolved to whichever version it considers highest for that name; the
+  /// direct-solc and AST compile paths (`build_compile_output`/`compile_ast_sources`) only ever
+  /// resolve a single version to begin with and populate this directly instead.
   pub contracts: BTreeMap<String, Contract>,
+  /// Every version of every contract this source produced, keyed by name and then by the exact
+  /// solc version that produced it. Only `collate_project_artifacts` (the project-routed compile
+  /// path, where a source can genuinely compile under more than one solc version) populates this;
+  /// see `ArtifactId`.
+  pub contracts_by_version: BTreeMap<String, BTreeMap<Version, Contract>>,
 }
 
 impl SourceArtifacts {
@@ -99,9 +233,138 @@ impl SourceArtifacts {
     }
   }
 
+  /// Every `(name, version)` identity this source produced a contract for - see `ArtifactId`.
+  pub fn artifact_ids(&self, path: &str) -> Vec<ArtifactId> {
+    let compiler = compiler_label_for_path(path);
+    self
+      .contracts_by_version
+      .iter()
+      .flat_map(|(name, versions)| {
+        let compiler = compiler.clone();
+        versions.keys().map(move |version| ArtifactId {
+          path: path.to_string(),
+          name: name.clone(),
+          source: path.to_string(),
+          compiler: compiler.clone(),
+          version: version.clone(),
+        })
+      })
+      .collect()
+  }
+
+  /// Resolves the stable identity of the `name` contract this source produced - the highest
+  /// entry in `contracts_by_version` when this source compiled under more than one version, or
+  /// `solc_version` for the single-version compile paths (`build_compile_output`,
+  /// `compile_ast_sources`) that never populate `contracts_by_version` to begin with. `None` if
+  /// `name` isn't one of `contracts`, or if neither version source is available.
+  pub fn artifact_id(&self, path: &str, name: &str) -> Option<ArtifactId> {
+    if !self.contracts.contains_key(name) {
+      return None;
+    }
+    let version = self
+      .contracts_by_version
+      .get(name)
+      .and_then(|versions| versions.keys().next_back())
+      .or(self.solc_version.as_ref())?;
+
+    Some(ArtifactId {
+      path: path.to_string(),
+      name: name.to_string(),
+      source: path.to_string(),
+      compiler: compiler_label_for_path(path),
+      version: version.clone(),
+    })
+  }
+
   pub fn to_json(&self) -> SourceArtifactsJson {
     SourceArtifactsJson::from_source_artifacts(self)
   }
+
+  /// The lean projection of `to_json` - see `SourceArtifactsJson::from_source_artifacts_compact`.
+  pub fn to_json_compact(&self) -> SourceArtifactsJson {
+    SourceArtifactsJson::from_source_artifacts_compact(self)
+  }
+
+  /// Rebuilds a `SourceArtifacts` from its serialised form, used to replay a cached compile
+  /// (see `cache::read`). Contract fields that only round-trip through
+  /// the full `ContractState` shape (gas estimates, ewasm, function debug data, immutable
+  /// references) are not cached and come back empty; everything else is restored in full.
+  fn from_json(json: &SourceArtifactsJson) -> napi::Result<Self> {
+    let ast = json
+      .ast
+      .as_ref()
+      .and_then(|value| serde_json::from_value(value.clone()).ok());
+    let solc_version = json
+      .solc_version
+      .as_deref()
+      .and_then(|version| Version::parse(version).ok());
+
+    let mut contracts = BTreeMap::new();
+    if let Some(entries) = &json.contracts {
+      for (name, state) in entries {
+        let state = contract_state_from_cached_json(state)?;
+        contracts.insert(name.clone(), Contract::from(state));
+      }
+    }
+
+    // Cached compiles are always single-version (see `cache::key`), so mirroring `contracts`
+    // one-for-one under the cached `solc_version` is exact, not an approximation.
+    let contracts_by_version = if let Some(version) = &solc_version {
+      contracts
+        .iter()
+        .map(|(name, contract)| {
+          let mut versions = BTreeMap::new();
+          versions.insert(version.clone(), contract.clone());
+          (name.clone(), versions)
+        })
+        .collect()
+    } else {
+      BTreeMap::new()
+    };
+
+    Ok(Self {
+      source_path: json.source_path.clone(),
+      source_id: json.source_id,
+      solc_version,
+      ast,
+      contracts,
+      contracts_by_version,
+    })
+  }
+}
+
+/// Re-derives the camelCase JSON shape `contract::contract_state_from_json_value` expects from a
+/// `JsContractState` snapshot, without relying on it (or the native `ContractState` types it
+/// doesn't carry) being `Serialize`.
+fn contract_state_from_cached_json(state: &JsContractState) -> napi::Result<contract::ContractState> {
+  let value = serde_json::json!({
+    "name": state.name,
+    "address": state.address,
+    "abi": state.abi,
+    "sourcePath": state.source_path,
+    "sourceId": state.source_id,
+    "creationBytecode": state.creation_bytecode.as_ref().map(|bytecode| serde_json::json!({
+      "hex": bytecode.hex,
+      "bytes": bytecode.bytes,
+    })),
+    "deployedBytecode": state.deployed_bytecode.as_ref().map(|bytecode| serde_json::json!({
+      "hex": bytecode.hex,
+      "bytes": bytecode.bytes,
+    })),
+    "metadata": state.metadata,
+    "userdoc": state.userdoc,
+    "devdoc": state.devdoc,
+    "storageLayout": state.storage_layout,
+    "methodIdentifiers": state.method_identifiers,
+    "assembly": state.assembly,
+    "legacyAssembly": state.legacy_assembly,
+    "opcodes": state.opcodes,
+    "ir": state.ir,
+    "irOptimized": state.ir_optimized,
+    "creationSourceMap": state.creation_source_map,
+  });
+
+  contract::contract_state_from_json_value(&value)
 }
 
 #[napi(object, js_name = "SourceArtifactsJson")]
@@ -118,9 +381,31 @@ pub struct SourceArtifactsJson {
   pub ast: Option<Value>,
   #[napi(ts_type = "Record<string, ContractState> | undefined")]
   pub contracts: Option<BTreeMap<String, JsContractState>>,
+  /// `ArtifactId` for each entry in `contracts`, keyed by the same contract name - see
+  /// `SourceArtifacts::artifact_id`.
+  #[napi(ts_type = "Record<string, ArtifactId> | undefined")]
+  pub artifact_ids: Option<BTreeMap<String, JsArtifactId>>,
+  /// Populated instead of `contracts`/`ast` when the caller requested `toJson`'s lean shape - see
+  /// `Contract::to_compact`/`JsCompactContractArtifact`. `None` for the default, full projection.
+  #[napi(ts_type = "Record<string, CompactContractArtifact> | undefined")]
+  pub contracts_compact: Option<BTreeMap<String, JsCompactContractArtifact>>,
 }
 
 impl SourceArtifactsJson {
+  fn artifact_ids_json(artifacts: &SourceArtifacts) -> Option<BTreeMap<String, JsArtifactId>> {
+    if artifacts.contracts.is_empty() {
+      return None;
+    }
+    let path = artifacts.source_path.as_deref().unwrap_or_default();
+    Some(
+      artifacts
+        .contracts
+        .keys()
+        .filter_map(|name| Some((name.clone(), artifacts.artifact_id(path, name)?.to_js())))
+        .collect(),
+    )
+  }
+
   fn from_source_artifacts(artifacts: &SourceArtifacts) -> Self {
     let ast = artifacts.ast.as_ref().and_then(|unit| {
       let mut value = serde_json::to_value(unit).ok()?;
@@ -152,6 +437,39 @@ impl SourceArtifactsJson {
         .map(|version| version.to_string()),
       ast,
       contracts,
+      artifact_ids: Self::artifact_ids_json(artifacts),
+      contracts_compact: None,
+    }
+  }
+
+  /// Same projection as `from_source_artifacts`, but with the full `contracts`/`ast` fields
+  /// dropped and `contracts_compact` populated instead - see `JsCompactContractArtifact`. Used
+  /// when a caller passes `compact: true` to `JsCompileOutput::to_json`, so a large multi-contract
+  /// output doesn't pay to serialize AST and debug fields it won't read.
+  fn from_source_artifacts_compact(artifacts: &SourceArtifacts) -> Self {
+    let contracts_compact = if artifacts.contracts.is_empty() {
+      None
+    } else {
+      Some(
+        artifacts
+          .contracts
+          .iter()
+          .map(|(name, contract)| (name.clone(), contract::compact_contract_artifact_to_js(contract)))
+          .collect(),
+      )
+    };
+
+    Self {
+      source_path: artifacts.source_path.clone(),
+      source_id: artifacts.source_id,
+      solc_version: artifacts
+        .solc_version
+        .as_ref()
+        .map(|version| version.to_string()),
+      ast: None,
+      contracts: None,
+      artifact_ids: Self::artifact_ids_json(artifacts),
+      contracts_compact,
     }
   }
 }
@@ -162,19 +480,200 @@ pub struct CompileOutput {
   pub artifacts: BTreeMap<String, SourceArtifacts>,
   pub artifact: Option<SourceArtifacts>,
   pub errors: Vec<CompilerError>,
+  /// Path to the build-info record for this compilation, if `CompilerConfig::build_info_enabled`
+  /// was set: the `build-info/<hash>.json` written for a standalone solc invocation, or, for
+  /// `compile_project`/`compile_contract`, the newest file in the project's own `build-info`
+  /// directory.
+  pub build_info_path: Option<String>,
+  /// `true` when nothing had to be recompiled: either the underlying Foundry project's own
+  /// content-hash cache determined every source was unchanged, or this result was itself served
+  /// from the virtual-source compile cache (see `cache::read`).
+  pub cached: bool,
+  /// Decoded creation (and, once captured, deployed) source maps for every contract in
+  /// `artifacts`, keyed by the same `<path>:<name>` identifier. Derived from `artifacts` rather
+  /// than stored independently, so it's always in sync with whatever contracts actually compiled.
+  pub source_maps: BTreeMap<String, ContractSourceMaps>,
 }
 
 impl CompileOutput {
-  pub fn has_compiler_errors(&self) -> bool {
+  /// `deny_warnings` mirrors `CompilerConfig::deny_warnings`: when set, a surviving warning
+  /// (one not dropped by `ignored_error_codes` filtering) counts as a compiler error too.
+  /// `suppressed_warning_codes` (`CompilerConfig::suppressed_warning_codes`) downgrades matching
+  /// diagnostics out of this calculation without dropping them from `errors` - unlike
+  /// `ignored_error_codes`, which filters them out of `errors` entirely before this is ever
+  /// called.
+  pub fn has_compiler_errors(
+    &self,
+    deny_warnings: bool,
+    suppressed_warning_codes: &BTreeSet<u64>,
+  ) -> bool {
+    self.errors.iter().any(|error| {
+      if is_suppressed_error(error, suppressed_warning_codes) {
+        return false;
+      }
+      error.severity == SeverityLevel::Error
+        || (deny_warnings && error.severity == SeverityLevel::Warning)
+    })
+  }
+
+  /// The subset of `errors` with `SeverityLevel::Warning` severity.
+  pub fn warnings(&self) -> Vec<CompilerError> {
     self
       .errors
       .iter()
-      .any(|error| error.severity == SeverityLevel::Error)
+      .filter(|error| error.severity == SeverityLevel::Warning)
+      .cloned()
+      .collect()
+  }
+
+  /// Every diagnostic in `errors`, grouped by `severity`.
+  pub fn errors_by_severity(&self) -> BTreeMap<SeverityLevel, Vec<CompilerError>> {
+    let mut grouped: BTreeMap<SeverityLevel, Vec<CompilerError>> = BTreeMap::new();
+    for error in &self.errors {
+      grouped.entry(error.severity).or_default().push(error.clone());
+    }
+    grouped
+  }
+
+  /// SMTChecker/model-checker findings split out of `errors` by their `CHC:`/`BMC:` prefix. See
+  /// `model_checker_diagnostics` (the free function).
+  pub fn model_checker_diagnostics(&self) -> Vec<ModelCheckerDiagnostic> {
+    model_checker_diagnostics(&self.errors)
   }
 
   pub fn to_json(&self) -> CompileOutputJson {
     CompileOutputJson::from_compile_output(self)
   }
+
+  /// Resolves a creation-bytecode program counter for the `path`/`name` contract to the source
+  /// location it originated from, by finding which entry in `source_maps` owns the instruction at
+  /// that offset. See `sourcemap::instruction_index_at_pc` for how PUSH immediates are skipped so
+  /// a raw PC lines up with a decoded entry index rather than a byte offset. Returns `None` if the
+  /// contract, its creation bytecode, or its source map aren't available, or if `pc` doesn't land
+  /// on an instruction boundary - there's no deployed-bytecode equivalent yet; see
+  /// `ContractSourceMaps`'s doc comment for why.
+  pub fn source_location_at_pc(&self, path: &str, name: &str, pc: usize) -> Option<SourceLocation> {
+    let maps = self.source_maps.get(&format!("{path}:{name}"))?;
+    let bytecode = self.artifacts.get(path)?.contracts.get(name)?.creation_bytecode()?;
+    let index = sourcemap::instruction_index_at_pc(bytecode.bytes(), pc)?;
+    maps.creation.get(index).cloned()
+  }
+
+  /// Resolves the stable `ArtifactId` for the `path`/`name` contract - see
+  /// `SourceArtifacts::artifact_id`.
+  pub fn artifact_id(&self, path: &str, name: &str) -> Option<ArtifactId> {
+    self.artifacts.get(path)?.artifact_id(path, name)
+  }
+
+  /// Persists every contract in `artifacts` under `dir` using `format`'s on-disk layout (see
+  /// `artifact_output::ArtifactEmitter`). Unlike `ProjectRunner::emit_artifacts`, which only ever
+  /// runs as a side effect of `compile_project`/`compile_contract` against the project's own
+  /// artifacts directory, this lets a caller write out any `CompileOutput` - including one from a
+  /// synthetic/pure compile, which never touches disk on its own - to a directory of its choosing.
+  pub fn write_to(
+    &self,
+    dir: &Path,
+    format: ArtifactFormat,
+    emit_sourceless_artifacts: bool,
+  ) -> napi::Result<()> {
+    to_napi_result(
+      super::artifact_output::emitter_for(format).emit(dir, &self.artifacts, emit_sourceless_artifacts),
+    )
+  }
+
+  /// Persists `artifacts` under `dir` as plain JSON via `artifact_output::JsonArtifactWriter` -
+  /// one file per `(source, contract)` in `shape`, plus a combined `raw_artifacts` build-info
+  /// file. Unlike `write_to`, which matches an existing Foundry/Hardhat project's on-disk layout,
+  /// this is the crate's own default shape, meant for embedders using it as a standalone build
+  /// step. `context` lets repeated calls into the same directory clean up artifacts left behind
+  /// by a source or contract that no longer exists.
+  pub fn write_json_artifacts(
+    &self,
+    dir: &Path,
+    shape: super::artifact_output::ArtifactShape,
+    context: &super::artifact_output::WriteContext,
+  ) -> napi::Result<super::artifact_output::WriteReport> {
+    to_napi_result(
+      (super::artifact_output::JsonArtifactWriter { shape }).write(
+        dir,
+        &self.artifacts,
+        &self.raw_artifacts,
+        context,
+      ),
+    )
+  }
+
+  /// Rebuilds a `CompileOutput` from its cached JSON form (see
+  /// `cache::read`).
+  pub(crate) fn from_json(json: &CompileOutputJson) -> napi::Result<Self> {
+    let artifacts = json
+      .artifacts
+      .as_ref()
+      .map(|entries| {
+        entries
+          .iter()
+          .map(|(path, artifact)| Ok((path.clone(), SourceArtifacts::from_json(artifact)?)))
+          .collect::<napi::Result<BTreeMap<_, _>>>()
+      })
+      .transpose()?
+      .unwrap_or_default();
+
+    let artifact = json
+      .artifact
+      .as_ref()
+      .map(SourceArtifacts::from_json)
+      .transpose()?;
+
+    let source_maps = decode_contract_source_maps(&artifacts);
+
+    Ok(Self {
+      raw_artifacts: json.raw_artifacts.clone().unwrap_or(Value::Null),
+      artifacts,
+      artifact,
+      errors: json.errors.clone().unwrap_or_default(),
+      build_info_path: json.build_info_path.clone(),
+      cached: false,
+      source_maps,
+    })
+  }
+
+  /// Combines the outputs of several independent solc invocations - one per mutually-compatible
+  /// `pragma solidity` version group - into the single result `ProjectRunner::compile` returns to
+  /// its caller. `artifact` follows the same convention as `into_core_compile_output`: it's only
+  /// populated when the merged set contains exactly one source.
+  pub(crate) fn merge(outputs: Vec<CompileOutput>) -> Self {
+    let mut raw_artifacts = Vec::with_capacity(outputs.len());
+    let mut artifacts = BTreeMap::new();
+    let mut errors = Vec::new();
+    let mut build_info_path = None;
+    let mut cached = true;
+    let mut source_maps = BTreeMap::new();
+
+    for output in outputs {
+      raw_artifacts.push(output.raw_artifacts);
+      artifacts.extend(output.artifacts);
+      errors.extend(output.errors);
+      build_info_path = build_info_path.or(output.build_info_path);
+      cached = cached && output.cached;
+      source_maps.extend(output.source_maps);
+    }
+
+    let artifact = artifacts
+      .values()
+      .next()
+      .cloned()
+      .filter(|_| artifacts.len() == 1);
+
+    Self {
+      raw_artifacts: Value::Array(raw_artifacts),
+      artifacts,
+      artifact,
+      errors,
+      build_info_path,
+      cached,
+      source_maps,
+    }
+  }
 }
 
 #[napi(object, js_name = "CompileOutputJson")]
@@ -189,6 +688,8 @@ pub struct CompileOutputJson {
   pub errors: Option<Vec<CompilerError>>,
   #[napi(ts_type = "Record<string, unknown> | undefined")]
   pub raw_artifacts: Option<Value>,
+  #[napi(ts_type = "string | undefined")]
+  pub build_info_path: Option<String>,
 }
 
 impl CompileOutputJson {
@@ -222,38 +723,132 @@ impl CompileOutputJson {
       } else {
         Some(output.raw_artifacts.clone())
       },
+      build_info_path: output.build_info_path.clone(),
+    }
+  }
+
+  /// The lean projection of `from_compile_output` - every `SourceArtifactsJson` entry carries
+  /// `contracts_compact` instead of the full `contracts`/`ast`. See
+  /// `JsCompileOutput::to_json`'s `compact` argument.
+  fn from_compile_output_compact(output: &CompileOutput) -> Self {
+    let artifact = output.artifact.as_ref().map(SourceArtifacts::to_json_compact);
+
+    let artifacts = if output.artifacts.is_empty() {
+      None
+    } else {
+      Some(
+        output
+          .artifacts
+          .iter()
+          .map(|(path, artifacts)| (path.clone(), artifacts.to_json_compact()))
+          .collect(),
+      )
+    };
+
+    let errors = if output.errors.is_empty() {
+      None
+    } else {
+      Some(output.errors.clone())
+    };
+
+    Self {
+      artifact,
+      artifacts,
+      errors,
+      raw_artifacts: if output.raw_artifacts.is_null() {
+        None
+      } else {
+        Some(output.raw_artifacts.clone())
+      },
+      build_info_path: output.build_info_path.clone(),
     }
   }
 }
 
-pub fn into_core_compile_output(output: ProjectCompileOutput<MultiCompiler>) -> CompileOutput {
+/// `true` when `error`'s `error_code` appears in `suppressed_warning_codes` - see
+/// `CompileOutput::has_compiler_errors`. A diagnostic with no `error_code` (e.g. Vyper's, see
+/// `to_vyper_compiler_error`) is never suppressed.
+fn is_suppressed_error(error: &CompilerError, suppressed_warning_codes: &BTreeSet<u64>) -> bool {
+  error
+    .error_code
+    .is_some_and(|code| suppressed_warning_codes.contains(&(code as u64)))
+}
+
+/// Drops diagnostics whose solc `error_code` appears in `ignored_error_codes`, letting callers
+/// silence known-noisy warnings (or errors) without touching the underlying solc settings.
+fn filter_ignored_errors(errors: Vec<CompilerError>, ignored_error_codes: &[u64]) -> Vec<CompilerError> {
+  if ignored_error_codes.is_empty() {
+    return errors;
+  }
+  errors
+    .into_iter()
+    .filter(|error| {
+      error
+        .error_code
+        .map(|code| !ignored_error_codes.contains(&(code as u64)))
+        .unwrap_or(true)
+    })
+    .collect()
+}
+
+pub fn into_core_compile_output(
+  output: ProjectCompileOutput<MultiCompiler>,
+  ignored_error_codes: &[u64],
+  severity_overrides: &BTreeMap<u64, Severity>,
+  promote_all_warnings_to_errors: bool,
+) -> CompileOutput {
+  // Foundry's own project cache already hashes sources and transitively marks importers of a
+  // changed file dirty; `is_unchanged` reports whether any of that work actually ran this time.
+  let cached = output.is_unchanged();
   let artifacts = collate_project_artifacts(&output);
   let artifact = artifacts
     .values()
     .next()
     .cloned()
     .filter(|_| artifacts.len() == 1);
-  CompileOutput {
-    raw_artifacts: aggregated_to_value(output.output()),
-    errors: output
+  let errors = filter_ignored_errors(
+    output
       .output()
       .errors
       .iter()
       .map(|error: &MultiCompilerError| multi_error_to_core(error))
       .collect(),
+    ignored_error_codes,
+  );
+  let errors = apply_severity_overrides(errors, severity_overrides, promote_all_warnings_to_errors);
+  let source_maps = decode_contract_source_maps(&artifacts);
+  CompileOutput {
+    raw_artifacts: aggregated_to_value(output.output()),
+    errors,
     artifact,
     artifacts,
+    build_info_path: None,
+    cached,
+    source_maps,
   }
 }
 
-pub fn from_standard_json(output: CompilerOutput) -> CompileOutput {
+pub fn from_standard_json(
+  output: CompilerOutput,
+  ignored_error_codes: &[u64],
+  severity_overrides: &BTreeMap<u64, Severity>,
+  promote_all_warnings_to_errors: bool,
+) -> CompileOutput {
   let raw_artifacts = serde_json::to_value(&output).unwrap_or(Value::Null);
   let errors = output
     .errors
     .iter()
     .map(|error: &FoundryCompilerError| solc_error_to_core(error))
     .collect();
-  build_compile_output(&output.contracts, &output.sources, raw_artifacts, errors)
+  build_compile_output(
+    &output.contracts,
+    &output.sources,
+    raw_artifacts,
+    errors,
+    ignored_error_codes,
+    severity_overrides,
+    promote_all_warnings_to_errors,
+  )
 }
 
 fn convert_source_ast(source: &SourceFile) -> Option<SourceUnit> {
@@ -298,6 +893,11 @@ fn solc_error_to_core(error: &FoundryCompilerError) -> CompilerError {
   }
 }
 
+/// Vyper diagnostics carry no solc-style numeric error code, so `error_code` is always `None`
+/// here and `filter_ignored_errors`/`apply_severity_overrides` - both keyed on `error_code` - pass
+/// every Vyper diagnostic through unfiltered regardless of `ignored_error_codes`/
+/// `severity_overrides`. `promote_all_warnings_to_errors` still applies, since that check only
+/// looks at `severity`.
 pub(crate) fn vyper_error_to_core(error: &VyperCompilationError) -> CompilerError {
   let severity = match error.severity {
     Severity::Error => SeverityLevel::Error,
@@ -331,12 +931,48 @@ fn multi_error_to_core(error: &MultiCompilerError) -> CompilerError {
   }
 }
 
+/// Recomputes each diagnostic's effective severity from its `error_code`, modeled on rustc's
+/// allow/warn/deny lint levels: `severity_overrides` (keyed by solc/vyper error code) wins first,
+/// `promote_all_warnings_to_errors` promotes any remaining `Warning` next, and anything else keeps
+/// the severity solc/vyper itself reported (already shaped by whatever
+/// `CompilerConfig::compiler_severity_filter` the underlying project builder was given). A no-op
+/// when neither override is set.
+fn apply_severity_overrides(
+  mut errors: Vec<CompilerError>,
+  severity_overrides: &BTreeMap<u64, Severity>,
+  promote_all_warnings_to_errors: bool,
+) -> Vec<CompilerError> {
+  if severity_overrides.is_empty() && !promote_all_warnings_to_errors {
+    return errors;
+  }
+  for error in &mut errors {
+    let override_severity = error
+      .error_code
+      .and_then(|code| severity_overrides.get(&(code as u64)));
+    if let Some(severity) = override_severity {
+      error.severity = match severity {
+        Severity::Error => SeverityLevel::Error,
+        Severity::Warning => SeverityLevel::Warning,
+        Severity::Info => SeverityLevel::Info,
+      };
+    } else if promote_all_warnings_to_errors && error.severity == SeverityLevel::Warning {
+      error.severity = SeverityLevel::Error;
+    }
+  }
+  errors
+}
+
 pub(crate) fn build_compile_output(
   contracts: &FileToContractsMap<FoundryContract>,
   sources: &BTreeMap<PathBuf, SourceFile>,
   raw_artifacts: Value,
   errors: Vec<CompilerError>,
+  ignored_error_codes: &[u64],
+  severity_overrides: &BTreeMap<u64, Severity>,
+  promote_all_warnings_to_errors: bool,
 ) -> CompileOutput {
+  let errors = filter_ignored_errors(errors, ignored_error_codes);
+  let errors = apply_severity_overrides(errors, severity_overrides, promote_all_warnings_to_errors);
   let mut artifacts: BTreeMap<String, SourceArtifacts> = BTreeMap::new();
 
   for (path, contract_map) in contracts {
@@ -366,13 +1002,62 @@ pub(crate) fn build_compile_output(
     .next()
     .cloned()
     .filter(|_| artifacts.len() == 1);
+  let source_maps = decode_contract_source_maps(&artifacts);
 
   CompileOutput {
     raw_artifacts,
     artifacts,
     artifact,
     errors,
+    build_info_path: None,
+    cached: false,
+    source_maps,
+  }
+}
+
+/// Builds `CompileOutput::source_maps` from a freshly-collated `artifacts` map by decoding each
+/// contract's `creationSourceMap` against the `{sourceId: path}` table `artifacts` itself carries.
+fn decode_contract_source_maps(
+  artifacts: &BTreeMap<String, SourceArtifacts>,
+) -> BTreeMap<String, ContractSourceMaps> {
+  let source_index: BTreeMap<u32, String> = artifacts
+    .values()
+    .filter_map(|entry| Some((entry.source_id?, entry.source_path.clone()?)))
+    .collect();
+
+  let mut source_maps = BTreeMap::new();
+  for (path, entry) in artifacts {
+    for (name, contract) in &entry.contracts {
+      let Some(compact) = contract.state().creation_source_map.as_deref() else {
+        continue;
+      };
+      source_maps.insert(
+        format!("{path}:{name}"),
+        ContractSourceMaps {
+          creation: sourcemap::decode_source_map(compact, &source_index),
+          deployed: Vec::new(),
+          creation_entries: sourcemap::decode_source_map_entries(compact),
+          deployed_entries: Vec::new(),
+        },
+      );
+    }
+  }
+  source_maps
+}
+
+/// Inserts `clean` - artifacts `incremental::load_clean_artifacts` reloaded from disk for files
+/// `incremental::filter_dirty` judged already up to date - into `result.artifacts` for any path the
+/// fresh compile didn't itself produce, then recomputes `source_maps` over the merged set so it
+/// isn't left describing only the freshly-compiled subset. A path the fresh compile did produce
+/// always wins over a disk read for the same path.
+pub(crate) fn merge_clean_artifacts(
+  result: &mut CompileOutput,
+  clean: BTreeMap<String, SourceArtifacts>,
+) {
+  for (path, entry) in clean {
+    result.artifacts.entry(path).or_insert(entry);
   }
+  result.source_maps = decode_contract_source_maps(&result.artifacts);
 }
 
 fn to_core_secondary_location(
@@ -409,29 +1094,58 @@ fn collate_project_artifacts(
 ) -> BTreeMap<String, SourceArtifacts> {
   let mut artifacts: BTreeMap<String, SourceArtifacts> = BTreeMap::new();
 
-  let mut version_lookup: BTreeMap<(String, String), Version> = BTreeMap::new();
-  for (path, name, _, version) in output.output().contracts.contracts_with_files_and_version() {
-    let key = path.to_string_lossy().to_string();
-    version_lookup.insert((key, name.clone()), version.clone());
-  }
-
-  for (path, name, artifact) in output.artifacts_with_files() {
+  // `contracts_with_files_and_version` walks every `(path, name, contract, version)` combination
+  // the aggregated output holds - unlike `artifacts_with_files` below, it doesn't collapse a
+  // contract that compiled under more than one solc version down to a single entry - so this is
+  // the only place that can populate `contracts_by_version` with every version rather than
+  // whichever one a plain `(path, name)` map happened to keep last.
+  for (path, name, foundry_contract, version) in
+    output.output().contracts.contracts_with_files_and_version()
+  {
     let key = path.to_string_lossy().to_string();
     let entry = artifacts
       .entry(key.clone())
       .or_insert_with(|| SourceArtifacts::new(Some(key.clone())));
 
-    let version = version_lookup.get(&(key.clone(), name.clone())).cloned();
-    if entry.solc_version.is_none() {
-      entry.solc_version = version.clone();
-    }
+    let mut contract = Contract::from_foundry_standard_json(name.clone(), foundry_contract);
+    contract.state_mut().source_path = Some(key.clone());
+    entry
+      .contracts_by_version
+      .entry(name.clone())
+      .or_default()
+      .insert(version.clone(), contract);
+  }
+
+  // `artifacts_with_files` resolves to the single `ConfigurableContractArtifact` foundry actually
+  // writes to disk per `(path, name)` - richer than the raw standard-json `Contract` above (gas
+  // estimates, storage layout, ...) - but carries no version, so wherever a `(path, name)` already
+  // has a recorded version, the richer contract replaces the plain one under the highest of them.
+  for (path, name, artifact) in output.artifacts_with_files() {
+    let key = path.to_string_lossy().to_string();
+    let Some(entry) = artifacts.get_mut(&key) else {
+      continue;
+    };
+    let Some(version) = entry
+      .contracts_by_version
+      .get(name)
+      .and_then(|versions| versions.keys().max().cloned())
+    else {
+      continue;
+    };
 
     let mut contract = Contract::from_configurable_artifact(name.clone(), artifact);
     contract.state_mut().source_path = Some(key.clone());
     if entry.source_id.is_none() {
       entry.source_id = contract.state().source_id;
     }
-    entry.contracts.insert(name.clone(), contract);
+    if entry.solc_version.is_none() {
+      entry.solc_version = Some(version.clone());
+    }
+    entry
+      .contracts_by_version
+      .entry(name.clone())
+      .or_default()
+      .insert(version, contract);
   }
 
   for (path, source, version) in output.output().sources.sources_with_version() {
@@ -450,6 +1164,24 @@ fn collate_project_artifacts(
     }
   }
 
+  // `contracts` stays the "caller sees one contract per name" view used everywhere outside this
+  // function (and by every other compile path, which only ever records one version to begin
+  // with): resolve it deterministically to the highest version in `contracts_by_version`, rather
+  // than whichever version a blind last-write-wins insert happened to keep.
+  for entry in artifacts.values_mut() {
+    let resolved: Vec<(String, Contract)> = entry
+      .contracts_by_version
+      .iter()
+      .filter_map(|(name, versions)| {
+        versions
+          .iter()
+          .next_back()
+          .map(|(_, contract)| (name.clone(), contract.clone()))
+      })
+      .collect();
+    entry.contracts.extend(resolved);
+  }
+
   artifacts
 }
 
@@ -460,21 +1192,36 @@ where
 {
   let mut root = Map::new();
   let mut contracts_map = Map::new();
+  let mut contracts_by_version_map = Map::new();
   for (path, entries) in aggregated.contracts.0.iter() {
     let mut contract_map = Map::new();
+    let mut contract_by_version_map = Map::new();
     for (name, versions) in entries.iter() {
+      let mut version_map = Map::new();
+      for versioned in versions.iter() {
+        if let Ok(value) = serde_json::to_value(&versioned.contract) {
+          version_map.insert(versioned.version.to_string(), value);
+        }
+      }
       if let Some(latest) = versions.last() {
         if let Ok(value) = serde_json::to_value(&latest.contract) {
           contract_map.insert(name.clone(), value);
         }
       }
+      contract_by_version_map.insert(name.clone(), Value::Object(version_map));
     }
-    contracts_map.insert(
-      path.to_string_lossy().to_string(),
-      Value::Object(contract_map),
-    );
+    let path_key = path.to_string_lossy().to_string();
+    contracts_map.insert(path_key.clone(), Value::Object(contract_map));
+    contracts_by_version_map.insert(path_key, Value::Object(contract_by_version_map));
   }
   root.insert("contracts".to_string(), Value::Object(contracts_map));
+  // `contracts` above keeps the pre-existing "latest version wins" shape for back-compat; this
+  // mirrors every version `collate_project_artifacts` would otherwise keep separate, so raw_artifacts
+  // consumers aren't stuck with the same silent collapse `ArtifactId` was introduced to fix.
+  root.insert(
+    "contractsByVersion".to_string(),
+    Value::Object(contracts_by_version_map),
+  );
 
   let mut sources_map = Map::new();
   for (path, entries) in aggregated.sources.0.iter() {
@@ -505,6 +1252,7 @@ pub struct JsSourceArtifacts {
   ast_unit: Option<SourceUnit>,
   json: SourceArtifactsJson,
   contracts: HashMap<String, Contract>,
+  contracts_by_version: HashMap<String, HashMap<String, Contract>>,
 }
 
 impl JsSourceArtifacts {
@@ -517,8 +1265,20 @@ impl JsSourceArtifacts {
       solc_version,
       ast,
       contracts,
+      contracts_by_version,
     } = artifacts;
 
+    let contracts_by_version = contracts_by_version
+      .into_iter()
+      .map(|(name, versions)| {
+        let versions = versions
+          .into_iter()
+          .map(|(version, contract)| (version.to_string(), contract))
+          .collect();
+        (name, versions)
+      })
+      .collect();
+
     Self {
       source_path,
       source_id,
@@ -526,6 +1286,7 @@ impl JsSourceArtifacts {
       ast_unit: ast,
       json,
       contracts: contracts.into_iter().collect(),
+      contracts_by_version,
     }
   }
 
@@ -557,6 +1318,7 @@ impl JsSourceArtifacts {
       ast_unit: None,
       json: SourceArtifactsJson::default(),
       contracts: HashMap::new(),
+      contracts_by_version: HashMap::new(),
     }
   }
 
@@ -603,6 +1365,25 @@ impl JsSourceArtifacts {
       .collect()
   }
 
+  /// Every version of every contract this source produced, keyed by contract name and then by
+  /// solc version string - unlike `contracts`, which only ever exposes the highest version per
+  /// name, this is how a caller sees a source that compiled under more than one solc version
+  /// (e.g. conflicting `pragma solidity` ranges across a project's imports).
+  #[napi(getter, ts_return_type = "Record<string, Record<string, Contract>>")]
+  pub fn contracts_by_version(&self) -> HashMap<String, HashMap<String, JsContract>> {
+    self
+      .contracts_by_version
+      .iter()
+      .map(|(name, versions)| {
+        let versions = versions
+          .iter()
+          .map(|(version, contract)| (version.clone(), contract::contract_class(contract)))
+          .collect();
+        (name.clone(), versions)
+      })
+      .collect()
+  }
+
   #[napi(js_name = "toJson", ts_return_type = "SourceArtifactsJson")]
   pub fn to_json(&self) -> SourceArtifactsJson {
     self.json.clone()
@@ -613,22 +1394,37 @@ impl JsSourceArtifacts {
 #[derive(Clone, Debug)]
 pub struct JsCompileOutput {
   json: CompileOutputJson,
+  /// Lean projection of `json` - see `CompileOutputJson::from_compile_output_compact` - served by
+  /// `to_json(compact: true)` instead of recomputing it per call.
+  compact_json: CompileOutputJson,
   raw_artifacts: Value,
   artifacts: HashMap<String, JsSourceArtifacts>,
   artifact: Option<JsSourceArtifacts>,
   errors: Vec<CompilerError>,
   has_compiler_errors: bool,
+  suppressed_warning_codes: BTreeSet<u64>,
+  build_info_path: Option<String>,
+  cached: bool,
+  source_maps: BTreeMap<String, ContractSourceMaps>,
 }
 
 impl JsCompileOutput {
-  fn from_core(core: CompileOutput) -> Self {
-    let has_compiler_errors = core.has_compiler_errors();
+  fn from_core(
+    core: CompileOutput,
+    deny_warnings: bool,
+    suppressed_warning_codes: &BTreeSet<u64>,
+  ) -> Self {
+    let has_compiler_errors = core.has_compiler_errors(deny_warnings, suppressed_warning_codes);
     let json = core.to_json();
+    let compact_json = CompileOutputJson::from_compile_output_compact(&core);
     let CompileOutput {
       raw_artifacts,
       artifacts,
       artifact,
       errors,
+      build_info_path,
+      cached,
+      source_maps,
     } = core;
 
     let artifacts = artifacts
@@ -639,11 +1435,16 @@ impl JsCompileOutput {
 
     Self {
       json,
+      compact_json,
       raw_artifacts,
       artifacts,
       artifact,
       errors,
       has_compiler_errors,
+      suppressed_warning_codes: suppressed_warning_codes.clone(),
+      build_info_path,
+      cached,
+      source_maps,
     }
   }
 }
@@ -654,11 +1455,16 @@ impl JsCompileOutput {
   pub fn new() -> Self {
     Self {
       json: CompileOutputJson::default(),
+      compact_json: CompileOutputJson::default(),
       raw_artifacts: Value::Null,
       artifacts: HashMap::new(),
       artifact: None,
       errors: Vec::new(),
       has_compiler_errors: false,
+      suppressed_warning_codes: BTreeSet::new(),
+      build_info_path: None,
+      cached: false,
+      source_maps: BTreeMap::new(),
     }
   }
 
@@ -703,14 +1509,147 @@ impl JsCompileOutput {
     self.has_compiler_errors
   }
 
+  /// The subset of `diagnostics` with `Warning` severity, including any downgraded by
+  /// `suppressedWarningCodes` - suppression only affects `hasCompilerErrors`, not this list.
+  #[napi(getter)]
+  pub fn warnings(&self) -> Vec<CompilerError> {
+    self
+      .errors
+      .iter()
+      .filter(|error| error.severity == SeverityLevel::Warning)
+      .cloned()
+      .collect()
+  }
+
+  /// `diagnostics` grouped by `severity`, keyed by its lowercase label (`"error"`, `"warning"`,
+  /// `"info"`) since napi can't hand a string-enum-keyed map straight to JS.
+  #[napi(getter, ts_return_type = "Record<string, CompilerError[]>")]
+  pub fn errors_by_severity(&self) -> HashMap<String, Vec<CompilerError>> {
+    let mut grouped: HashMap<String, Vec<CompilerError>> = HashMap::new();
+    for error in &self.errors {
+      let key = format!("{:?}", error.severity).to_lowercase();
+      grouped.entry(key).or_default().push(error.clone());
+    }
+    grouped
+  }
+
+  /// `true` when `code` was configured via `suppressedWarningCodes` - a diagnostic with this
+  /// `errorCode` still appears in `diagnostics`/`warnings`, but is excluded from
+  /// `hasCompilerErrors`.
+  #[napi]
+  pub fn is_suppressed(&self, code: i64) -> bool {
+    self.suppressed_warning_codes.contains(&(code as u64))
+  }
+
+  /// `compact` selects between the default, full projection (every `SourceArtifactsJson` carries
+  /// its `ContractState`/AST) and the lean one (`contracts_compact` instead - see
+  /// `Contract::to_compact`) for a large multi-contract output that doesn't want to serialize
+  /// every AST/debug field it won't read.
   #[napi(js_name = "toJson", ts_return_type = "CompileOutputJson")]
-  pub fn to_json(&self) -> CompileOutputJson {
-    self.json.clone()
+  pub fn to_json(&self, compact: Option<bool>) -> CompileOutputJson {
+    if compact.unwrap_or(false) {
+      self.compact_json.clone()
+    } else {
+      self.json.clone()
+    }
+  }
+
+  /// Path to the build-info JSON record for this compilation, when `buildInfoEnabled` was set -
+  /// see `CompileOutput::build_info_path`.
+  #[napi(getter, ts_return_type = "string | undefined")]
+  pub fn build_info_path(&self) -> Option<String> {
+    self.build_info_path.clone()
+  }
+
+  /// `true` when this result was served without recompiling anything - either the attached
+  /// Foundry project's content-hash cache found every source unchanged, or the result came from
+  /// the virtual-source compile cache.
+  #[napi(getter)]
+  pub fn cached(&self) -> bool {
+    self.cached
+  }
+
+  /// Decoded creation (and, once captured, deployed) source maps for every contract in
+  /// `artifacts`, keyed by the same `<path>:<name>` identifier.
+  #[napi(getter, ts_return_type = "Record<string, ContractSourceMaps>")]
+  pub fn source_maps(&self) -> HashMap<String, ContractSourceMaps> {
+    self.source_maps.clone().into_iter().collect()
+  }
+
+  /// SMTChecker/model-checker findings - unproven assertions, arithmetic overflows, and the like -
+  /// split out of `errors` by their `CHC:`/`BMC:` prefix. Empty unless `ModelCheckerSettings` was
+  /// configured with an engine other than `none`.
+  #[napi(getter)]
+  pub fn model_checker_diagnostics(&self) -> Vec<ModelCheckerDiagnostic> {
+    model_checker_diagnostics(self.errors.as_slice())
+  }
+
+  /// Resolves a creation-bytecode program counter for the `path`/`name` contract to the source
+  /// location it originated from - e.g. for turning a trace's PC into a stack-trace frame or
+  /// coverage overlay. See `CompileOutput::source_location_at_pc` for the exact lookup rules.
+  #[napi]
+  pub fn source_location_at_pc(
+    &self,
+    path: String,
+    name: String,
+    pc: u32,
+  ) -> Option<SourceLocation> {
+    let maps = self.source_maps.get(&format!("{path}:{name}"))?;
+    let bytecode = self
+      .artifacts
+      .get(&path)?
+      .contracts
+      .get(&name)?
+      .creation_bytecode()?;
+    let index = sourcemap::instruction_index_at_pc(bytecode.bytes(), pc as usize)?;
+    maps.creation.get(index).cloned()
+  }
+
+  /// Resolves the stable, compiler- and version-qualified identifier for the `path`/`name`
+  /// contract - see `ArtifactId`. Reads off `toJson()`'s `artifacts[path].artifactIds`, so it
+  /// always reflects whichever version `contracts_by_version`'s resolution picked for a
+  /// multi-version contract.
+  #[napi]
+  pub fn artifact_id(&self, path: String, name: String) -> Option<JsArtifactId> {
+    self
+      .json
+      .artifacts
+      .as_ref()?
+      .get(&path)?
+      .artifact_ids
+      .as_ref()?
+      .get(&name)
+      .cloned()
+  }
+
+  /// Renders a human-readable code-frame `formattedMessage` for every entry in `diagnostics`,
+  /// given the original source text for whatever files it points into - the offending line(s), a
+  /// caret/underline span, the `file:line:col` header, severity and error code, plus an inlined
+  /// frame per `secondarySourceLocations` entry. Set `color` for ANSI escapes (terminal
+  /// consumers) or leave it off for plain text (editor/LSP consumers). A diagnostic whose file
+  /// isn't in `sources` - or with no `sourceLocation` at all - comes back with whatever
+  /// `formattedMessage` it already had.
+  #[napi]
+  pub fn format_diagnostics(
+    &self,
+    sources: HashMap<String, String>,
+    color: bool,
+  ) -> Vec<CompilerError> {
+    let sources: BTreeMap<String, String> = sources.into_iter().collect();
+    self
+      .errors
+      .iter()
+      .map(|error| diagnostics::with_rendered_frame(error, &sources, color))
+      .collect()
   }
 }
 
-pub fn into_js_compile_output(core: CompileOutput) -> JsCompileOutput {
-  JsCompileOutput::from_core(core)
+pub fn into_js_compile_output(
+  core: CompileOutput,
+  deny_warnings: bool,
+  suppressed_warning_codes: &BTreeSet<u64>,
+) -> JsCompileOutput {
+  JsCompileOutput::from_core(core, deny_warnings, suppressed_warning_codes)
 }
 
 // -----------------------------------------------------------------------------
@@ -759,9 +1698,9 @@ mod tests {
     }"#;
 
     let output: StandardCompilerOutput = serde_json::from_str(json).expect("compiler output");
-    let core = from_standard_json(output);
+    let core = from_standard_json(output, &[], &BTreeMap::new(), false);
 
-    assert!(core.has_compiler_errors());
+    assert!(core.has_compiler_errors(false, &BTreeSet::new()));
     assert!(core.raw_artifacts["contracts"]["Test.sol"]["Test"].is_object());
     let snapshot = core.to_json();
     let artifacts = snapshot.artifacts.expect("artifacts snapshot");
@@ -781,6 +1720,72 @@ mod tests {
     assert_eq!(error.error_code, Some(42));
   }
 
+  #[test]
+  fn source_location_at_pc_resolves_the_decoded_entry_owning_that_instruction() {
+    let json = r#"{
+      "contracts": {
+        "Test.sol": {
+          "Test": {
+            "abi": [],
+            "evm": {
+              "bytecode": {
+                "object": "0x600160025b",
+                "sourceMap": "0:1:0:-:0;10:5:0:-:0;20:3:0:-:0"
+              },
+              "deployedBytecode": { "bytecode": { "object": "0x" }, "immutableReferences": {} }
+            }
+          }
+        }
+      },
+      "errors": [],
+      "sources": {
+        "Test.sol": { "id": 1 }
+      },
+      "version": "0.8.21"
+    }"#;
+
+    let output: StandardCompilerOutput = serde_json::from_str(json).expect("compiler output");
+    let core = from_standard_json(output, &[], &BTreeMap::new(), false);
+
+    // `0x60016002 5b"` is PUSH1 0x01, PUSH1 0x02, JUMPDEST - instructions start at bytes 0, 2, 4.
+    let location = core
+      .source_location_at_pc("Test.sol", "Test", 2)
+      .expect("source location");
+    assert_eq!(location.start, 10);
+    assert_eq!(location.end, 15);
+  }
+
+  #[test]
+  fn source_location_at_pc_is_none_off_an_instruction_boundary() {
+    let json = r#"{
+      "contracts": {
+        "Test.sol": {
+          "Test": {
+            "abi": [],
+            "evm": {
+              "bytecode": {
+                "object": "0x600160025b",
+                "sourceMap": "0:1:0:-:0;10:5:0:-:0;20:3:0:-:0"
+              },
+              "deployedBytecode": { "bytecode": { "object": "0x" }, "immutableReferences": {} }
+            }
+          }
+        }
+      },
+      "errors": [],
+      "sources": {
+        "Test.sol": { "id": 1 }
+      },
+      "version": "0.8.21"
+    }"#;
+
+    let output: StandardCompilerOutput = serde_json::from_str(json).expect("compiler output");
+    let core = from_standard_json(output, &[], &BTreeMap::new(), false);
+
+    assert!(core.source_location_at_pc("Test.sol", "Test", 1).is_none());
+    assert!(core.source_location_at_pc("Missing.sol", "Test", 0).is_none());
+  }
+
   #[test]
   fn from_standard_json_captures_ast_when_present() {
     use foundry_compilers::artifacts::ast::Ast;
@@ -815,7 +1820,7 @@ mod tests {
     output
       .sources
       .insert(PathBuf::from("Inline.sol"), source_file);
-    let core = from_standard_json(output);
+    let core = from_standard_json(output, &[], &BTreeMap::new(), false);
 
     let entry = core.artifacts.get("Inline.sol").expect("source entry");
     assert_eq!(entry.source_id, Some(1));
@@ -834,6 +1839,48 @@ mod tests {
     assert!(raw_snapshot["sources"]["Inline.sol"].get("ast").is_some());
   }
 
+  #[test]
+  fn standalone_source_artifact_surfaces_through_into_js_compile_output_and_to_json() {
+    use foundry_compilers::artifacts::ast::Ast;
+
+    let ast: Ast = serde_json::from_value(json!({
+      "absolutePath": "Library.sol",
+      "id": 7,
+      "exportedSymbols": {},
+      "nodeType": "SourceUnit",
+      "src": "0:0:0",
+      "nodes": []
+    }))
+    .expect("ast");
+
+    let source_file = SourceFile {
+      id: 7,
+      ast: Some(ast),
+    };
+
+    let mut output = CompilerOutput::default();
+    output
+      .sources
+      .insert(PathBuf::from("Library.sol"), source_file);
+    let core = from_standard_json(output, &[], &BTreeMap::new(), false);
+
+    let js_output = into_js_compile_output(core, false, &BTreeSet::new());
+    let artifacts = js_output.artifacts();
+    let entry = artifacts.get("Library.sol").expect("standalone artifact");
+    assert!(entry.ast_unit.is_some());
+    assert!(entry.contracts.is_empty());
+
+    let snapshot = js_output.to_json(None);
+    let source_snapshot = snapshot
+      .artifacts
+      .expect("artifacts snapshot")
+      .get("Library.sol")
+      .cloned()
+      .expect("library snapshot");
+    assert!(source_snapshot.ast.is_some());
+    assert!(source_snapshot.contracts.is_none());
+  }
+
   #[test]
   fn compiler_error_maps_severity_labels() {
     let json = r#"{
@@ -853,7 +1900,7 @@ mod tests {
     }"#;
 
     let output: StandardCompilerOutput = serde_json::from_str(json).expect("compiler output");
-    let core = from_standard_json(output);
+    let core = from_standard_json(output, &[], &BTreeMap::new(), false);
     assert_eq!(core.errors.len(), 1);
     let error = &core.errors[0];
     assert_eq!(error.severity, SeverityLevel::Warning);
@@ -886,6 +1933,9 @@ mod tests {
         }]),
         vyper_source_location: None,
       }],
+      build_info_path: None,
+      cached: false,
+      source_maps: BTreeMap::new(),
     };
 
     let mut artifacts = SourceArtifacts::default();
@@ -894,13 +1944,13 @@ mod tests {
     artifacts.contracts.insert("Widget".into(), contract);
     core.artifacts.insert("Widget.sol".into(), artifacts);
 
-    let js_output = into_js_compile_output(core);
+    let js_output = into_js_compile_output(core, false, &BTreeSet::new());
     assert!(js_output
       .artifacts
       .get("Widget.sol")
       .and_then(|entry| entry.contracts.get("Widget"))
       .is_some());
-    let snapshot = js_output.to_json();
+    let snapshot = js_output.to_json(None);
     assert!(snapshot
       .artifacts
       .as_ref()
@@ -920,4 +1970,191 @@ mod tests {
       Some("Test.sol")
     );
   }
+
+  #[test]
+  fn ignored_error_codes_are_filtered_and_deny_warnings_promotes_remaining_warning() {
+    let json = r#"{
+      "contracts": {},
+      "errors": [
+        {
+          "component": "general",
+          "errorCode": "2072",
+          "formattedMessage": "Warning: unused variable",
+          "message": "unused variable",
+          "severity": "warning",
+          "type": "Warning"
+        },
+        {
+          "component": "general",
+          "errorCode": "5667",
+          "formattedMessage": "Warning: unused function parameter",
+          "message": "unused function parameter",
+          "severity": "warning",
+          "type": "Warning"
+        }
+      ],
+      "sources": {},
+      "version": "0.8.24"
+    }"#;
+
+    let output: StandardCompilerOutput = serde_json::from_str(json).expect("compiler output");
+    let core = from_standard_json(output, &[2072], &BTreeMap::new(), false);
+
+    assert_eq!(core.errors.len(), 1);
+    assert_eq!(core.errors[0].error_code, Some(5667));
+    assert!(!core.has_compiler_errors(false, &BTreeSet::new()));
+    assert!(core.has_compiler_errors(true, &BTreeSet::new()));
+  }
+
+  #[test]
+  fn suppressed_warning_codes_stay_in_errors_but_drop_out_of_has_compiler_errors() {
+    let json = r#"{
+      "contracts": {},
+      "errors": [
+        {
+          "component": "general",
+          "errorCode": "5574",
+          "formattedMessage": "Warning: contract code size exceeds 24576 bytes",
+          "message": "contract code size exceeds 24576 bytes",
+          "severity": "warning",
+          "type": "Warning"
+        }
+      ],
+      "sources": {},
+      "version": "0.8.24"
+    }"#;
+
+    let output: StandardCompilerOutput = serde_json::from_str(json).expect("compiler output");
+    let core = from_standard_json(output, &[], &BTreeMap::new(), false);
+
+    let mut suppressed = BTreeSet::new();
+    suppressed.insert(5574u64);
+
+    assert_eq!(core.errors.len(), 1);
+    assert!(core.has_compiler_errors(true, &BTreeSet::new()));
+    assert!(!core.has_compiler_errors(true, &suppressed));
+
+    let js_output = into_js_compile_output(core, true, &suppressed);
+    assert!(!js_output.has_compiler_errors());
+    assert_eq!(js_output.diagnostics().len(), 1);
+    assert!(js_output.is_suppressed(5574));
+    assert!(!js_output.is_suppressed(2072));
+  }
+
+  #[test]
+  fn warnings_and_errors_by_severity_group_diagnostics() {
+    let json = r#"{
+      "contracts": {},
+      "errors": [
+        {
+          "component": "general",
+          "errorCode": "42",
+          "formattedMessage": "Error: detail",
+          "message": "detail",
+          "severity": "error",
+          "type": "TypeError"
+        },
+        {
+          "component": "general",
+          "errorCode": "2072",
+          "formattedMessage": "Warning: unused variable",
+          "message": "unused variable",
+          "severity": "warning",
+          "type": "Warning"
+        }
+      ],
+      "sources": {},
+      "version": "0.8.24"
+    }"#;
+
+    let output: StandardCompilerOutput = serde_json::from_str(json).expect("compiler output");
+    let core = from_standard_json(output, &[], &BTreeMap::new(), false);
+
+    assert_eq!(core.warnings().len(), 1);
+    assert_eq!(core.warnings()[0].error_code, Some(2072));
+
+    let grouped = core.errors_by_severity();
+    assert_eq!(grouped[&SeverityLevel::Error].len(), 1);
+    assert_eq!(grouped[&SeverityLevel::Warning].len(), 1);
+
+    let js_output = into_js_compile_output(core, false, &BTreeSet::new());
+    assert_eq!(js_output.warnings().len(), 1);
+    let js_grouped = js_output.errors_by_severity();
+    assert_eq!(js_grouped["error"].len(), 1);
+    assert_eq!(js_grouped["warning"].len(), 1);
+  }
+
+  #[test]
+  fn severity_override_wins_over_the_global_promotion_flag() {
+    let json = r#"{
+      "contracts": {},
+      "errors": [
+        {
+          "component": "general",
+          "errorCode": "2072",
+          "formattedMessage": "Warning: unused variable",
+          "message": "unused variable",
+          "severity": "warning",
+          "type": "Warning"
+        },
+        {
+          "component": "general",
+          "errorCode": "5667",
+          "formattedMessage": "Warning: unused function parameter",
+          "message": "unused function parameter",
+          "severity": "warning",
+          "type": "Warning"
+        }
+      ],
+      "sources": {},
+      "version": "0.8.24"
+    }"#;
+
+    let output: StandardCompilerOutput = serde_json::from_str(json).expect("compiler output");
+    let severity_overrides = BTreeMap::from([(2072, Severity::Info)]);
+    let core = from_standard_json(output, &[], &severity_overrides, true);
+
+    let unused_variable = core
+      .errors
+      .iter()
+      .find(|error| error.error_code == Some(2072))
+      .expect("unused variable diagnostic");
+    assert_eq!(
+      unused_variable.severity,
+      SeverityLevel::Info,
+      "an explicit override should win over promote_all_warnings_to_errors"
+    );
+    let unused_parameter = core
+      .errors
+      .iter()
+      .find(|error| error.error_code == Some(5667))
+      .expect("unused parameter diagnostic");
+    assert_eq!(
+      unused_parameter.severity,
+      SeverityLevel::Error,
+      "a warning without its own override should still be promoted"
+    );
+  }
+
+  #[test]
+  fn ignored_error_codes_do_not_filter_vyper_diagnostics() {
+    // `vyper_error_to_core` always produces `error_code: None`, since Vyper diagnostics carry no
+    // solc-style numeric code - `filter_ignored_errors` can only match on `error_code`, so a
+    // Vyper warning should pass through untouched no matter what's in `ignored_error_codes`.
+    let vyper_warning = CompilerError {
+      message: "unused variable".to_string(),
+      formatted_message: Some("Warning: unused variable".to_string()),
+      component: "vyper".to_string(),
+      severity: SeverityLevel::Warning,
+      error_type: "Vyper".to_string(),
+      error_code: None,
+      source_location: None,
+      secondary_source_locations: None,
+      vyper_source_location: None,
+    };
+
+    let filtered = filter_ignored_errors(vec![vyper_warning], &[2072, 5667]);
+
+    assert_eq!(filtered.len(), 1);
+  }
 }
@@ -1,23 +1,33 @@
-use std::collections::BTreeMap;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 
+use super::artifact_output;
+use super::cache;
+use super::incremental;
 use super::input::CompilationInput;
+use super::output;
 use super::output::{into_core_compile_output, CompileOutput};
 use crate::internal::config::CompilerLanguage;
 use crate::internal::path::canonicalize_path;
 use crate::internal::vyper;
 use crate::internal::{
+  config,
   config::CompilerConfig,
   errors::{map_err_with_context, Error, Result},
   project::{
-    build_project, create_synthetic_context, default_cache_dir, ProjectContext, ProjectLayout,
+    build_project, create_synthetic_context, default_cache_dir, latest_build_info_path,
+    ProjectContext, ProjectLayout,
   },
-  solc,
+  resolver, solc,
 };
 use foundry_compilers::artifacts::sources::Source as FoundrySource;
+use foundry_compilers::artifacts::{CompilerOutput, SolcInput};
 use foundry_compilers::compilers::multi::MultiCompiler;
 use foundry_compilers::{Project, ProjectCompileOutput};
+use semver::{Version, VersionReq};
 
 struct VirtualSourceEntry<'a> {
   original_path: Option<&'a str>,
@@ -44,6 +54,18 @@ impl<'a> ProjectRunner<'a> {
     match input {
       CompilationInput::InlineSource { source } => {
         if matches!(self.context.layout, ProjectLayout::Synthetic) && config.cache_enabled {
+          let effective_version = if config.auto_detect_solc_version {
+            solc::resolve_version(source, config.offline_mode)?
+          } else {
+            config.solc_version.clone()
+          };
+          let content_hash = FoundrySource::content_hash_of(source);
+          let cache_key = cache::key([content_hash], &effective_version, &config.solc_settings);
+          let cache_dir = default_cache_dir();
+          if let Some(cached) = cache::read(&cache_dir, &cache_key) {
+            return Ok(Some(cached));
+          }
+
           let mut paths = self.write_virtual_sources(
             config,
             [VirtualSourceEntry {
@@ -55,10 +77,23 @@ impl<'a> ProjectRunner<'a> {
           let path = paths
             .pop()
             .ok_or_else(|| Error::new("Failed to prepare virtual source for inline compilation"))?;
-          let output = self.compile_with_project(config, "Compilation failed", |project| {
-            project.compile_file(path)
-          });
-          output.map(|out| Some(into_core_compile_output(out)))
+          let cached_path = path.clone();
+          let output = self.compile_with_project(
+            config,
+            &effective_version,
+            "Compilation failed",
+            |project| project.compile_file(path),
+          );
+          let core_output = output.map(|out| {
+            into_core_compile_output(
+              out,
+              &config.ignored_error_codes,
+              &config.severity_overrides,
+              config.promote_all_warnings_to_errors,
+            )
+          })?;
+          cache::write(&cache_dir, cache_key, vec![cached_path], &core_output)?;
+          Ok(Some(core_output))
         } else {
           Ok(None)
         }
@@ -67,17 +102,98 @@ impl<'a> ProjectRunner<'a> {
         if matches!(self.context.layout, ProjectLayout::Synthetic) && !config.cache_enabled {
           return Ok(None);
         }
-        let normalized = self.context.normalise_paths(paths.as_slice())?;
-        let output = self.compile_with_project(config, "Compilation failed", |project| {
-          project.compile_files(normalized)
-        });
-        output.map(|out| Some(into_core_compile_output(out)))
+        let normalized = self.context.normalise_paths(config, paths.as_slice())?;
+        let closure = resolver::Graph::build(
+          &normalized,
+          &config.remappings,
+          &self.context.paths.include_paths,
+          &self.context.paths.libraries,
+        );
+
+        if self.context.auto_detect {
+          return self.compile_files_by_version(config, &closure).map(Some);
+        }
+
+        let all_files = closure.reachable_files().into_iter().collect::<Vec<_>>();
+        let files = self.dirty_files(config, &config.solc_version, &all_files, &closure)?;
+        let clean_files = clean_files(&all_files, &files);
+
+        if files.is_empty() {
+          let mut result = CompileOutput::merge(Vec::new());
+          result.cached = true;
+          output::merge_clean_artifacts(
+            &mut result,
+            incremental::load_clean_artifacts(
+              &self.context.paths.artifacts,
+              &self.context.root,
+              &clean_files,
+            ),
+          );
+          result
+            .errors
+            .extend(incremental::load_clean_errors(&default_cache_dir(), &clean_files));
+          return Ok(Some(result));
+        }
+
+        let recompiled_files = files.clone();
+        let output =
+          self.compile_with_project(config, &config.solc_version, "Compilation failed", |project| {
+            project.compile_files(files)
+          });
+        output.map(|out| {
+          let mut result = into_core_compile_output(
+            out,
+            &config.ignored_error_codes,
+            &config.severity_overrides,
+            config.promote_all_warnings_to_errors,
+          );
+          output::merge_clean_artifacts(
+            &mut result,
+            incremental::load_clean_artifacts(
+              &self.context.paths.artifacts,
+              &self.context.root,
+              &clean_files,
+            ),
+          );
+          incremental::record_errors(
+            &default_cache_dir(),
+            &self.context.root,
+            &recompiled_files,
+            &result.errors,
+          )
+          .ok();
+          result
+            .errors
+            .extend(incremental::load_clean_errors(&default_cache_dir(), &clean_files));
+          Some(result)
+        })
       }
       CompilationInput::SourceMap {
         sources,
         language_override,
       } => {
-        if matches!(self.context.layout, ProjectLayout::Synthetic) && config.cache_enabled {
+        if !matches!(self.context.layout, ProjectLayout::Synthetic) {
+          return Ok(None);
+        }
+
+        if config.auto_detect_solc_version || !config_version_satisfies_all(config, sources) {
+          // Auto-detect mode always resolves one version per mutually-compatible pragma group
+          // rather than trying the pinned `config.solc_version` first; otherwise, a pin that
+          // can't compile every source here falls back to the same per-group resolution. Neither
+          // path currently feeds the compile cache below; see `compile_multi_version`.
+          return self.compile_multi_version(config, sources, *language_override).map(Some);
+        }
+
+        if config.cache_enabled {
+          let content_hashes = sources
+            .values()
+            .map(|contents| FoundrySource::content_hash_of(contents));
+          let cache_key = cache::key(content_hashes, &config.solc_version, &config.solc_settings);
+          let cache_dir = default_cache_dir();
+          if let Some(cached) = cache::read(&cache_dir, &cache_key) {
+            return Ok(Some(cached));
+          }
+
           let files = self.write_virtual_sources(
             config,
             sources.iter().map(|(path, contents)| VirtualSourceEntry {
@@ -86,10 +202,21 @@ impl<'a> ProjectRunner<'a> {
             }),
             *language_override,
           )?;
-          let output = self.compile_with_project(config, "Compilation failed", move |project| {
-            project.compile_files(files.clone())
-          });
-          output.map(|out| Some(into_core_compile_output(out)))
+          let cached_files = files.clone();
+          let output =
+            self.compile_with_project(config, &config.solc_version, "Compilation failed", move |project| {
+              project.compile_files(files.clone())
+            });
+          let core_output = output.map(|out| {
+            into_core_compile_output(
+              out,
+              &config.ignored_error_codes,
+              &config.severity_overrides,
+              config.promote_all_warnings_to_errors,
+            )
+          })?;
+          cache::write(&cache_dir, cache_key, cached_files, &core_output)?;
+          Ok(Some(core_output))
         } else {
           Ok(None)
         }
@@ -99,10 +226,23 @@ impl<'a> ProjectRunner<'a> {
   }
 
   pub fn compile_project(&self, config: &CompilerConfig) -> Result<CompileOutput> {
-    let output = self.compile_with_project(config, "Project compilation failed", |project| {
-      project.compile()
-    });
-    output.map(into_core_compile_output)
+    let output = self.compile_with_project(
+      config,
+      &config.solc_version,
+      "Project compilation failed",
+      |project| project.compile(),
+    );
+    output.and_then(|out| {
+      let mut result = into_core_compile_output(
+        out,
+        &config.ignored_error_codes,
+        &config.severity_overrides,
+        config.promote_all_warnings_to_errors,
+      );
+      result.build_info_path = self.project_build_info_path(config);
+      self.emit_artifacts(config, &result)?;
+      Ok(result)
+    })
   }
 
   pub fn compile_contract(
@@ -111,16 +251,81 @@ impl<'a> ProjectRunner<'a> {
     contract_name: &str,
   ) -> Result<CompileOutput> {
     let name = contract_name.to_owned();
-    let output = self.compile_with_project(config, "Contract compilation failed", move |project| {
-      let path = project.find_contract_path(&name)?;
-      project.compile_file(path)
-    });
-    output.map(into_core_compile_output)
+    let output = self.compile_with_project(
+      config,
+      &config.solc_version,
+      "Contract compilation failed",
+      move |project| {
+        let path = project.find_contract_path(&name)?;
+        project.compile_file(path)
+      },
+    );
+    output.and_then(|out| {
+      let mut result = into_core_compile_output(
+        out,
+        &config.ignored_error_codes,
+        &config.severity_overrides,
+        config.promote_all_warnings_to_errors,
+      );
+      result.build_info_path = self.project_build_info_path(config);
+      self.emit_artifacts(config, &result)?;
+      Ok(result)
+    })
+  }
+
+  /// Path to the build-info record the project's own Foundry/Hardhat-style writer produced for
+  /// this compile (enabled via `set_build_info` in `build_project`), or `None` when
+  /// `config.build_info_enabled` is unset. Unlike `write_build_info`, this doesn't write anything
+  /// itself - it just locates the file foundry-compilers already wrote under the project's
+  /// `build-info` directory.
+  fn project_build_info_path(&self, config: &CompilerConfig) -> Option<String> {
+    if !config.build_info_enabled {
+      return None;
+    }
+    latest_build_info_path(&self.context.paths.build_infos)
+      .map(|path| path.to_string_lossy().into_owned())
+  }
+
+  /// Runs `config.artifact_format`'s emitter over `result.artifacts` against the project's
+  /// artifacts directory. A no-op for the default `Foundry` format, which foundry-compilers'
+  /// project writer already handled as part of the compile itself.
+  fn emit_artifacts(&self, config: &CompilerConfig, result: &CompileOutput) -> Result<()> {
+    artifact_output::emitter_for(config.artifact_format).emit(
+      &self.context.paths.artifacts,
+      &result.artifacts,
+      config.emit_sourceless_artifacts,
+    )
+  }
+
+  /// Narrows `files` down to the ones `incremental::filter_dirty` says actually need recompiling
+  /// under `solc_version`, or returns `files` unchanged when `config.cache_enabled` is off. Used by
+  /// the `FilePaths` compile paths (single-version and auto-detected per-bucket alike) right before
+  /// handing the list to `compile_files`.
+  fn dirty_files(
+    &self,
+    config: &CompilerConfig,
+    solc_version: &Version,
+    files: &[PathBuf],
+    closure: &resolver::Graph,
+  ) -> Result<Vec<PathBuf>> {
+    if !config.cache_enabled {
+      return Ok(files.to_vec());
+    }
+    incremental::filter_dirty(
+      &default_cache_dir(),
+      &self.context.root,
+      &self.context.paths.artifacts,
+      files,
+      closure,
+      solc_version,
+      &config.solc_settings,
+    )
   }
 
   fn compile_with_project<F>(
     &self,
     config: &CompilerConfig,
+    solc_version: &Version,
     label: &str,
     compile_fn: F,
   ) -> Result<ProjectCompileOutput<MultiCompiler>>
@@ -133,7 +338,7 @@ impl<'a> ProjectRunner<'a> {
     >,
   {
     if config.language.is_solc_language() {
-      solc::ensure_installed(&config.solc_version)?;
+      solc::ensure_installed(solc_version)?;
     } else if config.language == CompilerLanguage::Vyper {
       vyper::ensure_installed(config.vyper_settings.path.clone())?;
     }
@@ -197,6 +402,440 @@ impl<'a> ProjectRunner<'a> {
 
     Ok(paths)
   }
+
+  /// Compiles a `SourceMap` whose `pragma solidity` constraints aren't all satisfied by
+  /// `config.solc_version`: partitions `sources` into groups that share a common satisfiable
+  /// version constraint, compiles each group under its own solc, and merges the results. Each
+  /// group's solc is installed on demand unless `config.offline_mode` is set, in which case
+  /// every group's version is checked up front and, if any aren't already installed, this fails
+  /// fast with one error naming all of them rather than installing (or failing on) one group at a
+  /// time. Groups are independent of one another - they share no project or solc instance - so
+  /// they're compiled concurrently, up to `config.solc_jobs` workers at a time (defaulting to the
+  /// available CPU count, same as an unset `solc_jobs` elsewhere).
+  fn compile_multi_version(
+    &self,
+    config: &CompilerConfig,
+    sources: &BTreeMap<String, String>,
+    language_override: Option<CompilerLanguage>,
+  ) -> Result<CompileOutput> {
+    let pool = candidate_versions(config.offline_mode)?;
+    let groups = partition_by_version(sources, &pool)?;
+
+    if !config.restrictions.is_empty() {
+      let resolved_versions: BTreeMap<String, Version> = groups
+        .iter()
+        .flat_map(|group| {
+          group
+            .sources
+            .iter()
+            .map(|(path, _)| (path.clone(), group.version.clone()))
+        })
+        .collect();
+      config::check_restrictions(&config.restrictions, &resolved_versions, &config.solc_settings)?;
+    }
+
+    if config.offline_mode {
+      let mut missing: Vec<&Version> = groups
+        .iter()
+        .map(|group| &group.version)
+        .filter(|version| !matches!(solc::is_version_installed(version), Ok(true)))
+        .collect();
+      missing.sort();
+      missing.dedup();
+      if !missing.is_empty() {
+        let versions = missing
+          .iter()
+          .map(|version| version.to_string())
+          .collect::<Vec<_>>()
+          .join(", ");
+        return Err(Error::new(format!(
+          "Solc version(s) {versions} are not installed and offline mode is enabled. Pre-provision the binaries or disable offline mode to install them."
+        )));
+      }
+    } else {
+      for group in &groups {
+        solc::ensure_available(&group.version, config.offline_mode)?;
+      }
+    }
+
+    let jobs = config
+      .solc_jobs
+      .filter(|&jobs| jobs > 0)
+      .unwrap_or_else(|| std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1));
+
+    let mut outputs = Vec::with_capacity(groups.len());
+    for chunk in groups.chunks(jobs.max(1)) {
+      let chunk_outputs: Vec<Result<CompileOutput>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = chunk
+          .iter()
+          .map(|group| {
+            scope.spawn(|| {
+              let files = self.write_virtual_sources(
+                config,
+                group
+                  .sources
+                  .iter()
+                  .map(|(path, contents)| VirtualSourceEntry {
+                    original_path: Some(path.as_str()),
+                    contents: contents.as_str(),
+                  }),
+                language_override,
+              )?;
+
+              let version = group.version.clone();
+              let output =
+                self.compile_with_project(config, &version, "Compilation failed", move |project| {
+                  project.compile_files(files.clone())
+                });
+              output.map(|out| {
+                into_core_compile_output(
+                  out,
+                  &config.ignored_error_codes,
+                  &config.severity_overrides,
+                  config.promote_all_warnings_to_errors,
+                )
+              })
+            })
+          })
+          .collect();
+
+        handles
+          .into_iter()
+          .map(|handle| {
+            handle
+              .join()
+              .unwrap_or_else(|_| Err(Error::new("A compilation worker thread panicked")))
+          })
+          .collect()
+      });
+
+      for output in chunk_outputs {
+        outputs.push(output?);
+      }
+    }
+
+    Ok(CompileOutput::merge(outputs))
+  }
+
+  /// Compiles the on-disk files reachable from `closure` by partitioning them into solc version
+  /// buckets by import connectivity, unlike `compile_multi_version`'s pragma-only grouping of
+  /// in-memory sources: files that import one another share a bucket (a connected component of the import graph can't be split across solc
+  /// invocations), and each bucket compiles under the highest candidate that satisfies every
+  /// pragma in it. Used in place of a single `compile_with_project` call when
+  /// `self.context.auto_detect` is set, since `build_project`'s project is configured for one solc
+  /// version and can't otherwise span files pinned to incompatible compilers.
+  fn compile_files_by_version(
+    &self,
+    config: &CompilerConfig,
+    closure: &resolver::Graph,
+  ) -> Result<CompileOutput> {
+    let pool = candidate_versions(config.offline_mode)?;
+    let groups = partition_files_by_version(closure, &pool)?;
+
+    let mut outputs = Vec::with_capacity(groups.len());
+    let mut all_clean_files = Vec::new();
+    let mut all_recompiled_files = Vec::new();
+    for group in groups {
+      let version = group.version.clone();
+      let files = self.dirty_files(config, &version, &group.files, closure)?;
+      all_clean_files.extend(clean_files(&group.files, &files));
+      if files.is_empty() {
+        continue;
+      }
+      all_recompiled_files.extend(files.iter().cloned());
+      let output =
+        self.compile_with_project(config, &version, "Compilation failed", move |project| {
+          project.compile_files(files)
+        });
+      outputs.push(output.map(|out| {
+        into_core_compile_output(
+          out,
+          &config.ignored_error_codes,
+          &config.severity_overrides,
+          config.promote_all_warnings_to_errors,
+        )
+      })?);
+    }
+
+    let mut result = CompileOutput::merge(outputs);
+    output::merge_clean_artifacts(
+      &mut result,
+      incremental::load_clean_artifacts(
+        &self.context.paths.artifacts,
+        &self.context.root,
+        &all_clean_files,
+      ),
+    );
+    incremental::record_errors(
+      &default_cache_dir(),
+      &self.context.root,
+      &all_recompiled_files,
+      &result.errors,
+    )
+    .ok();
+    result
+      .errors
+      .extend(incremental::load_clean_errors(&default_cache_dir(), &all_clean_files));
+    Ok(result)
+  }
+}
+
+/// The subset of `all` that `dirty` (already deduped and sorted by `incremental::filter_dirty`)
+/// didn't name, i.e. the files a compile can skip recompiling and instead reload from disk.
+fn clean_files(all: &[PathBuf], dirty: &[PathBuf]) -> Vec<PathBuf> {
+  let dirty: BTreeSet<&PathBuf> = dirty.iter().collect();
+  all
+    .iter()
+    .filter(|file| !dirty.contains(file))
+    .cloned()
+    .collect()
+}
+
+/// Writes a combined `{ solcVersion, input, output }` record for a solo (non-project) solc
+/// invocation into `build-info/<hash>.json` under the cache directory, mirroring the build-info
+/// files Foundry/Hardhat emit for project compiles. The hash is derived from the solc version and
+/// the exact Standard JSON input, so identical compilations reuse the same file. Returns `None`
+/// when `config.build_info_enabled` is false.
+pub(crate) fn write_build_info(
+  config: &CompilerConfig,
+  solc_version: &Version,
+  input: &SolcInput,
+  output: &CompilerOutput,
+) -> Result<Option<PathBuf>> {
+  if !config.build_info_enabled {
+    return Ok(None);
+  }
+
+  let input_json = map_err_with_context(
+    serde_json::to_value(input),
+    "Failed to serialise solc input for build info",
+  )?;
+
+  let mut hasher = DefaultHasher::new();
+  solc_version.to_string().hash(&mut hasher);
+  input_json.to_string().hash(&mut hasher);
+  let key = format!("{:016x}", hasher.finish());
+
+  let dir = default_cache_dir().join("build-info");
+  fs::create_dir_all(&dir).map_err(|err| {
+    Error::new(format!(
+      "Failed to create build-info directory {}: {err}",
+      dir.display()
+    ))
+  })?;
+
+  let path = dir.join(format!("{key}.json"));
+  if !path.exists() {
+    let record = serde_json::json!({
+      "solcVersion": solc_version.to_string(),
+      "input": input_json,
+      "output": output,
+    });
+    let serialised = map_err_with_context(
+      serde_json::to_string(&record),
+      "Failed to serialise build info",
+    )?;
+    fs::write(&path, serialised)
+      .map_err(|err| Error::new(format!("Failed to write build info {}: {err}", path.display())))?;
+  }
+
+  Ok(Some(path))
+}
+
+/// One group of sources that share a satisfiable `pragma solidity` constraint, along with the
+/// solc version chosen to compile them (the highest candidate that satisfies every source's
+/// constraint in the group).
+///
+/// Shared with `compiler::core::compile_standard_sources`, which buckets a flat `Sources` map the
+/// same way when it isn't routed through a `ProjectRunner` at all (e.g. a synthetic, cache-disabled
+/// context).
+pub(crate) struct VersionGroup {
+  pub(crate) sources: Vec<(String, String)>,
+  pub(crate) version: Version,
+}
+
+/// Whether `config.solc_version` alone satisfies every source's `pragma solidity` constraint (or
+/// the source has none). When this is true, the existing single-version compile path applies
+/// unchanged; only a real conflict routes through `ProjectRunner::compile_multi_version` (or, for
+/// sources compiled outside a project, `compiler::core::compile_standard_sources`'s own bucketing).
+pub(crate) fn config_version_satisfies_all(
+  config: &CompilerConfig,
+  sources: &BTreeMap<String, String>,
+) -> bool {
+  sources.values().all(|contents| match parse_version_pragma(contents) {
+    Some(req) => req.matches(&config.solc_version),
+    None => true,
+  })
+}
+
+/// Extracts the `pragma solidity <constraint>;` expression from a source, if present, and parses
+/// it as a `VersionReq`. Solidity pragmas separate multiple comparators with whitespace (e.g.
+/// `>=0.8.0 <0.9.0`) rather than the comma `VersionReq::parse` expects, so they're rejoined here.
+pub(crate) fn parse_version_pragma(source: &str) -> Option<VersionReq> {
+  let marker = "pragma solidity";
+  let start = source.find(marker)? + marker.len();
+  let rest = &source[start..];
+  let end = rest.find(';')?;
+  let expr = rest[..end].trim();
+  if expr.is_empty() {
+    return None;
+  }
+
+  let normalised = expr.split_whitespace().collect::<Vec<_>>().join(", ");
+  VersionReq::parse(&normalised).ok()
+}
+
+/// The versions considered when resolving a pragma constraint: everything installed, plus -
+/// unless `offline_mode` is set - everything svm knows how to install. Sorted ascending so the
+/// caller can pick the highest match.
+pub(crate) fn candidate_versions(offline_mode: bool) -> Result<Vec<Version>> {
+  let mut versions = solc::installed_versions()?;
+  if !offline_mode {
+    for version in solc::available_versions()? {
+      if !versions.contains(&version) {
+        versions.push(version);
+      }
+    }
+  }
+  versions.sort();
+  Ok(versions)
+}
+
+/// Partitions `sources` into groups whose members share at least one mutually satisfying version
+/// from `pool`, first fit in path order: each source joins the first group whose remaining
+/// candidate set it intersects, narrowing that set, or starts a new group. This does not search
+/// for the fewest possible groups. Each group's final version is the highest of what's left.
+pub(crate) fn partition_by_version(
+  sources: &BTreeMap<String, String>,
+  pool: &[Version],
+) -> Result<Vec<VersionGroup>> {
+  struct Bucket {
+    sources: Vec<(String, String)>,
+    candidates: Vec<Version>,
+  }
+
+  let mut buckets: Vec<Bucket> = Vec::new();
+
+  for (path, contents) in sources {
+    let matching: Vec<Version> = match parse_version_pragma(contents) {
+      Some(req) => pool.iter().filter(|version| req.matches(version)).cloned().collect(),
+      None => pool.to_vec(),
+    };
+    if matching.is_empty() {
+      return Err(Error::new(format!(
+        "No installed or installable solc version satisfies the pragma solidity constraint in {path}"
+      )));
+    }
+
+    let mut placed = false;
+    for bucket in buckets.iter_mut() {
+      let intersection: Vec<Version> = bucket
+        .candidates
+        .iter()
+        .filter(|version| matching.contains(version))
+        .cloned()
+        .collect();
+      if !intersection.is_empty() {
+        bucket.candidates = intersection;
+        bucket.sources.push((path.clone(), contents.clone()));
+        placed = true;
+        break;
+      }
+    }
+
+    if !placed {
+      buckets.push(Bucket {
+        sources: vec![(path.clone(), contents.clone())],
+        candidates: matching,
+      });
+    }
+  }
+
+  buckets
+    .into_iter()
+    .map(|bucket| {
+      let version = bucket.candidates.iter().max().cloned().ok_or_else(|| {
+        Error::new("Failed to resolve a solc version for a pragma-compatible source group")
+      })?;
+      Ok(VersionGroup {
+        sources: bucket.sources,
+        version,
+      })
+    })
+    .collect()
+}
+
+/// One connected component of the on-disk import graph, along with the solc version chosen to
+/// compile it (the highest candidate that satisfies every file's `pragma solidity` constraint in
+/// the component).
+struct FileVersionGroup {
+  files: Vec<PathBuf>,
+  version: Version,
+}
+
+/// Partitions every file reachable in `closure` into the connected components of its (undirected)
+/// import graph via union-find over `closure.edges()`, then resolves each component's version as
+/// the highest of `pool` that satisfies every member's `pragma solidity` constraint. A component
+/// with no pragma at all is free to use the newest candidate.
+fn partition_files_by_version(
+  closure: &resolver::Graph,
+  pool: &[Version],
+) -> Result<Vec<FileVersionGroup>> {
+  let files: Vec<PathBuf> = closure.reachable_files().into_iter().collect();
+  let mut parent: BTreeMap<PathBuf, PathBuf> =
+    files.iter().cloned().map(|file| (file.clone(), file)).collect();
+
+  fn find(parent: &mut BTreeMap<PathBuf, PathBuf>, node: &PathBuf) -> PathBuf {
+    let root = parent[node].clone();
+    if &root == node {
+      return root;
+    }
+    let found = find(parent, &root);
+    parent.insert(node.clone(), found.clone());
+    found
+  }
+
+  for (importer, imported) in closure.edges() {
+    let root_a = find(&mut parent, importer);
+    let root_b = find(&mut parent, imported);
+    if root_a != root_b {
+      parent.insert(root_a, root_b);
+    }
+  }
+
+  let mut components: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
+  for file in &files {
+    let root = find(&mut parent, file);
+    components.entry(root).or_default().push(file.clone());
+  }
+
+  components
+    .into_values()
+    .map(|component_files| {
+      let mut candidates = pool.to_vec();
+      for file in &component_files {
+        let contents = fs::read_to_string(file).map_err(|err| {
+          Error::new(format!(
+            "Failed to read {} to detect its solc version: {err}",
+            file.display()
+          ))
+        })?;
+        if let Some(req) = parse_version_pragma(&contents) {
+          candidates.retain(|version| req.matches(version));
+        }
+      }
+      let version = candidates.into_iter().max().ok_or_else(|| {
+        Error::new(
+          "No installed or installable solc version satisfies every pragma solidity constraint \
+           in a connected group of imported files",
+        )
+      })?;
+      Ok(FileVersionGroup {
+        files: component_files,
+        version,
+      })
+    })
+    .collect()
 }
 
 fn determine_extension(original_path: Option<&str>, language: CompilerLanguage) -> String {
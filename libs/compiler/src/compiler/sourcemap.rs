@@ -0,0 +1,307 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::output::SourceLocation;
+use crate::internal::source_map::{decode_compact_entries, RawJump};
+
+/// A jump instruction's direction, the `j` field of a solc compact source-map entry.
+#[napi(string_enum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JumpType {
+  In,
+  Out,
+  Regular,
+}
+
+impl From<RawJump> for JumpType {
+  fn from(jump: RawJump) -> Self {
+    match jump {
+      RawJump::In => JumpType::In,
+      RawJump::Out => JumpType::Out,
+      RawJump::Regular => JumpType::Regular,
+    }
+  }
+}
+
+/// One decoded instruction entry from solc's compact source-map string (`s:l:f:j:m`), kept at
+/// full fidelity - unlike [`SourceLocation`], which resolves `f` to a file name and collapses
+/// `s`/`l` into a byte range - for consumers (coverage instrumentation, tracers) that also need
+/// the jump type and modifier depth.
+#[napi(object)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceMapEntry {
+  pub start: u32,
+  pub length: u32,
+  /// Index into the compilation's source list, or `-1` if solc didn't attribute a source file.
+  pub file_index: i32,
+  pub jump: JumpType,
+  pub modifier_depth: u32,
+}
+
+impl Default for SourceMapEntry {
+  fn default() -> Self {
+    Self {
+      start: 0,
+      length: 0,
+      file_index: -1,
+      jump: JumpType::Regular,
+      modifier_depth: 0,
+    }
+  }
+}
+
+/// Decodes a solc compact source map - a `;`-separated list of `s:l:f:j:m` entries - into one
+/// [`SourceMapEntry`] per bytecode instruction. Any field left empty in an entry inherits its
+/// value from the previous entry, so state carries forward across the whole list; a trailing
+/// empty entry inherits everything from the one before it. The actual decode is shared with
+/// [`crate::compile::output::decode_source_map`] via
+/// [`crate::internal::source_map::decode_compact_entries`], so both wrap the same carry-forward
+/// algorithm in their own type rather than maintaining independent copies of it.
+pub fn decode_source_map_entries(compact: &str) -> Vec<SourceMapEntry> {
+  decode_compact_entries(compact)
+    .into_iter()
+    .map(|entry| SourceMapEntry {
+      start: entry.start,
+      length: entry.length,
+      file_index: entry.file_index,
+      jump: entry.jump.into(),
+      modifier_depth: entry.modifier_depth,
+    })
+    .collect()
+}
+
+/// Decodes a solc compact source map - a `;`-separated list of `s:l:f:j:m` entries (byte start
+/// offset, length, source file index, jump type, modifier depth) where a field left empty
+/// inherits the previous entry's value, and the first entry's empty fields default to `0`/`-` -
+/// into one [`SourceLocation`] per bytecode instruction. `f` is resolved to a file name through
+/// `source_index`, the `{sourceId: path}` table produced for the compilation this map came from;
+/// an index missing from that table (including solc's `-1` "no source" sentinel) resolves to an
+/// empty file name. Only `s`/`l`/`f` carry through to the returned locations - `j` and `m` only
+/// affect how later entries in the same map inherit.
+pub fn decode_source_map(
+  compact: &str,
+  source_index: &BTreeMap<u32, String>,
+) -> Vec<SourceLocation> {
+  decode_source_map_entries(compact)
+    .into_iter()
+    .map(|entry| SourceLocation {
+      file: u32::try_from(entry.file_index)
+        .ok()
+        .and_then(|index| source_index.get(&index))
+        .cloned()
+        .unwrap_or_default(),
+      start: entry.start,
+      end: entry.start + entry.length,
+    })
+    .collect()
+}
+
+/// A byte offset resolved to a human-readable position: the file it falls in, plus its 1-based
+/// line and column, both counted in bytes like solc's own source-map offsets.
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourcePosition {
+  pub file: String,
+  pub line: u32,
+  pub column: u32,
+}
+
+/// Resolves a `SourceMapEntry`'s `file_index`/`start` to a [`SourcePosition`]: the file name comes
+/// from `source_index` (the `{sourceId: path}` table `decode_source_map` also uses), and the
+/// line/column come from counting bytes up to `offset` in that file's contents as found in
+/// `sources` (keyed the same way as `source_index`'s values) - line is `1 +` the number of `\n`
+/// bytes seen, column is the distance back to the preceding `\n` (or the start of the file).
+/// Returns `None` when the file index or its contents aren't available to look up.
+pub fn resolve_position(
+  file_index: i32,
+  offset: i32,
+  source_index: &BTreeMap<u32, String>,
+  sources: &BTreeMap<String, String>,
+) -> Option<SourcePosition> {
+  let file = u32::try_from(file_index)
+    .ok()
+    .and_then(|index| source_index.get(&index))?;
+  let contents = sources.get(file)?;
+
+  let offset = offset.max(0) as usize;
+  let preceding = &contents.as_bytes()[..offset.min(contents.len())];
+  let line = 1 + preceding.iter().filter(|byte| **byte == b'\n').count() as u32;
+  let column = 1 + preceding.iter().rev().take_while(|byte| **byte != b'\n').count() as u32;
+
+  Some(SourcePosition {
+    file: file.clone(),
+    line,
+    column,
+  })
+}
+
+/// Number of immediate operand bytes `opcode` consumes: `PUSH1` (`0x60`) through `PUSH32`
+/// (`0x7f`) take `opcode - 0x5f` bytes each; every other opcode takes none.
+fn push_immediate_len(opcode: u8) -> usize {
+  if (0x60..=0x7f).contains(&opcode) {
+    (opcode - 0x5f) as usize
+  } else {
+    0
+  }
+}
+
+/// Walks `bytecode` opcode-by-opcode from the start - skipping each PUSH instruction's immediate
+/// operand bytes rather than treating them as opcodes of their own - and returns the index of the
+/// instruction that owns byte offset `pc`. That index lines up directly with
+/// [`decode_source_map_entries`]'s output, so a tracer's raw program counter resolves to a source
+/// position via `entries[instruction_index_at_pc(code, pc)?]`. Returns `None` when `pc` doesn't
+/// land on an instruction boundary (it's inside a PUSH immediate) or is past the end of the code.
+pub fn instruction_index_at_pc(bytecode: &[u8], pc: usize) -> Option<usize> {
+  let mut offset = 0usize;
+  let mut instruction_index = 0usize;
+
+  while offset < bytecode.len() {
+    if offset == pc {
+      return Some(instruction_index);
+    }
+    if offset > pc {
+      return None;
+    }
+    offset += 1 + push_immediate_len(bytecode[offset]);
+    instruction_index += 1;
+  }
+
+  None
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn empty_fields_inherit_from_the_previous_entry() {
+    let mut source_index = BTreeMap::new();
+    source_index.insert(0u32, "src/Foo.sol".to_string());
+
+    let decoded = decode_source_map("10:5:0:-:0;20:3:0;:4", &source_index);
+
+    assert_eq!(
+      decoded,
+      vec![
+        SourceLocation {
+          file: "src/Foo.sol".to_string(),
+          start: 10,
+          end: 15,
+        },
+        SourceLocation {
+          file: "src/Foo.sol".to_string(),
+          start: 20,
+          end: 23,
+        },
+        SourceLocation {
+          file: "src/Foo.sol".to_string(),
+          start: 20,
+          end: 24,
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn unresolved_file_index_falls_back_to_an_empty_file_name() {
+    let decoded = decode_source_map("0:0:-1:-:0", &BTreeMap::new());
+    assert_eq!(
+      decoded,
+      vec![SourceLocation {
+        file: String::new(),
+        start: 0,
+        end: 0,
+      }]
+    );
+  }
+
+  #[test]
+  fn empty_source_map_decodes_to_no_entries() {
+    assert!(decode_source_map("", &BTreeMap::new()).is_empty());
+  }
+
+  #[test]
+  fn entries_carry_jump_type_and_modifier_depth() {
+    let decoded = decode_source_map_entries("10:5:0:i:1;20:3:2:o:2;:::-:");
+
+    assert_eq!(
+      decoded,
+      vec![
+        SourceMapEntry {
+          start: 10,
+          length: 5,
+          file_index: 0,
+          jump: JumpType::In,
+          modifier_depth: 1,
+        },
+        SourceMapEntry {
+          start: 20,
+          length: 3,
+          file_index: 2,
+          jump: JumpType::Out,
+          modifier_depth: 2,
+        },
+        SourceMapEntry {
+          start: 20,
+          length: 3,
+          file_index: 2,
+          jump: JumpType::Regular,
+          modifier_depth: 2,
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn a_trailing_empty_entry_inherits_everything() {
+    let decoded = decode_source_map_entries("10:5:0:i:1;");
+
+    assert_eq!(decoded.last(), decoded.first());
+  }
+
+  #[test]
+  fn resolve_position_counts_lines_and_columns_in_bytes() {
+    let mut source_index = BTreeMap::new();
+    source_index.insert(0u32, "src/Foo.sol".to_string());
+    let mut sources = BTreeMap::new();
+    sources.insert("src/Foo.sol".to_string(), "contract Foo {\n  uint x;\n}".to_string());
+
+    // Offset 17 lands on the 'u' of "uint", two bytes into the second line.
+    let position = resolve_position(0, 17, &source_index, &sources).expect("position");
+    assert_eq!(
+      position,
+      SourcePosition {
+        file: "src/Foo.sol".to_string(),
+        line: 2,
+        column: 3,
+      }
+    );
+  }
+
+  #[test]
+  fn resolve_position_returns_none_for_an_unknown_file_index() {
+    let sources = BTreeMap::new();
+    assert!(resolve_position(-1, 0, &BTreeMap::new(), &sources).is_none());
+  }
+
+  #[test]
+  fn instruction_index_at_pc_skips_push_immediates() {
+    // PUSH1 0x80, PUSH1 0x40, MSTORE, PUSH2 0x0102, POP
+    let bytecode = [0x60, 0x80, 0x60, 0x40, 0x52, 0x61, 0x01, 0x02, 0x50];
+
+    assert_eq!(instruction_index_at_pc(&bytecode, 0), Some(0));
+    assert_eq!(instruction_index_at_pc(&bytecode, 2), Some(1));
+    assert_eq!(instruction_index_at_pc(&bytecode, 4), Some(2));
+    assert_eq!(instruction_index_at_pc(&bytecode, 5), Some(3));
+    assert_eq!(instruction_index_at_pc(&bytecode, 8), Some(4));
+  }
+
+  #[test]
+  fn instruction_index_at_pc_rejects_offsets_inside_a_push_immediate() {
+    let bytecode = [0x60, 0x80, 0x00];
+    assert_eq!(instruction_index_at_pc(&bytecode, 1), None);
+  }
+}
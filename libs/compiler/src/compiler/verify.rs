@@ -0,0 +1,277 @@
+use std::collections::BTreeMap;
+
+use super::core::{compile_sources, init, SourceValue};
+use super::output::{CompilerError, SeverityLevel};
+use crate::internal::config::CompilerConfig;
+use crate::internal::errors::Result;
+
+/// One `//~` expectation scanned out of a source file, modeled on compiletest's annotation
+/// scheme. `line` is already resolved from whatever caret/pipe shorthand the comment used - see
+/// `parse_expectations`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiagnosticExpectation {
+  pub file: String,
+  pub line: usize,
+  pub severity: SeverityLevel,
+  pub matcher: String,
+}
+
+impl DiagnosticExpectation {
+  fn matches(&self, error: &CompilerError) -> bool {
+    if self.severity != error.severity {
+      return false;
+    }
+    match self.matcher.parse::<i64>() {
+      Ok(code) => error.error_code == Some(code),
+      Err(_) => error.message.contains(&self.matcher),
+    }
+  }
+}
+
+/// A diagnostic the compiler emitted that no `//~` annotation in `sources` claimed.
+#[derive(Clone, Debug)]
+pub struct UnexpectedDiagnostic {
+  pub file: Option<String>,
+  pub line: Option<usize>,
+  pub severity: SeverityLevel,
+  pub message: String,
+}
+
+/// Structured diff between a compile's actual diagnostics and the `//~` expectations scanned out
+/// of its sources, returned by [`verify_diagnostics`].
+#[derive(Clone, Debug)]
+pub struct DiagnosticVerification {
+  pub unmatched_expectations: Vec<DiagnosticExpectation>,
+  pub unexpected_diagnostics: Vec<UnexpectedDiagnostic>,
+}
+
+impl DiagnosticVerification {
+  pub fn passed(&self) -> bool {
+    self.unmatched_expectations.is_empty() && self.unexpected_diagnostics.is_empty()
+  }
+}
+
+/// Compiles `sources` under `config` and checks the result against every `//~` expectation found
+/// in them, compiletest-style: `//~ ERROR <code-or-substring>` and `//~ WARNING <...>` (`WARN` is
+/// a synonym for `WARNING`) expect a diagnostic on the same line as the comment; a leading run of
+/// carets (`//~^`, `//~^^`, ...) shifts the target up one source line per caret; `//~| <...>`
+/// binds to the same target line as the annotation immediately above it, so several expectations
+/// can stack under one line without repeating carets. An expectation's matcher is satisfied by a
+/// diagnostic on the same line and of the same severity whose `error_code` equals the matcher
+/// parsed as a number, or whose message contains the matcher as a substring.
+///
+/// Diagnostics without an expectation, and expectations no diagnostic satisfied, both show up in
+/// the returned [`DiagnosticVerification`] rather than as an error - callers assert on the shape
+/// of that diff however suits them (see `DiagnosticVerification::passed` for the common case).
+pub fn verify_diagnostics(
+  config: &CompilerConfig,
+  sources: BTreeMap<String, String>,
+) -> Result<DiagnosticVerification> {
+  let mut remaining: Vec<DiagnosticExpectation> = sources
+    .iter()
+    .flat_map(|(file, contents)| parse_expectations(file, contents))
+    .collect();
+
+  let source_values = sources
+    .iter()
+    .map(|(file, contents)| (file.clone(), SourceValue::Text(contents.clone())))
+    .collect();
+
+  let state = init(config.clone(), None)?;
+  let output = compile_sources(&state, config, source_values)?;
+
+  let mut unexpected = Vec::new();
+
+  for error in &output.errors {
+    let location = match &error.source_location {
+      Some(location) => location,
+      None => {
+        unexpected.push(UnexpectedDiagnostic {
+          file: None,
+          line: None,
+          severity: error.severity,
+          message: error.message.clone(),
+        });
+        continue;
+      }
+    };
+
+    let line = sources
+      .get(&location.file)
+      .map(|source| line_number(source, location.start));
+
+    let matched = line.and_then(|line| {
+      remaining
+        .iter()
+        .position(|expectation| {
+          expectation.file == location.file
+            && expectation.line == line
+            && expectation.matches(error)
+        })
+    });
+
+    match matched {
+      Some(index) => {
+        remaining.remove(index);
+      }
+      None => unexpected.push(UnexpectedDiagnostic {
+        file: Some(location.file.clone()),
+        line,
+        severity: error.severity,
+        message: error.message.clone(),
+      }),
+    }
+  }
+
+  Ok(DiagnosticVerification {
+    unmatched_expectations: remaining,
+    unexpected_diagnostics: unexpected,
+  })
+}
+
+/// 1-based line number of the byte offset `start` within `source`.
+fn line_number(source: &str, start: i32) -> usize {
+  let start = start.max(0) as usize;
+  1 + source.as_bytes()[..start.min(source.len())]
+    .iter()
+    .filter(|byte| **byte == b'\n')
+    .count()
+}
+
+/// Scans `contents` for `//~` annotations and resolves each to the `(line, severity, matcher)` it
+/// expects, per the scheme documented on [`verify_diagnostics`].
+fn parse_expectations(file: &str, contents: &str) -> Vec<DiagnosticExpectation> {
+  let mut expectations = Vec::new();
+  let mut previous_target: Option<usize> = None;
+
+  for (index, text) in contents.lines().enumerate() {
+    let current_line = index + 1;
+    let Some(annotation) = text.split("//~").nth(1) else {
+      continue;
+    };
+
+    let (shift, rest) = if let Some(rest) = annotation.strip_prefix('|') {
+      (None, rest)
+    } else {
+      let carets = annotation.chars().take_while(|c| *c == '^').count();
+      (Some(carets), &annotation[carets..])
+    };
+
+    let target = match shift {
+      Some(carets) => current_line.saturating_sub(carets),
+      None => match previous_target {
+        Some(target) => target,
+        None => continue,
+      },
+    };
+    previous_target = Some(target);
+
+    let rest = rest.trim_start();
+    let Some((keyword, matcher)) = rest.split_once(char::is_whitespace) else {
+      continue;
+    };
+    let severity = match keyword {
+      "ERROR" => SeverityLevel::Error,
+      "WARNING" | "WARN" => SeverityLevel::Warning,
+      _ => continue,
+    };
+
+    expectations.push(DiagnosticExpectation {
+      file: file.to_string(),
+      line: target,
+      severity,
+      matcher: matcher.trim().to_string(),
+    });
+  }
+
+  expectations
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_a_same_line_expectation() {
+    let source = "uint x = 1; //~ ERROR 1234\n";
+    let expectations = parse_expectations("A.sol", source);
+    assert_eq!(expectations.len(), 1);
+    assert_eq!(expectations[0].line, 1);
+    assert_eq!(expectations[0].severity, SeverityLevel::Error);
+    assert_eq!(expectations[0].matcher, "1234");
+  }
+
+  #[test]
+  fn single_caret_shifts_the_target_up_one_line() {
+    let source = "uint x = 1;\n//~^ ERROR unused variable\n";
+    let expectations = parse_expectations("A.sol", source);
+    assert_eq!(expectations.len(), 1);
+    assert_eq!(expectations[0].line, 1);
+  }
+
+  #[test]
+  fn stacked_carets_shift_the_target_up_multiple_lines() {
+    let source = "uint x = 1;\n\n//~^^ ERROR unused variable\n";
+    let expectations = parse_expectations("A.sol", source);
+    assert_eq!(expectations.len(), 1);
+    assert_eq!(expectations[0].line, 1);
+  }
+
+  #[test]
+  fn pipe_annotation_binds_to_the_previous_targets_line() {
+    let source = "uint x = 1;\n//~^ ERROR unused variable\n//~| WARNING shadowed\n";
+    let expectations = parse_expectations("A.sol", source);
+    assert_eq!(expectations.len(), 2);
+    assert_eq!(expectations[1].line, 1);
+    assert_eq!(expectations[1].severity, SeverityLevel::Warning);
+  }
+
+  #[test]
+  fn warn_is_a_synonym_for_warning() {
+    let source = "uint x = 1; //~ WARN shadowed\n";
+    let expectations = parse_expectations("A.sol", source);
+    assert_eq!(expectations[0].severity, SeverityLevel::Warning);
+  }
+
+  #[test]
+  fn expectation_matches_by_numeric_error_code_or_message_substring() {
+    let expectation = DiagnosticExpectation {
+      file: "A.sol".to_string(),
+      line: 1,
+      severity: SeverityLevel::Error,
+      matcher: "2072".to_string(),
+    };
+    let error = CompilerError {
+      message: "Unused local variable.".to_string(),
+      formatted_message: None,
+      component: "general".to_string(),
+      severity: SeverityLevel::Error,
+      error_type: "Warning".to_string(),
+      error_code: Some(2072),
+      source_location: None,
+      secondary_source_locations: None,
+      vyper_source_location: None,
+    };
+    assert!(expectation.matches(&error));
+
+    let substring_expectation = DiagnosticExpectation {
+      matcher: "Unused local".to_string(),
+      ..expectation.clone()
+    };
+    assert!(substring_expectation.matches(&error));
+
+    let mismatched = DiagnosticExpectation {
+      matcher: "nonexistent".to_string(),
+      ..expectation
+    };
+    assert!(!mismatched.matches(&error));
+  }
+
+  #[test]
+  fn line_number_counts_preceding_newlines() {
+    let source = "line one\nline two\nline three\n";
+    assert_eq!(line_number(source, 0), 1);
+    assert_eq!(line_number(source, 9), 2);
+    assert_eq!(line_number(source, 18), 3);
+  }
+}
@@ -0,0 +1,1359 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use std::str::FromStr;
+
+use foundry_compilers::{
+  artifacts::{
+    ast::SourceUnit, remappings::Remapping as FoundryRemapping, CompilerOutput, SolcInput,
+    SolcLanguage as FoundrySolcLanguage, Source, Sources,
+  },
+  buildinfo::BuildInfo,
+  solc::{CliSettings, Solc, SolcCompiler, SolcSettings, SolcVersionedInput},
+  Project, ProjectBuilder, ProjectCompileOutput, ProjectPathsConfig,
+};
+use foundry_config::{Config as FoundryConfig, SolcReq};
+use napi::bindgen_prelude::*;
+use napi::threadsafe_function::ThreadsafeFunctionCallMode;
+use napi::{JsObject, JsUnknown};
+use semver::Version;
+use serde_json::{json, Value};
+use sha3::{Digest, Keccak256};
+
+use crate::ast::utils::{from_js_value, sanitize_ast_value};
+use crate::compile::{from_standard_json, output};
+use crate::internal::{
+  errors::{map_napi_error, napi_error},
+  options::{
+    default_compiler_settings, parse_compiler_options, CompilerOptions, SolcConfig, SolcUserOptions,
+  },
+  solc,
+};
+use crate::types::{CompileOutput, CompileProgressEvent, CompileProgressKind};
+
+/// High-level façade for compiling Solidity sources with a pre-selected solc version.
+#[napi]
+pub struct SolidityCompiler {
+  default_config: SolcConfig,
+  constructor_overrides: Option<CompilerOptions>,
+  project: Option<ProjectState>,
+}
+
+impl SolidityCompiler {
+  fn resolve_config(&self, overrides: Option<&CompilerOptions>) -> Result<SolcConfig> {
+    let mut config = self.default_config.clone();
+
+    if let Some(constructor) = &self.constructor_overrides {
+      config = config.merge(Some(constructor))?;
+    }
+
+    if let Some(project) = &self.project {
+      if let Some(inferred) = &project.inferred {
+        config = config.overlay(inferred);
+      }
+    }
+
+    config.merge(overrides)
+  }
+
+  fn compile_standard_sources(
+    &self,
+    config: SolcConfig,
+    sources: Sources,
+    language: FoundrySolcLanguage,
+  ) -> Result<CompileOutput> {
+    let solc = if config.auto_detect_solc_version {
+      let version = map_napi_error(
+        solc::resolve_version_for_batch(
+          sources.values().map(|source| source.content.as_str()),
+          config.offline_mode,
+        ),
+        "Failed to auto-detect solc version from pragma directives",
+      )?;
+      emit_progress(&config, install_event(CompileProgressKind::SolcInstallStarted, &version));
+      map_napi_error(
+        solc::ensure_available(&version, config.offline_mode),
+        "Failed to install auto-detected solc version",
+      )?;
+      emit_progress(&config, install_event(CompileProgressKind::SolcInstallFinished, &version));
+      solc::ensure_installed(&version)?
+    } else {
+      solc::ensure_installed(&config.version)?
+    };
+    let mut input = SolcInput::new(language, sources, config.settings.clone());
+    input.sanitize(&solc.version);
+
+    let output: CompilerOutput = match &config.cache_path {
+      Some(cache_path) => self.compile_with_cache(cache_path, &solc, &input)?,
+      None => map_napi_error(solc.compile_as(&input), "Solc compilation failed")?,
+    };
+    emit_progress(&config, invocation_finished_event(&solc.version, output.errors.len()));
+    let build_info_path = self.write_build_info(&config, &solc, &input, &output)?;
+    Ok(to_compile_output(
+      from_standard_json(output, artifact_selection(&config)),
+      build_info_path,
+    ))
+  }
+
+  /// Writes a Hardhat/Foundry-style build-info file for `input`/`output` when
+  /// `config.emit_build_info` is set, returning the path written so it can be attached to the
+  /// returned `CompileOutput`. The on-disk shape (`_format`, `solcVersion`, `solcLongVersion`,
+  /// `input`, `output`) matches what `infer_hardhat_config` already reads back via
+  /// `BuildInfo::read`, so a `fromHardhatRoot`-bound compiler can round-trip through its own
+  /// output. The `id`/filename reuses this file's own `cache_digest` content hash rather than
+  /// Hardhat's internal id algorithm - nothing here needs byte-for-byte compatibility with
+  /// Hardhat's own ids, only that recompiling the same input overwrites the same file.
+  fn write_build_info<I: serde::Serialize>(
+    &self,
+    config: &SolcConfig,
+    solc: &Solc,
+    input: &I,
+    output: &CompilerOutput,
+  ) -> Result<Option<String>> {
+    if !config.emit_build_info {
+      return Ok(None);
+    }
+
+    let dir = match &config.build_info_dir {
+      Some(dir) => dir.clone(),
+      None => self
+        .project
+        .as_ref()
+        .map(|project| project.paths.build_infos.clone())
+        .ok_or_else(|| {
+          napi_error("emitBuildInfo requires a buildInfoDir override or a root-bound compiler")
+        })?,
+    };
+
+    let id = cache_digest(&solc.version, input);
+    let build_info = json!({
+      "id": id,
+      "_format": "ethers-rs-sol-build-info-1",
+      "solcVersion": solc.version.to_string(),
+      "solcLongVersion": solc.version.to_string(),
+      "input": input,
+      "output": output,
+    });
+
+    fs::create_dir_all(&dir)
+      .map_err(|err| napi_error(format!("Failed to create buildInfo directory: {err}")))?;
+    let path = dir.join(format!("{id}.json"));
+    let serialized = map_napi_error(
+      serde_json::to_string_pretty(&build_info),
+      "Failed to serialize build info",
+    )?;
+    fs::write(&path, serialized)
+      .map_err(|err| napi_error(format!("Failed to write build info: {err}")))?;
+
+    Ok(Some(path.to_string_lossy().into_owned()))
+  }
+
+  /// Serves `input` from `cache_path`'s on-disk index when an earlier call already compiled the
+  /// exact same sources under the exact same `version`/settings, and records a fresh entry
+  /// otherwise. The cache key folds the source text, language, and settings together with
+  /// `version` via `cache_digest`, since `SolcInput`'s own JSON serialization already carries all
+  /// three - changing the optimizer, `evmVersion`, output selection, or solc version all produce a
+  /// different key, same as a changed source would. Unlike `compile::cache`'s project-bound index,
+  /// there's no per-file dirty/clean split here: `compile_sources`/`compile_files` hand solc one
+  /// batch per call, so the cache entry covers that whole batch rather than individual files.
+  fn compile_with_cache<I: serde::Serialize>(
+    &self,
+    cache_path: &Path,
+    solc: &Solc,
+    input: &I,
+  ) -> Result<CompilerOutput> {
+    let key = cache_digest(&solc.version, input);
+    let mut index = read_compile_cache(cache_path);
+
+    if let Some(cached) = index.get(&key) {
+      return Ok(cached.clone());
+    }
+
+    let output: CompilerOutput =
+      map_napi_error(solc.compile_as(input), "Solc compilation failed")?;
+    index.insert(key, output.clone());
+    write_compile_cache(cache_path, &index);
+    Ok(output)
+  }
+
+  /// Extends `sources` in place with every file transitively imported by its current entries, so
+  /// `compile_sources`/`compile_files` hand solc a closed source set instead of failing on a
+  /// missing import. Resolves each import in the same priority order `internal::resolver::Graph`
+  /// uses for the project-bound facade - the longest-prefix remapping match, then
+  /// `include_paths`/library roots, then a plain relative join against the importing file's own
+  /// directory - except an import already satisfied by an entry already in `sources` is left alone
+  /// rather than re-read from disk, so inline sources passed to `compileSources`/`compileFiles`
+  /// take precedence over anything with the same name on disk. Works even without a bound project,
+  /// using whatever remappings `config.settings.remappings` carries. Unlike `Graph::build`, which
+  /// silently drops an import with nowhere to resolve to, this collects every one of those so the
+  /// caller can report them together instead of failing opaquely during compilation.
+  fn resolve_import_closure(
+    &self,
+    config: &SolcConfig,
+    sources: &mut BTreeMap<String, String>,
+  ) -> Vec<String> {
+    let remappings = config.settings.remappings.clone();
+    let include_paths = self
+      .project
+      .as_ref()
+      .and_then(|project| project.cli_settings.as_ref())
+      .map(|cli| cli.include_paths.clone())
+      .unwrap_or_default();
+    let libraries = self
+      .project
+      .as_ref()
+      .map(|project| project.paths.libraries.clone())
+      .unwrap_or_default();
+
+    let mut unresolved = Vec::new();
+    let mut visited: BTreeSet<String> = BTreeSet::new();
+    let mut queue: Vec<String> = sources.keys().cloned().collect();
+
+    while let Some(path) = queue.pop() {
+      if !visited.insert(path.clone()) {
+        continue;
+      }
+      let Some(contents) = sources.get(&path).cloned() else {
+        continue;
+      };
+      emit_progress(config, source_resolved_event(&path, visited.len(), sources.len()));
+
+      for import in extract_import_targets(&contents) {
+        if resolve_against_known(&path, &import, sources, &remappings).is_some() {
+          continue;
+        }
+
+        let resolved =
+          resolve_import_on_disk(&path, &import, &remappings, &include_paths, &libraries);
+        match resolved {
+          Some(resolved_path) => {
+            let key = normalise_import_path(&resolved_path);
+            if sources.contains_key(&key) {
+              continue;
+            }
+            match fs::read_to_string(&resolved_path) {
+              Ok(imported_contents) => {
+                sources.insert(key.clone(), imported_contents);
+                queue.push(key);
+              }
+              Err(_) => unresolved.push(format!("\"{import}\" imported from \"{path}\"")),
+            }
+          }
+          None => unresolved.push(format!("\"{import}\" imported from \"{path}\"")),
+        }
+      }
+    }
+
+    unresolved
+  }
+
+  /// Compiles `sources` as several independent solc invocations instead of one, partitioning the
+  /// import graph into connected components and pinning each to the highest solc release its
+  /// combined `pragma solidity` constraints allow - so a call whose sources span more than one
+  /// solc version doesn't have to fail the way `compile_standard_sources`'s single shared version
+  /// would. Buckets compile concurrently, chunked by `ProjectState.solc_jobs` when a project is
+  /// bound (`available_parallelism()` otherwise), mirroring `compile::multi::compile_many`'s
+  /// worker-pool shape, then merge the same way `compile_many` merges its jobs.
+  fn compile_standard_sources_multi_version(
+    &self,
+    config: SolcConfig,
+    sources: BTreeMap<String, String>,
+    language: FoundrySolcLanguage,
+  ) -> Result<CompileOutput> {
+    let edges = build_import_edges(&sources, &config.settings.remappings);
+    let versions = map_napi_error(
+      solc::resolve_version_graph(&sources, &edges, config.offline_mode),
+      "Failed to resolve solc versions across the source graph",
+    )?;
+
+    let mut buckets: BTreeMap<Version, BTreeMap<String, String>> = BTreeMap::new();
+    for (path, contents) in sources {
+      let version = versions.get(&path).cloned().unwrap_or_else(|| config.version.clone());
+      buckets.entry(version).or_default().insert(path, contents);
+    }
+    let jobs: Vec<(Version, BTreeMap<String, String>)> = buckets.into_iter().collect();
+
+    let worker_count = self
+      .project
+      .as_ref()
+      .and_then(|project| project.solc_jobs)
+      .or_else(|| std::thread::available_parallelism().map(|count| count.get()).ok())
+      .unwrap_or(1)
+      .max(1);
+
+    let mut bucket_outputs: Vec<output::CoreCompileOutput> = Vec::with_capacity(jobs.len());
+    for chunk in jobs.chunks(worker_count) {
+      for (version, _) in chunk {
+        emit_progress(&config, bucket_started_event(version));
+      }
+      let chunk_outputs: Vec<Result<output::CoreCompileOutput>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = chunk
+          .iter()
+          .map(|(version, bucket_sources)| {
+            scope.spawn(|| {
+              self.compile_version_bucket(version, bucket_sources.clone(), language.clone(), &config)
+            })
+          })
+          .collect();
+        handles
+          .into_iter()
+          .map(|handle| {
+            handle.join().unwrap_or_else(|_| {
+              Err(napi_error("A multi-version compile worker thread panicked"))
+            })
+          })
+          .collect()
+      });
+      for chunk_output in chunk_outputs {
+        bucket_outputs.push(chunk_output?);
+      }
+    }
+
+    Ok(merge_bucket_outputs(bucket_outputs))
+  }
+
+  /// Compiles one version bucket of `compile_standard_sources_multi_version`: a standalone
+  /// `SolcInput` pinned to `version`, cache-aware the same way `compile_standard_sources` is.
+  fn compile_version_bucket(
+    &self,
+    version: &Version,
+    sources: BTreeMap<String, String>,
+    language: FoundrySolcLanguage,
+    config: &SolcConfig,
+  ) -> Result<output::CoreCompileOutput> {
+    let solc = solc::ensure_installed(version)?;
+    let mut input = SolcInput::new(language, sources_from_map(sources), config.settings.clone());
+    input.sanitize(&solc.version);
+
+    let output: CompilerOutput = match &config.cache_path {
+      Some(cache_path) => self.compile_with_cache(cache_path, &solc, &input)?,
+      None => map_napi_error(solc.compile_as(&input), "Solc compilation failed")?,
+    };
+    emit_progress(config, invocation_finished_event(&solc.version, output.errors.len()));
+    Ok(output::from_standard_json(output, artifact_selection(config)))
+  }
+
+  fn compile_ast_sources(
+    &self,
+    config: SolcConfig,
+    ast_sources: BTreeMap<String, SourceUnit>,
+  ) -> Result<CompileOutput> {
+    let solc = solc::ensure_installed(&config.version)?;
+    let settings_value = map_napi_error(
+      serde_json::to_value(&config.settings),
+      "Failed to serialize settings",
+    )?;
+
+    let mut sources_value = serde_json::Map::new();
+    for (file_name, unit) in ast_sources {
+      let mut ast_value =
+        map_napi_error(serde_json::to_value(&unit), "Failed to serialise AST value")?;
+      sanitize_ast_value(&mut ast_value);
+      sources_value.insert(file_name, json!({ "ast": ast_value }));
+    }
+
+    let input = json!({
+      "language": "SolidityAST",
+      "sources": sources_value,
+      "settings": settings_value
+    });
+
+    let output: CompilerOutput = match &config.cache_path {
+      Some(cache_path) => self.compile_with_cache(cache_path, &solc, &input)?,
+      None => map_napi_error(solc.compile_as(&input), "Solc compilation failed")?,
+    };
+    emit_progress(&config, invocation_finished_event(&solc.version, output.errors.len()));
+    let build_info_path = self.write_build_info(&config, &solc, &input, &output)?;
+    Ok(to_compile_output(
+      from_standard_json(output, artifact_selection(&config)),
+      build_info_path,
+    ))
+  }
+
+  fn compile_with_project<F>(
+    &self,
+    config: SolcConfig,
+    compile_fn: F,
+    context: &str,
+  ) -> Result<CompileOutput>
+  where
+    F: FnOnce(
+      &Project<SolcCompiler>,
+    ) -> std::result::Result<
+      ProjectCompileOutput<SolcCompiler>,
+      foundry_compilers::error::SolcError,
+    >,
+  {
+    let state = self
+      .project
+      .as_ref()
+      .ok_or_else(|| napi_error("Project-aware compilation requires a root-bound compiler"))?;
+
+    solc::ensure_installed(&config.version)?;
+
+    let project = map_napi_error(
+      state.build_project(&config),
+      "Failed to configure Solidity project",
+    )?;
+    let output = map_napi_error(compile_fn(&project), context)?;
+
+    Ok(output::into_compile_output(output))
+  }
+}
+
+#[derive(Clone, Copy)]
+enum ProjectLayout {
+  Hardhat,
+  Foundry,
+}
+
+struct ProjectState {
+  _layout: ProjectLayout,
+  _root: PathBuf,
+  paths: ProjectPathsConfig<FoundrySolcLanguage>,
+  cached: bool,
+  offline: bool,
+  no_artifacts: bool,
+  solc_jobs: Option<usize>,
+  cli_settings: Option<CliSettings>,
+  inferred: Option<SolcConfig>,
+}
+
+impl ProjectState {
+  fn build_project(
+    &self,
+    config: &SolcConfig,
+  ) -> std::result::Result<Project<SolcCompiler>, foundry_compilers::error::SolcError> {
+    let mut builder = ProjectBuilder::default().paths(self.paths.clone());
+
+    if !self.cached {
+      builder = builder.set_cached(false);
+    }
+    if self.offline {
+      builder = builder.set_offline(true);
+    }
+    if self.no_artifacts {
+      builder = builder.set_no_artifacts(true);
+    }
+    if let Some(jobs) = self.solc_jobs {
+      if jobs == 1 {
+        builder = builder.single_solc_jobs();
+      } else {
+        builder = builder.solc_jobs(jobs);
+      }
+    }
+
+    let cli_settings = self.cli_settings.clone().unwrap_or_default();
+    let settings = SolcSettings {
+      settings: config.settings.clone(),
+      cli_settings,
+    };
+
+    builder.settings(settings).build(SolcCompiler::default())
+  }
+}
+
+fn build_foundry_state(root: &Path, base_config: &SolcConfig) -> Result<ProjectState> {
+  let figment = FoundryConfig::figment_with_root(root);
+  let config = map_napi_error(
+    FoundryConfig::try_from(figment),
+    "Failed to load foundry configuration",
+  )?
+  .sanitized()
+  .canonic();
+
+  let config_paths = config.project_paths();
+  let remappings: Vec<FoundryRemapping> = config_paths
+    .remappings
+    .iter()
+    .filter_map(|remapping| FoundryRemapping::from_str(&remapping.to_string()).ok())
+    .collect();
+
+  let paths_builder = ProjectPathsConfig::builder()
+    .root(config_paths.root.clone())
+    .cache(config_paths.cache.clone())
+    .artifacts(config_paths.artifacts.clone())
+    .build_infos(config_paths.build_infos.clone())
+    .sources(config_paths.sources.clone())
+    .tests(config_paths.tests.clone())
+    .scripts(config_paths.scripts.clone())
+    .libs(config_paths.libraries.clone())
+    .remappings(remappings);
+
+  let mut paths =
+    paths_builder.build_with_root::<FoundrySolcLanguage>(config_paths.root.clone());
+  paths.slash_paths();
+  let ethers_settings = map_napi_error(
+    config.solc_settings(),
+    "Failed to derive foundry compiler settings",
+  )?;
+  let settings_json = map_napi_error(
+    serde_json::to_value(&ethers_settings),
+    "Failed to serialise foundry compiler settings",
+  )?;
+  let settings: foundry_compilers::artifacts::Settings = map_napi_error(
+    serde_json::from_value(settings_json),
+    "Failed to convert foundry compiler settings",
+  )?;
+
+  let version = config
+    .solc
+    .as_ref()
+    .and_then(|req| match req {
+      SolcReq::Version(version) => Some(version.clone()),
+      _ => None,
+    })
+    .unwrap_or_else(|| base_config.version.clone());
+
+  let inferred = SolcConfig {
+    version,
+    settings,
+    language: FoundrySolcLanguage::Solidity,
+    auto_detect_solc_version: base_config.auto_detect_solc_version,
+    offline_mode: base_config.offline_mode,
+    cache_path: base_config.cache_path.clone(),
+    multi_version: base_config.multi_version,
+    decode_source_maps: base_config.decode_source_maps,
+    emit_build_info: base_config.emit_build_info,
+    build_info_dir: base_config.build_info_dir.clone(),
+    on_progress: base_config.on_progress.clone(),
+  };
+
+  Ok(ProjectState {
+    _layout: ProjectLayout::Foundry,
+    _root: paths.root.clone(),
+    paths,
+    cached: config.cache,
+    offline: config.offline,
+    no_artifacts: false,
+    solc_jobs: None,
+    cli_settings: Some(CliSettings {
+      extra_args: Vec::new(),
+      allow_paths: config
+        .allow_paths
+        .iter()
+        .cloned()
+        .chain(std::iter::once(config.__root.0.clone()))
+        .collect::<BTreeSet<_>>(),
+      base_path: Some(config.__root.0.clone()),
+      include_paths: config
+        .include_paths
+        .iter()
+        .cloned()
+        .collect::<BTreeSet<_>>(),
+    }),
+    inferred: Some(inferred),
+  })
+}
+
+fn build_hardhat_state(root: &Path, _base_config: &SolcConfig) -> Result<ProjectState> {
+  let mut paths = map_napi_error(
+    ProjectPathsConfig::hardhat(root),
+    "Failed to create hardhat project paths",
+  )?;
+  paths.slash_paths();
+
+  let inferred = infer_hardhat_config(&paths);
+
+  Ok(ProjectState {
+    _layout: ProjectLayout::Hardhat,
+    _root: paths.root.clone(),
+    paths,
+    cached: true,
+    offline: false,
+    no_artifacts: false,
+    solc_jobs: None,
+    cli_settings: inferred.as_ref().map(|(_, cli)| cli.clone()),
+    inferred: inferred.map(|(config, _)| config),
+  })
+}
+
+fn infer_hardhat_config(
+  paths: &ProjectPathsConfig<FoundrySolcLanguage>,
+) -> Option<(SolcConfig, CliSettings)> {
+  let entries = fs::read_dir(&paths.build_infos).ok()?;
+  let mut latest: Option<(SystemTime, PathBuf)> = None;
+
+  for entry in entries.flatten() {
+    let file_type = entry.file_type().ok()?;
+    if !file_type.is_file() {
+      continue;
+    }
+
+    if entry
+      .path()
+      .extension()
+      .and_then(|ext| ext.to_str())
+      .map(|ext| ext != "json")
+      .unwrap_or(true)
+    {
+      continue;
+    }
+
+    let modified = entry
+      .metadata()
+      .and_then(|meta| meta.modified())
+      .unwrap_or(SystemTime::UNIX_EPOCH);
+
+    match &mut latest {
+      Some((time, path)) => {
+        if modified > *time {
+          *time = modified;
+          *path = entry.path();
+        }
+      }
+      None => latest = Some((modified, entry.path())),
+    }
+  }
+
+  let (_, path) = latest?;
+
+  let build_info: BuildInfo<SolcVersionedInput, CompilerOutput> =
+    BuildInfo::read(&path).ok()?;
+
+  let inferred = SolcConfig {
+    version: build_info.solc_version.clone(),
+    settings: build_info.input.input.settings.clone(),
+    language: build_info.input.input.language,
+    auto_detect_solc_version: false,
+    offline_mode: false,
+    cache_path: None,
+    multi_version: false,
+    decode_source_maps: false,
+    emit_build_info: false,
+    build_info_dir: None,
+    on_progress: None,
+  };
+
+  Some((inferred, build_info.input.cli_settings.clone()))
+}
+
+/// Static helpers and configurable entry points exposed to JavaScript.
+#[napi]
+impl SolidityCompiler {
+  /// Download and cache the specified solc release via Foundry's SVM backend.
+  ///
+  /// Returns a Bun-friendly `AsyncTask` that resolves when the toolchain is
+  /// ready. If the release is already cached, the task resolves immediately.
+  /// Parsing errors and installation failures surface as JavaScript exceptions.
+  #[napi]
+  pub fn install_solc_version(version: String) -> Result<AsyncTask<solc::InstallSolcTask>> {
+    let parsed = solc::parse_version(&version)?;
+    Ok(solc::install_async(parsed))
+  }
+
+  /// Determine whether a specific solc release is already present in the local SVM cache.
+  ///
+  /// This helper never triggers downloads; it simply probes the cache, making it
+  /// suitable for test suites to fail fast when prerequisites are missing.
+  #[napi]
+  pub fn is_solc_version_installed(version: String) -> Result<bool> {
+    let parsed = solc::parse_version(&version)?;
+    solc::is_version_installed(&parsed)
+  }
+
+  /// Construct a compiler bound to a solc version and default compiler settings.
+  ///
+  /// Passing `solcVersion` is optional – when omitted, the default
+  /// `DEFAULT_SOLC_VERSION` is enforced. The constructor validates that the
+  /// requested version is already present; callers should invoke
+  /// `installSolcVersion` ahead of time. Optional `settings` are parsed exactly
+  /// once and cached for subsequent compilations.
+  #[napi(constructor, ts_args_type = "options?: CompilerOptions | undefined")]
+  pub fn new(env: Env, options: Option<JsUnknown>) -> Result<Self> {
+    let parsed = parse_compiler_options(&env, options)?;
+    let default_settings = default_compiler_settings();
+    let default_language = solc::default_language();
+    let default_config =
+      SolcConfig::new::<CompilerOptions>(&default_language, &default_settings, None)?;
+
+    let constructor_overrides = parsed;
+    let effective_config = default_config.merge(constructor_overrides.as_ref())?;
+
+    solc::ensure_installed(&effective_config.version)?;
+
+    Ok(SolidityCompiler {
+      default_config,
+      constructor_overrides,
+      project: None,
+    })
+  }
+
+  #[napi(
+    factory,
+    ts_args_type = "root: string, options?: CompilerOptions | undefined"
+  )]
+  pub fn from_foundry_root(env: Env, root: String, options: Option<JsUnknown>) -> Result<Self> {
+    let parsed = parse_compiler_options(&env, options)?;
+    let default_settings = default_compiler_settings();
+    let default_language = solc::default_language();
+    let default_config =
+      SolcConfig::new::<CompilerOptions>(&default_language, &default_settings, None)?;
+    let constructor_overrides = parsed;
+    let effective_config = default_config.merge(constructor_overrides.as_ref())?;
+
+    solc::ensure_installed(&effective_config.version)?;
+
+    let root_path = PathBuf::from(&root);
+    let state = build_foundry_state(&root_path, &effective_config)?;
+
+    if let Some(inferred) = &state.inferred {
+      solc::ensure_installed(&inferred.version)?;
+    }
+
+    Ok(SolidityCompiler {
+      default_config,
+      constructor_overrides,
+      project: Some(state),
+    })
+  }
+
+  #[napi(
+    factory,
+    ts_args_type = "root: string, options?: CompilerOptions | undefined"
+  )]
+  pub fn from_hardhat_root(env: Env, root: String, options: Option<JsUnknown>) -> Result<Self> {
+    let parsed = parse_compiler_options(&env, options)?;
+    let default_settings = default_compiler_settings();
+    let default_language = solc::default_language();
+    let default_config =
+      SolcConfig::new::<CompilerOptions>(&default_language, &default_settings, None)?;
+    let constructor_overrides = parsed;
+    let effective_config = default_config.merge(constructor_overrides.as_ref())?;
+
+    solc::ensure_installed(&effective_config.version)?;
+
+    let root_path = PathBuf::from(&root);
+    let state = build_hardhat_state(&root_path, &effective_config)?;
+
+    if let Some(inferred) = &state.inferred {
+      solc::ensure_installed(&inferred.version)?;
+    }
+
+    Ok(SolidityCompiler {
+      default_config,
+      constructor_overrides,
+      project: Some(state),
+    })
+  }
+
+  /// Compile Solidity/Yul source text or a pre-existing AST using the configured solc version.
+  ///
+  /// - When `target` is a string, the optional `solcLanguage` controls whether it is treated as
+  ///   Solidity (default) or Yul.
+  /// - Passing an object is interpreted as a Solidity AST and compiled directly.
+  /// - `options` allows per-call overrides that merge on top of the constructor defaults.
+  ///
+  /// The return value mirrors Foundry's standard JSON output and includes ABI,
+  /// bytecode, deployed bytecode and any solc diagnostics.
+  #[napi(ts_args_type = "target: string | object, options?: CompilerOptions | undefined")]
+  pub fn compile_source(
+    &self,
+    env: Env,
+    target: Either<String, JsObject>,
+    options: Option<JsUnknown>,
+  ) -> Result<CompileOutput> {
+    let parsed = parse_compiler_options(&env, options)?;
+    let mut config = self.resolve_config(parsed.as_ref())?;
+    let input = match target {
+      Either::A(source) => CompileInput::Source(single_virtual_source(source)),
+      Either::B(object) => {
+        let ast_unit: SourceUnit = env.from_js_value(object.into_unknown())?;
+        CompileInput::Ast(single_virtual_ast(ast_unit))
+      }
+    };
+
+    match input {
+      CompileInput::Source(source) => match config.language {
+        FoundrySolcLanguage::Solidity => {
+          self.compile_standard_sources(config, source, FoundrySolcLanguage::Solidity)
+        }
+        FoundrySolcLanguage::Yul => {
+          self.compile_standard_sources(config, source, FoundrySolcLanguage::Yul)
+        }
+        other => {
+          let _ = source;
+          Err(napi_error(format!(
+            "Unsupported solcLanguage \"{other:?}\" for inline sources"
+          )))
+        }
+      },
+      CompileInput::Ast(ast_sources) => {
+        config.language = FoundrySolcLanguage::Solidity;
+        self.compile_ast_sources(config, ast_sources)
+      }
+    }
+  }
+
+  /// Compile multiple sources supplied as a path keyed lookup.
+  #[napi(
+    ts_args_type = "sources: Record<string, string | object>, options?: CompilerOptions | undefined"
+  )]
+  pub fn compile_sources(
+    &self,
+    env: Env,
+    sources: JsObject,
+    options: Option<JsUnknown>,
+  ) -> Result<CompileOutput> {
+    let parsed = parse_compiler_options(&env, options)?;
+    let config = self.resolve_config(parsed.as_ref())?;
+
+    let raw_entries: BTreeMap<String, Value> =
+      from_js_value(&env, sources.into_unknown()).map_err(|err| napi_error(err.to_string()))?;
+    if raw_entries.is_empty() {
+      return Err(napi_error("compileSources requires at least one entry."));
+    }
+
+    let mut string_entries: BTreeMap<String, String> = BTreeMap::new();
+    let mut ast_entries: BTreeMap<String, SourceUnit> = BTreeMap::new();
+
+    for (path, value) in raw_entries {
+      match value {
+        Value::String(source) => {
+          string_entries.insert(path, source);
+        }
+        Value::Object(_) => {
+          let unit: SourceUnit =
+            map_napi_error(serde_json::from_value(value), "Failed to parse AST entry")?;
+          ast_entries.insert(path, unit);
+        }
+        _ => {
+          return Err(napi_error(
+            "compileSources expects each entry to be a Solidity/Yul source string or a Solidity AST object.",
+          ));
+        }
+      }
+    }
+
+    if !string_entries.is_empty() && !ast_entries.is_empty() {
+      return Err(napi_error(
+        "compileSources does not support mixing inline source strings with AST entries in the same call.",
+      ));
+    }
+
+    if !ast_entries.is_empty() {
+      let mut ast_config = config;
+      ast_config.language = FoundrySolcLanguage::Solidity;
+      return self.compile_ast_sources(ast_config, ast_entries);
+    }
+
+    let final_config = config;
+    let unresolved = self.resolve_import_closure(&final_config, &mut string_entries);
+    if !unresolved.is_empty() {
+      return Err(napi_error(format!(
+        "compileSources could not resolve the following imports: {}",
+        unresolved.join(", ")
+      )));
+    }
+
+    if final_config.multi_version {
+      if !matches!(
+        final_config.language,
+        FoundrySolcLanguage::Solidity | FoundrySolcLanguage::Yul
+      ) {
+        return Err(napi_error(format!(
+          "Unsupported solcLanguage \"{:?}\" for compileSources.",
+          final_config.language
+        )));
+      }
+      let language = final_config.language.clone();
+      return self.compile_standard_sources_multi_version(final_config, string_entries, language);
+    }
+
+    let sources = sources_from_map(string_entries);
+    match final_config.language {
+      FoundrySolcLanguage::Solidity => {
+        self.compile_standard_sources(final_config, sources, FoundrySolcLanguage::Solidity)
+      }
+      FoundrySolcLanguage::Yul => {
+        self.compile_standard_sources(final_config, sources, FoundrySolcLanguage::Yul)
+      }
+      other => Err(napi_error(format!(
+        "Unsupported solcLanguage \"{other:?}\" for compileSources."
+      ))),
+    }
+  }
+
+  /// Compile sources from on-disk files identified by their paths.
+  #[napi(ts_args_type = "paths: string[], options?: CompilerOptions | undefined")]
+  pub fn compile_files(
+    &self,
+    env: Env,
+    paths: Vec<String>,
+    options: Option<JsUnknown>,
+  ) -> Result<CompileOutput> {
+    if paths.is_empty() {
+      return Err(napi_error("compileFiles requires at least one path."));
+    }
+
+    let parsed = parse_compiler_options(&env, options)?;
+    let explicit_language = parsed
+      .as_ref()
+      .and_then(|opts| opts.solc_language())
+      .map(FoundrySolcLanguage::from);
+    let mut config = self.resolve_config(parsed.as_ref())?;
+
+    let mut string_entries: BTreeMap<String, String> = BTreeMap::new();
+    let mut ast_entries: BTreeMap<String, SourceUnit> = BTreeMap::new();
+    let mut detected_language: Option<FoundrySolcLanguage> = None;
+
+    for original in paths {
+      let content = map_napi_error(fs::read_to_string(&original), "Failed to read source file")?;
+      let canonical = fs::canonicalize(&original)
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| original.clone());
+
+      let extension = Path::new(&original)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+      let trimmed = content.trim_start();
+      let maybe_json = trimmed.starts_with('{');
+
+      if matches!(extension.as_deref(), Some("json")) {
+        if !maybe_json {
+          return Err(napi_error(
+            "JSON sources must contain a Solidity AST object.",
+          ));
+        }
+        let value: Value =
+          map_napi_error(serde_json::from_str(&content), "Failed to parse JSON input")?;
+        if !value.is_object() {
+          return Err(napi_error(
+            "JSON sources must contain a Solidity AST object.",
+          ));
+        }
+        let unit: SourceUnit =
+          map_napi_error(serde_json::from_value(value), "Failed to parse AST entry")?;
+        ast_entries.insert(canonical.clone(), unit);
+        continue;
+      }
+
+      let recognized_source_extension = matches!(extension.as_deref(), Some("sol") | Some("yul"));
+      if !recognized_source_extension && maybe_json {
+        let value: Value =
+          map_napi_error(serde_json::from_str(&content), "Failed to parse JSON input")?;
+        if value.is_object() {
+          let unit: SourceUnit =
+            map_napi_error(serde_json::from_value(value), "Failed to parse AST entry")?;
+          ast_entries.insert(canonical.clone(), unit);
+          continue;
+        }
+      }
+
+      string_entries.insert(canonical.clone(), content);
+
+      if explicit_language.is_none() {
+        let language = match extension.as_deref() {
+          Some("sol") => FoundrySolcLanguage::Solidity,
+          Some("yul") => FoundrySolcLanguage::Yul,
+          _ => {
+            return Err(napi_error(format!(
+              "Unable to infer solc language for \"{canonical}\". Provide solcLanguage explicitly.",
+            )));
+          }
+        };
+
+        if let Some(existing) = detected_language {
+          if existing != language {
+            return Err(napi_error(
+              "compileFiles requires all non-AST sources to share the same solc language. Provide solcLanguage explicitly to disambiguate.",
+            ));
+          }
+        } else {
+          detected_language = Some(language);
+        }
+      }
+
+    }
+
+    if !ast_entries.is_empty() {
+      if !string_entries.is_empty() {
+        return Err(napi_error(
+          "compileFiles does not support mixing AST entries with source files. Split the call per input type.",
+        ));
+      }
+      config.language = FoundrySolcLanguage::Solidity;
+      return self.compile_ast_sources(config, ast_entries);
+    }
+
+    let final_language = explicit_language
+      .or(detected_language)
+      .unwrap_or(FoundrySolcLanguage::Solidity);
+
+    config.language = final_language;
+
+    let unresolved = self.resolve_import_closure(&config, &mut string_entries);
+    if !unresolved.is_empty() {
+      return Err(napi_error(format!(
+        "compileFiles could not resolve the following imports: {}",
+        unresolved.join(", ")
+      )));
+    }
+
+    if config.multi_version {
+      return self.compile_standard_sources_multi_version(config, string_entries, final_language);
+    }
+
+    let sources = sources_from_map(string_entries);
+    self.compile_standard_sources(config, sources, final_language)
+  }
+
+  #[napi]
+  pub fn compile_project(
+    &self,
+    env: Env,
+    options: Option<JsUnknown>,
+  ) -> Result<CompileOutput> {
+    let parsed = parse_compiler_options(&env, options)?;
+    let config = self.resolve_config(parsed.as_ref())?;
+
+    self.compile_with_project(config, |project| project.compile(), "Project compilation failed")
+  }
+
+  #[napi]
+  pub fn compile_contract(
+    &self,
+    env: Env,
+    contract_name: String,
+    options: Option<JsUnknown>,
+  ) -> Result<CompileOutput> {
+    let parsed = parse_compiler_options(&env, options)?;
+    let config = self.resolve_config(parsed.as_ref())?;
+    let name = contract_name.clone();
+
+    self.compile_with_project(
+      config,
+      move |project| {
+        let path = project.find_contract_path(&name)?;
+        project.compile_file(path)
+      },
+      "Contract compilation failed",
+    )
+  }
+}
+
+enum CompileInput {
+  Source(Sources),
+  Ast(BTreeMap<String, SourceUnit>),
+}
+
+const VIRTUAL_SOURCE_PATH: &str = "__VIRTUAL__.sol";
+
+fn single_virtual_source(source: String) -> Sources {
+  let path = PathBuf::from(VIRTUAL_SOURCE_PATH);
+  let mut sources = Sources::new();
+  sources.insert(path, Source::new(source));
+  sources
+}
+
+fn single_virtual_ast(ast: SourceUnit) -> BTreeMap<String, SourceUnit> {
+  let mut sources = BTreeMap::new();
+  sources.insert(VIRTUAL_SOURCE_PATH.to_string(), ast);
+  sources
+}
+
+/// Gates `from_standard_json`'s decode work on `config.decode_source_maps` - the full AST isn't
+/// offered through this facade's `CompileOutput`, so `ast` always stays off.
+fn artifact_selection(config: &SolcConfig) -> output::ArtifactSelection {
+  output::ArtifactSelection {
+    source_maps: config.decode_source_maps,
+    ast: false,
+  }
+}
+
+/// Delivers `event` to `config.on_progress` when one is registered, otherwise a no-op. Callers
+/// build `event` inline at the call site rather than through a closure, since every event here is
+/// cheap (a handful of scalar fields) and not worth deferring construction for.
+fn emit_progress(config: &SolcConfig, event: CompileProgressEvent) {
+  if let Some(callback) = &config.on_progress {
+    callback.call(event, ThreadsafeFunctionCallMode::NonBlocking);
+  }
+}
+
+fn install_event(kind: CompileProgressKind, version: &Version) -> CompileProgressEvent {
+  CompileProgressEvent {
+    kind,
+    solc_version: Some(version.to_string()),
+    source_path: None,
+    total: None,
+    completed: None,
+    diagnostic_count: None,
+  }
+}
+
+fn bucket_started_event(version: &Version) -> CompileProgressEvent {
+  CompileProgressEvent {
+    kind: CompileProgressKind::VersionBucketStarted,
+    solc_version: Some(version.to_string()),
+    source_path: None,
+    total: None,
+    completed: None,
+    diagnostic_count: None,
+  }
+}
+
+fn source_resolved_event(path: &str, completed: usize, total: usize) -> CompileProgressEvent {
+  CompileProgressEvent {
+    kind: CompileProgressKind::SourceResolved,
+    solc_version: None,
+    source_path: Some(path.to_string()),
+    total: Some(total as u32),
+    completed: Some(completed as u32),
+    diagnostic_count: None,
+  }
+}
+
+fn invocation_finished_event(version: &Version, diagnostic_count: usize) -> CompileProgressEvent {
+  CompileProgressEvent {
+    kind: CompileProgressKind::SolcInvocationFinished,
+    solc_version: Some(version.to_string()),
+    source_path: None,
+    total: None,
+    completed: None,
+    diagnostic_count: Some(diagnostic_count as u32),
+  }
+}
+
+/// Converts `core` into the napi-facing `CompileOutput`, attaching `build_info_path` - mirrors
+/// `merge_bucket_outputs`/`compile::multi::merge_job_outputs`'s same `CoreCompileOutput` ->
+/// `CompileOutput` conversion, for a single compile rather than a merge across several.
+fn to_compile_output(core: output::CoreCompileOutput, build_info_path: Option<String>) -> CompileOutput {
+  let (model_checker_diagnostics, errors): (Vec<_>, Vec<_>) =
+    core.errors.into_iter().partition(output::is_model_checker_diagnostic);
+
+  CompileOutput {
+    artifacts: core.artifacts.into_iter().map(output::to_types_contract_artifact).collect(),
+    errors: errors.into_iter().map(output::to_types_compiler_error).collect(),
+    model_checker_diagnostics: model_checker_diagnostics
+      .into_iter()
+      .map(output::to_types_model_checker_diagnostic)
+      .collect(),
+    has_compiler_errors: core.has_compiler_errors,
+    source_list: core.source_list,
+    standalone_sources: Vec::new(),
+    cached: false,
+    build_info_path,
+  }
+}
+
+fn sources_from_map(entries: BTreeMap<String, String>) -> Sources {
+  let mut sources = Sources::new();
+  for (path, source) in entries {
+    sources.insert(PathBuf::from(path), Source::new(source));
+  }
+  sources
+}
+
+/// Key identifying one cached compile result: the solc `version` plus `input`'s own JSON
+/// serialization, which already carries the sources (or AST), language, and settings a cache needs
+/// to be sensitive to - a changed source, a changed optimizer/evmVersion/outputSelection setting,
+/// or a different solc version all produce a different key.
+fn cache_digest<I: serde::Serialize>(version: &Version, input: &I) -> String {
+  let serialized = serde_json::to_string(input).unwrap_or_default();
+  hex::encode(Keccak256::digest(format!("{version}:{serialized}").as_bytes()))
+}
+
+fn read_compile_cache(cache_path: &Path) -> BTreeMap<String, CompilerOutput> {
+  fs::read_to_string(cache_path)
+    .ok()
+    .and_then(|contents| serde_json::from_str(&contents).ok())
+    .unwrap_or_default()
+}
+
+fn write_compile_cache(cache_path: &Path, index: &BTreeMap<String, CompilerOutput>) {
+  if let Some(parent) = cache_path.parent() {
+    let _ = fs::create_dir_all(parent);
+  }
+  if let Ok(serialized) = serde_json::to_string(index) {
+    let _ = fs::write(cache_path, serialized);
+  }
+}
+
+/// Builds the "path -> the paths it imports" edge map `solc::resolve_version_graph` partitions
+/// into connected components, by re-resolving each file's imports against `sources` the same way
+/// `resolve_import_closure` does. Called only after the closure has already pulled in every
+/// transitive import, so every edge here resolves to an entry already present in `sources`.
+fn build_import_edges(
+  sources: &BTreeMap<String, String>,
+  remappings: &[FoundryRemapping],
+) -> BTreeMap<String, Vec<String>> {
+  let mut edges: BTreeMap<String, Vec<String>> = BTreeMap::new();
+  for (path, contents) in sources {
+    let imports = extract_import_targets(contents)
+      .into_iter()
+      .filter_map(|import| resolve_against_known(path, &import, sources, remappings))
+      .collect();
+    edges.insert(path.clone(), imports);
+  }
+  edges
+}
+
+/// Combines every version bucket's `CoreCompileOutput` into the single result
+/// `compile_standard_sources_multi_version` returns - artifacts deduplicated by `contract_name`
+/// (first bucket wins, by bucket order rather than completion order, so a chunked worker pool's
+/// result stays deterministic), `errors` concatenated in bucket order, mirroring
+/// `compile::multi::merge_job_outputs`.
+fn merge_bucket_outputs(outputs: Vec<output::CoreCompileOutput>) -> CompileOutput {
+  let mut by_name: BTreeMap<String, output::CoreContractArtifact> = BTreeMap::new();
+  let mut errors = Vec::new();
+  let mut has_compiler_errors = false;
+  let mut source_list = Vec::new();
+
+  for bucket_output in outputs {
+    has_compiler_errors = has_compiler_errors || bucket_output.has_compiler_errors;
+    errors.extend(bucket_output.errors);
+    if source_list.is_empty() {
+      source_list = bucket_output.source_list;
+    }
+    for artifact in bucket_output.artifacts {
+      by_name.entry(artifact.contract_name.clone()).or_insert(artifact);
+    }
+  }
+
+  let (model_checker_diagnostics, errors): (Vec<_>, Vec<_>) =
+    errors.into_iter().partition(output::is_model_checker_diagnostic);
+
+  CompileOutput {
+    artifacts: by_name
+      .into_values()
+      .map(output::to_types_contract_artifact)
+      .collect(),
+    errors: errors.into_iter().map(output::to_types_compiler_error).collect(),
+    model_checker_diagnostics: model_checker_diagnostics
+      .into_iter()
+      .map(output::to_types_model_checker_diagnostic)
+      .collect(),
+    has_compiler_errors,
+    source_list,
+    // No single bucket's compile spans every other bucket, and rehydrating standalone sources
+    // across buckets isn't something any caller of the multi-version mode has asked for yet.
+    // Build-info emission is similarly out of scope here - `write_build_info` assumes one solc
+    // input/output pair, not several independently-versioned buckets merged together.
+    standalone_sources: Vec::new(),
+    cached: false,
+    build_info_path: None,
+  }
+}
+
+/// Scans `contents` for `import "..."` / `import {...} from "..."` statements and returns the
+/// quoted import target of each one, in source order. Duplicated from
+/// `compiler::graph::extract_imports` rather than shared, since that function lives in the
+/// unrelated project-bound `Compiler` facade under `compiler/`.
+fn extract_import_targets(contents: &str) -> Vec<String> {
+  let mut imports = Vec::new();
+  let mut rest = contents;
+  while let Some(start) = rest.find("import") {
+    let after_keyword = &rest[start + "import".len()..];
+    if let Some(quote_start) = after_keyword
+      .find(['"', '\''])
+      .filter(|&idx| after_keyword[..idx].find(';').is_none())
+    {
+      let quote_char = after_keyword.as_bytes()[quote_start] as char;
+      let quoted = &after_keyword[quote_start + 1..];
+      if let Some(quote_end) = quoted.find(quote_char) {
+        imports.push(quoted[..quote_end].to_string());
+        rest = &quoted[quote_end + 1..];
+        continue;
+      }
+    }
+    rest = after_keyword;
+  }
+  imports
+}
+
+/// Matches `import` against an entry already present in `sources`: directly by key, by a relative
+/// join against `importing_path`'s own directory, or through the longest-prefix `remappings`
+/// match. Checked before falling back to disk so inline sources take precedence over a same-named
+/// file on the filesystem.
+fn resolve_against_known(
+  importing_path: &str,
+  import: &str,
+  sources: &BTreeMap<String, String>,
+  remappings: &[FoundryRemapping],
+) -> Option<String> {
+  if sources.contains_key(import) {
+    return Some(import.to_string());
+  }
+
+  if import.starts_with('.') {
+    let base = Path::new(importing_path).parent().unwrap_or_else(|| Path::new(""));
+    let joined = normalise_import_path(&base.join(import));
+    if sources.contains_key(&joined) {
+      return Some(joined);
+    }
+  }
+
+  let remapped = resolve_via_remapping(import, remappings).map(|path| normalise_import_path(&path));
+  remapped.filter(|candidate| sources.contains_key(candidate))
+}
+
+/// Resolves `import` to a path on disk, in the same priority order `internal::resolver::Graph`
+/// uses for the project-bound facade: the longest-prefix `remappings` match, then
+/// `include_paths`/`libraries` roots, then - for imports written relative to the importing file -
+/// a join against that file's own directory.
+fn resolve_import_on_disk(
+  importing_path: &str,
+  import: &str,
+  remappings: &[FoundryRemapping],
+  include_paths: &BTreeSet<PathBuf>,
+  libraries: &[PathBuf],
+) -> Option<PathBuf> {
+  if let Some(candidate) = resolve_via_remapping(import, remappings) {
+    if candidate.exists() {
+      return Some(candidate);
+    }
+  }
+
+  for root in include_paths.iter().chain(libraries.iter()) {
+    let candidate = root.join(import);
+    if candidate.exists() {
+      return Some(candidate);
+    }
+  }
+
+  if import.starts_with('.') {
+    let base = Path::new(importing_path).parent().unwrap_or_else(|| Path::new(""));
+    let candidate = base.join(import);
+    if candidate.exists() {
+      return Some(candidate);
+    }
+  }
+
+  None
+}
+
+/// The remapping whose `name` is the longest prefix of `import`, joined with `import`'s remainder
+/// - mirrors solc's own "most specific remapping wins" rule.
+fn resolve_via_remapping(import: &str, remappings: &[FoundryRemapping]) -> Option<PathBuf> {
+  let mut best: Option<&FoundryRemapping> = None;
+  for remapping in remappings {
+    if import.starts_with(remapping.name.as_str())
+      && best.map(|current| remapping.name.len() > current.name.len()).unwrap_or(true)
+    {
+      best = Some(remapping);
+    }
+  }
+
+  best.map(|remapping| {
+    let suffix = import[remapping.name.len()..].trim_start_matches('/');
+    PathBuf::from(&remapping.path).join(suffix)
+  })
+}
+
+/// Collapses `.`/`..` segments without touching the filesystem, so a relative import compares
+/// equal to the canonical keys already used throughout `sources`.
+fn normalise_import_path(path: &Path) -> String {
+  let mut stack: Vec<std::ffi::OsString> = Vec::new();
+  for component in path.components() {
+    match component {
+      std::path::Component::CurDir => {}
+      std::path::Component::ParentDir => {
+        stack.pop();
+      }
+      other => stack.push(other.as_os_str().to_os_string()),
+    }
+  }
+  PathBuf::from_iter(stack).to_string_lossy().replace('\\', "/")
+}
@@ -1,9 +1,6 @@
 mod paths;
 mod utils;
 
-pub use paths::{
-  create_current_dapptools_paths, create_current_hardhat_paths, create_dapptools_paths,
-  create_hardhat_paths,
-};
+pub use paths::{create_project_paths, PathOverrides, PathStyle};
 
 pub use utils::{find_artifacts_dir, find_libs, find_source_dir};
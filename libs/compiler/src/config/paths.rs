@@ -1,11 +1,69 @@
 use std::path::{Path, PathBuf};
 
-use foundry_compilers::{error::SolcError, solc::SolcLanguage, ProjectPathsConfig};
+use foundry_compilers::{solc::SolcLanguage, ProjectPathsConfig};
 use napi::bindgen_prelude::*;
 
-use crate::internal::errors::map_napi_error;
+use crate::internal::errors::{map_napi_error, napi_error};
 use crate::types::ProjectPaths;
 
+/// Which preset (if any) `create_project_paths` starts from before layering `overrides` on top.
+/// `Hardhat`/`Dapptools` delegate to `ProjectPathsConfig`'s own presets; `Foundry` and `Custom` are
+/// built directly from `FOUNDRY_RELATIVE_DEFAULTS` since `foundry_compilers` has no preset for
+/// either (dapptools' own `src`/`lib` layout predates the `src`/`test`/`script`/`out` convention
+/// modern Foundry projects use).
+#[napi(string_enum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathStyle {
+  Hardhat,
+  Dapptools,
+  Foundry,
+  Custom,
+}
+
+/// Individually overrides any of a project's resolved directories/search paths, applied on top of
+/// whatever `PathStyle` derives by default. A relative value is resolved against the project root;
+/// an absolute value is used as-is.
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct PathOverrides {
+  #[napi(ts_type = "string | undefined")]
+  pub sources: Option<String>,
+  #[napi(ts_type = "string | undefined")]
+  pub tests: Option<String>,
+  #[napi(ts_type = "string | undefined")]
+  pub scripts: Option<String>,
+  #[napi(ts_type = "string | undefined")]
+  pub artifacts: Option<String>,
+  #[napi(ts_type = "string | undefined")]
+  pub cache: Option<String>,
+  #[napi(ts_type = "string[] | undefined")]
+  pub libs: Option<Vec<String>>,
+  #[napi(ts_type = "string[] | undefined")]
+  pub include_paths: Option<Vec<String>>,
+  #[napi(ts_type = "string[] | undefined")]
+  pub allowed_paths: Option<Vec<String>>,
+}
+
+/// Relative directory names `PathStyle::Foundry`/`PathStyle::Custom` default to when `overrides`
+/// doesn't specify them - the standard Forge project layout.
+struct RelativeDefaults {
+  sources: &'static str,
+  tests: &'static str,
+  scripts: &'static str,
+  artifacts: &'static str,
+  cache: &'static str,
+  libs: &'static [&'static str],
+}
+
+const FOUNDRY_RELATIVE_DEFAULTS: RelativeDefaults = RelativeDefaults {
+  sources: "src",
+  tests: "test",
+  scripts: "script",
+  artifacts: "out",
+  cache: "cache/solidity-files-cache.json",
+  libs: &["lib"],
+};
+
 fn to_project_paths(config: ProjectPathsConfig<SolcLanguage>) -> ProjectPaths {
   ProjectPaths {
     root: config.root.to_string_lossy().to_string(),
@@ -22,53 +80,145 @@ fn to_project_paths(config: ProjectPathsConfig<SolcLanguage>) -> ProjectPaths {
   }
 }
 
-fn create_paths_with_root<F>(root_path: String, context: &str, factory: F) -> Result<ProjectPaths>
-where
-  F: FnOnce(&Path) -> std::result::Result<ProjectPathsConfig<SolcLanguage>, SolcError>,
-{
-  let root = PathBuf::from(root_path);
-  let config = map_napi_error(factory(&root), context)?;
-  Ok(to_project_paths(config))
+/// Resolves `value` against `root`: absolute values are used as-is, relative ones joined onto it.
+fn join_root(root: &Path, value: &str) -> PathBuf {
+  let candidate = PathBuf::from(value);
+  if candidate.is_absolute() {
+    candidate
+  } else {
+    root.join(candidate)
+  }
 }
 
-fn create_paths<F>(context: &str, factory: F) -> Result<ProjectPaths>
-where
-  F: FnOnce() -> std::result::Result<ProjectPathsConfig<SolcLanguage>, SolcError>,
-{
-  let config = map_napi_error(factory(), context)?;
-  Ok(to_project_paths(config))
-}
+/// Overwrites whichever of `config`'s directories/search paths `overrides` sets, leaving the rest
+/// at whatever the `PathStyle::Hardhat`/`PathStyle::Dapptools` preset already resolved them to.
+fn apply_overrides(config: &mut ProjectPathsConfig<SolcLanguage>, overrides: Option<&PathOverrides>) {
+  let Some(overrides) = overrides else {
+    return;
+  };
+  let root = config.root.clone();
 
-#[napi]
-pub fn create_hardhat_paths(root_path: String) -> Result<ProjectPaths> {
-  create_paths_with_root(
-    root_path,
-    "Failed to create hardhat paths",
-    ProjectPathsConfig::<SolcLanguage>::hardhat,
-  )
+  if let Some(value) = &overrides.sources {
+    config.sources = join_root(&root, value);
+  }
+  if let Some(value) = &overrides.tests {
+    config.tests = join_root(&root, value);
+  }
+  if let Some(value) = &overrides.scripts {
+    config.scripts = join_root(&root, value);
+  }
+  if let Some(value) = &overrides.artifacts {
+    config.artifacts = join_root(&root, value);
+  }
+  if let Some(value) = &overrides.cache {
+    config.cache = join_root(&root, value);
+  }
+  if let Some(values) = &overrides.libs {
+    config.libraries = values.iter().map(|value| join_root(&root, value)).collect();
+  }
+  if let Some(values) = &overrides.include_paths {
+    config.include_paths = values.iter().map(|value| join_root(&root, value)).collect();
+  }
+  if let Some(values) = &overrides.allowed_paths {
+    config.allowed_paths = values.iter().map(|value| join_root(&root, value)).collect();
+  }
 }
 
-#[napi]
-pub fn create_dapptools_paths(root_path: String) -> Result<ProjectPaths> {
-  create_paths_with_root(
-    root_path,
-    "Failed to create dapptools paths",
-    ProjectPathsConfig::<SolcLanguage>::dapptools,
-  )
-}
+/// Builds a `ProjectPathsConfig` straight from `defaults`/`root`, with any `overrides` field
+/// layered on top - the path `PathStyle::Foundry` and `PathStyle::Custom` share, since neither has
+/// a `foundry_compilers` preset to start from.
+fn build_relative_paths(
+  root: &Path,
+  defaults: &RelativeDefaults,
+  overrides: Option<&PathOverrides>,
+) -> ProjectPathsConfig<SolcLanguage> {
+  let resolve = |default: &str, value: Option<&String>| match value {
+    Some(value) => join_root(root, value),
+    None => root.join(default),
+  };
 
-#[napi]
-pub fn create_current_hardhat_paths() -> Result<ProjectPaths> {
-  create_paths(
-    "Failed to create current hardhat paths",
-    ProjectPathsConfig::<SolcLanguage>::current_hardhat,
-  )
+  let sources = resolve(defaults.sources, overrides.and_then(|o| o.sources.as_ref()));
+  let tests = resolve(defaults.tests, overrides.and_then(|o| o.tests.as_ref()));
+  let scripts = resolve(defaults.scripts, overrides.and_then(|o| o.scripts.as_ref()));
+  let artifacts = resolve(defaults.artifacts, overrides.and_then(|o| o.artifacts.as_ref()));
+  let cache = resolve(defaults.cache, overrides.and_then(|o| o.cache.as_ref()));
+  let libs: Vec<PathBuf> = overrides
+    .and_then(|o| o.libs.as_ref())
+    .map(|libs| libs.iter().map(|lib| join_root(root, lib)).collect())
+    .unwrap_or_else(|| defaults.libs.iter().map(|lib| root.join(lib)).collect());
+  let include_paths: Vec<PathBuf> = overrides
+    .and_then(|o| o.include_paths.as_ref())
+    .map(|paths| paths.iter().map(|path| join_root(root, path)).collect())
+    .unwrap_or_default();
+  let allowed_paths: Vec<PathBuf> = overrides
+    .and_then(|o| o.allowed_paths.as_ref())
+    .map(|paths| paths.iter().map(|path| join_root(root, path)).collect())
+    .unwrap_or_default();
+
+  let mut builder = ProjectPathsConfig::builder()
+    .root(root)
+    .cache(&cache)
+    .artifacts(&artifacts)
+    .sources(&sources)
+    .tests(&tests)
+    .scripts(&scripts)
+    .include_paths(include_paths)
+    .allowed_paths(allowed_paths);
+  builder = if libs.is_empty() {
+    builder.no_libs()
+  } else {
+    builder.libs(libs)
+  };
+
+  builder.build_with_root::<SolcLanguage>(root)
 }
 
+/// Resolves a project's directory layout under `root_path` (the current directory when omitted)
+/// for `style`, with `overrides` layered on top to individually override any directory or search
+/// path. `Custom` requires `overrides.sources` and `overrides.artifacts`; every other directory -
+/// for `Custom` as well as `Foundry` - defaults relative to `root_path` using the standard Forge
+/// project layout. Replaces the old fixed `create_hardhat_paths`/`create_dapptools_paths`/
+/// `create_current_hardhat_paths`/`create_current_dapptools_paths` quartet, whose combinatorial
+/// style-by-current-dir shape couldn't express a mixed or monorepo layout at all.
 #[napi]
-pub fn create_current_dapptools_paths() -> Result<ProjectPaths> {
-  create_paths(
-    "Failed to create current dapptools paths",
-    ProjectPathsConfig::<SolcLanguage>::current_dapptools,
-  )
+pub fn create_project_paths(
+  root_path: Option<String>,
+  style: PathStyle,
+  overrides: Option<PathOverrides>,
+) -> Result<ProjectPaths> {
+  let root = match root_path {
+    Some(path) => PathBuf::from(path),
+    None => map_napi_error(std::env::current_dir(), "Failed to resolve current directory")?,
+  };
+
+  let config = match style {
+    PathStyle::Hardhat => {
+      let mut config: ProjectPathsConfig<SolcLanguage> =
+        map_napi_error(ProjectPathsConfig::hardhat(&root), "Failed to create hardhat paths")?;
+      apply_overrides(&mut config, overrides.as_ref());
+      config
+    }
+    PathStyle::Dapptools => {
+      let mut config: ProjectPathsConfig<SolcLanguage> = map_napi_error(
+        ProjectPathsConfig::dapptools(&root),
+        "Failed to create dapptools paths",
+      )?;
+      apply_overrides(&mut config, overrides.as_ref());
+      config
+    }
+    PathStyle::Foundry => build_relative_paths(&root, &FOUNDRY_RELATIVE_DEFAULTS, overrides.as_ref()),
+    PathStyle::Custom => {
+      let has_required = overrides
+        .as_ref()
+        .is_some_and(|overrides| overrides.sources.is_some() && overrides.artifacts.is_some());
+      if !has_required {
+        return Err(napi_error(
+          "Custom path style requires overrides.sources and overrides.artifacts",
+        ));
+      }
+      build_relative_paths(&root, &FOUNDRY_RELATIVE_DEFAULTS, overrides.as_ref())
+    }
+  };
+
+  Ok(to_project_paths(config))
 }
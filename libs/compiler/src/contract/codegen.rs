@@ -0,0 +1,326 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::internal::errors::{Error, Result};
+
+/// Output language for [`generate_bindings`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BindingTarget {
+  TypeScript,
+  Rust,
+}
+
+impl BindingTarget {
+  pub fn parse(raw: &str) -> Option<Self> {
+    match raw {
+      "ts" | "typescript" => Some(Self::TypeScript),
+      "rust" | "rs" => Some(Self::Rust),
+      _ => None,
+    }
+  }
+}
+
+struct AbiFunction {
+  name: String,
+  inputs: Vec<Value>,
+  outputs: Vec<Value>,
+}
+
+/// Generates typed contract bindings from `abi` in `target`'s syntax: one typed method per ABI
+/// function, plus `creation_bytecode` (when present) inlined as a hex constant so the output is a
+/// ready-to-deploy factory. Overloaded functions - more than one ABI entry sharing a name - get a
+/// positional suffix (`name1`, `name2`, ...) appended in declaration order so every generated
+/// method name stays unique.
+pub fn generate_bindings(
+  contract_name: &str,
+  abi: &Value,
+  creation_bytecode: Option<&str>,
+  target: BindingTarget,
+) -> Result<String> {
+  let functions = collect_functions(abi)?;
+  let named = disambiguate_overloads(functions);
+
+  Ok(match target {
+    BindingTarget::TypeScript => render_typescript(contract_name, &named, creation_bytecode),
+    BindingTarget::Rust => render_rust(contract_name, &named, creation_bytecode),
+  })
+}
+
+fn collect_functions(abi: &Value) -> Result<Vec<AbiFunction>> {
+  let entries = abi
+    .as_array()
+    .ok_or_else(|| Error::new("ABI must be a JSON array"))?;
+
+  Ok(
+    entries
+      .iter()
+      .filter(|entry| entry.get("type").and_then(Value::as_str) == Some("function"))
+      .filter_map(|entry| {
+        let name = entry.get("name").and_then(Value::as_str)?.to_string();
+        let inputs = entry
+          .get("inputs")
+          .and_then(Value::as_array)
+          .cloned()
+          .unwrap_or_default();
+        let outputs = entry
+          .get("outputs")
+          .and_then(Value::as_array)
+          .cloned()
+          .unwrap_or_default();
+        Some(AbiFunction {
+          name,
+          inputs,
+          outputs,
+        })
+      })
+      .collect(),
+  )
+}
+
+/// Appends a positional suffix (`name1`, `name2`, ...) to every function whose name appears more
+/// than once in `functions`, in the order they were declared; names that appear only once are
+/// left untouched.
+fn disambiguate_overloads(functions: Vec<AbiFunction>) -> Vec<(String, AbiFunction)> {
+  let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+  for function in &functions {
+    *counts.entry(function.name.as_str()).or_insert(0) += 1;
+  }
+
+  let mut seen: BTreeMap<String, usize> = BTreeMap::new();
+  functions
+    .into_iter()
+    .map(|function| {
+      if counts[function.name.as_str()] <= 1 {
+        let binding_name = function.name.clone();
+        (binding_name, function)
+      } else {
+        let index = seen.entry(function.name.clone()).or_insert(0);
+        *index += 1;
+        (format!("{}{}", function.name, index), function)
+      }
+    })
+    .collect()
+}
+
+fn param_name(input: &Value, index: usize) -> String {
+  input
+    .get("name")
+    .and_then(Value::as_str)
+    .filter(|name| !name.is_empty())
+    .map(str::to_string)
+    .unwrap_or_else(|| format!("arg{index}"))
+}
+
+fn solidity_type_of(value: &Value) -> &str {
+  value.get("type").and_then(Value::as_str).unwrap_or("bytes")
+}
+
+fn ts_type(solidity_type: &str) -> String {
+  if let Some(element) = solidity_type.strip_suffix("[]") {
+    return format!("{}[]", ts_type(element));
+  }
+  if solidity_type.starts_with("uint") || solidity_type.starts_with("int") {
+    return "bigint".to_string();
+  }
+  match solidity_type {
+    "address" => "`0x${string}`".to_string(),
+    "bool" => "boolean".to_string(),
+    "string" => "string".to_string(),
+    "tuple" => "Record<string, unknown>".to_string(),
+    bytes if bytes.starts_with("bytes") => "`0x${string}`".to_string(),
+    _ => "unknown".to_string(),
+  }
+}
+
+fn rust_type(solidity_type: &str) -> String {
+  if let Some(element) = solidity_type.strip_suffix("[]") {
+    return format!("Vec<{}>", rust_type(element));
+  }
+  if solidity_type.starts_with("uint") || solidity_type.starts_with("int") {
+    return "U256".to_string();
+  }
+  match solidity_type {
+    "address" => "Address".to_string(),
+    "bool" => "bool".to_string(),
+    "string" => "String".to_string(),
+    "tuple" => "Vec<ethabi::Token>".to_string(),
+    bytes if bytes.starts_with("bytes") => "Bytes".to_string(),
+    _ => "ethabi::Token".to_string(),
+  }
+}
+
+fn screaming_snake_case(name: &str) -> String {
+  name
+    .chars()
+    .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+    .collect()
+}
+
+fn render_typescript(
+  contract_name: &str,
+  functions: &[(String, AbiFunction)],
+  creation_bytecode: Option<&str>,
+) -> String {
+  let mut out = format!("// Generated bindings for {contract_name}\n\n");
+
+  if let Some(bytecode) = creation_bytecode {
+    out.push_str(&format!(
+      "export const {}_BYTECODE = \"{bytecode}\" as const;\n\n",
+      screaming_snake_case(contract_name)
+    ));
+  }
+
+  out.push_str(&format!("export interface {contract_name} {{\n"));
+  for (binding_name, function) in functions {
+    let params = function
+      .inputs
+      .iter()
+      .enumerate()
+      .map(|(index, input)| {
+        format!("{}: {}", param_name(input, index), ts_type(solidity_type_of(input)))
+      })
+      .collect::<Vec<_>>()
+      .join(", ");
+    let returns = match function.outputs.len() {
+      0 => "void".to_string(),
+      1 => ts_type(solidity_type_of(&function.outputs[0])),
+      _ => format!(
+        "[{}]",
+        function
+          .outputs
+          .iter()
+          .map(|output| ts_type(solidity_type_of(output)))
+          .collect::<Vec<_>>()
+          .join(", ")
+      ),
+    };
+    out.push_str(&format!("  {binding_name}({params}): {returns};\n"));
+  }
+  out.push_str("}\n");
+
+  out
+}
+
+fn render_rust(
+  contract_name: &str,
+  functions: &[(String, AbiFunction)],
+  creation_bytecode: Option<&str>,
+) -> String {
+  let mut out = format!("// Generated bindings for {contract_name}\n\n");
+
+  if let Some(bytecode) = creation_bytecode {
+    out.push_str(&format!(
+      "pub const {}_BYTECODE: &str = \"{bytecode}\";\n\n",
+      screaming_snake_case(contract_name)
+    ));
+  }
+
+  out.push_str(&format!("pub trait {contract_name} {{\n"));
+  for (binding_name, function) in functions {
+    let params = function
+      .inputs
+      .iter()
+      .enumerate()
+      .map(|(index, input)| {
+        format!("{}: {}", param_name(input, index), rust_type(solidity_type_of(input)))
+      })
+      .collect::<Vec<_>>()
+      .join(", ");
+    let returns = match function.outputs.len() {
+      0 => "()".to_string(),
+      1 => rust_type(solidity_type_of(&function.outputs[0])),
+      _ => format!(
+        "({})",
+        function
+          .outputs
+          .iter()
+          .map(|output| rust_type(solidity_type_of(output)))
+          .collect::<Vec<_>>()
+          .join(", ")
+      ),
+    };
+    out.push_str(&format!(
+      "  fn {binding_name}(&self, {params}) -> {returns};\n"
+    ));
+  }
+  out.push_str("}\n");
+
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sample_abi() -> Value {
+    serde_json::json!([
+      {
+        "type": "function",
+        "name": "transfer",
+        "inputs": [
+          { "name": "to", "type": "address" },
+          { "name": "amount", "type": "uint256" }
+        ],
+        "outputs": [{ "name": "", "type": "bool" }]
+      },
+      {
+        "type": "function",
+        "name": "transfer",
+        "inputs": [
+          { "name": "to", "type": "address" },
+          { "name": "amount", "type": "uint256" },
+          { "name": "data", "type": "bytes" }
+        ],
+        "outputs": [{ "name": "", "type": "bool" }]
+      },
+      {
+        "type": "event",
+        "name": "Transfer",
+        "inputs": []
+      }
+    ])
+  }
+
+  #[test]
+  fn overloaded_functions_get_positional_suffixes_in_declaration_order() {
+    let functions = collect_functions(&sample_abi()).expect("functions");
+    let named = disambiguate_overloads(functions);
+    let names: Vec<&str> = named.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["transfer1", "transfer2"]);
+  }
+
+  #[test]
+  fn event_entries_are_not_treated_as_functions() {
+    let functions = collect_functions(&sample_abi()).expect("functions");
+    assert_eq!(functions.len(), 2);
+  }
+
+  #[test]
+  fn typescript_output_inlines_bytecode_and_typed_signatures() {
+    let rendered =
+      generate_bindings("Token", &sample_abi(), Some("0x6001"), BindingTarget::TypeScript)
+        .expect("bindings");
+    assert!(rendered.contains("export const TOKEN_BYTECODE = \"0x6001\" as const;"));
+    assert!(rendered.contains("transfer1(to: `0x${string}`, amount: bigint): boolean;"));
+    assert!(rendered.contains(
+      "transfer2(to: `0x${string}`, amount: bigint, data: `0x${string}`): boolean;"
+    ));
+  }
+
+  #[test]
+  fn rust_output_renders_a_trait_with_a_bytecode_constant() {
+    let rendered = generate_bindings("Token", &sample_abi(), Some("0x6001"), BindingTarget::Rust)
+      .expect("bindings");
+    assert!(rendered.contains("pub const TOKEN_BYTECODE: &str = \"0x6001\";"));
+    assert!(rendered.contains("pub trait Token {"));
+    assert!(rendered.contains("fn transfer1(&self, to: Address, amount: U256) -> bool;"));
+  }
+
+  #[test]
+  fn rejects_an_abi_that_is_not_a_json_array() {
+    let err = generate_bindings("Token", &serde_json::json!({}), None, BindingTarget::TypeScript)
+      .unwrap_err();
+    assert!(err.to_string().contains("ABI must be a JSON array"));
+  }
+}
@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+
+use foundry_compilers::artifacts::{Bytecode, ConfigurableContractArtifact, DeployedBytecode};
+use foundry_compilers::Artifact;
+use serde_json::Value;
+
+use super::contract_state_from_json_value;
+
+/// Raw bytecode owned by a [`ContractState`] - just the bytes, with `to_hex`/`bytes` accessors
+/// covering the two shapes callers need (hex string for display/linking, raw bytes for
+/// `Buffer`-returning getters).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContractBytecode {
+  bytes: Vec<u8>,
+}
+
+impl ContractBytecode {
+  pub fn from_bytes(bytes: Vec<u8>) -> Self {
+    Self { bytes }
+  }
+
+  pub fn to_hex(&self) -> String {
+    format!("0x{}", hex::encode(&self.bytes))
+  }
+
+  pub fn bytes(&self) -> &[u8] {
+    &self.bytes
+  }
+}
+
+/// A byte range within a contract's deployed bytecode where an immutable variable's value still
+/// needs to be written - solc's `evm.deployedBytecode.immutableReferences`.
+#[napi(object)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ImmutableSlot {
+  pub start: u32,
+  pub length: u32,
+}
+
+/// One entry of solc's legacy `evm.deployedBytecode.functionDebugData`: the bytecode-level
+/// location of a Yul/assembly function.
+#[napi(object)]
+#[derive(Clone, Debug, Default)]
+pub struct JsFunctionDebugDataEntry {
+  pub entry_point: Option<u32>,
+  pub id: Option<u32>,
+  pub parameter_slots: Option<u32>,
+  pub return_slots: Option<u32>,
+}
+
+/// solc's `evm.gasEstimates`, kept as opaque JSON under each of its three sections rather than
+/// modeled field-by-field - the numbers are stringified (and sometimes `"infinite"`), so there's
+/// little a typed struct buys over passing the JSON straight through.
+#[napi(object, js_name = "GasEstimates")]
+#[derive(Clone, Debug, Default)]
+pub struct JsGasEstimates {
+  #[napi(ts_type = "unknown | undefined")]
+  pub creation: Option<Value>,
+  #[napi(ts_type = "unknown | undefined")]
+  pub external: Option<Value>,
+  #[napi(ts_type = "unknown | undefined")]
+  pub internal: Option<Value>,
+}
+
+/// solc's `ewasm` output: the compiled wasm binary (hex-encoded) plus its human-readable wast
+/// text, when the eWASM backend was enabled.
+#[napi(object, js_name = "EwasmOutput")]
+#[derive(Clone, Debug, Default)]
+pub struct JsEwasm {
+  #[napi(ts_type = "`0x${string}` | undefined")]
+  pub wasm: Option<String>,
+  pub wast: Option<String>,
+}
+
+/// Everything [`super::Contract`] carries about a single compiled contract, independent of
+/// whichever solc output shape (standard-json, a `ConfigurableContractArtifact`, or a foundry
+/// `Project`'s on-disk `Artifact`) it was built from. Every `from_*` constructor below funnels
+/// into this same shape so downstream code (linking, codegen, JS snapshots) never has to care
+/// which source produced it.
+#[derive(Clone, Debug, Default)]
+pub struct ContractState {
+  pub name: String,
+  pub address: Option<String>,
+  pub abi: Option<Value>,
+  pub source_path: Option<String>,
+  pub source_id: Option<u32>,
+  pub creation_bytecode: Option<ContractBytecode>,
+  pub deployed_bytecode: Option<ContractBytecode>,
+  pub metadata: Option<Value>,
+  pub userdoc: Option<Value>,
+  pub devdoc: Option<Value>,
+  pub storage_layout: Option<Value>,
+  pub immutable_references: Option<HashMap<String, Vec<ImmutableSlot>>>,
+  pub method_identifiers: Option<HashMap<String, String>>,
+  pub function_debug_data: Option<HashMap<String, JsFunctionDebugDataEntry>>,
+  pub gas_estimates: Option<JsGasEstimates>,
+  pub assembly: Option<String>,
+  pub legacy_assembly: Option<Value>,
+  pub opcodes: Option<String>,
+  pub ir: Option<String>,
+  pub ir_optimized: Option<String>,
+  pub ewasm: Option<JsEwasm>,
+  pub creation_source_map: Option<String>,
+}
+
+impl ContractState {
+  pub fn new(name: impl Into<String>) -> Self {
+    Self {
+      name: name.into(),
+      ..Default::default()
+    }
+  }
+}
+
+pub(crate) fn new_state(name: impl Into<String>) -> ContractState {
+  ContractState::new(name)
+}
+
+pub(crate) fn immutable_references_to_js(
+  state: &ContractState,
+) -> Option<HashMap<String, Vec<ImmutableSlot>>> {
+  state.immutable_references.clone()
+}
+
+pub(crate) fn method_identifiers_to_js(state: &ContractState) -> Option<HashMap<String, String>> {
+  state.method_identifiers.clone()
+}
+
+pub(crate) fn function_debug_data_to_js(
+  state: &ContractState,
+) -> Option<HashMap<String, JsFunctionDebugDataEntry>> {
+  state.function_debug_data.clone()
+}
+
+pub(crate) fn gas_estimates_to_js(state: &ContractState) -> Option<JsGasEstimates> {
+  state.gas_estimates.clone()
+}
+
+pub(crate) fn ewasm_to_js(state: &ContractState) -> Option<JsEwasm> {
+  state.ewasm.clone()
+}
+
+/// Builds a [`ContractState`] from one contract entry of solc's standard-json output
+/// (`output.contracts[file][name]`), where bytecode and friends live nested under `evm`.
+pub(crate) fn from_foundry_standard_json(
+  name: impl Into<String>,
+  contract: &foundry_compilers::artifacts::contract::Contract,
+) -> ContractState {
+  let name = name.into();
+  let value = serde_json::to_value(contract).unwrap_or(Value::Null);
+  let flattened = flatten_evm_wrapped_contract(&name, &value);
+  contract_state_from_json_value(&flattened).unwrap_or_else(|_| ContractState::new(name))
+}
+
+/// Builds a [`ContractState`] from a [`ConfigurableContractArtifact`] (the shape `foundry_compilers`
+/// writes to `out/<file>/<name>.json`), where `bytecode`/`deployedBytecode` sit at the top level
+/// rather than nested under `evm`.
+pub(crate) fn from_configurable_artifact(
+  name: impl Into<String>,
+  artifact: &ConfigurableContractArtifact,
+) -> ContractState {
+  let name = name.into();
+  let value = serde_json::to_value(artifact).unwrap_or(Value::Null);
+  let flattened = flatten_flat_contract(&name, &value);
+  contract_state_from_json_value(&flattened).unwrap_or_else(|_| ContractState::new(name))
+}
+
+/// Builds a [`ContractState`] from a foundry `Project`'s cached [`Artifact`]. Unlike the
+/// standard-json/configurable-artifact paths, `Artifact` only exposes the compact bytecode
+/// (abi + bytecode + deployed bytecode) - there's no path back to the raw solc output, so
+/// `storage_layout`/`gas_estimates`/`method_identifiers`/metadata/debug fields are left unset.
+pub(crate) fn from_foundry_project_artifact(
+  name: impl Into<String>,
+  artifact: &impl Artifact,
+) -> ContractState {
+  let mut state = ContractState::new(name);
+
+  let bytecode_cow = artifact.get_contract_bytecode();
+  state.abi = bytecode_cow
+    .abi
+    .as_ref()
+    .and_then(|abi| serde_json::to_value(&**abi).ok());
+
+  let bytecode_raw = bytecode_cow.bytecode.as_ref();
+  let deployed_raw = bytecode_cow.deployed_bytecode.as_ref();
+  let deployed_bytecode_raw = deployed_raw.and_then(|bytecode| bytecode.bytecode.as_ref());
+
+  state.creation_bytecode = bytecode_raw.and_then(bytecode_to_state_bytecode);
+  state.deployed_bytecode = deployed_bytecode_raw.and_then(bytecode_to_state_bytecode);
+  state.creation_source_map = bytecode_raw.and_then(|bytecode| bytecode.source_map.clone());
+  state.immutable_references = deployed_raw.map(immutable_references_to_state);
+
+  state
+}
+
+fn bytecode_to_state_bytecode(bytecode: &Bytecode) -> Option<ContractBytecode> {
+  let bytes = bytecode.object.as_bytes()?.to_vec();
+  Some(ContractBytecode::from_bytes(bytes))
+}
+
+fn immutable_references_to_state(
+  deployed: &DeployedBytecode,
+) -> HashMap<String, Vec<ImmutableSlot>> {
+  deployed
+    .immutable_references
+    .iter()
+    .map(|(key, offsets)| {
+      let slots = offsets
+        .iter()
+        .map(|offset| ImmutableSlot {
+          start: offset.start as u32,
+          length: offset.length as u32,
+        })
+        .collect();
+      (key.clone(), slots)
+    })
+    .collect()
+}
+
+fn insert_if_present(obj: &mut serde_json::Map<String, Value>, key: &str, value: Option<&Value>) {
+  if let Some(value) = value {
+    if !value.is_null() {
+      obj.insert(key.to_string(), value.clone());
+    }
+  }
+}
+
+/// Re-nests solc standard-json's `evm.*`/`evm.deployedBytecode.*` fields into the flat shape
+/// [`super::contract_state_from_json_value`] expects, so both JSON-sourced constructors share one
+/// parser instead of duplicating its field-by-field extraction.
+fn flatten_evm_wrapped_contract(name: &str, contract: &Value) -> Value {
+  let evm = contract.get("evm");
+  let bytecode = evm.and_then(|evm| evm.get("bytecode"));
+  let deployed = evm.and_then(|evm| evm.get("deployedBytecode"));
+  let deployed_bytecode = deployed.and_then(|deployed| deployed.get("bytecode"));
+
+  let mut obj = serde_json::Map::new();
+  obj.insert("name".to_string(), Value::String(name.to_string()));
+  insert_if_present(&mut obj, "abi", contract.get("abi"));
+  insert_if_present(&mut obj, "metadata", contract.get("metadata"));
+  insert_if_present(&mut obj, "userdoc", contract.get("userdoc"));
+  insert_if_present(&mut obj, "devdoc", contract.get("devdoc"));
+  insert_if_present(&mut obj, "storageLayout", contract.get("storageLayout"));
+  insert_if_present(&mut obj, "ir", contract.get("ir"));
+  insert_if_present(&mut obj, "irOptimized", contract.get("irOptimized"));
+  insert_if_present(&mut obj, "ewasm", contract.get("ewasm"));
+  insert_if_present(&mut obj, "assembly", evm.and_then(|evm| evm.get("assembly")));
+  insert_if_present(
+    &mut obj,
+    "legacyAssembly",
+    evm.and_then(|evm| evm.get("legacyAssembly")),
+  );
+  insert_if_present(
+    &mut obj,
+    "methodIdentifiers",
+    evm.and_then(|evm| evm.get("methodIdentifiers")),
+  );
+  insert_if_present(
+    &mut obj,
+    "gasEstimates",
+    evm.and_then(|evm| evm.get("gasEstimates")),
+  );
+  insert_if_present(&mut obj, "creationBytecode", bytecode.and_then(|b| b.get("object")));
+  insert_if_present(&mut obj, "creationSourceMap", bytecode.and_then(|b| b.get("sourceMap")));
+  insert_if_present(&mut obj, "opcodes", bytecode.and_then(|b| b.get("opcodes")));
+  insert_if_present(
+    &mut obj,
+    "deployedBytecode",
+    deployed_bytecode.and_then(|b| b.get("object")),
+  );
+  insert_if_present(
+    &mut obj,
+    "immutableReferences",
+    deployed.and_then(|deployed| deployed.get("immutableReferences")),
+  );
+  insert_if_present(
+    &mut obj,
+    "functionDebugData",
+    deployed.and_then(|deployed| deployed.get("functionDebugData")),
+  );
+
+  Value::Object(obj)
+}
+
+/// Same as [`flatten_evm_wrapped_contract`], but for a [`ConfigurableContractArtifact`]'s flatter
+/// shape, where `bytecode`/`deployedBytecode` sit at the top level rather than under `evm`.
+fn flatten_flat_contract(name: &str, artifact: &Value) -> Value {
+  let bytecode = artifact.get("bytecode");
+  let deployed = artifact.get("deployedBytecode");
+  let deployed_bytecode = deployed.and_then(|deployed| deployed.get("bytecode"));
+
+  let mut obj = serde_json::Map::new();
+  obj.insert("name".to_string(), Value::String(name.to_string()));
+  insert_if_present(&mut obj, "abi", artifact.get("abi"));
+  insert_if_present(&mut obj, "metadata", artifact.get("metadata"));
+  insert_if_present(&mut obj, "userdoc", artifact.get("userdoc"));
+  insert_if_present(&mut obj, "devdoc", artifact.get("devdoc"));
+  insert_if_present(&mut obj, "storageLayout", artifact.get("storageLayout"));
+  insert_if_present(&mut obj, "ir", artifact.get("ir"));
+  insert_if_present(&mut obj, "irOptimized", artifact.get("irOptimized"));
+  insert_if_present(&mut obj, "ewasm", artifact.get("ewasm"));
+  insert_if_present(&mut obj, "assembly", artifact.get("assembly"));
+  insert_if_present(&mut obj, "legacyAssembly", artifact.get("legacyAssembly"));
+  insert_if_present(&mut obj, "methodIdentifiers", artifact.get("methodIdentifiers"));
+  insert_if_present(&mut obj, "gasEstimates", artifact.get("gasEstimates"));
+  insert_if_present(&mut obj, "creationBytecode", bytecode.and_then(|b| b.get("object")));
+  insert_if_present(&mut obj, "creationSourceMap", bytecode.and_then(|b| b.get("sourceMap")));
+  insert_if_present(&mut obj, "opcodes", bytecode.and_then(|b| b.get("opcodes")));
+  insert_if_present(
+    &mut obj,
+    "deployedBytecode",
+    deployed_bytecode.and_then(|b| b.get("object")),
+  );
+  insert_if_present(
+    &mut obj,
+    "immutableReferences",
+    deployed.and_then(|deployed| deployed.get("immutableReferences")),
+  );
+  insert_if_present(
+    &mut obj,
+    "functionDebugData",
+    deployed.and_then(|deployed| deployed.get("functionDebugData")),
+  );
+
+  Value::Object(obj)
+}
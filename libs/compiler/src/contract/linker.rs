@@ -0,0 +1,223 @@
+use std::collections::BTreeMap;
+
+use sha3::{Digest, Keccak256};
+
+use crate::internal::errors::{Error, Result};
+
+/// Number of hex characters a library placeholder and its replacement address both occupy: 20
+/// address bytes, two hex characters each.
+const SLOT_HEX_LEN: usize = 40;
+
+/// Resolves a link-map key (either a `path:Name` fully-qualified library name or a raw
+/// placeholder hash) to the 34-hex-character id solc embeds in a `__$<34 hex>$__` placeholder -
+/// the first 17 bytes of `keccak256("path:Name")`, hex-encoded.
+fn placeholder_id(key: &str) -> String {
+  if key.len() == 34 && key.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+    return key.to_ascii_lowercase();
+  }
+  let digest = Keccak256::digest(key.as_bytes());
+  hex::encode(&digest[..17])
+}
+
+/// Builds the placeholder text solc/ethers-solc would have left in the bytecode for each linked
+/// library: `__$<34 hex>$__` for the modern form, `__<name, truncated/padded to 36 chars>__` for
+/// the legacy pre-0.6.9 form.
+fn placeholders_for(qualified_name: &str) -> (String, String) {
+  let modern = format!("__${}$__", placeholder_id(qualified_name));
+
+  let legacy_name: String = qualified_name.chars().take(36).collect();
+  let legacy = format!("__{:_<36}__", legacy_name);
+
+  (modern, legacy)
+}
+
+/// Replaces every resolved library placeholder in `hex` (a `0x`-prefixed or bare hex string, as
+/// produced by [`super::ContractBytecode::to_hex`]) with its address from `resolved`, a map from
+/// fully-qualified `path:Name` library name to a lowercase, `0x`-prefixed 20-byte address hex
+/// string. Errors if any address isn't exactly 20 bytes, or if a `__...__` placeholder remains in
+/// the output once every entry in `resolved` has been applied - that means either the map is
+/// missing an entry, or the bytecode references a library under a name `resolved` didn't provide.
+pub fn link_bytecode(hex: &str, resolved: &BTreeMap<String, String>) -> Result<String> {
+  let had_prefix = hex.starts_with("0x") || hex.starts_with("0X");
+  let mut linked = hex.trim_start_matches("0x").trim_start_matches("0X").to_string();
+
+  for (qualified_name, address) in resolved {
+    let address_hex = address.trim_start_matches("0x").trim_start_matches("0X");
+    if address_hex.len() != SLOT_HEX_LEN || !address_hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+      return Err(Error::new(format!(
+        "Library address for \"{qualified_name}\" must be a 20-byte hex string, got \"{address}\""
+      )));
+    }
+    let address_hex = address_hex.to_ascii_lowercase();
+
+    let (modern, legacy) = placeholders_for(qualified_name);
+    linked = linked.replace(&modern, &address_hex);
+    linked = linked.replace(&legacy, &address_hex);
+  }
+
+  if let Some(start) = linked.find("__") {
+    let end = linked[start..].find("__").map(|i| start + i + 2).unwrap_or(linked.len());
+    return Err(Error::new(format!(
+      "Unresolved library placeholder in bytecode: \"{}\"",
+      &linked[start..end.min(linked.len())]
+    )));
+  }
+
+  Ok(if had_prefix {
+    format!("0x{linked}")
+  } else {
+    linked
+  })
+}
+
+/// Scans `hex` (a `0x`-prefixed or bare hex string, as produced by
+/// [`super::ContractBytecode::to_hex`]) for every unresolved `__$<34 hex>$__`/legacy placeholder
+/// and reports where it occurs, keyed by the placeholder's literal text mapped to its
+/// `(byte_start, byte_length)` occurrences - mirroring ethers-solc's own `linkReferences` record.
+/// The truncated hash can't be reversed back into the original `<file>:<library>` without hashing
+/// every candidate from `CompilerSettingsOptions::libraries`, so callers needing the library name
+/// have to match placeholders against their own link map themselves; this just tells them where to
+/// patch once they do.
+pub(crate) fn unresolved_link_references(
+  hex: Option<&str>,
+) -> BTreeMap<String, Vec<(usize, usize)>> {
+  let Some(hex) = hex else {
+    return BTreeMap::new();
+  };
+  let hex = hex.trim_start_matches("0x").trim_start_matches("0X");
+
+  let mut by_placeholder: BTreeMap<String, Vec<(usize, usize)>> = BTreeMap::new();
+  let mut rest = hex;
+  let mut consumed = 0usize;
+  while let Some(start) = rest.find("__$") {
+    let Some(end) = rest[start + 3..].find("$__") else {
+      break;
+    };
+    let placeholder_len = 3 + end + 3;
+    let placeholder = rest[start..start + placeholder_len].to_string();
+    let byte_start = (consumed + start) / 2;
+    by_placeholder
+      .entry(placeholder)
+      .or_default()
+      .push((byte_start, 20));
+
+    consumed += start + placeholder_len;
+    rest = &rest[start + placeholder_len..];
+  }
+
+  by_placeholder
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn replaces_a_modern_placeholder_with_the_resolved_address() {
+    let qualified_name = "src/Math.sol:Math";
+    let (modern, _) = placeholders_for(qualified_name);
+    let unlinked = format!("0x6000{modern}6001");
+
+    let mut resolved = BTreeMap::new();
+    resolved.insert(
+      qualified_name.to_string(),
+      "0x00000000000000000000000000000000000001".to_string(),
+    );
+
+    let linked = link_bytecode(&unlinked, &resolved).expect("link");
+    assert_eq!(
+      linked,
+      "0x60000000000000000000000000000000000000000000016001"
+    );
+  }
+
+  #[test]
+  fn replaces_a_legacy_placeholder_with_the_resolved_address() {
+    let (_, legacy) = placeholders_for("Math");
+    assert_eq!(legacy.len(), SLOT_HEX_LEN);
+    let unlinked = format!("0x6000{legacy}6001");
+
+    let mut resolved = BTreeMap::new();
+    resolved.insert(
+      "Math".to_string(),
+      "0x00000000000000000000000000000000000001".to_string(),
+    );
+
+    let linked = link_bytecode(&unlinked, &resolved).expect("link");
+    assert_eq!(
+      linked,
+      "0x60000000000000000000000000000000000000000000016001"
+    );
+  }
+
+  #[test]
+  fn accepts_a_raw_placeholder_hash_as_a_link_key() {
+    let qualified_name = "src/Math.sol:Math";
+    let (modern, _) = placeholders_for(qualified_name);
+    let placeholder_hash = modern
+      .trim_start_matches("__$")
+      .trim_end_matches("$__")
+      .to_string();
+    let unlinked = format!("0x{modern}");
+
+    let mut resolved = BTreeMap::new();
+    resolved.insert(
+      placeholder_hash,
+      "0x00000000000000000000000000000000000001".to_string(),
+    );
+
+    let linked = link_bytecode(&unlinked, &resolved).expect("link");
+    assert_eq!(
+      linked,
+      "0x0000000000000000000000000000000000000001"
+    );
+  }
+
+  #[test]
+  fn errors_when_a_placeholder_is_left_unresolved() {
+    let (modern, _) = placeholders_for("src/Math.sol:Math");
+    let unlinked = format!("0x{modern}");
+    let err = link_bytecode(&unlinked, &BTreeMap::new()).unwrap_err();
+    assert!(err.to_string().contains("Unresolved library placeholder"));
+  }
+
+  #[test]
+  fn errors_when_a_resolved_address_has_the_wrong_length() {
+    let qualified_name = "src/Math.sol:Math";
+    let (modern, _) = placeholders_for(qualified_name);
+    let unlinked = format!("0x{modern}");
+
+    let mut resolved = BTreeMap::new();
+    resolved.insert(qualified_name.to_string(), "0x0001".to_string());
+
+    let err = link_bytecode(&unlinked, &resolved).unwrap_err();
+    assert!(err.to_string().contains("must be a 20-byte hex string"));
+  }
+
+  #[test]
+  fn fully_linked_bytecode_with_no_resolutions_passes_through_unchanged() {
+    let linked = link_bytecode("0x600160025b", &BTreeMap::new()).expect("link");
+    assert_eq!(linked, "0x600160025b");
+  }
+
+  #[test]
+  fn unresolved_link_references_reports_the_byte_offset_of_each_placeholder() {
+    let (modern, _) = placeholders_for("src/Math.sol:Math");
+    let hex = format!("0x6000{modern}6001");
+
+    let offsets = unresolved_link_references(Some(&hex));
+
+    assert_eq!(offsets.get(&modern), Some(&vec![(2, 20)]));
+  }
+
+  #[test]
+  fn unresolved_link_references_is_empty_for_fully_linked_bytecode() {
+    let offsets = unresolved_link_references(Some("0x600160025b"));
+    assert!(offsets.is_empty());
+  }
+
+  #[test]
+  fn unresolved_link_references_is_empty_for_no_bytecode() {
+    assert!(unresolved_link_references(None).is_empty());
+  }
+}
@@ -1,7 +1,11 @@
+mod codegen;
 mod core;
+pub(crate) mod linker;
 
 use crate::ast::utils::from_js_value;
-use crate::internal::errors::napi_error;
+use crate::compiler::sourcemap::{decode_source_map_entries, SourceMapEntry};
+use crate::internal::errors::{napi_error, Error, Result as CrateResult};
+use codegen::BindingTarget;
 use core::{
   ewasm_to_js, from_configurable_artifact, from_foundry_project_artifact,
   from_foundry_standard_json, function_debug_data_to_js, gas_estimates_to_js,
@@ -12,7 +16,7 @@ use foundry_compilers::Artifact;
 use napi::bindgen_prelude::*;
 use napi::{JsUnknown, ValueType};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 pub use core::{
   ContractBytecode, ContractState, ImmutableSlot, JsEwasm, JsFunctionDebugDataEntry, JsGasEstimates,
@@ -97,6 +101,53 @@ impl Contract {
   pub fn with_deployed_bytecode(&mut self, bytecode: Option<ContractBytecode>) {
     self.state.deployed_bytecode = bytecode;
   }
+
+  /// Resolves every `__$<34 hex>$__`/legacy `__Name___...__` library placeholder in
+  /// `creation_bytecode`/`deployed_bytecode` against `resolved` (fully-qualified `path:Name` or
+  /// raw placeholder hash -> `0x`-prefixed 20-byte address) and rewrites both in place. Errors if
+  /// an address is the wrong length or a placeholder is left unresolved; see
+  /// [`linker::link_bytecode`] for the exact rules.
+  pub fn link_libraries(&mut self, resolved: &BTreeMap<String, String>) -> CrateResult<()> {
+    if let Some(bytecode) = &self.state.creation_bytecode {
+      let linked = linker::link_bytecode(&bytecode.to_hex(), resolved)?;
+      self.state.creation_bytecode = Some(decode_bytecode_hex(&linked)?);
+    }
+    if let Some(bytecode) = &self.state.deployed_bytecode {
+      let linked = linker::link_bytecode(&bytecode.to_hex(), resolved)?;
+      self.state.deployed_bytecode = Some(decode_bytecode_hex(&linked)?);
+    }
+    Ok(())
+  }
+
+  /// Collapses this contract to just what a deploy pipeline needs: `abi` plus the
+  /// bytecode/deployed-bytecode objects themselves, not flattened to hex - so a caller can still
+  /// resolve their unlinked library placeholders via [`linker::unresolved_link_references`].
+  /// Everything else `ContractState` carries (AST-derived debug data, storage layout,
+  /// userdoc/devdoc, ...) is dropped. See [`JsCompactContractArtifact`] for the JS-facing form.
+  pub fn to_compact(&self) -> CompactContractArtifact {
+    CompactContractArtifact {
+      abi: self.state.abi.clone(),
+      bytecode: self.state.creation_bytecode.clone(),
+      deployed_bytecode: self.state.deployed_bytecode.clone(),
+    }
+  }
+}
+
+/// Compact projection of a `Contract` - see [`Contract::to_compact`] - mirroring the shape
+/// established solc tooling (e.g. ethers-solc's `CompactContractBytecode`) uses for a downstream
+/// build step that only needs the ABI and the bytecode objects, not the full artifact.
+#[derive(Clone, Debug, Default)]
+pub struct CompactContractArtifact {
+  pub abi: Option<Value>,
+  pub bytecode: Option<ContractBytecode>,
+  pub deployed_bytecode: Option<ContractBytecode>,
+}
+
+fn decode_bytecode_hex(hex_str: &str) -> CrateResult<ContractBytecode> {
+  let trimmed = hex_str.strip_prefix("0x").unwrap_or(hex_str);
+  let bytes = hex::decode(trimmed)
+    .map_err(|err| Error::new(format!("Invalid hex-encoded bytecode after linking: {err}")))?;
+  Ok(ContractBytecode::from_bytes(bytes))
 }
 
 impl From<Contract> for ContractState {
@@ -115,6 +166,15 @@ impl From<ContractState> for Contract {
 // JavaScript-facing snapshots
 // -----------------------------------------------------------------------------
 
+/// A byte range within a contract's bytecode where an unlinked library placeholder still needs an
+/// address patched in - see [`linker::unresolved_link_references`].
+#[napi(object, js_name = "LinkReferenceOffset")]
+#[derive(Clone, Debug)]
+pub struct LinkReferenceOffset {
+  pub start: u32,
+  pub length: u32,
+}
+
 #[napi(object, js_name = "ContractBytecode")]
 #[derive(Clone, Debug)]
 pub struct JsContractBytecode {
@@ -122,13 +182,34 @@ pub struct JsContractBytecode {
   pub hex: Option<String>,
   #[napi(ts_type = "Uint8Array | null | undefined")]
   pub bytes: Option<Vec<u8>>,
+  /// Every unresolved `__$<34 hex>$__`/legacy library placeholder still left in `hex`, keyed by
+  /// its literal placeholder text. Empty once every library this bytecode references has been
+  /// linked via [`Contract::link_libraries`].
+  #[napi(ts_type = "Record<string, LinkReferenceOffset[]>")]
+  pub link_references: HashMap<String, Vec<LinkReferenceOffset>>,
 }
 
 impl From<&ContractBytecode> for JsContractBytecode {
   fn from(bytecode: &ContractBytecode) -> Self {
+    let hex = bytecode.to_hex();
+    let link_references = linker::unresolved_link_references(Some(&hex))
+      .into_iter()
+      .map(|(placeholder, offsets)| {
+        let offsets = offsets
+          .into_iter()
+          .map(|(start, length)| LinkReferenceOffset {
+            start: start as u32,
+            length: length as u32,
+          })
+          .collect();
+        (placeholder, offsets)
+      })
+      .collect();
+
     Self {
-      hex: Some(bytecode.to_hex()),
+      hex: Some(hex),
       bytes: Some(bytecode.bytes().to_vec()),
+      link_references,
     }
   }
 }
@@ -175,6 +256,37 @@ pub struct JsContractState {
   pub creation_source_map: Option<String>,
 }
 
+/// Ethers-solc "compact contract" shape: just what a deployment pipeline actually needs - ABI,
+/// bytecode hex, and method selectors - without the large optional debug fields (`assembly`,
+/// `ir`, `ewasm`, source maps, ...) `ContractState` also carries.
+#[napi(object, js_name = "CompactContractState")]
+#[derive(Clone, Debug)]
+pub struct JsCompactContractState {
+  #[napi(ts_type = "unknown | null | undefined")]
+  pub abi: Option<Value>,
+  #[napi(ts_type = "`0x${string}` | null | undefined")]
+  pub bytecode: Option<String>,
+  #[napi(ts_type = "`0x${string}` | null | undefined")]
+  pub deployed_bytecode: Option<String>,
+  #[napi(ts_type = "Record<string, `0x${string}`> | null | undefined")]
+  pub method_identifiers: Option<HashMap<String, String>>,
+}
+
+/// JS-facing [`CompactContractArtifact`]: `abi` plus the bytecode/deployedBytecode objects (hex,
+/// raw bytes, and unresolved link references) rather than flattening them to a sole hex string -
+/// unlike [`JsCompactContractState`], which matches ethers-solc's plain-hex shape, this keeps
+/// enough to link libraries against the result.
+#[napi(object, js_name = "CompactContractArtifact")]
+#[derive(Clone, Debug, Default)]
+pub struct JsCompactContractArtifact {
+  #[napi(ts_type = "unknown | null | undefined")]
+  pub abi: Option<Value>,
+  #[napi(ts_type = "ContractBytecode | null | undefined")]
+  pub bytecode: Option<JsContractBytecode>,
+  #[napi(ts_type = "ContractBytecode | null | undefined")]
+  pub deployed_bytecode: Option<JsContractBytecode>,
+}
+
 // -----------------------------------------------------------------------------
 // Conversions between Rust and JS representations
 // -----------------------------------------------------------------------------
@@ -183,6 +295,16 @@ pub fn contract_class(contract: &Contract) -> JsContract {
   JsContract::from_contract(contract.clone())
 }
 
+/// JS-facing snapshot of [`Contract::to_compact`] - see [`JsCompactContractArtifact`].
+pub fn compact_contract_artifact_to_js(contract: &Contract) -> JsCompactContractArtifact {
+  let compact = contract.to_compact();
+  JsCompactContractArtifact {
+    abi: compact.abi,
+    bytecode: compact.bytecode.as_ref().map(JsContractBytecode::from),
+    deployed_bytecode: compact.deployed_bytecode.as_ref().map(JsContractBytecode::from),
+  }
+}
+
 pub fn contract_state_to_js(state: &ContractState) -> JsContractState {
   JsContractState {
     name: state.name.clone(),
@@ -220,7 +342,7 @@ pub fn contract_state_to_js(state: &ContractState) -> JsContractState {
 // JSON helpers
 // -----------------------------------------------------------------------------
 
-fn contract_state_from_json_value(value: &Value) -> napi::Result<ContractState> {
+pub(crate) fn contract_state_from_json_value(value: &Value) -> napi::Result<ContractState> {
   let obj = value
     .as_object()
     .ok_or_else(|| napi_error("Contract state must be an object".to_string()))?;
@@ -503,6 +625,27 @@ impl JsContract {
     self.inner.state().creation_source_map.clone()
   }
 
+  /// Expands `creation_source_map`'s compressed `s:l:f:j:m` entries - each field inheriting the
+  /// previous entry's value when left empty - into one record per instruction. See
+  /// `compiler::sourcemap::decode_source_map_entries` for the exact decoding rules.
+  #[napi(getter)]
+  pub fn decoded_creation_source_map(&self) -> Option<Vec<SourceMapEntry>> {
+    self
+      .inner
+      .state()
+      .creation_source_map
+      .as_deref()
+      .map(decode_source_map_entries)
+  }
+
+  /// Always empty today: unlike `creation_source_map`, nothing in this crate's artifact ingestion
+  /// captures a raw deployed-bytecode source map to decode - the same gap
+  /// `compiler::output::ContractSourceMaps` documents for its own `deployed`/`deployed_entries`.
+  #[napi(getter)]
+  pub fn decoded_deployed_source_map(&self) -> Vec<SourceMapEntry> {
+    Vec::new()
+  }
+
   #[napi]
   pub fn with_address(&mut self, address: Option<String>) -> napi::Result<Self> {
     self.inner.with_address(address);
@@ -529,4 +672,50 @@ impl JsContract {
   pub fn to_json(&self) -> JsContractState {
     self.into_json()
   }
+
+  /// Flattens this contract down to the ethers-solc "compact contract" shape - see
+  /// [`JsCompactContractState`] - for callers that just want `abi`/`bytecode`/`deployedBytecode`/
+  /// `methodIdentifiers` without picking them out of the full [`JsContractState`].
+  #[napi]
+  pub fn to_compact(&self) -> JsCompactContractState {
+    let state = self.inner.state();
+    JsCompactContractState {
+      abi: state.abi.clone(),
+      bytecode: state.creation_bytecode.as_ref().map(ContractBytecode::to_hex),
+      deployed_bytecode: state.deployed_bytecode.as_ref().map(ContractBytecode::to_hex),
+      method_identifiers: method_identifiers_to_js(state),
+    }
+  }
+
+  /// Generates typed contract bindings (`target`: `"ts"` or `"rust"`) from this contract's ABI,
+  /// with its creation bytecode inlined as a hex constant so the output is a ready-to-deploy
+  /// factory. See [`codegen::generate_bindings`] for the overload-disambiguation rules.
+  #[napi(ts_args_type = "target: \"ts\" | \"rust\"")]
+  pub fn generate_bindings(&self, target: String) -> napi::Result<String> {
+    let target = BindingTarget::parse(&target)
+      .ok_or_else(|| napi_error(format!("Unsupported bindings target \"{target}\"")))?;
+    let abi = self
+      .inner
+      .state()
+      .abi
+      .clone()
+      .unwrap_or_else(|| Value::Array(Vec::new()));
+    let creation_bytecode = self.inner.creation_bytecode().map(ContractBytecode::to_hex);
+
+    codegen::generate_bindings(self.inner.name(), &abi, creation_bytecode.as_deref(), target)
+      .map_err(|err| napi_error(err.to_string()))
+  }
+
+  /// Links external library references into this contract's bytecode. `map` accepts both the
+  /// fully-qualified `path:Name` library name and the raw placeholder hash as keys. See
+  /// [`Contract::link_libraries`] for the exact resolution/error rules.
+  #[napi(ts_args_type = "map: Record<string, `0x${string}`>")]
+  pub fn link_libraries(&mut self, map: HashMap<String, String>) -> napi::Result<Self> {
+    let resolved: BTreeMap<String, String> = map.into_iter().collect();
+    self
+      .inner
+      .link_libraries(&resolved)
+      .map_err(|err| napi_error(err.to_string()))?;
+    Ok(self.clone())
+  }
 }
@@ -1,14 +1,24 @@
+pub(crate) mod coverage;
 mod error;
+pub(crate) mod getters;
 pub(crate) mod parser;
+pub(crate) mod printer;
 mod stitcher;
+pub(crate) mod transform;
 pub(crate) mod utils;
+pub(crate) mod watch;
+
+use std::cell::RefCell;
+use std::rc::Rc;
 
 use foundry_compilers::artifacts::ast::{
   ContractDefinition, ContractDefinitionPart, SourceUnit, SourceUnitPart, Visibility,
 };
-use foundry_compilers::artifacts::{output_selection::OutputSelection, Settings};
+use foundry_compilers::artifacts::{output_selection::OutputSelection, CompilerOutput, Settings};
 use napi::bindgen_prelude::*;
-use napi::{Env, JsUnknown};
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction};
+use napi::{Env, JsFunction, JsUnknown};
+use serde_json::Value;
 
 use self::utils::{from_js_value, sanitize_ast_value, to_js_value};
 use crate::internal::{
@@ -19,6 +29,14 @@ use crate::internal::{
 
 const DEFAULT_VIRTUAL_SOURCE: &str = "Instrumented.sol";
 
+/// One `addTransform` registration: the JS callback, kept alive via a napi `Ref` so it survives
+/// past the call that registered it, and the `nodeType` it's scoped to (`None` runs it against
+/// every `ContractDefinitionPart`).
+struct RegisteredTransform {
+  callback: Ref<()>,
+  node_kind: Option<String>,
+}
+
 /// High-level helper for manipulating Solidity ASTs prior to recompilation.
 #[napi]
 #[derive(Clone)]
@@ -26,7 +44,22 @@ pub struct Instrument {
   config: SolcConfig,
   ast: Option<SourceUnit>,
   source_name: Option<String>,
+  /// The exact text `load_source` parsed, kept alongside `source_name` so `to_source` can fall
+  /// back to slicing a node's own `src` range out of it when it can't regenerate that node kind.
+  /// `None` when the target AST came from `from_ast` instead, since there's no original text to
+  /// slice.
+  source_text: Option<String>,
   default_contract: Option<String>,
+  coverage_sites: Option<Vec<coverage::CoverageCounter>>,
+  /// Registered by `add_transform`, run by `apply_transforms`. `Rc<RefCell<_>>` rather than a
+  /// plain `Vec` so every clone `Ok(self.clone())` returns for chaining shares the same
+  /// registrations instead of forking them - `Ref` has no `Clone` impl that doesn't need an `Env`,
+  /// so a plain field can't survive `#[derive(Clone)]` on its own.
+  transforms: Rc<RefCell<Vec<RegisteredTransform>>>,
+  /// The active `watch` session's background worker, if any. `Rc<RefCell<_>>` for the same reason
+  /// as `transforms` - every clone in an `Ok(self.clone())` chain shares one worker rather than
+  /// each spawning its own, and a `watch::WatchHandle`'s `JoinHandle` has no `Clone` impl either.
+  watcher: Rc<RefCell<Option<watch::WatchHandle>>>,
 }
 
 impl Instrument {
@@ -199,6 +232,295 @@ impl Instrument {
     Ok(())
   }
 
+  /// Instruments every contract `instrument_coverage` targets with per-statement hit counters,
+  /// then stitches in the `__covHits` ledger through the same fragment-parse + id-remapping path
+  /// `inject_fragment_contract` uses. Contracts with no instrumentable function bodies are left
+  /// untouched rather than growing a `__covHits` mapping no counter ever writes to. Returns the
+  /// counters recorded across every targeted contract, which are also cached on `self` for
+  /// `coverage_map()`.
+  fn instrument_coverage_internal(
+    &mut self,
+    overrides: Option<&InstrumentOptions>,
+  ) -> Result<Vec<coverage::CoverageCounter>> {
+    self.update_default_contract(overrides);
+    let mut config = self.resolve_config(overrides)?;
+    let solc = solc::ensure_installed(&config.version)?;
+    let settings = Self::sanitize_settings(Some(config.settings.clone()));
+
+    let target_ast_ptr = self.target_ast_mut()? as *mut SourceUnit;
+    // safety: pointer valid during this scope
+    let target_ast = unsafe { &mut *target_ast_ptr };
+    let indices = self.contract_indices(target_ast, overrides)?;
+
+    let mut next_id = map_napi_error(utils::max_id(target_ast), "Failed to inspect AST ids")?;
+    let mut sites = Vec::new();
+
+    for idx in indices {
+      let contract_sites = map_napi_error(
+        coverage::instrument_contract(target_ast, idx, &solc, &settings, &mut next_id),
+        "Failed to instrument coverage counters",
+      )?;
+      if contract_sites.is_empty() {
+        continue;
+      }
+      sites.extend(contract_sites);
+
+      let fragment_source = coverage::storage_fragment_source();
+      let fragment_contract = map_napi_error(
+        parser::parse_fragment_contract(&fragment_source, &solc, &settings),
+        "Failed to parse coverage storage fragment",
+      )?;
+      let max_target_id = map_napi_error(utils::max_id(target_ast), "Failed to inspect AST ids")?;
+      map_napi_error(
+        stitcher::stitch_fragment_nodes_into_contract(
+          target_ast,
+          idx,
+          &fragment_contract,
+          max_target_id,
+        ),
+        "Failed to stitch coverage storage fragment",
+      )?;
+      next_id = map_napi_error(utils::max_id(target_ast), "Failed to inspect AST ids")?;
+    }
+
+    config.settings = settings;
+    self.config = config;
+    self.coverage_sites = Some(sites.clone());
+    Ok(sites)
+  }
+
+  /// Companion to `expose_variables_internal` for state the simple visibility flip can't reach:
+  /// structs declaring a mapping member, mappings of mappings resolving to a struct, and
+  /// multi-dimensional arrays of structs all make `solc` refuse to synthesize a public getter.
+  /// For every such variable in the targeted contract(s), synthesizes a `<name>_state_getter`
+  /// returning the struct's non-mapping fields and stitches it in the same way
+  /// `instrument_coverage_internal` stitches its storage fragment.
+  fn generate_state_getters_internal(&mut self, overrides: Option<&InstrumentOptions>) -> Result<()> {
+    self.update_default_contract(overrides);
+    let mut config = self.resolve_config(overrides)?;
+    let solc = solc::ensure_installed(&config.version)?;
+    let settings = Self::sanitize_settings(Some(config.settings.clone()));
+
+    let target_ast_ptr = self.target_ast_mut()? as *mut SourceUnit;
+    // safety: pointer valid during this scope
+    let target_ast = unsafe { &mut *target_ast_ptr };
+    let indices = self.contract_indices(target_ast, overrides)?;
+    let structs = map_napi_error(
+      getters::build_struct_registry(target_ast),
+      "Failed to inspect struct definitions",
+    )?;
+
+    for idx in indices {
+      let SourceUnitPart::ContractDefinition(contract) = target_ast
+        .nodes
+        .get(idx)
+        .ok_or_else(|| napi_error("Invalid contract index"))?
+      else {
+        continue;
+      };
+
+      let snippets: Vec<String> = contract
+        .nodes
+        .iter()
+        .filter_map(|part| match part {
+          ContractDefinitionPart::VariableDeclaration(variable)
+            if matches!(
+              variable.visibility,
+              Visibility::Private | Visibility::Internal
+            ) =>
+          {
+            getters::getter_snippet(variable, &structs)
+          }
+          _ => None,
+        })
+        .collect();
+
+      if snippets.is_empty() {
+        continue;
+      }
+
+      let fragment_source = snippets.join("\n\n");
+      let fragment_contract = map_napi_error(
+        parser::parse_fragment_contract(&fragment_source, &solc, &settings),
+        "Failed to parse state getter fragment",
+      )?;
+      let max_target_id = map_napi_error(utils::max_id(target_ast), "Failed to inspect AST ids")?;
+      map_napi_error(
+        stitcher::stitch_fragment_nodes_into_contract(
+          target_ast,
+          idx,
+          &fragment_contract,
+          max_target_id,
+        ),
+        "Failed to stitch state getters",
+      )?;
+    }
+
+    config.settings = settings;
+    self.config = config;
+    Ok(())
+  }
+
+  /// Regenerates Solidity text from the current target AST via `printer::to_source`, falling back
+  /// to slicing `source_text` for node kinds the printer hasn't been taught to regenerate.
+  fn to_source_internal(&self) -> Result<String> {
+    let ast = self.target_ast()?;
+    map_napi_error(
+      printer::to_source(ast, self.source_text.as_deref()),
+      "Failed to reconstruct source from the instrumented AST",
+    )
+  }
+
+  fn add_transform_internal(
+    &mut self,
+    env: &Env,
+    callback: JsFunction,
+    node_kind: Option<String>,
+  ) -> Result<()> {
+    let callback = env.create_reference(callback)?;
+    self
+      .transforms
+      .borrow_mut()
+      .push(RegisteredTransform { callback, node_kind });
+    Ok(())
+  }
+
+  /// Runs every `addTransform`-registered callback over each `ContractDefinitionPart` of the
+  /// targeted contract(s), in registration order, feeding each callback the node as sanitized JSON
+  /// (`to_js_value`) and reading its decision back via `transform::read_outcome`. Replacements are
+  /// folded into the node before the next callback sees it; injected sibling fragments from every
+  /// callback are collected and stitched in once per contract afterward, the same way
+  /// `generate_state_getters_internal` stitches its synthesized getters.
+  fn apply_transforms_internal(
+    &mut self,
+    env: &Env,
+    overrides: Option<&InstrumentOptions>,
+  ) -> Result<()> {
+    self.update_default_contract(overrides);
+    let transforms = self.transforms.clone();
+    let transforms = transforms.borrow();
+    if transforms.is_empty() {
+      return Ok(());
+    }
+
+    let mut config = self.resolve_config(overrides)?;
+    let solc = solc::ensure_installed(&config.version)?;
+    let settings = Self::sanitize_settings(Some(config.settings.clone()));
+
+    let target_ast_ptr = self.target_ast_mut()? as *mut SourceUnit;
+    // safety: pointer valid during this scope
+    let target_ast = unsafe { &mut *target_ast_ptr };
+    let indices = self.contract_indices(target_ast, overrides)?;
+
+    for idx in indices {
+      let SourceUnitPart::ContractDefinition(contract) = target_ast
+        .nodes
+        .get_mut(idx)
+        .ok_or_else(|| napi_error("Invalid contract index"))?
+      else {
+        continue;
+      };
+
+      let mut injected_snippets = Vec::new();
+
+      for member in &mut contract.nodes {
+        let mut node_value =
+          map_napi_error(serde_json::to_value(&*member), "Failed to inspect AST node")?;
+        sanitize_ast_value(&mut node_value);
+
+        for registered in transforms.iter() {
+          if !transform::matches_node_kind(&node_value, registered.node_kind.as_deref()) {
+            continue;
+          }
+
+          let callback: JsFunction = env.get_reference_value(&registered.callback)?;
+          let arg = to_js_value(env, &node_value)?;
+          let result = callback.call(None, &[arg])?;
+          let result_value: Value = from_js_value(env, result)?;
+
+          match transform::read_outcome(result_value) {
+            transform::TransformOutcome::Unchanged => {}
+            transform::TransformOutcome::Replace(replacement) => node_value = replacement,
+            transform::TransformOutcome::InjectSiblings(snippets) => {
+              injected_snippets.extend(snippets);
+            }
+          }
+        }
+
+        *member = map_napi_error(
+          serde_json::from_value(node_value),
+          "Failed to apply transform result",
+        )?;
+      }
+
+      if !injected_snippets.is_empty() {
+        let fragment_source = injected_snippets.join("\n\n");
+        let fragment_contract = map_napi_error(
+          parser::parse_fragment_contract(&fragment_source, &solc, &settings),
+          "Failed to parse transform-injected fragment",
+        )?;
+        let max_target_id = map_napi_error(utils::max_id(target_ast), "Failed to inspect AST ids")?;
+        map_napi_error(
+          stitcher::stitch_fragment_nodes_into_contract(
+            target_ast,
+            idx,
+            &fragment_contract,
+            max_target_id,
+          ),
+          "Failed to stitch transform-injected fragment",
+        )?;
+      }
+    }
+
+    config.settings = settings;
+    self.config = config;
+    Ok(())
+  }
+
+  /// Spawns (replacing any prior session, which `Drop`s and shuts down its worker) a background
+  /// thread that recompiles `source` and every subsequent `restart()` source, delivering each
+  /// [`watch::WatchResult`] to `on_result`. See [`watch::WatchHandle`] for what this worker does
+  /// and does not replay.
+  fn watch_internal(
+    &mut self,
+    env: &Env,
+    source: String,
+    on_result: JsFunction,
+    overrides: Option<&InstrumentOptions>,
+  ) -> Result<()> {
+    self.update_default_contract(overrides);
+    let config = self.resolve_config(overrides)?;
+    let file_name = self
+      .source_name
+      .clone()
+      .unwrap_or_else(|| DEFAULT_VIRTUAL_SOURCE.to_string());
+
+    let callback: ThreadsafeFunction<watch::WatchResult, ErrorStrategy::Fatal> =
+      on_result.create_threadsafe_function(0, |ctx| Ok(vec![ctx.value]))?;
+
+    let handle = watch::WatchHandle::spawn(config, file_name, source, callback)?;
+    *self.watcher.borrow_mut() = Some(handle);
+    Ok(())
+  }
+
+  fn restart_internal(&self, source: String) -> Result<()> {
+    let guard = self.watcher.borrow();
+    let handle = guard
+      .as_ref()
+      .ok_or_else(|| napi_error("No active watch session. Call watch first."))?;
+    handle.restart(source);
+    Ok(())
+  }
+
+  fn cancel_internal(&self) -> Result<()> {
+    let guard = self.watcher.borrow();
+    let handle = guard
+      .as_ref()
+      .ok_or_else(|| napi_error("No active watch session. Call watch first."))?;
+    handle.cancel();
+    Ok(())
+  }
+
   pub(crate) fn from_compiler_config(
     base: &SolcConfig,
     overrides: Option<&InstrumentOptions>,
@@ -212,7 +534,11 @@ impl Instrument {
       config,
       ast: None,
       source_name: None,
+      source_text: None,
       default_contract: None,
+      coverage_sites: None,
+      transforms: Rc::new(RefCell::new(Vec::new())),
+      watcher: Rc::new(RefCell::new(None)),
     };
     instrument.update_default_contract(overrides);
     Ok(instrument)
@@ -238,6 +564,7 @@ impl Instrument {
     self.config = config;
     self.ast = Some(ast);
     self.source_name = Some(DEFAULT_VIRTUAL_SOURCE.to_string());
+    self.source_text = Some(source.to_string());
     Ok(())
   }
 
@@ -261,6 +588,7 @@ impl Instrument {
     self.config = config;
     self.ast = Some(ast_unit);
     self.source_name = None;
+    self.source_text = None;
     Ok(())
   }
 
@@ -299,6 +627,46 @@ impl Instrument {
 
     self.inject_fragment_contract(fragment_contract, overrides)
   }
+
+  /// Recompiles the current target AST into full artifacts: a complete (non-`stop_after`)
+  /// `OutputSelection` covering ABI, bytecode, deployed bytecode, storage layout, and metadata.
+  /// Unlike `Ast::compile` (which surfaces solc diagnostics on its returned `CompileOutput`
+  /// instead of failing), any `output.errors` here are mapped straight into a structured
+  /// `napi::Error`, since a caller driving `Instrument` one step at a time wants to know
+  /// immediately that the AST it just produced doesn't compile.
+  fn compile_internal(&self, overrides: Option<&InstrumentOptions>) -> Result<CompilerOutput> {
+    let config = self.resolve_config(overrides)?;
+    let solc = solc::ensure_installed(&config.version)?;
+
+    let mut settings = config.settings;
+    settings.stop_after = None;
+    settings.output_selection = OutputSelection::default_output_selection();
+
+    let ast = self.target_ast()?;
+    let mut ast_value =
+      map_napi_error(serde_json::to_value(ast), "Failed to serialize AST value")?;
+    sanitize_ast_value(&mut ast_value);
+
+    let file_name = self.source_name.as_deref().unwrap_or(DEFAULT_VIRTUAL_SOURCE);
+    let output = map_napi_error(
+      solc::recompile_ast(&solc, file_name, ast_value, &settings),
+      "Failed to recompile instrumented AST",
+    )?;
+
+    if !output.errors.is_empty() {
+      let messages: Vec<&str> = output
+        .errors
+        .iter()
+        .map(|error| error.formatted_message.as_deref().unwrap_or(&error.message))
+        .collect();
+      return Err(napi_error(format!(
+        "solc reported errors recompiling the instrumented AST:\n{}",
+        messages.join("\n")
+      )));
+    }
+
+    Ok(output)
+  }
 }
 
 /// JavaScript-facing API surface.
@@ -318,7 +686,11 @@ impl Instrument {
       config,
       ast: None,
       source_name: None,
+      source_text: None,
       default_contract: None,
+      coverage_sites: None,
+      transforms: Rc::new(RefCell::new(Vec::new())),
+      watcher: Rc::new(RefCell::new(None)),
     };
     instrument.update_default_contract(parsed.as_ref());
     Ok(instrument)
@@ -424,6 +796,89 @@ impl Instrument {
     Ok(self.clone())
   }
 
+  /// Insert per-statement coverage counters (via an injected `__covHits` mapping) into every
+  /// instrumentable function body in the targeted contract(s), stripping `view`/`pure`
+  /// mutability where a counter write needs it. Call `coverageMap()` afterward to read back the
+  /// `counterId -> src` sites this pass recorded.
+  #[napi(
+    ts_args_type = "options?: InstrumentOptions | undefined",
+    ts_return_type = "this"
+  )]
+  pub fn instrument_coverage(
+    &mut self,
+    env: Env,
+    options: Option<JsUnknown>,
+  ) -> Result<Instrument> {
+    let parsed = parse_instrument_options(&env, options)?;
+    self.instrument_coverage_internal(parsed.as_ref())?;
+    Ok(self.clone())
+  }
+
+  /// The `counterId -> src` sites recorded by the most recent `instrumentCoverage()` call, or an
+  /// empty array if it hasn't been called yet.
+  #[napi(ts_return_type = "CoverageCounter[]")]
+  pub fn coverage_map(&self) -> Vec<coverage::CoverageCounter> {
+    self.coverage_sites.clone().unwrap_or_default()
+  }
+
+  /// Synthesize explicit getters for private/internal state `exposeInternalVariables` can't
+  /// safely make public: structs with a mapping member, nested mappings resolving to a struct, and
+  /// multi-dimensional arrays of structs. Leaves the originating variable's own visibility
+  /// untouched. Omitting `instrumentedContract` applies the change to all contracts.
+  #[napi(
+    ts_args_type = "options?: InstrumentOptions | undefined",
+    ts_return_type = "this"
+  )]
+  pub fn generate_state_getters(
+    &mut self,
+    env: Env,
+    options: Option<JsUnknown>,
+  ) -> Result<Instrument> {
+    let parsed = parse_instrument_options(&env, options)?;
+    self.generate_state_getters_internal(parsed.as_ref())?;
+    Ok(self.clone())
+  }
+
+  /// Regenerate Solidity source text from the current target AST, covering pragma directives,
+  /// imports, contract/interface/library definitions, state variables with their (possibly
+  /// instrumented) visibility, and function definitions with injected bodies. Node kinds this
+  /// doesn't render fall back to the matching `src` slice of the original source when `fromSource`
+  /// (rather than `fromAst`) populated the target AST; otherwise they raise.
+  #[napi]
+  pub fn to_source(&self) -> Result<String> {
+    self.to_source_internal()
+  }
+
+  /// Register a custom AST pass: `visitor` is invoked once per `ContractDefinitionPart` (or, with
+  /// `nodeKind` given, only for parts whose `nodeType` matches) by `applyTransforms`, receiving the
+  /// sanitized node as a plain object. Return `undefined`/the node unchanged to leave it alone,
+  /// `{ inject: string[] }` to leave it alone but splice the given Solidity snippets in as new
+  /// sibling members, or any other value to replace the node with it. Generalizes the hard-coded
+  /// `exposeInternal*`/`injectShadow*` passes into a pipeline callers can extend without a new
+  /// native method (event logging, require-message rewriting, assertion insertion, ...).
+  #[napi(ts_args_type = "visitor: (node: any) => any, nodeKind?: string | undefined")]
+  pub fn add_transform(
+    &mut self,
+    env: Env,
+    visitor: JsFunction,
+    node_kind: Option<String>,
+  ) -> Result<Instrument> {
+    self.add_transform_internal(&env, visitor, node_kind)?;
+    Ok(self.clone())
+  }
+
+  /// Run every `addTransform`-registered callback over the targeted contract(s). Omitting
+  /// `instrumentedContract` applies the pipeline to all contracts.
+  #[napi(
+    ts_args_type = "options?: InstrumentOptions | undefined",
+    ts_return_type = "this"
+  )]
+  pub fn apply_transforms(&mut self, env: Env, options: Option<JsUnknown>) -> Result<Instrument> {
+    let parsed = parse_instrument_options(&env, options)?;
+    self.apply_transforms_internal(&env, parsed.as_ref())?;
+    Ok(self.clone())
+  }
+
   #[napi(ts_return_type = "import('./ast-types').SourceUnit")]
   pub fn ast(&self, env: Env) -> Result<JsUnknown> {
     let ast = self.ast.as_ref().ok_or_else(|| {
@@ -433,6 +888,58 @@ impl Instrument {
     sanitize_ast_value(&mut ast_value);
     to_js_value(&env, &ast_value)
   }
+
+  /// Recompile the instrumented AST into runnable artifacts (bytecode, ABI, storage layout,
+  /// metadata). Fails if solc reports any errors against the instrumented contract.
+  #[napi(
+    ts_args_type = "options?: InstrumentOptions | undefined",
+    ts_return_type = "import('./solc-output').CompilerOutput"
+  )]
+  pub fn compile(&self, env: Env, options: Option<JsUnknown>) -> Result<JsUnknown> {
+    let parsed = parse_instrument_options(&env, options)?;
+    let output = self.compile_internal(parsed.as_ref())?;
+    let output_value =
+      map_napi_error(serde_json::to_value(&output), "Failed to serialize compiler output")?;
+    to_js_value(&env, &output_value)
+  }
+
+  /// Starts a background watch session: recompiles `source` on a dedicated worker thread and
+  /// delivers the result to `onResult`, then does the same for every subsequent `restart()` call,
+  /// coalescing a rapid burst of restarts into a single recompile of the last source in it.
+  /// Replaces any watch session already running on this `Instrument`. Does not replay
+  /// `addTransform`-registered passes - see [`watch::WatchHandle`].
+  #[napi(
+    ts_args_type = "source: string, onResult: (result: WatchResult) => void, options?: InstrumentOptions | undefined",
+    ts_return_type = "this"
+  )]
+  pub fn watch(
+    &mut self,
+    env: Env,
+    source: String,
+    on_result: JsFunction,
+    options: Option<JsUnknown>,
+  ) -> Result<Instrument> {
+    let parsed = parse_instrument_options(&env, options)?;
+    self.watch_internal(&env, source, on_result, parsed.as_ref())?;
+    Ok(self.clone())
+  }
+
+  /// Queues `source` for recompilation on the running watch session's worker. Fails if `watch`
+  /// hasn't been called yet.
+  #[napi(ts_return_type = "this")]
+  pub fn restart(&mut self, source: String) -> Result<Instrument> {
+    self.restart_internal(source)?;
+    Ok(self.clone())
+  }
+
+  /// Discards whatever source the running watch session's worker is about to recompile, without
+  /// shutting the worker down - a later `restart()` still delivers a result. Fails if `watch`
+  /// hasn't been called yet.
+  #[napi(ts_return_type = "this")]
+  pub fn cancel(&mut self) -> Result<Instrument> {
+    self.cancel_internal()?;
+    Ok(self.clone())
+  }
 }
 
 #[cfg(test)]
@@ -441,7 +948,6 @@ mod tests {
   use crate::internal::options::{InstrumentOptions, SolcConfig};
   use foundry_compilers::artifacts::CompilerOutput;
   use foundry_compilers::solc::Solc;
-  use serde_json::{json, Value};
 
   const TARGET_CONTRACT: &str = r#"
 // SPDX-License-Identifier: MIT
@@ -544,7 +1050,11 @@ contract Target {
       config,
       ast: None,
       source_name: None,
+      source_text: None,
       default_contract: None,
+      coverage_sites: None,
+      transforms: Rc::new(RefCell::new(Vec::new())),
+      watcher: Rc::new(RefCell::new(None)),
     };
 
     instrument
@@ -614,22 +1124,13 @@ contract Target {
     let mut ast_value = serde_json::to_value(ast).expect("serialize ast");
     sanitize_ast_value(&mut ast_value);
 
-    let settings_value =
-      serde_json::to_value(&instrument.config.settings).expect("serialize settings");
-
-    let input = json!({
-      "language": "SolidityAST",
-      "sources": {
-        DEFAULT_VIRTUAL_SOURCE: {
-          "ast": ast_value
-        }
-      },
-      "settings": settings_value
-    });
-
-    let output: CompilerOutput = solc
-      .compile_as(&input)
-      .expect("round-trip compilation attempt");
+    let output: CompilerOutput = solc::recompile_ast(
+      &solc,
+      DEFAULT_VIRTUAL_SOURCE,
+      ast_value.clone(),
+      &instrument.config.settings,
+    )
+    .expect("round-trip compilation attempt");
 
     assert!(
       output.errors.is_empty(),
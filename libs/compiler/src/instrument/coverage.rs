@@ -0,0 +1,353 @@
+use foundry_compilers::artifacts::ast::{
+  BlockOrStatement, ContractDefinitionPart, FunctionDefinition, SourceUnit, SourceUnitPart,
+  Statement,
+};
+use foundry_compilers::artifacts::Settings;
+use foundry_compilers::solc::Solc;
+use serde_json::Value;
+
+use super::error::InstrumentError;
+use super::{parser, utils};
+
+/// One coverage counter `instrument_contract` inserted: `counter_id` indexes into the
+/// contract's injected `__covHits` mapping, and `src` is the original statement's
+/// `"start:length:fileIndex"` range, so a caller can translate a recorded hit back onto the
+/// source text the way solc's own source maps are read elsewhere in this crate.
+#[napi(object)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CoverageCounter {
+  pub counter_id: i64,
+  pub src: String,
+}
+
+/// Solidity source for the per-contract coverage ledger, stitched into every contract
+/// `instrument_contract` actually instrumented. Unlike `crate::ast::coverage`'s `__cov`/
+/// `__covCount` pair, a `public` mapping needs no hand-written getter - solc already generates
+/// one.
+pub fn storage_fragment_source() -> String {
+  "mapping(uint256 => uint256) public __covHits;".to_string()
+}
+
+const COUNTER_TEMPLATE_SOURCE: &str = r#"
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.0;
+
+contract __CovCounterTemplate {
+  mapping(uint256 => uint256) __covHits;
+
+  function __covCounter() internal {
+    __covHits[0] += 1;
+  }
+}
+"#;
+
+/// Allocates counter ids in the order statements are visited, shared across every function in a
+/// contract so a single `__covHits` mapping can address all of them without collisions.
+#[derive(Default)]
+struct CounterAllocator(i64);
+
+impl CounterAllocator {
+  fn next(&mut self) -> i64 {
+    let id = self.0;
+    self.0 += 1;
+    id
+  }
+}
+
+/// Instruments every instrumentable function body in the contract at `contract_idx`, inserting a
+/// `__covHits[id] += 1;` counter immediately before every statement (and, for `if`/`for`/`while`,
+/// in front of each branch body separately), returning one [`CoverageCounter`] per id recording
+/// the original statement's `src`. Functions with no body (interface stubs, `abstract`
+/// declarations, bare signatures) are left untouched entirely. `next_id` is threaded in (and
+/// advanced) by the caller, the way `crate::ast::coverage::instrument_contract` threads its own,
+/// so ids stay unique across every contract a single `instrument_coverage` call targets.
+pub fn instrument_contract(
+  unit: &mut SourceUnit,
+  contract_idx: usize,
+  solc: &Solc,
+  settings: &Settings,
+  next_id: &mut i64,
+) -> std::result::Result<Vec<CoverageCounter>, InstrumentError> {
+  let template = counter_template(solc, settings)?;
+  let mut allocator = CounterAllocator::default();
+  let mut counters = Vec::new();
+
+  let SourceUnitPart::ContractDefinition(contract) = unit
+    .nodes
+    .get_mut(contract_idx)
+    .ok_or_else(|| InstrumentError::InvalidContractStructure("Invalid contract index".into()))?
+  else {
+    return Err(InstrumentError::InvalidContractStructure(
+      "Target index is not a contract definition".into(),
+    ));
+  };
+
+  for member in &mut contract.nodes {
+    let ContractDefinitionPart::FunctionDefinition(function) = member else {
+      continue;
+    };
+    instrument_function(function, &template, &mut allocator, next_id, &mut counters)?;
+  }
+
+  Ok(counters)
+}
+
+/// Parses the `__covHits[0] += 1;` counter statement once per `instrument_contract` call, so
+/// every counter it inserts is a clone (renumbered by [`utils::clone_with_new_ids`] and
+/// re-indexed by [`set_counter_index`]) rather than a fresh solc invocation per statement.
+fn counter_template(
+  solc: &Solc,
+  settings: &Settings,
+) -> std::result::Result<Value, InstrumentError> {
+  let unit = parser::parse_source_ast(
+    COUNTER_TEMPLATE_SOURCE,
+    "__CovCounterTemplate.sol",
+    solc,
+    settings,
+  )?;
+
+  let function = unit
+    .nodes
+    .iter()
+    .find_map(|part| match part {
+      SourceUnitPart::ContractDefinition(contract) => contract.nodes.iter().find_map(|member| {
+        match member {
+          ContractDefinitionPart::FunctionDefinition(function)
+            if function.name == "__covCounter" =>
+          {
+            Some(function)
+          }
+          _ => None,
+        }
+      }),
+      _ => None,
+    })
+    .ok_or_else(|| {
+      InstrumentError::ParseFailed("Coverage counter template produced no function".to_string())
+    })?;
+
+  let statement = function
+    .body
+    .as_ref()
+    .and_then(|body| body.statements.first())
+    .ok_or_else(|| {
+      InstrumentError::ParseFailed("Coverage counter template produced no statement".to_string())
+    })?;
+
+  serde_json::to_value(statement).map_err(|err| InstrumentError::JsonError(err.to_string()))
+}
+
+/// A function with no implementation (interface stub, `abstract` declaration) has nothing to
+/// instrument. Otherwise, strip `view`/`pure` mutability before walking the body, since a counter
+/// write touches storage - mirroring how `Instrument::expose_functions_internal` promotes
+/// visibility rather than leaving a mismatched modifier behind.
+fn instrument_function(
+  function: &mut FunctionDefinition,
+  template: &Value,
+  allocator: &mut CounterAllocator,
+  next_id: &mut i64,
+  counters: &mut Vec<CoverageCounter>,
+) -> std::result::Result<(), InstrumentError> {
+  if function.body.is_none() {
+    return Ok(());
+  }
+  strip_view_or_pure(function)?;
+
+  let body = function.body.as_mut().expect("checked above");
+  instrument_statement_list(&mut body.statements, template, allocator, next_id, counters)
+}
+
+fn strip_view_or_pure(function: &mut FunctionDefinition) -> std::result::Result<(), InstrumentError> {
+  let mut value = serde_json::to_value(&*function)?;
+  let is_view_or_pure = matches!(
+    value.get("stateMutability").and_then(Value::as_str),
+    Some("view") | Some("pure")
+  );
+  if is_view_or_pure {
+    value["stateMutability"] = Value::String("nonpayable".to_string());
+    *function = serde_json::from_value(value)?;
+  }
+  Ok(())
+}
+
+/// Inserts a counter ahead of every statement in `statements`, then recurses into whatever
+/// nested block each statement introduces. Ternary conditionals aren't split into per-branch
+/// counters here - doing so would require rewriting the enclosing statement's control flow, not
+/// just prepending a sibling - so a statement built around one is still counted once, as a whole.
+fn instrument_statement_list(
+  statements: &mut Vec<Statement>,
+  template: &Value,
+  allocator: &mut CounterAllocator,
+  next_id: &mut i64,
+  counters: &mut Vec<CoverageCounter>,
+) -> std::result::Result<(), InstrumentError> {
+  let mut idx = 0;
+  while idx < statements.len() {
+    let counter_stmt =
+      counter_statement_for(&statements[idx], template, allocator, next_id, counters)?;
+    statements.insert(idx, counter_stmt);
+    idx += 1;
+
+    instrument_nested(&mut statements[idx], template, allocator, next_id, counters)?;
+    idx += 1;
+  }
+  Ok(())
+}
+
+fn counter_statement_for(
+  original: &Statement,
+  template: &Value,
+  allocator: &mut CounterAllocator,
+  next_id: &mut i64,
+  counters: &mut Vec<CoverageCounter>,
+) -> std::result::Result<Statement, InstrumentError> {
+  let original_value = serde_json::to_value(original)?;
+  let src = original_value
+    .get("src")
+    .and_then(Value::as_str)
+    .unwrap_or_default()
+    .to_string();
+
+  let counter_id = allocator.next();
+  counters.push(CoverageCounter { counter_id, src });
+
+  let mut stmt_value = template.clone();
+  set_counter_index(&mut stmt_value, counter_id);
+  let statement: Statement =
+    serde_json::from_value(stmt_value).map_err(|err| InstrumentError::JsonError(err.to_string()))?;
+  utils::clone_with_new_ids(&statement, next_id)
+}
+
+/// Rewrites the template's `__covHits[0]` index literal in place to address `counter_id`. Looks
+/// specifically for the `IndexAccess` node's `indexExpression` field - rather than the first
+/// number `Literal` found anywhere in the template - so this doesn't depend on `serde_json`'s
+/// object key ordering to skip over the unrelated `1` in `+= 1`.
+fn set_counter_index(value: &mut Value, counter_id: i64) {
+  if let Value::Object(map) = value {
+    if let Some(index_expression) = map.get_mut("indexExpression") {
+      if matches!(
+        index_expression.get("nodeType"),
+        Some(Value::String(kind)) if kind == "Literal"
+      ) {
+        patch_number_literal(index_expression, counter_id);
+        return;
+      }
+    }
+    for child in map.values_mut() {
+      set_counter_index(child, counter_id);
+    }
+  } else if let Value::Array(items) = value {
+    for item in items.iter_mut() {
+      set_counter_index(item, counter_id);
+    }
+  }
+}
+
+fn patch_number_literal(literal: &mut Value, counter_id: i64) {
+  let decimal = counter_id.to_string();
+  if let Value::Object(map) = literal {
+    map.insert("value".to_string(), Value::String(decimal.clone()));
+    map.insert(
+      "hexValue".to_string(),
+      Value::String(hex_encode_ascii(&decimal)),
+    );
+  }
+}
+
+fn hex_encode_ascii(text: &str) -> String {
+  text.bytes().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Recurses into whatever nested statement list `statement` introduces. Plain statements
+/// (`Return`, `ExpressionStatement`, ...) have none, so they're left as-is.
+fn instrument_nested(
+  statement: &mut Statement,
+  template: &Value,
+  allocator: &mut CounterAllocator,
+  next_id: &mut i64,
+  counters: &mut Vec<CoverageCounter>,
+) -> std::result::Result<(), InstrumentError> {
+  match statement {
+    Statement::Block(block) => {
+      instrument_statement_list(&mut block.statements, template, allocator, next_id, counters)
+    }
+    Statement::UncheckedBlock(unchecked) => instrument_statement_list(
+      &mut unchecked.statements,
+      template,
+      allocator,
+      next_id,
+      counters,
+    ),
+    Statement::IfStatement(if_stmt) => {
+      instrument_branch(&mut if_stmt.true_body, template, allocator, next_id, counters)?;
+      if let Some(false_body) = if_stmt.false_body.as_mut() {
+        instrument_branch(false_body, template, allocator, next_id, counters)?;
+      }
+      Ok(())
+    }
+    Statement::WhileStatement(while_stmt) => {
+      instrument_branch(&mut while_stmt.body, template, allocator, next_id, counters)
+    }
+    Statement::DoWhileStatement(do_stmt) => instrument_statement_list(
+      &mut do_stmt.body.statements,
+      template,
+      allocator,
+      next_id,
+      counters,
+    ),
+    Statement::ForStatement(for_stmt) => {
+      instrument_branch(&mut for_stmt.body, template, allocator, next_id, counters)
+    }
+    _ => Ok(()),
+  }
+}
+
+/// `IfStatement`/loop bodies are `BlockOrStatement`: either a braced [`Block`] or a single bare
+/// statement. A bare statement is first wrapped in a synthetic `Block` (reusing its own `src` and
+/// a fresh id) so a counter has somewhere to be prepended, then instrumented like any other body.
+fn instrument_branch(
+  body: &mut BlockOrStatement,
+  template: &Value,
+  allocator: &mut CounterAllocator,
+  next_id: &mut i64,
+  counters: &mut Vec<CoverageCounter>,
+) -> std::result::Result<(), InstrumentError> {
+  if matches!(body, BlockOrStatement::Statement(_)) {
+    wrap_in_synthetic_block(body, next_id)?;
+  }
+  let BlockOrStatement::Block(block) = body else {
+    unreachable!("wrapped above");
+  };
+  instrument_statement_list(&mut block.statements, template, allocator, next_id, counters)
+}
+
+fn wrap_in_synthetic_block(
+  body: &mut BlockOrStatement,
+  next_id: &mut i64,
+) -> std::result::Result<(), InstrumentError> {
+  let BlockOrStatement::Statement(statement) = body else {
+    return Ok(());
+  };
+
+  let statement_value = serde_json::to_value(&*statement)?;
+  let src = statement_value
+    .get("src")
+    .and_then(Value::as_str)
+    .unwrap_or_default()
+    .to_string();
+
+  *next_id += 1;
+  let block_value = serde_json::json!({
+    "id": *next_id,
+    "nodeType": "Block",
+    "src": src,
+    "statements": [statement_value],
+  });
+
+  // Deserialize straight into the enum (rather than constructing `BlockOrStatement::Block`
+  // directly) so this doesn't depend on whether that variant boxes its `Block` - the same shape
+  // solc itself emits for a braced `if`/loop body in this position.
+  *body = serde_json::from_value(block_value)
+    .map_err(|err| InstrumentError::JsonError(err.to_string()))?;
+  Ok(())
+}
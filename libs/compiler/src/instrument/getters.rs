@@ -0,0 +1,241 @@
+use std::collections::{HashMap, HashSet};
+
+use foundry_compilers::artifacts::ast::{SourceUnit, VariableDeclaration};
+use serde_json::Value;
+
+use super::error::InstrumentError;
+
+/// Every `StructDefinition` in the target `SourceUnit`, keyed by its AST node id so a
+/// `UserDefinedTypeName`'s `referencedDeclaration` can resolve straight to the struct it names.
+/// Read as raw JSON rather than the crate's typed AST nodes, the same way
+/// `crate::ast::core::function_state_mutability_keyword` reads `stateMutability` - `TypeName`'s
+/// exact variant shapes (`Mapping`, `ArrayTypeName`, `UserDefinedTypeName`, ...) aren't depended
+/// on anywhere else in this crate.
+pub type StructRegistry = HashMap<i64, Value>;
+
+pub fn build_struct_registry(unit: &SourceUnit) -> Result<StructRegistry, InstrumentError> {
+  let value = serde_json::to_value(unit)?;
+  let mut registry = StructRegistry::new();
+  collect_structs(&value, &mut registry);
+  Ok(registry)
+}
+
+fn collect_structs(value: &Value, out: &mut StructRegistry) {
+  match value {
+    Value::Object(map) => {
+      if matches!(map.get("nodeType"), Some(Value::String(kind)) if kind == "StructDefinition") {
+        if let Some(id) = map.get("id").and_then(Value::as_i64) {
+          out.insert(id, value.clone());
+        }
+      }
+      map.values().for_each(|child| collect_structs(child, out));
+    }
+    Value::Array(items) => items.iter().for_each(|item| collect_structs(item, out)),
+    _ => {}
+  }
+}
+
+/// One layer peeled off a `VariableDeclaration`'s type on the way to its leaf type: a `Mapping`
+/// contributes a key parameter, an `ArrayTypeName` contributes an index parameter. Both read left
+/// to right as the getter's parameter list and as successive index expressions against the state
+/// variable (`stateVar[key0][key1]...`).
+enum Layer {
+  MapKey { solidity_type: String },
+  ArrayIndex,
+}
+
+struct PeeledType {
+  layers: Vec<Layer>,
+  leaf: Value,
+}
+
+fn peel_type(type_name: &Value) -> PeeledType {
+  let mut layers = Vec::new();
+  let mut current = type_name.clone();
+  loop {
+    match current.get("nodeType").and_then(Value::as_str) {
+      Some("Mapping") => {
+        let key_type = current.get("keyType").cloned().unwrap_or(Value::Null);
+        let solidity_type = type_string_of(&key_type).unwrap_or_else(|| "uint256".to_string());
+        layers.push(Layer::MapKey { solidity_type });
+        current = current.get("valueType").cloned().unwrap_or(Value::Null);
+      }
+      Some("ArrayTypeName") => {
+        layers.push(Layer::ArrayIndex);
+        current = current.get("baseType").cloned().unwrap_or(Value::Null);
+      }
+      _ => break,
+    }
+  }
+  PeeledType { layers, leaf: current }
+}
+
+fn type_string_of(node: &Value) -> Option<String> {
+  node
+    .get("typeDescriptions")?
+    .get("typeString")?
+    .as_str()
+    .map(str::to_string)
+}
+
+fn resolve_struct<'a>(leaf: &Value, structs: &'a StructRegistry) -> Option<&'a Value> {
+  if leaf.get("nodeType").and_then(Value::as_str) != Some("UserDefinedTypeName") {
+    return None;
+  }
+  let id = leaf.get("referencedDeclaration").and_then(Value::as_i64)?;
+  structs.get(&id)
+}
+
+/// True if `type_name` is a mapping, or is (possibly through arrays/another struct) rooted in one
+/// - i.e. a type that can never cross the ABI boundary and so must be dropped from a decomposed
+/// getter's return list. `visiting` guards against a struct that (directly or transitively)
+/// contains itself.
+fn type_name_contains_mapping(
+  type_name: &Value,
+  structs: &StructRegistry,
+  visiting: &mut HashSet<i64>,
+) -> bool {
+  match type_name.get("nodeType").and_then(Value::as_str) {
+    Some("Mapping") => true,
+    Some("ArrayTypeName") => type_name
+      .get("baseType")
+      .map(|base| type_name_contains_mapping(base, structs, visiting))
+      .unwrap_or(false),
+    Some("UserDefinedTypeName") => resolve_struct(type_name, structs)
+      .map(|def| struct_contains_mapping(def, structs, visiting))
+      .unwrap_or(false),
+    _ => false,
+  }
+}
+
+fn struct_contains_mapping(
+  struct_def: &Value,
+  structs: &StructRegistry,
+  visiting: &mut HashSet<i64>,
+) -> bool {
+  let Some(id) = struct_def.get("id").and_then(Value::as_i64) else {
+    return false;
+  };
+  if !visiting.insert(id) {
+    return false;
+  }
+  struct_def
+    .get("members")
+    .and_then(Value::as_array)
+    .map(|members| {
+      members.iter().any(|member| {
+        member
+          .get("typeName")
+          .map(|type_name| type_name_contains_mapping(type_name, structs, visiting))
+          .unwrap_or(false)
+      })
+    })
+    .unwrap_or(false)
+}
+
+/// The three shapes `solc` won't synthesize a public getter for: a struct declaring a mapping
+/// member (anywhere under `leaf`'s own fields), a mapping of mappings whose value is a struct, and
+/// a multi-dimensional array of structs. Anything else - plain value types, single mappings,
+/// single arrays, structs with only value-typed fields - is left to the ordinary visibility flip.
+fn needs_explicit_getter(type_name: &Value, structs: &StructRegistry) -> bool {
+  let peeled = peel_type(type_name);
+  let Some(struct_def) = resolve_struct(&peeled.leaf, structs) else {
+    return false;
+  };
+
+  let mapping_layers = peeled
+    .layers
+    .iter()
+    .filter(|layer| matches!(layer, Layer::MapKey { .. }))
+    .count();
+  let array_layers = peeled.layers.len() - mapping_layers;
+
+  mapping_layers >= 2
+    || array_layers >= 2
+    || struct_contains_mapping(struct_def, structs, &mut HashSet::new())
+}
+
+/// Reference types need an explicit data location in a function signature; value types don't.
+/// `memory` is always valid on a `public view` getter's return type. Mirrors
+/// `crate::ast::core::with_memory_location_if_needed`, kept local since `Instrument` and `Ast`
+/// build their wrapper snippets independently.
+fn with_memory_location_if_needed(type_string: &str) -> String {
+  let needs_location = type_string.ends_with(']')
+    || type_string.starts_with("struct ")
+    || type_string.starts_with("mapping(")
+    || type_string == "string";
+  if needs_location {
+    format!("{type_string} memory")
+  } else {
+    type_string.to_string()
+  }
+}
+
+/// Builds a `<name>_state_getter(...)` snippet for `variable` if its type is one
+/// [`needs_explicit_getter`] flags, decomposing it into every field reachable without crossing a
+/// mapping - `None` for variables the simple visibility flip in
+/// `Instrument::expose_internal_variables` already handles, or whose every field turns out to be
+/// itself unreadable.
+pub fn getter_snippet(variable: &VariableDeclaration, structs: &StructRegistry) -> Option<String> {
+  let value = serde_json::to_value(variable).ok()?;
+  let type_name = value.get("typeName")?;
+  if !needs_explicit_getter(type_name, structs) {
+    return None;
+  }
+
+  let peeled = peel_type(type_name);
+  let struct_def = resolve_struct(&peeled.leaf, structs)?;
+
+  let mut params = Vec::with_capacity(peeled.layers.len());
+  let mut access = variable.name.clone();
+  for (idx, layer) in peeled.layers.iter().enumerate() {
+    let param_name = format!("key{idx}");
+    match layer {
+      Layer::MapKey { solidity_type } => params.push(format!("{solidity_type} {param_name}")),
+      Layer::ArrayIndex => params.push(format!("uint256 {param_name}")),
+    }
+    access = format!("{access}[{param_name}]");
+  }
+
+  let mut returns = Vec::new();
+  let mut field_exprs = Vec::new();
+  for member in struct_def
+    .get("members")
+    .and_then(Value::as_array)
+    .into_iter()
+    .flatten()
+  {
+    let Some(member_type_name) = member.get("typeName") else {
+      continue;
+    };
+    if type_name_contains_mapping(member_type_name, structs, &mut HashSet::new()) {
+      continue;
+    }
+    let Some(field_name) = member.get("name").and_then(Value::as_str) else {
+      continue;
+    };
+    let Some(type_string) = type_string_of(member_type_name) else {
+      continue;
+    };
+
+    returns.push(with_memory_location_if_needed(&type_string));
+    field_exprs.push(format!("{access}.{field_name}"));
+  }
+
+  if returns.is_empty() {
+    return None;
+  }
+
+  let param_list = params.join(", ");
+  let returns_clause = returns.join(", ");
+  let body = if field_exprs.len() == 1 {
+    format!("return {};", field_exprs[0])
+  } else {
+    format!("return ({});", field_exprs.join(", "))
+  };
+
+  Some(format!(
+    "function {name}_state_getter({param_list}) public view returns ({returns_clause}) {{ {body} }}",
+    name = variable.name,
+  ))
+}
@@ -0,0 +1,64 @@
+use foundry_compilers::artifacts::ast::{ContractDefinition, SourceUnit, SourceUnitPart};
+
+use super::{error::InstrumentError, utils};
+
+/// Locates the contract `instrument.rs` should inject fragments into - by name when
+/// `contract_name` is given, otherwise the last `ContractDefinition` in the unit (mirroring
+/// solc's "last contract in the file is the main one" convention used elsewhere in this crate).
+pub fn find_target_contract_index(
+  ast: &SourceUnit,
+  contract_name: Option<&str>,
+) -> Result<usize, InstrumentError> {
+  let mut fallback: Option<usize> = None;
+
+  for (idx, part) in ast.nodes.iter().enumerate() {
+    let SourceUnitPart::ContractDefinition(contract) = part else {
+      continue;
+    };
+    if let Some(target) = contract_name {
+      if contract.name == target {
+        return Ok(idx);
+      }
+    } else {
+      fallback = Some(idx);
+    }
+  }
+
+  match contract_name {
+    Some(name) => Err(InstrumentError::InvalidContractStructure(format!(
+      "Contract '{}' not found",
+      name
+    ))),
+    None => fallback
+      .ok_or_else(|| InstrumentError::InvalidContractStructure("No ContractDefinition found".to_string())),
+  }
+}
+
+/// Appends `fragment_contract`'s members onto the contract at `contract_idx`, after renumbering
+/// the fragment's ids to start above `max_target_id`. Unlike `ast::stitcher`'s version of this
+/// function, there's no `ResolveConflictStrategy` to pick between - the fragments instrument.rs
+/// stitches in (coverage counters, generated getters, transform output) are always freshly
+/// parsed from generated source, so they never collide with the target contract's existing
+/// members.
+pub fn stitch_fragment_nodes_into_contract(
+  target: &mut SourceUnit,
+  contract_idx: usize,
+  fragment_contract: &ContractDefinition,
+  max_target_id: i64,
+) -> Result<(), InstrumentError> {
+  let SourceUnitPart::ContractDefinition(target_contract) = target
+    .nodes
+    .get_mut(contract_idx)
+    .ok_or_else(|| InstrumentError::InvalidContractStructure("Invalid contract index".to_string()))?
+  else {
+    return Err(InstrumentError::InvalidContractStructure(
+      "Target index is not a contract".to_string(),
+    ));
+  };
+
+  let mut fragment = fragment_contract.clone();
+  utils::renumber_contract_definition(&mut fragment, max_target_id)?;
+
+  target_contract.nodes.extend(fragment.nodes);
+  Ok(())
+}
@@ -0,0 +1,41 @@
+use serde_json::Value;
+
+/// What a registered transform decided for one `ContractDefinitionPart`, read back from the raw
+/// value its JS callback returned. There's no dedicated `#[napi(object)]` shape for this - the
+/// callback's return value is read as untyped JSON (the same convention
+/// `crate::instrument::getters` reads `TypeName` subtrees with) since callers are expected to
+/// return a plain object literal, not a typed binding:
+///
+/// - `undefined`/`null` - leave the node untouched.
+/// - `{ inject: string[] }` - leave the node untouched, but parse each string as a Solidity
+///   fragment and splice the result in as a new sibling member of the same contract.
+/// - anything else - replace the node with the returned value, re-parsed as the same
+///   `ContractDefinitionPart` kind.
+pub enum TransformOutcome {
+  Unchanged,
+  Replace(Value),
+  InjectSiblings(Vec<String>),
+}
+
+pub fn read_outcome(value: Value) -> TransformOutcome {
+  if value.is_null() {
+    return TransformOutcome::Unchanged;
+  }
+  if let Some(siblings) = value.get("inject").and_then(Value::as_array) {
+    let snippets = siblings
+      .iter()
+      .filter_map(Value::as_str)
+      .map(str::to_string)
+      .collect();
+    return TransformOutcome::InjectSiblings(snippets);
+  }
+  TransformOutcome::Replace(value)
+}
+
+/// True if a registered transform (optionally scoped to `node_kind`) should run against `node`.
+pub fn matches_node_kind(node: &Value, node_kind: Option<&str>) -> bool {
+  match node_kind {
+    None => true,
+    Some(kind) => node.get("nodeType").and_then(Value::as_str) == Some(kind),
+  }
+}
@@ -61,6 +61,16 @@ fn walk_renumber(node: &mut Value, next_id: &mut i64) {
   }
 }
 
+pub fn clone_with_new_ids<T>(value: &T, next_id: &mut i64) -> std::result::Result<T, InstrumentError>
+where
+  T: Serialize + DeserializeOwned,
+{
+  let mut json = serde_json::to_value(value).map_err(|err| InstrumentError::JsonError(err.to_string()))?;
+  walk_renumber(&mut json, next_id);
+  sanitize_ast_value(&mut json);
+  serde_json::from_value(json).map_err(|err| InstrumentError::JsonError(err.to_string()))
+}
+
 pub fn renumber_contract_definition(
   contract: &mut ContractDefinition,
   start_from: i64,
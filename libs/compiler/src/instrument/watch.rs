@@ -0,0 +1,196 @@
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use foundry_compilers::artifacts::error::Severity;
+use foundry_compilers::artifacts::output_selection::OutputSelection;
+use foundry_compilers::solc::Solc;
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::Result;
+
+use crate::internal::{
+  errors::{map_napi_error, napi_error},
+  options::SolcConfig,
+  solc,
+};
+
+use super::utils::sanitize_ast_value;
+use super::{parser, Instrument};
+
+/// How long [`run_worker`] waits, after the first `Restart` of a burst, for another one to arrive
+/// before giving up and actually recompiling - coalesces the rapid-fire `restart()` calls a
+/// keystroke-driven editor integration sends into a single recompile of the latest source.
+const DEBOUNCE: Duration = Duration::from_millis(150);
+
+enum WorkerMessage {
+  Restart(String),
+  Cancel,
+  Shutdown,
+}
+
+/// Delivered to the `onResult` callback passed to [`Instrument::watch`] after every recompile the
+/// worker runs. `output` is the full recompiled `CompilerOutput`, pre-serialized to JSON text
+/// since turning it into a `JsUnknown` needs an `Env`, which only the threadsafe function's
+/// JS-thread callback has, not the worker thread itself.
+#[napi(object)]
+pub struct WatchResult {
+  pub ok: bool,
+  pub output: Option<String>,
+  pub error: Option<String>,
+}
+
+impl WatchResult {
+  fn ok(output: String) -> Self {
+    Self {
+      ok: true,
+      output: Some(output),
+      error: None,
+    }
+  }
+
+  fn err(message: impl Into<String>) -> Self {
+    Self {
+      ok: false,
+      output: None,
+      error: Some(message.into()),
+    }
+  }
+}
+
+/// Owns the background recompile worker [`Instrument::watch`] spawns: the `Sender` half of its
+/// message channel, and its `JoinHandle` so `Drop` can shut it down cleanly rather than leaking a
+/// thread parked on `recv()`.
+///
+/// Scope: the worker only re-parses `source` and runs it through the same `solc` recompile
+/// `Instrument::compile` uses - it does NOT replay `addTransform`-registered passes. Those run a
+/// JS callback per node, and neither `JsFunction` nor the `Ref` `add_transform` stores it behind
+/// is `Send`, so there's no sound way to invoke them from this thread. A watched session that also
+/// uses `addTransform` needs to call `applyTransforms` itself on each recompiled result.
+pub struct WatchHandle {
+  sender: mpsc::Sender<WorkerMessage>,
+  thread: Option<thread::JoinHandle<()>>,
+}
+
+impl WatchHandle {
+  pub fn spawn(
+    config: SolcConfig,
+    file_name: String,
+    initial_source: String,
+    callback: ThreadsafeFunction<WatchResult, ErrorStrategy::Fatal>,
+  ) -> Result<Self> {
+    let solc = map_napi_error(
+      solc::ensure_installed(&config.version),
+      "Failed to resolve solc for watch session",
+    )?;
+
+    let (sender, receiver) = mpsc::channel();
+    sender
+      .send(WorkerMessage::Restart(initial_source))
+      .map_err(|_| napi_error("Failed to queue initial watch recompile"))?;
+
+    let thread = thread::spawn(move || run_worker(receiver, solc, config, file_name, callback));
+
+    Ok(Self {
+      sender,
+      thread: Some(thread),
+    })
+  }
+
+  pub fn restart(&self, source: String) {
+    let _ = self.sender.send(WorkerMessage::Restart(source));
+  }
+
+  pub fn cancel(&self) {
+    let _ = self.sender.send(WorkerMessage::Cancel);
+  }
+}
+
+impl Drop for WatchHandle {
+  fn drop(&mut self) {
+    let _ = self.sender.send(WorkerMessage::Shutdown);
+    if let Some(thread) = self.thread.take() {
+      let _ = thread.join();
+    }
+  }
+}
+
+/// The worker's main loop: blocks on the next message, then - if it's a `Restart` - drains
+/// [`DEBOUNCE`] worth of any further messages before recompiling, so a burst of `Restart` calls
+/// collapses into one recompile of the last source in the burst. A `Cancel` seen while draining
+/// discards whatever `Restart` preceded it in the same burst.
+fn run_worker(
+  receiver: mpsc::Receiver<WorkerMessage>,
+  solc: Solc,
+  config: SolcConfig,
+  file_name: String,
+  callback: ThreadsafeFunction<WatchResult, ErrorStrategy::Fatal>,
+) {
+  while let Ok(message) = receiver.recv() {
+    let mut pending = match message {
+      WorkerMessage::Restart(source) => Some(source),
+      WorkerMessage::Cancel => continue,
+      WorkerMessage::Shutdown => return,
+    };
+
+    loop {
+      match receiver.recv_timeout(DEBOUNCE) {
+        Ok(WorkerMessage::Restart(source)) => pending = Some(source),
+        Ok(WorkerMessage::Cancel) => pending = None,
+        Ok(WorkerMessage::Shutdown) => return,
+        Err(mpsc::RecvTimeoutError::Timeout) => break,
+        Err(mpsc::RecvTimeoutError::Disconnected) => return,
+      }
+    }
+
+    let Some(source) = pending else {
+      continue;
+    };
+
+    let result = recompile(&solc, &config, &file_name, &source);
+    callback.call(result, ThreadsafeFunctionCallMode::NonBlocking);
+  }
+}
+
+/// Re-parses `source` and recompiles it with the same full (non-`stop_after`) output selection
+/// `Instrument::compile_internal` uses, so a watch session's results are shaped the same way a
+/// synchronous `compile()` call's are.
+fn recompile(solc: &Solc, config: &SolcConfig, file_name: &str, source: &str) -> WatchResult {
+  let parse_settings = Instrument::sanitize_settings(Some(config.settings.clone()));
+  let ast = match parser::parse_source_ast(source, file_name, solc, &parse_settings) {
+    Ok(ast) => ast,
+    Err(err) => return WatchResult::err(err.to_string()),
+  };
+
+  let mut ast_value = match serde_json::to_value(&ast) {
+    Ok(value) => value,
+    Err(err) => return WatchResult::err(err.to_string()),
+  };
+  sanitize_ast_value(&mut ast_value);
+
+  let mut full_settings = config.settings.clone();
+  full_settings.stop_after = None;
+  full_settings.output_selection = OutputSelection::default_output_selection();
+
+  let output = match solc::recompile_ast(solc, file_name, ast_value, &full_settings) {
+    Ok(output) => output,
+    Err(err) => return WatchResult::err(err.to_string()),
+  };
+
+  // `output.errors` carries every severity (see `ast/core.rs::build_validation_report`'s own
+  // comment and `compiler/output.rs::has_compiler_errors`) - a warning/info diagnostic on an
+  // otherwise-clean recompile must not flip a watch session to `WatchResult::err`.
+  let error_messages: Vec<&str> = output
+    .errors
+    .iter()
+    .filter(|error| error.severity == Severity::Error)
+    .map(|error| error.formatted_message.as_deref().unwrap_or(&error.message))
+    .collect();
+  if !error_messages.is_empty() {
+    return WatchResult::err(error_messages.join("\n"));
+  }
+
+  match serde_json::to_string(&output) {
+    Ok(text) => WatchResult::ok(text),
+    Err(err) => WatchResult::err(err.to_string()),
+  }
+}
@@ -0,0 +1,21 @@
+use sha3::{Digest, Keccak256};
+
+/// Canonical content/digest hash backing every on-disk cache key in this crate
+/// ([`crate::ast::parse_cache`], [`crate::compiler::cache`], [`crate::compiler::incremental`],
+/// [`crate::compile::cache`]) - `sha3::Keccak256`, the same primitive
+/// [`crate::contract::linker`] already uses for placeholder ids, rather than each cache rolling
+/// its own `std::collections::hash_map::DefaultHasher`. `DefaultHasher`'s output isn't guaranteed
+/// stable across Rust versions or even process runs, which is fine for an in-memory `HashMap` but
+/// silently invalidates (or, worse, collides differently across runs of) a key that gets persisted
+/// to disk and compared against in a later process.
+pub(crate) fn keccak_hex(bytes: &[u8]) -> String {
+  hex::encode(Keccak256::digest(bytes))
+}
+
+/// [`keccak_hex`] over `parts` joined with `\0`, for composite keys made of several independent
+/// fields (content hash, solc version, settings JSON, ...) where concatenating them without a
+/// separator could let two different splits hash the same.
+pub(crate) fn keccak_hex_parts<'a>(parts: impl IntoIterator<Item = &'a str>) -> String {
+  let joined = parts.into_iter().collect::<Vec<_>>().join("\0");
+  keccak_hex(joined.as_bytes())
+}
@@ -9,12 +9,14 @@ use foundry_compilers::artifacts::{
 use foundry_compilers::solc::SolcLanguage as FoundrySolcLanguage;
 use napi::bindgen_prelude::*;
 use napi::{Env, JsObject, JsUnknown, NapiRaw, ValueType};
-use semver::Version;
+use semver::{Version, VersionReq};
 
-use crate::internal::errors::{map_napi_error, napi_error};
+use crate::internal::errors::{map_err_with_context, map_napi_error, napi_error};
 use crate::internal::path::{to_path_set, to_path_vec};
 use crate::internal::settings::{
-  merge_settings, sanitize_settings, CompilerSettingsOptions, JsCompilerSettingsOptions,
+  apply_requested_artifacts, apply_requested_outputs, apply_requested_outputs_to_selection,
+  merge_settings, merge_settings_reporting, sanitize_settings, ArtifactKind,
+  CompilerSettingsOptions, JsCompilerSettingsOptions, RequestedOutputKind, SettingsMergeStrategy,
   VyperSettingsOptions,
 };
 
@@ -71,6 +73,16 @@ fn solc_language_from(language: CompilerLanguage) -> Result<FoundrySolcLanguage>
   }
 }
 
+/// Selects how a project/contract compile's artifacts are written to disk (see
+/// `compiler::artifact_output`). `Foundry` is the default and relies entirely on
+/// foundry-compilers' own project writer; `Hardhat` additionally mirrors Hardhat's
+/// `<ContractName>.json` artifact shape so Hardhat-based tooling can read tevm's output directly.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ArtifactFormat {
+  Foundry,
+  Hardhat,
+}
+
 #[derive(Clone, Debug)]
 pub struct VyperCompilerSettings {
   pub path: Option<PathBuf>,
@@ -126,12 +138,30 @@ impl Default for VyperCompilerSettings {
 pub struct CompilerConfig {
   pub language: CompilerLanguage,
   pub solc_version: Version,
+  /// When set, `solc_version` is only a fallback: `ProjectRunner` instead derives the effective
+  /// version per compilation unit from the intersection of every source's `pragma solidity`
+  /// requirement, via `SolcVersionSelector::AutoDetect`.
+  pub auto_detect_solc_version: bool,
+  /// Per-path-pattern solc version/evm version/optimizer-runs bounds, checked against whichever
+  /// source matches each pattern. See [`CompilationRestriction`].
+  pub restrictions: Vec<CompilationRestriction>,
+  /// When non-empty, rewrites both `solc_settings.output_selection` and
+  /// `vyper_settings.output_selection` down to exactly these outputs, skipping everything else -
+  /// in particular the otherwise-default JSON AST output. See [`RequestedOutputKind`].
+  pub requested_outputs: Vec<RequestedOutputKind>,
   pub solc_settings: Settings,
   pub vyper_settings: VyperCompilerSettings,
+  /// Override key paths from the most recent `CompilerConfigOptions::settings` that
+  /// `merge_settings_reporting` couldn't match against the `CompilerSettingsOptions` schema - a
+  /// typo like `optmizer`, or a solc option this crate doesn't model. Empty unless overrides were
+  /// ever applied. See `internal::settings::merge_settings_reporting`.
+  pub ignored_settings_keys: Vec<String>,
   pub cache_enabled: bool,
   pub offline_mode: bool,
   pub no_artifacts: bool,
   pub build_info_enabled: bool,
+  pub emit_sourceless_artifacts: bool,
+  pub artifact_format: ArtifactFormat,
   pub slash_paths: bool,
   pub solc_jobs: Option<usize>,
   pub sparse_output: bool,
@@ -139,9 +169,34 @@ pub struct CompilerConfig {
   pub include_paths: BTreeSet<PathBuf>,
   pub library_paths: Vec<PathBuf>,
   pub remappings: Vec<Remapping>,
+  pub auto_detect_remappings: bool,
   pub ignored_file_paths: BTreeSet<PathBuf>,
   pub ignored_error_codes: Vec<u64>,
+  pub deny_warnings: bool,
   pub compiler_severity_filter: Severity,
+  /// Per-error-code severity promotion/demotion, modeled on rustc's allow/warn/deny lint levels:
+  /// a diagnostic whose solc/vyper `error_code` appears here is reported at this severity instead
+  /// of whatever solc/vyper itself assigned it. Checked before `promote_all_warnings_to_errors`,
+  /// which in turn is checked before falling back to the diagnostic's own severity. See
+  /// `compiler::output::apply_severity_overrides`.
+  pub severity_overrides: BTreeMap<u64, Severity>,
+  /// Catch-all fallback for `severity_overrides`: promotes every remaining `Warning` (one without
+  /// its own entry in `severity_overrides`) to `Error`.
+  pub promote_all_warnings_to_errors: bool,
+  /// Error codes a caller has chosen to treat as non-fatal, without hiding them: unlike
+  /// `ignored_error_codes`, which drops matching diagnostics from `errors` entirely, a suppressed
+  /// code still appears in the full diagnostics list - it's just excluded from
+  /// `CompileOutput::has_compiler_errors`, so CI can keep the signal while not failing the build on
+  /// it. See `compiler::output::CompileOutput::is_suppressed`.
+  pub suppressed_warning_codes: BTreeSet<u64>,
+  /// Named, inheritable layers of [`CompilerConfigOptions`], switched between by name via
+  /// [`CompilerConfigBuilder::with_profile`]. Populated by `CompilerConfigOptions::profiles` like
+  /// any other field - it carries no meaning on its own until `with_profile` resolves one.
+  pub profiles: BTreeMap<String, CompilerConfigProfile>,
+  /// Named config overlays fanned out into sibling configs by
+  /// [`CompilerConfigBuilder::build_revisions`]. Populated by `CompilerConfigOptions::revisions`
+  /// like any other field - it carries no meaning on its own until `build_revisions` resolves it.
+  pub revisions: Vec<RevisionSpec>,
 }
 
 impl Default for CompilerConfig {
@@ -150,12 +205,18 @@ impl Default for CompilerConfig {
       language: CompilerLanguage::Solidity,
       solc_version: crate::internal::solc::default_version()
         .unwrap_or_else(|_| Version::new(0, 8, 30)),
+      auto_detect_solc_version: false,
+      restrictions: Vec::new(),
+      requested_outputs: Vec::new(),
       solc_settings: Settings::default(),
       vyper_settings: VyperCompilerSettings::default(),
+      ignored_settings_keys: Vec::new(),
       cache_enabled: true,
       offline_mode: false,
       no_artifacts: false,
       build_info_enabled: false,
+      emit_sourceless_artifacts: false,
+      artifact_format: ArtifactFormat::Foundry,
       slash_paths: true,
       solc_jobs: None,
       sparse_output: false,
@@ -163,9 +224,16 @@ impl Default for CompilerConfig {
       include_paths: BTreeSet::new(),
       library_paths: Vec::new(),
       remappings: Vec::new(),
+      auto_detect_remappings: true,
       ignored_file_paths: BTreeSet::new(),
       ignored_error_codes: Vec::new(),
+      deny_warnings: false,
       compiler_severity_filter: Severity::Error,
+      severity_overrides: BTreeMap::new(),
+      promote_all_warnings_to_errors: false,
+      suppressed_warning_codes: BTreeSet::new(),
+      profiles: BTreeMap::new(),
+      revisions: Vec::new(),
     }
   }
 }
@@ -203,6 +271,203 @@ pub struct SolcConfigOptions {
   pub resolved_settings: Option<Settings>,
 }
 
+/// Overrides how the effective solc version for a compile is picked. `Pinned` behaves exactly
+/// like setting `SolcConfigOptions::version` directly - it's here mainly so a caller can flip
+/// back out of `AutoDetect` with one field. `AutoDetect` instead derives the version per
+/// compilation unit from the intersection of every involved source's `pragma solidity`
+/// requirement, picking the highest known version that satisfies all of them; see
+/// [`CompilerConfig::auto_detect_solc_version`] and `ProjectRunner::compile`/
+/// `compile_multi_version` for where that resolution actually happens.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SolcVersionSelector {
+  Pinned(Version),
+  AutoDetect,
+}
+
+/// Bounds a build can place on whichever sources match `path_pattern` (a `*`-only glob, same
+/// syntax as `compile_builder`'s file filter - enough for an extension like `*.t.sol` or a
+/// directory prefix like `src/legacy/*`). `min_solc`/`max_solc` are expressed as `VersionReq`s
+/// (e.g. `>=0.7.0`/`<0.8.0`) rather than plain versions so a bound can itself be a range; a
+/// matching source's *resolved* solc version must satisfy both. `min_evm_version`/`max_evm_version`
+/// and the optimizer-runs bounds are checked against the shared `solc_settings` every group
+/// compiles with today, since per-group settings overrides aren't threaded through
+/// `ProjectRunner::compile_multi_version` yet - only the resolved solc version truly varies per
+/// group.
+#[derive(Clone, Debug)]
+pub struct CompilationRestriction {
+  pub path_pattern: String,
+  pub min_solc: Option<VersionReq>,
+  pub max_solc: Option<VersionReq>,
+  pub min_evm_version: Option<crate::internal::settings::EvmVersion>,
+  pub max_evm_version: Option<crate::internal::settings::EvmVersion>,
+  pub min_optimizer_runs: Option<u32>,
+  pub max_optimizer_runs: Option<u32>,
+}
+
+impl CompilationRestriction {
+  fn matches_path(&self, path: &str) -> bool {
+    restriction_glob_match(&self.path_pattern, path)
+  }
+
+  /// Rejects a restriction whose own bounds can never be satisfied together, e.g. a higher
+  /// `min_evm_version` than `max_evm_version`. Run once at config build time, independent of any
+  /// source set.
+  fn validate_self_consistent(&self) -> Result<()> {
+    if let (Some(min), Some(max)) = (self.min_evm_version, self.max_evm_version) {
+      if min > max {
+        return Err(napi_error(format!(
+          "Compilation restriction `{}` has min_evm_version {min:?} above max_evm_version {max:?}",
+          self.path_pattern
+        )));
+      }
+    }
+    if let (Some(min), Some(max)) = (self.min_optimizer_runs, self.max_optimizer_runs) {
+      if min > max {
+        return Err(napi_error(format!(
+          "Compilation restriction `{}` has min_optimizer_runs {min} above max_optimizer_runs \
+           {max}",
+          self.path_pattern
+        )));
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Lightweight `*`-only glob matcher for [`CompilationRestriction::path_pattern`]: splits `pattern`
+/// on `*` and checks the fragments occur in `text` in order, anchoring the first/last fragment to
+/// the start/end unless `pattern` itself begins/ends with `*`.
+fn restriction_glob_match(pattern: &str, text: &str) -> bool {
+  if !pattern.contains('*') {
+    return text == pattern;
+  }
+
+  let mut rest = text;
+  let parts: Vec<&str> = pattern.split('*').collect();
+  for (index, part) in parts.iter().enumerate() {
+    if part.is_empty() {
+      continue;
+    }
+    if index == 0 {
+      if !rest.starts_with(part) {
+        return false;
+      }
+      rest = &rest[part.len()..];
+      continue;
+    }
+    match rest.find(part) {
+      Some(found) => rest = &rest[found + part.len()..],
+      None => return false,
+    }
+  }
+
+  pattern.ends_with('*') || rest.is_empty()
+}
+
+/// The first restriction (in declaration order) whose `path_pattern` matches `path`, if any.
+pub(crate) fn restriction_for_path<'a>(
+  restrictions: &'a [CompilationRestriction],
+  path: &str,
+) -> Option<&'a CompilationRestriction> {
+  restrictions
+    .iter()
+    .find(|restriction| restriction.matches_path(path))
+}
+
+/// Checks every source in `resolved_versions` against whichever [`CompilationRestriction`] its
+/// path matches: the resolved solc version against `min_solc`/`max_solc`, and the evm
+/// version/optimizer runs in `settings` (shared by every group today) against the restriction's
+/// respective bounds. Every violation is accumulated into one error naming the offending file and
+/// bound, rather than failing on the first, so a multi-file violation surfaces all at once.
+pub(crate) fn check_restrictions(
+  restrictions: &[CompilationRestriction],
+  resolved_versions: &BTreeMap<String, Version>,
+  settings: &Settings,
+) -> crate::internal::errors::Result<()> {
+  if restrictions.is_empty() {
+    return Ok(());
+  }
+
+  let settings_json = map_err_with_context(
+    serde_json::to_value(settings),
+    "Failed to inspect compiler settings for restriction checks",
+  )?;
+  let evm_version: Option<crate::internal::settings::EvmVersion> = settings_json
+    .get("evmVersion")
+    .cloned()
+    .and_then(|value| serde_json::from_value(value).ok());
+  let optimizer_runs = settings_json
+    .get("optimizer")
+    .and_then(|optimizer| optimizer.get("runs"))
+    .and_then(|runs| runs.as_u64())
+    .map(|runs| runs as u32);
+
+  let mut violations = Vec::new();
+  for (path, version) in resolved_versions {
+    let Some(restriction) = restriction_for_path(restrictions, path) else {
+      continue;
+    };
+
+    if let Some(min_solc) = &restriction.min_solc {
+      if !min_solc.matches(version) {
+        violations.push(format!(
+          "{path}: resolved solc {version} doesn't satisfy min_solc `{min_solc}` (`{}`)",
+          restriction.path_pattern
+        ));
+      }
+    }
+    if let Some(max_solc) = &restriction.max_solc {
+      if !max_solc.matches(version) {
+        violations.push(format!(
+          "{path}: resolved solc {version} doesn't satisfy max_solc `{max_solc}` (`{}`)",
+          restriction.path_pattern
+        ));
+      }
+    }
+    if let (Some(min), Some(actual)) = (restriction.min_evm_version, evm_version) {
+      if actual < min {
+        violations.push(format!(
+          "{path}: evm version {actual:?} is below min_evm_version {min:?} (`{}`)",
+          restriction.path_pattern
+        ));
+      }
+    }
+    if let (Some(max), Some(actual)) = (restriction.max_evm_version, evm_version) {
+      if actual > max {
+        violations.push(format!(
+          "{path}: evm version {actual:?} is above max_evm_version {max:?} (`{}`)",
+          restriction.path_pattern
+        ));
+      }
+    }
+    if let (Some(min), Some(actual)) = (restriction.min_optimizer_runs, optimizer_runs) {
+      if actual < min {
+        violations.push(format!(
+          "{path}: optimizer runs {actual} is below min_optimizer_runs {min} (`{}`)",
+          restriction.path_pattern
+        ));
+      }
+    }
+    if let (Some(max), Some(actual)) = (restriction.max_optimizer_runs, optimizer_runs) {
+      if actual > max {
+        violations.push(format!(
+          "{path}: optimizer runs {actual} is above max_optimizer_runs {max} (`{}`)",
+          restriction.path_pattern
+        ));
+      }
+    }
+  }
+
+  if violations.is_empty() {
+    Ok(())
+  } else {
+    Err(crate::internal::errors::Error::new(format!(
+      "Compilation restriction violation(s):\n  - {}",
+      violations.join("\n  - ")
+    )))
+  }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct VyperConfigOptions {
   pub path: Option<PathBuf>,
@@ -219,11 +484,20 @@ pub struct VyperConfigOptions {
 pub struct CompilerConfigOptions {
   pub compiler: Option<CompilerLanguage>,
   pub solc: SolcConfigOptions,
+  pub solc_version_selector: Option<SolcVersionSelector>,
+  pub restrictions: Option<Vec<CompilationRestriction>>,
+  pub requested_outputs: Option<Vec<RequestedOutputKind>>,
+  /// Plans the minimal `output_selection` needed to produce exactly these artifacts. See
+  /// [`CompilerConfigBuilder::with_requested_artifacts`] for the intersect-with-`resolved_settings`
+  /// behavior when both are present.
+  pub requested_artifacts: Option<Vec<ArtifactKind>>,
   pub vyper: VyperConfigOptions,
   pub cache_enabled: Option<bool>,
   pub offline_mode: Option<bool>,
   pub no_artifacts: Option<bool>,
   pub build_info_enabled: Option<bool>,
+  pub emit_sourceless_artifacts: Option<bool>,
+  pub artifact_format: Option<ArtifactFormat>,
   pub slash_paths: Option<bool>,
   pub solc_jobs: Option<Option<usize>>,
   pub sparse_output: Option<bool>,
@@ -231,27 +505,113 @@ pub struct CompilerConfigOptions {
   pub include_paths: Option<BTreeSet<PathBuf>>,
   pub library_paths: Option<Vec<PathBuf>>,
   pub remappings: Option<Vec<Remapping>>,
+  pub auto_detect_remappings: Option<bool>,
   pub ignored_file_paths: Option<BTreeSet<PathBuf>>,
   pub ignored_error_codes: Option<Vec<u64>>,
+  pub deny_warnings: Option<bool>,
   pub compiler_severity_filter: Option<Severity>,
+  pub severity_overrides: Option<BTreeMap<u64, Severity>>,
+  pub promote_all_warnings_to_errors: Option<bool>,
+  pub suppressed_warning_codes: Option<BTreeSet<u64>>,
+  /// Named, inheritable layers switched between by name via
+  /// [`CompilerConfigBuilder::with_profile`]. See [`CompilerConfigProfile`].
+  pub profiles: Option<BTreeMap<String, CompilerConfigProfile>>,
+  /// Named overlays fanned out into sibling configs by
+  /// [`CompilerConfigBuilder::build_revisions`]. See [`RevisionSpec`].
+  pub revisions: Option<Vec<RevisionSpec>>,
+}
+
+/// One named layer of a [`CompilerConfigOptions::profiles`] map: `inherits` names another profile
+/// in the same map whose own `options` are folded in first, so a child profile only needs to
+/// declare what it changes relative to its parent. See
+/// [`CompilerConfigBuilder::with_profile`] for how the chain is resolved and applied.
+#[derive(Clone, Debug, Default)]
+pub struct CompilerConfigProfile {
+  pub inherits: Option<String>,
+  pub options: CompilerConfigOptions,
+}
+
+/// One named variant in a [`CompilerConfigOptions::revisions`] fan-out, borrowed from
+/// compiletest's `revisions` header: `options` is a partial overlay applied on top of the
+/// otherwise-shared base config, so e.g. `{ name: "via-ir", options: { via_ir: true } }` and
+/// `{ name: "legacy", options: { via_ir: false } }` can compile the same sources two ways in one
+/// [`CompilerConfigBuilder::build_revisions`] call. Unlike [`CompilerConfigProfile`], revisions
+/// don't inherit from one another - each is layered directly on the shared base.
+#[derive(Clone, Debug, Default)]
+pub struct RevisionSpec {
+  pub name: String,
+  pub options: CompilerConfigOptions,
+}
+
+/// How `expose_internal_variables`/`expose_internal_functions` make a private/internal member
+/// reachable. `InPlace` (the default) mutates the member's own `visibility` to `public`, which is
+/// cheap but breaks `super` dispatch/virtual-override resolution since the member keeps its
+/// original AST node. `Wrapper` leaves the member untouched and instead stitches a synthetic
+/// public forwarder alongside it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Default)]
+pub enum ExposeStrategy {
+  #[default]
+  InPlace,
+  Wrapper,
+}
+
+/// How `inject_fragment_contract`/`inject_fragment_string`/`inject_fragment_ast` handle a fragment
+/// node whose id collides with one already present in the target unit. `Safe` (the default) skips
+/// the colliding node and reports it in the caller's diagnostics; `Replace` overwrites the
+/// existing node with the incoming fragment's.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Default)]
+pub enum ResolveConflictStrategy {
+  #[default]
+  Safe,
+  Replace,
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct AstConfigOptions {
   pub solc: SolcConfigOptions,
   pub instrumented_contract: Option<String>,
+  pub expose_strategy: Option<ExposeStrategy>,
+  pub resolve_conflict_strategy: Option<ResolveConflictStrategy>,
+  /// Minimum level the crate's internal `log` calls are emitted at. `None` leaves whatever level
+  /// the process already has configured untouched.
+  pub logging_level: Option<log::LevelFilter>,
+  /// Import remappings (e.g. `@openzeppelin/=node_modules/@openzeppelin/`) applied when parsing a
+  /// target that reaches its contract through a remapped import. Folded into
+  /// `solc.settings.remappings` - the same field `CompilerConfigOptions::solc` overlays - rather
+  /// than threaded separately, so a caller that already sets `solcSettings.remappings` directly
+  /// keeps working unchanged.
+  pub remappings: Option<Vec<String>>,
+  /// How many parsed `SourceUnit`s `ast::parse_cache::ParseCache` keeps in memory before evicting
+  /// the least-recently-used entry. `None` applies `ParseCache`'s own default.
+  pub parse_cache_capacity: Option<usize>,
+  /// A directory `ast::parse_cache::ParseCache` persists parsed AST JSON under, so a later process
+  /// re-parsing the same source/solc-version/settings skips solc entirely. `None` (the default)
+  /// keeps the cache in memory only, scoped to the current process.
+  pub parse_cache_dir: Option<PathBuf>,
 }
 
 impl AstConfigOptions {
   pub fn instrumented_contract(&self) -> Option<&str> {
     self.instrumented_contract.as_deref()
   }
+
+  pub fn expose_strategy(&self) -> ExposeStrategy {
+    self.expose_strategy.unwrap_or_default()
+  }
+
+  pub fn resolve_conflict_strategy(&self) -> ResolveConflictStrategy {
+    self.resolve_conflict_strategy.unwrap_or_default()
+  }
 }
 
 #[derive(Clone, Debug)]
 pub struct AstConfig {
   pub solc: SolcConfig,
   pub instrumented_contract: Option<String>,
+  pub resolve_conflict_strategy: ResolveConflictStrategy,
+  pub logging_level: Option<log::LevelFilter>,
+  pub parse_cache_capacity: Option<usize>,
+  pub parse_cache_dir: Option<PathBuf>,
 }
 
 impl AstConfig {
@@ -268,6 +628,12 @@ impl AstConfig {
     Ok(AstConfig {
       solc,
       instrumented_contract: options.and_then(|opts| opts.instrumented_contract.clone()),
+      resolve_conflict_strategy: options
+        .and_then(|opts| opts.resolve_conflict_strategy)
+        .unwrap_or_default(),
+      logging_level: options.and_then(|opts| opts.logging_level),
+      parse_cache_capacity: options.and_then(|opts| opts.parse_cache_capacity),
+      parse_cache_dir: options.and_then(|opts| opts.parse_cache_dir.clone()),
     })
   }
 
@@ -277,9 +643,24 @@ impl AstConfig {
       .instrumented_contract
       .clone()
       .or_else(|| self.instrumented_contract.clone());
+    let resolve_conflict_strategy = overrides
+      .resolve_conflict_strategy
+      .unwrap_or(self.resolve_conflict_strategy);
+    let logging_level = overrides.logging_level.or(self.logging_level);
+    let parse_cache_capacity = overrides
+      .parse_cache_capacity
+      .or(self.parse_cache_capacity);
+    let parse_cache_dir = overrides
+      .parse_cache_dir
+      .clone()
+      .or_else(|| self.parse_cache_dir.clone());
     Ok(AstConfig {
       solc,
       instrumented_contract,
+      resolve_conflict_strategy,
+      logging_level,
+      parse_cache_capacity,
+      parse_cache_dir,
     })
   }
 
@@ -312,6 +693,9 @@ impl TryFrom<&JsCompilerConfigOptions> for CompilerConfigOptions {
     if let Some(version) = options.solc_version.as_ref() {
       overrides.solc.version = Some(parse_version(version)?);
     }
+    if options.auto_detect_solc_version.unwrap_or(false) {
+      overrides.solc_version_selector = Some(SolcVersionSelector::AutoDetect);
+    }
 
     if let Some(language) = options.language {
       overrides.compiler = Some(language.into());
@@ -325,6 +709,8 @@ impl TryFrom<&JsCompilerConfigOptions> for CompilerConfigOptions {
     overrides.offline_mode = options.offline_mode;
     overrides.no_artifacts = options.no_artifacts;
     overrides.build_info_enabled = options.build_info_enabled;
+    overrides.emit_sourceless_artifacts = options.emit_sourceless_artifacts;
+    overrides.artifact_format = options.artifact_format.map(ArtifactFormat::from);
     overrides.slash_paths = options.slash_paths;
     overrides.solc_jobs = options
       .solc_jobs
@@ -343,6 +729,7 @@ impl TryFrom<&JsCompilerConfigOptions> for CompilerConfigOptions {
       .as_ref()
       .map(|paths| to_path_vec(paths.as_slice()));
     overrides.remappings = map_remappings(options.remappings.as_ref())?;
+    overrides.auto_detect_remappings = options.auto_detect_remappings;
     overrides.ignored_file_paths = options
       .ignored_paths
       .as_ref()
@@ -351,15 +738,62 @@ impl TryFrom<&JsCompilerConfigOptions> for CompilerConfigOptions {
       .ignored_error_codes
       .as_ref()
       .map(|codes| codes.iter().map(|code| *code as u64).collect());
+    overrides.deny_warnings = options.deny_warnings;
 
     if let Some(severity) = options.compiler_severity.as_ref() {
       overrides.compiler_severity_filter = Some(parse_severity(severity)?);
     }
 
+    if let Some(severity_overrides) = options.severity_overrides.as_ref() {
+      let mut parsed = BTreeMap::new();
+      for (code, severity) in severity_overrides {
+        let code: u64 = code.trim().parse().map_err(|_| {
+          napi_error(format!("Invalid error code \"{code}\" in severity_overrides"))
+        })?;
+        parsed.insert(code, parse_severity(severity)?);
+      }
+      overrides.severity_overrides = Some(parsed);
+    }
+    overrides.promote_all_warnings_to_errors = options.promote_all_warnings_to_errors;
+    overrides.suppressed_warning_codes = options
+      .suppressed_warning_codes
+      .as_ref()
+      .map(|codes| codes.iter().map(|code| *code as u64).collect());
+
     if let Some(vyper) = options.vyper.as_ref() {
       overrides.vyper = VyperConfigOptions::try_from(vyper)?;
     }
 
+    if let Some(restrictions) = options.restrictions.as_ref() {
+      overrides.restrictions = Some(
+        restrictions
+          .iter()
+          .map(CompilationRestriction::try_from)
+          .collect::<Result<Vec<_>>>()?,
+      );
+    }
+
+    overrides.requested_outputs = options.requested_outputs.clone();
+    overrides.requested_artifacts = options.requested_artifacts.clone();
+
+    if let Some(profiles) = options.profiles.as_ref() {
+      overrides.profiles = Some(
+        profiles
+          .iter()
+          .map(|(name, profile)| Ok((name.clone(), CompilerConfigProfile::try_from(profile)?)))
+          .collect::<Result<BTreeMap<_, _>>>()?,
+      );
+    }
+
+    if let Some(revisions) = options.revisions.as_ref() {
+      overrides.revisions = Some(
+        revisions
+          .iter()
+          .map(RevisionSpec::try_from)
+          .collect::<Result<Vec<_>>>()?,
+      );
+    }
+
     Ok(overrides)
   }
 }
@@ -413,6 +847,78 @@ impl TryFrom<JsVyperCompilerConfig> for VyperConfigOptions {
   }
 }
 
+impl TryFrom<&JsCompilationRestriction> for CompilationRestriction {
+  type Error = napi::Error;
+
+  fn try_from(options: &JsCompilationRestriction) -> Result<Self> {
+    Ok(CompilationRestriction {
+      path_pattern: options.path_pattern.clone(),
+      min_solc: options
+        .min_solc
+        .as_deref()
+        .map(parse_version_req)
+        .transpose()?,
+      max_solc: options
+        .max_solc
+        .as_deref()
+        .map(parse_version_req)
+        .transpose()?,
+      min_evm_version: options.min_evm_version,
+      max_evm_version: options.max_evm_version,
+      min_optimizer_runs: options.min_optimizer_runs,
+      max_optimizer_runs: options.max_optimizer_runs,
+    })
+  }
+}
+
+impl TryFrom<&JsCompilerConfigProfile> for CompilerConfigProfile {
+  type Error = napi::Error;
+
+  fn try_from(profile: &JsCompilerConfigProfile) -> Result<Self> {
+    Ok(CompilerConfigProfile {
+      inherits: profile.inherits.clone(),
+      options: profile
+        .options
+        .as_ref()
+        .map(CompilerConfigOptions::try_from)
+        .transpose()?
+        .unwrap_or_default(),
+    })
+  }
+}
+
+impl TryFrom<&JsRevisionSpec> for RevisionSpec {
+  type Error = napi::Error;
+
+  fn try_from(revision: &JsRevisionSpec) -> Result<Self> {
+    Ok(RevisionSpec {
+      name: revision.name.clone(),
+      options: revision
+        .options
+        .as_ref()
+        .map(CompilerConfigOptions::try_from)
+        .transpose()?
+        .unwrap_or_default(),
+    })
+  }
+}
+
+fn parse_version_req(value: &str) -> Result<VersionReq> {
+  map_napi_error(
+    VersionReq::parse(value.trim()),
+    "Failed to parse solc version requirement",
+  )
+}
+
+impl From<JsExposeStrategy> for ExposeStrategy {
+  fn from(strategy: JsExposeStrategy) -> Self {
+    match strategy {
+      JsExposeStrategy::InPlace => ExposeStrategy::InPlace,
+      JsExposeStrategy::Wrapper => ExposeStrategy::Wrapper,
+    }
+  }
+}
+
 impl TryFrom<&AstConfigOptions> for AstConfigOptions {
   type Error = napi::Error;
 
@@ -436,6 +942,19 @@ impl TryFrom<&JsAstConfigOptions> for AstConfigOptions {
       typed.solc.settings = Some(CompilerSettingsOptions::try_from(settings)?);
     }
     typed.instrumented_contract = options.instrumented_contract.clone();
+    typed.expose_strategy = options.expose_strategy.map(ExposeStrategy::from);
+
+    if let Some(remappings) = options.remappings.as_ref() {
+      map_remappings(Some(remappings))?;
+      typed
+        .solc
+        .settings
+        .get_or_insert_with(CompilerSettingsOptions::default)
+        .remappings = Some(remappings.clone());
+    }
+
+    typed.parse_cache_capacity = options.parse_cache_capacity.map(|capacity| capacity as usize);
+    typed.parse_cache_dir = options.parse_cache_dir.as_ref().map(PathBuf::from);
 
     Ok(typed)
   }
@@ -455,6 +974,10 @@ impl TryFrom<JsAstConfigOptions> for AstConfigOptions {
 pub struct JsCompilerConfigOptions {
   #[napi(ts_type = "string | undefined")]
   pub solc_version: Option<String>,
+  /// Derive the effective solc version per compile from the source set's `pragma solidity`
+  /// requirements instead of using `solc_version`. Takes priority over `solc_version` when true.
+  #[napi(ts_type = "boolean | undefined")]
+  pub auto_detect_solc_version: Option<bool>,
   #[napi(ts_type = "CompilerLanguage | undefined")]
   pub language: Option<JsCompilerLanguage>,
   #[napi(ts_type = "CompilerSettings | undefined")]
@@ -468,6 +991,10 @@ pub struct JsCompilerConfigOptions {
   #[napi(ts_type = "boolean | undefined")]
   pub build_info_enabled: Option<bool>,
   #[napi(ts_type = "boolean | undefined")]
+  pub emit_sourceless_artifacts: Option<bool>,
+  #[napi(ts_type = "ArtifactFormat | undefined")]
+  pub artifact_format: Option<JsArtifactFormat>,
+  #[napi(ts_type = "boolean | undefined")]
   pub slash_paths: Option<bool>,
   #[napi(ts_type = "number | undefined")]
   pub solc_jobs: Option<u32>,
@@ -481,14 +1008,92 @@ pub struct JsCompilerConfigOptions {
   pub library_paths: Option<Vec<String>>,
   #[napi(ts_type = "string[] | undefined")]
   pub remappings: Option<Vec<String>>,
+  #[napi(ts_type = "boolean | undefined")]
+  pub auto_detect_remappings: Option<bool>,
   #[napi(ts_type = "number[] | undefined")]
   pub ignored_error_codes: Option<Vec<i64>>,
+  #[napi(ts_type = "boolean | undefined")]
+  pub deny_warnings: Option<bool>,
   #[napi(ts_type = "string[] | undefined")]
   pub ignored_paths: Option<Vec<String>>,
   #[napi(ts_type = "string | undefined")]
   pub compiler_severity: Option<String>,
+  /// Per-error-code severity promotion/demotion, keyed by the diagnostic's `errorCode` as a
+  /// string (e.g. `{ "2072": "error" }` to treat unused-variable warnings as build failures).
+  /// Checked before `promoteAllWarningsToErrors`, which is itself checked before the diagnostic's
+  /// own severity.
+  #[napi(ts_type = "Record<string, string> | undefined")]
+  pub severity_overrides: Option<BTreeMap<String, String>>,
+  /// Catch-all fallback for `severityOverrides`: promotes every remaining warning (one without
+  /// its own entry in `severityOverrides`) to an error.
+  #[napi(ts_type = "boolean | undefined")]
+  pub promote_all_warnings_to_errors: Option<bool>,
+  /// Error codes to treat as non-fatal without hiding them: unlike `ignoredErrorCodes`, which
+  /// drops matching diagnostics entirely, a suppressed code still shows up in `CompileOutput`'s
+  /// full diagnostics list - it's just excluded from `hasCompilerErrors`. See
+  /// `JsCompileOutput::isSuppressed`.
+  #[napi(ts_type = "number[] | undefined")]
+  pub suppressed_warning_codes: Option<Vec<i64>>,
   #[napi(ts_type = "VyperCompilerConfig | undefined")]
   pub vyper: Option<JsVyperCompilerConfig>,
+  #[napi(ts_type = "CompilationRestriction[] | undefined")]
+  pub restrictions: Option<Vec<JsCompilationRestriction>>,
+  /// When set, rewrites the effective solc and Vyper output selections down to exactly these
+  /// outputs - skipping everything else, including the otherwise-default (and expensive) JSON AST
+  /// output unless `Ast` is itself requested.
+  #[napi(ts_type = "RequestedOutputKind[] | undefined")]
+  pub requested_outputs: Option<Vec<RequestedOutputKind>>,
+  /// Plans the minimal output selection needed to produce exactly these artifacts, replacing
+  /// whatever `solcSettings.outputSelection` would otherwise resolve to.
+  #[napi(ts_type = "ArtifactKind[] | undefined")]
+  pub requested_artifacts: Option<Vec<ArtifactKind>>,
+  /// Named, inheritable layers of this same options shape, switched between by name via
+  /// `compileBuilder(...).withProfile(name)`. See [`JsCompilerConfigProfile`].
+  #[napi(ts_type = "Record<string, CompilerConfigProfile> | undefined")]
+  pub profiles: Option<BTreeMap<String, JsCompilerConfigProfile>>,
+  /// Named overlays of this same options shape, fanned out into sibling configs by
+  /// `compileBuilder(...).buildRevisions()`. See [`JsRevisionSpec`].
+  #[napi(ts_type = "RevisionSpec[] | undefined")]
+  pub revisions: Option<Vec<JsRevisionSpec>>,
+}
+
+/// JavaScript-facing mirror of [`CompilerConfigProfile`].
+#[napi(object, js_name = "CompilerConfigProfile")]
+#[derive(Clone, Default)]
+pub struct JsCompilerConfigProfile {
+  #[napi(ts_type = "string | undefined")]
+  pub inherits: Option<String>,
+  #[napi(ts_type = "CompilerConfigOptions | undefined")]
+  pub options: Option<JsCompilerConfigOptions>,
+}
+
+/// JavaScript-facing mirror of [`RevisionSpec`].
+#[napi(object, js_name = "RevisionSpec")]
+#[derive(Clone, Default)]
+pub struct JsRevisionSpec {
+  pub name: String,
+  #[napi(ts_type = "CompilerConfigOptions | undefined")]
+  pub options: Option<JsCompilerConfigOptions>,
+}
+
+/// Per-path-pattern bounds, mirroring [`CompilationRestriction`]. `min_solc`/`max_solc` take a
+/// `VersionReq` expression (e.g. `">=0.7.0"`) rather than a bare version.
+#[napi(object, js_name = "CompilationRestriction")]
+#[derive(Clone, Default)]
+pub struct JsCompilationRestriction {
+  pub path_pattern: String,
+  #[napi(ts_type = "string | undefined")]
+  pub min_solc: Option<String>,
+  #[napi(ts_type = "string | undefined")]
+  pub max_solc: Option<String>,
+  #[napi(ts_type = "EvmVersion | undefined")]
+  pub min_evm_version: Option<crate::internal::settings::EvmVersion>,
+  #[napi(ts_type = "EvmVersion | undefined")]
+  pub max_evm_version: Option<crate::internal::settings::EvmVersion>,
+  #[napi(ts_type = "number | undefined")]
+  pub min_optimizer_runs: Option<u32>,
+  #[napi(ts_type = "number | undefined")]
+  pub max_optimizer_runs: Option<u32>,
 }
 
 #[napi(string_enum, js_name = "CompilerLanguage")]
@@ -499,6 +1104,29 @@ pub enum JsCompilerLanguage {
   Vyper,
 }
 
+#[napi(string_enum, js_name = "ArtifactFormat")]
+#[derive(Debug, Eq, PartialEq)]
+pub enum JsArtifactFormat {
+  Foundry,
+  Hardhat,
+}
+
+impl From<JsArtifactFormat> for ArtifactFormat {
+  fn from(format: JsArtifactFormat) -> Self {
+    match format {
+      JsArtifactFormat::Foundry => ArtifactFormat::Foundry,
+      JsArtifactFormat::Hardhat => ArtifactFormat::Hardhat,
+    }
+  }
+}
+
+#[napi(string_enum, js_name = "ExposeStrategy")]
+#[derive(Debug, Eq, PartialEq)]
+pub enum JsExposeStrategy {
+  InPlace,
+  Wrapper,
+}
+
 impl From<JsCompilerLanguage> for CompilerLanguage {
   fn from(language: JsCompilerLanguage) -> Self {
     match language {
@@ -556,6 +1184,14 @@ pub struct JsAstConfigOptions {
   pub solc_settings: Option<JsCompilerSettingsOptions>,
   #[napi(ts_type = "string | undefined")]
   pub instrumented_contract: Option<String>,
+  #[napi(ts_type = "ExposeStrategy | undefined")]
+  pub expose_strategy: Option<JsExposeStrategy>,
+  #[napi(ts_type = "string[] | undefined")]
+  pub remappings: Option<Vec<String>>,
+  #[napi(ts_type = "number | undefined")]
+  pub parse_cache_capacity: Option<u32>,
+  #[napi(ts_type = "string | undefined")]
+  pub parse_cache_dir: Option<String>,
 }
 
 #[napi(string_enum)]
@@ -679,6 +1315,7 @@ impl SolcConfig {
       merge_settings(
         default_settings,
         overrides.and_then(|opts| opts.compiler_settings()),
+        SettingsMergeStrategy::Replace,
       )?
     };
 
@@ -706,6 +1343,7 @@ impl SolcConfig {
       merge_settings(
         &self.settings,
         overrides.and_then(|opts| opts.compiler_settings()),
+        SettingsMergeStrategy::Replace,
       )?
     };
 
@@ -805,6 +1443,37 @@ fn parse_severity(value: &str) -> Result<Severity> {
   }
 }
 
+/// Walks `name`'s `inherits` chain within `profiles`, returning it root-most ancestor first and
+/// `name` itself last - the order [`CompilerConfigBuilder::with_profile`] applies each layer in.
+/// Errors on an unknown profile name or a cycle, rather than looping forever.
+fn resolve_profile_chain(
+  profiles: &BTreeMap<String, CompilerConfigProfile>,
+  name: &str,
+) -> Result<Vec<String>> {
+  let mut chain = Vec::new();
+  let mut seen = BTreeSet::new();
+  let mut current = name.to_string();
+
+  loop {
+    if !seen.insert(current.clone()) {
+      return Err(napi_error(format!(
+        "Compiler config profile \"{name}\" has a cyclic `inherits` chain through \"{current}\""
+      )));
+    }
+    let profile = profiles
+      .get(&current)
+      .ok_or_else(|| napi_error(format!("Unknown compiler config profile \"{current}\"")))?;
+    chain.push(current.clone());
+    match profile.inherits.clone() {
+      Some(parent) => current = parent,
+      None => break,
+    }
+  }
+
+  chain.reverse();
+  Ok(chain)
+}
+
 #[derive(Default)]
 pub(crate) struct CompilerConfigBuilder {
   config: CompilerConfig,
@@ -825,11 +1494,17 @@ impl CompilerConfigBuilder {
     let CompilerConfigOptions {
       compiler,
       mut solc,
+      solc_version_selector,
+      restrictions,
+      requested_outputs,
+      requested_artifacts,
       mut vyper,
       cache_enabled,
       offline_mode,
       no_artifacts,
       build_info_enabled,
+      emit_sourceless_artifacts,
+      artifact_format,
       slash_paths,
       solc_jobs,
       sparse_output,
@@ -837,9 +1512,16 @@ impl CompilerConfigBuilder {
       include_paths,
       library_paths,
       remappings,
+      auto_detect_remappings,
       ignored_file_paths,
       ignored_error_codes,
+      deny_warnings,
       compiler_severity_filter,
+      severity_overrides,
+      promote_all_warnings_to_errors,
+      suppressed_warning_codes,
+      profiles,
+      revisions,
     } = overrides;
 
     if let Some(language) = compiler {
@@ -849,11 +1531,38 @@ impl CompilerConfigBuilder {
     }
     if let Some(version) = solc.version.take() {
       self.config.solc_version = version;
+      self.config.auto_detect_solc_version = false;
     }
+    match solc_version_selector {
+      Some(SolcVersionSelector::Pinned(version)) => {
+        self.config.solc_version = version;
+        self.config.auto_detect_solc_version = false;
+      }
+      Some(SolcVersionSelector::AutoDetect) => {
+        self.config.auto_detect_solc_version = true;
+      }
+      None => {}
+    }
+    if let Some(restrictions) = restrictions {
+      self.config.restrictions = restrictions;
+    }
+    if let Some(requested_outputs) = requested_outputs {
+      self.config.requested_outputs = requested_outputs;
+    }
+    let had_resolved_settings = solc.resolved_settings.is_some();
     if let Some(settings) = solc.resolved_settings.take() {
       self.config.solc_settings = sanitize_settings(&settings)?;
     } else if let Some(settings) = solc.settings.take() {
-      self.config.solc_settings = merge_settings(&self.config.solc_settings, Some(&settings))?;
+      let merged = merge_settings_reporting(
+        &self.config.solc_settings,
+        Some(&settings),
+        SettingsMergeStrategy::Replace,
+      )?;
+      self.config.solc_settings = merged.settings;
+      self.config.ignored_settings_keys = merged.ignored;
+    }
+    if let Some(artifacts) = requested_artifacts {
+      apply_requested_artifacts(&mut self.config.solc_settings, &artifacts, had_resolved_settings);
     }
     if let Some(path) = vyper.path.take() {
       self.config.vyper_settings.path = Some(path);
@@ -888,6 +1597,12 @@ impl CompilerConfigBuilder {
     if let Some(build_info) = build_info_enabled {
       self.config.build_info_enabled = build_info;
     }
+    if let Some(emit_sourceless) = emit_sourceless_artifacts {
+      self.config.emit_sourceless_artifacts = emit_sourceless;
+    }
+    if let Some(format) = artifact_format {
+      self.config.artifact_format = format;
+    }
     if let Some(slash_paths) = slash_paths {
       self.config.slash_paths = slash_paths;
     }
@@ -909,23 +1624,104 @@ impl CompilerConfigBuilder {
     if let Some(remappings) = remappings {
       self.config.remappings = remappings;
     }
+    if let Some(auto_detect) = auto_detect_remappings {
+      self.config.auto_detect_remappings = auto_detect;
+    }
     if let Some(ignored_paths) = ignored_file_paths {
       self.config.ignored_file_paths = ignored_paths;
     }
     if let Some(ignored_codes) = ignored_error_codes {
       self.config.ignored_error_codes = ignored_codes;
     }
+    if let Some(deny_warnings) = deny_warnings {
+      self.config.deny_warnings = deny_warnings;
+    }
     if let Some(severity) = compiler_severity_filter {
       self.config.compiler_severity_filter = severity;
     }
+    if let Some(overrides) = severity_overrides {
+      self.config.severity_overrides = overrides;
+    }
+    if let Some(promote_all_warnings) = promote_all_warnings_to_errors {
+      self.config.promote_all_warnings_to_errors = promote_all_warnings;
+    }
+    if let Some(suppressed_codes) = suppressed_warning_codes {
+      self.config.suppressed_warning_codes = suppressed_codes;
+    }
+    if let Some(profiles) = profiles {
+      self.config.profiles = profiles;
+    }
+    if let Some(revisions) = revisions {
+      self.config.revisions = revisions;
+    }
+
+    Ok(self)
+  }
 
+  /// Resolves `name`'s inheritance chain within whatever `CompilerConfigOptions::profiles` a
+  /// prior `apply_compiler_options` call populated (root-most ancestor first, `name` itself last)
+  /// and folds each layer's `options` through `apply_compiler_options` in that order, so a more
+  /// specific profile wins over anything its ancestors set - the same "base → inherited →
+  /// selected" layering `apply_compiler_options` already does for a single options value. See
+  /// [`CompilerConfigProfile`].
+  pub fn with_profile(mut self, name: &str) -> Result<Self> {
+    let chain = resolve_profile_chain(&self.config.profiles, name)?;
+    for profile_name in chain {
+      let options = self.config.profiles[&profile_name].options.clone();
+      self = self.apply_compiler_options(options)?;
+    }
     Ok(self)
   }
 
+  /// Fluent equivalent of `CompilerConfigOptions::requested_artifacts`: plans the minimal
+  /// `output_selection` needed to produce exactly `artifacts` and applies it directly, replacing
+  /// whatever selection the builder already holds. Unlike going through
+  /// `apply_compiler_options`, a standalone call like this has no `resolved_settings` override to
+  /// intersect with, so it always replaces rather than narrows.
+  pub fn with_requested_artifacts(mut self, artifacts: Vec<ArtifactKind>) -> Self {
+    apply_requested_artifacts(&mut self.config.solc_settings, &artifacts, false);
+    self
+  }
+
   pub fn build(mut self) -> Result<CompilerConfig> {
     self.config.solc_settings = sanitize_settings(&self.config.solc_settings)?;
+    for restriction in &self.config.restrictions {
+      restriction.validate_self_consistent()?;
+    }
+    if !self.config.requested_outputs.is_empty() {
+      apply_requested_outputs(&mut self.config.solc_settings, &self.config.requested_outputs);
+      let mut vyper_selection = self
+        .config
+        .vyper_settings
+        .output_selection
+        .take()
+        .unwrap_or_default();
+      apply_requested_outputs_to_selection(&mut vyper_selection, &self.config.requested_outputs);
+      self.config.vyper_settings.output_selection = Some(vyper_selection);
+    }
     Ok(self.config)
   }
+
+  /// Fans `self` out across whatever `CompilerConfigOptions::revisions` a prior
+  /// `apply_compiler_options` call populated: resolves the shared base config once via
+  /// [`Self::build`], then for each [`RevisionSpec`] clones that base and layers its `options`
+  /// overlay on top via `apply_compiler_options`, finalizing each clone with its own `build()`
+  /// call. Lets a caller validate the same sources under several variants - e.g. `{ via_ir: true
+  /// }` vs `{ via_ir: false }`, or multiple `evm_version` targets - in one pass instead of
+  /// constructing and compiling N configs by hand.
+  pub fn build_revisions(self) -> Result<Vec<(String, CompilerConfig)>> {
+    let revisions = self.config.revisions.clone();
+    let base = self.build()?;
+    revisions
+      .into_iter()
+      .map(|revision| {
+        let config = CompilerConfigBuilder::with_base(base.clone())
+          .apply_compiler_options(revision.options)?
+          .build()?;
+        Ok((revision.name, config))
+      })
+      .collect()
+  }
 }
 
 #[cfg(test)]
@@ -1032,7 +1828,8 @@ mod tests {
       BTreeMap::from([("*".to_string(), Vec::new()), (String::new(), Vec::new())]),
     )]));
 
-    let merged = merge_settings(&base, Some(&overrides)).expect("settings");
+    let merged =
+      merge_settings(&base, Some(&overrides), SettingsMergeStrategy::Replace).expect("settings");
     assert!(
       !crate::internal::settings::output_selection_is_effectively_empty(&merged.output_selection),
       "merged selection should fallback to defaults"
@@ -1049,6 +1846,435 @@ mod tests {
     assert_eq!(built.language, baseline.language);
   }
 
+  #[test]
+  fn auto_detect_solc_version_selector_overrides_a_pinned_version() {
+    let mut options = CompilerConfigOptions::default();
+    options.solc.version = Some(Version::new(0, 8, 0));
+    options.solc_version_selector = Some(SolcVersionSelector::AutoDetect);
+    let config = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .build()
+      .expect("build");
+    assert!(config.auto_detect_solc_version);
+  }
+
+  #[test]
+  fn pinned_solc_version_selector_clears_auto_detect() {
+    let mut options = CompilerConfigOptions::default();
+    options.solc_version_selector = Some(SolcVersionSelector::Pinned(Version::new(0, 8, 19)));
+    let config = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .build()
+      .expect("build");
+    assert!(!config.auto_detect_solc_version);
+    assert_eq!(config.solc_version, Version::new(0, 8, 19));
+  }
+
+  #[test]
+  fn js_auto_detect_solc_version_flag_maps_to_the_selector() {
+    let mut options = JsCompilerConfigOptions::default();
+    options.auto_detect_solc_version = Some(true);
+    let parsed = CompilerConfigOptions::try_from(&options).expect("options");
+    assert_eq!(
+      parsed.solc_version_selector,
+      Some(SolcVersionSelector::AutoDetect)
+    );
+  }
+
+  #[test]
+  fn restriction_rejects_a_solc_version_outside_its_bounds() {
+    let restriction = CompilationRestriction {
+      path_pattern: "src/legacy/*".to_string(),
+      min_solc: Some(VersionReq::parse(">=0.7.0").expect("req")),
+      max_solc: Some(VersionReq::parse("<0.8.0").expect("req")),
+      min_evm_version: None,
+      max_evm_version: None,
+      min_optimizer_runs: None,
+      max_optimizer_runs: None,
+    };
+    let resolved_versions = BTreeMap::from([(
+      "src/legacy/Old.sol".to_string(),
+      Version::new(0, 8, 19),
+    )]);
+    let error = check_restrictions(&[restriction], &resolved_versions, &Settings::default())
+      .expect_err("should reject");
+    assert!(error.to_string().contains("max_solc"));
+  }
+
+  #[test]
+  fn restriction_glob_only_matches_the_pattern_it_covers() {
+    let restriction = CompilationRestriction {
+      path_pattern: "src/legacy/*".to_string(),
+      min_solc: Some(VersionReq::parse(">=0.8.0").expect("req")),
+      max_solc: None,
+      min_evm_version: None,
+      max_evm_version: None,
+      min_optimizer_runs: None,
+      max_optimizer_runs: None,
+    };
+    let resolved_versions = BTreeMap::from([(
+      "src/current/New.sol".to_string(),
+      Version::new(0, 4, 0),
+    )]);
+    check_restrictions(&[restriction], &resolved_versions, &Settings::default())
+      .expect("non-matching path should be ignored");
+  }
+
+  #[test]
+  fn inverted_optimizer_runs_bounds_are_rejected_at_build_time() {
+    let mut options = CompilerConfigOptions::default();
+    options.restrictions = Some(vec![CompilationRestriction {
+      path_pattern: "*".to_string(),
+      min_solc: None,
+      max_solc: None,
+      min_evm_version: None,
+      max_evm_version: None,
+      min_optimizer_runs: Some(500),
+      max_optimizer_runs: Some(200),
+    }]);
+    let error = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .build()
+      .expect_err("should reject inverted bounds");
+    assert!(error.to_string().contains("min_optimizer_runs"));
+  }
+
+  #[test]
+  fn js_compilation_restriction_parses_version_req_bounds() {
+    let mut options = JsCompilationRestriction::default();
+    options.path_pattern = "src/*".to_string();
+    options.min_solc = Some(">=0.8.0".to_string());
+    let parsed = CompilationRestriction::try_from(&options).expect("restriction");
+    assert_eq!(
+      parsed.min_solc,
+      Some(VersionReq::parse(">=0.8.0").expect("req"))
+    );
+  }
+
+  #[test]
+  fn requested_outputs_trims_solc_output_selection_to_requested_keys() {
+    let mut options = CompilerConfigOptions::default();
+    options.requested_outputs = Some(vec![RequestedOutputKind::Abi]);
+    let config = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .build()
+      .expect("build");
+
+    let selection = config.solc_settings.output_selection.as_ref();
+    let contracts = selection.get("*").and_then(|entry| entry.get("*"));
+    assert_eq!(
+      contracts.map(|outputs| outputs.as_slice()),
+      Some(["abi".to_string()].as_slice())
+    );
+    assert!(
+      selection.get("*").and_then(|entry| entry.get("")).is_none(),
+      "ast output should not be selected unless explicitly requested"
+    );
+  }
+
+  #[test]
+  fn requested_ast_output_uses_the_file_level_ast_selection() {
+    let mut options = CompilerConfigOptions::default();
+    options.requested_outputs = Some(vec![RequestedOutputKind::Ast]);
+    let config = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .build()
+      .expect("build");
+
+    assert!(
+      !crate::internal::settings::output_selection_is_effectively_empty(
+        &config.solc_settings.output_selection
+      )
+    );
+  }
+
+  #[test]
+  fn requested_outputs_also_trim_vyper_output_selection() {
+    let mut options = CompilerConfigOptions::default();
+    options.requested_outputs = Some(vec![RequestedOutputKind::StorageLayout]);
+    let config = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .build()
+      .expect("build");
+
+    let selection = config
+      .vyper_settings
+      .output_selection
+      .expect("vyper output selection");
+    let contracts = selection.as_ref().get("*").and_then(|entry| entry.get("*"));
+    assert_eq!(
+      contracts.map(|outputs| outputs.as_slice()),
+      Some(["storageLayout".to_string()].as_slice())
+    );
+  }
+
+  #[test]
+  fn requested_artifacts_replaces_output_selection_when_no_explicit_override() {
+    let mut options = CompilerConfigOptions::default();
+    options.requested_artifacts = Some(vec![ArtifactKind::Abi, ArtifactKind::Bytecode]);
+    let config = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .build()
+      .expect("build");
+
+    let selection = config.solc_settings.output_selection;
+    let contracts = selection.as_ref().get("*").and_then(|entry| entry.get("*"));
+    assert_eq!(
+      contracts.map(|outputs| outputs.as_slice()),
+      Some(["abi".to_string(), "evm.bytecode".to_string()].as_slice())
+    );
+  }
+
+  #[test]
+  fn requested_artifacts_intersect_with_an_explicit_resolved_override() {
+    let mut resolved = Settings::default();
+    resolved.output_selection = BTreeMap::from([(
+      "*".to_string(),
+      BTreeMap::from([(
+        "*".to_string(),
+        vec!["abi".to_string(), "storageLayout".to_string()],
+      )]),
+    )])
+    .into();
+
+    let mut options = CompilerConfigOptions::default();
+    options.solc.resolved_settings = Some(resolved);
+    options.requested_artifacts = Some(vec![ArtifactKind::Abi, ArtifactKind::Bytecode]);
+    let config = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .build()
+      .expect("build");
+
+    let selection = config.solc_settings.output_selection;
+    let contracts = selection.as_ref().get("*").and_then(|entry| entry.get("*"));
+    assert_eq!(
+      contracts.map(|outputs| outputs.as_slice()),
+      Some(["abi".to_string()].as_slice()),
+      "evm.bytecode wasn't in the explicit override and storageLayout wasn't requested, so only \
+       abi should survive the intersection"
+    );
+  }
+
+  #[test]
+  fn with_requested_artifacts_builder_method_sets_the_minimal_selection() {
+    let config = CompilerConfigBuilder::from_defaults()
+      .with_requested_artifacts(vec![ArtifactKind::Metadata])
+      .build()
+      .expect("build");
+
+    let selection = config.solc_settings.output_selection;
+    let contracts = selection.as_ref().get("*").and_then(|entry| entry.get("*"));
+    assert_eq!(
+      contracts.map(|outputs| outputs.as_slice()),
+      Some(["metadata".to_string()].as_slice())
+    );
+  }
+
+  #[test]
+  fn severity_overrides_are_parsed_from_string_keyed_js_map() {
+    let mut options = JsCompilerConfigOptions::default();
+    options.severity_overrides = Some(BTreeMap::from([("2072".to_string(), "info".to_string())]));
+    options.promote_all_warnings_to_errors = Some(true);
+    let overrides = CompilerConfigOptions::try_from(&options).expect("parse overrides");
+
+    assert_eq!(
+      overrides.severity_overrides,
+      Some(BTreeMap::from([(2072, Severity::Info)]))
+    );
+    assert_eq!(overrides.promote_all_warnings_to_errors, Some(true));
+
+    let config = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(overrides)
+      .expect("apply options")
+      .build()
+      .expect("build");
+    assert_eq!(config.severity_overrides.get(&2072), Some(&Severity::Info));
+    assert!(config.promote_all_warnings_to_errors);
+  }
+
+  #[test]
+  fn non_numeric_severity_override_key_is_rejected() {
+    let mut options = JsCompilerConfigOptions::default();
+    options.severity_overrides =
+      Some(BTreeMap::from([("not-a-code".to_string(), "error".to_string())]));
+    let error = CompilerConfigOptions::try_from(&options).expect_err("should fail");
+    assert!(error.to_string().contains("Invalid error code"));
+  }
+
+  #[test]
+  fn with_profile_applies_inherited_chain_in_order() {
+    let mut dev_options = CompilerConfigOptions::default();
+    dev_options.sparse_output = Some(true);
+
+    let mut ci_options = CompilerConfigOptions::default();
+    ci_options.build_info_enabled = Some(true);
+
+    let mut profiles = BTreeMap::new();
+    profiles.insert(
+      "dev".to_string(),
+      CompilerConfigProfile {
+        inherits: None,
+        options: dev_options,
+      },
+    );
+    profiles.insert(
+      "ci".to_string(),
+      CompilerConfigProfile {
+        inherits: Some("dev".to_string()),
+        options: ci_options,
+      },
+    );
+
+    let mut options = CompilerConfigOptions::default();
+    options.profiles = Some(profiles);
+
+    let config = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .with_profile("ci")
+      .expect("resolve profile")
+      .build()
+      .expect("build");
+
+    assert!(config.sparse_output, "ci should inherit dev's sparse_output");
+    assert!(
+      config.build_info_enabled,
+      "ci should apply its own build_info_enabled"
+    );
+  }
+
+  #[test]
+  fn with_profile_rejects_an_unknown_name() {
+    let error = CompilerConfigBuilder::from_defaults()
+      .with_profile("missing")
+      .expect_err("should fail");
+    assert!(error.to_string().contains("Unknown compiler config profile"));
+  }
+
+  #[test]
+  fn with_profile_rejects_a_cyclic_inherits_chain() {
+    let mut profiles = BTreeMap::new();
+    profiles.insert(
+      "a".to_string(),
+      CompilerConfigProfile {
+        inherits: Some("b".to_string()),
+        options: CompilerConfigOptions::default(),
+      },
+    );
+    profiles.insert(
+      "b".to_string(),
+      CompilerConfigProfile {
+        inherits: Some("a".to_string()),
+        options: CompilerConfigOptions::default(),
+      },
+    );
+
+    let mut options = CompilerConfigOptions::default();
+    options.profiles = Some(profiles);
+
+    let error = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .with_profile("a")
+      .expect_err("should detect cycle");
+    assert!(error.to_string().contains("cyclic"));
+  }
+
+  #[test]
+  fn js_profile_map_parses_into_an_inheritance_chain() {
+    let mut dev_options = JsCompilerConfigOptions::default();
+    dev_options.sparse_output = Some(true);
+
+    let mut profiles = BTreeMap::new();
+    profiles.insert(
+      "dev".to_string(),
+      JsCompilerConfigProfile {
+        inherits: None,
+        options: Some(dev_options),
+      },
+    );
+
+    let mut options = JsCompilerConfigOptions::default();
+    options.profiles = Some(profiles);
+
+    let overrides = CompilerConfigOptions::try_from(&options).expect("parse profiles");
+    let profile = overrides
+      .profiles
+      .expect("profiles map")
+      .remove("dev")
+      .expect("dev profile");
+    assert_eq!(profile.options.sparse_output, Some(true));
+  }
+
+  #[test]
+  fn build_revisions_layers_each_overlay_on_the_shared_base() {
+    let mut via_ir_options = CompilerConfigOptions::default();
+    via_ir_options.sparse_output = Some(true);
+
+    let mut legacy_options = CompilerConfigOptions::default();
+    legacy_options.build_info_enabled = Some(true);
+
+    let mut options = CompilerConfigOptions::default();
+    options.revisions = Some(vec![
+      RevisionSpec {
+        name: "via-ir".to_string(),
+        options: via_ir_options,
+      },
+      RevisionSpec {
+        name: "legacy".to_string(),
+        options: legacy_options,
+      },
+    ]);
+
+    let revisions = CompilerConfigBuilder::from_defaults()
+      .apply_compiler_options(options)
+      .expect("apply options")
+      .build_revisions()
+      .expect("build revisions");
+
+    assert_eq!(revisions.len(), 2);
+    assert_eq!(revisions[0].0, "via-ir");
+    assert!(revisions[0].1.sparse_output);
+    assert!(!revisions[0].1.build_info_enabled);
+    assert_eq!(revisions[1].0, "legacy");
+    assert!(!revisions[1].1.sparse_output);
+    assert!(revisions[1].1.build_info_enabled);
+  }
+
+  #[test]
+  fn build_revisions_is_empty_without_a_revisions_list() {
+    let revisions = CompilerConfigBuilder::from_defaults()
+      .build_revisions()
+      .expect("build revisions");
+    assert!(revisions.is_empty());
+  }
+
+  #[test]
+  fn js_revision_list_parses_into_named_overlays() {
+    let mut via_ir_options = JsCompilerConfigOptions::default();
+    via_ir_options.sparse_output = Some(true);
+
+    let mut options = JsCompilerConfigOptions::default();
+    options.revisions = Some(vec![JsRevisionSpec {
+      name: "via-ir".to_string(),
+      options: Some(via_ir_options),
+    }]);
+
+    let overrides = CompilerConfigOptions::try_from(&options).expect("parse revisions");
+    let revisions = overrides.revisions.expect("revisions list");
+    assert_eq!(revisions.len(), 1);
+    assert_eq!(revisions[0].name, "via-ir");
+    assert_eq!(revisions[0].options.sparse_output, Some(true));
+  }
+
   #[test]
   fn invalid_severity_string_is_rejected() {
     let mut options = JsCompilerConfigOptions::default();
@@ -0,0 +1,51 @@
+use std::sync::Once;
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+use crate::internal::errors::Result;
+
+static INIT: Once = Once::new();
+
+/// Minimal stderr [`Log`] backend. The crate has no logging-framework dependency of its own (no
+/// `env_logger`/`simplelog`/`fern`), so this is just enough to make the `log::{info, error}` calls
+/// scattered through [`crate::ast::core`] actually go somewhere when a host hasn't already
+/// installed its own logger via `log::set_logger`.
+struct StderrLogger;
+
+impl Log for StderrLogger {
+  fn enabled(&self, metadata: &Metadata) -> bool {
+    metadata.level() <= log::max_level()
+  }
+
+  fn log(&self, record: &Record) {
+    if !self.enabled(record.metadata()) {
+      return;
+    }
+    eprintln!("[{}] {}", record.level(), record.args());
+  }
+
+  fn flush(&self) {}
+}
+
+static LOGGER: StderrLogger = StderrLogger;
+
+/// Installs [`StderrLogger`] the first time it's called (subsequent calls are no-ops beyond
+/// applying `level`), then applies `level` if given. `log::set_logger` only ever succeeds once per
+/// process, so a second consumer calling this (or one that installed its own logger first) isn't
+/// treated as an error - we just adjust the max level on top of whatever is already registered.
+pub(crate) fn ensure_rust_logger(level: Option<LevelFilter>) -> Result<()> {
+  INIT.call_once(|| {
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(LevelFilter::Warn);
+  });
+  update_level(level);
+  Ok(())
+}
+
+/// Raises or lowers the global max log level. A `None` leaves whatever level is already in effect
+/// untouched, so a call site that didn't request a specific level doesn't clobber one set earlier.
+pub(crate) fn update_level(level: Option<LevelFilter>) {
+  if let Some(level) = level {
+    log::set_max_level(level);
+  }
+}
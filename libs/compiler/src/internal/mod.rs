@@ -0,0 +1,12 @@
+pub(crate) mod cache_key;
+pub(crate) mod config;
+pub(crate) mod errors;
+pub(crate) mod logging;
+pub(crate) mod options;
+pub(crate) mod path;
+pub(crate) mod project;
+pub(crate) mod resolver;
+pub(crate) mod settings;
+pub(crate) mod solc;
+pub(crate) mod source_map;
+pub(crate) mod vyper;
@@ -1,15 +1,85 @@
+use std::path::PathBuf;
+
 use foundry_compilers::artifacts::Settings;
 use foundry_compilers::solc::SolcLanguage as FoundrySolcLanguage;
 use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction};
 use napi::{Env, JsObject, JsUnknown, NapiRaw, ValueType};
 use semver::Version;
 
-use super::{errors::napi_error, settings::CompilerSettings, solc};
+use crate::types::CompileProgressEvent;
+
+use super::{
+  errors::napi_error,
+  settings::{apply_extra_output, CompilerSettingsOptions, ExtraOutputKind, JsCompilerSettingsOptions, SettingsMergeStrategy},
+  solc,
+};
 
 pub(crate) trait SolcUserOptions {
   fn solc_version(&self) -> Option<&str>;
   fn solc_language(&self) -> Option<SolcLanguage>;
-  fn settings(&self) -> Option<&CompilerSettings>;
+  fn settings(&self) -> Option<&JsCompilerSettingsOptions>;
+  fn extra_output(&self) -> &[ExtraOutputKind];
+
+  /// Whether to ignore `solc_version` and instead pick the highest installed/installable version
+  /// satisfying every compiled source's `pragma solidity` constraint. Only `CompilerOptions`
+  /// exposes this; every other implementor keeps the default of staying pinned to `solc_version`.
+  fn auto_detect_solc_version(&self) -> Option<bool> {
+    None
+  }
+
+  /// Restricts auto-detection (and `installSolcVersion`-style on-demand installs generally) to
+  /// versions already present locally, erroring instead of downloading a missing one. Defaults to
+  /// `false` outside `CompilerOptions`, which is the only place auto-detection is offered.
+  fn offline_mode(&self) -> Option<bool> {
+    None
+  }
+
+  /// Path to an on-disk content-hash cache of previous solc invocations, consulted by
+  /// `Compiler::compile_standard_sources` before recompiling the same sources/version/settings
+  /// combination again. Only `CompilerOptions` exposes this; caching elsewhere (AST parsing,
+  /// shadow merging) isn't worth the complexity those call sites would add for it.
+  fn cache_path(&self) -> Option<&str> {
+    None
+  }
+
+  /// Opt into compiling `compileSources`/`compileFiles`' non-AST sources as several concurrent
+  /// solc invocations, one per connected component of the import graph, each pinned to its own
+  /// pragma-resolved version - instead of requiring one shared version across the whole call.
+  /// Only `CompilerOptions` exposes this; every other implementor keeps the default of a single
+  /// invocation.
+  fn multi_version(&self) -> Option<bool> {
+    None
+  }
+
+  /// Decode each artifact's `evm.bytecode.sourceMap`/`evm.deployedBytecode.sourceMap` string into
+  /// `ContractBytecode.sourceMap`'s structured entries. Off by default since solc already returns
+  /// the raw string as soon as bytecode output is selected at all - decoding it is extra work a
+  /// caller that only wants bytecode shouldn't pay for.
+  fn decode_source_maps(&self) -> Option<bool> {
+    None
+  }
+
+  /// Write a Hardhat/Foundry-style build-info file after a successful compile and return its path
+  /// on the result. Only `CompilerOptions` exposes this; every other implementor keeps the
+  /// default of never writing one.
+  fn emit_build_info(&self) -> Option<bool> {
+    None
+  }
+
+  /// Directory a build-info file is written to when `emit_build_info` is set. Defaults to the
+  /// bound project's own `build_infos` directory; required when there isn't one (a compiler
+  /// constructed with `new` rather than `from_foundry_root`/`from_hardhat_root`).
+  fn build_info_dir(&self) -> Option<&str> {
+    None
+  }
+
+  /// Callback invoked with a [`CompileProgressEvent`] at each step of a compile - solc
+  /// install/version-bucket/source-resolve/invocation milestones. Only `CompilerOptions` exposes
+  /// this; every other implementor keeps the default of reporting nothing.
+  fn on_progress(&self) -> Option<ThreadsafeFunction<CompileProgressEvent, ErrorStrategy::Fatal>> {
+    None
+  }
 }
 
 #[napi(string_enum)]
@@ -39,7 +109,48 @@ macro_rules! define_options_struct {
       #[napi(ts_type = "import('./index').SolcLanguage | undefined")]
       pub solc_language: Option<SolcLanguage>,
       #[napi(ts_type = "import('./index').CompilerSettings | undefined")]
-      pub settings: Option<CompilerSettings>,
+      pub settings: Option<JsCompilerSettingsOptions>,
+      #[napi(ts_type = "import('./index').ExtraOutputKind[] | undefined")]
+      pub extra_output: Option<Vec<ExtraOutputKind>>,
+      /// Ignore `solcVersion` and pick the highest installed/installable version satisfying every
+      /// compiled source's `pragma solidity` constraint instead. Sources with no pragma at all
+      /// fall back to the default version.
+      #[napi(ts_type = "boolean | undefined")]
+      pub auto_detect_solc_version: Option<bool>,
+      /// Restrict solc version resolution (auto-detected or explicit) to versions already
+      /// installed locally, erroring instead of downloading a missing one.
+      #[napi(ts_type = "boolean | undefined")]
+      pub offline_mode: Option<bool>,
+      /// Path to a JSON file caching previous compile results, keyed by a hash of the sources,
+      /// resolved solc version, and settings. When set, a call whose inputs hash the same as a
+      /// prior one is served from the cache instead of invoking solc again.
+      #[napi(ts_type = "string | undefined")]
+      pub cache_path: Option<String>,
+      /// Compile `compileSources`/`compileFiles`' sources as several concurrent solc invocations,
+      /// one per pragma-resolved version bucket, instead of requiring one shared solc version
+      /// across the whole call. Lets a single call span contracts pinned to different solidity
+      /// versions, as long as files requiring different versions don't import one another.
+      #[napi(ts_type = "boolean | undefined")]
+      pub multi_version: Option<bool>,
+      /// Decode each artifact's bytecode source map into `ContractBytecode.sourceMap`'s structured
+      /// `{ start, length, fileIndex, jump, modifierDepth }` entries, so callers can map bytecode
+      /// program counters back to source ranges without reimplementing solc's delta-compressed
+      /// source map format themselves.
+      #[napi(ts_type = "boolean | undefined")]
+      pub decode_source_maps: Option<bool>,
+      /// After a successful compile, write a Hardhat/Foundry-style build-info file (solc input,
+      /// resolved version, and full raw output) and return its path on `CompileOutput.buildInfoPath`.
+      #[napi(ts_type = "boolean | undefined")]
+      pub emit_build_info: Option<bool>,
+      /// Directory build-info files are written to. Defaults to the bound project's own
+      /// `build_infos` directory; required when compiling without a bound project.
+      #[napi(ts_type = "string | undefined")]
+      pub build_info_dir: Option<String>,
+      /// Called with a `CompileProgressEvent` at each step of a compile - solc install,
+      /// per-version-bucket, per-file resolve, and per-invocation milestones - so a caller can
+      /// render live progress instead of blocking opaquely until the whole result returns.
+      #[napi(ts_type = "((event: import('./index').CompileProgressEvent) => void) | undefined")]
+      pub on_progress: Option<ThreadsafeFunction<CompileProgressEvent, ErrorStrategy::Fatal>>,
     }
 
     impl SolcUserOptions for $name {
@@ -51,9 +162,45 @@ macro_rules! define_options_struct {
         self.solc_language
       }
 
-      fn settings(&self) -> Option<&CompilerSettings> {
+      fn auto_detect_solc_version(&self) -> Option<bool> {
+        self.auto_detect_solc_version
+      }
+
+      fn offline_mode(&self) -> Option<bool> {
+        self.offline_mode
+      }
+
+      fn cache_path(&self) -> Option<&str> {
+        self.cache_path.as_deref()
+      }
+
+      fn multi_version(&self) -> Option<bool> {
+        self.multi_version
+      }
+
+      fn decode_source_maps(&self) -> Option<bool> {
+        self.decode_source_maps
+      }
+
+      fn emit_build_info(&self) -> Option<bool> {
+        self.emit_build_info
+      }
+
+      fn build_info_dir(&self) -> Option<&str> {
+        self.build_info_dir.as_deref()
+      }
+
+      fn on_progress(&self) -> Option<ThreadsafeFunction<CompileProgressEvent, ErrorStrategy::Fatal>> {
+        self.on_progress.clone()
+      }
+
+      fn settings(&self) -> Option<&JsCompilerSettingsOptions> {
         self.settings.as_ref()
       }
+
+      fn extra_output(&self) -> &[ExtraOutputKind] {
+        self.extra_output.as_deref().unwrap_or_default()
+      }
     }
   };
 }
@@ -71,7 +218,7 @@ pub struct AstOptions {
   #[napi(ts_type = "import('./index').SolcLanguage | undefined")]
   pub solc_language: Option<SolcLanguage>,
   #[napi(ts_type = "import('./index').CompilerSettings | undefined")]
-  pub settings: Option<CompilerSettings>,
+  pub settings: Option<JsCompilerSettingsOptions>,
   #[napi(ts_type = "string | undefined")]
   pub instrumented_contract: Option<String>,
 }
@@ -85,9 +232,114 @@ impl SolcUserOptions for AstOptions {
     self.solc_language
   }
 
-  fn settings(&self) -> Option<&CompilerSettings> {
+  fn settings(&self) -> Option<&JsCompilerSettingsOptions> {
+    self.settings.as_ref()
+  }
+
+  fn extra_output(&self) -> &[ExtraOutputKind] {
+    &[]
+  }
+}
+
+/// Options accepted by `Shadow`'s per-call overrides.
+///
+/// `remappings` and `virtual_sources` aren't part of [`SolcUserOptions`] - they don't affect
+/// solc's version/language/settings resolution, only which files get handed to solc alongside the
+/// target source. Callers read them directly off this struct instead.
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct ShadowOptions {
+  #[napi(ts_type = "string | undefined")]
+  pub solc_version: Option<String>,
+  #[napi(ts_type = "import('./index').SolcLanguage | undefined")]
+  pub solc_language: Option<SolcLanguage>,
+  #[napi(ts_type = "import('./index').CompilerSettings | undefined")]
+  pub settings: Option<JsCompilerSettingsOptions>,
+  /// Import remappings (e.g. `@openzeppelin/=node_modules/@openzeppelin/`) resolved while
+  /// parsing/compiling the target and shadow sources. Folded into `solc.settings.remappings`,
+  /// the same field a plain `settings.remappings` override would set, so the two compose instead
+  /// of one silently overwriting the other.
+  #[napi(ts_type = "string[] | undefined")]
+  pub remappings: Option<Vec<String>>,
+  /// Extra in-memory sources (file name -> Solidity source) made available to solc alongside the
+  /// target source, so a target or shadow fragment that `import`s a library, interface, or shared
+  /// type doesn't fail to resolve just because that file isn't on disk.
+  #[napi(ts_type = "Record<string, string> | undefined")]
+  pub virtual_sources: Option<std::collections::HashMap<String, String>>,
+}
+
+impl SolcUserOptions for ShadowOptions {
+  fn solc_version(&self) -> Option<&str> {
+    self.solc_version.as_deref()
+  }
+
+  fn solc_language(&self) -> Option<SolcLanguage> {
+    self.solc_language
+  }
+
+  fn settings(&self) -> Option<&JsCompilerSettingsOptions> {
+    self.settings.as_ref()
+  }
+
+  fn extra_output(&self) -> &[ExtraOutputKind] {
+    &[]
+  }
+}
+
+pub(crate) fn parse_shadow_options(
+  env: &Env,
+  value: Option<JsUnknown>,
+) -> Result<Option<ShadowOptions>> {
+  parse_options(value)?
+    .map(|unknown| unsafe { ShadowOptions::from_napi_value(env.raw(), unknown.raw()) })
+    .transpose()
+}
+
+/// Options accepted by `Instrument`'s per-call overrides.
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct InstrumentOptions {
+  #[napi(ts_type = "string | undefined")]
+  pub solc_version: Option<String>,
+  #[napi(ts_type = "import('./index').CompilerSettings | undefined")]
+  pub settings: Option<JsCompilerSettingsOptions>,
+  /// Name of the contract fragments get stitched into. Sticky across calls on the same
+  /// `Instrument` - once set, it's remembered as the default for later calls that omit it.
+  #[napi(ts_type = "string | undefined")]
+  pub instrumented_contract: Option<String>,
+}
+
+impl InstrumentOptions {
+  fn instrumented_contract(&self) -> Option<&str> {
+    self.instrumented_contract.as_deref()
+  }
+}
+
+impl SolcUserOptions for InstrumentOptions {
+  fn solc_version(&self) -> Option<&str> {
+    self.solc_version.as_deref()
+  }
+
+  fn solc_language(&self) -> Option<SolcLanguage> {
+    None
+  }
+
+  fn settings(&self) -> Option<&JsCompilerSettingsOptions> {
     self.settings.as_ref()
   }
+
+  fn extra_output(&self) -> &[ExtraOutputKind] {
+    &[]
+  }
+}
+
+pub(crate) fn parse_instrument_options(
+  env: &Env,
+  value: Option<JsUnknown>,
+) -> Result<Option<InstrumentOptions>> {
+  parse_options(value)?
+    .map(|unknown| unsafe { InstrumentOptions::from_napi_value(env.raw(), unknown.raw()) })
+    .transpose()
 }
 
 #[derive(Clone)]
@@ -95,6 +347,25 @@ pub(crate) struct SolcConfig {
   pub version: Version,
   pub settings: Settings,
   pub language: FoundrySolcLanguage,
+  /// Mirrors `SolcUserOptions::auto_detect_solc_version` - only `CompilerOptions` ever sets this,
+  /// but it lives here rather than being re-read from the options on every call so
+  /// `Compiler::compile_standard_sources` doesn't need its own copy of the override chain.
+  pub auto_detect_solc_version: bool,
+  pub offline_mode: bool,
+  /// Mirrors `SolcUserOptions::cache_path`, resolved to a `PathBuf` once rather than re-parsed
+  /// from the options string on every call.
+  pub cache_path: Option<PathBuf>,
+  /// Mirrors `SolcUserOptions::multi_version`.
+  pub multi_version: bool,
+  /// Mirrors `SolcUserOptions::decode_source_maps`.
+  pub decode_source_maps: bool,
+  /// Mirrors `SolcUserOptions::emit_build_info`.
+  pub emit_build_info: bool,
+  /// Mirrors `SolcUserOptions::build_info_dir`, resolved to a `PathBuf` once rather than
+  /// re-parsed from the options string on every call.
+  pub build_info_dir: Option<PathBuf>,
+  /// Mirrors `SolcUserOptions::on_progress`.
+  pub on_progress: Option<ThreadsafeFunction<CompileProgressEvent, ErrorStrategy::Fatal>>,
 }
 
 impl SolcConfig {
@@ -129,12 +400,41 @@ impl SolcConfig {
       .map(FoundrySolcLanguage::from)
       .unwrap_or_else(|| default_language.clone());
 
-    let settings = resolve_settings(default_settings, overrides.and_then(|opts| opts.settings()))?;
+    let mut settings =
+      resolve_settings(default_settings, overrides.and_then(|opts| opts.settings()))?;
+    if let Some(opts) = overrides {
+      apply_extra_output(&mut settings, opts.extra_output());
+    }
+
+    let auto_detect_solc_version = overrides
+      .and_then(|opts| opts.auto_detect_solc_version())
+      .unwrap_or(false);
+    let offline_mode = overrides.and_then(|opts| opts.offline_mode()).unwrap_or(false);
+    let cache_path = overrides
+      .and_then(|opts| opts.cache_path())
+      .map(PathBuf::from);
+    let multi_version = overrides.and_then(|opts| opts.multi_version()).unwrap_or(false);
+    let decode_source_maps = overrides
+      .and_then(|opts| opts.decode_source_maps())
+      .unwrap_or(false);
+    let emit_build_info = overrides.and_then(|opts| opts.emit_build_info()).unwrap_or(false);
+    let build_info_dir = overrides
+      .and_then(|opts| opts.build_info_dir())
+      .map(PathBuf::from);
+    let on_progress = overrides.and_then(|opts| opts.on_progress());
 
     Ok(SolcConfig {
       version,
       settings,
       language,
+      auto_detect_solc_version,
+      offline_mode,
+      cache_path,
+      multi_version,
+      decode_source_maps,
+      emit_build_info,
+      build_info_dir,
+      on_progress,
     })
   }
 
@@ -150,12 +450,50 @@ impl SolcConfig {
       .map(FoundrySolcLanguage::from)
       .unwrap_or_else(|| self.language.clone());
 
-    let settings = resolve_settings(&self.settings, overrides.and_then(|opts| opts.settings()))?;
+    let mut settings = resolve_settings(&self.settings, overrides.and_then(|opts| opts.settings()))?;
+    if let Some(opts) = overrides {
+      apply_extra_output(&mut settings, opts.extra_output());
+    }
+
+    let auto_detect_solc_version = overrides
+      .and_then(|opts| opts.auto_detect_solc_version())
+      .unwrap_or(self.auto_detect_solc_version);
+    let offline_mode = overrides
+      .and_then(|opts| opts.offline_mode())
+      .unwrap_or(self.offline_mode);
+    let cache_path = overrides
+      .and_then(|opts| opts.cache_path())
+      .map(PathBuf::from)
+      .or_else(|| self.cache_path.clone());
+    let multi_version = overrides
+      .and_then(|opts| opts.multi_version())
+      .unwrap_or(self.multi_version);
+    let decode_source_maps = overrides
+      .and_then(|opts| opts.decode_source_maps())
+      .unwrap_or(self.decode_source_maps);
+    let emit_build_info = overrides
+      .and_then(|opts| opts.emit_build_info())
+      .unwrap_or(self.emit_build_info);
+    let build_info_dir = overrides
+      .and_then(|opts| opts.build_info_dir())
+      .map(PathBuf::from)
+      .or_else(|| self.build_info_dir.clone());
+    let on_progress = overrides
+      .and_then(|opts| opts.on_progress())
+      .or_else(|| self.on_progress.clone());
 
     Ok(SolcConfig {
       version,
       settings,
       language,
+      auto_detect_solc_version,
+      offline_mode,
+      cache_path,
+      multi_version,
+      decode_source_maps,
+      emit_build_info,
+      build_info_dir,
+      on_progress,
     })
   }
 }
@@ -164,10 +502,11 @@ pub(crate) fn default_compiler_settings() -> Settings {
   Settings::default()
 }
 
-fn resolve_settings(base: &Settings, overrides: Option<&CompilerSettings>) -> Result<Settings> {
+fn resolve_settings(base: &Settings, overrides: Option<&JsCompilerSettingsOptions>) -> Result<Settings> {
   match overrides {
     Some(settings) => {
-      let mut merged = settings.clone().overlay(base)?;
+      let settings = CompilerSettingsOptions::try_from(settings)?;
+      let mut merged = settings.overlay(base, SettingsMergeStrategy::default())?;
       if merged.output_selection.as_ref().is_empty() {
         merged.output_selection = Settings::default().output_selection;
       }
@@ -242,6 +581,18 @@ fn parse_options(value: Option<JsUnknown>) -> Result<Option<JsUnknown>> {
         }
       }
 
+      if object.has_named_property("extraOutput")? {
+        let extra_output_value = object.get_named_property::<JsUnknown>("extraOutput")?;
+        match extra_output_value.get_type()? {
+          ValueType::Undefined | ValueType::Null | ValueType::Object => {}
+          _ => {
+            return Err(napi_error(
+              "extraOutput must be an array of ExtraOutputKind values when provided.",
+            ));
+          }
+        }
+      }
+
       if object.has_named_property("instrumentedContract")? {
         let contract_value = object.get_named_property::<JsUnknown>("instrumentedContract")?;
         match contract_value.get_type()? {
@@ -254,6 +605,30 @@ fn parse_options(value: Option<JsUnknown>) -> Result<Option<JsUnknown>> {
         }
       }
 
+      if object.has_named_property("remappings")? {
+        let remappings_value = object.get_named_property::<JsUnknown>("remappings")?;
+        match remappings_value.get_type()? {
+          ValueType::Undefined | ValueType::Null | ValueType::Object => {}
+          _ => {
+            return Err(napi_error(
+              "remappings must be an array of strings when provided.",
+            ));
+          }
+        }
+      }
+
+      if object.has_named_property("virtualSources")? {
+        let virtual_sources_value = object.get_named_property::<JsUnknown>("virtualSources")?;
+        match virtual_sources_value.get_type()? {
+          ValueType::Undefined | ValueType::Null | ValueType::Object => {}
+          _ => {
+            return Err(napi_error(
+              "virtualSources must be an object mapping file names to source text when provided.",
+            ));
+          }
+        }
+      }
+
       Ok(Some(object.into_unknown()))
     }
     _ => Err(napi_error("Options must be provided as an object.")),
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
 
+use foundry_compilers::artifacts::remappings::Remapping;
 use foundry_compilers::ProjectPathsConfig;
 
 #[napi(object)]
@@ -19,6 +20,7 @@ pub struct ProjectPaths {
   pub include_paths: Vec<String>,
   pub allowed_paths: Vec<String>,
   pub virtual_sources: Option<String>,
+  pub remappings: Vec<String>,
 }
 
 impl ProjectPaths {
@@ -47,6 +49,11 @@ impl ProjectPaths {
         .map(|path| path.to_string_lossy().to_string())
         .collect(),
       virtual_sources: None,
+      remappings: config
+        .remappings
+        .iter()
+        .map(|remapping| remapping.to_string())
+        .collect(),
     }
   }
 
@@ -56,6 +63,27 @@ impl ProjectPaths {
   }
 }
 
+/// Auto-derives `context:prefix=target` remapping strings for each directory in `libraries`,
+/// using [`Remapping::find_many`] - the same dapptools/hardhat-style discovery
+/// [`crate::internal::project::extend_remappings`] applies to a real project's library paths: for
+/// every immediate subdirectory `lib/<name>` containing a `src/` (or the directory itself, when it
+/// has no `src/`), this emits `<name>/=lib/<name>/src/`, and folds in any nested dependency's own
+/// `remappings.txt` it finds along the way. Paths are deduplicated by their fully-formatted string,
+/// first discovery wins.
+pub fn derive_remappings(libraries: &[PathBuf]) -> Vec<String> {
+  let mut seen = BTreeSet::new();
+  let mut remappings = Vec::new();
+  for library in libraries {
+    for remapping in Remapping::find_many(library) {
+      let formatted = remapping.to_string();
+      if seen.insert(formatted.clone()) {
+        remappings.push(formatted);
+      }
+    }
+  }
+  remappings
+}
+
 impl<L> From<&ProjectPathsConfig<L>> for ProjectPaths {
   fn from(config: &ProjectPathsConfig<L>) -> Self {
     ProjectPaths::from_config(config)
@@ -1,4 +1,4 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
@@ -18,13 +18,14 @@ use foundry_config::{Config as FoundryConfig, SolcReq};
 
 use crate::internal::config::{CompilerConfig, CompilerConfigOptions, SolcConfig};
 use crate::internal::errors::{map_err_with_context, Error, Result};
-use crate::internal::path::{canonicalize_path, canonicalize_with_base};
+use crate::internal::path::{canonicalize_path, canonicalize_with_base, ProjectPaths};
 use crate::internal::settings::CompilerSettingsOptions;
 
 #[derive(Clone)]
 pub enum ProjectLayout {
   Hardhat,
   Foundry,
+  Dapptools,
   Synthetic,
 }
 
@@ -34,6 +35,11 @@ pub struct ProjectContext {
   pub root: PathBuf,
   pub paths: ProjectPathsConfig<FoundrySolcLanguage>,
   pub virtual_sources_dir: Option<PathBuf>,
+  /// Whether `ProjectRunner` should derive each file's solc version from its `pragma solidity`
+  /// constraint instead of compiling everything under `config.solc_version`. On by default for
+  /// `Synthetic`, which has no project-level config of its own to pin one; `FoundryAdapter` and
+  /// `HardhatAdapter` turn it off whenever they found an explicit version to pin in `overrides`.
+  pub auto_detect: bool,
 }
 
 impl ProjectContext {
@@ -159,7 +165,7 @@ pub fn create_synthetic_context(base_dir: &Path) -> Result<ProjectContext> {
   let tests_dir = root.join("test");
   let scripts_dir = root.join("scripts");
 
-  let paths = ProjectPathsConfig::builder()
+  let mut paths = ProjectPathsConfig::builder()
     .root(&root)
     .cache(&cache_file)
     .artifacts(&artifacts_dir)
@@ -169,15 +175,45 @@ pub fn create_synthetic_context(base_dir: &Path) -> Result<ProjectContext> {
     .scripts(&scripts_dir)
     .no_libs()
     .build_with_root::<FoundrySolcLanguage>(&root);
+  paths.remappings.extend(find_remappings(&root, &paths.libraries));
 
   Ok(ProjectContext {
     layout: ProjectLayout::Synthetic,
     root,
     paths,
     virtual_sources_dir: Some(virtual_sources_dir),
+    auto_detect: true,
   })
 }
 
+/// Home directory, preferring `HOME`/`USERPROFILE` and falling back to the OS temp directory on
+/// the rare host where neither is set, so `default_cache_dir` always returns something writable.
+fn home_dir() -> PathBuf {
+  std::env::var_os("HOME")
+    .or_else(|| std::env::var_os("USERPROFILE"))
+    .map(PathBuf::from)
+    .unwrap_or_else(std::env::temp_dir)
+}
+
+/// Cache directory backing every synthetic (non-project) compile path - `Compiler::clear_cache`,
+/// `ProjectRunner::compile`'s `InlineSource`/`SourceMap` branches, and `resolve_project_paths`'
+/// fallback when no project is attached. Rooted at `~/.tevm/cache`, matching the per-project
+/// layout `create_synthetic_context` gives a project rooted at an explicit `base_dir`.
+pub fn default_cache_dir() -> PathBuf {
+  home_dir().join(".tevm").join("cache")
+}
+
+/// [`ProjectPaths`] for a synthetic workspace rooted at `base_dir`, without attaching a full
+/// [`ProjectContext`] - used by `resolve_project_paths` when the compiler has no project bound and
+/// just needs to report where it would cache/emit artifacts.
+pub fn synthetic_project_paths(base_dir: &Path) -> Result<ProjectPaths> {
+  let context = create_synthetic_context(base_dir)?;
+  Ok(
+    ProjectPaths::from_config(&context.paths)
+      .with_virtual_sources(context.virtual_sources_dir.as_deref()),
+  )
+}
+
 fn extend_paths_with_config(
   paths: &mut ProjectPathsConfig<FoundrySolcLanguage>,
   config: &CompilerConfig,
@@ -197,6 +233,131 @@ fn extend_paths_with_config(
   for path in &config.allow_paths {
     paths.allowed_paths.insert(path.clone());
   }
+
+  extend_remappings(paths, config);
+}
+
+/// Applies `config.remappings` to `paths`, plus - when `config.auto_detect_remappings` is set -
+/// whatever `Remapping::find_many` discovers under each declared library directory. This is what
+/// lets a synthetic (virtual-source) compile resolve imports like `@openzeppelin/contracts/...`
+/// without the caller materializing a full project layout with a foundry.toml.
+fn extend_remappings(paths: &mut ProjectPathsConfig<FoundrySolcLanguage>, config: &CompilerConfig) {
+  let mut seen: BTreeSet<String> = paths
+    .remappings
+    .iter()
+    .map(|remapping| remapping.to_string())
+    .collect();
+
+  let mut push_if_new = |remappings: &mut Vec<Remapping>, remapping: Remapping| {
+    if seen.insert(remapping.to_string()) {
+      remappings.push(remapping);
+    }
+  };
+
+  for remapping in &config.remappings {
+    push_if_new(&mut paths.remappings, remapping.clone());
+  }
+
+  if config.auto_detect_remappings {
+    for lib in &config.library_paths {
+      for remapping in Remapping::find_many(lib) {
+        push_if_new(&mut paths.remappings, remapping);
+      }
+    }
+  }
+}
+
+/// Auto-discovers remappings for `Synthetic` and `HardhatAdapter`, which - unlike `FoundryAdapter`
+/// - have no `foundry.toml` to read remappings from: walks `root/node_modules` and each directory
+/// in `libraries` one or two levels deep (an extra level for npm scope directories like
+/// `@openzeppelin`) and emits a `name/=path/` remapping for every package directory that contains
+/// Solidity sources, preferring a nested `src`/`contracts` subfolder over the package root when
+/// one exists. When more than one candidate would produce the same name, the shortest path wins.
+pub(crate) fn find_remappings(root: &Path, libraries: &[PathBuf]) -> Vec<Remapping> {
+  let mut found: BTreeMap<String, PathBuf> = BTreeMap::new();
+
+  let mut roots = vec![root.join("node_modules")];
+  roots.extend(libraries.iter().cloned());
+
+  for lib_root in roots {
+    for package_dir in package_directories(&lib_root) {
+      let Some(name) = package_dir.file_name().and_then(|name| name.to_str()) else {
+        continue;
+      };
+      let Some(source_dir) = resolve_source_dir(&package_dir) else {
+        continue;
+      };
+
+      let name = format!("{name}/");
+      let shorter = found
+        .get(&name)
+        .map(|existing| source_dir.as_os_str().len() < existing.as_os_str().len())
+        .unwrap_or(true);
+      if shorter {
+        found.insert(name, source_dir);
+      }
+    }
+  }
+
+  found
+    .into_iter()
+    .map(|(name, path)| Remapping {
+      context: None,
+      name,
+      path: format!("{}/", path.to_string_lossy().trim_end_matches('/')),
+    })
+    .collect()
+}
+
+/// Every immediate package directory under `lib_root`: its direct children, plus - for npm scope
+/// directories starting with `@` - their own children one level deeper.
+fn package_directories(lib_root: &Path) -> Vec<PathBuf> {
+  let Ok(entries) = fs::read_dir(lib_root) else {
+    return Vec::new();
+  };
+
+  let mut packages = Vec::new();
+  for entry in entries.flatten() {
+    let path = entry.path();
+    if !path.is_dir() {
+      continue;
+    }
+    let is_scope = path
+      .file_name()
+      .and_then(|name| name.to_str())
+      .map(|name| name.starts_with('@'))
+      .unwrap_or(false);
+    if is_scope {
+      if let Ok(scoped) = fs::read_dir(&path) {
+        packages.extend(scoped.flatten().map(|pkg| pkg.path()).filter(|pkg| pkg.is_dir()));
+      }
+    } else {
+      packages.push(path);
+    }
+  }
+  packages
+}
+
+/// The directory that should act as `package_dir`'s remapping target: a nested `src` or
+/// `contracts` subfolder containing Solidity sources if one exists, otherwise `package_dir` itself
+/// if it contains Solidity sources directly, otherwise `None`.
+fn resolve_source_dir(package_dir: &Path) -> Option<PathBuf> {
+  for nested in ["src", "contracts"] {
+    let candidate = package_dir.join(nested);
+    if contains_solidity_sources(&candidate) {
+      return Some(candidate);
+    }
+  }
+  contains_solidity_sources(package_dir).then(|| package_dir.to_path_buf())
+}
+
+fn contains_solidity_sources(dir: &Path) -> bool {
+  let Ok(entries) = fs::read_dir(dir) else {
+    return false;
+  };
+  entries
+    .flatten()
+    .any(|entry| entry.path().extension().and_then(|ext| ext.to_str()) == Some("sol"))
 }
 
 fn create_dir_if_missing(path: &Path) -> Result<()> {
@@ -309,11 +470,13 @@ impl FoundryAdapter {
       )
       .build_with_root::<FoundrySolcLanguage>(&config_paths.root);
     paths.slash_paths();
+    let auto_detect = overrides.solc.version.is_none();
     let context = ProjectContext {
       layout: ProjectLayout::Foundry,
       root: base_dir,
       paths,
       virtual_sources_dir: None,
+      auto_detect,
     };
 
     Ok((overrides, context))
@@ -366,29 +529,112 @@ impl HardhatAdapter {
       );
     }
 
-    overrides.library_paths = Some(
-      paths
-        .libraries
-        .iter()
-        .map(|p| canonicalize_with_base(&paths.root, p))
-        .collect::<Vec<_>>(),
-    );
+    let library_paths: Vec<PathBuf> = paths
+      .libraries
+      .iter()
+      .map(|p| canonicalize_with_base(&paths.root, p))
+      .collect();
+    overrides.remappings = Some(find_remappings(&paths.root, &library_paths));
+    overrides.library_paths = Some(library_paths);
 
+    let auto_detect = overrides.solc.version.is_none();
     let context = ProjectContext {
       layout: ProjectLayout::Hardhat,
       root: paths.root.clone(),
       paths,
       virtual_sources_dir: None,
+      auto_detect,
     };
 
     Ok((overrides, context))
   }
 }
 
-fn infer_hardhat_build_info(
-  paths: &ProjectPathsConfig<FoundrySolcLanguage>,
-) -> Option<(SolcConfig, CliSettingsData)> {
-  let entries = fs::read_dir(&paths.build_infos).ok()?;
+pub struct DapptoolsAdapter;
+
+impl DapptoolsAdapter {
+  /// Builds a `ProjectContext` for a dapp(1)-style layout: sources in `src/`, dependencies
+  /// (usually git submodules) in `lib/`, artifacts in `out/`, and a `cache/solc-file-cache.json`
+  /// cache file - there's no `dapp.json`/`foundry.toml` to read a pinned solc version or settings
+  /// from, so (like `create_synthetic_context`) this always turns `auto_detect` on.
+  pub fn load(root: &Path) -> Result<(CompilerConfigOptions, ProjectContext)> {
+    let root = canonicalize_path(root);
+    let cache_file = root.join("cache").join("solc-file-cache.json");
+    let artifacts_dir = root.join("out");
+    let build_info_dir = artifacts_dir.join("build-info");
+    let sources_dir = root.join("src");
+    let library_paths = vec![root.join("lib")];
+
+    let mut paths = ProjectPathsConfig::builder()
+      .root(&root)
+      .cache(&cache_file)
+      .artifacts(&artifacts_dir)
+      .build_infos(&build_info_dir)
+      .sources(&sources_dir)
+      .tests(&sources_dir)
+      .libs(library_paths.clone())
+      .build_with_root::<FoundrySolcLanguage>(&root);
+    paths.slash_paths();
+    paths.remappings.extend(find_remappings(&root, &library_paths));
+
+    let mut overrides = CompilerConfigOptions::default();
+    overrides.base_dir = Some(root.clone());
+    overrides.library_paths = Some(library_paths);
+
+    let context = ProjectContext {
+      layout: ProjectLayout::Dapptools,
+      root,
+      paths,
+      virtual_sources_dir: None,
+      auto_detect: true,
+    };
+
+    Ok((overrides, context))
+  }
+}
+
+/// Probes `root` for the marker files each ecosystem's tooling leaves behind and returns the
+/// layout whose adapter should load it, checked in priority order: a `foundry.toml` means
+/// `Foundry`; a hardhat config file or a hardhat `build-info` directory means `Hardhat`; a
+/// `src/` + `lib/` pair with neither of those means a dapp(1)-style `Dapptools` layout; anything
+/// else falls back to `Synthetic`, which works for a bare directory of `.sol` files.
+pub fn detect_layout(root: &Path) -> ProjectLayout {
+  if root.join("foundry.toml").exists() {
+    return ProjectLayout::Foundry;
+  }
+
+  if root.join("hardhat.config.js").exists()
+    || root.join("hardhat.config.ts").exists()
+    || root.join("artifacts").join("build-info").is_dir()
+  {
+    return ProjectLayout::Hardhat;
+  }
+
+  if root.join("src").is_dir() && root.join("lib").is_dir() {
+    return ProjectLayout::Dapptools;
+  }
+
+  ProjectLayout::Synthetic
+}
+
+/// Loads `root` through the adapter `detect_layout` picks for it, so callers that only have a
+/// directory - and don't know (or want to hard-code) which ecosystem it belongs to - get back
+/// correct paths, remappings, and overrides regardless.
+pub fn load_detected(root: &Path) -> Result<(CompilerConfigOptions, ProjectContext)> {
+  match detect_layout(root) {
+    ProjectLayout::Foundry => FoundryAdapter::load(root),
+    ProjectLayout::Hardhat => HardhatAdapter::load(root),
+    ProjectLayout::Dapptools => DapptoolsAdapter::load(root),
+    ProjectLayout::Synthetic => {
+      create_synthetic_context(root).map(|context| (CompilerConfigOptions::default(), context))
+    }
+  }
+}
+
+/// Newest `*.json` file directly under `dir` by modification time. Build-info files are named by
+/// content hash rather than by run, so the freshest file is the one the most recent compile wrote.
+pub(crate) fn latest_build_info_path(dir: &Path) -> Option<PathBuf> {
+  let entries = fs::read_dir(dir).ok()?;
   let mut latest: Option<(SystemTime, PathBuf)> = None;
 
   for entry in entries.flatten() {
@@ -423,7 +669,13 @@ fn infer_hardhat_build_info(
     }
   }
 
-  let (_, path) = latest?;
+  latest.map(|(_, path)| path)
+}
+
+fn infer_hardhat_build_info(
+  paths: &ProjectPathsConfig<FoundrySolcLanguage>,
+) -> Option<(SolcConfig, CliSettingsData)> {
+  let path = latest_build_info_path(&paths.build_infos)?;
   let build_info: BuildInfo<SolcVersionedInput, CompilerOutput> = BuildInfo::read(&path).ok()?;
 
   let compiler_config = SolcConfig {
@@ -477,6 +729,13 @@ mod tests {
     assert_eq!(resolved, vec![target.canonicalize().unwrap()]);
   }
 
+  #[test]
+  fn synthetic_context_enables_auto_detect_by_default() {
+    let temp = tempdir().expect("tempdir");
+    let context = create_synthetic_context(temp.path()).expect("context");
+    assert!(context.auto_detect);
+  }
+
   #[test]
   fn virtual_source_path_prepares_directory() {
     let temp = tempdir().expect("tempdir");
@@ -487,4 +746,88 @@ mod tests {
     assert!(path.ends_with("virtual-hash.sol"));
     assert!(path.parent().unwrap().exists());
   }
+
+  #[test]
+  fn finds_remappings_for_plain_and_scoped_packages() {
+    let temp = tempdir().expect("tempdir");
+    let root = temp.path();
+    let node_modules = root.join("node_modules");
+    std::fs::create_dir_all(node_modules.join("solmate/src")).expect("mkdir");
+    std::fs::write(node_modules.join("solmate/src/Owned.sol"), "contract Owned {}").expect("write");
+    std::fs::create_dir_all(node_modules.join("@openzeppelin/contracts/contracts")).expect("mkdir");
+    std::fs::write(
+      node_modules.join("@openzeppelin/contracts/contracts/ERC20.sol"),
+      "contract ERC20 {}",
+    )
+    .expect("write");
+
+    let remappings = find_remappings(root, &[]);
+    let names: Vec<&str> = remappings.iter().map(|r| r.name.as_str()).collect();
+    assert!(names.contains(&"solmate/"));
+    assert!(names.contains(&"contracts/"));
+
+    let solmate = remappings.iter().find(|r| r.name == "solmate/").unwrap();
+    assert!(solmate.path.ends_with("solmate/src/"));
+  }
+
+  #[test]
+  fn ignores_package_directories_without_solidity_sources() {
+    let temp = tempdir().expect("tempdir");
+    let root = temp.path();
+    let node_modules = root.join("node_modules");
+    std::fs::create_dir_all(node_modules.join("no-sol-here")).expect("mkdir");
+    std::fs::write(node_modules.join("no-sol-here/package.json"), "{}").expect("write");
+
+    assert!(find_remappings(root, &[]).is_empty());
+  }
+
+  #[test]
+  fn detects_foundry_by_its_config_file() {
+    let temp = tempdir().expect("tempdir");
+    std::fs::write(temp.path().join("foundry.toml"), "[profile.default]\n").expect("write");
+    assert!(matches!(detect_layout(temp.path()), ProjectLayout::Foundry));
+  }
+
+  #[test]
+  fn detects_hardhat_by_its_config_file_or_build_info_dir() {
+    let by_config = tempdir().expect("tempdir");
+    std::fs::write(
+      by_config.path().join("hardhat.config.ts"),
+      "export default {};",
+    )
+    .expect("write");
+    assert!(matches!(detect_layout(by_config.path()), ProjectLayout::Hardhat));
+
+    let by_build_info = tempdir().expect("tempdir");
+    std::fs::create_dir_all(by_build_info.path().join("artifacts/build-info")).expect("mkdir");
+    assert!(matches!(detect_layout(by_build_info.path()), ProjectLayout::Hardhat));
+  }
+
+  #[test]
+  fn detects_dapptools_by_its_src_and_lib_directories() {
+    let temp = tempdir().expect("tempdir");
+    std::fs::create_dir_all(temp.path().join("src")).expect("mkdir");
+    std::fs::create_dir_all(temp.path().join("lib")).expect("mkdir");
+    assert!(matches!(detect_layout(temp.path()), ProjectLayout::Dapptools));
+  }
+
+  #[test]
+  fn falls_back_to_synthetic_when_nothing_else_matches() {
+    let temp = tempdir().expect("tempdir");
+    assert!(matches!(detect_layout(temp.path()), ProjectLayout::Synthetic));
+  }
+
+  #[test]
+  fn dapptools_adapter_lays_out_src_lib_and_out() {
+    let temp = tempdir().expect("tempdir");
+    std::fs::create_dir_all(temp.path().join("src")).expect("mkdir");
+    std::fs::create_dir_all(temp.path().join("lib")).expect("mkdir");
+
+    let (_, context) = DapptoolsAdapter::load(temp.path()).expect("load");
+    assert!(matches!(context.layout, ProjectLayout::Dapptools));
+    assert!(context.auto_detect);
+    assert!(context.paths.sources.ends_with("src"));
+    assert!(context.paths.artifacts.ends_with("out"));
+    assert_eq!(context.paths.libraries, vec![context.root.join("lib")]);
+  }
 }
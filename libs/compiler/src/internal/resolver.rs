@@ -0,0 +1,224 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use foundry_compilers::artifacts::remappings::Remapping;
+
+use crate::compiler::graph::extract_imports;
+use crate::internal::path::canonicalize_path;
+
+/// The reachable subset of the on-disk import graph rooted at a set of entry files. Built by
+/// [`Graph::build`], which scans each file for `import` directives and resolves them the same way
+/// `build_project`'s underlying Foundry project would: `paths.libraries`/`remappings` and
+/// `include_paths` before the entries themselves have to exist. [`Graph::reachable_files`] is the
+/// minimal, correct set `build_project` should hand Foundry instead of the whole project root -
+/// everything the entries need, and nothing else.
+#[derive(Clone, Debug, Default)]
+pub struct Graph {
+  nodes: BTreeSet<PathBuf>,
+  edges: BTreeSet<(PathBuf, PathBuf)>,
+}
+
+impl Graph {
+  /// Walks `entries` and every file they transitively import (tolerating import cycles) and
+  /// returns the resulting graph. An import is resolved by first trying the longest-prefix
+  /// `remappings` match, then `include_paths` and `libraries` roots, then - for imports written
+  /// relative to the importing file - a plain relative join; an import that resolves to nothing on
+  /// disk under any of those is left out of the graph entirely, same as an unresolved import in
+  /// `compiler::graph::resolve_graph`.
+  pub fn build(
+    entries: &[PathBuf],
+    remappings: &[Remapping],
+    include_paths: &BTreeSet<PathBuf>,
+    libraries: &[PathBuf],
+  ) -> Self {
+    let mut nodes: BTreeSet<PathBuf> = entries
+      .iter()
+      .map(|entry| canonicalize_path(entry))
+      .collect();
+    let mut edges = BTreeSet::new();
+    let mut visited = BTreeSet::new();
+    let mut stack: Vec<PathBuf> = nodes.iter().cloned().collect();
+
+    while let Some(file) = stack.pop() {
+      if !visited.insert(file.clone()) {
+        continue;
+      }
+      let Ok(contents) = fs::read_to_string(&file) else {
+        continue;
+      };
+      for import in extract_imports(&contents) {
+        let Some(resolved) = resolve_import(&file, &import, remappings, include_paths, libraries)
+        else {
+          continue;
+        };
+        edges.insert((file.clone(), resolved.clone()));
+        if nodes.insert(resolved.clone()) {
+          stack.push(resolved);
+        }
+      }
+    }
+
+    Graph { nodes, edges }
+  }
+
+  /// Every file reached from the entry set, including the entries themselves.
+  pub fn reachable_files(&self) -> BTreeSet<PathBuf> {
+    self.nodes.clone()
+  }
+
+  pub fn edges(&self) -> &BTreeSet<(PathBuf, PathBuf)> {
+    &self.edges
+  }
+}
+
+/// Resolves a single `import` target written in `importing_file`, in priority order: the
+/// longest-prefix `remappings` match, then `include_paths`/`libraries` roots, then - only for
+/// imports starting with `.`/`..` - a join against `importing_file`'s own directory. Returns
+/// `None` (rather than a guess) when none of those candidates exist on disk.
+fn resolve_import(
+  importing_file: &Path,
+  import: &str,
+  remappings: &[Remapping],
+  include_paths: &BTreeSet<PathBuf>,
+  libraries: &[PathBuf],
+) -> Option<PathBuf> {
+  if let Some(candidate) = resolve_via_remapping(import, remappings) {
+    if candidate.exists() {
+      return Some(canonicalize_path(&candidate));
+    }
+  }
+
+  for root in include_paths.iter().chain(libraries.iter()) {
+    let candidate = root.join(import);
+    if candidate.exists() {
+      return Some(canonicalize_path(&candidate));
+    }
+  }
+
+  if import.starts_with('.') {
+    let base = importing_file.parent().unwrap_or_else(|| Path::new(""));
+    let candidate = base.join(import);
+    if candidate.exists() {
+      return Some(canonicalize_path(&candidate));
+    }
+  }
+
+  None
+}
+
+/// The remapping whose `name` is the longest prefix of `import`, joined with `import`'s remainder
+/// - mirrors solc's own "most specific remapping wins" rule.
+fn resolve_via_remapping(import: &str, remappings: &[Remapping]) -> Option<PathBuf> {
+  let mut best: Option<&Remapping> = None;
+  for remapping in remappings {
+    if import.starts_with(remapping.name.as_str())
+      && best
+        .map(|current| remapping.name.len() > current.name.len())
+        .unwrap_or(true)
+    {
+      best = Some(remapping);
+    }
+  }
+
+  best.map(|remapping| {
+    let suffix = import[remapping.name.len()..].trim_start_matches('/');
+    PathBuf::from(&remapping.path).join(suffix)
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn write(dir: &Path, relative: &str, contents: &str) -> PathBuf {
+    let path = dir.join(relative);
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent).expect("create parent");
+    }
+    fs::write(&path, contents).expect("write file");
+    path
+  }
+
+  #[test]
+  fn follows_relative_imports_transitively() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path();
+    write(root, "A.sol", "import \"./B.sol\";\ncontract A {}\n");
+    write(root, "B.sol", "contract B {}\n");
+
+    let entry = root.join("A.sol");
+    let graph = Graph::build(&[entry.clone()], &[], &BTreeSet::new(), &[]);
+
+    let reachable = graph.reachable_files();
+    assert_eq!(reachable.len(), 2);
+    assert!(reachable.contains(&canonicalize_path(&root.join("B.sol"))));
+  }
+
+  #[test]
+  fn resolves_the_longest_prefix_remapping() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path();
+    write(
+      root,
+      "A.sol",
+      "import \"@lib/token/ERC20.sol\";\ncontract A {}\n",
+    );
+    write(root, "vendor/token/ERC20.sol", "contract ERC20 {}\n");
+
+    let remappings = vec![Remapping {
+      context: None,
+      name: "@lib/".to_string(),
+      path: root.join("vendor/").to_string_lossy().to_string(),
+    }];
+
+    let entry = root.join("A.sol");
+    let graph = Graph::build(&[entry], &remappings, &BTreeSet::new(), &[]);
+
+    assert!(graph
+      .reachable_files()
+      .contains(&canonicalize_path(&root.join("vendor/token/ERC20.sol"))));
+  }
+
+  #[test]
+  fn resolves_through_a_library_root() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path();
+    write(root, "A.sol", "import \"lib-pkg/Util.sol\";\ncontract A {}\n");
+    let lib_dir = root.join("node_modules");
+    write(&lib_dir, "lib-pkg/Util.sol", "contract Util {}\n");
+
+    let entry = root.join("A.sol");
+    let graph = Graph::build(&[entry], &[], &BTreeSet::new(), &[lib_dir.clone()]);
+
+    assert!(graph
+      .reachable_files()
+      .contains(&canonicalize_path(&lib_dir.join("lib-pkg/Util.sol"))));
+  }
+
+  #[test]
+  fn tolerates_import_cycles() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path();
+    write(root, "A.sol", "import \"./B.sol\";\ncontract A {}\n");
+    write(root, "B.sol", "import \"./A.sol\";\ncontract B {}\n");
+
+    let entry = root.join("A.sol");
+    let graph = Graph::build(&[entry], &[], &BTreeSet::new(), &[]);
+
+    assert_eq!(graph.reachable_files().len(), 2);
+  }
+
+  #[test]
+  fn an_import_with_no_matching_file_is_left_unresolved() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let root = temp.path();
+    write(root, "A.sol", "import \"missing-package/X.sol\";\ncontract A {}\n");
+
+    let entry = root.join("A.sol");
+    let graph = Graph::build(&[entry], &[], &BTreeSet::new(), &[]);
+
+    assert_eq!(graph.reachable_files().len(), 1);
+    assert!(graph.edges().is_empty());
+  }
+}
@@ -1,11 +1,13 @@
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 
+use foundry_compilers::artifacts::vyper::{VyperOptimizationMode, VyperSettings};
 use foundry_compilers::artifacts::{output_selection::OutputSelection, Settings};
 use napi::bindgen_prelude::Result;
 use serde::{Deserialize, Deserializer, Serialize};
-use serde_json;
+use serde_json::{self, Value};
 
-use crate::internal::errors::map_napi_error;
+use crate::internal::errors::{map_napi_error, napi_error};
 
 /// Rust-facing optional overrides that can be merged into Foundry `Settings`.
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -52,8 +54,36 @@ pub struct CompilerSettingsOptions {
   pub libraries: Option<BTreeMap<String, BTreeMap<String, String>>>,
 }
 
+/// How [`merge_settings_json`] reconciles an array present on both sides of a merge. Modeled on how
+/// `docker-compose` merges list- and map-valued fields across layered files instead of letting the
+/// last file silently win.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SettingsMergeStrategy {
+  /// The override array replaces the base array outright - the behavior before this existed.
+  #[default]
+  Replace,
+  /// Concatenate base then override and drop duplicates, preserving first-seen order - so e.g. an
+  /// override `remappings` adds to the base list instead of discarding it, and an `outputSelection`
+  /// leaf gains the override's requested outputs alongside whatever the base already selected.
+  Append,
+}
+
 impl CompilerSettingsOptions {
-  pub(crate) fn overlay(self, base: &Settings) -> Result<Settings> {
+  pub(crate) fn overlay(
+    self,
+    base: &Settings,
+    strategy: SettingsMergeStrategy,
+  ) -> Result<Settings> {
+    if let Some(steps) = self
+      .optimizer
+      .as_ref()
+      .and_then(|optimizer| optimizer.details.as_ref())
+      .and_then(|details| details.yul_details.as_ref())
+      .and_then(|yul_details| yul_details.optimizer_steps.as_ref())
+    {
+      validate_optimizer_steps(steps)?;
+    }
+
     let mut base_value = map_napi_error(
       serde_json::to_value(base),
       "Failed to serialise base compiler settings",
@@ -63,7 +93,7 @@ impl CompilerSettingsOptions {
       "Failed to serialise compiler settings",
     )?;
 
-    merge_settings_json(&mut base_value, overrides);
+    merge_settings_json(&mut base_value, overrides, strategy);
 
     map_napi_error(
       serde_json::from_value(base_value),
@@ -137,8 +167,11 @@ pub struct SettingsMetadataOptions {
   pub use_literal_content: Option<bool>,
   #[serde(skip_serializing_if = "Option::is_none")]
   pub bytecode_hash: Option<BytecodeHash>,
-  #[serde(skip_serializing_if = "Option::is_none")]
-  pub cbor_metadata: Option<bool>,
+  /// Whether solc appends the CBOR metadata hash to deployed bytecode. Renders as solc's
+  /// `appendCBOR` key rather than the struct's default camelCase (`appendCbor`), since solc treats
+  /// "CBOR" as an all-caps acronym.
+  #[serde(rename = "appendCBOR", skip_serializing_if = "Option::is_none")]
+  pub append_cbor: Option<bool>,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -273,8 +306,8 @@ pub struct JsSettingsMetadataOptions {
   pub use_literal_content: Option<bool>,
   #[serde(skip_serializing_if = "Option::is_none")]
   pub bytecode_hash: Option<BytecodeHash>,
-  #[serde(skip_serializing_if = "Option::is_none")]
-  pub cbor_metadata: Option<bool>,
+  #[serde(rename = "appendCBOR", skip_serializing_if = "Option::is_none")]
+  pub append_cbor: Option<bool>,
 }
 
 #[napi(object, js_name = "ModelCheckerSettings")]
@@ -312,18 +345,31 @@ where
   Option::<T>::deserialize(deserializer).map(|opt| opt.unwrap_or_default())
 }
 
-pub(crate) fn merge_settings_json(base: &mut serde_json::Value, overrides: serde_json::Value) {
+pub(crate) fn merge_settings_json(
+  base: &mut serde_json::Value,
+  overrides: serde_json::Value,
+  strategy: SettingsMergeStrategy,
+) {
   match (base, overrides) {
     (serde_json::Value::Object(base_map), serde_json::Value::Object(overrides_map)) => {
       for (key, value) in overrides_map {
         match base_map.get_mut(&key) {
-          Some(existing) => merge_settings_json(existing, value),
+          Some(existing) => merge_settings_json(existing, value, strategy),
           None => {
             base_map.insert(key, value);
           }
         }
       }
     }
+    (serde_json::Value::Array(base_items), serde_json::Value::Array(override_items))
+      if strategy == SettingsMergeStrategy::Append =>
+    {
+      for item in override_items {
+        if !base_items.contains(&item) {
+          base_items.push(item);
+        }
+      }
+    }
     (target, value) => {
       *target = value;
     }
@@ -333,12 +379,21 @@ pub(crate) fn merge_settings_json(base: &mut serde_json::Value, overrides: serde
 pub fn merge_settings(
   base: &Settings,
   overrides: Option<&CompilerSettingsOptions>,
+  strategy: SettingsMergeStrategy,
 ) -> Result<Settings> {
   match overrides {
     Some(settings) => {
-      let mut merged = settings.clone().overlay(base)?;
+      if let Some(libraries) = &settings.libraries {
+        validate_library_addresses(libraries)?;
+      }
+      let mut merged = settings.clone().overlay(base, strategy)?;
       if let Some(selection) = &settings.output_selection {
-        merged.output_selection = selection.clone().into();
+        merged.output_selection = match strategy {
+          SettingsMergeStrategy::Replace => selection.clone().into(),
+          SettingsMergeStrategy::Append => {
+            merge_output_selection(&base.output_selection, selection, strategy)?
+          }
+        };
       }
       sanitize_settings(&merged)
     }
@@ -346,14 +401,915 @@ pub fn merge_settings(
   }
 }
 
+/// Unions `override_selection` into `base_selection` leaf-by-leaf (file -> contract -> output
+/// kinds) via [`merge_settings_json`] instead of the wholesale replace [`merge_settings`] otherwise
+/// does, so a [`SettingsMergeStrategy::Append`] override adds requested outputs alongside whatever
+/// the base already selected rather than discarding it.
+fn merge_output_selection(
+  base_selection: &OutputSelection,
+  override_selection: &BTreeMap<String, BTreeMap<String, Vec<String>>>,
+  strategy: SettingsMergeStrategy,
+) -> Result<OutputSelection> {
+  let mut base_value = map_napi_error(
+    serde_json::to_value(base_selection),
+    "Failed to serialise base output selection",
+  )?;
+  let override_value = map_napi_error(
+    serde_json::to_value(override_selection),
+    "Failed to serialise output selection override",
+  )?;
+  merge_settings_json(&mut base_value, override_value, strategy);
+  map_napi_error(
+    serde_json::from_value(base_value),
+    "Failed to parse merged output selection",
+  )
+}
+
+/// The merged `Settings` from [`merge_settings_reporting`] alongside any override key paths it
+/// couldn't match against the `CompilerSettingsOptions` schema.
+#[derive(Clone, Debug, Default)]
+pub struct MergedSettings {
+  pub settings: Settings,
+  pub ignored: Vec<String>,
+}
+
+/// Same merge as [`merge_settings`], plus a validation pass borrowed from the way Deno's tsconfig
+/// validator keeps a static list of recognized compiler options: every dotted key path present in
+/// `overrides` is checked against the paths a fully-populated `CompilerSettingsOptions` would
+/// serialize to, and anything with no counterpart (a typo like `optmizer`, or a solc option this
+/// crate doesn't model) is additionally surfaced in `ignored` rather than merging in silently.
+pub fn merge_settings_reporting(
+  base: &Settings,
+  overrides: Option<&CompilerSettingsOptions>,
+  strategy: SettingsMergeStrategy,
+) -> Result<MergedSettings> {
+  let ignored = match overrides {
+    Some(settings) => ignored_settings_keys(settings)?,
+    None => Vec::new(),
+  };
+  let settings = merge_settings(base, overrides, strategy)?;
+  Ok(MergedSettings { settings, ignored })
+}
+
+/// Dotted key paths whose values are a caller-defined map (a file/library/contract name) rather
+/// than a recognized solc setting, so [`collect_value_paths`] stops descending into them instead of
+/// mistaking arbitrary map keys for malformed nested settings.
+const OPAQUE_OVERRIDE_PATHS: &[&str] = &["outputSelection", "libraries", "modelChecker.contracts"];
+
+/// Pushes every dotted key path reachable from `value` into `out`, stopping at leaves, empty
+/// containers, and [`OPAQUE_OVERRIDE_PATHS`].
+fn collect_value_paths(value: &Value, prefix: &str, out: &mut Vec<String>) {
+  if let Value::Object(map) = value {
+    for (key, child) in map {
+      let path = if prefix.is_empty() {
+        key.clone()
+      } else {
+        format!("{prefix}.{key}")
+      };
+      let is_opaque = OPAQUE_OVERRIDE_PATHS.contains(&path.as_str());
+      out.push(path.clone());
+      if !is_opaque {
+        collect_value_paths(child, &path, out);
+      }
+    }
+  }
+}
+
+/// A `CompilerSettingsOptions` with every field populated, so serializing it yields every key path
+/// the schema recognizes - the comparison baseline for [`ignored_settings_keys`].
+fn fully_populated_settings_schema() -> CompilerSettingsOptions {
+  CompilerSettingsOptions {
+    stop_after: Some(String::new()),
+    remappings: Some(Vec::new()),
+    optimizer: Some(OptimizerSettingsOptions {
+      enabled: Some(false),
+      runs: Some(0),
+      details: Some(OptimizerDetailsOptions {
+        peephole: Some(false),
+        inliner: Some(false),
+        jumpdest_remover: Some(false),
+        order_literals: Some(false),
+        deduplicate: Some(false),
+        cse: Some(false),
+        constant_optimizer: Some(false),
+        yul: Some(false),
+        yul_details: Some(YulDetailsOptions {
+          stack_allocation: Some(false),
+          optimizer_steps: Some(String::new()),
+        }),
+        simple_counter_for_loop_unchecked_increment: Some(false),
+      }),
+    }),
+    model_checker: Some(ModelCheckerSettingsOptions {
+      contracts: BTreeMap::new(),
+      engine: Some(ModelCheckerEngine::Chc),
+      timeout: Some(0),
+      targets: Some(Vec::new()),
+      invariants: Some(Vec::new()),
+      show_unproved: Some(false),
+      div_mod_with_slacks: Some(false),
+      solvers: Some(Vec::new()),
+      show_unsupported: Some(false),
+      show_proved_safe: Some(false),
+    }),
+    metadata: Some(SettingsMetadataOptions {
+      use_literal_content: Some(false),
+      bytecode_hash: Some(BytecodeHash::Ipfs),
+      append_cbor: Some(false),
+    }),
+    output_selection: Some(BTreeMap::new()),
+    evm_version: Some(EvmVersion::Byzantium),
+    via_ir: Some(false),
+    debug: Some(DebuggingSettingsOptions {
+      revert_strings: Some(RevertStrings::Default),
+      debug_info: vec![String::new()],
+    }),
+    libraries: Some(BTreeMap::new()),
+  }
+}
+
+fn known_settings_key_paths() -> std::collections::BTreeSet<String> {
+  let schema = serde_json::to_value(fully_populated_settings_schema())
+    .expect("a fully populated CompilerSettingsOptions always serializes");
+  let mut paths = Vec::new();
+  collect_value_paths(&schema, "", &mut paths);
+  paths.extend(OPAQUE_OVERRIDE_PATHS.iter().map(|path| path.to_string()));
+  paths.into_iter().collect()
+}
+
+/// The dotted key paths in `overrides` with no counterpart in the `CompilerSettingsOptions`
+/// schema - see [`merge_settings_reporting`].
+fn ignored_settings_keys(overrides: &CompilerSettingsOptions) -> Result<Vec<String>> {
+  let value = map_napi_error(
+    serde_json::to_value(overrides),
+    "Failed to serialise compiler settings",
+  )?;
+  let mut override_paths = Vec::new();
+  collect_value_paths(&value, "", &mut override_paths);
+
+  let known = known_settings_key_paths();
+  let mut ignored: Vec<String> = override_paths
+    .into_iter()
+    .filter(|path| !known.contains(path))
+    .collect();
+  ignored.sort();
+  ignored.dedup();
+  Ok(ignored)
+}
+
+/// Parses `input` as JSONC - JSON that tolerates `//` and `/* */` comments and a trailing comma
+/// before a closing `}`/`]` - the same leniency Deno's `tsconfig.json` loader affords, since users
+/// routinely keep their solc config annotated and expect to paste it straight through rather than
+/// stripping it by hand first. Comments and trailing commas are stripped with [`strip_jsonc`]
+/// before the result is fed into the same `serde_json` deserialization `CompilerSettingsOptions`
+/// already uses for strict JSON.
+pub fn from_jsonc(input: &str) -> Result<CompilerSettingsOptions> {
+  let cleaned = strip_jsonc(input);
+  map_napi_error(
+    serde_json::from_str(&cleaned),
+    "Failed to parse JSONC compiler settings",
+  )
+}
+
+/// Strips `//` line comments, `/* */` block comments, and trailing commas from `input`, leaving
+/// string literals untouched so a setting value like a remapping containing `//` is preserved.
+fn strip_jsonc(input: &str) -> String {
+  strip_trailing_commas(&strip_jsonc_comments(input))
+}
+
+fn strip_jsonc_comments(input: &str) -> String {
+  let chars: Vec<char> = input.chars().collect();
+  let mut out = String::with_capacity(input.len());
+  let mut in_string = false;
+  let mut i = 0;
+  while i < chars.len() {
+    let ch = chars[i];
+    if in_string {
+      out.push(ch);
+      if ch == '\\' && i + 1 < chars.len() {
+        out.push(chars[i + 1]);
+        i += 2;
+        continue;
+      }
+      if ch == '"' {
+        in_string = false;
+      }
+      i += 1;
+      continue;
+    }
+    match ch {
+      '"' => {
+        in_string = true;
+        out.push(ch);
+        i += 1;
+      }
+      '/' if chars.get(i + 1) == Some(&'/') => {
+        while i < chars.len() && chars[i] != '\n' {
+          i += 1;
+        }
+      }
+      '/' if chars.get(i + 1) == Some(&'*') => {
+        i += 2;
+        while i + 1 < chars.len() && !(chars[i] == '*' && chars[i + 1] == '/') {
+          i += 1;
+        }
+        i = (i + 2).min(chars.len());
+      }
+      _ => {
+        out.push(ch);
+        i += 1;
+      }
+    }
+  }
+  out
+}
+
+fn strip_trailing_commas(input: &str) -> String {
+  let chars: Vec<char> = input.chars().collect();
+  let mut out = String::with_capacity(input.len());
+  let mut in_string = false;
+  let mut i = 0;
+  while i < chars.len() {
+    let ch = chars[i];
+    if in_string {
+      out.push(ch);
+      if ch == '\\' && i + 1 < chars.len() {
+        out.push(chars[i + 1]);
+        i += 2;
+        continue;
+      }
+      if ch == '"' {
+        in_string = false;
+      }
+      i += 1;
+      continue;
+    }
+    match ch {
+      '"' => {
+        in_string = true;
+        out.push(ch);
+        i += 1;
+      }
+      ',' => {
+        let mut lookahead = i + 1;
+        while lookahead < chars.len() && chars[lookahead].is_whitespace() {
+          lookahead += 1;
+        }
+        if lookahead < chars.len() && matches!(chars[lookahead], '}' | ']') {
+          i += 1;
+        } else {
+          out.push(ch);
+          i += 1;
+        }
+      }
+      _ => {
+        out.push(ch);
+        i += 1;
+      }
+    }
+  }
+  out
+}
+
+/// Parses a `foundry.toml`-style document (`[profile.default]`, `[profile.ci]`, ...) and resolves
+/// `profile` into a `CompilerSettingsOptions`. `default` is always read first, then `profile`'s own
+/// section is deep-merged on top via [`merge_settings_json`], so a non-default profile only needs
+/// to specify the fields it overrides - the same layering Foundry itself applies to profiles.
+/// Mirrors the multi-document handling in docker-compose-style config: one file describes several
+/// alternative configurations, resolved into one at load time. Recognizes the `optimizer`,
+/// `optimizer_runs`, `via_ir`, `evm_version`, `remappings`, `libraries`, and `model_checker` keys.
+pub fn load_profile(toml_str: &str, profile: &str) -> Result<CompilerSettingsOptions> {
+  let document: toml::Value =
+    toml_str.parse().map_err(|err| napi_error(format!("Failed to parse foundry.toml: {err}")))?;
+
+  let profiles = document
+    .get("profile")
+    .and_then(toml::Value::as_table)
+    .ok_or_else(|| napi_error("foundry.toml has no [profile] table"))?;
+
+  let mut merged = Value::Object(Default::default());
+  if let Some(default_section) = profiles.get("default") {
+    merge_settings_json(
+      &mut merged,
+      profile_section_to_settings_json(default_section)?,
+      SettingsMergeStrategy::Replace,
+    );
+  }
+  if profile != "default" {
+    let section = profiles
+      .get(profile)
+      .ok_or_else(|| napi_error(format!("Unknown foundry.toml profile \"{profile}\"")))?;
+    merge_settings_json(
+      &mut merged,
+      profile_section_to_settings_json(section)?,
+      SettingsMergeStrategy::Replace,
+    );
+  }
+
+  map_napi_error(
+    serde_json::from_value(merged),
+    "Failed to parse foundry.toml profile settings",
+  )
+}
+
+/// Maps one `[profile.*]` table's Foundry-style keys onto the `CompilerSettingsOptions` JSON shape
+/// [`merge_settings_json`] understands - see [`load_profile`].
+fn profile_section_to_settings_json(section: &toml::Value) -> Result<Value> {
+  let table = section
+    .as_table()
+    .ok_or_else(|| napi_error("foundry.toml profile section must be a table"))?;
+
+  let mut out = serde_json::Map::new();
+
+  let mut optimizer = serde_json::Map::new();
+  if let Some(enabled) = table.get("optimizer").and_then(toml::Value::as_bool) {
+    optimizer.insert("enabled".to_string(), Value::Bool(enabled));
+  }
+  if let Some(runs) = table.get("optimizer_runs").and_then(toml::Value::as_integer) {
+    optimizer.insert("runs".to_string(), Value::from(runs));
+  }
+  if !optimizer.is_empty() {
+    out.insert("optimizer".to_string(), Value::Object(optimizer));
+  }
+
+  if let Some(via_ir) = table.get("via_ir").and_then(toml::Value::as_bool) {
+    out.insert("viaIR".to_string(), Value::Bool(via_ir));
+  }
+  if let Some(evm_version) = table.get("evm_version").and_then(toml::Value::as_str) {
+    out.insert("evmVersion".to_string(), Value::String(evm_version.to_string()));
+  }
+  if let Some(remappings) = table.get("remappings").and_then(toml::Value::as_array) {
+    let remappings: Vec<Value> = remappings
+      .iter()
+      .filter_map(toml::Value::as_str)
+      .map(|remapping| Value::String(remapping.to_string()))
+      .collect();
+    out.insert("remappings".to_string(), Value::Array(remappings));
+  }
+  if let Some(libraries) = table.get("libraries").and_then(toml::Value::as_array) {
+    out.insert("libraries".to_string(), parse_library_entries(libraries)?);
+  }
+  if let Some(model_checker) = table.get("model_checker").and_then(toml::Value::as_table) {
+    out.insert(
+      "modelChecker".to_string(),
+      snake_case_keys_to_camel_case(model_checker),
+    );
+  }
+
+  Ok(Value::Object(out))
+}
+
+/// Parses `file:name:address` library entries (Foundry's `libraries = [...]` shape) into the
+/// `{ file: { name: address } }` map `CompilerSettingsOptions::libraries` expects.
+fn parse_library_entries(entries: &[toml::Value]) -> Result<Value> {
+  let mut by_file: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+  for entry in entries.iter().filter_map(toml::Value::as_str) {
+    let mut parts = entry.splitn(3, ':');
+    let (Some(file), Some(name), Some(address)) = (parts.next(), parts.next(), parts.next())
+    else {
+      return Err(napi_error(format!(
+        "Invalid library entry \"{entry}\": expected \"file:name:address\""
+      )));
+    };
+    by_file
+      .entry(file.to_string())
+      .or_default()
+      .insert(name.to_string(), address.to_string());
+  }
+  map_napi_error(serde_json::to_value(by_file), "Failed to serialise libraries")
+}
+
+/// Recursively converts a TOML table's `snake_case` keys to the `camelCase` keys the nested
+/// option structs (e.g. `ModelCheckerSettingsOptions`) expect, so a `[profile.*.model_checker]`
+/// section can be written the same idiomatic way the rest of a `foundry.toml` profile is.
+fn snake_case_keys_to_camel_case(table: &toml::Table) -> Value {
+  let converted: serde_json::Map<String, Value> = table
+    .iter()
+    .map(|(key, value)| (to_camel_case(key), toml_value_to_json(value)))
+    .collect();
+  Value::Object(converted)
+}
+
+fn toml_value_to_json(value: &toml::Value) -> Value {
+  match value {
+    toml::Value::Table(table) => snake_case_keys_to_camel_case(table),
+    toml::Value::Array(items) => Value::Array(items.iter().map(toml_value_to_json).collect()),
+    other => serde_json::to_value(other).unwrap_or(Value::Null),
+  }
+}
+
+fn to_camel_case(key: &str) -> String {
+  let mut camel = String::with_capacity(key.len());
+  let mut capitalize_next = false;
+  for ch in key.chars() {
+    if ch == '_' {
+      capitalize_next = true;
+    } else if capitalize_next {
+      camel.extend(ch.to_uppercase());
+      capitalize_next = false;
+    } else {
+      camel.push(ch);
+    }
+  }
+  camel
+}
+
+/// Every configured library address must be a `0x`-prefixed 20-byte hex string - the shape solc
+/// itself requires to link a deployed library address into a placeholder left in unlinked
+/// bytecode. Catching a malformed address here, before it ever reaches solc, turns a cryptic
+/// link-time failure into a precise error naming the offending file and library.
+fn validate_library_addresses(libraries: &BTreeMap<String, BTreeMap<String, String>>) -> Result<()> {
+  for (file, libs) in libraries {
+    for (name, address) in libs {
+      let is_valid_address = address.len() == 42
+        && address.starts_with("0x")
+        && address[2..].chars().all(|c| c.is_ascii_hexdigit());
+      if !is_valid_address {
+        return Err(napi_error(format!(
+          "Invalid library address for \"{file}:{name}\": expected a 0x-prefixed 20-byte hex string, got \"{address}\"."
+        )));
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Step abbreviations solc's Yul optimizer recognizes in an `optimizerSteps` sequence, per
+/// `OptimizerSteps.json`'s `AbstractMap`.
+const YUL_OPTIMIZER_STEP_CHARS: &str = "fucdlCFGrgVTUhigOojsxI";
+
+/// Validates a Yul `optimizer_steps` sequence against solc's grammar before it ever reaches solc:
+/// an optional main sequence of single-character step abbreviations, optionally followed by one
+/// `:` and a cleanup sequence drawn from the same abbreviation set, with `[...]` loop groups that
+/// must be balanced. Whitespace is ignored. Returns an error naming the offending character (or
+/// bracket) and its 0-based index, turning what would otherwise be a cryptic solc failure into a
+/// precise one.
+pub(crate) fn validate_optimizer_steps(steps: &str) -> Result<()> {
+  let mut open_brackets: Vec<usize> = Vec::new();
+  let mut seen_colon = false;
+
+  for (index, ch) in steps.char_indices() {
+    if ch.is_whitespace() {
+      continue;
+    }
+    match ch {
+      '[' => open_brackets.push(index),
+      ']' => {
+        if open_brackets.pop().is_none() {
+          return Err(napi_error(format!(
+            "Invalid Yul optimizer steps \"{steps}\": unmatched ']' at index {index}"
+          )));
+        }
+      }
+      ':' if !seen_colon => seen_colon = true,
+      ':' => {
+        return Err(napi_error(format!(
+          "Invalid Yul optimizer steps \"{steps}\": unexpected second ':' at index {index}"
+        )));
+      }
+      _ if YUL_OPTIMIZER_STEP_CHARS.contains(ch) => {}
+      _ => {
+        return Err(napi_error(format!(
+          "Invalid Yul optimizer steps \"{steps}\": unknown step '{ch}' at index {index}"
+        )));
+      }
+    }
+  }
+
+  if let Some(&index) = open_brackets.first() {
+    return Err(napi_error(format!(
+      "Invalid Yul optimizer steps \"{steps}\": unbalanced '[' at index {index}"
+    )));
+  }
+
+  Ok(())
+}
+
 pub fn sanitize_settings(settings: &Settings) -> Result<Settings> {
   let mut merged = settings.clone();
   if output_selection_is_effectively_empty(&merged.output_selection) {
     merged.output_selection = Settings::default().output_selection;
   }
+  if let Some(steps) = merged
+    .optimizer
+    .details
+    .as_ref()
+    .and_then(|details| details.yul_details.as_ref())
+    .and_then(|yul_details| yul_details.optimizer_steps.as_ref())
+  {
+    validate_optimizer_steps(steps)?;
+  }
+  validate_evm_version_capabilities(&merged)?;
   Ok(merged)
 }
 
+/// Which optional settings solc actually supports on a given `EvmVersion` - its capability surface
+/// changes across hardforks, so a combination that compiles cleanly on `cancun` can be rejected or
+/// silently misbuilt on an older target. Keyed by `EvmVersion` rather than scattering version
+/// checks across the merge pipeline, following the same classify-then-check shape the Deno
+/// tsconfig validator uses for its own compiler options.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct EvmVersionCapabilities {
+  /// The CHC model-checker engine's formal encoding assumes the memory-safety guarantees recent
+  /// EVM code generation provides, so this crate only allows it from Shanghai onward.
+  supports_chc_model_checker: bool,
+  /// Whether `metadata.bytecodeHash = "none"` (omitting the metadata hash entirely) is allowed -
+  /// solc only added the ability to opt out of metadata hashing from Constantinople onward.
+  supports_omitting_bytecode_hash: bool,
+}
+
+const fn evm_version_capabilities(version: EvmVersion) -> EvmVersionCapabilities {
+  match version {
+    EvmVersion::Byzantium => EvmVersionCapabilities {
+      supports_chc_model_checker: false,
+      supports_omitting_bytecode_hash: false,
+    },
+    EvmVersion::Constantinople
+    | EvmVersion::Petersburg
+    | EvmVersion::Istanbul
+    | EvmVersion::Berlin
+    | EvmVersion::London
+    | EvmVersion::Paris => EvmVersionCapabilities {
+      supports_chc_model_checker: false,
+      supports_omitting_bytecode_hash: true,
+    },
+    EvmVersion::Shanghai | EvmVersion::Cancun | EvmVersion::Prague => EvmVersionCapabilities {
+      supports_chc_model_checker: true,
+      supports_omitting_bytecode_hash: true,
+    },
+  }
+}
+
+/// Checks a merged `Settings` against [`evm_version_capabilities`] for the selected `evm_version`,
+/// and rejects codegen-only options when `stop_after = "parsing"` means solc never reaches
+/// codegen to apply them. Returns an actionable error instead of deferring to solc, which either
+/// rejects these combinations with a much less specific message or, for some, silently ignores the
+/// option instead of erroring at all. Reads through `settings`'s JSON representation rather than
+/// its concrete fields, the same way [`merge_settings_json`] reconciles settings without needing
+/// to know every nested type `Settings` itself uses.
+fn validate_evm_version_capabilities(settings: &Settings) -> Result<()> {
+  let value = map_napi_error(
+    serde_json::to_value(settings),
+    "Failed to serialise settings for EVM-version validation",
+  )?;
+
+  let version: EvmVersion = match value.get("evmVersion") {
+    Some(Value::String(_)) => map_napi_error(
+      serde_json::from_value(value["evmVersion"].clone()),
+      "Failed to parse evm_version for validation",
+    )?,
+    _ => EvmVersion::Prague,
+  };
+  let capabilities = evm_version_capabilities(version);
+
+  let chc_engine_selected = value
+    .pointer("/modelChecker/engine")
+    .and_then(Value::as_str)
+    == Some("chc");
+  if chc_engine_selected && !capabilities.supports_chc_model_checker {
+    return Err(napi_error(format!(
+      "modelChecker.engine \"chc\" requires evm_version >= shanghai, got {version:?}"
+    )));
+  }
+
+  let bytecode_hash_omitted = value.pointer("/metadata/bytecodeHash").and_then(Value::as_str)
+    == Some("none");
+  if bytecode_hash_omitted && !capabilities.supports_omitting_bytecode_hash {
+    return Err(napi_error(format!(
+      "metadata.bytecodeHash \"none\" requires evm_version >= constantinople, got {version:?}"
+    )));
+  }
+
+  if value.get("stopAfter").and_then(Value::as_str) == Some("parsing") {
+    if value.get("viaIR").and_then(Value::as_bool) == Some(true) {
+      return Err(napi_error(
+        "viaIR is a codegen-only option and cannot be combined with stop_after = \"parsing\"",
+      ));
+    }
+    let optimizer_enabled =
+      value.pointer("/optimizer/enabled").and_then(Value::as_bool) == Some(true);
+    let optimizer_has_details = value.pointer("/optimizer/details").is_some();
+    if optimizer_enabled || optimizer_has_details {
+      return Err(napi_error(
+        "optimizer settings are codegen-only and cannot be combined with stop_after = \"parsing\"",
+      ));
+    }
+  }
+
+  Ok(())
+}
+
+/// A solc output kind that can be requested without hand-writing a raw `outputSelection` map.
+///
+/// Each variant expands to the corresponding Standard JSON output-selection key under `"*": "*"`
+/// and is merged with (rather than replacing) any explicit `outputSelection` override.
+#[napi(string_enum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExtraOutputKind {
+  StorageLayout,
+  GasEstimates,
+  MethodIdentifiers,
+  Metadata,
+  DevDoc,
+  UserDoc,
+  Ir,
+  IrOptimized,
+}
+
+impl ExtraOutputKind {
+  fn output_selection_key(self) -> &'static str {
+    match self {
+      ExtraOutputKind::StorageLayout => "storageLayout",
+      ExtraOutputKind::GasEstimates => "evm.gasEstimates",
+      ExtraOutputKind::MethodIdentifiers => "evm.methodIdentifiers",
+      ExtraOutputKind::Metadata => "metadata",
+      ExtraOutputKind::DevDoc => "devdoc",
+      ExtraOutputKind::UserDoc => "userdoc",
+      ExtraOutputKind::Ir => "ir",
+      ExtraOutputKind::IrOptimized => "irOptimized",
+    }
+  }
+}
+
+/// Merges the output-selection keys implied by `extra` into `settings`, adding them under the
+/// `"*": "*"` wildcard alongside whatever the caller already selected.
+pub fn apply_extra_output(settings: &mut Settings, extra: &[ExtraOutputKind]) {
+  if extra.is_empty() {
+    return;
+  }
+
+  let mut selection: BTreeMap<String, BTreeMap<String, Vec<String>>> =
+    settings.output_selection.as_ref().clone();
+  let contracts = selection
+    .entry("*".to_string())
+    .or_default()
+    .entry("*".to_string())
+    .or_default();
+
+  for kind in extra {
+    let key = kind.output_selection_key();
+    if !contracts.iter().any(|existing| existing == key) {
+      contracts.push(key.to_string());
+    }
+  }
+
+  settings.output_selection = selection.into();
+}
+
+/// A minimal solc output kind a caller can request via a config's `requested_outputs` override to
+/// skip compiling anything else. Unlike [`ExtraOutputKind`] (which is additive, merged into
+/// whatever selection is already set), requesting these *replaces* the effective output selection
+/// with only these keys - the motivation is to skip the otherwise-default (and expensive) JSON
+/// AST output unless `Ast` is itself requested.
+#[napi(string_enum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RequestedOutputKind {
+  Abi,
+  DeployedBytecode,
+  MethodIdentifiers,
+  Ast,
+  StorageLayout,
+}
+
+impl RequestedOutputKind {
+  fn output_selection_key(self) -> &'static str {
+    match self {
+      RequestedOutputKind::Abi => "abi",
+      RequestedOutputKind::DeployedBytecode => "evm.deployedBytecode",
+      RequestedOutputKind::MethodIdentifiers => "evm.methodIdentifiers",
+      RequestedOutputKind::Ast => "ast",
+      RequestedOutputKind::StorageLayout => "storageLayout",
+    }
+  }
+}
+
+/// The output-selection map implied by `requested` - see [`RequestedOutputKind`]. `Ast` is spliced
+/// in via `OutputSelection::ast_output_selection()` rather than hand-written under the `"*"`
+/// contract wildcard, since AST output is keyed per source file rather than per contract.
+fn requested_output_selection(
+  requested: &[RequestedOutputKind],
+) -> BTreeMap<String, BTreeMap<String, Vec<String>>> {
+  let mut selection: BTreeMap<String, BTreeMap<String, Vec<String>>> = BTreeMap::new();
+
+  for kind in requested {
+    if *kind == RequestedOutputKind::Ast {
+      merge_ast_output_selection(&mut selection);
+      continue;
+    }
+
+    push_wildcard_output(&mut selection, kind.output_selection_key());
+  }
+
+  selection
+}
+
+/// Merges `OutputSelection::ast_output_selection()` into `selection`, keyed per source file
+/// (rather than under the `"*"` contract wildcard, since AST output is file-level, not
+/// per-contract) - shared between [`requested_output_selection`] and [`artifact_output_selection`].
+fn merge_ast_output_selection(selection: &mut BTreeMap<String, BTreeMap<String, Vec<String>>>) {
+  for (file, contracts) in OutputSelection::ast_output_selection().as_ref().clone() {
+    let entry = selection.entry(file).or_default();
+    for (contract, outputs) in contracts {
+      let existing = entry.entry(contract).or_default();
+      for output in outputs {
+        if !existing.contains(&output) {
+          existing.push(output);
+        }
+      }
+    }
+  }
+}
+
+/// Adds `key` under the `"*": "*"` wildcard (every file, every contract) if it isn't already
+/// selected there.
+fn push_wildcard_output(
+  selection: &mut BTreeMap<String, BTreeMap<String, Vec<String>>>,
+  key: &str,
+) {
+  let contracts = selection
+    .entry("*".to_string())
+    .or_default()
+    .entry("*".to_string())
+    .or_default();
+  if !contracts.iter().any(|existing| existing == key) {
+    contracts.push(key.to_string());
+  }
+}
+
+/// Rewrites `settings.output_selection` down to exactly the keys implied by `requested`, dropping
+/// anything selected previously. A no-op when `requested` is empty.
+pub fn apply_requested_outputs(settings: &mut Settings, requested: &[RequestedOutputKind]) {
+  if requested.is_empty() {
+    return;
+  }
+  settings.output_selection = requested_output_selection(requested).into();
+}
+
+/// Same rewrite as [`apply_requested_outputs`], but for a standalone `OutputSelection` (Vyper's
+/// settings carry one directly, rather than through a full `Settings`).
+pub fn apply_requested_outputs_to_selection(
+  selection: &mut OutputSelection,
+  requested: &[RequestedOutputKind],
+) {
+  if requested.is_empty() {
+    return;
+  }
+  *selection = requested_output_selection(requested).into();
+}
+
+/// Rust-facing optional overrides merged into a `VyperSettings`, mirroring
+/// `CompilerSettingsOptions`'s role for `Settings` - kept as its own (flatter) struct rather than
+/// reused, since Vyper's settings have no optimizer/model-checker/debug sub-structs to merge.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VyperSettingsOptions {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub optimize: Option<VyperOptimizationMode>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub evm_version: Option<EvmVersion>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub bytecode_metadata: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub search_paths: Option<Vec<PathBuf>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub output_selection: Option<OutputSelection>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub experimental_codegen: Option<bool>,
+}
+
+impl VyperSettingsOptions {
+  /// Same serialise-merge-deserialise approach as [`CompilerSettingsOptions::overlay`], so this
+  /// doesn't need its own typed conversion for every `VyperSettings` field.
+  pub(crate) fn overlay(self, base: &VyperSettings) -> Result<VyperSettings> {
+    let mut base_value = map_napi_error(
+      serde_json::to_value(base),
+      "Failed to serialise base Vyper settings",
+    )?;
+    let overrides = map_napi_error(
+      serde_json::to_value(self),
+      "Failed to serialise Vyper settings",
+    )?;
+    merge_settings_json(&mut base_value, overrides, SettingsMergeStrategy::Replace);
+    map_napi_error(
+      serde_json::from_value(base_value),
+      "Failed to parse Vyper settings",
+    )
+  }
+}
+
+/// A solc artifact a caller can request via `CompilerConfigOptions::requested_artifacts` (see
+/// `config::CompilerConfigBuilder::with_requested_artifacts`), computing the minimal
+/// `output_selection` needed to produce exactly these - in particular never emitting the
+/// expensive file-level `ast` selection unless `Ast` is itself requested.
+#[napi(string_enum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArtifactKind {
+  Abi,
+  Bytecode,
+  DeployedBytecode,
+  Metadata,
+  Ast,
+}
+
+impl ArtifactKind {
+  fn output_selection_key(self) -> &'static str {
+    match self {
+      ArtifactKind::Abi => "abi",
+      ArtifactKind::Bytecode => "evm.bytecode",
+      ArtifactKind::DeployedBytecode => "evm.deployedBytecode",
+      ArtifactKind::Metadata => "metadata",
+      ArtifactKind::Ast => "ast",
+    }
+  }
+}
+
+/// The minimal output-selection map needed to produce exactly `artifacts` - see [`ArtifactKind`].
+fn artifact_output_selection(
+  artifacts: &[ArtifactKind],
+) -> BTreeMap<String, BTreeMap<String, Vec<String>>> {
+  let mut selection: BTreeMap<String, BTreeMap<String, Vec<String>>> = BTreeMap::new();
+
+  for artifact in artifacts {
+    if *artifact == ArtifactKind::Ast {
+      merge_ast_output_selection(&mut selection);
+      continue;
+    }
+
+    push_wildcard_output(&mut selection, artifact.output_selection_key());
+  }
+
+  selection
+}
+
+/// Keeps only the `(file, contract, output)` entries present on both sides: `explicit`'s own
+/// `"*"` file/contract wildcards stand in for any file/contract `planned` names that aren't listed
+/// verbatim, and a literal `"*"` output on the `explicit` side is treated as "every output", so it
+/// never narrows what `planned` already asked for.
+fn intersect_output_selection(
+  explicit: &OutputSelection,
+  planned: &BTreeMap<String, BTreeMap<String, Vec<String>>>,
+) -> BTreeMap<String, BTreeMap<String, Vec<String>>> {
+  let explicit = explicit.as_ref();
+  let mut result = BTreeMap::new();
+
+  for (file, planned_contracts) in planned {
+    let Some(explicit_contracts) = explicit.get(file).or_else(|| explicit.get("*")) else {
+      continue;
+    };
+    let mut contracts = BTreeMap::new();
+    for (contract, planned_outputs) in planned_contracts {
+      let Some(explicit_outputs) = explicit_contracts
+        .get(contract)
+        .or_else(|| explicit_contracts.get("*"))
+      else {
+        continue;
+      };
+      let outputs: Vec<String> = planned_outputs
+        .iter()
+        .filter(|output| {
+          explicit_outputs
+            .iter()
+            .any(|allowed| allowed == "*" || allowed == *output)
+        })
+        .cloned()
+        .collect();
+      if !outputs.is_empty() {
+        contracts.insert(contract.clone(), outputs);
+      }
+    }
+    if !contracts.is_empty() {
+      result.insert(file.clone(), contracts);
+    }
+  }
+
+  result
+}
+
+/// Plans the minimal `output_selection` for `artifacts` (see [`ArtifactKind`]) and applies it to
+/// `settings.output_selection`. When `intersect_with_explicit` is true - i.e. `settings` already
+/// holds a caller-provided `resolved_settings` override - the planned selection is intersected
+/// with whatever was already selected rather than replacing it outright, so the explicit override
+/// still wins: the planner can only narrow it, never widen it. A no-op when `artifacts` is empty.
+pub fn apply_requested_artifacts(
+  settings: &mut Settings,
+  artifacts: &[ArtifactKind],
+  intersect_with_explicit: bool,
+) {
+  if artifacts.is_empty() {
+    return;
+  }
+
+  let planned = artifact_output_selection(artifacts);
+  settings.output_selection = if intersect_with_explicit {
+    intersect_output_selection(&settings.output_selection, &planned).into()
+  } else {
+    planned.into()
+  };
+}
+
 pub fn output_selection_is_effectively_empty(selection: &OutputSelection) -> bool {
   let map = selection.as_ref();
   if map.is_empty() {
@@ -390,7 +1346,9 @@ pub enum RevertStrings {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum ModelCheckerEngine {
+  Chc,
   Bmc,
+  All,
   None,
 }
 
@@ -430,7 +1388,7 @@ pub enum ModelCheckerInvariantKind {
 }
 
 #[napi(string_enum)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum EvmVersion {
   Byzantium,
@@ -489,6 +1447,23 @@ mod tests {
     );
   }
 
+  #[test]
+  fn sanitize_preserves_abi_only_selection() {
+    let mut settings = Settings::default();
+    settings.output_selection = BTreeMap::from([(
+      "*".to_string(),
+      BTreeMap::from([("*".to_string(), vec!["abi".to_string()])]),
+    )])
+    .into();
+
+    let sanitised = sanitize_settings(&settings).expect("sanitize");
+    assert_eq!(
+      sanitised.output_selection, settings.output_selection,
+      "an explicit ABI-only selection should survive sanitization unchanged, so callers can skip \
+       bytecode generation entirely"
+    );
+  }
+
   #[test]
   fn sanitize_preserves_stop_after_and_ast_selection() {
     let mut settings = Settings::default();
@@ -515,7 +1490,7 @@ mod tests {
   #[test]
   fn merge_preserves_base_when_no_overrides() {
     let base = Settings::default();
-    let merged = merge_settings(&base, None).expect("merge");
+    let merged = merge_settings(&base, None, SettingsMergeStrategy::Replace).expect("merge");
     assert_eq!(
       serde_json::to_value(&base).unwrap(),
       serde_json::to_value(&merged).unwrap()
@@ -529,7 +1504,8 @@ mod tests {
     let selection = OutputSelection::ast_output_selection();
     overrides.output_selection = Some(selection.as_ref().clone());
 
-    let merged = merge_settings(&base, Some(&overrides)).expect("merge");
+    let merged =
+      merge_settings(&base, Some(&overrides), SettingsMergeStrategy::Replace).expect("merge");
     assert_eq!(
       merged.output_selection, selection,
       "merge should replace base output selection with override"
@@ -559,7 +1535,7 @@ mod tests {
     overrides.metadata = Some(SettingsMetadataOptions {
       use_literal_content: Some(true),
       bytecode_hash: Some(BytecodeHash::None),
-      cbor_metadata: Some(false),
+      append_cbor: Some(false),
     });
     overrides.output_selection = Some(BTreeMap::from([(
       "Example.sol".to_string(),
@@ -578,7 +1554,8 @@ mod tests {
       )]),
     )]));
 
-    let merged = merge_settings(&base, Some(&overrides)).expect("merge");
+    let merged =
+      merge_settings(&base, Some(&overrides), SettingsMergeStrategy::Replace).expect("merge");
 
     let as_json = serde_json::to_value(&merged).expect("serialize settings");
 
@@ -593,6 +1570,7 @@ mod tests {
     assert_eq!(as_json["optimizer"]["details"]["yul"], json!(true));
     assert_eq!(as_json["metadata"]["useLiteralContent"], json!(true));
     assert_eq!(as_json["metadata"]["bytecodeHash"], json!("none"));
+    assert_eq!(as_json["metadata"]["appendCBOR"], json!(false));
     assert_eq!(as_json["evmVersion"], json!("prague"));
     assert_eq!(as_json["debug"]["revertStrings"], json!("debug"));
     assert_eq!(as_json["debug"]["debugInfo"], json!(["location"]));
@@ -601,4 +1579,365 @@ mod tests {
       json!("0x0000000000000000000000000000000000000001")
     );
   }
+
+  #[test]
+  fn merge_rejects_malformed_library_address() {
+    let base = Settings::default();
+    let mut overrides = CompilerSettingsOptions::default();
+    overrides.libraries = Some(BTreeMap::from([(
+      "Example.sol".to_string(),
+      BTreeMap::from([("LibExample".to_string(), "not-an-address".to_string())]),
+    )]));
+
+    let err = merge_settings(&base, Some(&overrides), SettingsMergeStrategy::Replace)
+      .expect_err("malformed address should fail");
+    assert!(err.to_string().contains("Example.sol:LibExample"));
+  }
+
+  #[test]
+  fn validate_optimizer_steps_accepts_main_and_cleanup_sequences() {
+    assert!(validate_optimizer_steps("").is_ok());
+    assert!(validate_optimizer_steps("fuc:ghi").is_ok());
+    assert!(validate_optimizer_steps("f [fc] c").is_ok());
+  }
+
+  #[test]
+  fn validate_optimizer_steps_rejects_unknown_step() {
+    let err = validate_optimizer_steps("fz").expect_err("z is not a known step");
+    assert!(err.to_string().contains("'z'"));
+    assert!(err.to_string().contains("index 1"));
+  }
+
+  #[test]
+  fn validate_optimizer_steps_rejects_unbalanced_brackets() {
+    let err = validate_optimizer_steps("f[fc").expect_err("missing closing bracket");
+    assert!(err.to_string().contains("unbalanced '['"));
+
+    let err = validate_optimizer_steps("f]fc").expect_err("missing opening bracket");
+    assert!(err.to_string().contains("unmatched ']'"));
+  }
+
+  #[test]
+  fn validate_optimizer_steps_rejects_second_colon() {
+    let err = validate_optimizer_steps("fc:fc:fc").expect_err("only one ':' is allowed");
+    assert!(err.to_string().contains("second ':'"));
+  }
+
+  #[test]
+  fn merge_rejects_invalid_yul_optimizer_steps() {
+    let base = Settings::default();
+    let mut overrides = CompilerSettingsOptions::default();
+    overrides.optimizer = Some(OptimizerSettingsOptions {
+      enabled: Some(true),
+      runs: Some(200),
+      details: Some(OptimizerDetailsOptions {
+        yul: Some(true),
+        yul_details: Some(YulDetailsOptions {
+          optimizer_steps: Some("fz".to_string()),
+          ..Default::default()
+        }),
+        ..Default::default()
+      }),
+    });
+
+    let err = merge_settings(&base, Some(&overrides), SettingsMergeStrategy::Replace)
+      .expect_err("unknown optimizer step should fail");
+    assert!(err.to_string().contains("unknown step 'z'"));
+  }
+
+  #[test]
+  fn merge_rejects_chc_model_checker_below_shanghai() {
+    let base = Settings::default();
+    let mut overrides = CompilerSettingsOptions::default();
+    overrides.evm_version = Some(EvmVersion::Paris);
+    overrides.model_checker = Some(ModelCheckerSettingsOptions {
+      engine: Some(ModelCheckerEngine::Chc),
+      ..Default::default()
+    });
+
+    let err = merge_settings(&base, Some(&overrides), SettingsMergeStrategy::Replace)
+      .expect_err("chc model checker should be rejected below shanghai");
+    assert!(err.to_string().contains("modelChecker.engine"));
+  }
+
+  #[test]
+  fn merge_allows_chc_model_checker_from_shanghai() {
+    let base = Settings::default();
+    let mut overrides = CompilerSettingsOptions::default();
+    overrides.evm_version = Some(EvmVersion::Shanghai);
+    overrides.model_checker = Some(ModelCheckerSettingsOptions {
+      engine: Some(ModelCheckerEngine::Chc),
+      ..Default::default()
+    });
+
+    merge_settings(&base, Some(&overrides), SettingsMergeStrategy::Replace)
+      .expect("chc model checker should be allowed from shanghai onward");
+  }
+
+  #[test]
+  fn merge_rejects_omitted_bytecode_hash_on_byzantium() {
+    let base = Settings::default();
+    let mut overrides = CompilerSettingsOptions::default();
+    overrides.evm_version = Some(EvmVersion::Byzantium);
+    overrides.metadata = Some(SettingsMetadataOptions {
+      bytecode_hash: Some(BytecodeHash::None),
+      ..Default::default()
+    });
+
+    let err = merge_settings(&base, Some(&overrides), SettingsMergeStrategy::Replace)
+      .expect_err("omitting the bytecode hash should be rejected on byzantium");
+    assert!(err.to_string().contains("metadata.bytecodeHash"));
+  }
+
+  #[test]
+  fn merge_rejects_via_ir_with_stop_after_parsing() {
+    let base = Settings::default();
+    let mut overrides = CompilerSettingsOptions::default();
+    overrides.stop_after = Some("parsing".to_string());
+    overrides.via_ir = Some(true);
+
+    let err = merge_settings(&base, Some(&overrides), SettingsMergeStrategy::Replace)
+      .expect_err("viaIR should be rejected alongside stop_after = parsing");
+    assert!(err.to_string().contains("codegen-only"));
+  }
+
+  #[test]
+  fn merge_rejects_optimizer_with_stop_after_parsing() {
+    let base = Settings::default();
+    let mut overrides = CompilerSettingsOptions::default();
+    overrides.stop_after = Some("parsing".to_string());
+    overrides.optimizer = Some(OptimizerSettingsOptions {
+      enabled: Some(true),
+      ..Default::default()
+    });
+
+    let err = merge_settings(&base, Some(&overrides), SettingsMergeStrategy::Replace)
+      .expect_err("optimizer settings should be rejected alongside stop_after = parsing");
+    assert!(err.to_string().contains("codegen-only"));
+  }
+
+  #[test]
+  fn merge_appends_remappings_without_duplicating() {
+    let base = Settings::default();
+    let mut seed = CompilerSettingsOptions::default();
+    seed.remappings = Some(vec!["lib/=lib/".to_string()]);
+    let base =
+      merge_settings(&base, Some(&seed), SettingsMergeStrategy::Replace).expect("seed merge");
+
+    let mut overrides = CompilerSettingsOptions::default();
+    overrides.remappings = Some(vec![
+      "lib/=lib/".to_string(),
+      "forge-std/=lib/forge-std/src/".to_string(),
+    ]);
+
+    let merged =
+      merge_settings(&base, Some(&overrides), SettingsMergeStrategy::Append).expect("merge");
+    let remappings: Vec<String> = merged.remappings.iter().map(|r| r.to_string()).collect();
+    assert_eq!(
+      remappings,
+      vec![
+        "lib/=lib/".to_string(),
+        "forge-std/=lib/forge-std/src/".to_string()
+      ],
+      "append strategy should union remappings, deduplicating while preserving order"
+    );
+  }
+
+  #[test]
+  fn merge_appends_output_selection_leaves() {
+    let base = Settings::default();
+    let mut seed = CompilerSettingsOptions::default();
+    seed.output_selection = Some(BTreeMap::from([(
+      "Example.sol".to_string(),
+      BTreeMap::from([("*".to_string(), vec!["abi".to_string()])]),
+    )]));
+    let base =
+      merge_settings(&base, Some(&seed), SettingsMergeStrategy::Replace).expect("seed merge");
+
+    let mut overrides = CompilerSettingsOptions::default();
+    overrides.output_selection = Some(BTreeMap::from([(
+      "Example.sol".to_string(),
+      BTreeMap::from([("*".to_string(), vec!["evm.bytecode".to_string()])]),
+    )]));
+
+    let merged =
+      merge_settings(&base, Some(&overrides), SettingsMergeStrategy::Append).expect("merge");
+    let as_json = serde_json::to_value(&merged).expect("serialize settings");
+    let leaf = as_json["outputSelection"]["Example.sol"]["*"]
+      .as_array()
+      .expect("leaf selection should be an array");
+    assert!(leaf.contains(&json!("abi")), "append should keep base leaf");
+    assert!(
+      leaf.contains(&json!("evm.bytecode")),
+      "append should add override leaf"
+    );
+  }
+
+  #[test]
+  fn merge_appends_libraries_across_files() {
+    let base = Settings::default();
+    let mut seed = CompilerSettingsOptions::default();
+    seed.libraries = Some(BTreeMap::from([(
+      "Example.sol".to_string(),
+      BTreeMap::from([(
+        "LibExample".to_string(),
+        "0x0000000000000000000000000000000000000001".to_string(),
+      )]),
+    )]));
+    let base =
+      merge_settings(&base, Some(&seed), SettingsMergeStrategy::Replace).expect("seed merge");
+
+    let mut overrides = CompilerSettingsOptions::default();
+    overrides.libraries = Some(BTreeMap::from([(
+      "Other.sol".to_string(),
+      BTreeMap::from([(
+        "LibOther".to_string(),
+        "0x0000000000000000000000000000000000000002".to_string(),
+      )]),
+    )]));
+
+    let merged =
+      merge_settings(&base, Some(&overrides), SettingsMergeStrategy::Append).expect("merge");
+    let as_json = serde_json::to_value(&merged).expect("serialize settings");
+    assert_eq!(
+      as_json["libraries"]["Example.sol"]["LibExample"],
+      json!("0x0000000000000000000000000000000000000001"),
+      "append should keep libraries from the base"
+    );
+    assert_eq!(
+      as_json["libraries"]["Other.sol"]["LibOther"],
+      json!("0x0000000000000000000000000000000000000002"),
+      "append should add libraries from the override"
+    );
+  }
+
+  #[test]
+  fn merge_reporting_finds_nothing_ignored_for_recognized_overrides() {
+    let base = Settings::default();
+    let mut overrides = CompilerSettingsOptions::default();
+    overrides.via_ir = Some(true);
+    overrides.optimizer = Some(OptimizerSettingsOptions {
+      enabled: Some(true),
+      runs: Some(200),
+      details: Some(OptimizerDetailsOptions {
+        yul: Some(true),
+        ..Default::default()
+      }),
+    });
+    overrides.output_selection = Some(BTreeMap::from([(
+      "Example.sol".to_string(),
+      BTreeMap::from([("*".to_string(), vec!["abi".to_string()])]),
+    )]));
+
+    let merged = merge_settings_reporting(&base, Some(&overrides), SettingsMergeStrategy::Replace)
+      .expect("merge");
+    assert!(
+      merged.ignored.is_empty(),
+      "recognized keys should never be reported as ignored, got {:?}",
+      merged.ignored
+    );
+    assert_eq!(merged.settings.via_ir, Some(true));
+  }
+
+  #[test]
+  fn merge_reporting_passes_through_with_no_overrides() {
+    let base = Settings::default();
+    let merged =
+      merge_settings_reporting(&base, None, SettingsMergeStrategy::Replace).expect("merge");
+    assert!(merged.ignored.is_empty());
+    assert_eq!(
+      serde_json::to_value(&base).unwrap(),
+      serde_json::to_value(&merged.settings).unwrap()
+    );
+  }
+
+  #[test]
+  fn load_profile_applies_requested_profile_over_default() {
+    let toml_str = r#"
+      [profile.default]
+      optimizer = true
+      optimizer_runs = 200
+      via_ir = false
+      remappings = ["lib/=lib/"]
+
+      [profile.ci]
+      optimizer_runs = 1000000
+      via_ir = true
+    "#;
+
+    let options = load_profile(toml_str, "ci").expect("load ci profile");
+    assert_eq!(options.optimizer.as_ref().unwrap().enabled, Some(true));
+    assert_eq!(options.optimizer.as_ref().unwrap().runs, Some(1_000_000));
+    assert_eq!(options.via_ir, Some(true));
+    assert_eq!(
+      options.remappings,
+      Some(vec!["lib/=lib/".to_string()]),
+      "ci should inherit fields it doesn't override"
+    );
+  }
+
+  #[test]
+  fn load_profile_maps_libraries_and_model_checker() {
+    let toml_str = r#"
+      [profile.default]
+      libraries = ["src/Example.sol:LibExample:0x0000000000000000000000000000000000000001"]
+
+      [profile.default.model_checker]
+      engine = "chc"
+      show_unproved = true
+    "#;
+
+    let options = load_profile(toml_str, "default").expect("load default profile");
+    let libraries = options.libraries.expect("libraries");
+    assert_eq!(
+      libraries["src/Example.sol"]["LibExample"],
+      "0x0000000000000000000000000000000000000001"
+    );
+    let model_checker = options.model_checker.expect("model checker");
+    assert!(matches!(model_checker.engine, Some(ModelCheckerEngine::Chc)));
+    assert_eq!(model_checker.show_unproved, Some(true));
+  }
+
+  #[test]
+  fn load_profile_rejects_an_unknown_profile() {
+    let toml_str = "[profile.default]\noptimizer = true\n";
+    let err = load_profile(toml_str, "missing").expect_err("unknown profile should fail");
+    assert!(err.to_string().contains("missing"));
+  }
+
+  #[test]
+  fn from_jsonc_strips_comments_and_trailing_commas() {
+    let jsonc = r#"
+      {
+        // prefer via-ir for smaller bytecode
+        "viaIR": true,
+        "optimizer": {
+          "enabled": true,
+          "runs": 200, /* keep this in sync with foundry.toml */
+        },
+        "remappings": [
+          "lib/=lib/", // trailing comma below is also tolerated
+        ],
+      }
+    "#;
+
+    let options = from_jsonc(jsonc).expect("parse jsonc settings");
+    assert_eq!(options.via_ir, Some(true));
+    assert_eq!(options.optimizer.as_ref().unwrap().enabled, Some(true));
+    assert_eq!(options.optimizer.as_ref().unwrap().runs, Some(200));
+    assert_eq!(options.remappings, Some(vec!["lib/=lib/".to_string()]));
+  }
+
+  #[test]
+  fn from_jsonc_preserves_slashes_inside_string_values() {
+    let jsonc = r#"{ "remappings": ["lib/=lib/"] }"#;
+    let options = from_jsonc(jsonc).expect("parse jsonc settings");
+    assert_eq!(options.remappings, Some(vec!["lib/=lib/".to_string()]));
+  }
+
+  #[test]
+  fn from_jsonc_rejects_malformed_input() {
+    let err = from_jsonc("{ \"viaIR\": }").expect_err("malformed jsonc should fail");
+    assert!(err.to_string().contains("JSONC"));
+  }
 }
@@ -1,7 +1,10 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::sync::{Mutex, OnceLock};
 
-use semver::Version;
+use semver::{Version, VersionReq};
+use serde_json::{json, Value};
 
+use foundry_compilers::artifacts::{CompilerOutput, Settings};
 use foundry_compilers::solc::{Solc, SolcLanguage};
 use napi::{bindgen_prelude::AsyncTask, Env, Task};
 
@@ -22,13 +25,27 @@ pub(crate) fn default_version() -> Result<Version> {
   parse_version(DEFAULT_SOLC_VERSION)
 }
 
+/// Looks up `version` among already-installed solc binaries, never reaching out to the network -
+/// this is what keeps `init`, `compile_standard_sources` and `compile_ast_sources` offline-safe
+/// regardless of `config.offline_mode`. Unlike `ensure_available`, a miss here is always an error:
+/// callers that want missing versions installed on demand go through `ensure_available` instead.
 pub(crate) fn ensure_installed(version: &Version) -> Result<Solc> {
   if let Some(solc) = find_installed_version(version)? {
     return Ok(solc);
   }
+  let installed = installed_versions().unwrap_or_default();
+  let installed_list = if installed.is_empty() {
+    "none".to_string()
+  } else {
+    installed
+      .iter()
+      .map(ToString::to_string)
+      .collect::<Vec<_>>()
+      .join(", ")
+  };
   Err(Error::new(format!(
-    "Solc {} is not installed. Call installSolcVersion first.",
-    version
+    "Solc {version} is not installed (installed versions searched via svm: {installed_list}). \
+     Call installSolcVersion first, or disable offline mode to allow an on-demand download."
   )))
 }
 
@@ -44,11 +61,282 @@ pub(crate) fn is_version_installed(version: &Version) -> Result<bool> {
   find_installed_version(version).map(|maybe| maybe.is_some())
 }
 
-pub(crate) fn install_async(version: Version) -> AsyncTask<InstallSolcTask> {
-  AsyncTask::new(InstallSolcTask { version })
+/// Every solc version currently installed via svm, used to resolve a `pragma solidity`
+/// constraint against what's already on disk before considering a fresh install.
+pub(crate) fn installed_versions() -> Result<Vec<Version>> {
+  map_err_with_context(Solc::installed_versions(), "Failed to list installed solc versions")
 }
 
-pub(crate) fn install_version(version: &Version) -> Result<()> {
+/// Every solc version svm knows how to install, used alongside `installed_versions` when
+/// resolving a `pragma solidity` constraint in online mode.
+pub(crate) fn available_versions() -> Result<Vec<Version>> {
+  map_err_with_context(Solc::all_versions(), "Failed to list available solc versions")
+}
+
+/// Installs `version` if it isn't already present, honoring `offline_mode` by refusing to reach
+/// out to the network. Unlike `ensure_installed`, this will install missing versions on demand -
+/// used by the multi-version pragma resolution in `ProjectRunner` rather than the single
+/// explicitly-configured `solc_version`.
+pub(crate) fn ensure_available(version: &Version, offline_mode: bool) -> Result<()> {
+  if is_version_installed(version)? {
+    return Ok(());
+  }
+  install_version(version, offline_mode)
+}
+
+/// Scans `source` for every `pragma solidity <expr>;` directive and intersects their constraints
+/// into a single `VersionReq`, so a file with more than one pragma (e.g. concatenated fragments)
+/// only matches a version every pragma allows. Solidity separates multiple comparators within one
+/// pragma with whitespace (e.g. `>=0.8.0 <0.9.0`) rather than the comma `VersionReq::parse`
+/// expects, so each expression is rejoined before parsing. A source with no pragma at all matches
+/// `VersionReq::STAR`; `resolve_version` is what falls back to `default_version()` in that case.
+pub(crate) fn parse_version_req_from_source(source: &str) -> Result<VersionReq> {
+  let marker = "pragma solidity";
+  let mut comparators: Vec<String> = Vec::new();
+  let mut rest = source;
+  while let Some(start) = rest.find(marker) {
+    rest = &rest[start + marker.len()..];
+    let Some(end) = rest.find(';') else {
+      break;
+    };
+    let expr = rest[..end].trim();
+    rest = &rest[end + 1..];
+    if expr.is_empty() {
+      continue;
+    }
+    let normalised = expr.split_whitespace().collect::<Vec<_>>().join(", ");
+    let req = map_err_with_context(
+      VersionReq::parse(&normalised),
+      "Failed to parse pragma solidity constraint",
+    )?;
+    comparators.extend(req.comparators.iter().map(ToString::to_string));
+  }
+
+  if comparators.is_empty() {
+    return Ok(VersionReq::STAR);
+  }
+  map_err_with_context(
+    VersionReq::parse(&comparators.join(", ")),
+    "Failed to merge pragma solidity constraints",
+  )
+}
+
+/// The highest version satisfying `req` among `installed_versions()` and, unless
+/// `installed_only`, every version svm knows how to install. `None` means nothing on the
+/// considered list matches - `resolve_version` turns that into a clear error naming the
+/// constraint for a contradictory pragma.
+pub(crate) fn find_latest_compatible(
+  req: &VersionReq,
+  installed_only: bool,
+) -> Result<Option<Version>> {
+  let mut versions = installed_versions()?;
+  if !installed_only {
+    for version in available_versions()? {
+      if !versions.contains(&version) {
+        versions.push(version);
+      }
+    }
+  }
+  versions.sort();
+  Ok(versions.into_iter().rev().find(|version| req.matches(version)))
+}
+
+/// Picks the solc version to use for `source`: the highest version satisfying every `pragma
+/// solidity` constraint in it, or `default_version()` when the source has no pragma at all. Set
+/// `offline` to restrict the search to already-installed versions rather than also considering
+/// versions svm could install.
+pub(crate) fn resolve_version(source: &str, offline: bool) -> Result<Version> {
+  if !source.contains("pragma solidity") {
+    return default_version();
+  }
+
+  let req = parse_version_req_from_source(source)?;
+  find_latest_compatible(&req, offline)?.ok_or_else(|| {
+    Error::new(format!(
+      "No installed{} solc version satisfies the pragma solidity constraint `{req}` in this source",
+      if offline { "" } else { " or installable" }
+    ))
+  })
+}
+
+/// One connected component of the import graph `resolve_version_graph` couldn't pin to a single
+/// solc version, collected so every conflicting component in a project surfaces at once instead of
+/// failing on the first.
+struct UnsatisfiableComponent {
+  files: Vec<String>,
+  requirement: VersionReq,
+}
+
+/// Picks the single highest solc version satisfying every `pragma solidity` constraint across a
+/// whole connected component of `sources`' import graph, for every component - not per file, since
+/// files that import one another are compiled together and so must share one version. `edges` maps
+/// a path to the paths it imports (as `compiler::graph::resolve_graph` already derives); direction
+/// doesn't matter for grouping, so an edge is treated as undirected. Unlike `resolve_version`, a
+/// component with no satisfying version doesn't fail fast: every unsatisfiable component is
+/// accumulated and reported together in a single error naming each one's files and combined
+/// constraint, so a caller sees every conflict in the project at once rather than fixing them one
+/// at a time. Returns the chosen version for every file in every satisfiable component.
+pub(crate) fn resolve_version_graph(
+  sources: &BTreeMap<String, String>,
+  edges: &BTreeMap<String, Vec<String>>,
+  offline: bool,
+) -> Result<BTreeMap<String, Version>> {
+  let mut resolved = BTreeMap::new();
+  let mut failures = Vec::new();
+
+  for component in connected_components(sources.keys(), edges) {
+    let mut requirement = VersionReq::STAR;
+    for path in &component {
+      if let Some(source) = sources.get(path) {
+        let req = parse_version_req_from_source(source)?;
+        requirement = intersect_version_reqs(&requirement, &req)?;
+      }
+    }
+
+    match find_latest_compatible(&requirement, offline)? {
+      Some(version) => {
+        for path in &component {
+          resolved.insert(path.clone(), version.clone());
+        }
+      }
+      None => failures.push(UnsatisfiableComponent {
+        files: component,
+        requirement,
+      }),
+    }
+  }
+
+  if !failures.is_empty() {
+    return Err(Error::new(format_unsatisfiable_components(&failures, offline)));
+  }
+  Ok(resolved)
+}
+
+/// Picks the solc version for a batch of sources compiled together in a single solc invocation -
+/// unlike `resolve_version_graph`, which first splits sources into import components and resolves
+/// one version per component, this intersects every source's `pragma solidity` constraint into
+/// one requirement since the whole batch is handed to solc at once regardless of imports. Falls
+/// back to `default_version()` when none of `sources` has a pragma at all.
+pub(crate) fn resolve_version_for_batch<'a>(
+  sources: impl IntoIterator<Item = &'a str>,
+  offline: bool,
+) -> Result<Version> {
+  let mut requirement = VersionReq::STAR;
+  let mut saw_pragma = false;
+  for source in sources {
+    if source.contains("pragma solidity") {
+      saw_pragma = true;
+    }
+    let req = parse_version_req_from_source(source)?;
+    requirement = intersect_version_reqs(&requirement, &req)?;
+  }
+
+  if !saw_pragma {
+    return default_version();
+  }
+
+  find_latest_compatible(&requirement, offline)?.ok_or_else(|| {
+    Error::new(format!(
+      "No installed{} solc version satisfies the pragma solidity constraints across these sources \
+       (combined requirement `{requirement}`)",
+      if offline { "" } else { " or installable" }
+    ))
+  })
+}
+
+/// Merges two already-parsed `pragma solidity` constraints the same way
+/// `parse_version_req_from_source` merges several pragmas within one file - by rejoining their
+/// comparators into a single `VersionReq` - so a component's combined requirement can be built up
+/// one file at a time as `resolve_version_graph` walks it.
+pub(crate) fn intersect_version_reqs(a: &VersionReq, b: &VersionReq) -> Result<VersionReq> {
+  let comparators: Vec<String> = a
+    .comparators
+    .iter()
+    .chain(b.comparators.iter())
+    .map(ToString::to_string)
+    .collect();
+  if comparators.is_empty() {
+    return Ok(VersionReq::STAR);
+  }
+  map_err_with_context(
+    VersionReq::parse(&comparators.join(", ")),
+    "Failed to intersect solc version constraints across an import component",
+  )
+}
+
+/// One bundled error message naming every component `resolve_version_graph` couldn't pin a version
+/// for, each with its sorted file list and combined constraint, so a caller can fix every conflict
+/// it reports instead of just the first one encountered.
+fn format_unsatisfiable_components(failures: &[UnsatisfiableComponent], offline: bool) -> String {
+  let mut message = format!(
+    "No installed{} solc version satisfies the pragma solidity constraints shared by the \
+     following import component(s):",
+    if offline { "" } else { " or installable" }
+  );
+  for failure in failures {
+    let mut files = failure.files.clone();
+    files.sort();
+    message.push_str(&format!(
+      "\n  - [{}] requires `{}`",
+      files.join(", "),
+      failure.requirement
+    ));
+  }
+  message
+}
+
+/// Groups `paths` into the connected components of the undirected graph implied by `edges` (`path
+/// -> the paths it imports`), via plain BFS - a file reachable from another through an import in
+/// either direction lands in the same component, since the two are compiled together and so share
+/// one version constraint regardless of which one imports which.
+fn connected_components<'a>(
+  paths: impl Iterator<Item = &'a String>,
+  edges: &BTreeMap<String, Vec<String>>,
+) -> Vec<Vec<String>> {
+  let mut undirected: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+  for (path, imports) in edges {
+    for imported in imports {
+      undirected.entry(path.as_str()).or_default().insert(imported.as_str());
+      undirected.entry(imported.as_str()).or_default().insert(path.as_str());
+    }
+  }
+
+  let all_paths: Vec<&str> = paths.map(String::as_str).collect();
+  let mut visited: BTreeSet<&str> = BTreeSet::new();
+  let mut components = Vec::new();
+
+  for start in all_paths {
+    if !visited.insert(start) {
+      continue;
+    }
+    let mut component = Vec::new();
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+      component.push(node.to_string());
+      for &neighbour in undirected.get(node).into_iter().flatten() {
+        if visited.insert(neighbour) {
+          queue.push_back(neighbour);
+        }
+      }
+    }
+    components.push(component);
+  }
+
+  components
+}
+
+pub(crate) fn install_async(version: Version, offline_mode: bool) -> AsyncTask<InstallSolcTask> {
+  AsyncTask::new(InstallSolcTask { version, offline_mode })
+}
+
+/// Installs `version`, unless `offline_mode` is set, in which case this fails fast with a clear
+/// error naming the missing version rather than reaching out to the network.
+pub(crate) fn install_version(version: &Version, offline_mode: bool) -> Result<()> {
+  if offline_mode {
+    return Err(Error::new(format!(
+      "Solc {version} is not installed and offline mode is enabled. Pre-provision the binary or disable offline mode to install it."
+    )));
+  }
   map_err_with_context(
     Solc::blocking_install(version).map(|_| ()),
     "Failed to install solc version",
@@ -57,6 +345,7 @@ pub(crate) fn install_version(version: &Version) -> Result<()> {
 
 pub struct InstallSolcTask {
   pub(crate) version: Version,
+  pub(crate) offline_mode: bool,
 }
 
 fn install_mutex() -> &'static Mutex<()> {
@@ -78,14 +367,386 @@ impl Task for InstallSolcTask {
     if to_napi_result(find_installed_version(&self.version))?.is_some() {
       return Ok(());
     }
-    to_napi_result(map_err_with_context(
-      Solc::blocking_install(&self.version),
-      "Failed to install solc version",
-    ))
-    .map(|_| ())
+    to_napi_result(install_version(&self.version, self.offline_mode))
   }
 
   fn resolve(&mut self, _env: Env, _output: Self::Output) -> napi::Result<Self::JsValue> {
     Ok(())
   }
 }
+
+pub(crate) fn install_many_async(
+  versions: Vec<Version>,
+  offline_mode: bool,
+) -> AsyncTask<InstallSolcVersionsTask> {
+  AsyncTask::new(InstallSolcVersionsTask { versions, offline_mode })
+}
+
+/// What [`InstallSolcVersionsTask`] found and did for one requested version: already installed
+/// (nothing to do), or newly installed by this call. `pub` (rather than `pub(crate)`) since
+/// `Compiler::install_solc_versions` - a public API - returns these directly.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InstallOutcome {
+  AlreadyPresent(Version),
+  Installed(Version),
+}
+
+/// Installs every version in `versions` that [`find_installed_version`] doesn't already report as
+/// present, one global `install_mutex()` guard at a time per version (same as [`InstallSolcTask`])
+/// but with independent downloads running concurrently across a bounded worker pool - "prepare the
+/// whole workspace" can pin a different compiler per contract, and installing them one after
+/// another in sequence would otherwise serialize every download behind the one mutex. Duplicate
+/// versions in `versions` are installed (or reported present) only once.
+pub struct InstallSolcVersionsTask {
+  pub(crate) versions: Vec<Version>,
+  pub(crate) offline_mode: bool,
+}
+
+/// Caps how many `blocking_install` downloads run at once - same reasoning as
+/// `ProjectRunner::compile_multi_version`'s `solc_jobs`, just without a per-call override since
+/// batch installs aren't latency-sensitive the way a single compile is.
+fn install_worker_count() -> usize {
+  std::thread::available_parallelism()
+    .map(|n| n.get())
+    .unwrap_or(1)
+}
+
+/// Installs every distinct version in `versions` that isn't already present, up to
+/// `install_worker_count()` downloads at a time, reporting what happened to each one. Shared by
+/// [`InstallSolcVersionsTask`] (the async, napi-facing path) and `Compiler::install_solc_versions`
+/// (the plain, blocking Rust API), so both install the same way.
+pub(crate) fn install_many(
+  versions: &[Version],
+  offline_mode: bool,
+) -> Result<Vec<InstallOutcome>> {
+  let mut unique = Vec::with_capacity(versions.len());
+  for version in versions {
+    if !unique.contains(version) {
+      unique.push(version.clone());
+    }
+  }
+
+  let jobs = install_worker_count().max(1);
+  let mut outcomes = Vec::with_capacity(unique.len());
+  for chunk in unique.chunks(jobs) {
+    let chunk_outcomes: Vec<Result<InstallOutcome>> = std::thread::scope(|scope| {
+      let handles: Vec<_> = chunk
+        .iter()
+        .map(|version| scope.spawn(|| install_one(version, offline_mode)))
+        .collect();
+
+      handles
+        .into_iter()
+        .map(|handle| {
+          handle
+            .join()
+            .unwrap_or_else(|_| Err(Error::new("A solc install worker thread panicked")))
+        })
+        .collect()
+    });
+
+    for outcome in chunk_outcomes {
+      outcomes.push(outcome?);
+    }
+  }
+
+  Ok(outcomes)
+}
+
+fn install_one(version: &Version, offline_mode: bool) -> Result<InstallOutcome> {
+  let _guard = install_mutex()
+    .lock()
+    .map_err(|err| Error::new(format!("Solc install mutex poisoned: {err}")))?;
+
+  if find_installed_version(version)?.is_some() {
+    return Ok(InstallOutcome::AlreadyPresent(version.clone()));
+  }
+  install_version(version, offline_mode)?;
+  Ok(InstallOutcome::Installed(version.clone()))
+}
+
+impl Task for InstallSolcVersionsTask {
+  type Output = Vec<InstallOutcome>;
+  type JsValue = JsInstallSolcVersionsResult;
+
+  fn compute(&mut self) -> napi::Result<Self::Output> {
+    to_napi_result(install_many(&self.versions, self.offline_mode))
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+    let mut installed = Vec::new();
+    let mut already_present = Vec::new();
+    for outcome in output {
+      match outcome {
+        InstallOutcome::Installed(version) => installed.push(version.to_string()),
+        InstallOutcome::AlreadyPresent(version) => already_present.push(version.to_string()),
+      }
+    }
+    Ok(JsInstallSolcVersionsResult {
+      installed,
+      already_present,
+    })
+  }
+}
+
+/// The versions `InstallSolcVersionsTask` newly installed versus those it found already present,
+/// so a caller can report install progress without re-checking every version itself.
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct JsInstallSolcVersionsResult {
+  pub installed: Vec<String>,
+  pub already_present: Vec<String>,
+}
+
+/// Default capacity of the process-wide AST parse cache used by [`cached_parse`]. Chosen to cover
+/// a batch workload's working set (many targets sharing a handful of distinct shadow fragments
+/// and settings) without growing unbounded in a long-lived Node process.
+const PARSE_CACHE_CAPACITY: usize = 256;
+
+/// A small capacity-bounded, least-recently-used cache keyed by an already-computed content
+/// hash. `cached_parse` is the only way this gets populated - solc parsing is a pure function of
+/// its inputs, so a different key is itself cache invalidation and entries never need to expire
+/// for any other reason.
+struct ParseCache {
+  capacity: usize,
+  entries: std::collections::HashMap<String, Value>,
+  order: VecDeque<String>,
+}
+
+impl ParseCache {
+  fn new(capacity: usize) -> Self {
+    Self {
+      capacity,
+      entries: std::collections::HashMap::new(),
+      order: VecDeque::new(),
+    }
+  }
+
+  fn get(&mut self, key: &str) -> Option<Value> {
+    let value = self.entries.get(key)?.clone();
+    self.touch(key);
+    Some(value)
+  }
+
+  fn touch(&mut self, key: &str) {
+    if let Some(pos) = self.order.iter().position(|existing| existing == key) {
+      self.order.remove(pos);
+    }
+    self.order.push_back(key.to_string());
+  }
+
+  fn insert(&mut self, key: String, value: Value) {
+    if self.entries.contains_key(&key) {
+      self.entries.insert(key.clone(), value);
+      self.touch(&key);
+      return;
+    }
+    if self.entries.len() >= self.capacity {
+      if let Some(oldest) = self.order.pop_front() {
+        self.entries.remove(&oldest);
+      }
+    }
+    self.order.push_back(key.clone());
+    self.entries.insert(key, value);
+  }
+}
+
+fn parse_cache() -> &'static Mutex<ParseCache> {
+  static PARSE_CACHE: OnceLock<Mutex<ParseCache>> = OnceLock::new();
+  PARSE_CACHE.get_or_init(|| Mutex::new(ParseCache::new(PARSE_CACHE_CAPACITY)))
+}
+
+/// Memoizes `compute` under `key` in a process-wide, capacity-bounded LRU, returning a clone of
+/// a prior result on a cache hit instead of invoking `compute` again. `key` must be a stable,
+/// content-addressed hash of every input `compute` depends on (e.g. source text, file name, solc
+/// version, serialized settings) - `compute` is assumed pure with respect to it.
+pub(crate) fn cached_parse<E>(
+  key: &str,
+  compute: impl FnOnce() -> std::result::Result<Value, E>,
+) -> std::result::Result<Value, E> {
+  if let Some(cached) = parse_cache().lock().ok().and_then(|mut cache| cache.get(key)) {
+    return Ok(cached);
+  }
+
+  let value = compute()?;
+  if let Ok(mut cache) = parse_cache().lock() {
+    cache.insert(key.to_string(), value.clone());
+  }
+  Ok(value)
+}
+
+/// Serializes `ast_value` (already run through `sanitize_ast_value`) as a `SolidityAST` compile
+/// input under `file_name`, alongside `settings`, and compiles it with `solc`. Shared by
+/// `Instrument::compile` and the `instrumented_ast_round_trip` test so both recompile an
+/// instrumented AST through the exact same path.
+pub(crate) fn recompile_ast(
+  solc: &Solc,
+  file_name: &str,
+  ast_value: Value,
+  settings: &Settings,
+) -> Result<CompilerOutput> {
+  let settings_value = map_err_with_context(
+    serde_json::to_value(settings),
+    "Failed to serialize compiler settings",
+  )?;
+
+  let input = json!({
+    "language": "SolidityAST",
+    "sources": {
+      file_name: { "ast": ast_value }
+    },
+    "settings": settings_value
+  });
+
+  map_err_with_context(solc.compile_as(&input), "Solc compilation failed")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_version_req_from_source_reads_a_single_pragma() {
+    let source = "pragma solidity ^0.8.0;\ncontract C {}";
+    let req = parse_version_req_from_source(source).expect("parse pragma");
+    assert!(req.matches(&Version::new(0, 8, 20)));
+    assert!(!req.matches(&Version::new(0, 9, 0)));
+  }
+
+  #[test]
+  fn parse_version_req_from_source_intersects_multiple_pragmas() {
+    let source = "pragma solidity >=0.8.0;\npragma solidity <0.8.20;\ncontract C {}";
+    let req = parse_version_req_from_source(source).expect("parse pragmas");
+    assert!(req.matches(&Version::new(0, 8, 10)));
+    assert!(!req.matches(&Version::new(0, 8, 20)));
+    assert!(!req.matches(&Version::new(0, 7, 6)));
+  }
+
+  #[test]
+  fn parse_version_req_from_source_matches_anything_without_a_pragma() {
+    let req = parse_version_req_from_source("contract C {}").expect("parse missing pragma");
+    assert!(req.matches(&Version::new(0, 4, 11)));
+    assert!(req.matches(&Version::new(0, 8, 30)));
+  }
+
+  #[test]
+  fn find_latest_compatible_returns_none_for_contradictory_pragmas() {
+    let req = parse_version_req_from_source(
+      "pragma solidity ^0.8.0;\npragma solidity ^0.7.0;\ncontract C {}",
+    )
+    .expect("parse pragmas");
+    assert_eq!(
+      find_latest_compatible(&req, true).expect("search installed versions"),
+      None,
+      "no single version can satisfy both ^0.8.0 and ^0.7.0"
+    );
+  }
+
+  #[test]
+  fn resolve_version_falls_back_to_default_without_a_pragma() {
+    let resolved = resolve_version("contract C {}", true).expect("resolve default");
+    assert_eq!(resolved, default_version().expect("default version"));
+  }
+
+  #[test]
+  fn resolve_version_rejects_a_contradictory_pragma() {
+    let source = "pragma solidity ^0.8.0;\npragma solidity ^0.7.0;\ncontract C {}";
+    let err = resolve_version(source, true).expect_err("contradictory pragma should fail");
+    assert!(err.message().contains("pragma solidity"));
+  }
+
+  #[test]
+  fn resolve_version_graph_assigns_one_shared_version_per_connected_component() {
+    let sources = BTreeMap::from([
+      ("A.sol".to_string(), "contract A {}".to_string()),
+      (
+        "B.sol".to_string(),
+        "pragma solidity ^0.8.0;\ncontract B {}".to_string(),
+      ),
+    ]);
+    let edges = BTreeMap::from([("A.sol".to_string(), vec!["B.sol".to_string()])]);
+
+    let resolved = resolve_version_graph(&sources, &edges, false).expect("resolve graph");
+
+    assert_eq!(resolved.len(), 2);
+    assert_eq!(resolved["A.sol"], resolved["B.sol"]);
+    assert!(resolved["B.sol"] >= Version::new(0, 8, 0));
+  }
+
+  #[test]
+  fn resolve_version_graph_resolves_independent_components_separately() {
+    let sources = BTreeMap::from([
+      (
+        "A.sol".to_string(),
+        "pragma solidity ^0.7.0;\ncontract A {}".to_string(),
+      ),
+      (
+        "B.sol".to_string(),
+        "pragma solidity ^0.8.0;\ncontract B {}".to_string(),
+      ),
+    ]);
+    let edges = BTreeMap::new();
+
+    let resolved = resolve_version_graph(&sources, &edges, false).expect("resolve graph");
+
+    assert!(resolved["A.sol"] < Version::new(0, 8, 0));
+    assert!(resolved["B.sol"] >= Version::new(0, 8, 0));
+  }
+
+  #[test]
+  fn resolve_version_graph_accumulates_a_diagnostic_per_unsatisfiable_component() {
+    let sources = BTreeMap::from([
+      (
+        "A.sol".to_string(),
+        "pragma solidity ^0.8.0;\npragma solidity ^0.7.0;\ncontract A {}".to_string(),
+      ),
+      (
+        "B.sol".to_string(),
+        "pragma solidity ^0.5.0;\npragma solidity ^0.4.0;\ncontract B {}".to_string(),
+      ),
+    ]);
+    let edges = BTreeMap::new();
+
+    let err = resolve_version_graph(&sources, &edges, true)
+      .expect_err("both components are individually contradictory");
+    assert!(err.message().contains("A.sol"));
+    assert!(err.message().contains("B.sol"));
+  }
+
+  #[test]
+  fn cached_parse_returns_memoized_value_without_recomputing() {
+    let mut cache = ParseCache::new(8);
+    cache.insert("key".to_string(), serde_json::json!({"calls": 1}));
+    assert_eq!(cache.get("key"), Some(serde_json::json!({"calls": 1})));
+    assert_eq!(cache.get("missing"), None);
+  }
+
+  #[test]
+  fn parse_cache_evicts_least_recently_used_entry_past_capacity() {
+    let mut cache = ParseCache::new(2);
+    cache.insert("a".to_string(), serde_json::json!(1));
+    cache.insert("b".to_string(), serde_json::json!(2));
+    cache.get("a");
+    cache.insert("c".to_string(), serde_json::json!(3));
+
+    assert_eq!(cache.get("a"), Some(serde_json::json!(1)));
+    assert_eq!(cache.get("b"), None, "least recently used entry should be evicted");
+    assert_eq!(cache.get("c"), Some(serde_json::json!(3)));
+  }
+
+  #[test]
+  fn cached_parse_memoizes_across_calls_with_the_same_key() {
+    let calls = Mutex::new(0);
+    let compute = || -> std::result::Result<Value, Error> {
+      *calls.lock().unwrap() += 1;
+      Ok(serde_json::json!({"ok": true}))
+    };
+
+    let unique_key = format!("cached_parse_memoizes_across_calls_with_the_same_key-{:p}", &calls);
+    let first = cached_parse(&unique_key, compute).expect("first call");
+    let second = cached_parse(&unique_key, compute).expect("second call");
+
+    assert_eq!(first, second);
+    assert_eq!(*calls.lock().unwrap(), 1, "second call should hit the cache");
+  }
+}
@@ -0,0 +1,82 @@
+/// Jump-instruction direction decoded from a compact source-map entry's `j` field - shared by
+/// [`crate::compile::output::JumpType`] and [`crate::compiler::sourcemap::JumpType`], which each
+/// convert a [`RawJump`] to their own local (napi-facing, in the latter case) enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RawJump {
+  In,
+  Out,
+  Regular,
+}
+
+impl RawJump {
+  fn parse(raw: &str) -> Option<Self> {
+    match raw {
+      "i" => Some(RawJump::In),
+      "o" => Some(RawJump::Out),
+      "-" => Some(RawJump::Regular),
+      _ => None,
+    }
+  }
+}
+
+/// One decoded instruction entry from solc's compact source-map string (`s:l:f:j:m`) - the shared
+/// decode core behind [`crate::compile::output::SourceMapEntry`] and
+/// [`crate::compiler::sourcemap::SourceMapEntry`], which each wrap a [`RawSourceMapEntry`] in
+/// their own richer type rather than re-implementing [`decode_compact_entries`] independently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RawSourceMapEntry {
+  pub start: u32,
+  pub length: u32,
+  pub file_index: i32,
+  pub jump: RawJump,
+  pub modifier_depth: u32,
+}
+
+impl Default for RawSourceMapEntry {
+  fn default() -> Self {
+    Self {
+      start: 0,
+      length: 0,
+      file_index: -1,
+      jump: RawJump::Regular,
+      modifier_depth: 0,
+    }
+  }
+}
+
+/// Decodes a solc compact source map - a `;`-separated list of `s:l:f:j:m` entries - into one
+/// [`RawSourceMapEntry`] per bytecode instruction. Any field left empty in an entry inherits its
+/// value from the previous entry, so state carries forward across the whole list; a trailing
+/// empty entry inherits everything from the one before it.
+pub(crate) fn decode_compact_entries(compact: &str) -> Vec<RawSourceMapEntry> {
+  if compact.is_empty() {
+    return Vec::new();
+  }
+
+  let mut last = RawSourceMapEntry::default();
+
+  compact
+    .split(';')
+    .map(|entry| {
+      let mut fields = entry.split(':');
+
+      if let Some(value) = fields.next().filter(|value| !value.is_empty()) {
+        last.start = value.parse().unwrap_or(last.start);
+      }
+      if let Some(value) = fields.next().filter(|value| !value.is_empty()) {
+        last.length = value.parse().unwrap_or(last.length);
+      }
+      if let Some(value) = fields.next().filter(|value| !value.is_empty()) {
+        last.file_index = value.parse().unwrap_or(last.file_index);
+      }
+      if let Some(value) = fields.next().filter(|value| !value.is_empty()) {
+        last.jump = RawJump::parse(value).unwrap_or(last.jump);
+      }
+      if let Some(value) = fields.next().filter(|value| !value.is_empty()) {
+        last.modifier_depth = value.parse().unwrap_or(last.modifier_depth);
+      }
+
+      last
+    })
+    .collect()
+}
@@ -0,0 +1,35 @@
+use std::path::PathBuf;
+
+use super::errors::{Error, Result};
+
+/// Confirms a Vyper compiler binary is available before `compile_with_project` hands off to
+/// `foundry_compilers`, mirroring [`super::solc::ensure_installed`]'s role for the Solidity path.
+/// Unlike solc, this crate doesn't manage Vyper installs itself (no `svm`-equivalent), so there's
+/// nothing to install on demand - `path` (from `VyperCompilerSettings::path`) is checked directly
+/// if given, otherwise `vyper` is looked up on `PATH`.
+pub(crate) fn ensure_installed(path: Option<PathBuf>) -> Result<()> {
+  match path {
+    Some(path) => {
+      if path.is_file() {
+        Ok(())
+      } else {
+        Err(Error::new(format!(
+          "Configured Vyper binary not found at {}",
+          path.display()
+        )))
+      }
+    }
+    None => which("vyper").map(|_| ()).ok_or_else(|| {
+      Error::new(
+        "Vyper binary not found on PATH. Set vyperSettings.path to an explicit binary location.",
+      )
+    }),
+  }
+}
+
+fn which(binary: &str) -> Option<PathBuf> {
+  let path_var = std::env::var_os("PATH")?;
+  std::env::split_paths(&path_var)
+    .map(|dir| dir.join(binary))
+    .find(|candidate| candidate.is_file())
+}
@@ -2,8 +2,17 @@
 extern crate napi_derive;
 
 mod ast;
+mod compile;
 mod compiler;
+mod compiler_project;
+mod contract;
+mod instrument;
 mod internal;
+mod shadow;
+mod types;
+
+#[cfg(test)]
+mod module_wiring_tests;
 
 pub use ast::{
   Ast, FragmentTarget as AstFragmentTarget, SourceTarget as AstSourceTarget, State as AstState,
@@ -13,12 +22,13 @@ pub use compiler::{
     SourceTarget as CompilerSourceTarget, SourceValue as CompilerSourceValue,
     State as CompilerState,
   },
+  graph::{GraphEdge, GraphNode, ImportCycle, SourceGraph, UnresolvedImport, VersionBucket},
   output::{
-    from_standard_json, into_core_compile_output, CompileOutput, CompilerError, ContractArtifact,
-    ContractBytecode, CoreCompileOutput, CoreCompilerError, CoreContractArtifact,
-    CoreSourceLocation, SourceLocation,
+    from_standard_json, into_core_compile_output, CompileOutput, CompilerError,
+    ContractSourceMaps, ModelCheckerDiagnostic, ModelCheckerEngineKind, SourceLocation,
   },
-  CompilationInput, Compiler,
+  verify_diagnostics, CompilationInput, Compiler, DiagnosticExpectation, DiagnosticVerification,
+  UnexpectedDiagnostic,
 };
 pub use internal::config::{
   AstConfig, AstConfigOptions, CompilerConfig, CompilerConfigOptions, JsAstConfigOptions,
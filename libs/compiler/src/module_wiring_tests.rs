@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+  use std::fs;
+  use std::path::{Path, PathBuf};
+
+  /// Directory a `mod <name>;` declaration inside `file` resolves `<name>` against: the parent
+  /// directory itself for `lib.rs`/`mod.rs` (Rust 2018's "this file is the directory's root"
+  /// rule), otherwise a same-named subdirectory of the declaring file.
+  fn module_dir_for(file: &Path) -> PathBuf {
+    let parent = file.parent().expect("every src file has a parent directory");
+    match file.file_stem().and_then(|stem| stem.to_str()) {
+      Some("lib") | Some("mod") => parent.to_path_buf(),
+      _ => parent.join(file.file_stem().unwrap()),
+    }
+  }
+
+  /// Extracts every `mod <name>;` declaration (`pub`/`pub(crate)`/`pub(super)` or bare) from a
+  /// source file - one per line, as this crate always writes them, so a plain line scan is enough
+  /// without pulling in a parser or regex crate just for this check.
+  fn declared_modules(contents: &str) -> Vec<String> {
+    contents
+      .lines()
+      .filter_map(|line| {
+        let trimmed = line.trim();
+        let trimmed = trimmed
+          .strip_prefix("pub(crate) ")
+          .or_else(|| trimmed.strip_prefix("pub(super) "))
+          .or_else(|| trimmed.strip_prefix("pub "))
+          .unwrap_or(trimmed);
+        let name = trimmed.strip_prefix("mod ")?.strip_suffix(';')?.trim();
+        let is_identifier = !name.is_empty()
+          && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        is_identifier.then(|| name.to_string())
+      })
+      .collect()
+  }
+
+  fn collect_rust_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+      return;
+    };
+    for entry in entries.flatten() {
+      let path = entry.path();
+      if path.is_dir() {
+        collect_rust_files(&path, out);
+      } else if path.extension().and_then(|ext| ext.to_str()) == Some("rs") {
+        out.push(path);
+      }
+    }
+  }
+
+  /// Regression test for the class of bug a maintainer review caught across several chunks of
+  /// this crate's history: a `mod foo;` declaration with no backing `foo.rs`/`foo/mod.rs` (so the
+  /// module - and everything that only imports through it - is silently never compiled), or a
+  /// flat `foo.rs` and `foo/mod.rs` both present for the same declaration (an `E0761` ambiguous
+  /// module collision). Either one should fail this test instead of only surfacing once someone
+  /// happens to try building the crate.
+  #[test]
+  fn every_declared_module_has_exactly_one_backing_file() {
+    let src_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let mut files = Vec::new();
+    collect_rust_files(&src_root, &mut files);
+
+    let mut problems = Vec::new();
+
+    for file in &files {
+      let Ok(contents) = fs::read_to_string(file) else {
+        continue;
+      };
+      let module_dir = module_dir_for(file);
+
+      for name in declared_modules(&contents) {
+        let flat = module_dir.join(format!("{name}.rs"));
+        let nested = module_dir.join(&name).join("mod.rs");
+
+        match (flat.exists(), nested.exists()) {
+          (false, false) => problems.push(format!(
+            "{}: `mod {name};` has no backing file (expected {} or {})",
+            file.display(),
+            flat.display(),
+            nested.display()
+          )),
+          (true, true) => problems.push(format!(
+            "{}: `mod {name};` is ambiguous between {} and {}",
+            file.display(),
+            flat.display(),
+            nested.display()
+          )),
+          _ => {}
+        }
+      }
+    }
+
+    assert!(problems.is_empty(), "\n{}", problems.join("\n"));
+  }
+}
@@ -5,6 +5,10 @@ pub(crate) mod utils;
 
 pub use error::ShadowError;
 
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use foundry_compilers::artifacts::remappings::Remapping;
 use foundry_compilers::solc::Solc;
 use napi::bindgen_prelude::*;
 use napi::{Env, JsUnknown};
@@ -12,7 +16,7 @@ use serde_json::Value;
 
 use self::utils::{from_js_value, to_js_value};
 use crate::internal::{
-  errors::map_napi_error,
+  errors::{map_napi_error, napi_error},
   options::{parse_shadow_options, ShadowOptions, SolcConfig},
   solc,
 };
@@ -23,6 +27,49 @@ use foundry_compilers::artifacts::{output_selection::OutputSelection, Settings};
 pub struct Shadow {
   source: String,
   config: SolcConfig,
+  /// Import remappings applied on every call, in addition to any passed as per-call overrides.
+  remappings: Vec<String>,
+  /// In-memory sources (file name -> Solidity source) available to solc alongside the target
+  /// and shadow sources on every call, in addition to any passed as per-call overrides.
+  virtual_sources: HashMap<String, String>,
+}
+
+/// Ethers-solc "compact contract" shape returned by [`Shadow::compile_stitched`]: ABI, creation
+/// and deployed bytecode, and method selectors. Bytecode comes back decoded as raw bytes rather
+/// than a hex string, unlike [`crate::contract::JsCompactContractState`] - callers invoking a
+/// full compile are expected to hand the bytes straight to a deployment pipeline.
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct CompiledContract {
+  #[napi(ts_type = "unknown | null | undefined")]
+  pub abi: Option<Value>,
+  pub bytecode: Option<Buffer>,
+  pub deployed_bytecode: Option<Buffer>,
+  #[napi(ts_type = "Record<string, `0x${string}`> | null | undefined")]
+  pub method_identifiers: Option<HashMap<String, String>>,
+}
+
+/// Stitched AST returned together with a [`stitcher::ShadowProvenanceEntry`] for every node the
+/// shadow fragment injected, as an alternative to `stitchIntoSource`/`stitchIntoAst`'s plain-AST
+/// return for callers that need to map an analyzed node back to user-authored shadow code.
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct StitchedAstWithProvenance {
+  #[napi(ts_type = "import('./ast-types').SourceUnit")]
+  pub ast: Value,
+  pub provenance: Vec<stitcher::ShadowProvenanceEntry>,
+}
+
+/// One entry in an ordered multi-fragment stitch (see [`Shadow::stitch_many_into_ast`]):
+/// `source` is the fragment's own Solidity text, and `target_contract_name` selects which
+/// contract in the target AST it lands in, same as `stitchIntoAst`'s `targetContractName` but
+/// per-fragment instead of shared across a whole call.
+#[napi(object)]
+#[derive(Clone, Debug, Default)]
+pub struct ShadowFragment {
+  pub source: String,
+  #[napi(ts_type = "string | undefined")]
+  pub target_contract_name: Option<String>,
 }
 
 impl Shadow {
@@ -34,10 +81,126 @@ impl Shadow {
     settings
   }
 
-  pub(crate) fn from_config(source: String, mut config: SolcConfig) -> Result<Self> {
+  /// Settings for [`Shadow::compile_stitched`]: unlike [`Shadow::sanitize_settings`], this does
+  /// not force `stopAfter: "parsing"` and restores a caller-provided `evmVersion` instead of
+  /// clearing it, so solc actually type-checks and generates code for the stitched contract.
+  ///
+  /// `evm_version`'s exact shape on the real `Settings` type isn't assumed here - it's applied
+  /// through a JSON round-trip rather than a typed field write, same as the rest of this crate's
+  /// handling of `foundry_compilers`-owned `Settings` internals.
+  fn compile_settings(
+    settings: Option<Settings>,
+    evm_version: Option<String>,
+  ) -> std::result::Result<Settings, ShadowError> {
+    let mut settings = settings.unwrap_or_default();
+    settings.stop_after = None;
+
+    let mut selection = std::collections::BTreeMap::new();
+    selection.insert(
+      "*".to_string(),
+      std::collections::BTreeMap::from([(
+        "*".to_string(),
+        vec![
+          "abi".to_string(),
+          "evm.bytecode".to_string(),
+          "evm.deployedBytecode".to_string(),
+          "evm.methodIdentifiers".to_string(),
+        ],
+      )]),
+    );
+    settings.output_selection = selection.into();
+
+    match evm_version {
+      Some(version) => {
+        let mut value = serde_json::to_value(&settings)?;
+        if let Some(object) = value.as_object_mut() {
+          object.insert("evmVersion".to_string(), Value::String(version));
+        }
+        settings = serde_json::from_value(value)?;
+      }
+      None => settings.evm_version = None,
+    }
+
+    Ok(settings)
+  }
+
+  fn splice_shadow_source(
+    target_source: &str,
+    offset: usize,
+    shadow_source: &str,
+  ) -> std::result::Result<String, ShadowError> {
+    if offset > target_source.len() || !target_source.is_char_boundary(offset) {
+      return Err(ShadowError::InvalidContractStructure(
+        "Contract closing brace falls outside target source".to_string(),
+      ));
+    }
+
+    let mut stitched =
+      String::with_capacity(target_source.len() + shadow_source.len() + "\n\n".len());
+    stitched.push_str(&target_source[..offset]);
+    stitched.push('\n');
+    stitched.push_str(shadow_source);
+    stitched.push('\n');
+    stitched.push_str(&target_source[offset..]);
+    Ok(stitched)
+  }
+
+  fn decode_hex_bytecode(hex_str: &str) -> std::result::Result<Buffer, ShadowError> {
+    let trimmed = hex_str.strip_prefix("0x").unwrap_or(hex_str);
+    let bytes = hex::decode(trimmed)
+      .map_err(|err| ShadowError::CompileFailed(format!("Invalid hex-encoded bytecode: {err}")))?;
+    Ok(bytes.into())
+  }
+
+  fn contract_output_to_artifact(
+    output: &Value,
+  ) -> std::result::Result<CompiledContract, ShadowError> {
+    let abi = output.get("abi").cloned();
+    let bytecode = output
+      .pointer("/evm/bytecode/object")
+      .and_then(Value::as_str)
+      .map(Self::decode_hex_bytecode)
+      .transpose()?;
+    let deployed_bytecode = output
+      .pointer("/evm/deployedBytecode/object")
+      .and_then(Value::as_str)
+      .map(Self::decode_hex_bytecode)
+      .transpose()?;
+    let method_identifiers = output.pointer("/evm/methodIdentifiers").and_then(|value| {
+      value.as_object().map(|entries| {
+        entries
+          .iter()
+          .filter_map(|(signature, selector)| {
+            selector
+              .as_str()
+              .map(|selector| (signature.clone(), format!("0x{selector}")))
+          })
+          .collect::<HashMap<_, _>>()
+      })
+    });
+
+    Ok(CompiledContract {
+      abi,
+      bytecode,
+      deployed_bytecode,
+      method_identifiers,
+    })
+  }
+
+  pub(crate) fn from_config(
+    source: String,
+    mut config: SolcConfig,
+    remappings: Vec<String>,
+    virtual_sources: HashMap<String, String>,
+  ) -> Result<Self> {
     config.settings = Self::sanitize_settings(Some(config.settings));
     solc::ensure_installed(&config.version)?;
-    Ok(Shadow { source, config })
+    Ok(Shadow {
+      source,
+      config,
+      remappings,
+      virtual_sources,
+    })
   }
 
   fn resolve_config(&self, overrides: Option<&ShadowOptions>) -> Result<SolcConfig> {
@@ -46,15 +209,59 @@ impl Shadow {
     Ok(config)
   }
 
+  /// Merges the instance's remappings with any passed for this call, preserving first-seen
+  /// order and dropping exact duplicates so the two sources compose instead of one clobbering
+  /// the other.
+  fn resolve_remappings(&self, overrides: Option<&ShadowOptions>) -> Vec<String> {
+    let mut remappings = self.remappings.clone();
+    if let Some(extra) = overrides.and_then(|opts| opts.remappings.as_ref()) {
+      for remapping in extra {
+        if !remappings.contains(remapping) {
+          remappings.push(remapping.clone());
+        }
+      }
+    }
+    remappings
+  }
+
+  /// Merges the instance's virtual sources with any passed for this call; a per-call entry
+  /// overrides an instance-level entry of the same file name.
+  fn resolve_virtual_sources(&self, overrides: Option<&ShadowOptions>) -> HashMap<String, String> {
+    let mut sources = self.virtual_sources.clone();
+    if let Some(extra) = overrides.and_then(|opts| opts.virtual_sources.as_ref()) {
+      sources.extend(extra.clone());
+    }
+    sources
+  }
+
+  /// Parses `remappings` and folds them into `settings.remappings`, the same field a plain
+  /// `settings.remappings` override would populate. Existing entries are kept; new ones are
+  /// appended, skipping exact duplicates.
+  fn apply_remappings(mut settings: Settings, remappings: &[String]) -> Result<Settings> {
+    for value in remappings {
+      let remapping = Remapping::from_str(value)
+        .map_err(|err| napi_error(format!("Invalid remapping \"{value}\": {err}")))?;
+      if !settings
+        .remappings
+        .iter()
+        .any(|existing| existing.to_string() == remapping.to_string())
+      {
+        settings.remappings.push(remapping);
+      }
+    }
+    Ok(settings)
+  }
+
   fn parse_target_ast(
     &self,
     solc: &Solc,
     settings: &foundry_compilers::artifacts::Settings,
     source: &str,
     file_name: &str,
+    extra_sources: &HashMap<String, String>,
   ) -> Result<Value> {
     map_napi_error(
-      parser::parse_source_ast(source, file_name, solc, settings),
+      parser::parse_source_ast(source, file_name, extra_sources, solc, settings),
       "Failed to parse target source",
     )
   }
@@ -63,10 +270,27 @@ impl Shadow {
     &self,
     solc: &Solc,
     settings: &foundry_compilers::artifacts::Settings,
+    extra_sources: &HashMap<String, String>,
   ) -> Result<Value> {
     let wrapped = parser::wrap_shadow_source(&self.source);
     map_napi_error(
-      parser::parse_source_ast(&wrapped, "Shadow.sol", solc, settings),
+      parser::parse_source_ast(&wrapped, "Shadow.sol", extra_sources, solc, settings),
+      "Failed to parse shadow fragment",
+    )
+  }
+
+  /// The file-level counterpart of [`Shadow::parse_shadow_ast`]: parses the fragment on its own,
+  /// without nesting it inside a throwaway contract, so free functions/`struct`/`enum`/`error`/
+  /// `using`/`import` declarations parse as `SourceUnit`-level nodes in their own right.
+  fn parse_shadow_ast_file_level(
+    &self,
+    solc: &Solc,
+    settings: &foundry_compilers::artifacts::Settings,
+    extra_sources: &HashMap<String, String>,
+  ) -> Result<Value> {
+    let wrapped = parser::wrap_shadow_source_file_level(&self.source);
+    map_napi_error(
+      parser::parse_source_ast(&wrapped, "Shadow.sol", extra_sources, solc, settings),
       "Failed to parse shadow fragment",
     )
   }
@@ -75,10 +299,31 @@ impl Shadow {
     &self,
     solc: &Solc,
     settings: &foundry_compilers::artifacts::Settings,
+    extra_sources: &HashMap<String, String>,
     target_ast: &mut Value,
     target_contract_name: Option<&str>,
   ) -> Result<Value> {
-    let shadow_ast = self.parse_shadow_ast(solc, settings)?;
+    let (stitched, _provenance) = self.stitch_into_ast_with_provenance_internal(
+      solc,
+      settings,
+      extra_sources,
+      target_ast,
+      target_contract_name,
+    )?;
+    Ok(stitched)
+  }
+
+  /// The provenance-tracking counterpart of [`Shadow::stitch_into_ast_internal`], returning a
+  /// [`stitcher::ShadowProvenanceEntry`] for every injected node alongside the stitched AST.
+  fn stitch_into_ast_with_provenance_internal(
+    &self,
+    solc: &Solc,
+    settings: &foundry_compilers::artifacts::Settings,
+    extra_sources: &HashMap<String, String>,
+    target_ast: &mut Value,
+    target_contract_name: Option<&str>,
+  ) -> Result<(Value, Vec<stitcher::ShadowProvenanceEntry>)> {
+    let shadow_ast = self.parse_shadow_ast(solc, settings, extra_sources)?;
     let max_target_id = utils::find_max_id(target_ast);
 
     let contract_idx = map_napi_error(
@@ -86,16 +331,96 @@ impl Shadow {
       "Failed to locate target contract",
     )?;
 
-    map_napi_error(
+    let provenance = map_napi_error(
       stitcher::stitch_shadow_nodes_into_contract(
         target_ast,
         contract_idx,
         &shadow_ast,
         max_target_id,
+        "Shadow.sol",
       ),
       "Failed to stitch shadow nodes",
     )?;
 
+    Ok((target_ast.clone(), provenance))
+  }
+
+  /// Core of [`Shadow::stitch_many_into_source`]/[`Shadow::stitch_many_into_ast`]: wraps every
+  /// fragment's source into one combined file via [`parser::wrap_shadow_fragments`], parses it
+  /// with a single solc invocation, then stitches each fragment's `ShadowN` contract into its
+  /// resolved target contract in order via
+  /// [`stitcher::stitch_many_shadow_fragments_into_contracts`].
+  fn stitch_many_into_ast_internal(
+    solc: &Solc,
+    settings: &foundry_compilers::artifacts::Settings,
+    extra_sources: &HashMap<String, String>,
+    mut target_ast: Value,
+    fragments: Vec<ShadowFragment>,
+  ) -> Result<Value> {
+    let sources: Vec<&str> = fragments.iter().map(|f| f.source.as_str()).collect();
+    let wrapped = parser::wrap_shadow_fragments(sources);
+    let shadow_ast = map_napi_error(
+      parser::parse_source_ast(&wrapped, "Shadow.sol", extra_sources, solc, settings),
+      "Failed to parse shadow fragments",
+    )?;
+    let shadow_nodes = shadow_ast
+      .get("nodes")
+      .and_then(Value::as_array)
+      .ok_or_else(|| napi_error("Shadow fragments AST missing nodes"))?;
+
+    let mut targets = Vec::with_capacity(fragments.len());
+    for (index, fragment) in fragments.iter().enumerate() {
+      let shadow_contract = shadow_nodes
+        .get(index + 1)
+        .cloned()
+        .ok_or_else(|| napi_error(format!("Missing parsed fragment at index {index}")))?;
+      let contract_idx = map_napi_error(
+        stitcher::find_target_contract_index(
+          &target_ast,
+          fragment.target_contract_name.as_deref(),
+        ),
+        "Failed to locate target contract",
+      )?;
+      targets.push(stitcher::ShadowFragmentTarget {
+        contract_idx,
+        shadow_contract,
+      });
+    }
+
+    let max_target_id = utils::find_max_id(&target_ast);
+    map_napi_error(
+      stitcher::stitch_many_shadow_fragments_into_contracts(
+        &mut target_ast,
+        targets,
+        max_target_id,
+        "Shadow.sol",
+      ),
+      "Failed to stitch shadow fragments",
+    )?;
+
+    Ok(target_ast)
+  }
+
+  /// The `SourceUnit`-level counterpart of [`Shadow::stitch_into_ast_internal`]: instead of
+  /// locating a target contract and injecting the fragment as members, this parses the fragment
+  /// on its own via [`Shadow::parse_shadow_ast_file_level`] and splices its top-level nodes
+  /// directly into `target_ast.nodes`, so a fragment mixing free functions, `struct`/`enum`/
+  /// `error`/`using` declarations, and `import`s with no enclosing contract has somewhere to land.
+  fn stitch_file_level_into_ast_internal(
+    &self,
+    solc: &Solc,
+    settings: &foundry_compilers::artifacts::Settings,
+    extra_sources: &HashMap<String, String>,
+    target_ast: &mut Value,
+  ) -> Result<Value> {
+    let shadow_ast = self.parse_shadow_ast_file_level(solc, settings, extra_sources)?;
+    let max_target_id = utils::find_max_id(target_ast);
+
+    map_napi_error(
+      stitcher::stitch_shadow_nodes_into_source_unit(target_ast, &shadow_ast, max_target_id),
+      "Failed to stitch shadow nodes at the file level",
+    )?;
+
     Ok(target_ast.clone())
   }
 }
@@ -116,10 +441,108 @@ impl Shadow {
     let parsed = parse_shadow_options(&env, options)?;
     let default_settings = Self::sanitize_settings(None);
     let config = SolcConfig::new(&default_settings, parsed.as_ref())?;
-    Shadow::from_config(source, config)
+    let remappings = parsed
+      .as_ref()
+      .and_then(|opts| opts.remappings.clone())
+      .unwrap_or_default();
+    let virtual_sources = parsed
+      .as_ref()
+      .and_then(|opts| opts.virtual_sources.clone())
+      .unwrap_or_default();
+    Shadow::from_config(source, config, remappings, virtual_sources)
+  }
+
+  /// Stitch an ordered list of fragments into Solidity source text in a single pass.
+  ///
+  /// Unlike constructing several `Shadow` instances and calling `stitchIntoAst` repeatedly, every
+  /// fragment here is wrapped into one combined source (see
+  /// [`parser::wrap_shadow_fragments`]) and parsed with a single solc invocation, then applied to
+  /// `targetAst` in order - so solc's startup cost is paid once no matter how many fragments are
+  /// given, injected ids stay unique across all of them, and a later fragment may reference a
+  /// symbol an earlier one just injected. Each fragment picks its own target contract via
+  /// `targetContractName`; when omitted, the last contract in the file is used, same as
+  /// `stitchIntoAst`.
+  ///
+  /// Returns a fully analysed AST (`SourceUnit`) as a plain JS object following Foundry's typings.
+  #[napi(
+    ts_args_type = "fragments: ShadowFragment[], targetSource: string, sourceName?: string | undefined, options?: ShadowOptions | undefined",
+    ts_return_type = "import('./ast-types').SourceUnit"
+  )]
+  pub fn stitch_many_into_source(
+    env: Env,
+    fragments: Vec<ShadowFragment>,
+    target_source: String,
+    source_name: Option<String>,
+    options: Option<JsUnknown>,
+  ) -> Result<JsUnknown> {
+    let parsed = parse_shadow_options(&env, options)?;
+    let default_settings = Self::sanitize_settings(None);
+    let config = SolcConfig::new(&default_settings, parsed.as_ref())?;
+    let solc = solc::ensure_installed(&config.version)?;
+    let file_name = source_name.as_deref().unwrap_or("Contract.sol");
+
+    let remappings = parsed
+      .as_ref()
+      .and_then(|opts| opts.remappings.clone())
+      .unwrap_or_default();
+    let extra_sources = parsed
+      .as_ref()
+      .and_then(|opts| opts.virtual_sources.clone())
+      .unwrap_or_default();
+    let settings =
+      Self::apply_remappings(Self::sanitize_settings(Some(config.settings)), &remappings)?;
+
+    let target_ast = map_napi_error(
+      parser::parse_source_ast(&target_source, file_name, &extra_sources, &solc, &settings),
+      "Failed to parse target source",
+    )?;
+
+    let stitched =
+      Self::stitch_many_into_ast_internal(&solc, &settings, &extra_sources, target_ast, fragments)?;
+    to_js_value(&env, &stitched)
+  }
+
+  /// The already-parsed-AST counterpart of [`Shadow::stitch_many_into_source`]: accepts `targetAst`
+  /// directly instead of parsing it from source, same relationship `stitchIntoAst` has to
+  /// `stitchIntoSource`.
+  #[napi(
+    ts_args_type = "fragments: ShadowFragment[], targetAst: import('./ast-types').SourceUnit, options?: ShadowOptions | undefined",
+    ts_return_type = "import('./ast-types').SourceUnit"
+  )]
+  pub fn stitch_many_into_ast(
+    env: Env,
+    fragments: Vec<ShadowFragment>,
+    target_ast: JsUnknown,
+    options: Option<JsUnknown>,
+  ) -> Result<JsUnknown> {
+    let parsed = parse_shadow_options(&env, options)?;
+    let default_settings = Self::sanitize_settings(None);
+    let config = SolcConfig::new(&default_settings, parsed.as_ref())?;
+    let solc = solc::ensure_installed(&config.version)?;
+
+    let remappings = parsed
+      .as_ref()
+      .and_then(|opts| opts.remappings.clone())
+      .unwrap_or_default();
+    let extra_sources = parsed
+      .as_ref()
+      .and_then(|opts| opts.virtual_sources.clone())
+      .unwrap_or_default();
+    let settings =
+      Self::apply_remappings(Self::sanitize_settings(Some(config.settings)), &remappings)?;
+
+    let target_ast_value: Value = from_js_value(&env, target_ast)?;
+    let stitched = Self::stitch_many_into_ast_internal(
+      &solc,
+      &settings,
+      &extra_sources,
+      target_ast_value,
+      fragments,
+    )?;
+    to_js_value(&env, &stitched)
   }
 
-  /// Parse + stitch the shadow fragment into Solidity source text.
+  /// Parse + stitch the shadow fragment into Solidity source text as members of a contract.
   ///
   /// - `targetSource` is the Solidity code whose AST will be expanded.
   /// - `sourceName` controls diagnostic file names (defaults to `Contract.sol`).
@@ -127,6 +550,9 @@ impl Shadow {
   ///   contract in the file is used.
   /// - `options` offer per-call overrides for the solc version/settings.
   ///
+  /// Use [`Shadow::stitch_file_level_into_source`] instead when the fragment has free functions,
+  /// `struct`/`enum`/`error`/`using` declarations, or `import`s with no enclosing contract.
+  ///
   /// Returns a fully analysed AST (`SourceUnit`) as a plain JS object following Foundry's typings.
   #[napi(
     ts_args_type = "targetSource: string, sourceName?: string | undefined, targetContractName?: string | undefined, options?: ShadowOptions | undefined",
@@ -145,19 +571,26 @@ impl Shadow {
     let solc = solc::ensure_installed(&config.version)?;
     let file_name = source_name.as_deref().unwrap_or("Contract.sol");
 
-    let settings = Self::sanitize_settings(Some(config.settings.clone()));
+    let remappings = self.resolve_remappings(parsed.as_ref());
+    let extra_sources = self.resolve_virtual_sources(parsed.as_ref());
+    let settings = Self::apply_remappings(
+      Self::sanitize_settings(Some(config.settings.clone())),
+      &remappings,
+    )?;
 
-    let mut target_ast = self.parse_target_ast(&solc, &settings, &target_source, file_name)?;
+    let mut target_ast =
+      self.parse_target_ast(&solc, &settings, &target_source, file_name, &extra_sources)?;
     let stitched = self.stitch_into_ast_internal(
       &solc,
       &settings,
+      &extra_sources,
       &mut target_ast,
       target_contract_name.as_deref(),
     )?;
     to_js_value(&env, &stitched)
   }
 
-  /// Stitch the fragment into an already parsed AST.
+  /// Stitch the fragment into an already parsed AST as members of a contract.
   ///
   /// Accepts any Foundry-style AST object (for example, one produced by
   /// `Shadow.stitchIntoSource` or captured from fixtures). Returns a fresh AST
@@ -178,18 +611,269 @@ impl Shadow {
     let config = self.resolve_config(parsed.as_ref())?;
     let solc = solc::ensure_installed(&config.version)?;
 
-    let settings = Self::sanitize_settings(Some(config.settings.clone()));
+    let remappings = self.resolve_remappings(parsed.as_ref());
+    let extra_sources = self.resolve_virtual_sources(parsed.as_ref());
+    let settings = Self::apply_remappings(
+      Self::sanitize_settings(Some(config.settings.clone())),
+      &remappings,
+    )?;
 
     let mut target_ast_value: Value = from_js_value(&env, target_ast)?;
     let stitched = self.stitch_into_ast_internal(
       &solc,
       &settings,
+      &extra_sources,
       &mut target_ast_value,
       target_contract_name.as_deref(),
     )?;
 
     to_js_value(&env, &stitched)
   }
+
+  /// Like [`Shadow::stitch_into_ast`], but also returns a [`stitcher::ShadowProvenanceEntry`] for
+  /// every node the shadow fragment injected, same as [`Shadow::stitch_into_source_with_map`].
+  #[napi(
+    ts_args_type = "targetAst: import('./ast-types').SourceUnit, targetContractName?: string | undefined, sourceName?: string | undefined, options?: ShadowOptions | undefined",
+    ts_return_type = "StitchedAstWithProvenance"
+  )]
+  pub fn stitch_into_ast_with_map(
+    &self,
+    env: Env,
+    target_ast: JsUnknown,
+    target_contract_name: Option<String>,
+    _source_name: Option<String>,
+    options: Option<JsUnknown>,
+  ) -> Result<StitchedAstWithProvenance> {
+    let parsed = parse_shadow_options(&env, options)?;
+    let config = self.resolve_config(parsed.as_ref())?;
+    let solc = solc::ensure_installed(&config.version)?;
+
+    let remappings = self.resolve_remappings(parsed.as_ref());
+    let extra_sources = self.resolve_virtual_sources(parsed.as_ref());
+    let settings = Self::apply_remappings(
+      Self::sanitize_settings(Some(config.settings.clone())),
+      &remappings,
+    )?;
+
+    let mut target_ast_value: Value = from_js_value(&env, target_ast)?;
+    let (ast, provenance) = self.stitch_into_ast_with_provenance_internal(
+      &solc,
+      &settings,
+      &extra_sources,
+      &mut target_ast_value,
+      target_contract_name.as_deref(),
+    )?;
+
+    Ok(StitchedAstWithProvenance { ast, provenance })
+  }
+
+  /// Like [`Shadow::stitch_into_source`], but also returns a [`stitcher::ShadowProvenanceEntry`]
+  /// for every node the shadow fragment injected - its id in the stitched AST plus its original
+  /// byte offset/length within the shadow fragment source - so callers can map an analyzed node
+  /// back to user-authored shadow code for diagnostics or editor navigation.
+  #[napi(
+    ts_args_type = "targetSource: string, sourceName?: string | undefined, targetContractName?: string | undefined, options?: ShadowOptions | undefined",
+    ts_return_type = "StitchedAstWithProvenance"
+  )]
+  pub fn stitch_into_source_with_map(
+    &self,
+    env: Env,
+    target_source: String,
+    source_name: Option<String>,
+    target_contract_name: Option<String>,
+    options: Option<JsUnknown>,
+  ) -> Result<StitchedAstWithProvenance> {
+    let parsed = parse_shadow_options(&env, options)?;
+    let config = self.resolve_config(parsed.as_ref())?;
+    let solc = solc::ensure_installed(&config.version)?;
+    let file_name = source_name.as_deref().unwrap_or("Contract.sol");
+
+    let remappings = self.resolve_remappings(parsed.as_ref());
+    let extra_sources = self.resolve_virtual_sources(parsed.as_ref());
+    let settings = Self::apply_remappings(
+      Self::sanitize_settings(Some(config.settings.clone())),
+      &remappings,
+    )?;
+
+    let mut target_ast =
+      self.parse_target_ast(&solc, &settings, &target_source, file_name, &extra_sources)?;
+    let (ast, provenance) = self.stitch_into_ast_with_provenance_internal(
+      &solc,
+      &settings,
+      &extra_sources,
+      &mut target_ast,
+      target_contract_name.as_deref(),
+    )?;
+
+    Ok(StitchedAstWithProvenance { ast, provenance })
+  }
+
+  /// The file-level counterpart of [`Shadow::stitch_into_source`]: instead of locating a target
+  /// contract and injecting the fragment as members, this splices the fragment's own top-level
+  /// nodes directly into the target `SourceUnit`'s `nodes` array, so a fragment of free functions,
+  /// `struct`/`enum`/`error`/`using` declarations, and `import`s - with no enclosing contract - has
+  /// somewhere to land. A fragment containing a construct that only makes sense as a contract
+  /// member (a `modifier`) is rejected rather than silently dropped.
+  ///
+  /// - `targetSource` is the Solidity code whose AST will be expanded.
+  /// - `sourceName` controls diagnostic file names (defaults to `Contract.sol`).
+  /// - `options` offer per-call overrides for the solc version/settings.
+  ///
+  /// Returns a fully analysed AST (`SourceUnit`) as a plain JS object following Foundry's typings.
+  #[napi(
+    ts_args_type = "targetSource: string, sourceName?: string | undefined, options?: ShadowOptions | undefined",
+    ts_return_type = "import('./ast-types').SourceUnit"
+  )]
+  pub fn stitch_file_level_into_source(
+    &self,
+    env: Env,
+    target_source: String,
+    source_name: Option<String>,
+    options: Option<JsUnknown>,
+  ) -> Result<JsUnknown> {
+    let parsed = parse_shadow_options(&env, options)?;
+    let config = self.resolve_config(parsed.as_ref())?;
+    let solc = solc::ensure_installed(&config.version)?;
+    let file_name = source_name.as_deref().unwrap_or("Contract.sol");
+
+    let remappings = self.resolve_remappings(parsed.as_ref());
+    let extra_sources = self.resolve_virtual_sources(parsed.as_ref());
+    let settings = Self::apply_remappings(
+      Self::sanitize_settings(Some(config.settings.clone())),
+      &remappings,
+    )?;
+
+    let mut target_ast =
+      self.parse_target_ast(&solc, &settings, &target_source, file_name, &extra_sources)?;
+    let stitched =
+      self.stitch_file_level_into_ast_internal(&solc, &settings, &extra_sources, &mut target_ast)?;
+    to_js_value(&env, &stitched)
+  }
+
+  /// The file-level counterpart of [`Shadow::stitch_into_ast`]; see
+  /// [`Shadow::stitch_file_level_into_source`] for what "file-level" means here.
+  #[napi(
+    ts_args_type = "targetAst: import('./ast-types').SourceUnit, sourceName?: string | undefined, options?: ShadowOptions | undefined",
+    ts_return_type = "import('./ast-types').SourceUnit"
+  )]
+  pub fn stitch_file_level_into_ast(
+    &self,
+    env: Env,
+    target_ast: JsUnknown,
+    _source_name: Option<String>,
+    options: Option<JsUnknown>,
+  ) -> Result<JsUnknown> {
+    let parsed = parse_shadow_options(&env, options)?;
+    let config = self.resolve_config(parsed.as_ref())?;
+    let solc = solc::ensure_installed(&config.version)?;
+
+    let remappings = self.resolve_remappings(parsed.as_ref());
+    let extra_sources = self.resolve_virtual_sources(parsed.as_ref());
+    let settings = Self::apply_remappings(
+      Self::sanitize_settings(Some(config.settings.clone())),
+      &remappings,
+    )?;
+
+    let mut target_ast_value: Value = from_js_value(&env, target_ast)?;
+    let stitched = self.stitch_file_level_into_ast_internal(
+      &solc,
+      &settings,
+      &extra_sources,
+      &mut target_ast_value,
+    )?;
+
+    to_js_value(&env, &stitched)
+  }
+
+  /// Stitch the shadow fragment into target source and run a full solc compilation of it.
+  ///
+  /// Unlike `stitchIntoSource`/`stitchIntoAst`, this does not stop after parsing: `evmVersion`
+  /// is restored to `evmVersion` (or left to solc's default when omitted) and `outputSelection`
+  /// is narrowed to `abi`/`evm.bytecode`/`evm.deployedBytecode`/`evm.methodIdentifiers`, so the
+  /// injected members are actually type-checked and code-generated.
+  ///
+  /// Returns a compact artifact - ABI, creation/deployed bytecode as raw bytes, and method
+  /// identifiers - following Foundry's compact-contract typings. Solc errors introduced by the
+  /// shadow fragment (as opposed to a failure to invoke solc at all) surface as a rejected
+  /// promise carrying `ShadowError::CompileFailed`'s message.
+  #[napi(
+    ts_args_type = "targetSource: string, sourceName?: string | undefined, targetContractName?: string | undefined, evmVersion?: string | undefined, options?: ShadowOptions | undefined",
+    ts_return_type = "CompiledContract"
+  )]
+  pub fn compile_stitched(
+    &self,
+    env: Env,
+    target_source: String,
+    source_name: Option<String>,
+    target_contract_name: Option<String>,
+    evm_version: Option<String>,
+    options: Option<JsUnknown>,
+  ) -> Result<CompiledContract> {
+    let parsed = parse_shadow_options(&env, options)?;
+    let config = self.config.merge(parsed.as_ref())?;
+    let solc = solc::ensure_installed(&config.version)?;
+    let file_name = source_name.as_deref().unwrap_or("Contract.sol");
+
+    let remappings = self.resolve_remappings(parsed.as_ref());
+    let extra_sources = self.resolve_virtual_sources(parsed.as_ref());
+
+    let parse_settings = Self::apply_remappings(
+      Self::sanitize_settings(Some(config.settings.clone())),
+      &remappings,
+    )?;
+    let target_ast = self.parse_target_ast(
+      &solc,
+      &parse_settings,
+      &target_source,
+      file_name,
+      &extra_sources,
+    )?;
+
+    let contract_idx = map_napi_error(
+      stitcher::find_target_contract_index(&target_ast, target_contract_name.as_deref()),
+      "Failed to locate target contract",
+    )?;
+    let contract_node = target_ast
+      .get("nodes")
+      .and_then(Value::as_array)
+      .and_then(|nodes| nodes.get(contract_idx))
+      .ok_or_else(|| napi_error("Invalid contract index"))?;
+    let contract_name = utils::get_contract_name(contract_node)
+      .map(str::to_string)
+      .ok_or_else(|| napi_error("Target contract has no name"))?;
+
+    let offset = map_napi_error(
+      stitcher::contract_closing_brace_offset(contract_node),
+      "Failed to locate contract body",
+    )?;
+    let stitched_source = map_napi_error(
+      Self::splice_shadow_source(&target_source, offset, &self.source),
+      "Failed to splice shadow fragment into target source",
+    )?;
+
+    let compile_settings = map_napi_error(
+      Self::compile_settings(Some(config.settings), evm_version),
+      "Failed to prepare compile settings",
+    )?;
+    let compile_settings = Self::apply_remappings(compile_settings, &remappings)?;
+
+    let output = map_napi_error(
+      parser::compile_source(
+        &stitched_source,
+        file_name,
+        &contract_name,
+        &extra_sources,
+        &solc,
+        &compile_settings,
+      ),
+      "Failed to compile stitched contract",
+    )?;
+
+    map_napi_error(
+      Self::contract_output_to_artifact(&output),
+      "Failed to read compiled contract output",
+    )
+  }
 }
 
 #[cfg(test)]
@@ -224,12 +908,29 @@ contract Target {
     let default_settings = Shadow::sanitize_settings(None);
     let config =
       SolcConfig::new(&default_settings, Option::<&ShadowOptions>::None).expect("config");
-    let shadow = Shadow::from_config(SHADOW_FUNC.to_string(), config).expect("shadow");
-    let mut target_ast =
-      parser::parse_source_ast(TARGET_CONTRACT, "Target.sol", &solc, &default_settings)
-        .expect("parse target");
+    let shadow = Shadow::from_config(
+      SHADOW_FUNC.to_string(),
+      config,
+      Vec::new(),
+      HashMap::new(),
+    )
+    .expect("shadow");
+    let mut target_ast = parser::parse_source_ast(
+      TARGET_CONTRACT,
+      "Target.sol",
+      &HashMap::new(),
+      &solc,
+      &default_settings,
+    )
+    .expect("parse target");
     let stitched = shadow
-      .stitch_into_ast_internal(&solc, &default_settings, &mut target_ast, Some("Target"))
+      .stitch_into_ast_internal(
+        &solc,
+        &default_settings,
+        &HashMap::new(),
+        &mut target_ast,
+        Some("Target"),
+      )
       .expect("stitch");
 
     let contract = stitched
@@ -256,4 +957,306 @@ contract Target {
       "stitched AST should contain added function"
     );
   }
+
+  #[test]
+  fn stitch_with_map_returns_provenance_for_injected_node() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+
+    let default_settings = Shadow::sanitize_settings(None);
+    let config =
+      SolcConfig::new(&default_settings, Option::<&ShadowOptions>::None).expect("config");
+    let shadow = Shadow::from_config(
+      SHADOW_FUNC.to_string(),
+      config,
+      Vec::new(),
+      HashMap::new(),
+    )
+    .expect("shadow");
+    let mut target_ast = parser::parse_source_ast(
+      TARGET_CONTRACT,
+      "Target.sol",
+      &HashMap::new(),
+      &solc,
+      &default_settings,
+    )
+    .expect("parse target");
+    let (stitched, provenance) = shadow
+      .stitch_into_ast_with_provenance_internal(
+        &solc,
+        &default_settings,
+        &HashMap::new(),
+        &mut target_ast,
+        Some("Target"),
+      )
+      .expect("stitch with map");
+
+    assert!(!provenance.is_empty(), "should record provenance entries");
+    assert!(
+      provenance
+        .iter()
+        .all(|entry| entry.source_name == "Shadow.sol"),
+      "every entry should be attributed to the shadow fragment"
+    );
+
+    let contract = stitched
+      .get("nodes")
+      .and_then(|n| n.as_array())
+      .and_then(|nodes| nodes.last())
+      .expect("contract node");
+    let added_fn = contract
+      .get("nodes")
+      .and_then(|n| n.as_array())
+      .and_then(|nodes| {
+        nodes.iter().find(|node| {
+          node.get("name").and_then(Value::as_str) == Some("added")
+        })
+      })
+      .expect("added function node");
+    let added_id = added_fn.get("id").and_then(Value::as_i64).expect("id");
+
+    let entry = provenance
+      .iter()
+      .find(|entry| entry.new_node_id == added_id)
+      .expect("provenance entry for the injected function");
+    assert_eq!(entry.original_length, SHADOW_FUNC.len() as i64);
+  }
+
+  #[test]
+  fn stitches_many_fragments_in_order_with_unique_ids() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+
+    let default_settings = Shadow::sanitize_settings(None);
+    let target_ast = parser::parse_source_ast(
+      TARGET_CONTRACT,
+      "Target.sol",
+      &HashMap::new(),
+      &solc,
+      &default_settings,
+    )
+    .expect("parse target");
+
+    let fragments = vec![
+      ShadowFragment {
+        source: "function first() public view returns (uint256) { return value; }".to_string(),
+        target_contract_name: Some("Target".to_string()),
+      },
+      ShadowFragment {
+        source: "function second() public view returns (uint256) { return first(); }".to_string(),
+        target_contract_name: Some("Target".to_string()),
+      },
+    ];
+
+    let stitched = Shadow::stitch_many_into_ast_internal(
+      &solc,
+      &default_settings,
+      &HashMap::new(),
+      target_ast,
+      fragments,
+    )
+    .expect("multi-fragment stitch");
+
+    let contract = stitched
+      .get("nodes")
+      .and_then(|n| n.as_array())
+      .and_then(|nodes| nodes.last())
+      .expect("contract node");
+    let contract_nodes = contract
+      .get("nodes")
+      .and_then(|n| n.as_array())
+      .expect("contract nodes");
+
+    let names: Vec<&str> = contract_nodes
+      .iter()
+      .filter_map(|node| node.get("name").and_then(Value::as_str))
+      .collect();
+    assert!(
+      names.contains(&"first") && names.contains(&"second"),
+      "both fragments should be stitched in"
+    );
+
+    let first_pos = names.iter().position(|name| *name == "first").unwrap();
+    let second_pos = names.iter().position(|name| *name == "second").unwrap();
+    assert!(
+      first_pos < second_pos,
+      "fragments should be stitched in the order given"
+    );
+
+    let mut ids: Vec<i64> = contract_nodes
+      .iter()
+      .filter_map(|node| node.get("id").and_then(Value::as_i64))
+      .collect();
+    let before_dedup = ids.len();
+    ids.sort_unstable();
+    ids.dedup();
+    assert_eq!(
+      ids.len(),
+      before_dedup,
+      "injected ids across fragments should be unique"
+    );
+  }
+
+  const SHADOW_FREE_FUNCTION: &str = "function helper(uint256 x) pure returns (uint256) { return x + 1; }";
+
+  #[test]
+  fn stitches_free_function_at_the_file_level() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+
+    let default_settings = Shadow::sanitize_settings(None);
+    let config =
+      SolcConfig::new(&default_settings, Option::<&ShadowOptions>::None).expect("config");
+    let shadow = Shadow::from_config(
+      SHADOW_FREE_FUNCTION.to_string(),
+      config,
+      Vec::new(),
+      HashMap::new(),
+    )
+    .expect("shadow");
+    let mut target_ast = parser::parse_source_ast(
+      TARGET_CONTRACT,
+      "Target.sol",
+      &HashMap::new(),
+      &solc,
+      &default_settings,
+    )
+    .expect("parse target");
+    let stitched = shadow
+      .stitch_file_level_into_ast_internal(&solc, &default_settings, &HashMap::new(), &mut target_ast)
+      .expect("file-level stitch");
+
+    let nodes = stitched
+      .get("nodes")
+      .and_then(|n| n.as_array())
+      .expect("source unit nodes");
+    assert!(
+      nodes.iter().any(|node| {
+        node.get("nodeType").and_then(Value::as_str) == Some("FunctionDefinition")
+          && node.get("name").and_then(Value::as_str) == Some("helper")
+      }),
+      "stitched source unit should contain the free function at the top level"
+    );
+  }
+
+  #[test]
+  fn compiles_stitched_contract_with_bytecode() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+
+    let default_settings = Shadow::sanitize_settings(None);
+    let config =
+      SolcConfig::new(&default_settings, Option::<&ShadowOptions>::None).expect("config");
+    let shadow = Shadow::from_config(
+      SHADOW_FUNC.to_string(),
+      config,
+      Vec::new(),
+      HashMap::new(),
+    )
+    .expect("shadow");
+
+    let target_ast = parser::parse_source_ast(
+      TARGET_CONTRACT,
+      "Target.sol",
+      &HashMap::new(),
+      &solc,
+      &default_settings,
+    )
+    .expect("parse target");
+    let contract_idx =
+      stitcher::find_target_contract_index(&target_ast, Some("Target")).expect("contract index");
+    let contract_node =
+      &target_ast.get("nodes").and_then(|n| n.as_array()).unwrap()[contract_idx];
+    let offset = stitcher::contract_closing_brace_offset(contract_node).expect("offset");
+
+    let stitched_source =
+      Shadow::splice_shadow_source(TARGET_CONTRACT, offset, SHADOW_FUNC).expect("splice");
+    assert!(stitched_source.contains("function added"));
+
+    let compile_settings =
+      Shadow::compile_settings(Some(shadow.config.settings.clone()), None).expect("settings");
+    let output = parser::compile_source(
+      &stitched_source,
+      "Target.sol",
+      "Target",
+      &HashMap::new(),
+      &solc,
+      &compile_settings,
+    )
+    .expect("compile");
+
+    let artifact = Shadow::contract_output_to_artifact(&output).expect("artifact");
+    assert!(
+      artifact.bytecode.is_some(),
+      "compiled contract should have creation bytecode"
+    );
+    assert!(
+      artifact
+        .method_identifiers
+        .unwrap_or_default()
+        .keys()
+        .any(|signature| signature.starts_with("added(")),
+      "method identifiers should include the injected function"
+    );
+  }
+
+  const REMAPPED_TARGET: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+import "@lib/Helper.sol";
+
+contract WithImport {
+  function double(uint256 x) public pure returns (uint256) {
+    return Helper.double(x);
+  }
+}
+"#;
+
+  const HELPER_SOURCE: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+library Helper {
+  function double(uint256 x) public pure returns (uint256) {
+    return x * 2;
+  }
+}
+"#;
+
+  #[test]
+  fn resolves_remapped_import_via_virtual_source() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+
+    let default_settings = Shadow::sanitize_settings(None);
+    let settings = Shadow::apply_remappings(default_settings, &["@lib/=virtual/".to_string()])
+      .expect("remappings should parse");
+
+    let mut extra_sources = HashMap::new();
+    extra_sources.insert("virtual/Helper.sol".to_string(), HELPER_SOURCE.to_string());
+
+    let target_ast = parser::parse_source_ast(
+      REMAPPED_TARGET,
+      "WithImport.sol",
+      &extra_sources,
+      &solc,
+      &settings,
+    )
+    .expect("parse target with remapped import");
+
+    let contract_idx = stitcher::find_target_contract_index(&target_ast, Some("WithImport"))
+      .expect("contract index");
+    let nodes = target_ast.get("nodes").and_then(|n| n.as_array()).unwrap();
+    assert_eq!(
+      utils::get_contract_name(&nodes[contract_idx]),
+      Some("WithImport"),
+      "should resolve the import through the remapping and virtual source"
+    );
+  }
 }
@@ -9,6 +9,7 @@ pub enum ShadowError {
   InvalidContractStructure(String),
   JsonError(String),
   CompilerError(String),
+  CompileFailed(String),
 }
 
 impl std::fmt::Display for ShadowError {
@@ -20,6 +21,7 @@ impl std::fmt::Display for ShadowError {
       Self::InvalidContractStructure(msg) => write!(f, "Invalid contract structure: {}", msg),
       Self::JsonError(msg) => write!(f, "JSON error: {}", msg),
       Self::CompilerError(msg) => write!(f, "Compiler error: {}", msg),
+      Self::CompileFailed(msg) => write!(f, "Compilation failed: {}", msg),
     }
   }
 }
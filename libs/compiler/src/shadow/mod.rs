@@ -1,11 +0,0 @@
-mod error;
-mod lib;
-mod parser;
-mod stitcher;
-mod utils;
-
-#[cfg(test)]
-mod tests;
-
-pub use error::ShadowError;
-pub use lib::Shadow;
@@ -1,35 +1,145 @@
 use foundry_compilers::artifacts::{Settings, SolcInput, SolcLanguage, Source, Sources};
 use foundry_compilers::solc::Solc;
 use serde_json::Value;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
 
 use super::error::ShadowError;
+use crate::internal::solc as solc_internal;
+
+/// Build a `Sources` map containing `source` under `file_name` plus every entry in
+/// `extra_sources`, so imports that reference an in-memory library/interface resolve without
+/// needing a real file on disk. `file_name` always wins if `extra_sources` also defines it.
+fn build_sources(source: &str, file_name: &str, extra_sources: &HashMap<String, String>) -> Sources {
+  let mut sources = Sources::new();
+  for (name, content) in extra_sources {
+    if name != file_name {
+      sources.insert(PathBuf::from(name), Source::new(content));
+    }
+  }
+  sources.insert(PathBuf::from(file_name), Source::new(source));
+  sources
+}
+
+/// Stable content hash of everything `parse_source_ast` reads: the source, its file name, every
+/// extra source, the solc version, and the serialized settings. Two calls with the same key are
+/// assumed to produce the same AST, since parsing is a pure function of these inputs - this is
+/// what lets `internal::solc::cached_parse` skip re-invoking solc on a repeat call.
+fn parse_cache_key(
+  source: &str,
+  file_name: &str,
+  extra_sources: &HashMap<String, String>,
+  solc: &Solc,
+  settings: &Settings,
+) -> String {
+  let mut hasher = std::collections::hash_map::DefaultHasher::new();
+  source.hash(&mut hasher);
+  file_name.hash(&mut hasher);
+  solc.version.to_string().hash(&mut hasher);
+  serde_json::to_string(settings).unwrap_or_default().hash(&mut hasher);
+
+  let mut extra: Vec<(&String, &String)> = extra_sources.iter().collect();
+  extra.sort_by(|(a, _), (b, _)| a.cmp(b));
+  for (name, content) in extra {
+    name.hash(&mut hasher);
+    content.hash(&mut hasher);
+  }
+
+  format!("{:016x}", hasher.finish())
+}
 
 /// Parse Solidity source code and return AST JSON
 /// Uses stopAfter: "parsing" to get syntax-only AST
+///
+/// Memoized in a process-wide LRU keyed on `source`/`file_name`/`extra_sources`/solc
+/// version/settings (see `parse_cache_key`): a `Shadow` instance re-parsing the same wrapped
+/// fragment across many `stitch_*` calls, or many targets sharing a solc config, hits the cache
+/// instead of re-invoking solc.
 pub fn parse_source_ast(
   source: &str,
   file_name: &str,
+  extra_sources: &HashMap<String, String>,
   solc: &Solc,
   settings: &Settings,
 ) -> Result<Value, ShadowError> {
-  let mut sources = Sources::new();
-  sources.insert(PathBuf::from(file_name), Source::new(source));
+  let key = parse_cache_key(source, file_name, extra_sources, solc, settings);
+  solc_internal::cached_parse(&key, || -> Result<Value, ShadowError> {
+    let sources = build_sources(source, file_name, extra_sources);
+
+    let mut input = SolcInput::new(SolcLanguage::Solidity, sources, settings.clone());
+    input.sanitize(&solc.version);
+
+    let parse_output: Value = solc
+      .compile_as(&input)
+      .map_err(|e| ShadowError::CompilerError(e.to_string()))?;
+
+    let ast = parse_output
+      .get("sources")
+      .and_then(|s| s.get(file_name))
+      .and_then(|s| s.get("ast"))
+      .ok_or_else(|| ShadowError::ParseFailed("Failed to extract AST".to_string()))?;
+
+    Ok(ast.clone())
+  })
+}
+
+/// Run a full (non-parse-only) solc compile of `source` and return the compiler's `contracts`
+/// entry for `contract_name` within `file_name`.
+///
+/// Unlike [`parse_source_ast`], `settings` here is expected to carry a real `outputSelection`
+/// (not `stopAfter: "parsing"`), so the injected members are actually type-checked and code
+/// generated. Errors reported by solc at this stage - as opposed to a hard invocation failure -
+/// come back as [`ShadowError::CompileFailed`] so callers can tell "solc couldn't run" apart from
+/// "solc ran and rejected the stitched contract".
+pub fn compile_source(
+  source: &str,
+  file_name: &str,
+  contract_name: &str,
+  extra_sources: &HashMap<String, String>,
+  solc: &Solc,
+  settings: &Settings,
+) -> Result<Value, ShadowError> {
+  let sources = build_sources(source, file_name, extra_sources);
 
   let mut input = SolcInput::new(SolcLanguage::Solidity, sources, settings.clone());
   input.sanitize(&solc.version);
 
-  let parse_output: Value = solc
+  let output: Value = solc
     .compile_as(&input)
     .map_err(|e| ShadowError::CompilerError(e.to_string()))?;
 
-  let ast = parse_output
-    .get("sources")
-    .and_then(|s| s.get(file_name))
-    .and_then(|s| s.get("ast"))
-    .ok_or_else(|| ShadowError::ParseFailed("Failed to extract AST".to_string()))?;
+  let errors: Vec<String> = output
+    .get("errors")
+    .and_then(Value::as_array)
+    .into_iter()
+    .flatten()
+    .filter(|entry| entry.get("severity").and_then(Value::as_str) == Some("error"))
+    .map(|entry| {
+      entry
+        .get("formattedMessage")
+        .or_else(|| entry.get("message"))
+        .and_then(Value::as_str)
+        .unwrap_or("unknown solc error")
+        .to_string()
+    })
+    .collect();
+
+  if !errors.is_empty() {
+    return Err(ShadowError::CompileFailed(errors.join("\n")));
+  }
 
-  Ok(ast.clone())
+  output
+    .get("contracts")
+    .and_then(|files| files.get(file_name))
+    .and_then(|contracts| contracts.get(contract_name))
+    .cloned()
+    .ok_or_else(|| {
+      ShadowError::InvalidContractStructure(format!(
+        "Compiler output missing contract '{}' in '{}'",
+        contract_name, file_name
+      ))
+    })
 }
 
 /// Wrap shadow source in minimal contract boilerplate
@@ -46,6 +156,36 @@ contract Shadow {{
   )
 }
 
+/// Wrap shadow source with just a license/pragma header, leaving it otherwise untouched.
+///
+/// Unlike [`wrap_shadow_source`], this does not nest the fragment inside a throwaway contract:
+/// a file-level stitch needs the fragment's free functions, `struct`/`enum`/`error`/`using`
+/// declarations and `import`s to parse as `SourceUnit`-level nodes in their own right, not as
+/// members of some contract that never existed in the fragment's own source.
+pub fn wrap_shadow_source_file_level(source: &str) -> String {
+  format!(
+    r#"// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.0;
+
+{}
+"#,
+    source
+  )
+}
+
+/// Wrap an ordered list of shadow fragments into a single source file, each nested in its own
+/// throwaway contract (`Shadow0`, `Shadow1`, ...) so one solc invocation parses every fragment at
+/// once instead of paying solc's startup cost once per fragment.
+pub fn wrap_shadow_fragments<'a>(fragments: impl IntoIterator<Item = &'a str>) -> String {
+  let mut wrapped = String::from(
+    "// SPDX-License-Identifier: UNLICENSED\npragma solidity ^0.8.0;\n\n",
+  );
+  for (index, fragment) in fragments.into_iter().enumerate() {
+    wrapped.push_str(&format!("contract Shadow{index} {{\n    {fragment}\n}}\n\n"));
+  }
+  wrapped
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -70,6 +210,23 @@ contract Example {
     assert!(wrapped.contains(SAMPLE_FRAGMENT));
   }
 
+  #[test]
+  fn wraps_fragment_without_a_contract_for_file_level_stitching() {
+    let wrapped = wrap_shadow_source_file_level(SAMPLE_FRAGMENT);
+    assert!(wrapped.contains("pragma solidity ^0.8.0;"));
+    assert!(!wrapped.contains("contract Shadow"));
+    assert!(wrapped.contains(SAMPLE_FRAGMENT));
+  }
+
+  #[test]
+  fn wraps_each_fragment_in_its_own_numbered_contract() {
+    let wrapped = wrap_shadow_fragments(["function one() public {}", "function two() public {}"]);
+    assert!(wrapped.contains("contract Shadow0"));
+    assert!(wrapped.contains("contract Shadow1"));
+    assert!(wrapped.contains("function one() public {}"));
+    assert!(wrapped.contains("function two() public {}"));
+  }
+
   fn find_default_solc() -> Option<Solc> {
     let version = solc::default_version().ok()?;
     Solc::find_svm_installed_version(&version).ok().flatten()
@@ -81,8 +238,9 @@ contract Example {
       return;
     };
     let settings = Shadow::sanitize_settings(None);
-    let ast: Value = parse_source_ast(SAMPLE_CONTRACT, "Example.sol", &solc, &settings)
-      .expect("should parse contract");
+    let ast: Value =
+      parse_source_ast(SAMPLE_CONTRACT, "Example.sol", &HashMap::new(), &solc, &settings)
+        .expect("should parse contract");
     assert!(ast.get("nodes").is_some(), "AST should contain nodes array");
     let nodes = ast
       .get("nodes")
@@ -97,8 +255,8 @@ contract Example {
       return;
     };
     let settings = Shadow::sanitize_settings(None);
-    let ast =
-      parse_source_ast(SAMPLE_CONTRACT, "Example.sol", &solc, &settings).expect("parse contract");
+    let ast = parse_source_ast(SAMPLE_CONTRACT, "Example.sol", &HashMap::new(), &solc, &settings)
+      .expect("parse contract");
     let unit: foundry_compilers::artifacts::ast::SourceUnit =
       serde_json::from_value(ast).expect("deserialize SourceUnit");
     assert!(
@@ -109,4 +267,33 @@ contract Example {
       "typed AST should contain contract definition"
     );
   }
+
+  #[test]
+  fn parse_cache_key_is_stable_for_identical_inputs() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+    let settings = Shadow::sanitize_settings(None);
+    let key_a = parse_cache_key(SAMPLE_CONTRACT, "Example.sol", &HashMap::new(), &solc, &settings);
+    let key_b = parse_cache_key(SAMPLE_CONTRACT, "Example.sol", &HashMap::new(), &solc, &settings);
+    assert_eq!(key_a, key_b, "identical inputs should hash to the same key");
+  }
+
+  #[test]
+  fn parse_cache_key_differs_when_extra_sources_change() {
+    let Some(solc) = find_default_solc() else {
+      return;
+    };
+    let settings = Shadow::sanitize_settings(None);
+    let mut extra = HashMap::new();
+    extra.insert("Lib.sol".to_string(), "library Lib {}".to_string());
+
+    let without_extra =
+      parse_cache_key(SAMPLE_CONTRACT, "Example.sol", &HashMap::new(), &solc, &settings);
+    let with_extra = parse_cache_key(SAMPLE_CONTRACT, "Example.sol", &extra, &solc, &settings);
+    assert_ne!(
+      without_extra, with_extra,
+      "a different set of extra sources must not collide in the cache"
+    );
+  }
 }
@@ -41,24 +41,99 @@ pub fn find_target_contract_index(
   }
 }
 
-/// Stitch shadow nodes into target contract
-/// Modifies target_root in place
-pub fn stitch_shadow_nodes_into_contract(
-  target_root: &mut Value,
-  contract_idx: usize,
-  shadow_ast: &Value,
-  max_target_id: i64,
-) -> Result<(), ShadowError> {
-  let shadow_nodes = shadow_ast
-    .get("nodes")
-    .and_then(|v| v.as_array())
-    .ok_or_else(|| ShadowError::InvalidContractStructure("Shadow AST missing nodes".to_string()))?;
+/// Returns the byte offset of a contract's closing brace in its original source text, read from
+/// the node's `src` field (`"start:length:fileIndex"`). Used to splice the shadow fragment's raw
+/// source directly into the target contract without re-printing the AST back to text.
+pub fn contract_closing_brace_offset(contract_node: &Value) -> Result<usize, ShadowError> {
+  let src = contract_node
+    .get("src")
+    .and_then(Value::as_str)
+    .ok_or_else(|| ShadowError::InvalidContractStructure("Contract node missing src".to_string()))?;
 
-  if shadow_nodes.len() <= 1 {
-    return Err(ShadowError::NoNodesFound);
+  let mut fields = src.split(':');
+  let start: usize = fields
+    .next()
+    .and_then(|value| value.parse().ok())
+    .ok_or_else(|| ShadowError::InvalidContractStructure(format!("Malformed src '{}'", src)))?;
+  let length: usize = fields
+    .next()
+    .and_then(|value| value.parse().ok())
+    .ok_or_else(|| ShadowError::InvalidContractStructure(format!("Malformed src '{}'", src)))?;
+
+  start
+    .checked_add(length)
+    .and_then(|end| end.checked_sub(1))
+    .ok_or_else(|| ShadowError::InvalidContractStructure(format!("Malformed src '{}'", src)))
+}
+
+/// One injected node's provenance back into the shadow fragment it came from, modeled on
+/// solc/ethers-solc's `sourcemap` `s:l:f` triple: `new_node_id` is the node's id in the *stitched*
+/// AST (after renumbering against the target's ids), and `original_offset`/`original_length` are
+/// its byte range within the wrapped shadow fragment source named `source_name` - unaffected by
+/// renumbering, since only `id` fields change, not `src`. Returned by
+/// [`stitch_shadow_nodes_into_contract`] alongside the mutated target AST, so downstream tooling
+/// can map an analyzed node back to user-authored shadow code.
+#[napi(object)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShadowProvenanceEntry {
+  pub new_node_id: i64,
+  pub original_offset: i64,
+  pub original_length: i64,
+  pub source_name: String,
+}
+
+/// Parses a solc `src` string (`"start:length:fileIndex"`) into `(start, length)`.
+fn parse_src_offset(src: &str) -> Option<(i64, i64)> {
+  let mut fields = src.split(':');
+  let start: i64 = fields.next()?.parse().ok()?;
+  let length: i64 = fields.next()?.parse().ok()?;
+  Some((start, length))
+}
+
+/// Walks `node` and its descendants, recording a [`ShadowProvenanceEntry`] for every object that
+/// carries both an `id` and a `src` field - i.e. every renumbered shadow AST node - attributing
+/// each to `source_name`.
+fn collect_provenance(node: &Value, source_name: &str, out: &mut Vec<ShadowProvenanceEntry>) {
+  match node {
+    Value::Object(map) => {
+      if let (Some(Value::Number(id)), Some(Value::String(src))) =
+        (map.get("id"), map.get("src"))
+      {
+        if let (Some(new_node_id), Some((original_offset, original_length))) =
+          (id.as_i64(), parse_src_offset(src))
+        {
+          out.push(ShadowProvenanceEntry {
+            new_node_id,
+            original_offset,
+            original_length,
+            source_name: source_name.to_string(),
+          });
+        }
+      }
+      for child in map.values() {
+        collect_provenance(child, source_name, out);
+      }
+    }
+    Value::Array(items) => {
+      for item in items {
+        collect_provenance(item, source_name, out);
+      }
+    }
+    _ => {}
   }
+}
 
-  let mut shadow_contract = shadow_nodes[1].clone();
+/// Shared core of [`stitch_shadow_nodes_into_contract`] and
+/// [`stitch_many_shadow_fragments_into_contracts`]: renumbers `shadow_contract`'s own nodes
+/// against `max_target_id`, records their provenance, and appends them into `target_root`'s
+/// contract at `contract_idx`.
+fn stitch_shadow_contract_into_contract(
+  target_root: &mut Value,
+  contract_idx: usize,
+  mut shadow_contract: Value,
+  max_target_id: i64,
+  shadow_source_name: &str,
+) -> Result<Vec<ShadowProvenanceEntry>, ShadowError> {
   utils::renumber_ids(&mut shadow_contract, max_target_id);
 
   let shadow_contract_nodes = shadow_contract
@@ -69,6 +144,11 @@ pub fn stitch_shadow_nodes_into_contract(
     })?
     .clone();
 
+  let mut provenance = Vec::with_capacity(shadow_contract_nodes.len());
+  for node in &shadow_contract_nodes {
+    collect_provenance(node, shadow_source_name, &mut provenance);
+  }
+
   let target_nodes = target_root
     .get_mut("nodes")
     .and_then(|v| v.as_array_mut())
@@ -89,6 +169,139 @@ pub fn stitch_shadow_nodes_into_contract(
     target_contract_nodes.push(node);
   }
 
+  Ok(provenance)
+}
+
+/// Stitch shadow nodes into target contract
+/// Modifies target_root in place
+///
+/// Returns a [`ShadowProvenanceEntry`] for every injected node, attributed to `shadow_source_name`
+/// (the file name `shadow_ast` was parsed under), so callers can surface where each stitched node
+/// came from in the original shadow fragment.
+pub fn stitch_shadow_nodes_into_contract(
+  target_root: &mut Value,
+  contract_idx: usize,
+  shadow_ast: &Value,
+  max_target_id: i64,
+  shadow_source_name: &str,
+) -> Result<Vec<ShadowProvenanceEntry>, ShadowError> {
+  let shadow_nodes = shadow_ast
+    .get("nodes")
+    .and_then(|v| v.as_array())
+    .ok_or_else(|| ShadowError::InvalidContractStructure("Shadow AST missing nodes".to_string()))?;
+
+  if shadow_nodes.len() <= 1 {
+    return Err(ShadowError::NoNodesFound);
+  }
+
+  stitch_shadow_contract_into_contract(
+    target_root,
+    contract_idx,
+    shadow_nodes[1].clone(),
+    max_target_id,
+    shadow_source_name,
+  )
+}
+
+/// One fragment in an ordered multi-fragment stitch: `contract_idx` is the index (in the shared
+/// `target_root`) of the contract this fragment's members should land in, and `shadow_contract` is
+/// the fragment's already-parsed `ContractDefinition` node (e.g. the Nth `ShadowN` contract out of
+/// [`super::parser::wrap_shadow_fragments`]'s combined source) - not yet renumbered.
+pub struct ShadowFragmentTarget {
+  pub contract_idx: usize,
+  pub shadow_contract: Value,
+}
+
+/// Stitch an ordered list of fragments into their respective target contracts in a single pass,
+/// carrying the running `find_max_id` forward from one fragment to the next so injected ids stay
+/// unique across all of them, whether they land in the same contract or different ones. Fragments
+/// are applied strictly in the order given, so a later fragment may reference a symbol an earlier
+/// one just injected. All fragments are attributed to the same `shadow_source_name`, since they
+/// were wrapped and parsed together as one combined shadow source.
+pub fn stitch_many_shadow_fragments_into_contracts(
+  target_root: &mut Value,
+  fragments: Vec<ShadowFragmentTarget>,
+  mut max_target_id: i64,
+  shadow_source_name: &str,
+) -> Result<Vec<ShadowProvenanceEntry>, ShadowError> {
+  let mut provenance = Vec::new();
+  for fragment in fragments {
+    let entries = stitch_shadow_contract_into_contract(
+      target_root,
+      fragment.contract_idx,
+      fragment.shadow_contract,
+      max_target_id,
+      shadow_source_name,
+    )?;
+    for entry in &entries {
+      max_target_id = max_target_id.max(entry.new_node_id);
+    }
+    provenance.extend(entries);
+  }
+  Ok(provenance)
+}
+
+/// `nodeType`s that only make sense as a member of a `ContractDefinition` and can never legally
+/// sit directly in a `SourceUnit`'s top-level `nodes` array - a shadow fragment containing one of
+/// these was written assuming it would be stitched into a contract, so
+/// [`stitch_shadow_nodes_into_source_unit`] rejects it outright instead of producing an AST solc
+/// would refuse to compile anyway. Notably, `event` is *not* contract-only - free events have been
+/// legal at the file level since solc 0.8.22.
+const CONTRACT_ONLY_NODE_TYPES: &[&str] = &["ModifierDefinition"];
+
+/// Stitch a shadow fragment's own top-level nodes directly into a target `SourceUnit`.
+///
+/// Unlike [`stitch_shadow_nodes_into_contract`], there is no contract to locate: `shadow_ast` is
+/// expected to be a `SourceUnit` parsed from [`super::parser::wrap_shadow_source_file_level`],
+/// whose top-level nodes (free functions, `struct`/`enum`/`error`/`using`/`import` declarations,
+/// even whole contracts) are renumbered against `max_target_id` and appended to `target_root`'s
+/// `nodes` array as-is. The shadow fragment's own `PragmaDirective` is dropped since the target
+/// file already has one.
+pub fn stitch_shadow_nodes_into_source_unit(
+  target_root: &mut Value,
+  shadow_ast: &Value,
+  max_target_id: i64,
+) -> Result<(), ShadowError> {
+  let shadow_nodes = shadow_ast
+    .get("nodes")
+    .and_then(|v| v.as_array())
+    .ok_or_else(|| ShadowError::InvalidContractStructure("Shadow AST missing nodes".to_string()))?;
+
+  let mut fragment_nodes: Vec<Value> = shadow_nodes
+    .iter()
+    .filter(|node| {
+      node.get("nodeType").and_then(Value::as_str) != Some("PragmaDirective")
+    })
+    .cloned()
+    .collect();
+
+  if fragment_nodes.is_empty() {
+    return Err(ShadowError::NoNodesFound);
+  }
+
+  for node in &fragment_nodes {
+    let node_type = node.get("nodeType").and_then(Value::as_str).unwrap_or("");
+    if CONTRACT_ONLY_NODE_TYPES.contains(&node_type) {
+      return Err(ShadowError::InvalidContractStructure(format!(
+        "'{}' cannot be stitched at the file level; it is only valid inside a contract",
+        node_type
+      )));
+    }
+  }
+
+  let mut next_id = max_target_id;
+  for node in &mut fragment_nodes {
+    utils::renumber_ids(node, next_id);
+    next_id = next_id.max(utils::find_max_id(node));
+  }
+
+  let target_nodes = target_root
+    .get_mut("nodes")
+    .and_then(|v| v.as_array_mut())
+    .ok_or_else(|| ShadowError::InvalidContractStructure("Target AST missing nodes".to_string()))?;
+
+  target_nodes.extend(fragment_nodes);
+
   Ok(())
 }
 
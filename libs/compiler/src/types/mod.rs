@@ -1,7 +1,10 @@
 mod output;
 mod paths;
+mod progress;
 
 pub use output::{
-  CompileOutput, CompilerError, ContractArtifact, ContractBytecode, SourceLocation,
+  CompileOutput, CompilerError, ContractArtifact, ContractBytecode, JumpType,
+  ModelCheckerDiagnostic, SourceLocation, SourceMapEntry, StandaloneSourceArtifact,
 };
 pub use paths::ProjectPaths;
+pub use progress::{CompileProgressEvent, CompileProgressKind};
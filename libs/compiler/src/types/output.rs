@@ -16,12 +16,33 @@ pub struct SourceLocation {
   pub end: i32,
 }
 
+#[napi(string_enum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JumpType {
+  In,
+  Out,
+  Regular,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct SourceMapEntry {
+  pub start: u32,
+  pub length: u32,
+  /// Index into `CompileOutput.sourceList`, or -1 if solc didn't attribute a source file.
+  pub file_index: i32,
+  pub jump: JumpType,
+  pub modifier_depth: u32,
+}
+
 #[napi(object)]
 #[derive(Debug, Clone)]
 pub struct ContractBytecode {
   pub hex: Option<String>,
   #[napi(ts_type = "Uint8Array | undefined")]
   pub bytes: Option<Vec<u8>>,
+  #[napi(ts_type = "ReadonlyArray<SourceMapEntry> | undefined")]
+  pub source_map: Option<Vec<SourceMapEntry>>,
 }
 
 #[napi(object)]
@@ -33,6 +54,52 @@ pub struct ContractArtifact {
   pub abi_json: Option<String>,
   pub bytecode: Option<ContractBytecode>,
   pub deployed_bytecode: Option<ContractBytecode>,
+  /// Present when `ExtraOutputKind::StorageLayout` was requested.
+  #[napi(ts_type = "unknown | undefined")]
+  pub storage_layout: Option<Value>,
+  /// Present when `ExtraOutputKind::GasEstimates` was requested.
+  #[napi(ts_type = "unknown | undefined")]
+  pub gas_estimates: Option<Value>,
+  /// Present when `ExtraOutputKind::MethodIdentifiers` was requested.
+  #[napi(ts_type = "unknown | undefined")]
+  pub method_identifiers: Option<Value>,
+  /// Present when `ExtraOutputKind::Metadata` was requested.
+  #[napi(ts_type = "unknown | undefined")]
+  pub metadata: Option<Value>,
+  /// Present when `ExtraOutputKind::DevDoc` was requested.
+  #[napi(ts_type = "unknown | undefined")]
+  pub devdoc: Option<Value>,
+  /// Present when `ExtraOutputKind::UserDoc` was requested.
+  #[napi(ts_type = "unknown | undefined")]
+  pub userdoc: Option<Value>,
+  /// Present when `ExtraOutputKind::Ir` was requested.
+  pub ir: Option<String>,
+  /// Present when `ExtraOutputKind::IrOptimized` was requested.
+  pub ir_optimized: Option<String>,
+}
+
+/// One solc SMTChecker/model-checker finding (an unproved property or discovered counterexample),
+/// recognized by its `CHC:`/`BMC:` message prefix. Kept out of `CompileOutput.errors` so a failed
+/// proof never gets mistaken for an ordinary compile error/warning.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct ModelCheckerDiagnostic {
+  pub message: String,
+  pub severity: String,
+  pub source_location: Option<SourceLocation>,
+}
+
+/// A compiled source path that produced no `ContractDefinition` - a pragma-only file, one holding
+/// only free functions, library-free constants, or bare `error`/`struct` declarations. These never
+/// show up in `CompileOutput.artifacts`, so callers that need to resolve an AST for every source
+/// (not just ones with contracts) read it from here instead.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct StandaloneSourceArtifact {
+  pub source_path: String,
+  pub source_id: u32,
+  #[napi(ts_type = "unknown")]
+  pub ast: Value,
 }
 
 #[napi(object)]
@@ -40,5 +107,20 @@ pub struct ContractArtifact {
 pub struct CompileOutput {
   pub artifacts: Vec<ContractArtifact>,
   pub errors: Vec<CompilerError>,
+  /// SMTChecker/model-checker findings, separated out of `errors` by their `CHC:`/`BMC:` prefix.
+  pub model_checker_diagnostics: Vec<ModelCheckerDiagnostic>,
   pub has_compiler_errors: bool,
+  /// Resolves a `SourceMapEntry.fileIndex` back to the path solc compiled it from.
+  pub source_list: Vec<String>,
+  /// Every compiled source path appears exactly once across `artifacts` and this list: a path
+  /// shows up here instead of `artifacts` when it contains no `ContractDefinition`.
+  pub standalone_sources: Vec<StandaloneSourceArtifact>,
+  /// `true` when nothing had to be recompiled: the project's file cache (see
+  /// `SolidityProject::from_hardhat_root`/`from_dapptools_root`'s `cached` flag) determined every
+  /// source's content hash, imports, and solc settings were unchanged since the prior compile.
+  pub cached: bool,
+  /// Path to the Hardhat/Foundry-style build-info file this compile wrote, when `emitBuildInfo`
+  /// was set. `None` when the option was left off, or when no build-info directory was available
+  /// to write to (only project-bound compilers default one; inline compiles need `buildInfoDir`).
+  pub build_info_path: Option<String>,
 }
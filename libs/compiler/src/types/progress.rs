@@ -0,0 +1,34 @@
+/// Distinguishes the kinds of event `CompilerOptions.onProgress` can receive during a compile.
+/// Which of `CompileProgressEvent`'s optional payload fields are set depends on this.
+#[napi(string_enum)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CompileProgressKind {
+  SolcInstallStarted,
+  SolcInstallFinished,
+  VersionBucketStarted,
+  SourceResolved,
+  SolcInvocationFinished,
+}
+
+/// One progress notification delivered to `CompilerOptions.onProgress` during
+/// `compileSources`/`compileFiles`/`compileProject`, so a caller can render live progress instead
+/// of blocking opaquely until the whole `CompileOutput` returns. Shaped as one flat struct with
+/// per-kind optional payload fields, the same way `instrument::watch::WatchResult` carries more
+/// than one outcome through a single callback type.
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct CompileProgressEvent {
+  pub kind: CompileProgressKind,
+  /// Solc version involved, set on `SolcInstallStarted`/`SolcInstallFinished`/
+  /// `VersionBucketStarted`/`SolcInvocationFinished`.
+  pub solc_version: Option<String>,
+  /// File path just resolved, set on `SourceResolved`.
+  pub source_path: Option<String>,
+  /// Total files in the batch being resolved, set alongside `SourceResolved`.
+  pub total: Option<u32>,
+  /// Running count of files resolved so far (including `sourcePath`), set alongside
+  /// `SourceResolved`.
+  pub completed: Option<u32>,
+  /// Combined error and warning count solc reported, set on `SolcInvocationFinished`.
+  pub diagnostic_count: Option<u32>,
+}